@@ -122,6 +122,44 @@ fn bench_full_pipeline(c: &mut Criterion) {
         });
     });
 
+    // Only the most recent frames are scanned, however deep the stack
+    group.bench_function("parse_and_path/deep", |b| {
+        b.iter(|| {
+            let stack = CallStack::from(black_box(Some(DEEP_STACK)));
+            stack.path()
+        });
+    });
+
+    group.bench_function("parse_and_file_name/deep", |b| {
+        b.iter(|| {
+            let stack = CallStack::from(black_box(Some(DEEP_STACK)));
+            stack.file_name()
+        });
+    });
+
+    group.bench_function("parse_and_location/deep", |b| {
+        b.iter(|| {
+            let stack = CallStack::from(black_box(Some(DEEP_STACK)));
+            stack.location()
+        });
+    });
+
+    // What the log command does: write the location into a reused buffer
+    let mut buffer = String::new();
+    group.bench_function("write_location/simple", |b| {
+        b.iter(|| {
+            buffer.clear();
+            CallStack::from(black_box(Some(SIMPLE_STACK))).write_location(&mut buffer);
+        });
+    });
+
+    group.bench_function("write_location/deep", |b| {
+        b.iter(|| {
+            buffer.clear();
+            CallStack::from(black_box(Some(DEEP_STACK))).write_location(&mut buffer);
+        });
+    });
+
     group.finish();
 }
 
@@ -0,0 +1,120 @@
+#![allow(clippy::unwrap_used)]
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use serde_json::json;
+use tauri::Manager;
+use tauri::test::MockRuntime;
+use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+use tauri_plugin_tracing::{Builder, LogLevel, LogMessage, log};
+use tracing::Level;
+
+// Realistic call stack from a Tauri app
+const SIMPLE_STACK: &str = r#"Error
+    at info (http://localhost:1420/src/utils/logger.ts:42:5)
+    at handleClick (http://localhost:1420/src/components/Button.tsx:15:3)
+    at onClick (http://localhost:1420/src/App.tsx:28:9)"#;
+
+// Call stack with node_modules (should be filtered)
+const STACK_WITH_NODE_MODULES: &str = r#"Error
+    at Object.invoke (http://localhost:1420/node_modules/@tauri-apps/api/dist/core.js:123:45)
+    at async fetchData (http://localhost:1420/node_modules/some-lib/index.js:10:5)
+    at handleSubmit (http://localhost:1420/src/components/Form.tsx:55:12)
+    at processForm (http://localhost:1420/src/utils/forms.ts:20:3)"#;
+
+/// A subscriber that formats every event and discards the output.
+fn sink_subscriber(max_level: Level) -> impl tracing::Subscriber + Send + Sync {
+    tracing_subscriber::fmt()
+        .with_max_level(max_level)
+        .with_writer(std::io::sink)
+        .finish()
+}
+
+fn bench_log_command(c: &mut Criterion) {
+    let mut group = c.benchmark_group("log_command");
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+    let message = LogMessage::from(vec![json!("button clicked"), json!({ "id": 42 })]);
+
+    // Calls the command directly, without IPC
+    let call = |level: LogLevel, message: LogMessage, call_stack: &str| {
+        log::<MockRuntime>(
+            window.clone(),
+            app.state(),
+            app.state(),
+            app.state(),
+            app.state(),
+            level,
+            message,
+            Some(black_box(call_stack)),
+            None,
+            None,
+            None,
+            None,
+        )
+    };
+
+    // Records below the subscriber's level should cost almost nothing
+    tracing::subscriber::with_default(sink_subscriber(Level::WARN), || {
+        group.bench_function("filtered_out", |b| {
+            b.iter_batched(
+                || message.clone(),
+                |message| call(LogLevel::Debug, message, SIMPLE_STACK),
+                BatchSize::SmallInput,
+            );
+        });
+    });
+
+    tracing::subscriber::with_default(sink_subscriber(Level::TRACE), || {
+        group.bench_function("enabled/file_name", |b| {
+            b.iter_batched(
+                || message.clone(),
+                |message| call(LogLevel::Info, message, SIMPLE_STACK),
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.bench_function("enabled/location", |b| {
+            b.iter_batched(
+                || message.clone(),
+                |message| call(LogLevel::Error, message, STACK_WITH_NODE_MODULES),
+                BatchSize::SmallInput,
+            );
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_log_ipc(c: &mut Criterion) {
+    let mut group = c.benchmark_group("log_command_ipc");
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+    let args = |level: u8| {
+        json!({
+            "level": level,
+            "message": ["button clicked", { "id": 42 }],
+            "callStack": SIMPLE_STACK,
+        })
+    };
+
+    // The full path of a frontend log call, including deserialization
+    tracing::subscriber::with_default(sink_subscriber(Level::WARN), || {
+        let debug = args(2);
+        group.bench_function("filtered_out", |b| {
+            b.iter(|| invoke_command(&window, "plugin:tracing|log", black_box(debug.clone())));
+        });
+    });
+
+    tracing::subscriber::with_default(sink_subscriber(Level::TRACE), || {
+        let info = args(3);
+        group.bench_function("enabled", |b| {
+            b.iter(|| invoke_command(&window, "plugin:tracing|log", black_box(info.clone())));
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_log_command, bench_log_ipc);
+criterion_main!(benches);
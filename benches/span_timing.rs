@@ -0,0 +1,62 @@
+#![allow(clippy::unwrap_used)]
+use std::time::Instant;
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use tauri_plugin_tracing::SpanTimingLayer;
+use tracing_subscriber::Registry;
+use tracing_subscriber::layer::SubscriberExt;
+
+fn bench_span_timing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("span_timing");
+
+    let (layer, capture) = SpanTimingLayer::new();
+    let subscriber = Registry::default().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        // The cost of the layer when not capturing
+        group.bench_function("idle", |b| {
+            b.iter(|| {
+                let span = tracing::info_span!("process_item", id = black_box(1));
+                span.in_scope(|| black_box(()));
+            });
+        });
+
+        // Span names are interned per callsite, so recording the enter, exit
+        // and close events of a span should not format its name
+        group.bench_function("capturing", |b| {
+            b.iter_custom(|iters| {
+                capture.start_capture();
+                let start = Instant::now();
+                for _ in 0..iters {
+                    let span = tracing::info_span!("process_item", id = black_box(1));
+                    span.in_scope(|| black_box(()));
+                }
+                let elapsed = start.elapsed();
+                black_box(capture.stop_capture());
+                elapsed
+            });
+        });
+
+        group.bench_function("capturing/nested", |b| {
+            b.iter_custom(|iters| {
+                capture.start_capture();
+                let start = Instant::now();
+                for _ in 0..iters {
+                    let outer = tracing::info_span!("load_document");
+                    outer.in_scope(|| {
+                        let inner = tracing::debug_span!("parse");
+                        inner.in_scope(|| black_box(()));
+                    });
+                }
+                let elapsed = start.elapsed();
+                black_box(capture.stop_capture());
+                elapsed
+            });
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_span_timing);
+criterion_main!(benches);
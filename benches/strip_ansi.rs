@@ -1,6 +1,8 @@
 #![allow(clippy::unwrap_used)]
 use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
 use std::io::Write;
+use std::sync::Mutex;
+use std::time::Instant;
 use tauri_plugin_tracing::StripAnsiWriter;
 use tracing_subscriber::fmt::MakeWriter;
 
@@ -29,6 +31,10 @@ fn with_ansi_complex() -> &'static [u8] {
     b"\x1b[2m2024-01-15T10:30:00.000Z\x1b[0m \x1b[1;31m ERROR\x1b[0m \x1b[2mmy_app::handler\x1b[0m\x1b[2m:\x1b[0m \x1b[1;33mConnection failed\x1b[0m error=\"\x1b[31mTimeout after 30s\x1b[0m\" retries=\x1b[36m3\x1b[0m"
 }
 
+fn with_editor_link() -> &'static [u8] {
+    b"\x1b[2m2024-01-15T10:30:00.000Z\x1b[0m \x1b[32m INFO\x1b[0m \x1b[2m\x1b]8;;file:///app/src/main.rs\x1b\\src/main.rs\x1b[0m\x1b[2m:\x1b[0m\x1b[2m42\x1b]8;;\x1b\\:\x1b[0m Processing request"
+}
+
 fn bench_strip_ansi_writer(c: &mut Criterion) {
     let mut group = c.benchmark_group("strip_ansi_writer");
 
@@ -40,7 +46,7 @@ fn bench_strip_ansi_writer(c: &mut Criterion) {
     ] {
         group.throughput(Throughput::Bytes(input.len() as u64));
         group.bench_with_input(BenchmarkId::new("fast_path", name), input, |b, input| {
-            let writer = StripAnsiWriter::new(Vec::with_capacity(512));
+            let writer = StripAnsiWriter::new(Mutex::new(Vec::with_capacity(512)));
             b.iter(|| {
                 let mut guard = writer.make_writer();
                 guard.write_all(black_box(input)).unwrap();
@@ -53,10 +59,11 @@ fn bench_strip_ansi_writer(c: &mut Criterion) {
         ("with_ansi_simple", with_ansi_simple()),
         ("with_ansi_log_line", with_ansi_log_line()),
         ("with_ansi_complex", with_ansi_complex()),
+        ("with_editor_link", with_editor_link()),
     ] {
         group.throughput(Throughput::Bytes(input.len() as u64));
         group.bench_with_input(BenchmarkId::new("slow_path", name), input, |b, input| {
-            let writer = StripAnsiWriter::new(Vec::with_capacity(512));
+            let writer = StripAnsiWriter::new(Mutex::new(Vec::with_capacity(512)));
             b.iter(|| {
                 let mut guard = writer.make_writer();
                 guard.write_all(black_box(input)).unwrap();
@@ -83,7 +90,7 @@ fn bench_throughput(c: &mut Criterion) {
     group.throughput(Throughput::Bytes(total_bytes as u64));
 
     group.bench_function("mixed_log_batch", |b| {
-        let writer = StripAnsiWriter::new(Vec::with_capacity(1024));
+        let writer = StripAnsiWriter::new(Mutex::new(Vec::with_capacity(1024)));
         b.iter(|| {
             let mut guard = writer.make_writer();
             for line in &log_lines {
@@ -95,5 +102,50 @@ fn bench_throughput(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_strip_ansi_writer, bench_throughput);
+fn bench_threads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strip_ansi_threads");
+    const THREADS: u64 = 4;
+
+    // Each thread writes `iters / THREADS` lines through `writer`
+    fn run<M>(writer: &StripAnsiWriter<M>, iters: u64) -> std::time::Duration
+    where
+        M: for<'a> tracing_subscriber::fmt::MakeWriter<'a> + Sync,
+    {
+        let start = Instant::now();
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    for _ in 0..iters / THREADS {
+                        let mut guard = writer.make_writer();
+                        guard.write_all(black_box(with_ansi_log_line())).unwrap();
+                    }
+                });
+            }
+        });
+        start.elapsed()
+    }
+
+    group.throughput(Throughput::Bytes(with_ansi_log_line().len() as u64));
+
+    // A writer made per call, like `NonBlocking`, needs no lock
+    group.bench_function("per_call_writer", |b| {
+        let writer = StripAnsiWriter::new(std::io::sink);
+        b.iter_custom(|iters| run(&writer, iters));
+    });
+
+    // A shared writer behind a Mutex serializes the threads
+    group.bench_function("mutex_writer", |b| {
+        let writer = StripAnsiWriter::new(Mutex::new(std::io::sink()));
+        b.iter_custom(|iters| run(&writer, iters));
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_strip_ansi_writer,
+    bench_throughput,
+    bench_threads
+);
 criterion_main!(benches);
@@ -1,4 +1,48 @@
-const COMMANDS: &[&str] = &["log"];
+const COMMANDS: &[&str] = &[
+    "log",
+    "log_batch",
+    "report_frame_times",
+    "sync_clock",
+    "operation_start",
+    "operation_end",
+    "operation_get",
+    "operation_list",
+    "span_start",
+    "span_end",
+    "set_log_level",
+    "set_target_level",
+    "reset_log_filter",
+    "get_effective_filter",
+    "get_log_stats",
+    "clear_logs",
+    "mark",
+    "get_logs_around_mark",
+    "set_context",
+    "remove_context",
+    "clear_context",
+    "set_current_route",
+    "report_invariant_violation",
+    "get_invariant_violations",
+    "progress",
+    "gauge",
+    "counter",
+    "begin_test",
+    "end_test",
+    "get_schema",
+    "analyze_noise",
+    "apply_noise_filters",
+    "create_diagnostic_report",
+    "start_span_aware_profile",
+    "stop_span_aware_profile",
+    "list_profiling_sessions",
+    "get_profiling_session",
+    "get_span_stats",
+    "get_filter_stats",
+    "tail_logs",
+    "stop_tail_logs",
+    "heartbeat_pong",
+    "webview_ready",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS).build();
@@ -1,7 +1,12 @@
 //! Call stack parsing and filtering utilities.
 //!
 //! This module provides types for parsing JavaScript call stacks and extracting
-//! meaningful location information for log messages.
+//! meaningful location information for log messages. Parsing requires the
+//! `callstack` feature; without it, webview records are located by their
+//! target only.
+
+#[cfg(feature = "callstack")]
+use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
@@ -13,7 +18,7 @@ use serde::{Deserialize, Serialize};
 /// # Examples
 ///
 /// ```
-/// use tauri_plugin_tracing::CallStackLine;
+/// use tauri_plugin_tracing_core::CallStackLine;
 ///
 /// // Create from a string
 /// let line = CallStackLine::from("at foo (src/app.ts:10:5)");
@@ -81,7 +86,7 @@ impl CallStackLine {
     /// # Examples
     ///
     /// ```
-    /// use tauri_plugin_tracing::CallStackLine;
+    /// use tauri_plugin_tracing_core::CallStackLine;
     ///
     /// let line = CallStackLine::from("at foo (src/old.ts:10:5)");
     /// let replaced = line.replace("old", "new");
@@ -90,28 +95,22 @@ impl CallStackLine {
     pub fn replace(&self, from: &str, to: &str) -> Self {
         CallStackLine(self.0.replace(from, to))
     }
-
-    /// Removes the `localhost:PORT/` prefix from URLs for cleaner output.
-    fn strip_localhost(&self) -> String {
-        let mut result = self.to_string();
-        if let Some(start) = result.find("localhost:")
-            && let Some(slash_pos) = result[start..].find('/')
-        {
-            result.replace_range(0..start + slash_pos + 1, "");
-        }
-        result
-    }
 }
 
-/// A parsed JavaScript call stack.
+/// A JavaScript call stack.
 ///
-/// This type parses a newline-separated call stack string and provides methods
+/// This type wraps a newline-separated call stack string and provides methods
 /// to extract different levels of location detail for log messages.
 ///
+/// Frames are found lazily, borrowing from the string. [`path()`](Self::path)
+/// and [`file_name()`](Self::file_name) scan from the end and stop at the most
+/// recent frame they need, so only [`location()`](Self::location) visits every
+/// frame.
+///
 /// # Examples
 ///
 /// ```
-/// use tauri_plugin_tracing::CallStack;
+/// use tauri_plugin_tracing_core::CallStack;
 ///
 /// // Parse a simple call stack
 /// let stack = CallStack::new(Some("Error\n    at foo (src/app.ts:10:5)\n    at bar (src/lib.ts:20:3)"));
@@ -124,59 +123,89 @@ impl CallStackLine {
 /// ```
 ///
 /// ```
-/// use tauri_plugin_tracing::CallStack;
+/// use tauri_plugin_tracing_core::CallStack;
 ///
 /// // node_modules paths are filtered out
 /// let stack = CallStack::new(Some("Error\n    at node_modules/lib/index.js:1:1\n    at src/app.ts:10:5"));
 /// let location = stack.location();
 /// assert!(!location.contains("node_modules"));
 /// ```
+#[cfg(feature = "callstack")]
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
-pub struct CallStack(pub Vec<CallStackLine>);
+pub struct CallStack<'a>(Cow<'a, str>);
 
-impl From<Option<&str>> for CallStack {
-    fn from(value: Option<&str>) -> Self {
-        let lines = value
-            .unwrap_or("")
-            .split("\n")
-            .map(|line| CallStackLine(line.to_string()))
-            .collect();
-        Self(lines)
+#[cfg(feature = "callstack")]
+impl<'a> From<Option<&'a str>> for CallStack<'a> {
+    fn from(value: Option<&'a str>) -> Self {
+        Self(Cow::Borrowed(value.unwrap_or("")))
     }
 }
 
-impl From<Option<String>> for CallStack {
+#[cfg(feature = "callstack")]
+impl From<Option<String>> for CallStack<'static> {
     fn from(value: Option<String>) -> Self {
-        let lines = value
-            .unwrap_or("".to_string())
-            .split("\n")
-            .map(|line| CallStackLine(line.to_string()))
-            .collect();
-        Self(lines)
+        Self(Cow::Owned(value.unwrap_or_default()))
     }
 }
 
-impl CallStack {
+#[cfg(feature = "callstack")]
+impl<'a> CallStack<'a> {
     /// Creates a new `CallStack` from an optional string.
-    pub fn new(value: Option<&str>) -> Self {
+    pub fn new(value: Option<&'a str>) -> Self {
         CallStack::from(value)
     }
 
+    /// Returns the frames that pass the filter, oldest first.
+    ///
+    /// Frames in `node_modules` and native code are skipped, and
+    /// `localhost:PORT/` prefixes are stripped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tauri_plugin_tracing_core::CallStack;
+    ///
+    /// let stack = CallStack::new(Some("Error\n    at node_modules/lib/index.js:1:1\n    at src/app.ts:10:5"));
+    /// assert_eq!(stack.frames().next_back(), Some("    at src/app.ts:10:5"));
+    /// ```
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.0.split('\n').filter_map(fmap_location)
+    }
+
     /// Returns the full filtered location as a `#`-separated string.
     ///
     /// This includes all stack frames that pass the filter (excluding
     /// `node_modules` and native code), joined with `#`.
     /// Used for `trace` and `error` log levels.
     pub fn location(&self) -> CallStackLine {
-        CallStackLine(
-            self.0
-                .iter()
-                .filter_map(fmap_location)
-                .collect::<Vec<String>>()
-                .clone()
-                .join("#"),
-        )
+        let mut location = String::new();
+        self.write_location(&mut location);
+        CallStackLine(location)
+    }
+
+    /// Appends the [`location()`](Self::location) to `out`.
+    ///
+    /// Reusing `out` across calls avoids allocating at all. The `log` command
+    /// locates records this way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tauri_plugin_tracing_core::CallStack;
+    ///
+    /// let stack = CallStack::new(Some("Error\n    at foo (http://localhost:1420/src/app.ts:10:5)"));
+    /// let mut out = String::new();
+    /// stack.write_location(&mut out);
+    /// assert_eq!(out, stack.location().as_str());
+    /// ```
+    pub fn write_location(&self, out: &mut String) {
+        for (i, frame) in self.frames().enumerate() {
+            if i > 0 {
+                out.push('#');
+            }
+            out.push_str(frame);
+        }
     }
 
     /// Returns the path of the last (most recent) stack frame.
@@ -184,10 +213,16 @@ impl CallStack {
     /// This extracts just the last location from the full call stack.
     /// Used for `debug` and `warn` log levels.
     pub fn path(&self) -> CallStackLine {
-        match self.location().split("#").last() {
-            Some(file_name) => CallStackLine(file_name.to_string()),
-            None => CallStackLine("unknown".to_string()),
-        }
+        CallStackLine::from(self.path_str())
+    }
+
+    /// Returns the [`path()`](Self::path) borrowed from the stack.
+    pub fn path_str(&self) -> &str {
+        // The part after the last `#` of the location
+        self.frames()
+            .next_back()
+            .and_then(|frame| frame.rsplit('#').next())
+            .unwrap_or("")
     }
 
     /// Returns just the filename (without path) of the most recent stack frame.
@@ -195,26 +230,61 @@ impl CallStack {
     /// This is the most concise location format.
     /// Used for `info` log level.
     pub fn file_name(&self) -> CallStackLine {
-        match self.location().split("/").last() {
-            Some(file_name) => CallStackLine(file_name.to_string()),
-            None => CallStackLine("unknown".to_string()),
+        let mut file_name = String::new();
+        self.write_file_name(&mut file_name);
+        CallStackLine(file_name)
+    }
+
+    /// Appends the [`file_name()`](Self::file_name) to `out`.
+    pub fn write_file_name(&self, out: &mut String) {
+        // The part after the last `/` of the location, found by scanning back
+        // to the most recent frame that has one
+        let stack: &str = &self.0;
+        let mut end = stack.len();
+        for line in stack.rsplit('\n') {
+            if let Some(frame) = fmap_location(line)
+                && let Some(slash_pos) = frame.rfind('/')
+            {
+                out.push_str(&frame[slash_pos + 1..]);
+                for frame in stack[end..].split('\n').skip(1).filter_map(fmap_location) {
+                    out.push('#');
+                    out.push_str(frame);
+                }
+                return;
+            }
+            end = (end - line.len()).saturating_sub(1);
         }
+        self.write_location(out);
     }
 }
 
 /// Substrings that indicate a stack frame should be filtered out.
+#[cfg(feature = "callstack")]
 const FILTERED_LINES: [&str; 2] = ["node_modules", "forEach@[native code]"];
 
 /// Filters and transforms a call stack line.
 ///
 /// Returns `None` if the line should be filtered out (e.g., `node_modules`),
 /// otherwise returns the line with localhost URLs stripped.
-fn fmap_location(line: &CallStackLine) -> Option<String> {
+#[cfg(feature = "callstack")]
+fn fmap_location(line: &str) -> Option<&str> {
     if FILTERED_LINES
         .iter()
         .any(|filtered| line.contains(filtered))
     {
         return None;
     }
-    Some(line.strip_localhost())
+    Some(strip_localhost(line))
+}
+
+/// Removes the `localhost:PORT/` prefix from URLs for cleaner output.
+#[cfg(feature = "callstack")]
+fn strip_localhost(line: &str) -> &str {
+    match line.find("localhost:") {
+        Some(start) => match line[start..].find('/') {
+            Some(slash_pos) => &line[start + slash_pos + 1..],
+            None => line,
+        },
+        None => line,
+    }
 }
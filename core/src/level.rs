@@ -0,0 +1,100 @@
+//! The levels of records.
+
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// An enum representing the available verbosity levels of the logger.
+///
+/// It is very similar to `log::Level`, but serializes to unsigned ints instead of strings.
+///
+/// # Examples
+///
+/// ```
+/// use tauri_plugin_tracing_core::LogLevel;
+///
+/// // Default is Info
+/// assert!(matches!(LogLevel::default(), LogLevel::Info));
+///
+/// // Convert to tracing::Level
+/// let level: tracing::Level = LogLevel::Debug.into();
+/// assert_eq!(level, tracing::Level::DEBUG);
+///
+/// // Convert from tracing::Level
+/// let log_level: LogLevel = tracing::Level::WARN.into();
+/// assert!(matches!(log_level, LogLevel::Warn));
+/// ```
+#[derive(Debug, Clone, Deserialize_repr, Serialize_repr, Default)]
+#[repr(u16)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum LogLevel {
+    /// The "trace" level.
+    ///
+    /// Designates very low priority, often extremely verbose, information.
+    Trace = 1,
+    /// The "debug" level.
+    ///
+    /// Designates lower priority information.
+    Debug,
+    /// The "info" level.
+    ///
+    /// Designates useful information.
+    #[default]
+    Info,
+    /// The "warn" level.
+    ///
+    /// Designates hazardous situations.
+    Warn,
+    /// The "error" level.
+    ///
+    /// Designates very serious errors.
+    Error,
+}
+
+// Levels are serialized as numbers, which the derive does not support
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for LogLevel {
+    fn schema_name() -> String {
+        "LogLevel".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, Metadata, SchemaObject};
+
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(
+                    "The severity level: 1 = trace, 2 = debug, 3 = info, 4 = warn, 5 = error."
+                        .to_string(),
+                ),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::Integer.into()),
+            enum_values: Some((1..=5).map(serde_json::Value::from).collect()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl From<LogLevel> for tracing::Level {
+    fn from(log_level: LogLevel) -> Self {
+        match log_level {
+            LogLevel::Trace => tracing::Level::TRACE,
+            LogLevel::Debug => tracing::Level::DEBUG,
+            LogLevel::Info => tracing::Level::INFO,
+            LogLevel::Warn => tracing::Level::WARN,
+            LogLevel::Error => tracing::Level::ERROR,
+        }
+    }
+}
+
+impl From<tracing::Level> for LogLevel {
+    fn from(log_level: tracing::Level) -> Self {
+        match log_level {
+            tracing::Level::TRACE => LogLevel::Trace,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::WARN => LogLevel::Warn,
+            tracing::Level::ERROR => LogLevel::Error,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Record types of [`tauri-plugin-tracing`](https://docs.rs/tauri-plugin-tracing).
+//!
+//! The plugin sends [`RecordPayload`]s to webviews and receives levels and
+//! [`LogMessage`]s from them. Tools that read or write these records outside
+//! the app, such as log viewers, test harnesses, or forwarders running as a
+//! sidecar, can use the same types from this crate without depending on
+//! Tauri. The plugin re-exports every type, so values convert without
+//! mapping.
+//!
+//! ## Feature Flags
+//!
+//! - **`callstack`**: Parses JavaScript call stacks to locate webview
+//!   records, see [`CallStack`]
+//! - **`schema`**: Derives `schemars::JsonSchema` for the records
+//! - **`specta`**: Derives `specta::Type` for the records
+
+mod callstack;
+mod level;
+mod record;
+
+#[cfg(feature = "callstack")]
+pub use callstack::CallStack;
+pub use callstack::CallStackLine;
+pub use level::LogLevel;
+pub use record::{Breadcrumb, LogMessage, RecordFields, RecordPayload};
@@ -0,0 +1,243 @@
+//! The records the plugin sends to webviews.
+
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::LogLevel;
+
+/// A log message consisting of one or more values.
+///
+/// Mirrors the arguments of a JavaScript `console.log` call. Strings are kept
+/// as-is, while objects, arrays, numbers and other values keep their JSON
+/// structure instead of being stringified on the frontend.
+///
+/// When displayed, the parts are joined with ", " and non-string values are
+/// rendered as compact JSON. The alternate format (`{:#}`) renders them as
+/// pretty-printed JSON instead.
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing_core::LogMessage;
+///
+/// let message = LogMessage::from(vec![
+///     serde_json::json!("state"),
+///     serde_json::json!({ "count": 2 }),
+/// ]);
+/// assert_eq!(message.to_string(), r#"state, {"count":2}"#);
+/// assert_eq!(message.args().as_deref(), Some(r#"[{"count":2}]"#));
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogMessage(Vec<serde_json::Value>);
+
+impl LogMessage {
+    /// Returns the non-string parts as a compact JSON array.
+    ///
+    /// Returns `None` if every part is a string, so plain text messages do not
+    /// carry a redundant `args` field.
+    pub fn args(&self) -> Option<String> {
+        let args: Vec<&serde_json::Value> = self.iter().filter(|v| !v.is_string()).collect();
+        if args.is_empty() {
+            return None;
+        }
+        serde_json::to_string(&args).ok()
+    }
+}
+
+impl From<Vec<serde_json::Value>> for LogMessage {
+    fn from(parts: Vec<serde_json::Value>) -> Self {
+        Self(parts)
+    }
+}
+
+impl std::ops::Deref for LogMessage {
+    type Target = Vec<serde_json::Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for LogMessage {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl std::fmt::Display for LogMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, part) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match part {
+                serde_json::Value::String(s) => f.write_str(s)?,
+                value if f.alternate() => write!(f, "{value:#}")?,
+                value => write!(f, "{value}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Payload for a log record, used when emitting events to the webview.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RecordPayload {
+    /// The formatted log message.
+    ///
+    /// This is the event's `message` field, or the value of its first field
+    /// if it has none.
+    pub message: String,
+    /// The severity level of the log.
+    pub level: LogLevel,
+    /// The event's fields other than `message`, rendered as strings, in the
+    /// order they were recorded.
+    #[serde(default, skip_serializing_if = "RecordFields::is_empty")]
+    #[cfg_attr(feature = "specta", specta(type = std::collections::BTreeMap<String, String>))]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "std::collections::BTreeMap<String, String>")
+    )]
+    pub fields: RecordFields,
+    /// The events leading up to an `ERROR` event, oldest first, when
+    /// breadcrumbs are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breadcrumbs: Option<Vec<Breadcrumb>>,
+    /// The record's sequence number, shared by every output, when sequence
+    /// numbers are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    /// The number of records dropped on the way to the webview since the
+    /// previous record, if any were.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gap: Option<u64>,
+}
+
+/// The fields of a [`RecordPayload`], by name, in the order they were
+/// recorded.
+///
+/// Serializes as a JSON object whose keys keep that order, which JavaScript
+/// preserves when iterating over the object.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordFields(Vec<(String, String)>);
+
+impl RecordFields {
+    /// Returns the value of the field `name`, if the record has it.
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns whether the record has the field `name`.
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Sets the field `name`, keeping its position if it was already set.
+    pub fn insert(&mut self, name: String, value: String) {
+        match self.0.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, old)) => *old = value,
+            None => self.0.push((name, value)),
+        }
+    }
+
+    /// Returns the number of fields.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the record has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the fields' names and values in recorded order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl FromIterator<(String, String)> for RecordFields {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        let mut fields = Self::default();
+        for (name, value) in iter {
+            fields.insert(name, value);
+        }
+        fields
+    }
+}
+
+impl<const N: usize> From<[(String, String); N]> for RecordFields {
+    fn from(fields: [(String, String); N]) -> Self {
+        fields.into_iter().collect()
+    }
+}
+
+impl IntoIterator for RecordFields {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Serialize for RecordFields {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in &self.0 {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for RecordFields {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldsVisitor;
+
+        impl<'de> Visitor<'de> for FieldsVisitor {
+            type Value = RecordFields;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a map of field names to values")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut fields = RecordFields::default();
+                while let Some((name, value)) = map.next_entry()? {
+                    fields.insert(name, value);
+                }
+                Ok(fields)
+            }
+        }
+
+        deserializer.deserialize_map(FieldsVisitor)
+    }
+}
+
+/// An event recorded in a breadcrumb trail or a log history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Breadcrumb {
+    /// When the event was recorded, in milliseconds since the Unix epoch.
+    pub timestamp_ms: f64,
+    /// The event's level.
+    pub level: LogLevel,
+    /// The event's target.
+    pub target: String,
+    /// The event's `message` field, or an empty string if it has none.
+    pub message: String,
+}
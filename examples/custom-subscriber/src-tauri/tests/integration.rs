@@ -368,6 +368,10 @@ fn record_payload_serialization() {
     let payload = RecordPayload {
         message: "test message".to_string(),
         level: LogLevel::Info,
+        fields: Default::default(),
+        breadcrumbs: None,
+        seq: None,
+        gap: None,
     };
 
     // Verify payload can be serialized (required for emit)
@@ -391,6 +395,10 @@ fn record_payload_levels() {
         let payload = RecordPayload {
             message: "test".to_string(),
             level,
+            fields: Default::default(),
+            breadcrumbs: None,
+            seq: None,
+            gap: None,
         };
         let json = serde_json::to_string(&payload).unwrap();
         assert!(
@@ -734,6 +742,7 @@ fn span_correlation_report_display() {
             },
         ],
         events: vec![],
+        resources: None,
     };
 
     // Verify Display implementation
@@ -742,6 +751,32 @@ fn span_correlation_report_display() {
     assert!(output.contains("100"));
     assert!(output.contains("test::span1"));
     assert!(output.contains("50.0%"));
+    assert!(!output.contains("Resources:"));
+}
+
+#[test]
+fn span_correlation_report_display_with_resources() {
+    use tauri_plugin_tracing::{ProfileResult, ResourceSnapshot, SpanCorrelationReport};
+
+    let report = SpanCorrelationReport {
+        profile: ProfileResult {
+            sample_count: 10,
+            duration_ms: 100,
+            flamegraph_path: "/tmp/test.svg".into(),
+        },
+        duration_us: 100_000,
+        active_spans: vec![],
+        events: vec![],
+        resources: Some(ResourceSnapshot {
+            rss_bytes: Some(52_000_000),
+            cpu_percent: Some(12.5),
+            open_fds: Some(40),
+            thread_count: Some(8),
+        }),
+    };
+
+    let output = report.to_string();
+    assert!(output.contains("Resources: rss=52.0MB cpu=12.5% fds=40 threads=8"));
 }
 
 #[test]
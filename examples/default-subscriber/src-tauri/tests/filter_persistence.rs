@@ -0,0 +1,79 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+//! Tests for runtime filter changes and their persistence.
+//!
+//! The filter handle belongs to the global subscriber, so these tests run in
+//! their own binary, sharing one app.
+
+use tauri::Manager;
+use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+use tauri_plugin_tracing::{Builder, FILTER_FILE_NAME, FilterHandle, LevelFilter};
+
+#[test]
+fn filter_changes_are_persisted_until_reset() {
+    let app = mock_app(
+        Builder::new()
+            .with_max_level(LevelFilter::INFO)
+            .with_filter_persistence()
+            .with_default_subscriber(),
+    )
+    .unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let filter = app.state::<FilterHandle>();
+    let path = filter.store_path().cloned().expect("store path");
+    assert_eq!(path.file_name().unwrap(), FILTER_FILE_NAME);
+    assert!(!tracing::enabled!(target: "my_app::sync", tracing::Level::DEBUG));
+
+    invoke_command(
+        &window,
+        "plugin:tracing|set_target_level",
+        serde_json::json!({ "target": "my_app::sync", "level": 2 }),
+    )
+    .unwrap();
+    assert!(tracing::enabled!(target: "my_app::sync", tracing::Level::DEBUG));
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("my_app::sync"), "{saved}");
+
+    // The effective filter reflects both the configured and the changed levels
+    filter.set_target("my_app::db", LevelFilter::OFF).unwrap();
+    let effective = invoke_command(
+        &window,
+        "plugin:tracing|get_effective_filter",
+        serde_json::json!({}),
+    )
+    .unwrap();
+    assert_eq!(
+        effective,
+        serde_json::json!({
+            "level": 3,
+            "targets": { "my_app::db": null, "my_app::sync": 2 }
+        })
+    );
+
+    invoke_command(
+        &window,
+        "plugin:tracing|reset_log_filter",
+        serde_json::json!({}),
+    )
+    .unwrap();
+    assert!(!tracing::enabled!(target: "my_app::sync", tracing::Level::DEBUG));
+    assert!(!path.exists());
+    assert_eq!(
+        filter.effective().targets,
+        std::collections::BTreeMap::new()
+    );
+
+    // Level changes can be undone on their own
+    filter.set_level(LevelFilter::DEBUG).unwrap();
+    assert!(tracing::enabled!(tracing::Level::DEBUG));
+    filter.clear_level().unwrap();
+    assert!(!tracing::enabled!(tracing::Level::DEBUG));
+
+    // Hand edits to the file are picked up on reload
+    std::fs::write(&path, r#"{ "level": "trace" }"#).unwrap();
+    filter.reload_stored().unwrap();
+    assert!(tracing::enabled!(tracing::Level::TRACE));
+    std::fs::remove_file(&path).unwrap();
+    filter.reload_stored().unwrap();
+    assert!(!tracing::enabled!(tracing::Level::DEBUG));
+}
@@ -6,8 +6,8 @@
 
 use std::path::PathBuf;
 use tauri_plugin_tracing::{
-    Builder, CallStack, CallStackLine, LevelFilter, LogLevel, MaxFileSize, Rotation,
-    RotationStrategy, Target, TimezoneStrategy,
+    Builder, CallStack, CallStackLine, InstanceStrategy, LevelFilter, LogLevel, MaxFileSize,
+    Rotation, RotationStrategy, SyncPolicy, Target, TimezoneStrategy, VersionLayout,
 };
 
 #[test]
@@ -32,6 +32,32 @@ fn builder_with_target() {
         .build::<tauri::Wry>();
 }
 
+#[test]
+fn builder_with_target_glob() {
+    let builder = Builder::new()
+        .with_max_level(LevelFilter::DEBUG)
+        .with_target_glob("tao::*", LevelFilter::WARN)
+        .with_target_glob("*::hyper::*", LevelFilter::OFF);
+    assert_eq!(
+        builder.configured_target_globs(),
+        [
+            ("tao::*".to_string(), LevelFilter::WARN),
+            ("*::hyper::*".to_string(), LevelFilter::OFF),
+        ]
+    );
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn builder_with_span_level() {
+    let builder = Builder::new()
+        .with_max_level(LevelFilter::INFO)
+        .with_span_level(LevelFilter::TRACE);
+    assert_eq!(builder.configured_span_level(), Some(LevelFilter::TRACE));
+    assert_eq!(Builder::new().configured_span_level(), None);
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
 #[test]
 fn builder_with_colors() {
     let _plugin = Builder::new()
@@ -167,6 +193,16 @@ fn call_stack_empty() {
     assert_eq!(stack.location().as_str(), "");
 }
 
+#[test]
+fn call_stack_scans_back_to_relevant_frame() {
+    let stack = CallStack::new(Some(
+        "Error\n    at src/app.ts:10:5\n    at node_modules/lib/index.js:1:1",
+    ));
+    assert_eq!(stack.frames().count(), 2);
+    assert_eq!(stack.path().as_str(), "    at src/app.ts:10:5");
+    assert_eq!(stack.file_name().as_str(), "app.ts:10:5");
+}
+
 #[test]
 fn call_stack_strips_localhost() {
     let stack = CallStack::new(Some("at http://localhost:1420/src/app.ts:10:5"));
@@ -290,6 +326,98 @@ fn builder_with_rotation_strategy() {
         .build::<tauri::Wry>();
 }
 
+#[test]
+fn builder_with_instance_strategy() {
+    let builder = Builder::new().with_file_logging();
+    assert_eq!(
+        builder.configured_instance_strategy(),
+        InstanceStrategy::Shared
+    );
+
+    let builder = builder.with_instance_strategy(InstanceStrategy::Lock);
+    assert_eq!(
+        builder.configured_instance_strategy(),
+        InstanceStrategy::Lock
+    );
+    let _plugin = builder.build::<tauri::Wry>();
+
+    let _plugin = Builder::new()
+        .with_file_logging()
+        .with_instance_strategy(InstanceStrategy::ProcessId)
+        .build::<tauri::Wry>();
+
+    let _plugin = Builder::new()
+        .with_file_logging()
+        .with_instance_strategy(InstanceStrategy::Forward)
+        .build::<tauri::Wry>();
+}
+
+#[test]
+fn builder_with_version_layout() {
+    let builder = Builder::new().with_file_logging();
+    assert_eq!(builder.configured_version_layout(), VersionLayout::Flat);
+
+    let builder = builder
+        .with_version_layout(VersionLayout::Directory)
+        .with_rotation_strategy(RotationStrategy::KeepSome(14));
+    assert_eq!(
+        builder.configured_version_layout(),
+        VersionLayout::Directory
+    );
+    let _plugin = builder.build::<tauri::Wry>();
+
+    let _plugin = Builder::new()
+        .with_file_logging()
+        .with_version_layout(VersionLayout::FileName)
+        .build::<tauri::Wry>();
+}
+
+#[test]
+fn builder_with_stderr_threshold() {
+    let builder = Builder::new();
+    assert_eq!(builder.configured_stderr_threshold(), None);
+
+    let builder = builder.with_stderr_threshold(LevelFilter::WARN);
+    assert_eq!(
+        builder.configured_stderr_threshold(),
+        Some(LevelFilter::WARN)
+    );
+    assert!(builder.validate().unwrap().warnings().is_empty());
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn builder_with_file_name_template() {
+    let builder = Builder::new().with_file_logging();
+    assert_eq!(builder.configured_file_name_template(), None);
+
+    let builder = builder
+        .with_instance_strategy(InstanceStrategy::Lock)
+        .with_file_name_template("{prefix}.{date}.{hostname}.{instance}.log");
+    assert_eq!(
+        builder.configured_file_name_template(),
+        Some("{prefix}.{date}.{hostname}.{instance}.log")
+    );
+    assert!(builder.validate().is_ok());
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn builder_with_sync_policy() {
+    let builder = Builder::new().with_file_logging();
+    assert_eq!(builder.configured_sync_policy(), SyncPolicy::Never);
+
+    let builder = builder.with_sync_policy(SyncPolicy::EveryN(100));
+    assert_eq!(builder.configured_sync_policy(), SyncPolicy::EveryN(100));
+    let _plugin = builder.build::<tauri::Wry>();
+
+    let _plugin = Builder::new()
+        .with_file_logging()
+        .with_max_file_size(MaxFileSize::mb(10))
+        .with_sync_policy(SyncPolicy::Interval(std::time::Duration::from_secs(1)))
+        .build::<tauri::Wry>();
+}
+
 #[test]
 fn builder_full_rotation_configuration() {
     // Test full configuration
@@ -337,6 +465,10 @@ fn record_payload_serialization() {
     let payload = RecordPayload {
         message: "test message".to_string(),
         level: LogLevel::Info,
+        fields: Default::default(),
+        breadcrumbs: None,
+        seq: None,
+        gap: None,
     };
 
     // Verify payload can be serialized (required for emit)
@@ -360,6 +492,10 @@ fn record_payload_levels() {
         let payload = RecordPayload {
             message: "test".to_string(),
             level,
+            fields: Default::default(),
+            breadcrumbs: None,
+            seq: None,
+            gap: None,
         };
         let json = serde_json::to_string(&payload).unwrap();
         assert!(
@@ -932,3 +1068,4066 @@ fn builder_with_flamegraph_and_layer() {
         .with_default_subscriber()
         .build::<tauri::Wry>();
 }
+
+// ============================================================================
+// Span context propagation tests
+// ============================================================================
+
+#[test]
+fn span_context_registry_round_trip() {
+    use tauri_plugin_tracing::SpanContextRegistry;
+    use tracing_subscriber::{layer::SubscriberExt, registry};
+
+    let registry_state = SpanContextRegistry::default();
+
+    tracing::subscriber::with_default(registry().with(tracing_subscriber::fmt::layer()), || {
+        let span = tracing::info_span!("load_items");
+        let context = registry_state.register(&span).unwrap();
+
+        assert_eq!(context.trace_id.len(), 32);
+        assert_eq!(context.span_id.len(), 16);
+
+        let found = registry_state.get(&context).unwrap();
+        assert_eq!(found.id(), span.id());
+
+        // Registering the same span again returns the same context
+        assert_eq!(registry_state.register(&span).unwrap(), context);
+        assert_eq!(registry_state.len(), 1);
+
+        assert!(registry_state.release(&context));
+        assert!(registry_state.get(&context).is_none());
+        assert!(registry_state.is_empty());
+    });
+}
+
+#[test]
+fn span_context_child_inherits_trace_id() {
+    use tauri_plugin_tracing::SpanContextRegistry;
+    use tracing_subscriber::{layer::SubscriberExt, registry};
+
+    let registry_state = SpanContextRegistry::default();
+
+    tracing::subscriber::with_default(registry().with(tracing_subscriber::fmt::layer()), || {
+        let parent = tracing::info_span!("parent");
+        let parent_context = registry_state.register(&parent).unwrap();
+
+        let child = tracing::info_span!(parent: &parent, "child");
+        let child_context = registry_state.register(&child).unwrap();
+
+        assert_eq!(parent_context.trace_id, child_context.trace_id);
+        assert_ne!(parent_context.span_id, child_context.span_id);
+
+        let unrelated = tracing::info_span!(parent: None, "unrelated");
+        let unrelated_context = registry_state.register(&unrelated).unwrap();
+        assert_ne!(parent_context.trace_id, unrelated_context.trace_id);
+    });
+}
+
+#[test]
+fn span_context_registry_evicts_oldest() {
+    use tauri_plugin_tracing::SpanContextRegistry;
+    use tracing_subscriber::registry;
+
+    let registry_state = SpanContextRegistry::with_capacity(2);
+
+    tracing::subscriber::with_default(registry(), || {
+        let first = registry_state
+            .register(&tracing::info_span!("first"))
+            .unwrap();
+        let _second = registry_state
+            .register(&tracing::info_span!("second"))
+            .unwrap();
+        let _third = registry_state
+            .register(&tracing::info_span!("third"))
+            .unwrap();
+
+        assert_eq!(registry_state.len(), 2);
+        assert!(registry_state.get(&first).is_none());
+    });
+}
+
+#[test]
+fn span_context_serialization() {
+    use tauri_plugin_tracing::SpanContext;
+
+    let context = SpanContext {
+        trace_id: "0af7651916cd43dd8448eb211c80319c".to_string(),
+        span_id: "00f067aa0ba902b7".to_string(),
+    };
+
+    let json = serde_json::to_string(&context).unwrap();
+    assert!(json.contains("traceId"));
+    assert!(json.contains("spanId"));
+
+    let parsed: SpanContext = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, context);
+}
+
+#[test]
+fn traceparent_round_trip_resolves_registered_span() {
+    use tauri_plugin_tracing::{SpanContext, SpanContextRegistry};
+
+    let subscriber = tracing_subscriber::registry();
+    tracing::subscriber::with_default(subscriber, || {
+        let registry = SpanContextRegistry::default();
+        let span = tracing::info_span!("request");
+        let context = registry.register(&span).unwrap();
+
+        let header = context.to_traceparent();
+        let parsed = SpanContext::from_traceparent(&header).unwrap();
+        assert_eq!(parsed, context);
+        assert_eq!(registry.get(&parsed).unwrap().id(), span.id());
+    });
+}
+
+#[test]
+fn traceparent_rejects_malformed_values() {
+    use tauri_plugin_tracing::SpanContext;
+
+    for value in [
+        "",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        "00-4bf92f3577b34da6-00f067aa0ba902b7-01",
+    ] {
+        assert!(SpanContext::from_traceparent(value).is_none(), "{value}");
+    }
+
+    // Future versions may append fields
+    assert!(
+        SpanContext::from_traceparent(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+        )
+        .is_some()
+    );
+}
+
+// ============================================================================
+// Slow span detection tests
+// ============================================================================
+
+/// Records the target and fields of every event it sees.
+#[derive(Clone, Default)]
+struct EventRecorder(std::sync::Arc<std::sync::Mutex<Vec<(String, String)>>>);
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for EventRecorder {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        struct Fields(String);
+        impl tracing::field::Visit for Fields {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                self.0.push_str(&format!("{}={:?} ", field.name(), value));
+            }
+        }
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.0
+            .lock()
+            .unwrap()
+            .push((event.metadata().target().to_string(), fields.0));
+    }
+}
+
+#[test]
+fn builder_with_slow_span_threshold() {
+    use std::time::Duration;
+
+    let builder = Builder::new();
+    assert!(builder.configured_slow_span_threshold().is_none());
+
+    let builder = builder.with_slow_span_threshold(Duration::from_millis(50));
+    assert_eq!(
+        builder.configured_slow_span_threshold(),
+        Some(Duration::from_millis(50))
+    );
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn slow_span_layer_warns_with_parent_chain() {
+    use std::time::Duration;
+    use tauri_plugin_tracing::{SLOW_SPAN_TARGET, SlowSpanLayer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let recorder = EventRecorder::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(SlowSpanLayer::new(Duration::from_millis(5)))
+        .with(recorder.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let outer = tracing::info_span!("outer");
+        let _outer = outer.enter();
+        let inner = tracing::info_span!("inner");
+        {
+            let _inner = inner.enter();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        drop(inner);
+    });
+
+    let events = recorder.0.lock().unwrap();
+    let (target, fields) = events
+        .iter()
+        .find(|(_, fields)| fields.contains("span=\"inner\""))
+        .expect("slow span warning for inner");
+    assert_eq!(target, SLOW_SPAN_TARGET);
+    assert!(fields.contains("parents=outer"), "{fields}");
+    assert!(fields.contains("duration_ms="), "{fields}");
+}
+
+#[test]
+fn slow_span_layer_ignores_fast_spans() {
+    use std::time::Duration;
+    use tauri_plugin_tracing::SlowSpanLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let recorder = EventRecorder::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(SlowSpanLayer::new(Duration::from_secs(60)))
+        .with(recorder.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("fast");
+        let _enter = span.enter();
+    });
+
+    assert!(recorder.0.lock().unwrap().is_empty());
+}
+
+#[test]
+fn slow_span_layer_ignores_idle_time() {
+    use std::time::Duration;
+    use tauri_plugin_tracing::SlowSpanLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let recorder = EventRecorder::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(SlowSpanLayer::new(Duration::from_millis(20)))
+        .with(recorder.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        // Open for longer than the threshold, but entered only briefly
+        let span = tracing::info_span!("idle");
+        span.in_scope(|| {});
+        std::thread::sleep(Duration::from_millis(30));
+        span.in_scope(|| {});
+    });
+
+    assert!(recorder.0.lock().unwrap().is_empty());
+}
+
+// ============================================================================
+// Resource monitor tests
+// ============================================================================
+
+#[test]
+fn builder_with_resource_monitor() {
+    use std::time::Duration;
+
+    let builder = Builder::new();
+    assert!(builder.configured_resource_monitor_interval().is_none());
+
+    let builder = builder.with_resource_monitor(Duration::from_secs(30));
+    assert_eq!(
+        builder.configured_resource_monitor_interval(),
+        Some(Duration::from_secs(30))
+    );
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn resource_snapshot_capture() {
+    use tauri_plugin_tracing::ResourceSnapshot;
+
+    let snapshot = ResourceSnapshot::capture();
+    assert!(snapshot.cpu_percent.is_none());
+
+    #[cfg(target_os = "linux")]
+    {
+        assert!(snapshot.rss_bytes.unwrap() > 0);
+        assert!(snapshot.thread_count.unwrap() >= 1);
+        assert!(snapshot.open_fds.unwrap() >= 3);
+    }
+}
+
+#[test]
+fn resource_snapshot_serialization() {
+    use tauri_plugin_tracing::ResourceSnapshot;
+
+    let snapshot = ResourceSnapshot {
+        rss_bytes: Some(1024),
+        cpu_percent: None,
+        open_fds: Some(12),
+        thread_count: Some(4),
+    };
+    let json = serde_json::to_value(&snapshot).unwrap();
+    assert_eq!(json["rssBytes"], 1024);
+    assert!(json["cpuPercent"].is_null());
+    assert_eq!(json["openFds"], 12);
+    assert_eq!(json["threadCount"], 4);
+}
+
+#[test]
+fn resource_monitor_records_latest_snapshot() {
+    use std::time::Duration;
+    use tauri_plugin_tracing::ResourceMonitor;
+
+    let monitor = ResourceMonitor::start(Duration::from_millis(10)).unwrap();
+    assert_eq!(monitor.interval(), Duration::from_millis(10));
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while monitor.latest().is_none() && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(monitor.latest().is_some());
+}
+
+// ============================================================================
+// Frame timing tests
+// ============================================================================
+
+#[test]
+fn frame_stats_percentiles() {
+    use tauri_plugin_tracing::FrameStats;
+
+    let mut times = vec![16.0; 98];
+    times.push(120.0);
+    times.push(250.0);
+
+    let stats = FrameStats::from_frame_times(&times).unwrap();
+    assert_eq!(stats.count, 100);
+    assert_eq!(stats.p50_ms, 16.0);
+    assert_eq!(stats.p95_ms, 16.0);
+    assert_eq!(stats.p99_ms, 120.0);
+    assert_eq!(stats.max_ms, 250.0);
+    assert!((stats.mean_ms - 19.38).abs() < 1e-9);
+}
+
+#[test]
+fn frame_stats_ignores_non_finite_and_empty() {
+    use tauri_plugin_tracing::FrameStats;
+
+    assert!(FrameStats::from_frame_times(&[]).is_none());
+    assert!(FrameStats::from_frame_times(&[f64::NAN, f64::INFINITY]).is_none());
+
+    let stats = FrameStats::from_frame_times(&[f64::NAN, 10.0]).unwrap();
+    assert_eq!(stats.count, 1);
+    assert_eq!(stats.p99_ms, 10.0);
+}
+
+#[test]
+fn frame_timing_report_deserialization() {
+    use tauri_plugin_tracing::{FrameTimingReport, LongFrame};
+
+    let report: FrameTimingReport = serde_json::from_str(
+        r#"{"frameTimesMs":[16.6,80.0],"longFrames":[{"timestampMs":1700000000000.5,"durationMs":80.0}]}"#,
+    )
+    .unwrap();
+    assert_eq!(report.frame_times_ms, vec![16.6, 80.0]);
+    assert_eq!(
+        report.long_frames,
+        vec![LongFrame {
+            timestamp_ms: 1_700_000_000_000.5,
+            duration_ms: 80.0,
+        }]
+    );
+}
+
+#[test]
+fn builder_with_output_capture() {
+    let builder = Builder::new();
+    assert!(!builder.configured_output_capture());
+
+    let builder = builder.with_output_capture();
+    assert!(builder.configured_output_capture());
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Traced commands
+// ============================================================================
+
+mod traced {
+    use tauri_plugin_tracing::traced_command;
+
+    #[traced_command]
+    #[tauri::command]
+    pub fn rename(id: u32, name: String) -> Result<String, String> {
+        if name.is_empty() {
+            return Err("name is empty".into());
+        }
+        Ok(format!("{id}: {name}"))
+    }
+
+    #[traced_command]
+    #[tauri::command]
+    pub async fn sign_in(user: String, #[redact] password: String) -> bool {
+        tracing::info!("checking credentials");
+        !user.is_empty() && !password.is_empty()
+    }
+}
+
+#[test]
+fn traced_command_logs_invocation_and_errors() {
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tracing::Level;
+
+    let events = with_captured_logs(|| {
+        assert_eq!(traced::rename(1, "notes".into()).unwrap(), "1: notes");
+        assert!(traced::rename(2, String::new()).is_err());
+    });
+
+    let messages: Vec<_> = events.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "command invoked",
+            "command completed",
+            "command invoked",
+            "command failed"
+        ]
+    );
+    assert!(events.iter().all(|e| e.target == "ipc::command"));
+    assert!(events.iter().all(|e| e.in_span("command")));
+    assert_eq!(events[0].field("command"), Some("rename"));
+    assert!(events[1].field("duration_ms").is_some());
+    assert_eq!(events[3].level, Level::WARN);
+    assert_eq!(events[3].field("error"), Some("\"name is empty\""));
+}
+
+#[test]
+fn traced_command_redacts_arguments() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(buffer.clone()),
+    );
+    let signed_in = tracing::subscriber::with_default(subscriber, || {
+        tauri::async_runtime::block_on(traced::sign_in("ada".into(), "hunter2".into()))
+    });
+    assert!(signed_in);
+
+    let output = buffer.contents();
+    assert!(output.contains(r#"user="ada""#), "{output}");
+    assert!(output.contains(r#"password="[redacted]""#), "{output}");
+    assert!(!output.contains("hunter2"), "{output}");
+    assert!(output.contains("checking credentials"), "{output}");
+}
+
+// ============================================================================
+// Test utilities
+// ============================================================================
+
+#[test]
+fn with_captured_logs_records_levels_fields_and_spans() {
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tracing::Level;
+
+    let events = with_captured_logs(|| {
+        let span = tracing::info_span!("load_items");
+        let _enter = span.enter();
+        tracing::debug!(count = 3, name = "items", "loaded");
+        tracing::error!("failed");
+    });
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].level, Level::DEBUG);
+    assert_eq!(events[0].message, "loaded");
+    assert_eq!(events[0].field("count"), Some("3"));
+    assert_eq!(events[0].field("name"), Some("items"));
+    assert!(events[0].in_span("load_items"));
+    assert_eq!(events[1].level, Level::ERROR);
+    assert!(events[1].target.starts_with("integration"));
+}
+
+#[test]
+fn capturing_layer_take_drains_events() {
+    use tauri_plugin_tracing::test::CapturingLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let layer = CapturingLayer::new();
+    let subscriber = tracing_subscriber::registry().with(layer.clone());
+    tracing::subscriber::with_default(subscriber, || tracing::info!("one"));
+
+    assert_eq!(layer.take().len(), 1);
+    assert!(layer.events().is_empty());
+}
+
+#[test]
+fn mock_app_log_command_is_captured() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tracing::Level;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({ "level": 4, "message": ["from", "webview"] }),
+        )
+        .unwrap();
+    });
+
+    let event = events
+        .iter()
+        .find(|e| e.message == "from, webview")
+        .expect("log command event");
+    assert_eq!(event.level, Level::WARN);
+    assert!(event.in_span("log"));
+}
+
+#[test]
+fn default_subscriber_reports_existing_subscriber() {
+    use tauri_plugin_tracing::test::{mock_app, with_captured_logs};
+
+    let mut result = None;
+    with_captured_logs(|| {
+        result = Some(mock_app(Builder::new().with_default_subscriber()));
+    });
+    let Some(Err(error)) = result else {
+        panic!("plugin initialized over an existing subscriber");
+    };
+    assert!(
+        error.to_string().contains("try_with_default_subscriber()"),
+        "{error}"
+    );
+}
+
+#[test]
+fn try_with_default_subscriber_keeps_existing_subscriber() {
+    use tauri_plugin_tracing::test::{mock_app, with_captured_logs};
+    use tracing::Level;
+
+    let events = with_captured_logs(|| {
+        mock_app(Builder::new().try_with_default_subscriber()).unwrap();
+    });
+
+    assert!(
+        events
+            .iter()
+            .any(|e| e.level == Level::WARN && e.message.contains("already set"))
+    );
+    assert!(!events.iter().any(|e| e.message == "tracing initialized"));
+}
+
+#[test]
+fn log_command_shows_forwarded_target() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({
+                "level": 3,
+                "message": ["mounted", { "count": 3 }],
+                "callStack": "src/ui.rs:42",
+                "target": "app::ui",
+            }),
+        )
+        .unwrap();
+    });
+
+    let event = events
+        .iter()
+        .find(|e| e.message.starts_with("mounted"))
+        .expect("log command event");
+    assert_eq!(event.field(""), Some("app::ui@ui.rs:42"));
+    assert_eq!(event.field("args"), Some(r#"[{"count":3}]"#));
+}
+
+#[test]
+fn log_command_records_route_and_session_on_the_log_span() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(buffer.clone()),
+    );
+    tracing::subscriber::with_default(subscriber, || {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({
+                "level": 3,
+                "message": ["opened"],
+                "route": "/settings#sync",
+                "session": "4f1c",
+            }),
+        )
+        .unwrap();
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({ "level": 3, "message": ["without page"] }),
+        )
+        .unwrap();
+    });
+
+    let contents = buffer.contents();
+    let lines: Vec<_> = contents.lines().collect();
+    assert_eq!(lines.len(), 2, "{contents}");
+    assert!(
+        lines[0].contains("log{w=main route=/settings#sync session=4f1c}"),
+        "{}",
+        lines[0]
+    );
+    assert!(lines[1].contains("log{w=main}"), "{}", lines[1]);
+}
+
+#[test]
+fn capturing_webview_layer_records_payloads() {
+    use tauri_plugin_tracing::test::capturing_webview_layer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (layer, records) = capturing_webview_layer();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::warn!(code = 7, attempt = 2, "disk almost full");
+        tracing::error!(reason = "timeout");
+    });
+
+    let records = records.take();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].message, "disk almost full");
+    assert!(matches!(records[0].level, LogLevel::Warn));
+    assert_eq!(records[0].fields.get("code").map(String::as_str), Some("7"));
+    assert!(!records[0].fields.contains_key("message"));
+    // Fields keep the order they were recorded in, not alphabetical order
+    let names: Vec<&str> = records[0].fields.iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["code", "attempt"]);
+    // Without a message, the first field is used
+    assert_eq!(records[1].message, "timeout");
+    assert!(matches!(records[1].level, LogLevel::Error));
+}
+
+#[test]
+fn webview_layer_skips_levels_and_blocked_targets() {
+    use tauri_plugin_tracing::test::{CapturingLayer, capturing_webview_layer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (layer, records) = capturing_webview_layer();
+    let layer = layer
+        .with_max_level(LevelFilter::INFO)
+        .with_blocked_targets(["tao"]);
+    let capturing = CapturingLayer::new();
+    let subscriber = tracing_subscriber::registry()
+        .with(layer)
+        .with(capturing.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::debug!("too verbose");
+        tracing::info!(target: "tao::platform_impl", "event loop");
+        tracing::info!(target: "taoist", "not blocked");
+        tracing::warn!("kept");
+    });
+
+    let messages: Vec<_> = records.take().into_iter().map(|r| r.message).collect();
+    assert_eq!(messages, ["not blocked", "kept"]);
+    // Skipped events are disabled before any layer sees them
+    let seen: Vec<_> = capturing.take().into_iter().map(|e| e.message).collect();
+    assert_eq!(seen, ["not blocked", "kept"]);
+}
+
+#[test]
+fn mock_app_webview_layer_respects_builder_filter() {
+    use tauri::Manager;
+    use tauri_plugin_tracing::WebviewLayer;
+    use tauri_plugin_tracing::test::{listen_webview_records, mock_app};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let builder = Builder::new()
+        .with_max_level(LevelFilter::INFO)
+        .with_target("noisy", LevelFilter::ERROR);
+    let filter = builder.build_filter();
+    let app = mock_app(builder).unwrap();
+    let records = listen_webview_records(&app);
+
+    let subscriber = tracing_subscriber::registry()
+        .with(WebviewLayer::new(app.app_handle().clone()))
+        .with(filter);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("kept");
+        tracing::debug!("too verbose");
+        tracing::warn!(target: "noisy", "filtered by target");
+        tracing::error!(target: "noisy", "kept from noisy");
+    });
+
+    assert_eq!(records.messages(), vec!["kept", "kept from noisy"]);
+}
+
+#[test]
+fn renamed_plugin_uses_its_name_for_commands_and_events() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tauri::{Listener, Manager};
+    use tauri_plugin_tracing::WebviewLayer;
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let app = mock_app(Builder::new().with_plugin_name("tracing-admin")).unwrap();
+    let window = mock_window(&app, "admin").unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing-admin|log",
+            serde_json::json!({ "level": 3, "message": ["from admin"] }),
+        )
+        .unwrap();
+        assert!(
+            invoke_command(
+                &window,
+                "plugin:tracing|log",
+                serde_json::json!({ "level": 3, "message": ["unregistered"] }),
+            )
+            .is_err()
+        );
+    });
+    assert!(events.iter().any(|e| e.message == "from admin"));
+    assert!(!events.iter().any(|e| e.message == "unregistered"));
+
+    let counts = Arc::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+    for (n, channel) in ["tracing-admin://log", "tracing://log"]
+        .into_iter()
+        .enumerate()
+    {
+        let counts = counts.clone();
+        app.listen_any(channel, move |_| {
+            counts[n].fetch_add(1, Ordering::Relaxed);
+        });
+    }
+    let subscriber =
+        tracing_subscriber::registry().with(WebviewLayer::new(app.app_handle().clone()));
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("to the admin channel");
+    });
+    assert_eq!(counts[0].load(Ordering::Relaxed), 1);
+    assert_eq!(counts[1].load(Ordering::Relaxed), 0);
+}
+
+// ============================================================================
+// Webview Forwarding Pause Tests
+// ============================================================================
+
+#[test]
+fn paused_webview_layer_buffers_until_resumed() {
+    use tauri_plugin_tracing::test::capturing_webview_layer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (layer, records) = capturing_webview_layer();
+    let forwarding = layer.forwarding();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("before pause");
+        forwarding.pause();
+        tracing::info!("while paused 1");
+        tracing::info!("while paused 2");
+    });
+
+    assert!(forwarding.is_paused());
+    assert_eq!(forwarding.buffered(), 2);
+    assert_eq!(records.messages(), vec!["before pause"]);
+}
+
+#[test]
+fn resumed_webview_layer_emits_buffered_records_in_order() {
+    use tauri::Manager;
+    use tauri_plugin_tracing::test::{listen_webview_records, mock_app};
+    use tauri_plugin_tracing::{WebviewForwarding, WebviewLayer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let records = listen_webview_records(&app);
+    let layer = WebviewLayer::new(app.app_handle().clone());
+    // The layer shares the plugin's managed forwarding state
+    let forwarding = app.state::<WebviewForwarding>().inner().clone();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        forwarding.pause();
+        tracing::info!("first");
+        tracing::info!("second");
+        assert!(records.records().is_empty());
+
+        forwarding.resume(app.app_handle());
+        tracing::info!("third");
+    });
+
+    assert!(!forwarding.is_paused());
+    assert_eq!(forwarding.buffered(), 0);
+    assert_eq!(records.messages(), vec!["first", "second", "third"]);
+}
+
+#[test]
+fn webview_ready_resumes_forwarding_without_holding_the_buffer() {
+    use tauri::{Listener, Manager};
+    use tauri_plugin_tracing::test::{
+        invoke_command, listen_webview_records, mock_app, mock_window,
+    };
+    use tauri_plugin_tracing::{RecordPayload, WebviewForwarding, WebviewLayer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+    let records = listen_webview_records(&app);
+    // A listener that logs while records are emitted must not deadlock
+    app.listen_any("tracing://log", |event| {
+        let payload: RecordPayload = serde_json::from_str(event.payload()).unwrap();
+        if payload.message == "first" {
+            tracing::info!("logged while emitting");
+        }
+    });
+    let layer = WebviewLayer::new(app.app_handle().clone());
+    let forwarding = app.state::<WebviewForwarding>().inner().clone();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        forwarding.pause();
+        tracing::info!("first");
+        tracing::info!("second");
+
+        invoke_command(
+            &window,
+            "plugin:tracing|webview_ready",
+            serde_json::json!({}),
+        )
+        .unwrap();
+        tracing::info!("third");
+    });
+
+    assert!(!forwarding.is_paused());
+    assert_eq!(
+        records.messages(),
+        vec!["first", "second", "logged while emitting", "third"]
+    );
+}
+
+#[test]
+fn paused_webview_layer_drops_oldest_records_when_full() {
+    use tauri_plugin_tracing::test::capturing_webview_layer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (layer, _records) = capturing_webview_layer();
+    let forwarding = layer.forwarding();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    forwarding.pause();
+    tracing::subscriber::with_default(subscriber, || {
+        for i in 0..1005 {
+            tracing::info!("record {i}");
+        }
+    });
+
+    assert_eq!(forwarding.buffered(), 1000);
+}
+
+#[test]
+fn resumed_webview_layer_reports_dropped_records_as_a_gap() {
+    use tauri::Manager;
+    use tauri_plugin_tracing::test::{listen_webview_records, mock_app};
+    use tauri_plugin_tracing::{WebviewForwarding, WebviewLayer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let records = listen_webview_records(&app);
+    let layer = WebviewLayer::new(app.app_handle().clone());
+    let forwarding = app.state::<WebviewForwarding>().inner().clone();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        forwarding.pause();
+        for i in 0..1003 {
+            tracing::info!("record {i}");
+        }
+        assert_eq!(forwarding.dropped(), 3);
+
+        forwarding.resume(app.app_handle());
+        tracing::info!("after resume");
+    });
+
+    let records = records.records();
+    assert_eq!(records.len(), 1001);
+    assert_eq!(records[0].message, "record 3");
+    assert_eq!(records[0].gap, Some(3));
+    assert!(records[1..].iter().all(|record| record.gap.is_none()));
+    assert_eq!(forwarding.dropped(), 0);
+}
+
+// ============================================================================
+// Message Translator Tests
+// ============================================================================
+
+#[test]
+fn message_translator_only_applies_to_webview_records() {
+    use tauri::Manager;
+    use tauri_plugin_tracing::WebviewLayer;
+    use tauri_plugin_tracing::test::{listen_webview_records, mock_app};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let builder = Builder::new().with_message_translator(|record| {
+        match record.fields.get("event_id")?.as_str() {
+            "E1001" => Some("Impossible de joindre le serveur".to_string()),
+            _ => None,
+        }
+    });
+    assert!(builder.has_message_translator());
+    let app = mock_app(builder).unwrap();
+    let records = listen_webview_records(&app);
+
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .without_time()
+                .with_ansi(false)
+                .with_writer(buffer.clone()),
+        )
+        .with(WebviewLayer::new(app.app_handle().clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::error!(event_id = "E1001", "db connection failed");
+        tracing::info!("synced");
+    });
+
+    assert_eq!(
+        records.messages(),
+        vec!["Impossible de joindre le serveur", "synced"]
+    );
+    let contents = buffer.contents();
+    assert!(contents.contains("db connection failed"), "{contents}");
+    assert!(!contents.contains("Impossible"), "{contents}");
+}
+
+// ============================================================================
+// Sequence Number Tests
+// ============================================================================
+
+#[test]
+fn sequence_numbers_match_across_outputs() {
+    use tauri_plugin_tracing::test::capturing_webview_layer;
+    use tauri_plugin_tracing::{SequenceFields, SequenceLayer, current_sequence};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let buffer = SharedBuffer::default();
+    let (webview_layer, records) = capturing_webview_layer();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .without_time()
+                .with_ansi(false)
+                .with_writer(buffer.clone())
+                .fmt_fields(SequenceFields::new(
+                    tracing_subscriber::fmt::format::DefaultFields::new(),
+                )),
+        )
+        .with(webview_layer)
+        .with(SequenceLayer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("first");
+        tracing::warn!(code = 7, "second");
+    });
+    assert_eq!(current_sequence(), None);
+
+    let records = records.records();
+    let seqs: Vec<u64> = records.iter().map(|r| r.seq.unwrap()).collect();
+    assert_eq!(seqs.len(), 2);
+    assert!(seqs[0] < seqs[1], "{seqs:?}");
+
+    let contents = buffer.contents();
+    let lines: Vec<_> = contents.lines().collect();
+    assert!(
+        lines[0].ends_with(&format!("first seq={}", seqs[0])),
+        "{}",
+        lines[0]
+    );
+    assert!(
+        lines[1].ends_with(&format!("second code=7 seq={}", seqs[1])),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn builder_with_sequence_numbers() {
+    let builder = Builder::new()
+        .with_sequence_numbers()
+        .with_default_subscriber();
+    assert!(builder.configured_sequence_numbers());
+    assert!(!Builder::new().configured_sequence_numbers());
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Structured Log Message Tests
+// ============================================================================
+
+#[test]
+fn log_message_display_renders_json_parts() {
+    use tauri_plugin_tracing::LogMessage;
+
+    let message: LogMessage =
+        serde_json::from_value(serde_json::json!(["state", { "a": [1, 2] }, 3, null])).unwrap();
+
+    assert_eq!(message.to_string(), r#"state, {"a":[1,2]}, 3, null"#);
+    assert_eq!(
+        format!("{message:#}"),
+        "state, {\n  \"a\": [\n    1,\n    2\n  ]\n}, 3, null"
+    );
+    assert_eq!(message.args().as_deref(), Some(r#"[{"a":[1,2]},3,null]"#));
+}
+
+#[test]
+fn log_message_args_is_none_for_plain_strings() {
+    use tauri_plugin_tracing::LogMessage;
+
+    let message: LogMessage = serde_json::from_value(serde_json::json!(["a", "b"])).unwrap();
+
+    assert_eq!(message.to_string(), "a, b");
+    assert!(message.args().is_none());
+}
+
+#[test]
+fn log_command_records_structured_args() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({ "level": 3, "message": ["state", { "open": true }] }),
+        )
+        .unwrap();
+    });
+
+    let event = events
+        .iter()
+        .find(|e| e.message == r#"state, {"open":true}"#)
+        .expect("log command event");
+    assert_eq!(event.field("args"), Some(r#"[{"open":true}]"#));
+}
+
+// ============================================================================
+// Message Size Limit Tests
+// ============================================================================
+
+#[test]
+fn builder_with_message_limits() {
+    let builder = Builder::new()
+        .with_max_message_len(64 * 1024)
+        .with_max_stack_frames(50);
+    assert_eq!(builder.configured_max_message_len(), Some(64 * 1024));
+    assert_eq!(builder.configured_max_stack_frames(), Some(50));
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn builder_message_limits_default_to_none() {
+    let builder = Builder::new();
+    assert!(builder.configured_max_message_len().is_none());
+    assert!(builder.configured_max_stack_frames().is_none());
+}
+
+#[test]
+fn log_command_truncates_long_messages() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new().with_max_message_len(8)).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        for message in ["short", "a much longer message", "ééééé"] {
+            invoke_command(
+                &window,
+                "plugin:tracing|log",
+                serde_json::json!({ "level": 3, "message": [message] }),
+            )
+            .unwrap();
+        }
+    });
+
+    let events: Vec<_> = events.iter().filter(|e| e.target.is_empty()).collect();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].message, "short");
+    assert_eq!(events[0].field("truncated"), None);
+    assert_eq!(events[1].message, "a much l…");
+    assert_eq!(events[1].field("truncated"), Some("true"));
+    // Truncation never splits a multi-byte character
+    assert_eq!(events[2].message, "éééé…");
+}
+
+#[test]
+fn log_command_truncates_deep_call_stacks() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    // The `Error` header is not counted as a frame
+    let app = mock_app(Builder::new().with_max_stack_frames(1)).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+    let stack = "Error\nat one (http://localhost/a.js:1:1)\nat two (http://localhost/b.js:2:2)";
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({ "level": 1, "message": ["deep"], "callStack": stack }),
+        )
+        .unwrap();
+    });
+
+    let event = events
+        .iter()
+        .find(|e| e.message == "deep")
+        .expect("log command event");
+    assert_eq!(event.field("truncated"), Some("true"));
+    assert!(!event.field("").unwrap_or_default().contains("b.js"));
+}
+
+#[test]
+fn log_command_keeps_truncated_args_valid_json() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new().with_max_message_len(64)).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+    let long = "x".repeat(200);
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({
+                "level": 3,
+                "message": ["object", { "key": long, "nested": [long] }],
+            }),
+        )
+        .unwrap();
+    });
+
+    let event = events
+        .iter()
+        .find(|e| e.message == "object")
+        .expect("log command event");
+    assert_eq!(event.field("truncated"), Some("true"));
+    let args = event.field("args").expect("args field");
+    assert!(args.len() <= 64);
+    let args: serde_json::Value = serde_json::from_str(args).unwrap();
+    assert!(args[0]["key"].as_str().unwrap().ends_with('…'));
+}
+
+// ============================================================================
+// Operation Lifecycle Tests
+// ============================================================================
+
+#[test]
+fn operation_start_and_end_record_outcome() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tracing::Level;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        let handle = invoke_command(
+            &window,
+            "plugin:tracing|operation_start",
+            serde_json::json!({ "name": "import", "metadata": { "files": 3 } }),
+        )
+        .unwrap();
+        let id = handle["id"].as_str().unwrap().to_string();
+        assert_eq!(handle["name"], "import");
+        assert!(handle["spanContext"].is_object());
+
+        let listed = invoke_command(
+            &window,
+            "plugin:tracing|operation_list",
+            serde_json::json!({}),
+        )
+        .unwrap();
+        assert_eq!(listed.as_array().unwrap().len(), 1);
+
+        invoke_command(
+            &window,
+            "plugin:tracing|operation_end",
+            serde_json::json!({ "id": id, "outcome": "failure" }),
+        )
+        .unwrap();
+
+        let ended = invoke_command(
+            &window,
+            "plugin:tracing|operation_get",
+            serde_json::json!({ "id": id }),
+        )
+        .unwrap();
+        assert!(ended.is_null());
+    });
+
+    let start = events
+        .iter()
+        .find(|e| e.message == "operation `import` started")
+        .expect("start event");
+    assert!(start.in_span("operation"));
+    let end = events
+        .iter()
+        .find(|e| e.message == "operation `import` failed")
+        .expect("end event");
+    assert_eq!(end.level, Level::WARN);
+    assert_eq!(end.field("outcome"), Some("failure"));
+}
+
+#[test]
+fn operation_end_rejects_unknown_id() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let err = invoke_command(
+        &window,
+        "plugin:tracing|operation_end",
+        serde_json::json!({ "id": "op-missing" }),
+    )
+    .unwrap_err();
+    assert_eq!(err, "Unknown operation: op-missing");
+}
+
+#[test]
+fn log_command_reattaches_to_operation() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        let handle = invoke_command(
+            &window,
+            "plugin:tracing|operation_start",
+            serde_json::json!({ "name": "upload" }),
+        )
+        .unwrap();
+        // A reloaded page looks the operation up again by id
+        let resumed = invoke_command(
+            &window,
+            "plugin:tracing|operation_get",
+            serde_json::json!({ "id": handle["id"] }),
+        )
+        .unwrap();
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({
+                "level": 3,
+                "message": ["chunk sent"],
+                "spanContext": resumed["spanContext"],
+            }),
+        )
+        .unwrap();
+    });
+
+    let event = events
+        .iter()
+        .find(|e| e.message == "chunk sent")
+        .expect("log event");
+    assert!(event.in_span("operation"));
+}
+
+#[test]
+fn operation_registry_warns_about_unfinished_operations() {
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tauri_plugin_tracing::{OperationOutcome, OperationRegistry, SpanContextRegistry};
+
+    let operations = OperationRegistry::default();
+    let spans = SpanContextRegistry::default();
+
+    let events = with_captured_logs(|| {
+        let finished = operations.start("finished", None, &spans).unwrap();
+        operations.start("abandoned", None, &spans).unwrap();
+        operations
+            .end(&finished.id, OperationOutcome::Success, &spans)
+            .unwrap();
+        assert_eq!(operations.list().len(), 1);
+        operations.warn_unfinished();
+    });
+
+    assert!(
+        events
+            .iter()
+            .any(|e| e.message == "operation `finished` ended")
+    );
+    let warnings: Vec<_> = events
+        .iter()
+        .filter(|e| e.message.ends_with("never ended"))
+        .collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message, "operation `abandoned` never ended");
+    // Ending an operation releases its propagated span
+    assert_eq!(spans.len(), 1);
+}
+
+#[test]
+fn operation_registry_caps_open_operations() {
+    use tauri_plugin_tracing::{
+        Error, MAX_OPERATIONS, OperationOutcome, OperationRegistry, SpanContextRegistry,
+    };
+
+    let operations = OperationRegistry::default();
+    let spans = SpanContextRegistry::default();
+
+    let handles: Vec<_> = (0..MAX_OPERATIONS)
+        .map(|_| operations.start("busy", None, &spans).unwrap())
+        .collect();
+    assert!(matches!(
+        operations.start("one too many", None, &spans),
+        Err(Error::TooManyOperations(MAX_OPERATIONS))
+    ));
+
+    // Ending an operation makes room for another
+    operations
+        .end(&handles[0].id, OperationOutcome::Success, &spans)
+        .unwrap();
+    assert!(operations.start("fits", None, &spans).is_ok());
+}
+
+// ============================================================================
+// RFC 5424 Format Tests
+// ============================================================================
+
+#[derive(Clone, Default)]
+struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for SharedBuffer {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn rfc5424_lines(format: tauri_plugin_tracing::Rfc5424Format, f: impl FnOnce()) -> Vec<String> {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .event_format(format)
+            .with_writer(buffer.clone()),
+    );
+    tracing::subscriber::with_default(subscriber, f);
+    buffer.contents().lines().map(str::to_string).collect()
+}
+
+#[test]
+fn builder_with_rfc5424_format() {
+    use tauri_plugin_tracing::LogFormat;
+
+    let builder = Builder::new().with_format(LogFormat::Rfc5424);
+    assert!(matches!(builder.configured_format(), LogFormat::Rfc5424));
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn syslog_severity_mapping() {
+    use tauri_plugin_tracing::syslog_severity;
+    use tracing::Level;
+
+    assert_eq!(syslog_severity(&Level::ERROR), 3);
+    assert_eq!(syslog_severity(&Level::WARN), 4);
+    assert_eq!(syslog_severity(&Level::INFO), 6);
+    assert_eq!(syslog_severity(&Level::DEBUG), 7);
+    assert_eq!(syslog_severity(&Level::TRACE), 7);
+}
+
+#[test]
+fn rfc5424_format_writes_header_and_structured_data() {
+    use tauri_plugin_tracing::{Rfc5424Format, SyslogFacility};
+
+    let format = Rfc5424Format::new("my app")
+        .with_hostname("host-1")
+        .with_facility(SyslogFacility::Local0);
+    let lines = rfc5424_lines(format, || {
+        let span = tracing::info_span!("request");
+        let _enter = span.enter();
+        tracing::warn!(target: "app::db", rows = 3, query = "say \"hi\"", "slow\nquery");
+    });
+
+    assert_eq!(lines.len(), 1);
+    let line = &lines[0];
+    // Local0 (16) * 8 + Warning (4)
+    assert!(line.starts_with("<132>1 "), "{line}");
+    let pid = std::process::id().to_string();
+    let fields: Vec<&str> = line.splitn(8, ' ').collect();
+    assert!(fields[1].ends_with('Z'));
+    assert_eq!(fields[1].len(), "2024-01-15T10:30:00.000000Z".len());
+    assert_eq!(fields[2], "host-1");
+    // Spaces are not allowed in header fields
+    assert_eq!(fields[3], "myapp");
+    assert_eq!(fields[4], pid);
+    assert_eq!(fields[5], "app::db");
+    assert!(
+        line.ends_with(r#"[fields@32473 rows="3" query="say \"hi\"" spans="request"] slow query"#),
+        "{line}"
+    );
+}
+
+#[test]
+fn rfc5424_format_uses_nilvalue_without_fields() {
+    use tauri_plugin_tracing::Rfc5424Format;
+
+    let lines = rfc5424_lines(Rfc5424Format::new("app").with_hostname(""), || {
+        tracing::info!(target: "plain", "hello");
+    });
+
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("<14>1 "));
+    assert!(lines[0].contains(" - app "), "{}", lines[0]);
+    assert!(lines[0].ends_with(" plain - hello"), "{}", lines[0]);
+}
+
+#[test]
+fn rfc5424_format_writes_attributes() {
+    use tauri_plugin_tracing::Rfc5424Format;
+
+    let format = Rfc5424Format::new("app")
+        .with_hostname("host")
+        .with_attributes([("service.name", "my-app"), ("env", "a \"b\"")]);
+    let lines = rfc5424_lines(format, || {
+        tracing::info!(target: "plain", "hello");
+        tracing::info!(target: "plain", rows = 3, "fields");
+    });
+
+    assert_eq!(lines.len(), 2);
+    assert!(
+        lines[0].ends_with(r#" plain [resource@32473 service.name="my-app" env="a \"b\""] hello"#),
+        "{}",
+        lines[0]
+    );
+    assert!(
+        lines[1].ends_with(r#" plain [fields@32473 rows="3"][resource@32473 service.name="my-app" env="a \"b\""] fields"#),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn builder_with_resource_attributes() {
+    let builder = Builder::new()
+        .with_resource_attributes([("service.name", "my-app")])
+        .with_resource_attributes([("deployment.environment", "prod")]);
+    assert_eq!(
+        builder.configured_resource_attributes(),
+        [
+            ("service.name".to_string(), "my-app".to_string()),
+            ("deployment.environment".to_string(), "prod".to_string()),
+        ]
+    );
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn builder_with_build_info() {
+    use tauri_plugin_tracing::{BuildInfo, BuildVariant, build_info};
+
+    let info = build_info!();
+    let variant = if cfg!(debug_assertions) {
+        BuildVariant::Debug
+    } else {
+        BuildVariant::Release
+    };
+    assert_eq!(info.variant, variant);
+    assert!(info.features.is_empty());
+
+    let builder = Builder::new()
+        .with_build_info(BuildInfo::new(BuildVariant::Release).with_git_hash("4f2c1e9"));
+    assert_eq!(
+        builder
+            .configured_build_info()
+            .and_then(|info| info.git_hash.as_deref()),
+        Some("4f2c1e9")
+    );
+    assert_eq!(builder.configured_build_variant(), BuildVariant::Release);
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn variant_targets_apply_to_the_build_variant_only() {
+    use tauri_plugin_tracing::{BuildInfo, BuildVariant};
+    use tracing::Level;
+
+    let builder = |variant| {
+        Builder::new()
+            .with_max_level(LevelFilter::WARN)
+            .with_target("my_app", LevelFilter::INFO)
+            .with_variant_target(BuildVariant::Debug, "my_app", LevelFilter::TRACE)
+            .with_variant_target(BuildVariant::Release, "my_app::sync", LevelFilter::ERROR)
+            .with_build_info(BuildInfo::new(variant))
+    };
+
+    let debug = builder(BuildVariant::Debug).build_filter();
+    assert!(debug.would_enable("my_app::db", &Level::TRACE));
+    assert!(debug.would_enable("my_app::sync", &Level::INFO));
+
+    let release = builder(BuildVariant::Release).build_filter();
+    assert!(!release.would_enable("my_app::db", &Level::DEBUG));
+    assert!(release.would_enable("my_app::db", &Level::INFO));
+    assert!(!release.would_enable("my_app::sync", &Level::WARN));
+    assert_eq!(
+        builder(BuildVariant::Release)
+            .configured_variant_targets()
+            .len(),
+        2
+    );
+}
+
+// ============================================================================
+// Error Chain Tests
+// ============================================================================
+
+/// An error with a cause, for checking that source chains are recorded.
+#[derive(Debug)]
+struct SyncError(std::io::Error);
+
+impl std::fmt::Display for SyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("sync failed")
+    }
+}
+
+impl std::error::Error for SyncError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn sync_error() -> SyncError {
+    SyncError(std::io::Error::new(
+        std::io::ErrorKind::ConnectionRefused,
+        "connection refused",
+    ))
+}
+
+#[test]
+fn trace_err_logs_error_with_origin_and_location() {
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tauri_plugin_tracing::{ResultExt, TRACED_ERROR_TARGET};
+
+    let events = with_captured_logs(|| {
+        let ok: Result<u8, SyncError> = Ok(1);
+        assert_eq!(ok.trace_err("my_app::sync").unwrap(), 1);
+        let failed: Result<u8, SyncError> = Err(sync_error());
+        assert!(failed.trace_err("my_app::sync").is_err());
+    });
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.level, tracing::Level::ERROR);
+    assert_eq!(event.target, TRACED_ERROR_TARGET);
+    assert_eq!(event.message, "sync failed");
+    assert_eq!(event.field("origin"), Some("my_app::sync"));
+    assert!(event.field("location").unwrap().contains("integration.rs"));
+}
+
+#[test]
+fn rfc5424_format_records_error_sources() {
+    use tauri_plugin_tracing::Rfc5424Format;
+
+    let lines = rfc5424_lines(Rfc5424Format::new("app"), || {
+        let error = sync_error();
+        tracing::error!(error = &error as &dyn std::error::Error, "sync stopped");
+    });
+
+    assert_eq!(lines.len(), 1);
+    assert!(
+        lines[0].contains(r#"error="sync failed" error.source.1="connection refused""#),
+        "{}",
+        lines[0]
+    );
+}
+
+// ============================================================================
+// Configuration Validation Tests
+// ============================================================================
+
+#[test]
+fn validate_accepts_default_configuration() {
+    let config = Builder::new().with_file_logging().validate().unwrap();
+    assert!(config.warnings().is_empty());
+}
+
+#[test]
+fn validate_warns_about_ignored_options() {
+    use tauri_plugin_tracing::ConfigIssue;
+
+    let config = Builder::new()
+        .with_max_file_size(MaxFileSize::mb(10))
+        .target(Target::Pipe { handle: None })
+        .target(Target::Pipe { handle: Some(3) })
+        .filter_for(
+            Target::Stderr,
+            tauri_plugin_tracing::tracing_subscriber::filter::Targets::new(),
+        )
+        .validate()
+        .unwrap();
+    let split = Builder::new()
+        .targets([Target::Stderr])
+        .with_stderr_threshold(LevelFilter::WARN)
+        .validate()
+        .unwrap();
+
+    let warnings = config.warnings();
+    assert!(warnings.iter().all(|issue| !issue.is_error()));
+    assert!(warnings.iter().any(|issue| matches!(
+        issue,
+        ConfigIssue::FileOptionWithoutFileTarget("with_max_file_size")
+    )));
+    assert!(warnings.iter().any(|issue| matches!(
+        issue,
+        ConfigIssue::IgnoredTarget(Target::Pipe { handle: Some(3) })
+    )));
+    assert!(
+        warnings
+            .iter()
+            .any(|issue| matches!(issue, ConfigIssue::FilterForMissingTarget(Target::Stderr)))
+    );
+    assert!(
+        warnings
+            .iter()
+            .any(|issue| matches!(issue, ConfigIssue::PipeSharesStdout))
+    );
+    assert!(matches!(
+        split.warnings(),
+        [ConfigIssue::StderrThresholdWithoutStdout]
+    ));
+}
+
+#[test]
+fn validate_rejects_keeping_no_files_and_unusable_folders() {
+    use tauri_plugin_tracing::ConfigIssue;
+
+    let file = std::env::temp_dir().join("tauri-tracing-validate-file");
+    std::fs::write(&file, "").unwrap();
+
+    let error = Builder::new()
+        .target(Target::Folder {
+            path: file.join("logs"),
+            file_name: None,
+        })
+        .with_rotation_strategy(RotationStrategy::KeepSome(0))
+        .validate()
+        .unwrap_err();
+    let _ = std::fs::remove_file(&file);
+
+    assert_eq!(error.errors().count(), 2);
+    assert!(
+        error
+            .issues()
+            .iter()
+            .any(|issue| matches!(issue, ConfigIssue::KeepNoFiles))
+    );
+    assert!(error.issues().iter().any(
+        |issue| matches!(issue, ConfigIssue::UnwritableFolder { path, .. } if path.ends_with("logs"))
+    ));
+    assert!(
+        error
+            .to_string()
+            .starts_with("invalid tracing configuration: ")
+    );
+}
+
+#[test]
+fn validate_rejects_unusable_file_name_templates() {
+    use tauri_plugin_tracing::ConfigIssue;
+
+    for template in ["{prefix}-{date}.log", "{prefix}.{date}.{host}.log"] {
+        let error = Builder::new()
+            .with_file_logging()
+            .with_file_name_template(template)
+            .validate()
+            .unwrap_err();
+        assert!(error.errors().any(|issue| matches!(
+            issue,
+            ConfigIssue::InvalidFileNameTemplate { template: t, .. } if t == template
+        )));
+    }
+}
+
+// ============================================================================
+// Pipe Target Tests
+// ============================================================================
+
+#[test]
+fn builder_with_pipe_target() {
+    let builder = Builder::new()
+        .clear_targets()
+        .target(Target::Pipe { handle: None });
+    assert!(
+        builder
+            .configured_targets()
+            .iter()
+            .any(|t| matches!(t, Target::Pipe { handle: None }))
+    );
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn pipe_layer_writes_length_prefixed_json() {
+    use std::io::Read;
+    use tauri_plugin_tracing::PipeLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (mut reader, writer) = std::io::pipe().unwrap();
+    let subscriber = tracing_subscriber::registry().with(PipeLayer::new(writer).unwrap());
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("job");
+        let _enter = span.enter();
+        tracing::warn!(target: "app::worker", rows = 3, "line one\nline two");
+        tracing::info!(target: "app::worker", "done");
+    });
+
+    let mut read_record = || {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len).unwrap();
+        let mut record = vec![0; u32::from_be_bytes(len) as usize];
+        reader.read_exact(&mut record).unwrap();
+        serde_json::from_slice::<serde_json::Value>(&record).unwrap()
+    };
+    let first = read_record();
+    assert_eq!(first["level"], "WARN");
+    assert_eq!(first["target"], "app::worker");
+    assert_eq!(first["message"], "line one\nline two");
+    assert_eq!(first["spans"], serde_json::json!(["job"]));
+    assert_eq!(first["fields"]["rows"], 3);
+    assert!(first["timestamp"].as_u64().unwrap() > 0);
+    let second = read_record();
+    assert_eq!(second["message"], "done");
+    assert!(second.get("fields").is_none());
+}
+
+// ============================================================================
+// GELF Target Tests
+// ============================================================================
+
+#[test]
+fn builder_with_gelf_target() {
+    use tauri_plugin_tracing::GelfTransport;
+
+    let builder = Builder::new().target(Target::Gelf {
+        host: "127.0.0.1".to_string(),
+        port: 12201,
+        transport: GelfTransport::Udp,
+    });
+    assert!(
+        builder
+            .configured_targets()
+            .iter()
+            .any(|t| matches!(t, Target::Gelf { port: 12201, .. }))
+    );
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn gelf_layer_sends_udp_message_with_fields() {
+    use std::net::UdpSocket;
+    use std::time::Duration;
+    use tauri_plugin_tracing::{GelfLayer, GelfTransport};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+    collector
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let port = collector.local_addr().unwrap().port();
+
+    let layer = GelfLayer::new("127.0.0.1", port, GelfTransport::Udp)
+        .unwrap()
+        .with_host("test-host")
+        .with_attributes([("service.name", "test-app"), ("rows", "attribute")]);
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("import");
+        let _enter = span.enter();
+        tracing::warn!(target: "app::db", rows = 3, ratio = 0.5, ok = true, id = "x", "slow query");
+    });
+
+    let mut buf = [0u8; 8192];
+    let len = collector.recv(&mut buf).unwrap();
+    let message: serde_json::Value = serde_json::from_slice(&buf[..len]).unwrap();
+
+    assert_eq!(message["version"], "1.1");
+    assert_eq!(message["host"], "test-host");
+    assert_eq!(message["short_message"], "slow query");
+    assert_eq!(message["level"], 4);
+    assert_eq!(message["_target"], "app::db");
+    assert_eq!(message["_spans"], "import");
+    assert_eq!(message["_rows"], 3);
+    assert_eq!(message["_service.name"], "test-app");
+    assert_eq!(message["_ratio"], 0.5);
+    assert_eq!(message["_ok"], "true");
+    // `_id` is reserved by Graylog
+    assert_eq!(message["_id_"], "x");
+    assert!(message.get("_id").is_none());
+    assert!(message["timestamp"].as_f64().unwrap() > 0.0);
+}
+
+#[test]
+fn gelf_layer_chunks_large_udp_messages() {
+    use std::net::UdpSocket;
+    use std::time::Duration;
+    use tauri_plugin_tracing::{GelfLayer, GelfTransport};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+    collector
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let port = collector.local_addr().unwrap().port();
+
+    let layer = GelfLayer::with_chunk_size("127.0.0.1", port, GelfTransport::Udp, 112).unwrap();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    let long = "x".repeat(500);
+    tracing::subscriber::with_default(subscriber, || tracing::info!("{long}"));
+
+    let mut chunks = Vec::new();
+    let mut buf = [0u8; 512];
+    loop {
+        let len = collector.recv(&mut buf).unwrap();
+        assert!(len <= 112);
+        assert_eq!(&buf[..2], &[0x1e, 0x0f]);
+        let count = buf[11] as usize;
+        chunks.push((buf[10], buf[2..10].to_vec(), buf[12..len].to_vec()));
+        if chunks.len() == count {
+            break;
+        }
+    }
+
+    // All chunks share one message id
+    assert!(chunks.iter().all(|(_, id, _)| *id == chunks[0].1));
+    chunks.sort_by_key(|(sequence, _, _)| *sequence);
+    let payload: Vec<u8> = chunks.into_iter().flat_map(|(_, _, data)| data).collect();
+    let message: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+    assert_eq!(message["short_message"], long);
+}
+
+#[test]
+fn gelf_layer_sends_null_delimited_tcp_messages() {
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use tauri_plugin_tracing::{GelfLayer, GelfTransport};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let layer = GelfLayer::new("127.0.0.1", port, GelfTransport::Tcp).unwrap();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::error!("first");
+        tracing::info!("second");
+    });
+
+    let (mut stream, _) = listener.accept().unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut received = Vec::new();
+    let mut buf = [0u8; 1024];
+    while received.iter().filter(|b| **b == 0).count() < 2 {
+        let len = stream.read(&mut buf).unwrap();
+        assert!(len > 0);
+        received.extend_from_slice(&buf[..len]);
+    }
+
+    let messages: Vec<serde_json::Value> = received
+        .split(|b| *b == 0)
+        .filter(|frame| !frame.is_empty())
+        .map(|frame| serde_json::from_slice(frame).unwrap())
+        .collect();
+    assert_eq!(messages[0]["short_message"], "first");
+    assert_eq!(messages[0]["level"], 3);
+    assert_eq!(messages[1]["short_message"], "second");
+    assert_eq!(messages[1]["level"], 6);
+}
+
+// ============================================================================
+// Loki Target Tests
+// ============================================================================
+
+#[test]
+fn loki_layer_pushes_labeled_streams() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use tauri_plugin_tracing::LokiLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/loki/api/v1/push", listener.local_addr().unwrap());
+
+    let layer = LokiLayer::new(url, [("app", "test-app")])
+        .unwrap()
+        .with_attributes([("deployment.environment", "test"), ("rows", "attribute")]);
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("log", w = %"main");
+        let _enter = span.enter();
+        tracing::warn!(target: "app::db", rows = 3, "slow query");
+    });
+
+    let (mut stream, _) = listener.accept().unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    assert!(request_line.starts_with("POST /loki/api/v1/push "));
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        if header == "\r\n" {
+            break;
+        }
+        if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+            content_length = value.trim().parse().unwrap();
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    stream
+        .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+        .unwrap();
+
+    let push: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let pushed = &push["streams"][0];
+    assert_eq!(pushed["stream"]["app"], "test-app");
+    assert_eq!(pushed["stream"]["level"], "warn");
+    assert_eq!(pushed["stream"]["window"], "main");
+    let timestamp = pushed["values"][0][0].as_str().unwrap();
+    assert!(timestamp.parse::<u128>().is_ok());
+    let line: serde_json::Value =
+        serde_json::from_str(pushed["values"][0][1].as_str().unwrap()).unwrap();
+    assert_eq!(line["message"], "slow query");
+    assert_eq!(line["target"], "app::db");
+    assert_eq!(line["spans"], "log");
+    assert_eq!(line["rows"], 3);
+    assert_eq!(line["deployment.environment"], "test");
+}
+
+// ============================================================================
+// OTLP Target Tests
+// ============================================================================
+
+#[test]
+fn otlp_layer_exports_log_records() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use tauri_plugin_tracing::OtlpLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/v1/logs", listener.local_addr().unwrap());
+
+    let layer = OtlpLayer::new(
+        url,
+        [("Authorization", "Bearer secret")],
+        [
+            ("service.name", "default"),
+            ("service.version", "1.2.3"),
+            ("service.name", "test-app"),
+        ],
+    )
+    .unwrap();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("log", w = %"main");
+        let _enter = span.enter();
+        tracing::warn!(target: "app::db", rows = 3, ratio = 0.5, cached = false, "slow query");
+    });
+
+    let (mut stream, _) = listener.accept().unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    assert!(request_line.starts_with("POST /v1/logs "));
+    let mut content_length = 0;
+    let mut authorization = None;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        if header == "\r\n" {
+            break;
+        }
+        let lower = header.to_ascii_lowercase();
+        if let Some(value) = lower.strip_prefix("content-length:") {
+            content_length = value.trim().parse().unwrap();
+        }
+        if lower.starts_with("authorization:") {
+            authorization = Some(header.trim_end().to_string());
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+    assert_eq!(
+        authorization.as_deref(),
+        Some("Authorization: Bearer secret")
+    );
+
+    let export: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let resource_logs = &export["resourceLogs"][0];
+    let attribute = |attributes: &serde_json::Value, key: &str| {
+        attributes
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|attribute| attribute["key"] == key)
+            .map(|attribute| attribute["value"].clone())
+    };
+    let resource = &resource_logs["resource"]["attributes"];
+    assert_eq!(resource.as_array().unwrap().len(), 2);
+    assert_eq!(
+        attribute(resource, "service.name").unwrap()["stringValue"],
+        "test-app"
+    );
+    let scope = &resource_logs["scopeLogs"][0];
+    assert_eq!(scope["scope"]["name"], "app::db");
+    let record = &scope["logRecords"][0];
+    assert_eq!(record["severityNumber"], 13);
+    assert_eq!(record["severityText"], "WARN");
+    assert_eq!(record["body"]["stringValue"], "slow query");
+    assert!(
+        record["timeUnixNano"]
+            .as_str()
+            .unwrap()
+            .parse::<u128>()
+            .is_ok()
+    );
+    let attributes = &record["attributes"];
+    assert_eq!(
+        attribute(attributes, "code.namespace").unwrap()["stringValue"],
+        "app::db"
+    );
+    assert_eq!(
+        attribute(attributes, "tauri.window").unwrap()["stringValue"],
+        "main"
+    );
+    assert_eq!(attribute(attributes, "rows").unwrap()["intValue"], "3");
+    assert_eq!(attribute(attributes, "ratio").unwrap()["doubleValue"], 0.5);
+    assert_eq!(attribute(attributes, "cached").unwrap()["boolValue"], false);
+}
+
+// ============================================================================
+// JSON Schema Tests
+// ============================================================================
+
+#[test]
+fn json_schema_describes_records_and_results() {
+    use tauri_plugin_tracing::{LogLevel, RecordPayload, json_schema};
+
+    let schema = json_schema();
+    let definitions = &schema["definitions"];
+    for name in [
+        "RecordPayload",
+        "LogRecord",
+        "LogRecordBatch",
+        "MarkContext",
+        "LogStats",
+        "EffectiveFilter",
+    ] {
+        assert!(definitions[name].is_object(), "{name} is not defined");
+    }
+    assert_eq!(
+        definitions["LogLevel"]["enum"],
+        serde_json::json!([1, 2, 3, 4, 5])
+    );
+    assert_eq!(
+        definitions["LogRecordBatch"]["properties"]["records"]["items"]["$ref"],
+        "#/definitions/LogRecord"
+    );
+
+    let payload = serde_json::to_value(RecordPayload {
+        message: "hello".into(),
+        level: LogLevel::Info,
+        fields: [("code".to_string(), "7".to_string())].into(),
+        breadcrumbs: Some(Vec::new()),
+        seq: Some(1),
+        gap: Some(2),
+    })
+    .unwrap();
+    let properties = definitions["RecordPayload"]["properties"]
+        .as_object()
+        .unwrap();
+    for key in payload.as_object().unwrap().keys() {
+        assert!(properties.contains_key(key), "{key} is not in the schema");
+    }
+}
+
+#[test]
+fn get_schema_command_returns_schema() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let schema =
+        invoke_command(&window, "plugin:tracing|get_schema", serde_json::json!({})).unwrap();
+    assert_eq!(schema, tauri_plugin_tracing::json_schema());
+}
+
+// ============================================================================
+// Diagnostic Report Tests
+// ============================================================================
+
+#[test]
+fn diagnostic_report_bundles_logs_config_and_screenshots() {
+    use std::io::Read;
+    use tauri::Manager;
+    use tauri_plugin_tracing::test::mock_app;
+    use tauri_plugin_tracing::{Attachment, DiagnosticReportOptions, create_diagnostic_report};
+
+    let log_dir = std::env::temp_dir().join(format!("tauri-tracing-diag-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&log_dir);
+    std::fs::create_dir_all(&log_dir).unwrap();
+    std::fs::write(log_dir.join("diag.2024-01-15.log"), "older\n").unwrap();
+    std::fs::write(log_dir.join("diag.2024-01-16.log"), "0123456789").unwrap();
+    std::fs::write(log_dir.join("other.log"), "not ours").unwrap();
+
+    let app = mock_app(
+        Builder::new()
+            .with_max_level(LevelFilter::DEBUG)
+            .clear_targets()
+            .target(Target::Folder {
+                path: log_dir.clone(),
+                file_name: Some("diag".to_string()),
+            }),
+    )
+    .unwrap();
+
+    let path = create_diagnostic_report(
+        app.app_handle(),
+        DiagnosticReportOptions {
+            description: Some("sync stopped".to_string()),
+            screenshots: vec![Attachment {
+                name: "../screen.png".to_string(),
+                bytes: vec![1, 2, 3],
+            }],
+            max_log_bytes: Some(12),
+            output_dir: Some(log_dir.join("reports")),
+        },
+    )
+    .unwrap();
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let mut read = |name: &str| {
+        let mut contents = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        contents
+    };
+
+    let manifest: serde_json::Value = serde_json::from_str(&read("manifest.json")).unwrap();
+    let files: Vec<&str> = manifest["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["path"].as_str().unwrap())
+        .collect();
+    assert!(files.contains(&"system.json"));
+    assert!(files.contains(&"invariants.json"));
+    assert!(files.contains(&"screenshots/screen.png"));
+    assert!(!files.contains(&"logs/other.log"));
+
+    let config: serde_json::Value = serde_json::from_str(&read("config.json")).unwrap();
+    assert_eq!(config["level"], "debug");
+    assert_eq!(read("description.txt"), "sync stopped");
+
+    // Both logs were written in the same instant, so only the total size is known
+    let logs: usize = files
+        .iter()
+        .filter(|file| file.starts_with("logs/diag."))
+        .map(|file| read(file).len())
+        .sum();
+    assert_eq!(logs, 12);
+
+    let _ = std::fs::remove_dir_all(&log_dir);
+}
+
+#[test]
+fn signed_diagnostic_report_detects_modified_logs() {
+    use std::io::{Read, Write};
+    use tauri::Manager;
+    use tauri_plugin_tracing::test::mock_app;
+    use tauri_plugin_tracing::{
+        DiagnosticReportOptions, SignatureStatus, create_diagnostic_report, verify_log_bundle,
+    };
+
+    let dir = std::env::temp_dir().join(format!("tauri-tracing-verify-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let app = mock_app(Builder::new().with_report_signing_key("s3cret")).unwrap();
+    let path = create_diagnostic_report(
+        app.app_handle(),
+        DiagnosticReportOptions {
+            description: Some("sync stopped".to_string()),
+            output_dir: Some(dir.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let verification = verify_log_bundle(&path, Some(b"s3cret")).unwrap();
+    assert!(verification.is_intact(), "{verification:?}");
+    assert_eq!(verification.signature, SignatureStatus::Valid);
+    assert_eq!(
+        verify_log_bundle(&path, Some(b"guess")).unwrap().signature,
+        SignatureStatus::Invalid
+    );
+    assert!(verify_log_bundle(&path, None).unwrap().is_intact());
+
+    // Rewrite the report with an edited description and an extra file
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let mut files = Vec::new();
+    for n in 0..archive.len() {
+        let mut file = archive.by_index(n).unwrap();
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).unwrap();
+        files.push((file.name().to_string(), bytes));
+    }
+    let edited = dir.join("edited.zip");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&edited).unwrap());
+    for (name, bytes) in files {
+        let bytes = if name == "description.txt" {
+            b"works fine".to_vec()
+        } else {
+            bytes
+        };
+        zip.start_file(name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&bytes).unwrap();
+    }
+    zip.start_file("logs/extra.log", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    zip.finish().unwrap();
+
+    let verification = verify_log_bundle(&edited, Some(b"s3cret")).unwrap();
+    assert!(!verification.is_intact());
+    assert_eq!(verification.modified, ["description.txt"]);
+    assert_eq!(verification.unlisted, ["logs/extra.log"]);
+    assert!(verification.missing.is_empty());
+    assert_eq!(verification.signature, SignatureStatus::Valid);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ============================================================================
+// Diagnostics Endpoint Tests
+// ============================================================================
+
+#[test]
+fn diagnostics_endpoint_requires_a_token() {
+    use std::io::{Read, Write};
+    use tauri::Manager;
+    use tauri_plugin_tracing::DiagnosticsEndpoint;
+    use tauri_plugin_tracing::test::mock_app;
+
+    let app = mock_app(Builder::new().with_diagnostics_endpoint("e2e-token")).unwrap();
+    let addr = app.state::<DiagnosticsEndpoint>().addr();
+    assert!(addr.ip().is_loopback());
+
+    let request = |method: &str, path: &str, token: &str, body: &str| {
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: {addr}\r\nAuthorization: Bearer {token}\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status: u16 = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (
+            status,
+            serde_json::from_str::<serde_json::Value>(body).unwrap(),
+        )
+    };
+
+    // A client that never sends its request does not hold up the others
+    let _stalled = std::net::TcpStream::connect(addr).unwrap();
+    let started = std::time::Instant::now();
+    let (status, _) = request("GET", "/health", "wrong", "");
+    assert_eq!(status, 401);
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+
+    let (status, health) = request("GET", "/health", "e2e-token", "");
+    assert_eq!(status, 200);
+    assert_eq!(health["status"], "ok");
+    assert_eq!(health["pid"], std::process::id());
+
+    let (status, tail) = request("GET", "/logs/tail?lines=5", "e2e-token", "");
+    assert_eq!(status, 200);
+    assert_eq!(tail["lines"], serde_json::json!([]));
+    let (status, _) = request(
+        "GET",
+        &format!("/logs/tail?lines={}", usize::MAX),
+        "e2e-token",
+        "",
+    );
+    assert_eq!(status, 200);
+
+    // The filter can only be changed with the default subscriber
+    let (status, _) = request("POST", "/filters", "e2e-token", r#"{"level": "debug"}"#);
+    assert_eq!(status, 503);
+    let (status, _) = request("DELETE", "/health", "e2e-token", "");
+    assert_eq!(status, 405);
+    let (status, _) = request("GET", "/metrics", "e2e-token", "");
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn validate_rejects_empty_endpoint_token() {
+    use tauri_plugin_tracing::ConfigIssue;
+
+    let error = Builder::new()
+        .with_diagnostics_endpoint("")
+        .validate()
+        .unwrap_err();
+    assert!(matches!(
+        error.errors().collect::<Vec<_>>()[..],
+        [ConfigIssue::EmptyEndpointToken]
+    ));
+}
+
+// ============================================================================
+// Invariant Tests
+// ============================================================================
+
+#[test]
+fn invariant_violations_are_logged_and_counted() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tauri_plugin_tracing::{INVARIANT_TARGET, invariant, invariant_violations};
+    use tracing::Level;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let items = 3;
+    let events = with_captured_logs(|| {
+        for _ in 0..2 {
+            invariant!(
+                items < 2,
+                "integration-cart-size",
+                items,
+                "cart is too large"
+            );
+        }
+        invariant!(items > 2, "integration-cart-held");
+        invoke_command(
+            &window,
+            "plugin:tracing|report_invariant_violation",
+            serde_json::json!({ "id": "integration-ui-state" }),
+        )
+        .unwrap();
+    });
+
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|e| e.level == Level::ERROR));
+    assert!(events.iter().all(|e| e.target == INVARIANT_TARGET));
+    assert_eq!(events[1].message, "cart is too large");
+    assert_eq!(events[1].field("invariant"), Some("integration-cart-size"));
+    assert_eq!(events[1].field("condition"), Some("items < 2"));
+    assert_eq!(events[1].field("violations"), Some("2"));
+    assert_eq!(events[1].field("items"), Some("3"));
+    assert_eq!(
+        events[2].message,
+        "invariant violated: integration-ui-state"
+    );
+    assert_eq!(events[2].field("w"), Some("main"));
+
+    let violations = invariant_violations();
+    let count = |id: &str| {
+        violations
+            .iter()
+            .find(|invariant| invariant.id == id)
+            .map(|invariant| invariant.violations)
+    };
+    assert_eq!(count("integration-cart-size"), Some(2));
+    assert_eq!(count("integration-ui-state"), Some(1));
+    assert_eq!(count("integration-cart-held"), None);
+}
+
+// ============================================================================
+// Progress Tests
+// ============================================================================
+
+#[test]
+fn progress_is_throttled_and_must_not_go_backwards() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tauri_plugin_tracing::{PROGRESS_TARGET, progress};
+    use tracing::Level;
+
+    let app = mock_app(Builder::new().with_progress_events()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+    let handle = app.handle();
+
+    let events = with_captured_logs(|| {
+        for n in 1..=5 {
+            progress(handle, "import", n, 10, Some("importing"));
+        }
+        progress(handle, "import", 3, 10, None);
+        invoke_command(
+            &window,
+            "plugin:tracing|progress",
+            serde_json::json!({ "taskId": "import", "current": 12, "total": 10 }),
+        )
+        .unwrap();
+    });
+
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|e| e.target == PROGRESS_TARGET));
+    assert_eq!(events[0].level, Level::INFO);
+    assert_eq!(events[0].message, "import: importing (1/10)");
+    assert_eq!(events[0].field("task"), Some("import"));
+    assert_eq!(events[1].level, Level::WARN);
+    assert_eq!(events[1].field("previous"), Some("5"));
+    assert_eq!(events[2].message, "import: done (10/10)");
+    assert_eq!(events[2].field("current"), Some("10"));
+}
+
+// ============================================================================
+// Metrics Tests
+// ============================================================================
+
+#[test]
+fn metrics_are_logged_at_trace() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tauri_plugin_tracing::{METRICS_TARGET, counter, gauge};
+    use tracing::Level;
+
+    let app =
+        mock_app(Builder::new().with_metrics_events(std::time::Duration::from_secs(1))).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        gauge("sync.queue_depth", 12.0);
+        counter("sync.retries", 2);
+        invoke_command(
+            &window,
+            "plugin:tracing|gauge",
+            serde_json::json!({ "name": "ui.fps", "value": 59.5 }),
+        )
+        .unwrap();
+        invoke_command(
+            &window,
+            "plugin:tracing|counter",
+            serde_json::json!({ "name": "ui.clicks", "inc": 1 }),
+        )
+        .unwrap();
+    });
+
+    assert_eq!(events.len(), 4);
+    assert!(
+        events
+            .iter()
+            .all(|e| e.target == METRICS_TARGET && e.level == Level::TRACE)
+    );
+    assert_eq!(events[0].message, "sync.queue_depth = 12");
+    assert_eq!(events[0].field("metric"), Some("sync.queue_depth"));
+    assert_eq!(events[1].field("inc"), Some("2"));
+    assert_eq!(events[2].field("value"), Some("59.5"));
+    assert_eq!(events[3].field("metric"), Some("ui.clicks"));
+}
+
+#[test]
+fn builder_with_metrics_events() {
+    let builder = Builder::new();
+    assert_eq!(builder.configured_metrics_interval(), None);
+
+    let builder = builder.with_metrics_events(std::time::Duration::from_millis(250));
+    assert_eq!(
+        builder.configured_metrics_interval(),
+        Some(std::time::Duration::from_millis(250))
+    );
+}
+
+// ============================================================================
+// Marker Tests
+// ============================================================================
+
+#[test]
+fn log_history_returns_records_around_latest_mark() {
+    use tauri_plugin_tracing::{LogHistory, MarkContext, mark};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let history = LogHistory::new(5);
+    let subscriber = tracing_subscriber::registry().with(history.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        mark("checkout");
+        tracing::info!("opened cart");
+        tracing::info!("loading cart");
+        mark("checkout");
+        tracing::warn!("payment slow");
+        tracing::info!("done");
+    });
+
+    assert_eq!(history.records().len(), 5);
+    let Some(MarkContext::Memory {
+        before,
+        mark,
+        after,
+    }) = history.around_mark("checkout", 1, 5)
+    else {
+        panic!("mark should be in the history");
+    };
+    let messages = |records: &[tauri_plugin_tracing::Breadcrumb]| {
+        records
+            .iter()
+            .map(|r| r.message.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(messages(&before), ["loading cart"]);
+    assert_eq!(mark.message, "checkout");
+    assert_eq!(messages(&after), ["payment slow", "done"]);
+    assert!(history.around_mark("missing", 1, 1).is_none());
+}
+
+#[test]
+fn mark_command_records_marker_event() {
+    use tauri_plugin_tracing::MARK_TARGET;
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|mark",
+            serde_json::json!({ "label": "save clicked" }),
+        )
+        .unwrap();
+    });
+    assert!(
+        events
+            .iter()
+            .any(|e| e.target == MARK_TARGET && e.message == "save clicked")
+    );
+
+    // Without the default subscriber there is neither history nor a file
+    let context = invoke_command(
+        &window,
+        "plugin:tracing|get_logs_around_mark",
+        serde_json::json!({ "label": "save clicked" }),
+    )
+    .unwrap();
+    assert!(context.is_null());
+}
+
+// ============================================================================
+// Noise Analysis Tests
+// ============================================================================
+
+#[test]
+fn noise_analyzer_recommends_noisy_third_party_crates() {
+    use std::time::Duration;
+    use tauri_plugin_tracing::{LogLevel, NoiseAnalyzer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let analyzer = NoiseAnalyzer::new();
+    let subscriber = tracing_subscriber::registry().with(analyzer.clone());
+    let emitter = std::thread::spawn(move || {
+        tracing::subscriber::with_default(subscriber, || {
+            std::thread::sleep(Duration::from_millis(50));
+            for _ in 0..20 {
+                tracing::debug!(target: "my_app::sync", "first-party");
+            }
+            for _ in 0..10 {
+                tracing::trace!(target: "chatty::pool", "checkout");
+            }
+            tracing::debug!(target: "wry::webview", "ipc");
+            tracing::warn!(target: "tao::event_loop", "only warnings");
+        })
+    });
+
+    let report = analyzer.analyze(Duration::from_millis(500), &["my_app"]);
+    emitter.join().unwrap();
+
+    assert_eq!(report.total_events, 32);
+    assert_eq!(report.crates[0].name, "my_app");
+    assert_eq!(report.crates[1].verbose_events, 10);
+    let recommended: Vec<_> = report
+        .recommendations
+        .iter()
+        .map(|r| (r.target.as_str(), r.events_removed))
+        .collect();
+    assert_eq!(recommended, [("chatty", 10), ("wry", 1)]);
+    assert!(matches!(report.recommendations[0].level, LogLevel::Warn));
+}
+
+#[test]
+fn noise_commands_require_default_subscriber() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let err = invoke_command(
+        &window,
+        "plugin:tracing|analyze_noise",
+        serde_json::json!({ "windowMs": 10 }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "Internal logger disabled and cannot be acquired or attached"
+    );
+}
+
+// ============================================================================
+// Sampling Tests
+// ============================================================================
+
+#[test]
+fn builder_with_sampling() {
+    use tauri_plugin_tracing::SampleRate;
+
+    let builder = Builder::new()
+        .with_sampling("my_app::render", SampleRate::OneIn(100))
+        .with_sampling("my_app::io", SampleRate::OneIn(10));
+    assert_eq!(
+        builder.configured_sampling(),
+        &[
+            ("my_app::render".to_string(), SampleRate::OneIn(100)),
+            ("my_app::io".to_string(), SampleRate::OneIn(10)),
+        ]
+    );
+    assert_eq!(builder.build_sampling_layer().rules().len(), 2);
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+#[test]
+fn sampling_layer_keeps_one_in_n_per_callsite() {
+    use tauri_plugin_tracing::test::CapturingLayer;
+    use tauri_plugin_tracing::{SampleRate, SamplingLayer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let capture = CapturingLayer::new();
+    let subscriber = tracing_subscriber::registry().with(capture.clone()).with(
+        SamplingLayer::new()
+            .with_rule("render", SampleRate::OneIn(10))
+            .with_rule("render::hot", SampleRate::OneIn(50)),
+    );
+
+    tracing::subscriber::with_default(subscriber, || {
+        for i in 0..100 {
+            tracing::info!(target: "render", i, "frame");
+            tracing::info!(target: "render::hot", i, "hot frame");
+            tracing::info!(target: "renderer", i, "not a submodule");
+            tracing::warn!(target: "render", i, "never sampled");
+        }
+    });
+
+    let events = capture.take();
+    let count = |message: &str| events.iter().filter(|e| e.message == message).count();
+    assert_eq!(count("frame"), 10);
+    assert_eq!(count("hot frame"), 2);
+    assert_eq!(count("not a submodule"), 100);
+    assert_eq!(count("never sampled"), 100);
+
+    // Sampling is deterministic: the first of every N events is kept
+    let kept: Vec<_> = events
+        .iter()
+        .filter(|e| e.message == "frame")
+        .map(|e| e.field("i").unwrap().to_string())
+        .collect();
+    assert_eq!(kept[..3], ["0", "10", "20"]);
+}
+
+#[test]
+fn sampled_fields_appends_sample_rate() {
+    use tauri_plugin_tracing::{SampleRate, SampledFields, SamplingLayer};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .without_time()
+                .fmt_fields(SampledFields::new(
+                    tracing_subscriber::fmt::format::DefaultFields::new(),
+                ))
+                .with_writer(buffer.clone()),
+        )
+        .with(SamplingLayer::new().with_rule("render", SampleRate::OneIn(100)));
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(target: "render", "frame");
+        tracing::info!(target: "other", "plain");
+    });
+
+    let output = buffer.contents();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("frame sampled=1/100"), "{}", lines[0]);
+    assert!(lines[1].ends_with("plain"), "{}", lines[1]);
+}
+
+#[test]
+fn sample_rate_does_not_leak_past_rejected_events() {
+    use std::sync::{Arc, Mutex};
+    use tauri_plugin_tracing::{SampleRate, SamplingLayer, current_sample_rate};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+
+    /// Records the sample rate every event is written with.
+    #[derive(Clone, Default)]
+    struct RateLayer(Arc<Mutex<Vec<Option<SampleRate>>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for RateLayer {
+        fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            self.0.lock().unwrap().push(current_sample_rate());
+        }
+    }
+
+    /// Rejects events from `render::muted` after sampling kept them.
+    struct MuteLayer;
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for MuteLayer {
+        fn event_enabled(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) -> bool {
+            event.metadata().target() != "render::muted"
+        }
+    }
+
+    let rates = RateLayer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(rates.clone())
+        .with(MuteLayer)
+        .with(SamplingLayer::new().with_rule("render", SampleRate::OneIn(100)));
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(target: "render::muted", "frame");
+        tracing::info!(target: "other", "plain");
+        tracing::info!(target: "render", "frame");
+    });
+
+    // The muted event's rate is not attached to the next one
+    assert_eq!(
+        *rates.0.lock().unwrap(),
+        [None, Some(SampleRate::OneIn(100))]
+    );
+}
+
+// ============================================================================
+// Lock Instrumentation Tests
+// ============================================================================
+
+#[test]
+fn traced_mutex_warns_on_long_hold_with_owner_span() {
+    use std::time::Duration;
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tauri_plugin_tracing::{SYNC_DEBUG_TARGET, TracedMutex};
+
+    let mutex = TracedMutex::new("cache", 0).with_threshold(Duration::from_millis(5));
+    let events = with_captured_logs(|| {
+        let span = tracing::info_span!("refresh_cache");
+        let _enter = span.enter();
+        let mut guard = mutex.lock().unwrap();
+        *guard += 1;
+        std::thread::sleep(Duration::from_millis(10));
+    });
+
+    let warning = events
+        .iter()
+        .find(|e| e.field("held_ms").is_some())
+        .expect("long hold warning");
+    assert_eq!(warning.level, tracing::Level::WARN);
+    assert_eq!(warning.target, SYNC_DEBUG_TARGET);
+    assert_eq!(warning.field("lock"), Some("cache"));
+    assert_eq!(warning.field("owner_span"), Some("refresh_cache"));
+    assert_eq!(mutex.into_inner().unwrap(), 1);
+}
+
+#[test]
+fn traced_mutex_warns_on_long_wait_with_holder() {
+    use std::sync::{Arc, Barrier};
+    use std::time::Duration;
+    use tauri_plugin_tracing::TracedMutex;
+    use tauri_plugin_tracing::test::with_captured_logs;
+
+    let mutex = Arc::new(TracedMutex::new("db", ()).with_threshold(Duration::from_millis(5)));
+    let barrier = Arc::new(Barrier::new(2));
+
+    let holder = {
+        let mutex = mutex.clone();
+        let barrier = barrier.clone();
+        std::thread::Builder::new()
+            .name("db-writer".to_string())
+            .spawn(move || {
+                let span = tracing::info_span!("write_batch");
+                let _enter = span.enter();
+                let _guard = mutex.lock().unwrap();
+                barrier.wait();
+                std::thread::sleep(Duration::from_millis(20));
+            })
+            .unwrap()
+    };
+
+    barrier.wait();
+    let events = with_captured_logs(|| drop(mutex.lock().unwrap()));
+    holder.join().unwrap();
+
+    let warning = events
+        .iter()
+        .find(|e| e.field("wait_ms").is_some())
+        .expect("long wait warning");
+    assert_eq!(warning.field("lock"), Some("db"));
+    assert_eq!(warning.field("owner_thread"), Some("db-writer"));
+}
+
+#[test]
+fn traced_rwlock_warns_only_past_threshold() {
+    use std::time::Duration;
+    use tauri_plugin_tracing::TracedRwLock;
+    use tauri_plugin_tracing::test::with_captured_logs;
+
+    let lock = TracedRwLock::new("settings", 1).with_threshold(Duration::from_millis(5));
+    let events = with_captured_logs(|| {
+        assert_eq!(*lock.read().unwrap(), 1);
+        *lock.write().unwrap() = 2;
+    });
+    assert!(events.is_empty(), "{events:?}");
+
+    let events = with_captured_logs(|| {
+        let _guard = lock.write().unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+    });
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].field("lock"), Some("settings"));
+    assert!(events[0].field("held_ms").is_some());
+}
+
+// ============================================================================
+// Webview Timestamp Tests
+// ============================================================================
+
+#[test]
+fn log_command_records_webview_timestamp() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({
+                "level": 3,
+                "message": ["clicked"],
+                "timestamp": 1_700_000_000_123.5,
+            }),
+        )
+        .unwrap();
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({ "level": 3, "message": ["untimed"] }),
+        )
+        .unwrap();
+    });
+
+    let clicked = events.iter().find(|e| e.message == "clicked").unwrap();
+    assert_eq!(
+        clicked.field("logical_time"),
+        Some("2023-11-14T22:13:20.1235Z")
+    );
+    let untimed = events.iter().find(|e| e.message == "untimed").unwrap();
+    assert_eq!(untimed.field("logical_time"), None);
+}
+
+#[test]
+fn log_batch_command_emits_records_in_order() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tracing::Level;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log_batch",
+            serde_json::json!({
+                "records": [
+                    { "level": 2, "message": ["first"], "timestamp": 1_700_000_000_000.0 },
+                    { "level": 5, "message": ["second"], "timestamp": 1_700_000_000_001.0 },
+                ]
+            }),
+        )
+        .unwrap();
+    });
+
+    let logged: Vec<_> = events.iter().filter(|e| e.in_span("log")).collect();
+    assert_eq!(logged.len(), 2);
+    assert_eq!(logged[0].message, "first");
+    assert_eq!(logged[0].level, Level::DEBUG);
+    assert_eq!(
+        logged[0].field("logical_time"),
+        Some("2023-11-14T22:13:20Z")
+    );
+    assert_eq!(logged[1].message, "second");
+    assert_eq!(logged[1].level, Level::ERROR);
+    assert_eq!(
+        logged[1].field("logical_time"),
+        Some("2023-11-14T22:13:20.001Z")
+    );
+}
+
+// ============================================================================
+// Clock Sync Tests
+// ============================================================================
+
+#[test]
+fn clock_sync_samples_are_monotonic() {
+    use tauri_plugin_tracing::ClockSync;
+
+    let clock = ClockSync::default();
+    let first = clock.sample();
+    let second = clock.sample();
+    assert!(second.monotonic_ms >= first.monotonic_ms);
+    let wall_delta = second.wall_ms - first.wall_ms;
+    let monotonic_delta = second.monotonic_ms - first.monotonic_ms;
+    assert!((wall_delta - monotonic_delta).abs() < 0.001);
+}
+
+#[test]
+fn clock_sync_offsets_are_per_window() {
+    use tauri_plugin_tracing::ClockSync;
+
+    let clock = ClockSync::default();
+    clock.set_offset("main", -4.0, 1.5);
+    assert_eq!(clock.offset("main"), Some(-4.0));
+    assert_eq!(clock.round_trip("main"), Some(1.5));
+    assert_eq!(clock.offset("other"), None);
+    assert_eq!(clock.to_backend_time("other", 100.0), 100.0);
+
+    clock.set_offset("main", f64::NAN, 1.0);
+    assert_eq!(clock.offset("main"), Some(-4.0));
+
+    clock.forget("main");
+    assert_eq!(clock.to_backend_time("main", 100.0), 100.0);
+}
+
+#[test]
+fn sync_clock_command_applies_offset_to_webview_timestamps() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let sample =
+        invoke_command(&window, "plugin:tracing|sync_clock", serde_json::json!({})).unwrap();
+    assert!(sample["monotonicMs"].as_f64().unwrap() >= 0.0);
+    assert!(sample["wallMs"].as_f64().unwrap() > 1_600_000_000_000.0);
+
+    invoke_command(
+        &window,
+        "plugin:tracing|sync_clock",
+        serde_json::json!({ "offsetMs": 250.0, "roundTripMs": 0.5 }),
+    )
+    .unwrap();
+
+    let events = with_captured_logs(|| {
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({
+                "level": 3,
+                "message": ["synced"],
+                "timestamp": 1_700_000_000_000.0,
+            }),
+        )
+        .unwrap();
+        invoke_command(
+            &window,
+            "plugin:tracing|report_frame_times",
+            serde_json::json!({
+                "report": {
+                    "frameTimesMs": [80.0],
+                    "longFrames": [{ "timestampMs": 1_700_000_000_000.0, "durationMs": 80.0 }],
+                }
+            }),
+        )
+        .unwrap();
+    });
+
+    let synced = events.iter().find(|e| e.message == "synced").unwrap();
+    assert_eq!(
+        synced.field("logical_time"),
+        Some("2023-11-14T22:13:20.25Z")
+    );
+    let frame = events
+        .iter()
+        .find(|e| e.field("duration_ms").is_some())
+        .unwrap();
+    assert_eq!(frame.field("timestamp_ms"), Some("1700000000250.0"));
+}
+
+// ============================================================================
+// Rotation Hook Tests
+// ============================================================================
+
+#[test]
+fn builder_on_rotation() {
+    let builder = Builder::new().with_file_logging();
+    assert!(!builder.has_rotation_hook());
+
+    let builder = builder.on_rotation(|_old_path, _new_path| {});
+    assert!(builder.has_rotation_hook());
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Breadcrumb Tests
+// ============================================================================
+
+#[test]
+fn builder_with_breadcrumbs() {
+    let builder = Builder::new();
+    assert_eq!(builder.configured_breadcrumbs(), None);
+
+    let builder = builder.with_breadcrumbs(20);
+    assert_eq!(builder.configured_breadcrumbs(), Some(20));
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn breadcrumbs_attached_to_error_payloads() {
+    use tauri_plugin_tracing::BreadcrumbLayer;
+    use tauri_plugin_tracing::test::capturing_webview_layer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (layer, records) = capturing_webview_layer();
+    let subscriber = tracing_subscriber::registry()
+        .with(layer)
+        .with(BreadcrumbLayer::new(2));
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::trace!("too verbose for breadcrumbs");
+        tracing::debug!("opening project");
+        tracing::info!(target: "my_app::sync", "syncing");
+        tracing::warn!("retrying");
+        tracing::error!("sync failed");
+    });
+
+    let records = records.take();
+    assert_eq!(records.len(), 5);
+    assert!(records[..4].iter().all(|r| r.breadcrumbs.is_none()));
+
+    // Only the last two DEBUG+ events are kept, oldest first
+    let trail = records[4].breadcrumbs.as_ref().expect("breadcrumbs");
+    assert_eq!(trail.len(), 2);
+    assert_eq!(trail[0].message, "syncing");
+    assert_eq!(trail[0].target, "my_app::sync");
+    assert!(matches!(trail[0].level, LogLevel::Info));
+    assert_eq!(trail[1].message, "retrying");
+
+    // The payload carries the trail to the frontend
+    let json = serde_json::to_value(&records[4]).unwrap();
+    assert_eq!(json["breadcrumbs"][1]["message"], "retrying");
+    assert!(
+        serde_json::to_value(&records[3])
+            .unwrap()
+            .get("breadcrumbs")
+            .is_none()
+    );
+}
+
+// ============================================================================
+// Navigation Breadcrumb Tests
+// ============================================================================
+
+#[test]
+fn builder_with_navigation_breadcrumbs() {
+    let builder = Builder::new();
+    assert!(!builder.configured_navigation_breadcrumbs());
+
+    let builder = builder.with_navigation_breadcrumbs();
+    assert!(builder.configured_navigation_breadcrumbs());
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn navigation_breadcrumbs_log_page_loads_and_focus() {
+    use tauri::WindowEvent;
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tauri_plugin_tracing::{NAVIGATION_TARGET, NavigationBreadcrumbs};
+
+    let navigation = NavigationBreadcrumbs::default();
+    let events = with_captured_logs(|| {
+        navigation.page_loaded("main", "tauri://localhost/");
+        navigation.page_loaded("main", "tauri://localhost/settings");
+        navigation.page_loaded("main", "tauri://localhost/settings");
+        navigation.window_event("main", &WindowEvent::Focused(false));
+        navigation.window_event("main", &WindowEvent::Focused(true));
+        navigation.window_event("main", &WindowEvent::Destroyed);
+        navigation.page_loaded("main", "tauri://localhost/settings");
+    });
+
+    let messages: Vec<_> = events.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "loaded tauri://localhost/",
+            "navigated to tauri://localhost/settings",
+            "reloaded tauri://localhost/settings",
+            "window lost focus",
+            "window focused",
+            "window closed",
+            "loaded tauri://localhost/settings",
+        ]
+    );
+    assert!(events.iter().all(|e| e.target == NAVIGATION_TARGET));
+    assert!(events.iter().all(|e| e.level == tracing::Level::INFO));
+    assert!(events.iter().all(|e| e.field("w") == Some("main")));
+    assert_eq!(events[1].field("from"), Some("tauri://localhost/"));
+    assert_eq!(events[2].field("url"), Some("tauri://localhost/settings"));
+}
+
+#[test]
+fn set_current_route_logs_changes_and_opens_route_spans() {
+    use tauri::Manager;
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tauri_plugin_tracing::{CurrentRoutes, ROUTE_TARGET};
+
+    let builder = Builder::new().with_route_spans();
+    assert!(builder.configured_route_spans());
+    let app = mock_app(builder).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        for (route, message) in [
+            ("/settings", "opened"),
+            ("/settings", "again"),
+            ("/sync", "synced"),
+        ] {
+            invoke_command(
+                &window,
+                "plugin:tracing|set_current_route",
+                serde_json::json!({ "route": route }),
+            )
+            .unwrap();
+            invoke_command(
+                &window,
+                "plugin:tracing|log",
+                serde_json::json!({ "level": 3, "message": [message] }),
+            )
+            .unwrap();
+        }
+    });
+
+    let changes: Vec<_> = events.iter().filter(|e| e.target == ROUTE_TARGET).collect();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].message, "route set to /settings");
+    assert_eq!(changes[1].message, "route changed to /sync");
+    assert_eq!(changes[1].field("from"), Some("/settings"));
+    assert_eq!(changes[1].field("w"), Some("main"));
+
+    for message in ["opened", "again", "synced"] {
+        let record = events
+            .iter()
+            .find(|e| e.message == message)
+            .expect("log command event");
+        assert_eq!(record.spans, ["route", "log"]);
+    }
+    assert_eq!(
+        app.state::<CurrentRoutes>().route("main").as_deref(),
+        Some("/sync")
+    );
+}
+
+// ============================================================================
+// Launch Logging Tests
+// ============================================================================
+
+#[test]
+fn builder_with_launch_logging() {
+    let builder = Builder::new();
+    assert_eq!(builder.configured_launch_logging(), None);
+
+    let builder = builder.with_launch_logging(["--token"]);
+    assert_eq!(
+        builder.configured_launch_logging(),
+        Some(&["--token".to_string()][..])
+    );
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn launch_log_redacts_sensitive_args_and_url_parameters() {
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tauri_plugin_tracing::{LAUNCH_TARGET, LaunchLog};
+
+    let launch = LaunchLog::new(["--token"]);
+    let args = ["app", "--token", "s3cret", "--token=s3cret", "a.txt"].map(String::from);
+    assert_eq!(
+        launch.redact_args(&args),
+        ["app", "--token", "REDACTED", "--token=REDACTED", "a.txt"]
+    );
+
+    let url: tauri::Url = "my-app://auth?token=s3cret&next=home".parse().unwrap();
+    assert_eq!(
+        launch.redact_url(&url),
+        "my-app://auth?token=REDACTED&next=home"
+    );
+
+    let events = with_captured_logs(|| {
+        launch.log_forwarded_args(&args, "/home/user");
+        launch.log_deep_links(&[url]);
+    });
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|e| e.target == LAUNCH_TARGET));
+    assert_eq!(events[0].message, "second instance launched");
+    assert_eq!(events[0].field("cwd"), Some("/home/user"));
+    assert!(!events[0].field("args").unwrap().contains("s3cret"));
+    assert_eq!(
+        events[1].message,
+        "opened my-app://auth?token=REDACTED&next=home"
+    );
+}
+
+// ============================================================================
+// Update Logging Tests
+// ============================================================================
+
+#[test]
+fn builder_with_update_log_file() {
+    let builder = Builder::new();
+    assert_eq!(builder.configured_update_log_file(), None);
+
+    let builder = builder.with_update_log_file("updates");
+    assert_eq!(builder.configured_update_log_file(), Some("updates"));
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn update_log_records_lifecycle_and_progress() {
+    use tauri_plugin_tracing::test::with_captured_logs;
+    use tauri_plugin_tracing::{UPDATE_TARGET, UpdateLog};
+    use tracing::Level;
+
+    let log = UpdateLog::new();
+    let events = with_captured_logs(|| {
+        log.checking("1.0.0");
+        log.available("1.1.0");
+        // 25 chunks of 4%: the first chunk and every 10% are logged
+        for _ in 0..25 {
+            log.progress(40, Some(1000));
+        }
+        log.downloaded();
+        log.failed(&"signature mismatch");
+    });
+
+    assert!(events.iter().all(|e| e.target == UPDATE_TARGET));
+    let progress: Vec<_> = events
+        .iter()
+        .filter(|e| e.level == Level::DEBUG)
+        .map(|e| e.field("percent").unwrap())
+        .collect();
+    assert_eq!(
+        progress,
+        [
+            "4", "12", "20", "32", "40", "52", "60", "72", "80", "92", "100"
+        ]
+    );
+
+    let downloaded = events.iter().find(|e| e.message == "update downloaded");
+    assert_eq!(downloaded.unwrap().field("bytes"), Some("1000"));
+
+    let failed = events.last().unwrap();
+    assert_eq!(failed.level, Level::ERROR);
+    assert_eq!(failed.message, "update failed: signature mismatch");
+    assert_eq!(failed.field("version"), Some("1.1.0"));
+}
+
+// ============================================================================
+// Log Context Tests
+// ============================================================================
+
+#[test]
+fn context_fields_appends_log_context() {
+    use tauri_plugin_tracing::{ContextFields, LogContext};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let context = LogContext::new();
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .without_time()
+                .with_ansi(false)
+                .fmt_fields(ContextFields::new(
+                    tracing_subscriber::fmt::format::DefaultFields::new(),
+                ))
+                .with_writer(buffer.clone()),
+        )
+        .with(context.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("anonymous");
+        context.set("user_id", "42");
+        context.set("workspace", "acme");
+        context.set("user_id", "43");
+        tracing::info!(rows = 3, "signed in");
+        assert_eq!(context.remove("workspace").as_deref(), Some("acme"));
+        tracing::info!("switched");
+        context.clear();
+        tracing::info!("signed out");
+    });
+
+    let output = buffer.contents();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].ends_with("anonymous"), "{}", lines[0]);
+    assert!(
+        lines[1].ends_with("signed in rows=3 user_id=43 workspace=acme"),
+        "{}",
+        lines[1]
+    );
+    assert!(lines[2].ends_with("switched user_id=43"), "{}", lines[2]);
+    assert!(lines[3].ends_with("signed out"), "{}", lines[3]);
+    assert!(tauri_plugin_tracing::current_context().is_empty());
+}
+
+#[test]
+fn spawned_work_keeps_span_and_scoped_context() {
+    use tauri_plugin_tracing::{
+        ContextFields, LogContext, SuppressionLayer, block_in_place_traced, scoped_context,
+        spawn_blocking_traced, spawn_traced, suppressed,
+    };
+    use tracing::Instrument;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let context = LogContext::new();
+    context.set("user_id", "42");
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .without_time()
+                .with_ansi(false)
+                .fmt_fields(ContextFields::new(
+                    tracing_subscriber::fmt::format::DefaultFields::new(),
+                ))
+                .with_writer(buffer.clone()),
+        )
+        .with(context.clone())
+        .with(SuppressionLayer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        let work = async {
+            spawn_traced(async { tracing::info!("spawned") })
+                .await
+                .unwrap();
+            spawn_blocking_traced(|| tracing::info!("blocking"))
+                .await
+                .unwrap();
+            spawn_traced(async { block_in_place_traced(|| tracing::info!("in place")) })
+                .await
+                .unwrap();
+            suppressed(async {
+                spawn_traced(async { tracing::info!("suppressed") })
+                    .await
+                    .unwrap();
+            })
+            .await;
+        };
+        let work = work.instrument(tracing::info_span!("log", w = "main"));
+        tauri::async_runtime::block_on(scoped_context([("job", "sync"), ("user_id", "43")], work));
+        tracing::info!("done");
+    });
+
+    let output = buffer.contents();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 4, "{output}");
+    for (line, message) in lines.iter().zip(["spawned", "blocking", "in place"]) {
+        assert!(line.contains("log{w=\"main\"}"), "{line}");
+        assert!(
+            line.ends_with(&format!("{message} user_id=43 job=sync")),
+            "{line}"
+        );
+    }
+    assert!(lines[3].ends_with("done user_id=42"), "{}", lines[3]);
+}
+
+#[test]
+fn rfc5424_format_writes_log_context() {
+    use tauri_plugin_tracing::{LogContext, Rfc5424Format};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let context = LogContext::new();
+    context.set("user_id", "42");
+    context.set("rows", "ignored");
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .event_format(Rfc5424Format::new("app").with_hostname("host"))
+                .with_writer(buffer.clone()),
+        )
+        .with(context);
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(target: "plain", rows = 3, "fields");
+    });
+
+    let output = buffer.contents();
+    assert!(
+        output.ends_with(" plain [fields@32473 rows=\"3\" user_id=\"42\"] fields\n"),
+        "{output}"
+    );
+}
+
+#[test]
+fn builder_with_log_context() {
+    use tauri_plugin_tracing::LogContext;
+
+    let context = LogContext::new();
+    let builder = Builder::new().with_log_context(context.clone());
+    context.set("session_id", "8f3c");
+    assert_eq!(
+        builder.configured_log_context().entries(),
+        [("session_id".to_string(), "8f3c".to_string())]
+    );
+    let _plugin = builder.with_default_subscriber().build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Suppression Tests
+// ============================================================================
+
+#[test]
+fn suppressed_events_skip_all_layers() {
+    use tauri_plugin_tracing::test::{CapturingLayer, capturing_webview_layer};
+    use tauri_plugin_tracing::{SuppressionLayer, suppress_logs, suppressed, with_suppressed};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (webview_layer, records) = capturing_webview_layer();
+    let events = CapturingLayer::new();
+    let subscriber = tracing_subscriber::registry()
+        .with(events.clone())
+        .with(webview_layer)
+        .with(SuppressionLayer);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("before");
+        with_suppressed(|| tracing::info!("checking password"));
+        {
+            let _guard = suppress_logs();
+            tracing::warn!("checking license");
+        }
+        tauri::async_runtime::block_on(suppressed(async {
+            yield_once().await;
+            tracing::error!("inside suppressed future");
+        }));
+        tracing::info!("after");
+    });
+
+    let messages: Vec<_> = events.take().into_iter().map(|e| e.message).collect();
+    assert_eq!(messages, ["before", "after"]);
+    assert_eq!(records.messages(), ["before", "after"]);
+}
+
+/// Yields once, so the suppressed future is polled more than once.
+async fn yield_once() {
+    let mut yielded = false;
+    std::future::poll_fn(|cx| {
+        if yielded {
+            std::task::Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            std::task::Poll::Pending
+        }
+    })
+    .await;
+}
+
+// ============================================================================
+// Webview Span Tests
+// ============================================================================
+
+#[test]
+fn span_start_and_end_record_duration_and_error() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+    use tracing::Level;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let events = with_captured_logs(|| {
+        let outer = invoke_command(
+            &window,
+            "plugin:tracing|span_start",
+            serde_json::json!({ "name": "save-clicked" }),
+        )
+        .unwrap();
+        assert!(outer.is_object());
+        let inner = invoke_command(
+            &window,
+            "plugin:tracing|span_start",
+            serde_json::json!({ "name": "validate", "parent": outer }),
+        )
+        .unwrap();
+        // Nested spans share the trace id of their parent
+        assert_eq!(inner["traceId"], outer["traceId"]);
+
+        invoke_command(
+            &window,
+            "plugin:tracing|log",
+            serde_json::json!({ "level": 3, "message": ["checking"], "spanContext": inner }),
+        )
+        .unwrap();
+        invoke_command(
+            &window,
+            "plugin:tracing|span_end",
+            serde_json::json!({ "context": inner, "durationMs": 1.5, "error": "Error: invalid" }),
+        )
+        .unwrap();
+        invoke_command(
+            &window,
+            "plugin:tracing|span_end",
+            serde_json::json!({ "context": outer, "durationMs": 4.0 }),
+        )
+        .unwrap();
+
+        // Ended spans can't be ended again
+        let error = invoke_command(
+            &window,
+            "plugin:tracing|span_end",
+            serde_json::json!({ "context": outer }),
+        )
+        .unwrap_err();
+        assert!(error.as_str().unwrap().starts_with("Unknown span"));
+    });
+
+    let log = events
+        .iter()
+        .find(|e| e.message == "checking")
+        .expect("log event");
+    assert_eq!(log.spans, ["webview_span", "webview_span", "log"]);
+
+    let failed = events
+        .iter()
+        .find(|e| e.message == "span failed")
+        .expect("failure event");
+    assert_eq!(failed.level, Level::ERROR);
+    assert_eq!(failed.field("error"), Some("Error: invalid"));
+    assert_eq!(failed.field("duration_ms"), Some("1.5"));
+
+    let ended = events
+        .iter()
+        .find(|e| e.message == "span ended")
+        .expect("end event");
+    assert_eq!(ended.level, Level::DEBUG);
+    assert_eq!(ended.spans, ["webview_span"]);
+}
+
+// ============================================================================
+// Filter Statistics Tests
+// ============================================================================
+
+#[test]
+fn builder_with_filter_stats() {
+    let builder = Builder::new();
+    assert!(!builder.configured_filter_stats());
+
+    let builder = builder.with_filter_stats();
+    assert!(builder.configured_filter_stats());
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn filter_stats_require_the_option() {
+    use tauri::Manager;
+    use tauri_plugin_tracing::test::mock_app;
+
+    let app = mock_app(Builder::new()).unwrap();
+    assert!(matches!(
+        tauri_plugin_tracing::get_filter_stats(app.app_handle()),
+        Err(tauri_plugin_tracing::Error::FilterStatsDisabled)
+    ));
+}
+
+// ============================================================================
+// Log Tail Tests
+// ============================================================================
+
+#[test]
+fn tail_logs_without_a_file_target() {
+    use tauri::Manager;
+    use tauri_plugin_tracing::test::mock_app;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let tail = tauri_plugin_tracing::tail_logs(app.app_handle(), 100, true).unwrap();
+    assert_eq!(tail.path, None);
+    assert!(tail.lines.is_empty());
+    assert!(!tail.following);
+    tauri_plugin_tracing::stop_tail_logs(app.app_handle());
+}
+
+// ============================================================================
+// Notification Tests
+// ============================================================================
+
+#[test]
+fn builder_with_notifications() {
+    use tauri_plugin_tracing::Notifications;
+
+    let builder = Builder::new();
+    assert_eq!(builder.configured_notifications(), None);
+
+    let notifications =
+        Notifications::errors().with_rate_limit(5, std::time::Duration::from_secs(60));
+    let builder = builder.with_notifications(notifications);
+    assert_eq!(builder.configured_notifications(), Some(notifications));
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+#[test]
+fn notify_layer_emits_marked_events_and_errors_with_rate_limit() {
+    use std::sync::{Arc, Mutex};
+    use tauri::{Listener, Manager};
+    use tauri_plugin_tracing::test::mock_app;
+    use tauri_plugin_tracing::{NOTIFY_EVENT, Notifications, UserNotification};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let app = mock_app(Builder::new()).unwrap();
+    let notifications = Arc::new(Mutex::new(Vec::new()));
+    let sink = notifications.clone();
+    app.listen_any(NOTIFY_EVENT, move |event| {
+        let notification: UserNotification = serde_json::from_str(event.payload()).unwrap();
+        sink.lock().unwrap().push(notification);
+    });
+
+    let layer = Notifications::errors()
+        .with_rate_limit(3, std::time::Duration::from_secs(60))
+        .layer(app.app_handle().clone());
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("not marked");
+        tracing::warn!(notify = true, title = "Could not save", "the disk is full");
+        tracing::error!(notify = false, "opted out");
+        tracing::error!("first error");
+        tracing::error!("second error");
+        tracing::error!("over the limit");
+    });
+
+    let notifications = notifications.lock().unwrap();
+    let shown: Vec<_> = notifications
+        .iter()
+        .map(|n| (n.title.as_str(), n.body.as_str()))
+        .collect();
+    assert_eq!(
+        shown,
+        [
+            ("Could not save", "the disk is full"),
+            ("Error", "first error"),
+            ("Error", "second error"),
+        ]
+    );
+    assert!(matches!(notifications[0].level, LogLevel::Warn));
+    assert!(notifications.iter().all(|n| n.suppressed == 0));
+}
+
+// ============================================================================
+// Devtools Console Tests
+// ============================================================================
+
+#[test]
+fn builder_with_devtools_console() {
+    let builder = Builder::new();
+    assert!(!builder.configured_devtools_console());
+
+    let builder = builder.with_devtools_console();
+    assert!(builder.configured_devtools_console());
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Heartbeat Tests
+// ============================================================================
+
+#[test]
+fn builder_with_heartbeat() {
+    let builder = Builder::new();
+    assert_eq!(builder.configured_heartbeat_interval(), None);
+
+    let builder = builder.with_heartbeat(std::time::Duration::from_secs(10));
+    assert_eq!(
+        builder.configured_heartbeat_interval(),
+        Some(std::time::Duration::from_secs(10))
+    );
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Event Loop Watchdog Tests
+// ============================================================================
+
+#[test]
+fn builder_with_event_loop_watchdog() {
+    let builder = Builder::new();
+    assert_eq!(builder.configured_event_loop_threshold(), None);
+
+    let builder = builder.with_event_loop_watchdog(std::time::Duration::from_millis(250));
+    assert_eq!(
+        builder.configured_event_loop_threshold(),
+        Some(std::time::Duration::from_millis(250))
+    );
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Webview Crash Tests
+// ============================================================================
+
+#[test]
+fn webview_crash_payload_matches_the_guest_api() {
+    use tauri_plugin_tracing::{WebviewCrash, WebviewCrashReason};
+
+    let crash = WebviewCrash {
+        label: "main".to_string(),
+        reason: WebviewCrashReason::OutOfMemory,
+        code: Some(1),
+    };
+    assert_eq!(
+        serde_json::to_value(&crash).unwrap(),
+        serde_json::json!({ "label": "main", "reason": "outOfMemory", "code": 1 })
+    );
+
+    // Every reason is listed in the guest API's WebviewCrash type
+    let listener = std::fs::read_to_string(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../guest-js/listener.ts"),
+    )
+    .unwrap();
+    for reason in [
+        WebviewCrashReason::Crashed,
+        WebviewCrashReason::OutOfMemory,
+        WebviewCrashReason::Terminated,
+        WebviewCrashReason::Unresponsive,
+        WebviewCrashReason::OtherProcessFailed,
+    ] {
+        let name = serde_json::to_value(reason).unwrap();
+        let name = name.as_str().unwrap();
+        assert!(listener.contains(&format!("'{name}'")), "{name} is missing");
+    }
+}
+
+// ============================================================================
+// Unified Logging Tests
+// ============================================================================
+
+#[cfg(target_vendor = "apple")]
+#[test]
+fn os_log_layer_maps_targets_to_categories() {
+    use tauri_plugin_tracing::OsLogLayer;
+
+    let layer = OsLogLayer::new("com.example.app")
+        .with_category("my_app::db", "storage")
+        .with_category("my_app::db::cache", "cache");
+
+    assert_eq!(layer.subsystem(), "com.example.app");
+    assert_eq!(layer.category("my_app::sync"), "sync");
+    assert_eq!(layer.category("my_app::sync::queue"), "sync");
+    assert_eq!(layer.category("my_app"), "my_app");
+    assert_eq!(layer.category(""), "webview");
+    assert_eq!(layer.category("my_app::db::pool"), "storage");
+    assert_eq!(layer.category("my_app::db::cache"), "cache");
+    assert_eq!(layer.category("my_app::dbx"), "dbx");
+}
+
+// ============================================================================
+// Early Logging Tests
+// ============================================================================
+
+#[test]
+fn early_events_are_replayed_into_the_subscriber() {
+    use tauri_plugin_tracing::early;
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    early::init();
+    tracing::info!(rows = 3, "config parsed");
+    tracing::trace!("below the subscriber's level");
+    tracing::warn!(path = ?"/etc/app.toml", "config file missing");
+    let events = early::take().unwrap();
+    assert_eq!(events.len(), 3);
+    assert!(early::take().is_none());
+
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .without_time()
+            .with_ansi(false)
+            .with_writer(buffer.clone())
+            .with_filter(LevelFilter::INFO),
+    );
+    tracing::subscriber::with_default(subscriber, || events.replay());
+
+    let output = buffer.contents();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2, "{output}");
+    assert!(lines[0].ends_with("config parsed rows=3"), "{}", lines[0]);
+    assert!(lines[0].contains("integration:"), "{}", lines[0]);
+    assert!(
+        lines[1].ends_with(r#"config file missing path="/etc/app.toml""#),
+        "{}",
+        lines[1]
+    );
+}
+
+// ============================================================================
+// Event Catalog Tests
+// ============================================================================
+
+tauri_plugin_tracing::event_ids! {
+    E1001: ERROR = "db connection failed";
+    W2001: WARN = "sync retried";
+}
+
+#[test]
+fn log_id_records_the_event_id() {
+    use tauri_plugin_tracing::log_id;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let buffer = SharedBuffer::default();
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .without_time()
+            .with_ansi(false)
+            .with_target(false)
+            .with_writer(buffer.clone()),
+    );
+    tracing::subscriber::with_default(subscriber, || {
+        log_id!(E1001);
+        log_id!(W2001, attempt = 2, "sync retried after timeout");
+    });
+
+    let output = buffer.contents();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2, "{output}");
+    assert!(
+        lines[0].ends_with("ERROR db connection failed event_id=E1001"),
+        "{}",
+        lines[0]
+    );
+    assert!(
+        lines[1].ends_with("WARN sync retried after timeout event_id=W2001 attempt=2"),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn builder_with_event_catalog() {
+    use tauri_plugin_tracing::{ConfigIssue, EventId};
+
+    let builder = Builder::new().with_event_catalog([E1001, W2001]);
+    assert_eq!(builder.configured_event_catalog(), [E1001, W2001]);
+    assert!(builder.validate().unwrap().warnings().is_empty());
+
+    let reworded = EventId {
+        description: "database unreachable",
+        ..E1001
+    };
+    let config = Builder::new()
+        .with_event_catalog([E1001, W2001, reworded])
+        .validate()
+        .unwrap();
+    assert!(matches!(
+        config.warnings(),
+        [ConfigIssue::ConflictingEventId("E1001")]
+    ));
+}
+
+// ============================================================================
+// Test Mode Tests
+// ============================================================================
+
+#[test]
+fn test_capture_records_the_logs_of_each_test() {
+    use tauri_plugin_tracing::{Error, TEST_TARGET, TestCapture};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let dir = std::env::temp_dir().join("tauri-tracing-test-mode");
+    let _ = std::fs::remove_dir_all(&dir);
+    let capture = TestCapture::new(&dir);
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+    let logs = tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("before any test");
+        capture.begin("checkout: applies coupon");
+        assert!(capture.is_running("checkout: applies coupon"));
+        tracing::warn!(target: "app::cart", "coupon expired");
+        assert!(matches!(
+            capture.end("another test"),
+            Err(Error::UnknownTest(name)) if name == "another test"
+        ));
+        let logs = capture.end("checkout: applies coupon").unwrap();
+        tracing::info!("after the test");
+        logs
+    });
+
+    assert!(!capture.is_running("checkout: applies coupon"));
+    assert_eq!(logs.name, "checkout: applies coupon");
+    assert_eq!(logs.dropped, 0);
+    let messages: Vec<_> = logs.records.iter().map(|r| r.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "test started: checkout: applies coupon",
+            "coupon expired",
+            "test ended: checkout: applies coupon",
+        ]
+    );
+    assert_eq!(logs.records[0].target, TEST_TARGET);
+
+    let path = logs.path.unwrap();
+    assert_eq!(path, dir.join("checkout__applies_coupon.log"));
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<_> = contents.lines().collect();
+    assert_eq!(lines.len(), 3, "{contents}");
+    assert!(
+        lines[1].ends_with(" WARN app::cart: coupon expired"),
+        "{}",
+        lines[1]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn end_test_requires_test_mode() {
+    use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+
+    let app = mock_app(Builder::new()).unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let error = invoke_command(
+        &window,
+        "plugin:tracing|end_test",
+        serde_json::json!({ "name": "login" }),
+    )
+    .unwrap_err();
+    assert_eq!(error, "Test mode is not enabled");
+}
+
+#[test]
+fn builder_with_test_mode() {
+    let builder = Builder::new().with_default_subscriber().with_test_mode();
+    assert!(builder.configured_test_mode());
+    assert!(!Builder::new().configured_test_mode());
+    let _plugin = builder.build::<tauri::Wry>();
+}
+
+// ============================================================================
+// Guest API Tests
+// ============================================================================
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../..")
+}
+
+#[test]
+fn guest_js_only_invokes_known_commands() {
+    let root = repo_root();
+    let mut invoked = Vec::new();
+    for entry in std::fs::read_dir(root.join("guest-js")).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        if !name.ends_with(".ts") || name.ends_with(".test.ts") || name.ends_with(".bench.ts") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        for part in source.split("'plugin:tracing|").skip(1) {
+            let command = part.split('\'').next().unwrap();
+            invoked.push((name.clone(), command.to_string()));
+        }
+    }
+    assert!(!invoked.is_empty());
+
+    // Every command has an allow permission, generated or hand-written
+    let mut permissions = String::new();
+    for dir in ["permissions", "permissions/autogenerated/commands"] {
+        for entry in std::fs::read_dir(root.join(dir)).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                permissions.push_str(&std::fs::read_to_string(path).unwrap());
+            }
+        }
+    }
+    for (file, command) in invoked {
+        assert!(
+            permissions.contains(&format!("commands.allow = [\"{command}\"]")),
+            "guest-js/{file} invokes unknown command `{command}`"
+        );
+    }
+}
+
+#[test]
+fn guest_js_version_matches_crate() {
+    let root = repo_root();
+    let package: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(root.join("package.json")).unwrap()).unwrap();
+    let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    let crate_version = manifest
+        .lines()
+        .skip_while(|line| *line != "[package]")
+        .find_map(|line| line.strip_prefix("version = "))
+        .unwrap()
+        .trim_matches('"');
+    assert_eq!(package["version"], crate_version);
+}
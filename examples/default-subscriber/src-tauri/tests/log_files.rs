@@ -0,0 +1,54 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+//! Tests for log file stats and clearing.
+//!
+//! The file target belongs to the global subscriber, so these tests run in
+//! their own binary, sharing one app.
+
+use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window};
+use tauri_plugin_tracing::{Builder, LevelFilter, Target};
+
+#[test]
+fn log_stats_count_and_clear_rotated_files() {
+    let log_dir = std::env::temp_dir().join(format!("tauri-tracing-files-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&log_dir);
+    std::fs::create_dir_all(&log_dir).unwrap();
+    let rotated = log_dir.join("app.2000-01-01.log");
+    std::fs::write(&rotated, "rotated log\n").unwrap();
+    let unrelated = log_dir.join("notes.txt");
+    std::fs::write(&unrelated, "not a log").unwrap();
+
+    let app = mock_app(
+        Builder::new()
+            .with_max_level(LevelFilter::INFO)
+            .clear_targets()
+            .target(Target::Folder {
+                path: log_dir.clone(),
+                file_name: None,
+            })
+            .with_default_subscriber(),
+    )
+    .unwrap();
+    let window = mock_window(&app, "main").unwrap();
+
+    let stats = invoke_command(
+        &window,
+        "plugin:tracing|get_log_stats",
+        serde_json::json!({}),
+    )
+    .unwrap();
+    let current = stats["currentFile"].as_str().expect("current file");
+    assert!(current.contains("app.") && current != rotated.to_str().unwrap());
+    assert_eq!(stats["rotatedFiles"], 1);
+    assert!(stats["totalBytes"].as_u64().unwrap() >= 12);
+    assert!(stats["lastRotationMs"].is_number());
+
+    let stats =
+        invoke_command(&window, "plugin:tracing|clear_logs", serde_json::json!({})).unwrap();
+    assert_eq!(stats["rotatedFiles"], 0);
+    assert!(stats["lastRotationMs"].is_null());
+    assert!(!rotated.exists());
+    assert!(std::path::Path::new(current).exists());
+    assert!(unrelated.exists());
+
+    let _ = std::fs::remove_dir_all(&log_dir);
+}
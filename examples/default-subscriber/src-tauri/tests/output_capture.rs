@@ -0,0 +1,113 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+//! Tests for native stdout/stderr capture.
+//!
+//! Capture redirects process-wide file descriptors and needs a global
+//! subscriber, so these tests run in their own binary.
+
+#![cfg(unix)]
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri_plugin_tracing::{CAPTURED_STDERR_TARGET, CAPTURED_STDOUT_TARGET, OutputCapture};
+use tracing_subscriber::layer::SubscriberExt;
+
+type Captured = Arc<Mutex<Vec<(String, tracing::Level, String)>>>;
+
+struct Recorder(Captured);
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Recorder {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        struct Message(String);
+        impl tracing::field::Visit for Message {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = format!("{value:?}");
+                }
+            }
+        }
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        self.0.lock().unwrap().push((
+            event.metadata().target().to_string(),
+            *event.metadata().level(),
+            message.0,
+        ));
+    }
+}
+
+fn wait_for(captured: &Captured, target: &str, message: &str) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if captured
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(t, _, m)| t == target && m == message)
+        {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+#[test]
+fn output_capture_reemits_lines_as_events() {
+    let captured = Captured::default();
+    tracing::subscriber::set_global_default(
+        tracing_subscriber::registry().with(Recorder(captured.clone())),
+    )
+    .unwrap();
+
+    let capture = OutputCapture::start().unwrap();
+
+    // Write to the descriptors directly, bypassing the test harness capture
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(b"native stdout line\n").unwrap();
+    stdout.flush().unwrap();
+    drop(stdout);
+    std::io::stderr()
+        .write_all(b"native stderr line\r\n")
+        .unwrap();
+
+    assert!(wait_for(
+        &captured,
+        CAPTURED_STDOUT_TARGET,
+        "native stdout line"
+    ));
+    assert!(wait_for(
+        &captured,
+        CAPTURED_STDERR_TARGET,
+        "native stderr line"
+    ));
+    assert!(
+        captured
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(t, level, m)| t == CAPTURED_STDERR_TARGET
+                && *level == tracing::Level::WARN
+                && m == "native stderr line")
+    );
+
+    // The original streams remain writable and are not captured
+    let mut original = capture.stdout_writer();
+    writeln!(original.as_ref(), "written to original stdout").unwrap();
+    original.flush().unwrap();
+
+    drop(capture);
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(
+        !captured
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(_, _, m)| m == "written to original stdout")
+    );
+}
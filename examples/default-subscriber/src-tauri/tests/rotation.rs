@@ -0,0 +1,47 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+//! Tests for log file rotation callbacks.
+//!
+//! Only the global subscriber writes to the file target, so these tests run
+//! in their own binary.
+
+use std::time::Duration;
+
+use tauri_plugin_tracing::test::mock_app;
+use tauri_plugin_tracing::{Builder, MaxFileSize, RotationStrategy, Target};
+
+#[test]
+fn on_rotation_reports_size_based_rollover() {
+    let dir = std::env::temp_dir().join(format!("tracing-rotation-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let app = mock_app(
+        Builder::new()
+            .with_default_subscriber()
+            .clear_targets()
+            .target(Target::Folder {
+                path: dir.clone(),
+                file_name: Some("rotating".to_string()),
+            })
+            .with_max_file_size(MaxFileSize::kb(1))
+            .with_rotation_strategy(RotationStrategy::KeepSome(3))
+            .on_rotation(move |old_path, new_path| {
+                let _ = sender.send((old_path.to_path_buf(), new_path.to_path_buf()));
+            }),
+    )
+    .unwrap();
+
+    for i in 0..100 {
+        tracing::warn!(i, "filling the log file until it rotates");
+    }
+
+    let (old_path, new_path) = receiver
+        .recv_timeout(Duration::from_secs(5))
+        .expect("rotation callback");
+    assert_eq!(old_path, dir.join("rotating.log.1"));
+    assert_eq!(new_path, dir.join("rotating.log"));
+    assert!(old_path.exists());
+
+    drop(app);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,50 @@
+/**
+ * Clock synchronization with the Rust backend.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { timestamp } from './log'
+import type { ClockSample } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Measures the offset between this page's clock and the backend's, and stores
+ * it in the backend.
+ *
+ * The backend's clock is sampled several times. For the sample with the
+ * shortest round trip, the backend is assumed to have read its clock halfway
+ * through the call. The resulting offset is then applied by the backend to
+ * every timestamp this window sends, so frontend events line up with backend
+ * events in merged timelines.
+ *
+ * Call this once after the page loads; the offset is discarded on reload.
+ *
+ * @param samples - How many times to sample the backend's clock. Defaults to 5.
+ * @returns The offset in milliseconds added to this window's timestamps
+ *
+ * @example
+ * ```ts
+ * import { syncClock } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const offsetMs = await syncClock();
+ * ```
+ */
+export async function syncClock(samples = 5): Promise<number> {
+  let best: { offsetMs: number; roundTripMs: number } | undefined
+  for (let i = 0; i < Math.max(1, samples); i++) {
+    const sentAt = timestamp()
+    const sample = await invoke<ClockSample>(pluginCommand('sync_clock'))
+    const receivedAt = timestamp()
+    const roundTripMs = receivedAt - sentAt
+    if (best === undefined || roundTripMs < best.roundTripMs) {
+      best = {
+        offsetMs: sample.wallMs - (sentAt + receivedAt) / 2,
+        roundTripMs
+      }
+    }
+  }
+  await invoke<ClockSample>(pluginCommand('sync_clock'), best)
+  return best?.offsetMs ?? 0
+}
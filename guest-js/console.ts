@@ -11,6 +11,7 @@ import { listen, type Event, type UnlistenFn } from '@tauri-apps/api/event'
 import { trace, debug, info, warn, error } from './log'
 import { LogLevel, type RecordPayload } from './types'
 import { cleanMessage } from './utils'
+import { pluginEvent } from './plugin-name'
 
 type ConsoleFn = (...args: unknown[]) => void
 
@@ -226,7 +227,7 @@ export async function takeoverConsole(): Promise<() => void> {
 
   // Listen for Rust tracing events and output using the ORIGINAL console methods
   const unlisten: UnlistenFn = await listen(
-    'tracing://log',
+    pluginEvent('log'),
     (event: Event<RecordPayload>) => {
       const { level } = event.payload
       const message = cleanMessage(event.payload.message)
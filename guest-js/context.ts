@@ -0,0 +1,54 @@
+/**
+ * Context added to every log event, such as the signed-in user.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Adds `key=value` to every subsequent event, from the backend and every
+ * webview, until it is removed.
+ *
+ * Setting a key again replaces its value.
+ *
+ * @param key - The field name, e.g. `user_id`
+ * @param value - The field value
+ *
+ * @example
+ * ```ts
+ * import { setContext } from '@fltsci/tauri-plugin-tracing';
+ *
+ * await setContext('user_id', user.id);
+ * await setContext('workspace', workspace.slug);
+ * ```
+ */
+export async function setContext(key: string, value: string): Promise<void> {
+  await invoke<void>(pluginCommand('set_context'), { key, value })
+}
+
+/**
+ * Stops adding `key` to events.
+ *
+ * @param key - A key set with {@link setContext}
+ */
+export async function removeContext(key: string): Promise<void> {
+  await invoke<void>(pluginCommand('remove_context'), { key })
+}
+
+/**
+ * Stops adding any context to events, e.g. when the user logs out.
+ *
+ * @example
+ * ```ts
+ * import { clearContext } from '@fltsci/tauri-plugin-tracing';
+ *
+ * async function logout() {
+ *   await clearContext();
+ * }
+ * ```
+ */
+export async function clearContext(): Promise<void> {
+  await invoke<void>(pluginCommand('clear_context'))
+}
@@ -0,0 +1,83 @@
+/**
+ * Diagnostic report bundles for "report a problem" flows.
+ *
+ * These require the `diagnostics` feature in the Rust plugin and the
+ * `allow-create-diagnostic-report` permission, which is not part of the
+ * default set.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * A file to include in a report, such as a screenshot.
+ */
+export interface DiagnosticAttachment {
+  /** The file name, e.g. `main-window.png` */
+  name: string
+  /** The file contents */
+  data: Blob | ArrayBuffer | Uint8Array
+}
+
+/**
+ * Options for {@link createDiagnosticReport}.
+ */
+export interface DiagnosticReportOptions {
+  /** The user's description of the problem */
+  description?: string
+  /** Screenshots or other files to include */
+  screenshots?: DiagnosticAttachment[]
+  /** The most bytes of logs to include. Defaults to 20 MiB. */
+  maxLogBytes?: number
+}
+
+/**
+ * Writes a zip file with recent logs, the plugin configuration, system
+ * information, the latest profiling capture, and the given screenshots.
+ *
+ * Reports may contain personal data; let users review what is sent.
+ *
+ * @returns The path to the zip file, in the `diagnostics` directory of the
+ * app cache directory
+ *
+ * @example
+ * ```ts
+ * import { createDiagnosticReport } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const path = await createDiagnosticReport({
+ *   description: feedbackForm.text,
+ *   screenshots: [{ name: 'screen.png', data: screenshotBlob }]
+ * });
+ * ```
+ */
+export async function createDiagnosticReport(
+  options: DiagnosticReportOptions = {}
+): Promise<string> {
+  const screenshots = await Promise.all(
+    (options.screenshots ?? []).map(async ({ name, data }) => ({
+      name,
+      bytes: Array.from(await toBytes(data))
+    }))
+  )
+  return await invoke<string>(pluginCommand('create_diagnostic_report'), {
+    options: {
+      description: options.description,
+      screenshots,
+      maxLogBytes: options.maxLogBytes
+    }
+  })
+}
+
+async function toBytes(
+  data: Blob | ArrayBuffer | Uint8Array
+): Promise<Uint8Array> {
+  if (data instanceof Uint8Array) {
+    return data
+  }
+  if (data instanceof ArrayBuffer) {
+    return new Uint8Array(data)
+  }
+  return new Uint8Array(await data.arrayBuffer())
+}
@@ -0,0 +1,95 @@
+/**
+ * Disk usage of the backend's log files, for settings UIs.
+ *
+ * These require the plugin's default subscriber with a file target. Without
+ * one, all counts are zero.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type Event, type UnlistenFn } from '@tauri-apps/api/event'
+import type { LogStats, LogTail, TailLines } from './types'
+import { pluginCommand, pluginEvent } from './plugin-name'
+
+/**
+ * Returns the number and size of the log files.
+ *
+ * @example
+ * ```ts
+ * import { getLogStats } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const { totalBytes } = await getLogStats();
+ * label.textContent = `Logs are using ${Math.round(totalBytes / 1e6)} MB`;
+ * ```
+ */
+export async function getLogStats(): Promise<LogStats> {
+  return await invoke<LogStats>(pluginCommand('get_log_stats'))
+}
+
+/**
+ * Deletes the rotated log files and empties the current one.
+ *
+ * Requires the `allow-clear-logs` permission, which is not part of the
+ * default set.
+ *
+ * @returns The stats after clearing
+ */
+export async function clearLogs(): Promise<LogStats> {
+  return await invoke<LogStats>(pluginCommand('clear_logs'))
+}
+
+/**
+ * Returns the last lines of the file this instance writes to, for a live log
+ * view.
+ *
+ * With `follow`, new lines are then emitted to {@link onTailLines} listeners
+ * until {@link stopTailLogs} is called, also after the logs rotate. One
+ * follower is shared by every window.
+ *
+ * Requires the `tracing:log-files` permission, which is not part of the
+ * default set.
+ *
+ * @param lines - How many lines to return
+ * @param options - Whether to follow the file
+ *
+ * @example
+ * ```ts
+ * import { onTailLines, tailLogs } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const unlisten = await onTailLines(({ lines }) => view.append(...lines));
+ * const { lines } = await tailLogs(500, { follow: true });
+ * view.replace(lines);
+ * ```
+ */
+export async function tailLogs(
+  lines: number,
+  options: { follow?: boolean } = {}
+): Promise<LogTail> {
+  return await invoke<LogTail>(pluginCommand('tail_logs'), {
+    lines,
+    follow: options.follow ?? false
+  })
+}
+
+/**
+ * Stops emitting new lines to {@link onTailLines} listeners.
+ */
+export async function stopTailLogs(): Promise<void> {
+  await invoke(pluginCommand('stop_tail_logs'))
+}
+
+/**
+ * Attaches a listener for the new lines of a log file followed with
+ * {@link tailLogs}.
+ *
+ * @param fn - Callback function called with each batch of new lines
+ * @returns A function to unsubscribe
+ */
+export async function onTailLines(
+  fn: (tail: TailLines) => void
+): Promise<UnlistenFn> {
+  return await listen(pluginEvent('tail'), (event: Event<TailLines>) => {
+    fn(event.payload)
+  })
+}
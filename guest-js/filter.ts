@@ -0,0 +1,119 @@
+/**
+ * Runtime changes to the backend's log filter.
+ *
+ * These require the plugin's default subscriber. When filter persistence is
+ * enabled in the backend, changes survive app restarts until reset.
+ *
+ * Changing the filter requires the `tracing:filter-control` permission,
+ * which is not part of `tracing:default`.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import type {
+  EffectiveFilter,
+  FilterStats,
+  LogLevel,
+  NoiseRecommendation,
+  NoiseReport
+} from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Sets the level for targets without a more specific level.
+ *
+ * @param level - The most verbose level to record
+ *
+ * @example
+ * ```ts
+ * import { setLogLevel, LogLevel } from '@fltsci/tauri-plugin-tracing';
+ *
+ * await setLogLevel(LogLevel.Debug);
+ * ```
+ */
+export async function setLogLevel(level: LogLevel): Promise<void> {
+  await invoke<void>(pluginCommand('set_log_level'), { level })
+}
+
+/**
+ * Sets the level for a target and its submodules.
+ *
+ * @param target - The target, usually a Rust module path
+ * @param level - The most verbose level to record, or `null` to clear it
+ */
+export async function setTargetLevel(
+  target: string,
+  level: LogLevel | null
+): Promise<void> {
+  await invoke<void>(pluginCommand('set_target_level'), { target, level })
+}
+
+/**
+ * Discards all runtime filter changes, including persisted ones.
+ */
+export async function resetLogFilter(): Promise<void> {
+  await invoke<void>(pluginCommand('reset_log_filter'))
+}
+
+/**
+ * Returns the filter in effect, so debug UIs can show what is being captured.
+ *
+ * @example
+ * ```ts
+ * import { getEffectiveFilter } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const { level, targets } = await getEffectiveFilter();
+ * ```
+ */
+export async function getEffectiveFilter(): Promise<EffectiveFilter> {
+  return await invoke<EffectiveFilter>(pluginCommand('get_effective_filter'))
+}
+
+/**
+ * Returns the backend's events dropped by the filter, by target.
+ *
+ * Requires `Builder::with_filter_stats()` in the backend.
+ *
+ * @example
+ * ```ts
+ * import { getFilterStats } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const { totalEvents, targets } = await getFilterStats();
+ * console.table(targets.slice(0, 10));
+ * ```
+ */
+export async function getFilterStats(): Promise<FilterStats> {
+  return await invoke<FilterStats>(pluginCommand('get_filter_stats'))
+}
+
+/**
+ * Counts the backend's events per crate for a while and recommends levels
+ * for the noisiest third-party crates, such as `tao`, `wry`, and `hyper`.
+ *
+ * @param windowMs - How long to count events, 10s by default and 60s at most
+ *
+ * @example
+ * ```ts
+ * import { analyzeNoise, applyNoiseFilters } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const { recommendations } = await analyzeNoise(30_000);
+ * await applyNoiseFilters(recommendations);
+ * ```
+ */
+export async function analyzeNoise(windowMs?: number): Promise<NoiseReport> {
+  return await invoke<NoiseReport>(pluginCommand('analyze_noise'), { windowMs })
+}
+
+/**
+ * Sets the level of each recommended target, like `setTargetLevel()`.
+ *
+ * Requires the `tracing:filter-control` permission.
+ *
+ * @param recommendations - Recommendations returned by `analyzeNoise()`
+ */
+export async function applyNoiseFilters(
+  recommendations: NoiseRecommendation[]
+): Promise<void> {
+  await invoke<void>(pluginCommand('apply_noise_filters'), { recommendations })
+}
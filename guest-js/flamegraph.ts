@@ -8,6 +8,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import { pluginCommand } from './plugin-name'
 
 /**
  * Generates a flamegraph SVG from recorded profiling data.
@@ -28,7 +29,7 @@ import { invoke } from '@tauri-apps/api/core'
  * ```
  */
 export async function generateFlamegraph(): Promise<string> {
-  return await invoke<string>('plugin:tracing|generate_flamegraph')
+  return await invoke<string>(pluginCommand('generate_flamegraph'))
 }
 
 /**
@@ -52,5 +53,5 @@ export async function generateFlamegraph(): Promise<string> {
  * ```
  */
 export async function generateFlamechart(): Promise<string> {
-  return await invoke<string>('plugin:tracing|generate_flamechart')
+  return await invoke<string>(pluginCommand('generate_flamechart'))
 }
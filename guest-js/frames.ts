@@ -0,0 +1,119 @@
+/**
+ * Frame timing sampler for diagnosing webview jank.
+ *
+ * Long frames are reported to the Rust backend, where they are logged under
+ * the `webview::frames` target alongside backend spans.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import type { FrameStats, LongFrame } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Options for {@link startFrameMonitor}.
+ */
+export interface FrameMonitorOptions {
+  /** Frames longer than this are reported as long frames. Defaults to 50ms. */
+  thresholdMs?: number
+  /** How often collected frame times are sent to the backend. Defaults to 5000ms. */
+  reportIntervalMs?: number
+  /** Called with the aggregate statistics returned for each report. */
+  onStats?: (stats: FrameStats) => void
+}
+
+/**
+ * Sends a batch of frame timings to the backend.
+ *
+ * @param frameTimesMs - Duration of every frame in the batch
+ * @param longFrames - Frames that exceeded the threshold
+ * @returns Aggregate statistics, or `null` if the batch was empty
+ */
+export async function reportFrameTimes(
+  frameTimesMs: number[],
+  longFrames: LongFrame[] = []
+): Promise<FrameStats | null> {
+  return await invoke<FrameStats | null>(pluginCommand('report_frame_times'), {
+    report: { frameTimesMs, longFrames }
+  })
+}
+
+/**
+ * Starts sampling frame durations with `requestAnimationFrame`.
+ *
+ * Frame times are collected and periodically reported to the backend, which
+ * logs each long frame as a warning and a p95/p99 summary at debug level.
+ * Batches without long frames are still reported so the statistics stay
+ * accurate.
+ *
+ * @param options - Threshold, reporting interval, and statistics callback
+ * @returns A function that stops the monitor and flushes pending frames
+ *
+ * @example
+ * ```ts
+ * import { startFrameMonitor } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const stop = startFrameMonitor({
+ *   thresholdMs: 50,
+ *   onStats: (stats) => console.log(`p99: ${stats.p99Ms}ms`)
+ * });
+ *
+ * // Later...
+ * stop();
+ * ```
+ */
+export function startFrameMonitor(
+  options: FrameMonitorOptions = {}
+): () => void {
+  const thresholdMs = options.thresholdMs ?? 50
+  const reportIntervalMs = options.reportIntervalMs ?? 5000
+
+  let frameTimes: number[] = []
+  let longFrames: LongFrame[] = []
+  let last: number | undefined
+  let handle: number | undefined
+
+  const onFrame = (now: number) => {
+    if (last !== undefined) {
+      const duration = now - last
+      frameTimes.push(duration)
+      if (duration > thresholdMs) {
+        longFrames.push({
+          timestampMs: performance.timeOrigin + last,
+          durationMs: duration
+        })
+      }
+    }
+    last = now
+    handle = requestAnimationFrame(onFrame)
+  }
+
+  const flush = () => {
+    if (frameTimes.length === 0) {
+      return
+    }
+    const batch = frameTimes
+    const long = longFrames
+    frameTimes = []
+    longFrames = []
+    reportFrameTimes(batch, long)
+      .then((stats) => {
+        if (stats && options.onStats) {
+          options.onStats(stats)
+        }
+      })
+      .catch(console.error)
+  }
+
+  handle = requestAnimationFrame(onFrame)
+  const timer = setInterval(flush, reportIntervalMs)
+
+  return () => {
+    if (handle !== undefined) {
+      cancelAnimationFrame(handle)
+    }
+    clearInterval(timer)
+    flush()
+  }
+}
@@ -0,0 +1,33 @@
+/**
+ * Answers the Rust backend's heartbeat pings.
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type Event, type UnlistenFn } from '@tauri-apps/api/event'
+import { pluginCommand, pluginEvent } from './plugin-name'
+
+/**
+ * Answers the backend's heartbeat pings from this window.
+ *
+ * With `Builder::with_heartbeat()`, the backend pings every window at each
+ * heartbeat. Once this window has answered a ping, each ping it misses, e.g.
+ * because a long task blocks its JavaScript thread, is logged as a warning
+ * with the last known main thread activity.
+ *
+ * Pings are answered from the event loop, so a frozen page stops answering.
+ *
+ * @returns A function to stop answering pings
+ *
+ * @example
+ * ```ts
+ * import { startHeartbeat } from '@fltsci/tauri-plugin-tracing';
+ *
+ * await startHeartbeat();
+ * ```
+ */
+export async function startHeartbeat(): Promise<UnlistenFn> {
+  return await listen(pluginEvent('ping'), (event: Event<number>) => {
+    void invoke(pluginCommand('heartbeat_pong'), { seq: event.payload })
+  })
+}
@@ -1,5 +1,12 @@
 import { describe, it, expect } from 'vitest'
-import { formatPrintf, getCircularReplacer } from './index'
+import {
+  cleanValue,
+  formatPrintf,
+  getCircularReplacer,
+  limitMessage,
+  limitStack
+} from './index'
+import { pluginCommand, pluginEvent, setPluginName } from './plugin-name'
 
 describe('formatPrintf', () => {
   it('handles %s string substitution', () => {
@@ -156,3 +163,105 @@ describe('getCircularReplacer', () => {
     expect(result).toBe('[1,2,{"nested":true}]')
   })
 })
+
+describe('cleanValue', () => {
+  it('keeps objects and arrays structured', () => {
+    expect(cleanValue({ a: 1, b: [true, null] })).toEqual({
+      a: 1,
+      b: [true, null]
+    })
+  })
+
+  it('keeps numbers and booleans', () => {
+    expect(cleanValue(42)).toBe(42)
+    expect(cleanValue(false)).toBe(false)
+  })
+
+  it('strips ANSI codes from strings', () => {
+    expect(cleanValue('\u001b[31mred\u001b[0m')).toBe('red')
+  })
+
+  it('replaces circular references', () => {
+    const obj: Record<string, unknown> = { a: 1 }
+    obj.self = obj
+    expect(cleanValue(obj)).toEqual({ a: 1, self: '[Circular]' })
+  })
+
+  it('stringifies values JSON cannot represent', () => {
+    expect(cleanValue(undefined)).toBe('undefined')
+    expect(cleanValue(new TypeError('bad'))).toBe('TypeError: bad')
+  })
+})
+
+describe('limitMessage', () => {
+  type Message = Parameters<typeof limitMessage>[0]
+
+  it('leaves messages alone without a limit', () => {
+    const message = ['x'.repeat(100)] as Message
+    expect(limitMessage(message, undefined)).toBe(message)
+  })
+
+  it('cuts strings just past the limit', () => {
+    const message = ['x'.repeat(100)] as Message
+    expect(limitMessage(message, 8)).toEqual(['x'.repeat(9)])
+  })
+
+  it('does not split surrogate pairs', () => {
+    const message = ['x'.repeat(8) + '😀'] as Message
+    expect(limitMessage(message, 8)).toEqual(['x'.repeat(8) + '😀'])
+  })
+
+  it('cuts strings nested in arguments', () => {
+    const message = [
+      'object',
+      { key: 'x'.repeat(1000), list: ['y'.repeat(1000)] }
+    ] as Message
+    const [, args] = limitMessage(message, 64)
+    const json = JSON.stringify(args)
+    expect(json.length).toBeGreaterThan(64)
+    expect(json.length).toBeLessThanOrEqual(128)
+    expect(args).toHaveProperty('key', expect.stringMatching(/^x+$/))
+  })
+
+  it('sends oversized arguments as cut JSON text', () => {
+    const list = Array.from({ length: 100 }, (_, i) => i)
+    const [, args] = limitMessage(['list', list] as Message, 16)
+    expect(args).toBe('[0,1,2,3,4,5,6,7,')
+  })
+})
+
+describe('limitStack', () => {
+  const stack = [
+    'Error',
+    '    at one (a.js:1:1)',
+    '    at two (b.js:2:2)',
+    '    at three (c.js:3:3)'
+  ].join('\n')
+
+  it('does not count the Error header as a frame', () => {
+    expect(limitStack(stack, 1)).toBe(
+      'Error\n    at one (a.js:1:1)\n    at two (b.js:2:2)'
+    )
+  })
+
+  it('counts every line of stacks without a header', () => {
+    expect(limitStack('one@a.js:1:1\ntwo@b.js:2:2\nthree@c.js:3:3', 0)).toBe(
+      'one@a.js:1:1'
+    )
+  })
+
+  it('leaves stacks alone without a limit', () => {
+    expect(limitStack(stack, null)).toBe(stack)
+  })
+})
+
+describe('setPluginName', () => {
+  it('prefixes commands and events with the plugin name', () => {
+    expect(pluginCommand('log')).toBe('plugin:tracing|log')
+    expect(pluginEvent('log')).toBe('tracing://log')
+    setPluginName('tracing-admin')
+    expect(pluginCommand('log')).toBe('plugin:tracing-admin|log')
+    expect(pluginEvent('log')).toBe('tracing-admin://log')
+    setPluginName('tracing')
+  })
+})
@@ -30,20 +30,180 @@ export {
   LogLevel,
   type LogMessage,
   type LoggerFn,
-  type RecordPayload
+  type RecordPayload,
+  type Breadcrumb,
+  type SpanContext,
+  type LongFrame,
+  type FrameStats,
+  type ClockSample,
+  type EffectiveFilter,
+  type CrateNoise,
+  type NoiseRecommendation,
+  type NoiseReport,
+  type TargetDrops,
+  type FilterStats,
+  type ViolatedInvariant,
+  type ProgressUpdate,
+  type MetricsBatch,
+  type TestLogs,
+  type LogStats,
+  type LogTail,
+  type TailLines,
+  type MarkContext,
+  type ResourceSnapshot,
+  type OperationHandle,
+  type OperationOutcome
 } from './types'
 
+// Re-export the plugin name
+export { setPluginName } from './plugin-name'
+
 // Re-export logging functions
-export { trace, debug, info, warn, error } from './log'
+export {
+  trace,
+  debug,
+  info,
+  warn,
+  error,
+  withSpanContext,
+  logBatch,
+  timestamp,
+  sessionId,
+  setCurrentRoute,
+  type BatchRecord,
+  type SpanLogger
+} from './log'
+
+// Re-export log persistence
+export {
+  enablePersistence,
+  disablePersistence,
+  pendingRecordCount,
+  type PersistenceOptions
+} from './persist'
+
+// Re-export W3C trace context helpers
+export {
+  createTraceparent,
+  tracedFetch,
+  type TracedFetchOptions
+} from './traceparent'
+
+// Re-export frame timing
+export {
+  startFrameMonitor,
+  reportFrameTimes,
+  type FrameMonitorOptions
+} from './frames'
+
+// Re-export invariant reporting
+export { invariant, getInvariantViolations } from './invariant'
+
+// Re-export progress reporting
+export { progress, onProgress } from './progress'
+
+// Re-export metrics
+export { gauge, counter, onMetrics } from './metrics'
+
+// Re-export end-to-end test capture
+export { beginTest, endTest } from './test-mode'
+
+// Re-export runtime filter changes
+export {
+  setLogLevel,
+  setTargetLevel,
+  resetLogFilter,
+  getEffectiveFilter,
+  getFilterStats,
+  analyzeNoise,
+  applyNoiseFilters
+} from './filter'
+
+// Re-export log file management
+export {
+  getLogStats,
+  clearLogs,
+  tailLogs,
+  stopTailLogs,
+  onTailLines
+} from './files'
+
+// Re-export markers
+export { mark, getLogsAroundMark } from './marks'
+
+// Re-export log context
+export { setContext, removeContext, clearContext } from './context'
+
+// Re-export JSON Schema
+export { getSchema } from './schema'
+
+// Re-export clock synchronization
+export { syncClock } from './clock'
+
+// Re-export long-running operations
+export {
+  startOperation,
+  resumeOperation,
+  endOperation,
+  listOperations,
+  type Operation
+} from './operations'
+
+// Re-export frontend spans
+export { withSpan, currentSpan, type SpanOptions } from './spans'
 
 // Re-export listener functions
-export { attachLogger, attachConsole } from './listener'
+export {
+  attachLogger,
+  attachConsole,
+  onLog,
+  onWebviewCrashed,
+  onNotification,
+  type OnLogOptions,
+  type UserNotification,
+  type WebviewCrash
+} from './listener'
+
+// Re-export heartbeat
+export { startHeartbeat } from './heartbeat'
+
+// Re-export timers
+export { time, timeLog, timeEnd } from './timers'
 
 // Re-export console interception
 export { interceptConsole, restoreConsole, takeoverConsole } from './console'
 
 // Re-export utilities (for testing and advanced usage)
-export { formatPrintf, getCircularReplacer } from './utils'
+export {
+  cleanValue,
+  formatPrintf,
+  getCircularReplacer,
+  limitMessage,
+  limitStack
+} from './utils'
 
 // Re-export flamegraph functions
 export { generateFlamegraph, generateFlamechart } from './flamegraph'
+
+// Re-export diagnostic reports
+export {
+  createDiagnosticReport,
+  type DiagnosticAttachment,
+  type DiagnosticReportOptions
+} from './diagnostics'
+
+// Re-export span-aware profiling and saved sessions
+export {
+  startSpanAwareProfile,
+  stopSpanAwareProfile,
+  listProfilingSessions,
+  getProfilingSession,
+  getSpanStats,
+  type SpanAwareProfileOptions,
+  type ProfilingSession,
+  type SpanCorrelationReport,
+  type ActiveSpan,
+  type SpanEvent,
+  type ThreadSpans,
+  type SpanStats
+} from './profiling'
@@ -0,0 +1,57 @@
+/**
+ * Reporting of violated invariants to the Rust backend.
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import type { ViolatedInvariant } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Reports a violated invariant to the backend if `condition` is falsy.
+ *
+ * The backend logs an error under `app::invariant` with the invariant's id,
+ * and counts its violations for `getInvariantViolations()` and diagnostic
+ * reports.
+ *
+ * @param condition - The condition that should always hold
+ * @param id - A unique id for the invariant, stable across versions
+ * @param message - What went wrong, `invariant violated: <id>` by default
+ *
+ * @example
+ * ```ts
+ * import { invariant } from '@fltsci/tauri-plugin-tracing';
+ *
+ * invariant(cart.total >= 0, 'cart-total-non-negative', 'cart total is negative');
+ * ```
+ */
+export function invariant(
+  condition: unknown,
+  id: string,
+  message?: string
+): void {
+  if (condition) {
+    return
+  }
+  invoke<void>(pluginCommand('report_invariant_violation'), {
+    id,
+    message
+  }).catch(console.error)
+}
+
+/**
+ * Returns the invariants violated since the app started, from the backend
+ * and all webviews, most violated first.
+ *
+ * @example
+ * ```ts
+ * import { getInvariantViolations } from '@fltsci/tauri-plugin-tracing';
+ *
+ * console.table(await getInvariantViolations());
+ * ```
+ */
+export async function getInvariantViolations(): Promise<ViolatedInvariant[]> {
+  return await invoke<ViolatedInvariant[]>(
+    pluginCommand('get_invariant_violations')
+  )
+}
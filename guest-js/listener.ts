@@ -3,9 +3,11 @@
  * @module
  */
 
+import { invoke } from '@tauri-apps/api/core'
 import { listen, type Event, type UnlistenFn } from '@tauri-apps/api/event'
 import { LogLevel, type LoggerFn, type RecordPayload } from './types'
 import { cleanMessage } from './utils'
+import { pluginCommand, pluginEvent } from './plugin-name'
 
 /**
  * Attaches a custom listener for log events from the Rust backend.
@@ -29,12 +31,167 @@ import { cleanMessage } from './utils'
  * ```
  */
 export async function attachLogger(fn: LoggerFn): Promise<UnlistenFn> {
-  return await listen('tracing://log', (event: Event<RecordPayload>) => {
-    const { level } = event.payload
-    const message = cleanMessage(event.payload.message)
+  const unlisten = await listen(
+    pluginEvent('log'),
+    (event: Event<RecordPayload>) => {
+      const { level } = event.payload
+      const message = cleanMessage(event.payload.message)
 
-    fn({ message, level })
-  })
+      fn({ message, level })
+    }
+  )
+  await webviewReady()
+  return unlisten
+}
+
+/**
+ * Options for {@link onLog}.
+ */
+export interface OnLogOptions {
+  /** The least severe level passed to the callback. Defaults to all levels. */
+  level?: LogLevel
+}
+
+/**
+ * Attaches a listener for log events from the Rust backend that receives the
+ * whole record.
+ *
+ * Unlike {@link attachLogger}, the callback also receives the event's fields,
+ * breadcrumbs, sequence number, and gap, and records below `options.level`
+ * are skipped. A record's `gap` is the number of records the backend dropped
+ * before it, e.g. while no webview was loaded.
+ *
+ * @param fn - Callback function called for each log entry
+ * @param options - The least severe level to receive
+ * @returns A function to unsubscribe from log events
+ *
+ * @example
+ * ```ts
+ * const unlisten = await onLog(
+ *   ({ message, fields, breadcrumbs }) =>
+ *     sendToErrorTracking(message, fields, breadcrumbs),
+ *   { level: LogLevel.Warn }
+ * );
+ *
+ * // Show where records are missing
+ * await onLog(({ message, gap }) => {
+ *   if (gap) appendLine(`… ${gap} records dropped`);
+ *   appendLine(message);
+ * });
+ * ```
+ */
+export async function onLog(
+  fn: LoggerFn,
+  options: OnLogOptions = {}
+): Promise<UnlistenFn> {
+  const minLevel = options.level ?? LogLevel.Trace
+  const unlisten = await listen(
+    pluginEvent('log'),
+    (event: Event<RecordPayload>) => {
+      if (event.payload.level < minLevel) {
+        return
+      }
+      fn({ ...event.payload, message: cleanMessage(event.payload.message) })
+    }
+  )
+  await webviewReady()
+  return unlisten
+}
+
+/**
+ * Tells the backend that this webview listens to `tracing://log`, so records
+ * buffered while no webview was open are emitted now rather than before the
+ * listener exists.
+ */
+async function webviewReady(): Promise<void> {
+  await invoke<void>(pluginCommand('webview_ready')).catch(console.error)
+}
+
+/**
+ * A webview whose process crashed, was killed, or stopped responding, as
+ * emitted by the Rust backend.
+ */
+export interface WebviewCrash {
+  /** The label of the webview */
+  label: string
+  /** Why the webview's process failed */
+  reason:
+    | 'crashed'
+    | 'outOfMemory'
+    | 'terminated'
+    | 'unresponsive'
+    | 'otherProcessFailed'
+  /** The platform's code for the failure, if any */
+  code: number | null
+}
+
+/**
+ * Attaches a listener for crashes of other webviews of the app.
+ *
+ * The crashed webview cannot run its own listeners, so this lets the
+ * remaining windows react, e.g. by telling the user or reloading it.
+ *
+ * @param fn - Callback function called for each crash
+ * @returns A function to unsubscribe from crash events
+ *
+ * @example
+ * ```ts
+ * const unlisten = await onWebviewCrashed(({ label, reason }) => {
+ *   showToast(`Window ${label} stopped working (${reason})`);
+ * });
+ * ```
+ */
+export async function onWebviewCrashed(
+  fn: (crash: WebviewCrash) => void
+): Promise<UnlistenFn> {
+  return await listen(
+    pluginEvent('webview-crashed'),
+    (event: Event<WebviewCrash>) => {
+      fn(event.payload)
+    }
+  )
+}
+
+/**
+ * A backend event to show to the user, as emitted by the Rust backend when
+ * notifications are enabled with `Builder::with_notifications()`.
+ */
+export interface UserNotification {
+  /** The event's `title` field, or a title for its level */
+  title: string
+  /** The event's message */
+  body: string
+  /** The event's level */
+  level: LogLevel
+  /** The event's target */
+  target: string
+  /** How many notifications the rate limit dropped since the previous one */
+  suppressed?: number
+}
+
+/**
+ * Attaches a listener for backend events to show to the user, such as
+ * `tracing::error!(notify = true, title = "Could not save", "...")`.
+ *
+ * @param fn - Callback function called for each notification
+ * @returns A function to unsubscribe from notifications
+ *
+ * @example
+ * ```ts
+ * const unlisten = await onNotification(({ title, body, suppressed }) => {
+ *   showToast(title, suppressed ? `${body} (+${suppressed} more)` : body);
+ * });
+ * ```
+ */
+export async function onNotification(
+  fn: (notification: UserNotification) => void
+): Promise<UnlistenFn> {
+  return await listen(
+    pluginEvent('notify'),
+    (event: Event<UserNotification>) => {
+      fn(event.payload)
+    }
+  )
 }
 
 /**
@@ -4,8 +4,20 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
-import { LogLevel, type LogMessage } from './types'
-import { cleanMessage } from './utils'
+import { LogLevel, type LogMessage, type SpanContext } from './types'
+import { bufferRecord, isBuffering } from './persist'
+import { cleanMessage, limitMessage, limitStack, logLimits } from './utils'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Trace context attached to a log record.
+ */
+export interface LogContext {
+  /** A backend span to record the message under */
+  spanContext?: SpanContext
+  /** A W3C `traceparent` header value to record with the message */
+  traceparent?: string
+}
 
 /**
  * Internal function to send a log message to the Rust backend.
@@ -14,15 +26,192 @@ import { cleanMessage } from './utils'
  * and invokes the tracing plugin command.
  *
  * @param level - The severity level of the log
+ * @param context - Optional trace context to record the message under
  * @param msg - The message parts to log
  */
-function log(level: LogLevel, ...msg: LogMessage) {
-  const message = cleanMessage(msg)
-  invoke<void>('plugin:tracing|log', {
+export function sendLog(
+  level: LogLevel,
+  context: LogContext,
+  ...msg: LogMessage
+) {
+  const limits = logLimits()
+  const record = {
     level,
-    message,
-    callStack: new Error().stack
-  }).catch(console.error)
+    message: limitMessage(cleanMessage(msg), limits.maxMessageLen),
+    callStack: limitStack(new Error().stack, limits.maxStackFrames),
+    spanContext: context.spanContext,
+    traceparent: context.traceparent,
+    timestamp: timestamp(),
+    route: route(),
+    session: SESSION
+  }
+  // Queue behind records waiting for the backend to keep them in order
+  if (isBuffering()) {
+    bufferRecord(record)
+    return
+  }
+  invoke<void>(pluginCommand('log'), record).catch((error) => {
+    if (!bufferRecord(record)) {
+      console.error(error)
+    }
+  })
+}
+
+/**
+ * The id of this page load, sent with each record so the backend can tell
+ * the records of a reloaded webview apart.
+ */
+const SESSION: string =
+  typeof crypto !== 'undefined' && 'randomUUID' in crypto
+    ? crypto.randomUUID()
+    : Math.random().toString(36).slice(2)
+
+/**
+ * Returns the id of this page load, as recorded in the `session` field of
+ * the backend's `log` span.
+ */
+export function sessionId(): string {
+  return SESSION
+}
+
+/** The route set with {@link setCurrentRoute}. */
+let currentRoute: string | undefined
+
+/**
+ * Returns the page's route: the one set with {@link setCurrentRoute}, or
+ * else its path, query, and hash, so records of single-page apps can be
+ * grouped by route.
+ */
+function route(): string | undefined {
+  if (currentRoute !== undefined || typeof location === 'undefined') {
+    return currentRoute
+  }
+  return location.pathname + location.search + location.hash
+}
+
+/**
+ * Tells the backend which screen the window shows, e.g. after each
+ * navigation of the frontend's router.
+ *
+ * The backend logs the change under `webview::route` and records the route
+ * on the records that follow, in place of the page's location. With
+ * `Builder::with_route_spans()`, the window's records are also logged in a
+ * `route` span that lasts until the next route.
+ *
+ * @param route - The route or screen name
+ *
+ * @example
+ * ```ts
+ * import { setCurrentRoute } from '@fltsci/tauri-plugin-tracing';
+ *
+ * router.afterEach((to) => setCurrentRoute(to.fullPath));
+ * ```
+ */
+export async function setCurrentRoute(route: string): Promise<void> {
+  currentRoute = route
+  await invoke<void>(pluginCommand('set_current_route'), { route })
+}
+
+/**
+ * Returns the current time in milliseconds since the Unix epoch.
+ *
+ * Uses the monotonic `performance.now()` clock anchored at
+ * `performance.timeOrigin`, so records created in quick succession keep their
+ * order even if the system clock changes.
+ */
+export function timestamp(): number {
+  return performance.timeOrigin + performance.now()
+}
+
+/**
+ * A log record for {@link logBatch}.
+ */
+export interface BatchRecord extends LogContext {
+  level: LogLevel
+  message: LogMessage
+  /** When the record was created. Defaults to the time of the batch call. */
+  timestamp?: number
+  /** The record's page route. Defaults to the current route. */
+  route?: string
+}
+
+/**
+ * Sends several log records to the Rust backend in one IPC call.
+ *
+ * Each record keeps the time it was created, so the backend can order them
+ * correctly even though they arrive together.
+ *
+ * @param records - The records to send, in order
+ *
+ * @example
+ * ```ts
+ * import { logBatch, LogLevel, timestamp } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const pending = [];
+ * pending.push({ level: LogLevel.Debug, message: ['drag', x, y], timestamp: timestamp() });
+ * // ...
+ * await logBatch(pending);
+ * ```
+ */
+export async function logBatch(records: BatchRecord[]): Promise<void> {
+  const now = timestamp()
+  const currentRoute = route()
+  const { maxMessageLen } = logLimits()
+  await invoke<void>(pluginCommand('log_batch'), {
+    records: records.map((record) => ({
+      level: record.level,
+      message: limitMessage(cleanMessage(record.message), maxMessageLen),
+      spanContext: record.spanContext,
+      traceparent: record.traceparent,
+      timestamp: record.timestamp ?? now,
+      route: record.route ?? currentRoute,
+      session: SESSION
+    }))
+  })
+}
+
+function log(level: LogLevel, ...msg: LogMessage) {
+  sendLog(level, {}, ...msg)
+}
+
+/**
+ * Logging functions bound to a backend span.
+ */
+export interface SpanLogger {
+  trace(...message: LogMessage): void
+  debug(...message: LogMessage): void
+  info(...message: LogMessage): void
+  warn(...message: LogMessage): void
+  error(...message: LogMessage): void
+}
+
+/**
+ * Returns logging functions that record messages as children of a backend span.
+ *
+ * Use this with a `SpanContext` returned by a command to connect frontend
+ * logs to the backend work that produced the data, creating a single trace
+ * across the IPC boundary.
+ *
+ * @param context - The span context received from the backend
+ * @returns Logging functions bound to the span
+ *
+ * @example
+ * ```ts
+ * import { withSpanContext } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const { items, span } = await invoke('load_items');
+ * withSpanContext(span).info('rendered', items.length, 'items');
+ * ```
+ */
+export function withSpanContext(context: SpanContext): SpanLogger {
+  const logContext: LogContext = { spanContext: context }
+  return {
+    trace: (...message) => sendLog(LogLevel.Trace, logContext, ...message),
+    debug: (...message) => sendLog(LogLevel.Debug, logContext, ...message),
+    info: (...message) => sendLog(LogLevel.Info, logContext, ...message),
+    warn: (...message) => sendLog(LogLevel.Warn, logContext, ...message),
+    error: (...message) => sendLog(LogLevel.Error, logContext, ...message)
+  }
 }
 
 /**
@@ -0,0 +1,57 @@
+/**
+ * Log context around user actions.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import type { MarkContext } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Records a marker event in the backend, e.g. when the user clicks a button.
+ *
+ * @param label - Names the marker for {@link getLogsAroundMark}
+ *
+ * @example
+ * ```ts
+ * import { mark } from '@fltsci/tauri-plugin-tracing';
+ *
+ * button.addEventListener('click', () => mark('checkout clicked'));
+ * ```
+ */
+export async function mark(label: string): Promise<void> {
+  await invoke<void>(pluginCommand('mark'), { label })
+}
+
+/**
+ * Returns the records just before and after the latest marker with `label`.
+ *
+ * Records come from the backend's in-memory log history, enabled with
+ * `with_log_history()`, or otherwise from the log files.
+ *
+ * Requires the `tracing:log-files` permission, which is not part of the
+ * default set.
+ *
+ * @param label - The marker's label
+ * @param before - How many records to return before the marker. Defaults to 20.
+ * @param after - How many records to return after the marker. Defaults to 20.
+ * @returns The records, or `null` if the marker cannot be found
+ *
+ * @example
+ * ```ts
+ * import { getLogsAroundMark } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const context = await getLogsAroundMark('checkout clicked', 50, 10);
+ * ```
+ */
+export async function getLogsAroundMark(
+  label: string,
+  before?: number,
+  after?: number
+): Promise<MarkContext | null> {
+  return await invoke<MarkContext | null>(
+    pluginCommand('get_logs_around_mark'),
+    { label, before, after }
+  )
+}
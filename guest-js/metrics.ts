@@ -0,0 +1,69 @@
+/**
+ * Lightweight metrics for live dashboards.
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type Event, type UnlistenFn } from '@tauri-apps/api/event'
+import type { MetricsBatch } from './types'
+import { pluginCommand, pluginEvent } from './plugin-name'
+
+/**
+ * Sets the gauge `name` to `value`.
+ *
+ * The backend logs it at `TRACE` under `app::metrics`, and batches it for
+ * `onMetrics()` listeners with `Builder::with_metrics_events()`.
+ *
+ * @param name - The metric's name, e.g. `ui.fps`
+ * @param value - The current value
+ *
+ * @example
+ * ```ts
+ * import { gauge } from '@fltsci/tauri-plugin-tracing';
+ *
+ * gauge('ui.fps', framesLastSecond);
+ * ```
+ */
+export function gauge(name: string, value: number): void {
+  invoke<void>(pluginCommand('gauge'), { name, value }).catch(console.error)
+}
+
+/**
+ * Adds `inc` to the counter `name`.
+ *
+ * @param name - The metric's name, e.g. `sync.retries`
+ * @param inc - The amount to add, defaults to 1
+ *
+ * @example
+ * ```ts
+ * import { counter } from '@fltsci/tauri-plugin-tracing';
+ *
+ * counter('sync.retries');
+ * ```
+ */
+export function counter(name: string, inc = 1): void {
+  invoke<void>(pluginCommand('counter'), { name, inc }).catch(console.error)
+}
+
+/**
+ * Listens to batches of metrics from the backend and all webviews.
+ *
+ * Requires `Builder::with_metrics_events()` in the backend. A batch arrives
+ * every interval in which metrics were recorded, with the latest value of
+ * each gauge and the increments of each counter since the previous batch.
+ *
+ * @example
+ * ```ts
+ * const unlisten = await onMetrics(({ gauges, counters, totals }) => {
+ *   if ('ui.fps' in gauges) setFps(gauges['ui.fps']);
+ *   if ('sync.retries' in counters) setRetries(totals['sync.retries']);
+ * });
+ * ```
+ */
+export async function onMetrics(
+  fn: (batch: MetricsBatch) => void
+): Promise<UnlistenFn> {
+  return await listen(pluginEvent('metrics'), (event: Event<MetricsBatch>) => {
+    fn(event.payload)
+  })
+}
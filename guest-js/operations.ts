@@ -0,0 +1,125 @@
+/**
+ * Long-running operations tracked by the Rust backend.
+ *
+ * An operation opens a backend span that stays open until it is ended, even
+ * across page reloads, so installs, imports, and uploads show up as a single
+ * span with their outcome and duration.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { type SpanLogger, sendLog, withSpanContext } from './log'
+import {
+  LogLevel,
+  type OperationHandle,
+  type OperationOutcome
+} from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * An open operation, with a logger bound to its span.
+ */
+export interface Operation extends OperationHandle {
+  /** Logs records as children of the operation's span */
+  logger: SpanLogger
+  /**
+   * Ends the operation.
+   *
+   * @param outcome - How the operation finished. Defaults to `'success'`.
+   */
+  end(outcome?: OperationOutcome): Promise<OperationHandle>
+}
+
+function toOperation(handle: OperationHandle): Operation {
+  const logger: SpanLogger = handle.spanContext
+    ? withSpanContext(handle.spanContext)
+    : {
+        trace: (...message) => sendLog(LogLevel.Trace, {}, ...message),
+        debug: (...message) => sendLog(LogLevel.Debug, {}, ...message),
+        info: (...message) => sendLog(LogLevel.Info, {}, ...message),
+        warn: (...message) => sendLog(LogLevel.Warn, {}, ...message),
+        error: (...message) => sendLog(LogLevel.Error, {}, ...message)
+      }
+  return {
+    ...handle,
+    logger,
+    end: (outcome) => endOperation(handle.id, outcome)
+  }
+}
+
+/**
+ * Starts a long-running operation.
+ *
+ * Store the returned `id` (e.g. in `sessionStorage`) to re-attach to the
+ * operation with {@link resumeOperation} after a page reload.
+ *
+ * @param name - A short name for the operation, such as `'import'`
+ * @param metadata - Optional JSON data recorded on the operation's span
+ * @returns The started operation
+ * @throws If too many operations are already open
+ *
+ * @example
+ * ```ts
+ * import { startOperation } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const op = await startOperation('upload', { bytes: file.size });
+ * try {
+ *   await upload(file);
+ *   await op.end('success');
+ * } catch (e) {
+ *   op.logger.error('upload failed', e);
+ *   await op.end('failure');
+ * }
+ * ```
+ */
+export async function startOperation(
+  name: string,
+  metadata?: unknown
+): Promise<Operation> {
+  const handle = await invoke<OperationHandle>(pluginCommand('operation_start'), {
+    name,
+    metadata
+  })
+  return toOperation(handle)
+}
+
+/**
+ * Re-attaches to an operation that is still open.
+ *
+ * @param id - The id returned by {@link startOperation}
+ * @returns The operation, or `null` if it has already ended
+ */
+export async function resumeOperation(id: string): Promise<Operation | null> {
+  const handle = await invoke<OperationHandle | null>(
+    pluginCommand('operation_get'),
+    { id }
+  )
+  return handle ? toOperation(handle) : null
+}
+
+/**
+ * Ends an operation by id.
+ *
+ * @param id - The id returned by {@link startOperation}
+ * @param outcome - How the operation finished. Defaults to `'success'`.
+ * @returns The operation's final state
+ */
+export async function endOperation(
+  id: string,
+  outcome: OperationOutcome = 'success'
+): Promise<OperationHandle> {
+  return await invoke<OperationHandle>(pluginCommand('operation_end'), {
+    id,
+    outcome
+  })
+}
+
+/**
+ * Lists all open operations, longest-running first.
+ *
+ * @returns Handles of the open operations
+ */
+export async function listOperations(): Promise<OperationHandle[]> {
+  return await invoke<OperationHandle[]>(pluginCommand('operation_list'))
+}
@@ -0,0 +1,257 @@
+/**
+ * Buffering of frontend logs while the backend is unavailable.
+ *
+ * Records that cannot be sent, e.g. before the plugin is initialized or
+ * while the backend restarts during development, are kept in IndexedDB and
+ * replayed through the `log_batch` command once it responds again. As they
+ * survive page reloads, records from early page load are not lost either.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import type { LogLevel, LogMessage, SpanContext } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Options for {@link enablePersistence}.
+ */
+export interface PersistenceOptions {
+  /** The IndexedDB database to keep records in. Defaults to `tauri-plugin-tracing`. */
+  databaseName?: string
+  /** The most records kept; the oldest are dropped first. Defaults to 10,000. */
+  maxRecords?: number
+  /** How long to wait before retrying to send records, in milliseconds. Defaults to 1,000. */
+  retryIntervalMs?: number
+}
+
+/**
+ * A record as sent to the `log` and `log_batch` commands.
+ */
+export interface PendingRecord {
+  level: LogLevel
+  message: LogMessage
+  callStack?: string
+  spanContext?: SpanContext
+  traceparent?: string
+  timestamp: number
+  route?: string
+  session?: string
+}
+
+/** Where records wait to be sent, oldest first. */
+interface RecordStore {
+  add(record: PendingRecord, maxRecords: number): Promise<void>
+  /** Returns up to `limit` of the oldest records and their keys. */
+  peek(limit: number): Promise<[IDBValidKey[], PendingRecord[]]>
+  /** Removes the records from the first to the last of `keys`. */
+  remove(keys: IDBValidKey[]): Promise<void>
+  count(): Promise<number>
+  close(): void
+}
+
+const STORE_NAME = 'records'
+/** The most records sent in one `log_batch` call. */
+const BATCH_SIZE = 500
+
+let state:
+  | {
+      store: RecordStore
+      maxRecords: number
+      retryIntervalMs: number
+      pending: number
+      flushing: boolean
+      retry?: ReturnType<typeof setTimeout>
+    }
+  | undefined
+
+/**
+ * Keeps records that cannot be sent to the backend, and replays them once
+ * it is available.
+ *
+ * Call this as early as possible during page load, before logging anything.
+ * Records left over from a previous page load are replayed right away. While
+ * records are waiting, new records are queued behind them so they arrive in
+ * order. Each record keeps the time it was created.
+ *
+ * Falls back to memory if IndexedDB is not available.
+ *
+ * @param options - Where and how many records to keep
+ *
+ * @example
+ * ```ts
+ * import { enablePersistence, info } from '@fltsci/tauri-plugin-tracing';
+ *
+ * await enablePersistence();
+ * info('page loading'); // Sent once the backend is ready
+ * ```
+ */
+export async function enablePersistence(
+  options: PersistenceOptions = {}
+): Promise<void> {
+  disablePersistence()
+  const store = await openStore(
+    options.databaseName ?? 'tauri-plugin-tracing'
+  ).catch(() => memoryStore())
+  state = {
+    store,
+    maxRecords: options.maxRecords ?? 10_000,
+    retryIntervalMs: options.retryIntervalMs ?? 1_000,
+    pending: await store.count(),
+    flushing: false
+  }
+  void flush()
+}
+
+/**
+ * Stops keeping records that cannot be sent.
+ *
+ * Records that are still waiting stay in IndexedDB and are replayed by the
+ * next call to {@link enablePersistence}.
+ */
+export function disablePersistence(): void {
+  if (state === undefined) {
+    return
+  }
+  clearTimeout(state.retry)
+  state.store.close()
+  state = undefined
+}
+
+/**
+ * Returns the number of records waiting to be sent to the backend.
+ */
+export function pendingRecordCount(): number {
+  return state?.pending ?? 0
+}
+
+/**
+ * Returns whether new records have to wait behind records that were not
+ * sent yet.
+ */
+export function isBuffering(): boolean {
+  return (state?.pending ?? 0) > 0
+}
+
+/**
+ * Keeps `record` to send it later.
+ *
+ * @returns `false` if persistence is not enabled
+ */
+export function bufferRecord(record: PendingRecord): boolean {
+  if (state === undefined) {
+    return false
+  }
+  const current = state
+  current.pending = Math.min(current.pending + 1, current.maxRecords)
+  current.store
+    .add(record, current.maxRecords)
+    .then(() => scheduleFlush(current))
+    .catch(console.error)
+  return true
+}
+
+function scheduleFlush(current: NonNullable<typeof state>) {
+  if (state === current && current.retry === undefined && !current.flushing) {
+    current.retry = setTimeout(() => void flush(), 0)
+  }
+}
+
+/** Sends waiting records in batches until none are left or sending fails. */
+async function flush(): Promise<void> {
+  const current = state
+  if (current === undefined || current.flushing) {
+    return
+  }
+  current.retry = undefined
+  current.flushing = true
+  try {
+    for (;;) {
+      const [keys, records] = await current.store.peek(BATCH_SIZE)
+      if (records.length === 0) {
+        current.pending = 0
+        return
+      }
+      await invoke<void>(pluginCommand('log_batch'), { records })
+      await current.store.remove(keys)
+      current.pending = await current.store.count()
+    }
+  } catch {
+    // The backend is not available yet
+    if (state === current) {
+      current.retry = setTimeout(() => void flush(), current.retryIntervalMs)
+    }
+  } finally {
+    current.flushing = false
+  }
+}
+
+function request<T>(req: IDBRequest<T>): Promise<T> {
+  return new Promise((resolve, reject) => {
+    req.onsuccess = () => resolve(req.result)
+    req.onerror = () => reject(req.error)
+  })
+}
+
+async function openStore(name: string): Promise<RecordStore> {
+  const open = indexedDB.open(name, 1)
+  open.onupgradeneeded = () =>
+    open.result.createObjectStore(STORE_NAME, { autoIncrement: true })
+  const db = await request(open)
+  const objectStore = (mode: IDBTransactionMode) =>
+    db.transaction(STORE_NAME, mode).objectStore(STORE_NAME)
+
+  return {
+    async add(record, maxRecords) {
+      const store = objectStore('readwrite')
+      await request(store.add(record))
+      const excess = (await request(store.count())) - maxRecords
+      if (excess > 0) {
+        const oldest = await request(store.getAllKeys(null, excess))
+        await request(
+          store.delete(IDBKeyRange.bound(oldest[0], oldest[oldest.length - 1]))
+        )
+      }
+    },
+    async peek(limit) {
+      const store = objectStore('readonly')
+      return Promise.all([
+        request(store.getAllKeys(null, limit)),
+        request(store.getAll(null, limit) as IDBRequest<PendingRecord[]>)
+      ])
+    },
+    async remove(keys) {
+      if (keys.length > 0) {
+        await request(
+          objectStore('readwrite').delete(
+            IDBKeyRange.bound(keys[0], keys[keys.length - 1])
+          )
+        )
+      }
+    },
+    count: () => request(objectStore('readonly').count()),
+    close: () => db.close()
+  }
+}
+
+function memoryStore(): RecordStore {
+  let records: [number, PendingRecord][] = []
+  let nextKey = 0
+
+  return {
+    async add(record, maxRecords) {
+      records.push([nextKey++, record])
+      records = records.slice(-maxRecords)
+    },
+    async peek(limit) {
+      const oldest = records.slice(0, limit)
+      return [oldest.map(([key]) => key), oldest.map(([, record]) => record)]
+    },
+    async remove(keys) {
+      const last = keys[keys.length - 1] as number
+      records = records.filter(([key]) => key > last)
+    },
+    count: async () => records.length,
+    close: () => {}
+  }
+}
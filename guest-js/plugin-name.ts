@@ -0,0 +1,44 @@
+/**
+ * The name the backend plugin is registered under.
+ * @module
+ */
+
+let pluginName = 'tracing'
+
+/**
+ * Sets the name the backend plugin is registered under.
+ *
+ * Apps that register the plugin with `Builder::with_plugin_name()` must call
+ * this before any other function of this package, so commands are invoked
+ * and events are listened to under that name.
+ *
+ * @param name - The name passed to `Builder::with_plugin_name()`
+ *
+ * @example
+ * ```ts
+ * import { setPluginName, info } from '@fltsci/tauri-plugin-tracing';
+ *
+ * setPluginName('tracing-admin');
+ * info('admin panel loaded');
+ * ```
+ */
+export function setPluginName(name: string): void {
+  pluginName = name
+}
+
+/**
+ * Returns the IPC name of a plugin command, e.g. `plugin:tracing|log`.
+ *
+ * @param command - The command name
+ * @returns The command name prefixed with the plugin name
+ */
+export const pluginCommand = (command: string): string =>
+  `plugin:${pluginName}|${command}`
+
+/**
+ * Returns the channel of a plugin event, e.g. `tracing://log`.
+ *
+ * @param event - The event name
+ * @returns The event name prefixed with the plugin name
+ */
+export const pluginEvent = (event: string): string => `${pluginName}://${event}`
@@ -0,0 +1,231 @@
+/**
+ * Span-aware CPU profiling, and saved sessions for comparing performance
+ * across app versions.
+ *
+ * These require the `profiling` feature in the Rust plugin, the profiling
+ * plugin registered with `init_profiling()`, and the `allow-` permission of
+ * each command, which are not part of the default set.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import type { ResourceSnapshot } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * A saved profiling session, as returned by {@link listProfilingSessions}.
+ */
+export interface ProfilingSession {
+  /** The session ID, for {@link getProfilingSession} */
+  id: string
+  /** The report file */
+  path: string
+  /** The app version that recorded the session */
+  appVersion: string
+  /** When the session was saved, in milliseconds since the Unix epoch */
+  createdAtMs: number
+  /** Total profile duration in microseconds */
+  durationUs: number
+  /** Total samples collected */
+  sampleCount: number
+  /** The span that made the backend's profiling watchdog record the session */
+  trigger: string | null
+}
+
+/**
+ * A span that was active during CPU profiling.
+ */
+export interface ActiveSpan {
+  /** Span name */
+  name: string
+  /** Recorded span fields, which distinguish instances of the span */
+  fields?: Record<string, string>
+  /** Total time the span was active, in microseconds */
+  totalTimeUs: number
+  /** Number of times the span was entered */
+  enterCount: number
+  /** Percentage of the profile duration the span was active */
+  percentage: number
+}
+
+/**
+ * A recorded span timing event.
+ */
+export interface SpanEvent {
+  /** Span name */
+  name: string
+  /** Span ID */
+  spanId: number
+  /** Parent span ID, 0 if none */
+  parentId: number
+  /** Event type */
+  eventType: 'enter' | 'exit' | 'close'
+  /** Time since the capture started, in microseconds */
+  timestampUs: number
+  /** Ordinal of the thread where the event occurred, numbered from 1 */
+  threadOrdinal: number
+  /** Name of the thread where the event occurred, if it has one */
+  threadName: string | null
+  /** Recorded span fields, such as `id` and `path` */
+  fields?: Record<string, string>
+}
+
+/**
+ * The spans that were active on one thread during CPU profiling.
+ */
+export interface ThreadSpans {
+  /** Thread ordinal, as in {@link SpanEvent} */
+  threadOrdinal: number
+  /** Thread name, if it has one */
+  threadName: string | null
+  /** Spans that were active on the thread, by total time */
+  activeSpans: ActiveSpan[]
+}
+
+/**
+ * A CPU profile correlated with span timing, as saved by the backend.
+ */
+export interface SpanCorrelationReport {
+  /** The CPU profile */
+  profile: {
+    flamegraph_path: string
+    sample_count: number
+    duration_ms: number
+  }
+  /** Total profile duration in microseconds */
+  durationUs: number
+  /** Spans that were active during profiling, by total time */
+  activeSpans: ActiveSpan[]
+  /** Spans that were active on each thread, by thread ordinal */
+  threads: ThreadSpans[]
+  /** Raw span events */
+  events: SpanEvent[]
+  /** Latest resource snapshot, if the resource monitor is enabled */
+  resources: ResourceSnapshot | null
+  /** When and by which app version the report was created */
+  metadata: {
+    appName: string
+    appVersion: string
+    pluginVersion: string
+    os: string
+    arch: string
+    createdAtMs: number
+    trigger: string | null
+  }
+}
+
+/**
+ * Options for {@link startSpanAwareProfile}.
+ */
+export interface SpanAwareProfileOptions {
+  /** The sampling frequency in Hz */
+  frequency?: number
+  /** The file name of the flamegraph */
+  filename?: string
+}
+
+/**
+ * Starts CPU profiling and span timing capture in the backend.
+ *
+ * @example
+ * ```ts
+ * import { startSpanAwareProfile, stopSpanAwareProfile } from '@fltsci/tauri-plugin-tracing';
+ *
+ * await startSpanAwareProfile({ frequency: 100 });
+ * // ... reproduce the slow interaction ...
+ * const report = await stopSpanAwareProfile();
+ * for (const span of report.activeSpans) {
+ *   console.log(span.name, `${span.percentage.toFixed(1)}%`);
+ * }
+ * ```
+ */
+export async function startSpanAwareProfile(
+  options?: SpanAwareProfileOptions
+): Promise<void> {
+  await invoke<void>(pluginCommand('start_span_aware_profile'), { options })
+}
+
+/**
+ * Stops profiling and returns the report correlating the CPU profile with
+ * span timing. The report is also saved as a profiling session.
+ */
+export async function stopSpanAwareProfile(): Promise<SpanCorrelationReport> {
+  return await invoke<SpanCorrelationReport>(
+    pluginCommand('stop_span_aware_profile')
+  )
+}
+
+/**
+ * Returns the saved profiling sessions, newest first.
+ *
+ * @example
+ * ```ts
+ * import { listProfilingSessions, getProfilingSession } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const [latest, previous] = await listProfilingSessions();
+ * const before = await getProfilingSession(previous.id);
+ * const after = await getProfilingSession(latest.id);
+ * ```
+ */
+export async function listProfilingSessions(): Promise<ProfilingSession[]> {
+  return await invoke<ProfilingSession[]>(
+    pluginCommand('list_profiling_sessions')
+  )
+}
+
+/**
+ * Returns the report of a saved profiling session.
+ *
+ * @param id - The session ID returned by {@link listProfilingSessions}
+ */
+export async function getProfilingSession(
+  id: string
+): Promise<SpanCorrelationReport> {
+  return await invoke<SpanCorrelationReport>(
+    pluginCommand('get_profiling_session'),
+    { id }
+  )
+}
+
+/**
+ * The busy time statistics of a span name, returned by {@link getSpanStats}.
+ *
+ * Percentiles are estimated from power-of-two histogram buckets.
+ */
+export interface SpanStats {
+  /** Span name (target::name format) */
+  name: string
+  /** Number of times the span was entered and exited */
+  count: number
+  /** Total busy time, in microseconds */
+  totalUs: number
+  /** Mean busy time per entry, in microseconds */
+  meanUs: number
+  /** Longest busy time of an entry, in microseconds */
+  maxUs: number
+  /** Estimated median busy time, in microseconds */
+  p50Us: number
+  /** Estimated 90th percentile busy time, in microseconds */
+  p90Us: number
+  /** Estimated 99th percentile busy time, in microseconds */
+  p99Us: number
+}
+
+/**
+ * Returns the busy time statistics of each backend span name, by total time.
+ *
+ * Requires `Builder::with_span_stats()` in the backend. Statistics are kept
+ * at all times, without starting a profile.
+ *
+ * @example
+ * ```ts
+ * import { getSpanStats } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const stats = await getSpanStats();
+ * console.table(stats.slice(0, 10));
+ * ```
+ */
+export async function getSpanStats(): Promise<SpanStats[]> {
+  return await invoke<SpanStats[]>(pluginCommand('get_span_stats'))
+}
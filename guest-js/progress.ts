@@ -0,0 +1,69 @@
+/**
+ * Progress reporting of long-running tasks to the Rust backend.
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type Event, type UnlistenFn } from '@tauri-apps/api/event'
+import type { ProgressUpdate } from './types'
+import { pluginCommand, pluginEvent } from './plugin-name'
+
+/**
+ * Reports the progress of a task as `current` of `total` steps.
+ *
+ * The backend logs it under `app::progress`, at most once per second for
+ * each task besides its first and last update, and drops updates that go
+ * backwards with a warning. The task ends when `current` reaches `total`.
+ *
+ * @param taskId - The task's id, reusable once the task is done
+ * @param current - The number of steps done
+ * @param total - The number of steps in the task
+ * @param message - What the task is doing
+ *
+ * @example
+ * ```ts
+ * import { progress } from '@fltsci/tauri-plugin-tracing';
+ *
+ * for (const [n, file] of files.entries()) {
+ *   await upload(file);
+ *   progress('upload', n + 1, files.length, `uploaded ${file.name}`);
+ * }
+ * ```
+ */
+export function progress(
+  taskId: string,
+  current: number,
+  total: number,
+  message?: string
+): void {
+  invoke<void>(pluginCommand('progress'), {
+    taskId,
+    current,
+    total,
+    message
+  }).catch(console.error)
+}
+
+/**
+ * Listens to progress updates from the backend and all webviews.
+ *
+ * Requires `Builder::with_progress_events()` in the backend. Updates arrive
+ * at most 10 times per second for each task, and always when it is done.
+ *
+ * @example
+ * ```ts
+ * const unlisten = await onProgress(({ taskId, current, total, done }) => {
+ *   setProgressBar(taskId, current / total, done);
+ * });
+ * ```
+ */
+export async function onProgress(
+  fn: (update: ProgressUpdate) => void
+): Promise<UnlistenFn> {
+  return await listen(
+    pluginEvent('progress'),
+    (event: Event<ProgressUpdate>) => {
+      fn(event.payload)
+    }
+  )
+}
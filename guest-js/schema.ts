@@ -0,0 +1,29 @@
+/**
+ * JSON Schema for records and query results.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Returns a JSON Schema (draft 7) document whose `definitions` describe the
+ * records emitted on `tracing://log` (`RecordPayload`), the records sent with
+ * `log()` and `logBatch()` (`LogRecord`, `LogRecordBatch`), and the
+ * results of `getLogsAroundMark()`, `getLogStats()`, and
+ * `getEffectiveFilter()`.
+ *
+ * Requires the `schema` feature in the backend.
+ *
+ * @example
+ * ```ts
+ * import { getSchema } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const schema = await getSchema();
+ * const validate = ajv.compile({ ...schema, $ref: '#/definitions/RecordPayload' });
+ * ```
+ */
+export async function getSchema(): Promise<Record<string, unknown>> {
+  return await invoke<Record<string, unknown>>(pluginCommand('get_schema'))
+}
@@ -0,0 +1,117 @@
+/**
+ * Backend spans around frontend callbacks.
+ *
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { type SpanLogger, sendLog, withSpanContext } from './log'
+import { LogLevel, type SpanContext } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Spans whose callbacks are running, innermost last.
+ *
+ * JavaScript has no async context, so callbacks that run concurrently may
+ * see each other's spans as parents. Pass `parent` explicitly where that
+ * matters.
+ */
+const activeSpans: SpanContext[] = []
+
+/**
+ * Options for {@link withSpan}.
+ */
+export interface SpanOptions {
+  /**
+   * The span to nest under. Defaults to the innermost span whose
+   * {@link withSpan} callback is running.
+   */
+  parent?: SpanContext
+}
+
+function unboundLogger(): SpanLogger {
+  return {
+    trace: (...message) => sendLog(LogLevel.Trace, {}, ...message),
+    debug: (...message) => sendLog(LogLevel.Debug, {}, ...message),
+    info: (...message) => sendLog(LogLevel.Info, {}, ...message),
+    warn: (...message) => sendLog(LogLevel.Warn, {}, ...message),
+    error: (...message) => sendLog(LogLevel.Error, {}, ...message)
+  }
+}
+
+function describeError(e: unknown): string {
+  if (e instanceof Error) {
+    return e.stack ?? `${e.name}: ${e.message}`
+  }
+  return String(e)
+}
+
+/**
+ * Returns the innermost span whose {@link withSpan} callback is running.
+ */
+export function currentSpan(): SpanContext | undefined {
+  return activeSpans[activeSpans.length - 1]
+}
+
+/**
+ * Runs a callback inside a backend span and measures how long it takes.
+ *
+ * The span nests under the innermost running `withSpan()` callback, or under
+ * `options.parent`. If the callback throws or rejects, the exception is
+ * recorded as the span's error and rethrown.
+ *
+ * @param name - A short name for the span, such as `'save-clicked'`
+ * @param fn - The callback, which receives a logger bound to the span
+ * @param options - Optional span settings
+ * @returns The callback's result
+ *
+ * @example
+ * ```ts
+ * import { withSpan } from '@fltsci/tauri-plugin-tracing';
+ *
+ * button.onclick = () =>
+ *   withSpan('save-clicked', async (log) => {
+ *     log.debug('saving', doc.id);
+ *     await save(doc);
+ *   });
+ * ```
+ */
+export async function withSpan<T>(
+  name: string,
+  fn: (log: SpanLogger) => T | Promise<T>,
+  options: SpanOptions = {}
+): Promise<T> {
+  const parent = options.parent ?? currentSpan()
+  const context = await invoke<SpanContext | null>(pluginCommand('span_start'), {
+    name,
+    parent
+  }).catch(() => null)
+
+  if (context) {
+    activeSpans.push(context)
+  }
+  const started = performance.now()
+  let error: string | undefined
+  try {
+    return await fn(context ? withSpanContext(context) : unboundLogger())
+  } catch (e) {
+    error = describeError(e)
+    if (!context) {
+      // The span is disabled, but the exception should still be recorded
+      sendLog(LogLevel.Error, {}, `${name} failed`, error)
+    }
+    throw e
+  } finally {
+    if (context) {
+      const index = activeSpans.lastIndexOf(context)
+      if (index !== -1) {
+        activeSpans.splice(index, 1)
+      }
+      invoke<void>(pluginCommand('span_end'), {
+        context,
+        durationMs: performance.now() - started,
+        error
+      }).catch(console.error)
+    }
+  }
+}
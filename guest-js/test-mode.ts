@@ -0,0 +1,50 @@
+/**
+ * Per-test log capture for end-to-end test suites.
+ * @module
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import type { TestLogs } from './types'
+import { pluginCommand } from './plugin-name'
+
+/**
+ * Starts capturing the logs of the end-to-end test `name`.
+ *
+ * Requires `Builder::with_test_mode()` in the backend and the
+ * `tracing:test-mode` permission, which is not part of the default set. A
+ * test that has not ended yet is discarded.
+ *
+ * @param name - The test's name, e.g. its full title
+ *
+ * @example
+ * ```ts
+ * import { beginTest, endTest } from '@fltsci/tauri-plugin-tracing';
+ *
+ * beforeEach(async function () {
+ *   await beginTest(this.currentTest.fullTitle());
+ * });
+ * ```
+ */
+export async function beginTest(name: string): Promise<void> {
+  await invoke(pluginCommand('begin_test'), { name })
+}
+
+/**
+ * Stops capturing the logs of the end-to-end test `name` and returns them.
+ *
+ * The logs are also written to `tests/{name}.log` in the app log directory,
+ * with characters that are unsafe in file names replaced with `_`.
+ *
+ * @param name - The name passed to `beginTest()`
+ *
+ * @example
+ * ```ts
+ * afterEach(async function () {
+ *   const logs = await endTest(this.currentTest.fullTitle());
+ *   if (this.currentTest.state === 'failed') attach(logs.path);
+ * });
+ * ```
+ */
+export async function endTest(name: string): Promise<TestLogs> {
+  return await invoke<TestLogs>(pluginCommand('end_test'), { name })
+}
@@ -0,0 +1,79 @@
+/**
+ * Timers in the style of `console.time()`, logged to the Rust backend.
+ * @module
+ */
+
+import { debug, warn } from './log'
+
+const timers = new Map<string, number>()
+
+function elapsed(label: string): number | undefined {
+  const started = timers.get(label)
+  if (started === undefined) {
+    warn(`Timer '${label}' does not exist`)
+    return undefined
+  }
+  return performance.now() - started
+}
+
+/**
+ * Starts a timer with the given label.
+ *
+ * Like `console.time()`, starting a timer that is already running logs a
+ * warning and keeps the original start time.
+ *
+ * @param label - The timer's name. Defaults to `default`.
+ *
+ * @example
+ * ```ts
+ * import { time, timeEnd } from '@fltsci/tauri-plugin-tracing';
+ *
+ * time('load');
+ * await loadProject();
+ * timeEnd('load'); // Logs "load: 123.456ms" at the debug level
+ * ```
+ */
+export function time(label = 'default'): void {
+  if (timers.has(label)) {
+    warn(`Timer '${label}' already exists`)
+    return
+  }
+  timers.set(label, performance.now())
+}
+
+/**
+ * Logs the time elapsed since {@link time} was called with `label`, at the
+ * debug level, and keeps the timer running.
+ *
+ * @param label - The timer's name. Defaults to `default`.
+ * @param data - Values logged after the elapsed time
+ * @returns The elapsed time in milliseconds, or `undefined` if the timer does
+ * not exist
+ */
+export function timeLog(
+  label = 'default',
+  ...data: unknown[]
+): number | undefined {
+  const ms = elapsed(label)
+  if (ms !== undefined) {
+    debug(`${label}: ${ms.toFixed(3)}ms`, ...data)
+  }
+  return ms
+}
+
+/**
+ * Logs the time elapsed since {@link time} was called with `label`, at the
+ * debug level, and stops the timer.
+ *
+ * @param label - The timer's name. Defaults to `default`.
+ * @returns The elapsed time in milliseconds, or `undefined` if the timer does
+ * not exist
+ */
+export function timeEnd(label = 'default'): number | undefined {
+  const ms = elapsed(label)
+  if (ms !== undefined) {
+    timers.delete(label)
+    debug(`${label}: ${ms.toFixed(3)}ms`)
+  }
+  return ms
+}
@@ -0,0 +1,102 @@
+/**
+ * W3C Trace Context helpers for webview-initiated HTTP requests.
+ * @module
+ */
+
+import { sendLog } from './log'
+import { LogLevel, type SpanContext } from './types'
+
+/**
+ * Returns `bytes` random bytes as a lowercase hex string.
+ *
+ * @param bytes - The number of random bytes to generate
+ * @returns The hex-encoded bytes
+ */
+function randomHex(bytes: number): string {
+  const buffer = new Uint8Array(bytes)
+  crypto.getRandomValues(buffer)
+  return Array.from(buffer, (b) => b.toString(16).padStart(2, '0')).join('')
+}
+
+/**
+ * Creates a W3C `traceparent` header value.
+ *
+ * When a backend `SpanContext` is given, the header continues that trace so
+ * the request is correlated with the backend span; otherwise a new trace is
+ * started with random ids.
+ *
+ * @param context - Optional backend span to continue
+ * @returns A `traceparent` header value
+ *
+ * @example
+ * ```ts
+ * import { createTraceparent } from '@fltsci/tauri-plugin-tracing';
+ *
+ * const headers = { traceparent: createTraceparent(span) };
+ * ```
+ */
+export function createTraceparent(context?: SpanContext): string {
+  const traceId = context?.traceId ?? randomHex(16)
+  const spanId = context?.spanId ?? randomHex(8)
+  return `00-${traceId}-${spanId}-01`
+}
+
+/**
+ * Options for {@link tracedFetch}.
+ */
+export interface TracedFetchOptions {
+  /** A backend span to continue. A new trace is started if omitted. */
+  context?: SpanContext
+  /**
+   * The fetch implementation to use, e.g. `fetch` from `@tauri-apps/plugin-http`.
+   * Defaults to the global `fetch`.
+   */
+  fetch?: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
+}
+
+/**
+ * Performs an HTTP request carrying a W3C `traceparent` header.
+ *
+ * The request and its outcome are logged through the backend with the same
+ * `traceparent` value, so backend logs can be correlated with server-side
+ * tracing systems.
+ *
+ * @param input - The resource to fetch
+ * @param init - Request options; existing headers are preserved
+ * @param options - Trace context and fetch implementation
+ * @returns The response
+ *
+ * @example
+ * ```ts
+ * import { tracedFetch } from '@fltsci/tauri-plugin-tracing';
+ * import { fetch } from '@tauri-apps/plugin-http';
+ *
+ * const response = await tracedFetch('https://api.example.com/items', {}, { fetch });
+ * ```
+ */
+export async function tracedFetch(
+  input: RequestInfo | URL,
+  init: RequestInit = {},
+  options: TracedFetchOptions = {}
+): Promise<Response> {
+  const traceparent = createTraceparent(options.context)
+  const headers = new Headers(init.headers)
+  headers.set('traceparent', traceparent)
+
+  const method = init.method ?? 'GET'
+  const url = input instanceof Request ? input.url : String(input)
+  const doFetch = options.fetch ?? fetch
+
+  try {
+    const response = await doFetch(input, { ...init, headers })
+    sendLog(
+      LogLevel.Debug,
+      { traceparent },
+      `${method} ${url} -> ${response.status}`
+    )
+    return response
+  } catch (err) {
+    sendLog(LogLevel.Error, { traceparent }, `${method} ${url} failed:`, err)
+    throw err
+  }
+}
@@ -63,6 +63,289 @@ export interface RecordPayload {
   level: LogLevel
   /** The log message content */
   message: LogMessage
+  /** The event's fields other than `message`, rendered as strings, in recorded order */
+  fields?: Record<string, string>
+  /** The events leading up to an error, oldest first, when breadcrumbs are enabled */
+  breadcrumbs?: Breadcrumb[]
+  /** The record's sequence number, shared by every output, when sequence numbers are enabled */
+  seq?: number
+  /** The number of records dropped on the way to the webview since the previous record, if any were */
+  gap?: number
+}
+
+/**
+ * An event recorded in the breadcrumb trail of an error or the backend's
+ * log history.
+ */
+export interface Breadcrumb {
+  /** When the event was recorded, in milliseconds since the Unix epoch */
+  timestampMs: number
+  /** The event's level */
+  level: LogLevel
+  /** The event's target */
+  target: string
+  /** The event's message */
+  message: string
+}
+
+/**
+ * Identifies a backend span that frontend log records can be attached to.
+ *
+ * Obtained from a command response (see `SpanContextExt` in the Rust crate)
+ * and passed back via `withSpanContext()`.
+ */
+export interface SpanContext {
+  /** 128-bit trace identifier, as 32 hex characters */
+  traceId: string
+  /** 64-bit span identifier, as 16 hex characters */
+  spanId: string
+}
+
+/**
+ * A frame that exceeded the long-frame threshold.
+ */
+export interface LongFrame {
+  /** When the frame started, in milliseconds since the Unix epoch */
+  timestampMs: number
+  /** How long the frame took, in milliseconds */
+  durationMs: number
+}
+
+/**
+ * Aggregate frame timing statistics returned by the backend.
+ */
+export interface FrameStats {
+  /** Number of frames in the batch */
+  count: number
+  /** Mean frame duration in milliseconds */
+  meanMs: number
+  /** Median frame duration in milliseconds */
+  p50Ms: number
+  /** 95th percentile frame duration in milliseconds */
+  p95Ms: number
+  /** 99th percentile frame duration in milliseconds */
+  p99Ms: number
+  /** Longest frame duration in milliseconds */
+  maxMs: number
+}
+
+/**
+ * A reading of the backend's clock, returned by `sync_clock`.
+ */
+export interface ClockSample {
+  /** Milliseconds on the backend's monotonic clock since the plugin started */
+  monotonicMs: number
+  /** The same instant in milliseconds since the Unix epoch */
+  wallMs: number
+}
+
+/**
+ * The backend's log filter in effect, returned by `get_effective_filter`.
+ *
+ * Levels are the most verbose level recorded; `null` means nothing is recorded.
+ */
+export interface EffectiveFilter {
+  /** The level for targets without a more specific level */
+  level: LogLevel | null
+  /** Levels for targets and their submodules */
+  targets: Record<string, LogLevel | null>
+}
+
+/**
+ * The event counts of a crate, as returned by `analyzeNoise()`.
+ */
+export interface CrateNoise {
+  /** The crate name, the first segment of its targets */
+  name: string
+  /** The number of events */
+  events: number
+  /** The number of `INFO`, `DEBUG`, and `TRACE` events */
+  verboseEvents: number
+  /** The share of all events, from 0 to 1 */
+  share: number
+}
+
+/**
+ * A level recommended for a target by `analyzeNoise()`.
+ */
+export interface NoiseRecommendation {
+  /** The target to set the level of */
+  target: string
+  /** The recommended level */
+  level: LogLevel
+  /** The number of events during the analysis the level would have removed */
+  eventsRemoved: number
+}
+
+/**
+ * The result of `analyzeNoise()`.
+ */
+export interface NoiseReport {
+  /** How long events were counted, in milliseconds */
+  windowMs: number
+  /** The number of events during the analysis */
+  totalEvents: number
+  /** The event counts of each crate, noisiest first */
+  crates: CrateNoise[]
+  /** The recommended levels, noisiest first */
+  recommendations: NoiseRecommendation[]
+}
+
+/**
+ * The events dropped by the filter for a target.
+ */
+export interface TargetDrops {
+  /** The event target, usually a module path */
+  target: string
+  /** The number of dropped events */
+  events: number
+}
+
+/**
+ * The result of `getFilterStats()`.
+ */
+export interface FilterStats {
+  /** How long events were counted, in milliseconds */
+  windowMs: number
+  /** The number of dropped events */
+  totalEvents: number
+  /** The dropped events of each target, most dropped first */
+  targets: TargetDrops[]
+}
+
+/**
+ * How often an invariant was violated, as returned by
+ * `getInvariantViolations()`.
+ */
+export interface ViolatedInvariant {
+  /** The invariant's id */
+  id: string
+  /** The number of violations since the app started */
+  violations: number
+  /** When it was last violated, in milliseconds since the Unix epoch */
+  lastViolationMs: number
+}
+
+/**
+ * A progress update, as passed to `onProgress()` listeners.
+ */
+export interface ProgressUpdate {
+  /** The task's id */
+  taskId: string
+  /** The number of steps done */
+  current: number
+  /** The number of steps in the task */
+  total: number
+  /** What the task is doing */
+  message: string | null
+  /** Whether the task is complete */
+  done: boolean
+}
+
+/**
+ * A batch of metrics, as passed to `onMetrics()` listeners.
+ */
+export interface MetricsBatch {
+  /** The latest value of each gauge set since the previous batch */
+  gauges: Record<string, number>
+  /** The increments of each counter since the previous batch */
+  counters: Record<string, number>
+  /** The totals since the app started of the counters in `counters` */
+  totals: Record<string, number>
+}
+
+/**
+ * The logs of an end-to-end test, as returned by `endTest()`.
+ */
+export interface TestLogs {
+  /** The test's name */
+  name: string
+  /** The file the logs were written to, or `null` if it could not be written */
+  path: string | null
+  /** The events recorded during the test, oldest first */
+  records: Breadcrumb[]
+  /** The number of events over the limit of 100 000, which are not captured */
+  dropped: number
+}
+
+/**
+ * The records around a marker, as returned by `getLogsAroundMark()`.
+ *
+ * Found in the backend's in-memory log history, or otherwise in a log file as
+ * formatted lines. File lines do not extend into other files.
+ */
+export type MarkContext =
+  | {
+      source: 'memory'
+      before: Breadcrumb[]
+      mark: Breadcrumb
+      after: Breadcrumb[]
+    }
+  | {
+      source: 'file'
+      path: string
+      before: string[]
+      mark: string
+      after: string[]
+    }
+
+/**
+ * The size of the file target's logs, as returned by `getLogStats()`.
+ */
+export interface LogStats {
+  /** The directory logs are written to, or `null` without a file target */
+  logDir: string | null
+  /** The file this instance is writing to */
+  currentFile: string | null
+  /** The size of the current file in bytes */
+  currentFileBytes: number
+  /** The number of log files other than the current one */
+  rotatedFiles: number
+  /** The size of all log files in bytes */
+  totalBytes: number
+  /** When the newest rotated file was last written to, in milliseconds since the Unix epoch */
+  lastRotationMs: number | null
+}
+
+/**
+ * The last lines of the logs, as returned by `tailLogs()`.
+ */
+export interface LogTail {
+  /** The file the lines were read from, or `null` without a file target */
+  path: string | null
+  /** The lines, oldest first */
+  lines: string[]
+  /** Whether new lines are emitted to `onTailLines()` listeners */
+  following: boolean
+}
+
+/**
+ * New lines of the followed log file, passed to `onTailLines()` listeners.
+ */
+export interface TailLines {
+  /** The file the lines were read from */
+  path: string
+  /** The new lines, oldest first */
+  lines: string[]
+}
+
+/**
+ * How a long-running operation finished.
+ */
+export type OperationOutcome = 'success' | 'failure' | 'cancelled'
+
+/**
+ * Describes an open operation, as returned by the backend.
+ */
+export interface OperationHandle {
+  /** The operation's stable id */
+  id: string
+  /** The name the operation was started with */
+  name: string
+  /** Context for logging under the operation's span, if it is enabled */
+  spanContext: SpanContext | null
+  /** Milliseconds since the operation started */
+  elapsedMs: number
 }
 
 /**
@@ -71,3 +354,17 @@ export interface RecordPayload {
  * @param payload - The log record containing level and message
  */
 export type LoggerFn = (payload: RecordPayload) => void
+
+/**
+ * Process resource usage, as sampled by the backend's resource monitor.
+ */
+export interface ResourceSnapshot {
+  /** Resident set size in bytes */
+  rssBytes: number | null
+  /** CPU usage since the previous sample, as a percentage of one core */
+  cpuPercent: number | null
+  /** Number of open file descriptors */
+  openFds: number | null
+  /** Number of threads in the process */
+  threadCount: number | null
+}
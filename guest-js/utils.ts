@@ -63,6 +63,31 @@ export function getCircularReplacer() {
 export const cleanUntypedValue = (value: unknown): string =>
   stripAnsi(JSON.stringify(value, getCircularReplacer()))
 
+/**
+ * Converts a log argument to a value that survives IPC with its structure intact.
+ *
+ * Strings are stripped of ANSI codes. Objects, arrays, numbers, booleans and
+ * `null` are kept as JSON values (circular references become `"[Circular]"`)
+ * so the backend can record them as structured data. Values JSON cannot
+ * represent, such as `undefined`, functions and errors, are converted to strings.
+ *
+ * @param value - Any value passed to a logging function
+ * @returns A JSON-compatible representation of the value
+ */
+export const cleanValue = (value: unknown): unknown => {
+  if (typeof value === 'string') {
+    return stripAnsi(value)
+  }
+  if (value instanceof Error) {
+    return stripAnsi(`${value.name}: ${value.message}`)
+  }
+  const json = JSON.stringify(value, getCircularReplacer())
+  if (json === undefined) {
+    return stripAnsi(value)
+  }
+  return JSON.parse(stripAnsi(json))
+}
+
 /**
  * Performs printf-style string formatting like console.log.
  *
@@ -112,14 +137,15 @@ export function formatPrintf(
 /**
  * Sanitizes a log message for transmission to the Rust backend.
  *
- * Handles printf-style format strings (like console.log), strips ANSI codes,
- * and converts values to safe string representations.
+ * Handles printf-style format strings (like console.log) and strips ANSI
+ * codes. Arguments that are not consumed by the format string keep their
+ * structure (see {@link cleanValue}), so objects reach the backend as JSON.
  *
  * @param message - The log message to clean
  * @returns A sanitized LogMessage array
  */
 export const cleanMessage = (message: LogMessage): LogMessage => {
-  const safeMessage: string[] = []
+  const safeMessage: unknown[] = []
   if (typeof message === 'string') {
     safeMessage.push(stripAnsi(message))
   } else if (Array.isArray(message)) {
@@ -132,11 +158,11 @@ export const cleanMessage = (message: LogMessage): LogMessage => {
       const [formatted, remaining] = formatPrintf(message[0], message.slice(1))
       safeMessage.push(stripAnsi(formatted))
       for (const arg of remaining) {
-        safeMessage.push(stripAnsi(arg))
+        safeMessage.push(cleanValue(arg))
       }
     } else {
       for (const msg of message) {
-        safeMessage.push(stripAnsi(msg))
+        safeMessage.push(cleanValue(msg))
       }
     }
   } else if (typeof message === 'object') {
@@ -151,3 +177,129 @@ export const cleanMessage = (message: LogMessage): LogMessage => {
   }
   return safeMessage as LogMessage
 }
+
+/**
+ * Limits on frontend records configured with the backend's
+ * `with_max_message_len()` and `with_max_stack_frames()`.
+ */
+export interface LogLimits {
+  /** Maximum length of the message and its structured arguments */
+  maxMessageLen?: number | null
+  /** Maximum number of call stack frames */
+  maxStackFrames?: number | null
+}
+
+/**
+ * Returns the limits the backend passed to this webview, if any.
+ *
+ * @returns The configured limits, or an empty object if none were set
+ */
+export function logLimits(): LogLimits {
+  const scope = globalThis as { __TAURI_PLUGIN_TRACING_LIMITS__?: LogLimits }
+  return scope.__TAURI_PLUGIN_TRACING_LIMITS__ ?? {}
+}
+
+/**
+ * Cuts a string to just over `maxLen` UTF-16 code units without splitting a
+ * surrogate pair.
+ *
+ * The text is kept past the limit, so the backend still sees it as too long
+ * and cuts it at the exact byte length.
+ */
+const cutText = (text: string, maxLen: number): string => {
+  if (text.length <= maxLen + 1) {
+    return text
+  }
+  const end = /[\ud800-\udbff]/.test(text[maxLen]) ? maxLen + 2 : maxLen + 1
+  return text.slice(0, end)
+}
+
+const cutStrings = (value: unknown, maxLen: number): unknown => {
+  if (typeof value === 'string') {
+    return cutText(value, maxLen)
+  }
+  if (Array.isArray(value)) {
+    return value.map((item) => cutStrings(item, maxLen))
+  }
+  if (typeof value === 'object' && value !== null) {
+    return Object.fromEntries(
+      Object.entries(value).map(([key, item]) => [
+        key,
+        cutStrings(item, maxLen)
+      ])
+    )
+  }
+  return value
+}
+
+/**
+ * Shortens the strings in a structured argument until its JSON is within
+ * twice `maxLen`, but never within `maxLen` itself, so the backend still
+ * shortens it to the exact limit and marks the record as truncated.
+ *
+ * Arguments that cannot be shortened that way, such as huge arrays, are sent
+ * as their cut JSON text.
+ */
+const limitArg = (arg: object, maxLen: number): unknown => {
+  let cut = cutStrings(arg, maxLen)
+  let json = JSON.stringify(cut)
+  for (let cap = maxLen >> 1; json.length > 2 * maxLen && cap > 0; cap >>= 1) {
+    const next = cutStrings(arg, cap)
+    const nextJson = JSON.stringify(next)
+    if (nextJson.length <= maxLen) {
+      break
+    }
+    cut = next
+    json = nextJson
+  }
+  return json.length > 2 * maxLen ? cutText(json, maxLen) : cut
+}
+
+/**
+ * Shortens a cleaned message so an oversized record does not cross IPC.
+ *
+ * Strings, including those nested in structured arguments, are cut just past
+ * `maxLen` and left for the backend to truncate exactly, add the `…` marker
+ * to, and mark as truncated. See the backend's `with_max_message_len()`.
+ *
+ * @param message - A message returned by {@link cleanMessage}
+ * @param maxLen - The backend's maximum message length
+ * @returns The shortened message
+ */
+export function limitMessage(
+  message: LogMessage,
+  maxLen: number | null | undefined
+): LogMessage {
+  if (maxLen == null) {
+    return message
+  }
+  return message.map((part: unknown) =>
+    typeof part === 'object' && part !== null
+      ? limitArg(part, maxLen)
+      : cutStrings(part, maxLen)
+  ) as LogMessage
+}
+
+/**
+ * Shortens a call stack so an oversized record does not cross IPC.
+ *
+ * The `Error` line that starts V8 stacks is not counted as a frame. One frame
+ * more than `maxFrames` is kept, so the backend still marks the record as
+ * truncated.
+ *
+ * @param stack - The call stack of the log call
+ * @param maxFrames - The backend's maximum number of frames
+ * @returns The shortened call stack
+ */
+export function limitStack(
+  stack: string | undefined,
+  maxFrames: number | null | undefined
+): string | undefined {
+  if (stack === undefined || maxFrames == null) {
+    return stack
+  }
+  const lines = stack.split('\n')
+  const header =
+    !lines[0].trimStart().startsWith('at ') && !lines[0].includes('@')
+  return lines.slice(0, maxFrames + 1 + Number(header)).join('\n')
+}
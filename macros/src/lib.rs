@@ -0,0 +1,203 @@
+//! Procedural macros for [`tauri-plugin-tracing`](https://docs.rs/tauri-plugin-tracing).
+//!
+//! Use them through the `macros` feature of `tauri-plugin-tracing`, which
+//! re-exports them.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, quote};
+use syn::{FnArg, ItemFn, Pat, ReturnType, Type, parse_macro_input};
+
+/// Parameter types that Tauri injects rather than deserializing from the
+/// frontend. They carry no useful value to log.
+const INJECTED_TYPES: &[&str] = &[
+    "AppHandle",
+    "Channel",
+    "Request",
+    "State",
+    "Webview",
+    "WebviewWindow",
+    "Window",
+];
+
+/// Logs invocations of a Tauri command under the `ipc::command` target.
+///
+/// Place it above `#[tauri::command]`. Each call runs in an `INFO` span named
+/// `command` with a `command` field holding the function name and one field
+/// per argument, recorded with its `Debug` representation. A `DEBUG` event is
+/// logged when the command is invoked and when it completes, with its
+/// `duration_ms`. If the command returns a `Result` and it is an `Err`, a
+/// `WARN` event with the `error` is logged instead.
+///
+/// Arguments can be annotated:
+///
+/// - `#[redact]` records the argument as `"[redacted]"`, so its type does not
+///   need to implement `Debug`.
+/// - `#[skip]` leaves the argument out.
+///
+/// Arguments that Tauri injects, such as `State`, `AppHandle`, and
+/// `WebviewWindow`, are left out automatically.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tauri_plugin_tracing::traced_command;
+///
+/// #[traced_command]
+/// #[tauri::command]
+/// async fn sign_in(user: String, #[redact] password: String) -> Result<(), String> {
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn traced_command(args: TokenStream, item: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            proc_macro2::TokenStream::from(args)
+                .into_iter()
+                .next()
+                .map_or_else(proc_macro2::Span::call_site, |token| token.span()),
+            "`traced_command` takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let function = parse_macro_input!(item as ItemFn);
+    expand(function).into()
+}
+
+fn expand(mut function: ItemFn) -> TokenStream2 {
+    let tracing = quote!(::tauri_plugin_tracing::tracing);
+    let name = function.sig.ident.to_string();
+
+    let mut fields = Vec::new();
+    for input in &mut function.sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            continue;
+        };
+        let redact = take_attribute(&mut arg.attrs, "redact");
+        let skip = take_attribute(&mut arg.attrs, "skip");
+        let Pat::Ident(pat) = &*arg.pat else {
+            continue;
+        };
+        if skip || is_injected(&arg.ty) {
+            continue;
+        }
+        let ident = &pat.ident;
+        if redact {
+            fields.push(quote!(#ident = "[redacted]"));
+        } else {
+            fields.push(quote!(#ident = ?#ident));
+        }
+    }
+
+    let block = &function.block;
+    let body = if function.sig.asyncness.is_some() {
+        quote! {
+            #tracing::Instrument::instrument(async move #block, __traced_span.clone()).await
+        }
+    } else {
+        quote!(__traced_span.in_scope(|| #block))
+    };
+    let result_type = match &function.sig.output {
+        ReturnType::Type(_, ty) if !contains_impl_trait(ty) => Some(quote!(: #ty)),
+        _ => None,
+    };
+    let report = if returns_result(&function.sig.output) {
+        quote! {
+            match &__traced_result {
+                ::core::result::Result::Ok(_) => #tracing::debug!(
+                    target: "ipc::command",
+                    parent: &__traced_span,
+                    command = #name,
+                    duration_ms = __traced_ms,
+                    "command completed"
+                ),
+                ::core::result::Result::Err(error) => #tracing::warn!(
+                    target: "ipc::command",
+                    parent: &__traced_span,
+                    command = #name,
+                    duration_ms = __traced_ms,
+                    error = ?error,
+                    "command failed"
+                ),
+            }
+        }
+    } else {
+        quote! {
+            #tracing::debug!(
+                target: "ipc::command",
+                parent: &__traced_span,
+                command = #name,
+                duration_ms = __traced_ms,
+                "command completed"
+            );
+        }
+    };
+
+    let ItemFn {
+        attrs, vis, sig, ..
+    } = &function;
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __traced_span = #tracing::info_span!(
+                target: "ipc::command",
+                "command",
+                command = #name,
+                #(#fields,)*
+                duration_ms = #tracing::field::Empty,
+            );
+            #tracing::debug!(
+                target: "ipc::command",
+                parent: &__traced_span,
+                command = #name,
+                "command invoked"
+            );
+            let __traced_start = ::std::time::Instant::now();
+            let __traced_result #result_type = #body;
+            let __traced_ms = __traced_start.elapsed().as_secs_f64() * 1000.0;
+            __traced_span.record("duration_ms", __traced_ms);
+            #report
+            __traced_result
+        }
+    }
+}
+
+/// Removes the attribute `name` from `attrs`, returning whether it was there.
+fn take_attribute(attrs: &mut Vec<syn::Attribute>, name: &str) -> bool {
+    let before = attrs.len();
+    attrs.retain(|attr| !attr.path().is_ident(name));
+    attrs.len() != before
+}
+
+/// Returns the last path segment of a type, looking through references.
+fn last_segment(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(reference) => last_segment(&reference.elem),
+        _ => None,
+    }
+}
+
+fn is_injected(ty: &Type) -> bool {
+    last_segment(ty).is_some_and(|segment| INJECTED_TYPES.contains(&segment.as_str()))
+}
+
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => last_segment(ty).is_some_and(|segment| segment == "Result"),
+        ReturnType::Default => false,
+    }
+}
+
+fn contains_impl_trait(ty: &Type) -> bool {
+    fn walk(tokens: TokenStream2) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => ident == "impl",
+            proc_macro2::TokenTree::Group(group) => walk(group.stream()),
+            _ => false,
+        })
+    }
+    walk(ty.to_token_stream())
+}
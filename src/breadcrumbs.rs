@@ -0,0 +1,186 @@
+//! Breadcrumb trails attached to error events.
+//!
+//! An error on its own rarely explains how the app got there. [`BreadcrumbLayer`]
+//! keeps the most recent `DEBUG` and higher events in a rolling buffer, and
+//! when an `ERROR` event is recorded, from the backend or a webview, it makes
+//! the buffer available as that event's breadcrumb trail.
+//!
+//! The trail is added as a `breadcrumbs=[...]` JSON field by the plugin's
+//! formatters and as the `breadcrumbs` property of webview payloads. Custom
+//! layers, e.g. an error reporter, can read it with [`current_breadcrumbs()`].
+//!
+//! Only events that pass the subscriber's filter are recorded, so `DEBUG`
+//! breadcrumbs require a filter that enables them.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::{Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::layer::Context;
+
+use crate::Breadcrumb;
+
+thread_local! {
+    static CURRENT_TRAIL: RefCell<Option<Vec<Breadcrumb>>> = const { RefCell::new(None) };
+}
+
+/// Records `event` as it is being dispatched.
+pub(crate) fn breadcrumb_from_event(event: &tracing::Event<'_>) -> Breadcrumb {
+    let metadata = event.metadata();
+    let mut message = MessageVisitor::default();
+    event.record(&mut message);
+    Breadcrumb {
+        timestamp_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0,
+        level: (*metadata.level()).into(),
+        target: metadata.target().to_string(),
+        message: message.0,
+    }
+}
+
+/// Returns the breadcrumb trail of the `ERROR` event currently being
+/// dispatched on this thread, oldest first.
+///
+/// Only meaningful while an event is being recorded by layers that sit
+/// inside a [`BreadcrumbLayer`]. Returns `None` for other events.
+pub fn current_breadcrumbs() -> Option<Vec<Breadcrumb>> {
+    CURRENT_TRAIL.with(|trail| trail.borrow().clone())
+}
+
+/// A tracing layer that attaches recent events to `ERROR` events.
+///
+/// Add the layer after the output layers, so it sees events before they are
+/// written and the outputs can read [`current_breadcrumbs()`].
+///
+/// # Example
+///
+/// Use [`Builder::with_breadcrumbs()`](crate::Builder::with_breadcrumbs) with
+/// the default subscriber, or add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{BreadcrumbFields, BreadcrumbLayer};
+/// # use tracing_subscriber::{Registry, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+/// Registry::default()
+///     .with(fmt::layer().fmt_fields(BreadcrumbFields::new(fmt::format::DefaultFields::new())))
+///     .with(BreadcrumbLayer::new(20))
+///     .init();
+/// ```
+#[derive(Debug)]
+pub struct BreadcrumbLayer {
+    trail: Mutex<VecDeque<Breadcrumb>>,
+    capacity: usize,
+}
+
+impl BreadcrumbLayer {
+    /// Creates a layer that keeps the last `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            trail: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Returns the number of events kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns a copy of the events recorded so far, oldest first.
+    pub fn trail(&self) -> Vec<Breadcrumb> {
+        self.trail
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for BreadcrumbLayer {
+    fn event_enabled(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) -> bool {
+        // Inner layers record the event after this, so they see its trail
+        let trail = (*event.metadata().level() == Level::ERROR).then(|| self.trail());
+        CURRENT_TRAIL.with(|current| *current.borrow_mut() = trail);
+        true
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        CURRENT_TRAIL.with(|current| current.borrow_mut().take());
+
+        if *event.metadata().level() > Level::DEBUG || self.capacity == 0 {
+            return;
+        }
+        let breadcrumb = breadcrumb_from_event(event);
+
+        let mut trail = self.trail.lock().unwrap_or_else(|e| e.into_inner());
+        if trail.len() >= self.capacity {
+            trail.pop_front();
+        }
+        trail.push_back(breadcrumb);
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.0 = value.to_string();
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+/// Wraps a field formatter to append `breadcrumbs=[...]` to `ERROR` events.
+///
+/// The plugin's formatters use this automatically. When composing your own
+/// subscriber, pass it to `fmt::layer().fmt_fields()`.
+#[derive(Debug, Clone, Default)]
+pub struct BreadcrumbFields<N> {
+    inner: N,
+}
+
+impl<N> BreadcrumbFields<N> {
+    /// Wraps `inner`.
+    pub fn new(inner: N) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'writer, N> FormatFields<'writer> for BreadcrumbFields<N>
+where
+    N: for<'w> FormatFields<'w>,
+{
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> std::fmt::Result {
+        self.inner.format_fields(writer.by_ref(), fields)?;
+        let trail = CURRENT_TRAIL.with(|trail| {
+            trail
+                .borrow()
+                .as_ref()
+                .and_then(|trail| serde_json::to_string(trail).ok())
+        });
+        if let Some(trail) = trail {
+            write!(writer, " breadcrumbs={trail}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Build metadata of the app.
+//!
+//! Logs collected from the field are hard to match to the build that wrote
+//! them. The [`build_info!`](crate::build_info) macro captures the app's
+//! build variant, git hash, and enabled Cargo features where it is expanded,
+//! and [`Builder::with_build_info()`](crate::Builder::with_build_info) adds
+//! them to the resource attributes of the remote and RFC 5424 outputs, as
+//! `build.variant`, `build.git_hash`, and `build.features`.
+//!
+//! The build variant also selects the target filters set with
+//! [`Builder::with_variant_target()`](crate::Builder::with_variant_target),
+//! such as `TRACE` for the app's own modules in debug builds only, without
+//! `cfg` blocks in app code.
+
+use serde::Serialize;
+
+/// Whether the app was built with debug assertions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum BuildVariant {
+    /// Built with `debug_assertions`, as by `tauri dev`.
+    Debug,
+    /// Built without `debug_assertions`, as by `tauri build`.
+    Release,
+}
+
+impl BuildVariant {
+    /// Returns the variant this crate was built as.
+    ///
+    /// Dependencies are built with the app's profile, so this is the app's
+    /// variant unless the profile overrides the settings of dependencies.
+    /// [`build_info!`](crate::build_info) checks the app's own.
+    pub const fn current() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Debug
+        } else {
+            Self::Release
+        }
+    }
+}
+
+impl std::fmt::Display for BuildVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Debug => "debug",
+            Self::Release => "release",
+        })
+    }
+}
+
+/// The build variant, git hash, and enabled features of the app.
+///
+/// Usually created with [`build_info!`](crate::build_info).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct BuildInfo {
+    /// Whether the app was built with debug assertions.
+    pub variant: BuildVariant,
+    /// The git commit the app was built from.
+    pub git_hash: Option<String>,
+    /// The enabled Cargo features of the app.
+    pub features: Vec<String>,
+}
+
+impl BuildInfo {
+    /// Creates build metadata with no git hash or features.
+    pub fn new(variant: BuildVariant) -> Self {
+        Self {
+            variant,
+            git_hash: None,
+            features: Vec::new(),
+        }
+    }
+
+    /// Sets the git commit the app was built from.
+    pub fn with_git_hash(mut self, git_hash: impl Into<String>) -> Self {
+        self.git_hash = Some(git_hash.into());
+        self
+    }
+
+    /// Adds enabled Cargo features of the app.
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns the resource attributes describing the build.
+    pub(crate) fn attributes(&self) -> Vec<(String, String)> {
+        let mut attributes = vec![("build.variant".to_string(), self.variant.to_string())];
+        if let Some(git_hash) = &self.git_hash {
+            attributes.push(("build.git_hash".to_string(), git_hash.clone()));
+        }
+        if !self.features.is_empty() {
+            attributes.push(("build.features".to_string(), self.features.join(",")));
+        }
+        attributes
+    }
+}
+
+/// Captures the [`BuildInfo`] of the crate the macro is expanded in.
+///
+/// The variant follows the crate's `debug_assertions`. The git hash is read
+/// from the `GIT_HASH` environment variable at compile time, which the app's
+/// build script can set with `cargo:rustc-env=GIT_HASH=...`. The features
+/// are those of the listed ones that are enabled.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{Builder, build_info};
+/// Builder::new()
+///     .with_build_info(build_info!("beta-channel", "sync"))
+///     .with_default_subscriber()
+///     .build::<tauri::Wry>();
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    ($($feature:literal),* $(,)?) => {{
+        let variant = if cfg!(debug_assertions) {
+            $crate::BuildVariant::Debug
+        } else {
+            $crate::BuildVariant::Release
+        };
+        let features: &[(&str, bool)] = &[$(($feature, cfg!(feature = $feature))),*];
+        let info = $crate::BuildInfo::new(variant).with_features(
+            features
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(feature, _)| *feature),
+        );
+        match option_env!("GIT_HASH") {
+            Some(git_hash) => info.with_git_hash(git_hash),
+            None => info,
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_the_build_as_attributes() {
+        let info = BuildInfo::new(BuildVariant::Release)
+            .with_git_hash("4f2c1e9")
+            .with_features(["sync", "beta-channel"]);
+        assert_eq!(
+            info.attributes(),
+            [
+                ("build.variant".to_string(), "release".to_string()),
+                ("build.git_hash".to_string(), "4f2c1e9".to_string()),
+                (
+                    "build.features".to_string(),
+                    "sync,beta-channel".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            BuildInfo::new(BuildVariant::Debug).attributes(),
+            [("build.variant".to_string(), "debug".to_string())]
+        );
+    }
+}
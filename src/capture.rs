@@ -0,0 +1,173 @@
+//! Capture of native stdout/stderr output.
+//!
+//! C libraries and `println!` debugging in dependencies write straight to the
+//! process's stdout and stderr file descriptors, bypassing tracing entirely.
+//! [`OutputCapture`] redirects those descriptors through pipes and re-emits
+//! every captured line as an event under the [`CAPTURED_STDOUT_TARGET`]
+//! target at `INFO` or the [`CAPTURED_STDERR_TARGET`] target at `WARN`, so
+//! the output reaches log files and the webview like any other event.
+//!
+//! While capture is active, anything written to the original streams must go
+//! through [`OutputCapture::stdout_writer()`] or
+//! [`OutputCapture::stderr_writer()`]. A layer that writes to
+//! [`std::io::stdout`] would feed its own output back into the capture.
+//! The plugin's own stdout and stderr targets do this automatically.
+
+use std::fs::File;
+use std::sync::Arc;
+
+/// The target used for lines captured from stdout.
+pub const CAPTURED_STDOUT_TARGET: &str = "captured::stdout";
+
+/// The target used for lines captured from stderr.
+pub const CAPTURED_STDERR_TARGET: &str = "captured::stderr";
+
+/// Redirects the process's stdout and stderr into tracing events.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_output_capture()`](crate::Builder::with_output_capture) is
+/// used. Dropping it restores the original descriptors.
+///
+/// Only supported on Unix platforms.
+pub struct OutputCapture {
+    stdout: Arc<File>,
+    stderr: Arc<File>,
+    #[cfg(unix)]
+    _redirects: [unix::Redirect; 2],
+}
+
+impl OutputCapture {
+    /// Starts capturing stdout and stderr.
+    ///
+    /// Returns [`Error::NotImplemented`](crate::Error::NotImplemented) on
+    /// platforms without Unix file descriptors.
+    #[cfg(unix)]
+    pub fn start() -> crate::Result<Self> {
+        use std::io::Write;
+
+        // Flush buffered output so it lands on the original streams
+        let _ = std::io::stdout().flush();
+        let _ = std::io::stderr().flush();
+
+        let stdout = unix::Redirect::new(libc::STDOUT_FILENO, CAPTURED_STDOUT_TARGET)?;
+        let stderr = unix::Redirect::new(libc::STDERR_FILENO, CAPTURED_STDERR_TARGET)?;
+        Ok(Self {
+            stdout: stdout.original()?,
+            stderr: stderr.original()?,
+            _redirects: [stdout, stderr],
+        })
+    }
+
+    /// Starts capturing stdout and stderr.
+    ///
+    /// Returns [`Error::NotImplemented`](crate::Error::NotImplemented) on
+    /// platforms without Unix file descriptors.
+    #[cfg(not(unix))]
+    pub fn start() -> crate::Result<Self> {
+        Err(crate::Error::NotImplemented)
+    }
+
+    /// Returns a writer for the original stdout, bypassing the capture.
+    ///
+    /// The returned value implements [`MakeWriter`](tracing_subscriber::fmt::MakeWriter),
+    /// so it can be passed to `fmt::layer().with_writer()`.
+    pub fn stdout_writer(&self) -> Arc<File> {
+        self.stdout.clone()
+    }
+
+    /// Returns a writer for the original stderr, bypassing the capture.
+    pub fn stderr_writer(&self) -> Arc<File> {
+        self.stderr.clone()
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::fs::File;
+    use std::io::{BufRead, BufReader};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+    use std::sync::Arc;
+
+    /// One redirected descriptor and the saved original it replaced.
+    pub(super) struct Redirect {
+        fd: RawFd,
+        saved: OwnedFd,
+    }
+
+    impl Redirect {
+        /// Points `fd` at a new pipe and spawns a thread that logs each line.
+        pub(super) fn new(fd: RawFd, target: &'static str) -> std::io::Result<Self> {
+            let saved = dup(fd)?;
+
+            let mut fds = [0 as RawFd; 2];
+            // SAFETY: `fds` is a valid two-element buffer for pipe(2)
+            if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // SAFETY: pipe(2) succeeded, so both descriptors are open and owned by us
+            let (read, write) =
+                unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+            // SAFETY: both descriptors are open; dup2 atomically replaces `fd`
+            if unsafe { libc::dup2(write.as_raw_fd(), fd) } < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // `fd` now holds the only write end, so the reader sees EOF once it is restored
+            drop(write);
+
+            std::thread::Builder::new()
+                .name(format!("tracing-{target}"))
+                .spawn(move || forward_lines(File::from(read), target))?;
+
+            Ok(Self { fd, saved })
+        }
+
+        /// Returns a handle to the original stream.
+        pub(super) fn original(&self) -> std::io::Result<Arc<File>> {
+            Ok(Arc::new(File::from(dup(self.saved.as_raw_fd())?)))
+        }
+    }
+
+    impl Drop for Redirect {
+        fn drop(&mut self) {
+            // SAFETY: both descriptors are open; this restores the original stream
+            unsafe {
+                libc::dup2(self.saved.as_raw_fd(), self.fd);
+            }
+        }
+    }
+
+    fn dup(fd: RawFd) -> std::io::Result<OwnedFd> {
+        // SAFETY: dup(2) has no memory safety requirements
+        let new = unsafe { libc::dup(fd) };
+        if new < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: dup(2) succeeded, so `new` is an open descriptor we own
+        Ok(unsafe { OwnedFd::from_raw_fd(new) })
+    }
+
+    fn forward_lines(read: File, target: &'static str) {
+        let mut reader = BufReader::new(read);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) | Err(_) => return,
+                Ok(_) => {
+                    let text = String::from_utf8_lossy(&line);
+                    let text = text.trim_end_matches(['\n', '\r']);
+                    if text.is_empty() {
+                        continue;
+                    }
+                    // Event targets must be constant, so branch on the known values
+                    if target == super::CAPTURED_STDERR_TARGET {
+                        tracing::warn!(target: super::CAPTURED_STDERR_TARGET, "{text}");
+                    } else {
+                        tracing::info!(target: super::CAPTURED_STDOUT_TARGET, "{text}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Clock synchronization between webviews and the backend.
+//!
+//! Webview timestamps are taken from `performance.timeOrigin +
+//! performance.now()`, which can drift from the backend's clock by several
+//! milliseconds, enough to misorder events in a merged timeline. The
+//! `sync_clock` command lets each window measure and store the offset between
+//! the two clocks, which [`ClockSync`] then applies to the window's timestamps.
+//!
+//! ```javascript
+//! import { syncClock } from '@fltsci/tauri-plugin-tracing';
+//!
+//! const offsetMs = await syncClock();
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// A reading of the backend's clock, as returned by `sync_clock`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ClockSample {
+    /// Milliseconds on the backend's monotonic clock since the plugin started.
+    pub monotonic_ms: f64,
+    /// The same instant in milliseconds since the Unix epoch.
+    ///
+    /// Derived from the monotonic clock, so it never jumps backwards.
+    pub wall_ms: f64,
+}
+
+/// The measured offset of one window's clock.
+#[derive(Debug, Clone, Copy)]
+struct ClockOffset {
+    offset_ms: f64,
+    round_trip_ms: f64,
+}
+
+/// Clock offsets of webview windows relative to the backend.
+///
+/// The JavaScript `syncClock()` helper samples the backend's clock with the
+/// `sync_clock` command, keeps the sample with the shortest round trip, and
+/// assumes the backend read its clock halfway through that call. The offset
+/// it computes is sent back and stored here for the calling window, then added
+/// to every timestamp that window sends, in log records and frame timing
+/// reports. Offsets are discarded when a page reloads.
+///
+/// The plugin manages an instance of this type in Tauri state.
+#[derive(Debug)]
+pub struct ClockSync {
+    anchor: Instant,
+    anchor_wall_ms: f64,
+    offsets: Mutex<HashMap<String, ClockOffset>>,
+}
+
+impl Default for ClockSync {
+    fn default() -> Self {
+        Self {
+            anchor: Instant::now(),
+            anchor_wall_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs_f64() * 1000.0)
+                .unwrap_or_default(),
+            offsets: Mutex::default(),
+        }
+    }
+}
+
+impl ClockSync {
+    /// Reads the backend's clock.
+    pub fn sample(&self) -> ClockSample {
+        let monotonic_ms = self.anchor.elapsed().as_secs_f64() * 1000.0;
+        ClockSample {
+            monotonic_ms,
+            wall_ms: self.anchor_wall_ms + monotonic_ms,
+        }
+    }
+
+    /// Stores the offset of `window`'s clock, in milliseconds to add to its
+    /// timestamps.
+    ///
+    /// `round_trip_ms` is the duration of the call the offset was measured
+    /// with; the error of the offset is at most half of it.
+    pub fn set_offset(&self, window: &str, offset_ms: f64, round_trip_ms: f64) {
+        if !offset_ms.is_finite() {
+            return;
+        }
+        tracing::debug!(
+            w = window,
+            offset_ms,
+            round_trip_ms,
+            "synchronized webview clock"
+        );
+        self.offsets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                window.to_string(),
+                ClockOffset {
+                    offset_ms,
+                    round_trip_ms,
+                },
+            );
+    }
+
+    /// Returns the stored offset of `window`'s clock, if it has been synced.
+    pub fn offset(&self, window: &str) -> Option<f64> {
+        self.offsets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(window)
+            .map(|offset| offset.offset_ms)
+    }
+
+    /// Returns the round trip of the call `window`'s offset was measured with.
+    pub fn round_trip(&self, window: &str) -> Option<f64> {
+        self.offsets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(window)
+            .map(|offset| offset.round_trip_ms)
+    }
+
+    /// Converts a timestamp from `window` to the backend's clock.
+    ///
+    /// Timestamps from windows that have not been synced are returned as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tauri_plugin_tracing::ClockSync;
+    ///
+    /// let clock = ClockSync::default();
+    /// assert_eq!(clock.to_backend_time("main", 1000.0), 1000.0);
+    ///
+    /// clock.set_offset("main", 12.5, 2.0);
+    /// assert_eq!(clock.to_backend_time("main", 1000.0), 1012.5);
+    /// ```
+    pub fn to_backend_time(&self, window: &str, timestamp_ms: f64) -> f64 {
+        timestamp_ms + self.offset(window).unwrap_or_default()
+    }
+
+    /// Discards the offset of `window`, e.g. because its page reloaded.
+    pub fn forget(&self, window: &str) {
+        self.offsets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(window);
+    }
+}
@@ -0,0 +1,329 @@
+//! Color themes for terminal output.
+//!
+//! `tracing-subscriber` colors terminal output with a fixed palette: a color
+//! per level, dimmed timestamps, targets and separators, and italic field
+//! names. [`ThemedWriter`] rewrites those ANSI codes as they are written, so
+//! a [`ColorTheme`] applies to the Full, Compact, and Pretty formats alike.
+
+use std::borrow::Cow;
+use std::io::Write;
+
+use colored::Color;
+use tracing::Level;
+
+/// The colors of terminal output.
+///
+/// Use with [`Builder::with_color_theme()`](crate::Builder::with_color_theme),
+/// or wrap a writer in [`ThemedWriter`] for a custom subscriber.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{Builder, Color, ColorTheme};
+/// # use tracing::Level;
+/// let theme = ColorTheme::no_dim()
+///     .with_level_color(Level::DEBUG, Color::Cyan)
+///     .with_field_names(Color::BrightWhite);
+///
+/// Builder::new().with_color_theme(theme).with_default_subscriber();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorTheme {
+    trace: Color,
+    debug: Color,
+    info: Color,
+    warn: Color,
+    error: Color,
+    bold_levels: bool,
+    dim: bool,
+    field_names: Option<Color>,
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self {
+            trace: Color::Magenta,
+            debug: Color::Blue,
+            info: Color::Green,
+            warn: Color::Yellow,
+            error: Color::Red,
+            bold_levels: false,
+            dim: true,
+            field_names: None,
+        }
+    }
+}
+
+impl ColorTheme {
+    /// Creates a theme with `tracing-subscriber`'s colors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a theme with bright, bold level colors, no dimmed text, and
+    /// bright white field names, for low-contrast terminals.
+    pub fn high_contrast() -> Self {
+        Self {
+            trace: Color::BrightMagenta,
+            debug: Color::BrightCyan,
+            info: Color::BrightGreen,
+            warn: Color::BrightYellow,
+            error: Color::BrightRed,
+            bold_levels: true,
+            dim: false,
+            field_names: Some(Color::BrightWhite),
+        }
+    }
+
+    /// Creates a theme with `tracing-subscriber`'s level colors that does not
+    /// dim timestamps, targets, and separators.
+    pub fn no_dim() -> Self {
+        Self {
+            dim: false,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the color of a level.
+    ///
+    /// The Pretty format also uses it for the message and fields.
+    pub fn with_level_color(mut self, level: Level, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        match level {
+            Level::TRACE => self.trace = color,
+            Level::DEBUG => self.debug = color,
+            Level::INFO => self.info = color,
+            Level::WARN => self.warn = color,
+            Level::ERROR => self.error = color,
+        }
+        self
+    }
+
+    /// Sets whether level colors are bold.
+    pub fn with_bold_levels(mut self, bold: bool) -> Self {
+        self.bold_levels = bold;
+        self
+    }
+
+    /// Sets whether timestamps, targets, and separators are dimmed.
+    pub fn with_dimming(mut self, dim: bool) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    /// Highlights field names in `color` rather than in italics.
+    pub fn with_field_names(mut self, color: impl Into<Color>) -> Self {
+        self.field_names = Some(color.into());
+        self
+    }
+
+    /// Returns the color of a level.
+    pub fn level_color(&self, level: Level) -> Color {
+        match level {
+            Level::TRACE => self.trace,
+            Level::DEBUG => self.debug,
+            Level::INFO => self.info,
+            Level::WARN => self.warn,
+            Level::ERROR => self.error,
+        }
+    }
+
+    /// Returns the SGR parameters replacing `params`, or `None` to drop the
+    /// sequence.
+    fn remap<'a>(&self, params: &'a str) -> Option<Cow<'a, str>> {
+        // Leave resets and extended colors, whose arguments look like basic
+        // colors, alone
+        if params.is_empty() || params.split(';').any(|p| p == "38" || p == "48") {
+            return Some(Cow::Borrowed(params));
+        }
+        // Italic alone marks field names; Pretty combines it with dim for
+        // source locations
+        if params == "3"
+            && let Some(color) = self.field_names
+        {
+            return Some(color.to_fg_str());
+        }
+        let mut mapped: Vec<Cow<'_, str>> = Vec::new();
+        for param in params.split(';') {
+            let level = match param {
+                "31" => Level::ERROR,
+                "32" => Level::INFO,
+                "33" => Level::WARN,
+                "34" => Level::DEBUG,
+                "35" => Level::TRACE,
+                "2" if !self.dim => continue,
+                _ => {
+                    mapped.push(Cow::Borrowed(param));
+                    continue;
+                }
+            };
+            if self.bold_levels && !mapped.iter().any(|p| *p == "1") {
+                mapped.push(Cow::Borrowed("1"));
+            }
+            mapped.push(self.level_color(level).to_fg_str());
+        }
+        (!mapped.is_empty()).then(|| Cow::Owned(mapped.join(";")))
+    }
+
+    /// Rewrites the SGR sequences in `buf` and writes the result to `writer`.
+    fn write_themed<W: Write>(&self, writer: &mut W, buf: &[u8]) -> std::io::Result<()> {
+        // Fast path: nothing to rewrite without an ESC byte
+        let Some(first_esc) = memchr::memchr(0x1b, buf) else {
+            return writer.write_all(buf);
+        };
+
+        let mut output = Vec::with_capacity(buf.len() + 16);
+        output.extend_from_slice(&buf[..first_esc]);
+        let mut i = first_esc;
+        while i < buf.len() {
+            if buf[i] != 0x1b || buf.get(i + 1) != Some(&b'[') {
+                output.push(buf[i]);
+                i += 1;
+                continue;
+            }
+            // Find the final byte of the CSI sequence
+            let start = i + 2;
+            let Some(len) = buf[start..].iter().position(|b| (0x40..=0x7e).contains(b)) else {
+                output.extend_from_slice(&buf[i..]);
+                break;
+            };
+            let end = start + len;
+            let params = std::str::from_utf8(&buf[start..end]).ok();
+            match params {
+                Some(params) if buf[end] == b'm' => {
+                    if let Some(params) = self.remap(params) {
+                        output.extend_from_slice(b"\x1b[");
+                        output.extend_from_slice(params.as_bytes());
+                        output.push(b'm');
+                    }
+                }
+                _ => output.extend_from_slice(&buf[i..=end]),
+            }
+            i = end + 1;
+        }
+        writer.write_all(&output)
+    }
+}
+
+/// A [`MakeWriter`](tracing_subscriber::fmt::MakeWriter) that applies a
+/// [`ColorTheme`] to ANSI-colored output.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_plugin_tracing::{ColorTheme, ThemedWriter};
+/// use tauri_plugin_tracing::tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
+///
+/// tracing_subscriber::registry()
+///     .with(fmt::layer().with_writer(ThemedWriter::new(std::io::stdout, ColorTheme::high_contrast())))
+///     .init();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThemedWriter<M> {
+    inner: M,
+    theme: ColorTheme,
+}
+
+impl<M> ThemedWriter<M> {
+    /// Creates a writer that writes to `inner` with `theme`'s colors.
+    pub fn new(inner: M, theme: ColorTheme) -> Self {
+        Self { inner, theme }
+    }
+}
+
+/// A writer handle returned by the [`MakeWriter`](tracing_subscriber::fmt::MakeWriter) implementation.
+///
+/// This type implements [`std::io::Write`] and rewrites ANSI codes during writes.
+pub struct ThemedWriterGuard<'a, W> {
+    inner: W,
+    theme: &'a ColorTheme,
+}
+
+impl<W: Write> Write for ThemedWriterGuard<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.theme.write_themed(&mut self.inner, buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<'a, M> tracing_subscriber::fmt::MakeWriter<'a> for ThemedWriter<M>
+where
+    M: tracing_subscriber::fmt::MakeWriter<'a>,
+{
+    type Writer = ThemedWriterGuard<'a, M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        ThemedWriterGuard {
+            inner: self.inner.make_writer(),
+            theme: &self.theme,
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+        ThemedWriterGuard {
+            inner: self.inner.make_writer_for(meta),
+            theme: &self.theme,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn themed(theme: &ColorTheme, input: &[u8]) -> String {
+        let mut output = Vec::new();
+        theme.write_themed(&mut output, input).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn default_theme_keeps_output() {
+        let input =
+            b"\x1b[2m2024-01-01\x1b[0m \x1b[32m INFO\x1b[0m \x1b[3mkey\x1b[0m\x1b[2m=\x1b[0m1";
+        assert_eq!(themed(&ColorTheme::new(), input).as_bytes(), input);
+    }
+
+    #[test]
+    fn level_colors_are_replaced() {
+        let theme = ColorTheme::new().with_level_color(Level::INFO, Color::BrightGreen);
+        assert_eq!(
+            themed(&theme, b"\x1b[32m INFO\x1b[0m"),
+            "\x1b[92m INFO\x1b[0m"
+        );
+        assert_eq!(
+            themed(&theme, b"\x1b[1;32mmsg\x1b[0m"),
+            "\x1b[1;92mmsg\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn no_dim_drops_dim_sequences() {
+        let output = themed(&ColorTheme::no_dim(), b"\x1b[2mtarget:\x1b[0m msg");
+        assert_eq!(output, "target:\x1b[0m msg");
+        let output = themed(&ColorTheme::no_dim(), b"\x1b[2;3mat src/main.rs\x1b[0m");
+        assert_eq!(output, "\x1b[3mat src/main.rs\x1b[0m");
+    }
+
+    #[test]
+    fn high_contrast_bolds_levels_and_highlights_fields() {
+        let theme = ColorTheme::high_contrast();
+        assert_eq!(
+            themed(&theme, b"\x1b[31mERROR\x1b[0m"),
+            "\x1b[1;91mERROR\x1b[0m"
+        );
+        assert_eq!(themed(&theme, b"\x1b[3mkey\x1b[0m"), "\x1b[97mkey\x1b[0m");
+    }
+
+    #[test]
+    fn extended_colors_and_other_sequences_are_kept() {
+        let theme = ColorTheme::high_contrast();
+        let input = b"\x1b[38;5;31mx\x1b[0m\x1b[2Ky\x1b[";
+        assert_eq!(themed(&theme, input).as_bytes(), input);
+    }
+}
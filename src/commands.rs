@@ -1,35 +1,518 @@
 //! Tauri command handlers for the tracing plugin.
 
-use crate::callstack::{CallStack, CallStackLine};
-use crate::layer::{LogLevel, LogMessage};
-use tauri::Runtime;
+#[cfg(feature = "callstack")]
+use crate::CallStack;
+use crate::clock::{ClockSample, ClockSync};
+use crate::context::LogContext;
+use crate::filter::{EffectiveFilter, FilterHandle};
+use crate::filter_stats::FilterStats;
+use crate::frames::{FrameStats, FrameTimingReport, record_frame_report};
+use crate::log_files::{LogFiles, LogStats};
+use crate::log_tail::LogTail;
+use crate::marks::MarkContext;
+use crate::noise::{NoiseRecommendation, NoiseReport};
+use crate::operations::{OperationHandle, OperationOutcome, OperationRegistry};
+use crate::routes::CurrentRoutes;
+use crate::span_context::{SpanContext, SpanContextRegistry};
+use crate::test_mode::{TestCapture, TestLogs};
+use crate::webview_spans;
+use crate::{LogLevel, LogMessage};
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::time::Duration;
+use tauri::{Runtime, State};
 use tracing::Level;
+use tracing::field::display;
+use tracing_subscriber::filter::LevelFilter;
 
 #[cfg(feature = "flamegraph")]
 use tauri::AppHandle;
 
+/// Size limits applied to records received by the [`log`] command.
+///
+/// The plugin manages an instance of this type in Tauri state, configured with
+/// [`Builder::with_max_message_len()`](crate::Builder::with_max_message_len)
+/// and [`Builder::with_max_stack_frames()`](crate::Builder::with_max_stack_frames).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogLimits {
+    /// Maximum message length in bytes.
+    pub max_message_len: Option<usize>,
+    /// Maximum number of call stack frames.
+    pub max_stack_frames: Option<usize>,
+}
+
+impl LogLimits {
+    /// Returns a script that passes the limits to the guest bindings, so they
+    /// can apply them before sending records over IPC.
+    pub(crate) fn init_script(&self) -> String {
+        let limits = serde_json::json!({
+            "maxMessageLen": self.max_message_len,
+            "maxStackFrames": self.max_stack_frames,
+        });
+        format!("window.__TAURI_PLUGIN_TRACING_LIMITS__ = Object.freeze({limits});")
+    }
+}
+
+/// Marker appended to text cut short by [`LogLimits`].
+const TRUNCATION_MARKER: &str = "…";
+
+/// The largest buffer [`emit_record`] keeps between records, in bytes, so one
+/// huge record does not pin its memory for the lifetime of the thread.
+const MAX_RETAINED_CAPACITY: usize = 64 * 1024;
+
+/// The number of records `get_logs_around_mark` returns on each side of a
+/// marker by default.
+const DEFAULT_MARK_CONTEXT: usize = 20;
+
+/// How long `analyze_noise` counts events by default.
+const DEFAULT_NOISE_WINDOW_MS: u64 = 10_000;
+
+/// The longest noise analysis a webview can request, as each holds a
+/// blocking thread for its whole window.
+const MAX_NOISE_WINDOW_MS: u64 = 60_000;
+
+/// Shortens `text` to at most `max_len` bytes, respecting char boundaries.
+///
+/// Returns `true` if the text was truncated.
+fn truncate_text(text: &mut String, max_len: usize) -> bool {
+    if text.len() <= max_len {
+        return false;
+    }
+    let mut end = max_len;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(TRUNCATION_MARKER);
+    true
+}
+
+/// Shortens the structured `args` of `message` to at most `max_len` bytes.
+///
+/// The strings inside the arguments are shortened, so `args` stays valid
+/// JSON, and `args` is dropped if that is not enough. Returns `true` if the
+/// arguments were shortened or dropped.
+fn truncate_args(message: &LogMessage, args: &mut Option<String>, max_len: usize) -> bool {
+    if args.as_ref().is_none_or(|json| json.len() <= max_len) {
+        return false;
+    }
+    let mut max_string_len = max_len;
+    while max_string_len > 0 {
+        max_string_len /= 2;
+        let parts: Vec<serde_json::Value> = message
+            .iter()
+            .filter(|part| !part.is_string())
+            .cloned()
+            .map(|mut part| {
+                truncate_strings(&mut part, max_string_len);
+                part
+            })
+            .collect();
+        if let Ok(json) = serde_json::to_string(&parts)
+            && json.len() <= max_len
+        {
+            *args = Some(json);
+            return true;
+        }
+    }
+    *args = None;
+    true
+}
+
+/// Shortens every string in `value` to at most `max_len` bytes.
+fn truncate_strings(value: &mut serde_json::Value, max_len: usize) {
+    match value {
+        serde_json::Value::String(text) => {
+            truncate_text(text, max_len);
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                truncate_strings(item, max_len);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                truncate_strings(field, max_len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Keeps only the first `max_frames` frames of a call stack.
+///
+/// The `Error: message` line that V8 puts before the frames is kept and not
+/// counted. Returns `true` if frames were dropped.
+#[cfg(feature = "callstack")]
+fn truncate_stack(stack: &str, max_frames: usize) -> (&str, bool) {
+    let header = stack.split('\n').next().is_some_and(is_stack_header);
+    let max_lines = max_frames + usize::from(header);
+    match stack.match_indices('\n').nth(max_lines.saturating_sub(1)) {
+        Some((end, _)) if max_lines > 0 => (&stack[..end], true),
+        Some(_) => ("", true),
+        None => (stack, false),
+    }
+}
+
+/// Returns `true` if the first line of a call stack is V8's `Error: message`
+/// header rather than a frame. Frames start with `at` in V8 and contain `@`
+/// in other engines.
+#[cfg(feature = "callstack")]
+fn is_stack_header(line: &str) -> bool {
+    !line.trim_start().starts_with("at ") && !line.contains('@')
+}
+
+/// A log record sent by the frontend.
+///
+/// The [`log`] command takes these fields as individual arguments, and
+/// [`log_batch`] takes a list of records.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogRecord {
+    /// The record's level.
+    pub level: LogLevel,
+    /// The message parts.
+    pub message: LogMessage,
+    /// The JavaScript call stack where the record was created.
+    #[serde(default)]
+    pub call_stack: Option<String>,
+    /// The target of a record forwarded from another `tracing` subscriber,
+    /// such as Rust compiled to WebAssembly. It is shown before the location.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// A backend span to record the message under.
+    #[serde(default)]
+    pub span_context: Option<SpanContext>,
+    /// A W3C `traceparent` header value to record with the message.
+    #[serde(default)]
+    pub traceparent: Option<String>,
+    /// When the record was created in the webview, in milliseconds since the
+    /// Unix epoch (`performance.timeOrigin + performance.now()`).
+    ///
+    /// Adjusted by the window's [`ClockSync`] offset, if it has been synced.
+    #[serde(default)]
+    pub timestamp: Option<f64>,
+    /// The page's route when the record was created, e.g. `/settings#sync`.
+    #[serde(default)]
+    pub route: Option<String>,
+    /// The id of the page load that created the record, which changes when
+    /// the webview reloads.
+    #[serde(default)]
+    pub session: Option<String>,
+}
+
+/// Formats a webview timestamp as an RFC 3339 string in UTC.
+///
+/// Rounds to whole microseconds, the best precision `performance.now()` offers.
+fn logical_time(epoch_ms: f64) -> Option<String> {
+    if !epoch_ms.is_finite() {
+        return None;
+    }
+    let nanos = (epoch_ms * 1000.0).round() as i128 * 1000;
+    time::OffsetDateTime::from_unix_timestamp_nanos(nanos)
+        .ok()?
+        .format(&time::format_description::well_known::Rfc3339)
+        .ok()
+}
+
+/// Records a log message from the frontend.
+///
+/// When `timestamp` is given, it is recorded as the `logical_time` field, so
+/// merged timelines can order frontend events by when they happened rather
+/// than by when they arrived over IPC.
+///
+/// The record is logged in a `log` span with the window label as its `w`
+/// field, and the page's `route` and `session` id when the webview sends
+/// them, so outputs that write span fields group records by window and page.
+/// Records without a route get the one set with [`set_current_route`].
 #[tauri::command]
-#[tracing::instrument(skip_all, fields(w = %CallStackLine::from(webview_window.label())))]
+#[allow(clippy::too_many_arguments)]
 pub fn log<R: Runtime>(
     webview_window: tauri::WebviewWindow<R>,
+    span_registry: State<'_, SpanContextRegistry>,
+    operations: State<'_, OperationRegistry>,
+    limits: State<'_, LogLimits>,
+    clock: State<'_, ClockSync>,
+    routes: State<'_, CurrentRoutes>,
     level: LogLevel,
     message: LogMessage,
     call_stack: Option<&str>,
+    target: Option<String>,
+    span_context: Option<SpanContext>,
+    traceparent: Option<String>,
+    timestamp: Option<f64>,
+    route: Option<String>,
+    session: Option<String>,
 ) {
-    let stack = CallStack::from(call_stack);
-    let loc = match level {
-        LogLevel::Trace => stack.location(),
-        LogLevel::Debug => stack.path(),
-        LogLevel::Info => stack.file_name(),
-        LogLevel::Warn => stack.path(),
-        LogLevel::Error => stack.location(),
+    let record = RecordRef {
+        level: &level,
+        message: &message,
+        call_stack,
+        target: target.as_deref(),
+        span_context: span_context.as_ref(),
+        traceparent: traceparent.as_deref(),
+        timestamp,
+        route: route.as_deref(),
+        session: session.as_deref(),
     };
+    emit_record(
+        webview_window.label(),
+        &span_registry,
+        &operations,
+        &limits,
+        &clock,
+        &routes,
+        record,
+    );
+}
+
+/// Records several log messages from the frontend in one IPC call.
+///
+/// Records are emitted in order, each as if sent by [`log`].
+#[tauri::command]
+pub fn log_batch<R: Runtime>(
+    webview_window: tauri::WebviewWindow<R>,
+    span_registry: State<'_, SpanContextRegistry>,
+    operations: State<'_, OperationRegistry>,
+    limits: State<'_, LogLimits>,
+    clock: State<'_, ClockSync>,
+    routes: State<'_, CurrentRoutes>,
+    records: Vec<LogRecord>,
+) {
+    for record in &records {
+        emit_record(
+            webview_window.label(),
+            &span_registry,
+            &operations,
+            &limits,
+            &clock,
+            &routes,
+            record.borrowed(),
+        );
+    }
+}
+
+/// A [`LogRecord`] borrowed from the command arguments, so the call stack and
+/// strings are not copied.
+struct RecordRef<'a> {
+    level: &'a LogLevel,
+    message: &'a LogMessage,
+    call_stack: Option<&'a str>,
+    target: Option<&'a str>,
+    span_context: Option<&'a SpanContext>,
+    traceparent: Option<&'a str>,
+    timestamp: Option<f64>,
+    route: Option<&'a str>,
+    session: Option<&'a str>,
+}
+
+impl LogRecord {
+    fn borrowed(&self) -> RecordRef<'_> {
+        RecordRef {
+            level: &self.level,
+            message: &self.message,
+            call_stack: self.call_stack.as_deref(),
+            target: self.target.as_deref(),
+            span_context: self.span_context.as_ref(),
+            traceparent: self.traceparent.as_deref(),
+            timestamp: self.timestamp,
+            route: self.route.as_deref(),
+            session: self.session.as_deref(),
+        }
+    }
+}
+
+/// Text buffers reused by [`emit_record`] on each thread.
+#[derive(Default)]
+struct Buffers {
+    text: String,
+    loc: String,
+}
+
+thread_local! {
+    static BUFFERS: RefCell<Buffers> = RefCell::default();
+}
+
+/// Runs `f` with this thread's [`Buffers`], cleared.
+///
+/// Falls back to fresh buffers if they are in use, e.g. when a layer logs a
+/// frontend record while handling another one.
+fn with_buffers<T>(f: impl FnOnce(&mut Buffers) -> T) -> T {
+    BUFFERS.with(|buffers| match buffers.try_borrow_mut() {
+        Ok(mut buffers) => {
+            let result = f(&mut buffers);
+            for buffer in [&mut buffers.text, &mut buffers.loc] {
+                buffer.clear();
+                buffer.shrink_to(MAX_RETAINED_CAPACITY);
+            }
+            result
+        }
+        Err(_) => f(&mut Buffers::default()),
+    })
+}
+
+/// Returns whether an event at `level` from [`emit_record`] would be recorded.
+///
+/// This checks the same target as the emitted events, so records filtered out
+/// by level or target cost no more than the check. Records that only a filter
+/// on the `log` span would enable, e.g. an `EnvFilter` span directive, are
+/// dropped.
+fn record_enabled(level: &LogLevel) -> bool {
+    macro_rules! enabled {
+        ($level:expr) => {
+            tracing::enabled!(kind: tracing::metadata::Kind::EVENT, target: "", $level)
+        };
+    }
+    match level {
+        LogLevel::Trace => enabled!(Level::TRACE),
+        LogLevel::Debug => enabled!(Level::DEBUG),
+        LogLevel::Info => enabled!(Level::INFO),
+        LogLevel::Warn => enabled!(Level::WARN),
+        LogLevel::Error => enabled!(Level::ERROR),
+    }
+}
+
+/// Writes the location of a record to `out`: the part of its call stack shown
+/// at its level, after its target if it has one.
+fn write_loc(level: &LogLevel, call_stack: Option<&str>, target: Option<&str>, out: &mut String) {
+    #[cfg(feature = "callstack")]
+    {
+        let stack = CallStack::from(call_stack);
+        match level {
+            LogLevel::Trace | LogLevel::Error => stack.write_location(out),
+            LogLevel::Debug | LogLevel::Warn => out.push_str(stack.path_str()),
+            LogLevel::Info => stack.write_file_name(out),
+        }
+    }
+    // Without parsing, records are located by their target only
+    #[cfg(not(feature = "callstack"))]
+    let _ = (level, call_stack);
+    if let Some(target) = target {
+        if !out.is_empty() {
+            out.insert(0, '@');
+        }
+        out.insert_str(0, target);
+    }
+}
+
+fn emit_record(
+    label: &str,
+    span_registry: &SpanContextRegistry,
+    operations: &OperationRegistry,
+    limits: &LogLimits,
+    clock: &ClockSync,
+    routes: &CurrentRoutes,
+    record: RecordRef<'_>,
+) {
+    // Skip all work, including creating the span, for records nothing would
+    // see. This also covers levels compiled out with `max-level-*` features.
+    if !record_enabled(record.level) {
+        return;
+    }
+    with_buffers(|buffers| {
+        emit_enabled_record(
+            label,
+            span_registry,
+            operations,
+            limits,
+            clock,
+            routes,
+            record,
+            buffers,
+        )
+    });
+}
+
+fn emit_enabled_record(
+    label: &str,
+    span_registry: &SpanContextRegistry,
+    operations: &OperationRegistry,
+    limits: &LogLimits,
+    clock: &ClockSync,
+    routes: &CurrentRoutes,
+    record: RecordRef<'_>,
+    buffers: &mut Buffers,
+) {
+    let RecordRef {
+        level,
+        message,
+        call_stack,
+        target,
+        span_context,
+        traceparent,
+        timestamp,
+        route,
+        session,
+    } = record;
+    let traceparent = traceparent.and_then(SpanContext::from_traceparent);
+
+    // Re-parent under a propagated backend span when the frontend supplies one,
+    // either directly or as the parent id of a W3C traceparent, and else
+    // under the window's route span
+    let parent = span_context
+        .or(traceparent.as_ref())
+        .and_then(|ctx| span_registry.get(ctx).or_else(|| operations.span_for(ctx)))
+        .or_else(|| routes.span(label));
+    // Records sent without a route were created on the window's current one
+    let stored_route = route.is_none().then(|| routes.route(label)).flatten();
+    let route = route.or(stored_route.as_deref());
+    macro_rules! log_span {
+        ($($parent:tt)*) => {
+            tracing::info_span!(
+                $($parent)*
+                "log",
+                w = %label,
+                route = route.map(display),
+                session = session.map(display),
+                traceparent = tracing::field::Empty,
+            )
+        };
+    }
+    let span = match parent {
+        Some(parent) => log_span!(parent: &parent,),
+        None => log_span!(),
+    };
+    if let Some(traceparent) = &traceparent {
+        span.record("traceparent", traceparent.to_traceparent());
+    }
+    let _enter = span.enter();
+
+    let mut truncated = false;
+    #[cfg(feature = "callstack")]
+    let call_stack = match (call_stack, limits.max_stack_frames) {
+        (Some(stack), Some(max_frames)) => {
+            let (stack, cut) = truncate_stack(stack, max_frames);
+            truncated |= cut;
+            Some(stack)
+        }
+        (stack, _) => stack,
+    };
+
+    let Buffers { text, loc } = buffers;
+    let _ = write!(text, "{message}");
+    let mut args = message.args();
+    if let Some(max_len) = limits.max_message_len {
+        truncated |= truncate_text(text, max_len);
+        truncated |= truncate_args(message, &mut args, max_len);
+    }
+
+    write_loc(level, call_stack, target, loc);
+    let truncated = truncated.then_some(true);
+    let logical_time = timestamp
+        .map(|timestamp| clock.to_backend_time(label, timestamp))
+        .and_then(logical_time);
     macro_rules! emit_event {
         ($level:expr) => {
             tracing::event!(
                 target: "",
                 $level,
-                %message,
+                message = %text,
+                args = args.as_deref(),
+                truncated,
+                logical_time = logical_time.as_deref(),
                 "" = %loc,
             )
         };
@@ -43,6 +526,545 @@ pub fn log<R: Runtime>(
     }
 }
 
+/// Records a batch of webview frame timings.
+///
+/// Long frames are logged individually and the batch is summarized with
+/// percentile statistics, which are also returned to the caller.
+#[tauri::command]
+pub fn report_frame_times<R: Runtime>(
+    webview_window: tauri::WebviewWindow<R>,
+    clock: State<'_, ClockSync>,
+    mut report: FrameTimingReport,
+) -> Option<FrameStats> {
+    let label = webview_window.label();
+    for frame in &mut report.long_frames {
+        frame.timestamp_ms = clock.to_backend_time(label, frame.timestamp_ms);
+    }
+    record_frame_report(label, &report)
+}
+
+/// Reads the backend's clock, and stores the calling window's clock offset
+/// when one is given.
+///
+/// The JavaScript `syncClock()` helper runs the handshake described on
+/// [`ClockSync`].
+#[tauri::command]
+pub fn sync_clock<R: Runtime>(
+    webview_window: tauri::WebviewWindow<R>,
+    clock: State<'_, ClockSync>,
+    offset_ms: Option<f64>,
+    round_trip_ms: Option<f64>,
+) -> ClockSample {
+    if let Some(offset_ms) = offset_ms {
+        clock.set_offset(
+            webview_window.label(),
+            offset_ms,
+            round_trip_ms.unwrap_or_default(),
+        );
+    }
+    clock.sample()
+}
+
+/// Starts a long-running operation and returns its handle.
+///
+/// Fails if [`MAX_OPERATIONS`](crate::MAX_OPERATIONS) operations
+/// are already open.
+#[tauri::command]
+pub fn operation_start(
+    operations: State<'_, OperationRegistry>,
+    span_registry: State<'_, SpanContextRegistry>,
+    name: String,
+    metadata: Option<serde_json::Value>,
+) -> crate::Result<OperationHandle> {
+    operations.start(&name, metadata.as_ref(), &span_registry)
+}
+
+/// Ends an operation started with [`operation_start`].
+#[tauri::command]
+pub fn operation_end(
+    operations: State<'_, OperationRegistry>,
+    span_registry: State<'_, SpanContextRegistry>,
+    id: String,
+    outcome: Option<OperationOutcome>,
+) -> crate::Result<OperationHandle> {
+    operations.end(&id, outcome.unwrap_or_default(), &span_registry)
+}
+
+/// Returns an open operation, so a reloaded page can re-attach to it.
+#[tauri::command]
+pub fn operation_get(
+    operations: State<'_, OperationRegistry>,
+    id: String,
+) -> Option<OperationHandle> {
+    operations.get(&id)
+}
+
+/// Lists all open operations, longest-running first.
+#[tauri::command]
+pub fn operation_list(operations: State<'_, OperationRegistry>) -> Vec<OperationHandle> {
+    operations.list()
+}
+
+/// Opens a span around a frontend callback and returns its context.
+///
+/// The span is a child of `parent` if the frontend supplies one. Returns
+/// `None` if the span is disabled by the filter.
+#[tauri::command]
+pub fn span_start<R: Runtime>(
+    webview_window: tauri::WebviewWindow<R>,
+    span_registry: State<'_, SpanContextRegistry>,
+    operations: State<'_, OperationRegistry>,
+    name: String,
+    parent: Option<SpanContext>,
+) -> Option<SpanContext> {
+    let parent = parent.and_then(|ctx| {
+        span_registry
+            .get(&ctx)
+            .or_else(|| operations.span_for(&ctx))
+    });
+    webview_spans::start(webview_window.label(), &name, parent, &span_registry)
+}
+
+/// Closes a span opened with [`span_start`], recording the callback's
+/// duration and the exception it threw, if any.
+#[tauri::command]
+pub fn span_end(
+    span_registry: State<'_, SpanContextRegistry>,
+    context: SpanContext,
+    duration_ms: Option<f64>,
+    error: Option<String>,
+) -> crate::Result<()> {
+    webview_spans::end(&context, duration_ms, error.as_deref(), &span_registry)
+}
+
+/// Sets the level for targets without a more specific level.
+///
+/// Requires the default subscriber; see [`FilterHandle`].
+#[tauri::command]
+pub fn set_log_level<R: Runtime>(app: tauri::AppHandle<R>, level: LogLevel) -> crate::Result<()> {
+    filter_handle(&app)?.set_level(LevelFilter::from_level(level.into()))
+}
+
+/// Sets the level for a target and its submodules, or clears it if `level` is
+/// omitted.
+///
+/// Requires the default subscriber; see [`FilterHandle`].
+#[tauri::command]
+pub fn set_target_level<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    target: String,
+    level: Option<LogLevel>,
+) -> crate::Result<()> {
+    let filter = filter_handle(&app)?;
+    match level {
+        Some(level) => filter.set_target(target, LevelFilter::from_level(level.into())),
+        None => filter.clear_target(&target),
+    }
+}
+
+/// Discards all runtime filter changes, including persisted ones.
+///
+/// Requires the default subscriber; see [`FilterHandle`].
+#[tauri::command]
+pub fn reset_log_filter<R: Runtime>(app: tauri::AppHandle<R>) -> crate::Result<()> {
+    filter_handle(&app)?.reset()
+}
+
+/// Returns the level for targets without a more specific level, and the
+/// levels of individual targets.
+///
+/// Requires the default subscriber; see [`FilterHandle`].
+#[tauri::command]
+pub fn get_effective_filter<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> crate::Result<EffectiveFilter> {
+    Ok(filter_handle(&app)?.effective())
+}
+
+/// Returns the events dropped by the filter, by target.
+///
+/// See [`get_filter_stats()`](crate::get_filter_stats).
+#[tauri::command]
+pub fn get_filter_stats<R: Runtime>(app: tauri::AppHandle<R>) -> crate::Result<FilterStats> {
+    crate::get_filter_stats(&app)
+}
+
+fn filter_handle<R: Runtime>(app: &tauri::AppHandle<R>) -> crate::Result<State<'_, FilterHandle>> {
+    use tauri::Manager;
+
+    app.try_state::<FilterHandle>()
+        .ok_or(crate::Error::LoggerNotInitialized)
+}
+
+/// Returns the size of the file target's logs.
+///
+/// Without a file target, all counts are zero; see [`LogFiles`].
+#[tauri::command]
+pub fn get_log_stats<R: Runtime>(app: tauri::AppHandle<R>) -> crate::Result<LogStats> {
+    use tauri::Manager;
+
+    match app.try_state::<LogFiles>() {
+        Some(files) => files.stats(),
+        None => Ok(LogStats::default()),
+    }
+}
+
+/// Deletes the rotated log files and empties the current one.
+///
+/// Returns the stats afterwards; see [`LogFiles::clear()`].
+#[tauri::command]
+pub fn clear_logs<R: Runtime>(app: tauri::AppHandle<R>) -> crate::Result<LogStats> {
+    use tauri::Manager;
+
+    match app.try_state::<LogFiles>() {
+        Some(files) => files.clear(),
+        None => Ok(LogStats::default()),
+    }
+}
+
+/// Returns the last `lines` lines of the logs, and with `follow`, emits new
+/// lines on the `tracing://tail` event; see [`tail_logs()`](crate::tail_logs).
+#[tauri::command]
+pub fn tail_logs<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    lines: usize,
+    follow: bool,
+) -> crate::Result<LogTail> {
+    crate::tail_logs(&app, lines, follow)
+}
+
+/// Stops emitting new lines; see [`stop_tail_logs()`](crate::stop_tail_logs).
+#[tauri::command]
+pub fn stop_tail_logs<R: Runtime>(app: tauri::AppHandle<R>) {
+    crate::stop_tail_logs(&app);
+}
+
+/// Records a marker event; see [`mark()`](crate::mark).
+#[tauri::command]
+pub fn mark(label: String) {
+    crate::mark(&label);
+}
+
+/// Returns the records around the latest marker with `label`; see
+/// [`logs_around_mark()`](crate::logs_around_mark).
+///
+/// Returns 20 records on each side by default.
+#[tauri::command]
+pub fn get_logs_around_mark<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    label: String,
+    before: Option<usize>,
+    after: Option<usize>,
+) -> crate::Result<Option<MarkContext>> {
+    crate::logs_around_mark(
+        &app,
+        &label,
+        before.unwrap_or(DEFAULT_MARK_CONTEXT),
+        after.unwrap_or(DEFAULT_MARK_CONTEXT),
+    )
+}
+
+/// Adds `key` to every subsequent event; see [`LogContext::set()`].
+#[tauri::command]
+pub fn set_context(context: State<'_, LogContext>, key: String, value: String) {
+    context.set(key, value);
+}
+
+/// Stops adding `key` to events; see [`LogContext::remove()`].
+#[tauri::command]
+pub fn remove_context(context: State<'_, LogContext>, key: String) {
+    context.remove(&key);
+}
+
+/// Stops adding any context to events, e.g. on logout; see
+/// [`LogContext::clear()`].
+#[tauri::command]
+pub fn clear_context(context: State<'_, LogContext>) {
+    context.clear();
+}
+
+/// Logs a violated invariant of the frontend as an `ERROR` event, and counts
+/// it; see [`invariant!`](crate::invariant).
+#[tauri::command]
+pub fn report_invariant_violation<R: Runtime>(
+    webview_window: tauri::WebviewWindow<R>,
+    id: String,
+    message: Option<String>,
+) {
+    let violations = crate::count_invariant_violation(&id);
+    let message = message.unwrap_or_else(|| format!("invariant violated: {id}"));
+    tracing::error!(
+        target: crate::INVARIANT_TARGET,
+        w = webview_window.label(),
+        invariant = id,
+        violations,
+        "{message}"
+    );
+}
+
+/// Returns the invariants violated since the app started; see
+/// [`invariant_violations()`](crate::invariant_violations).
+#[tauri::command]
+pub fn get_invariant_violations() -> Vec<crate::ViolatedInvariant> {
+    crate::invariant_violations()
+}
+
+/// Reports the progress of a task as `current` of `total` steps; see
+/// [`progress()`](crate::progress).
+#[tauri::command]
+pub fn progress<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    tracker: State<'_, crate::ProgressTracker>,
+    task_id: String,
+    current: u64,
+    total: u64,
+    message: Option<String>,
+) {
+    tracker.report(&app, &task_id, current, total, message.as_deref());
+}
+
+/// Sets the gauge `name` to `value`; see [`gauge()`](crate::gauge).
+#[tauri::command]
+pub fn gauge(name: String, value: f64) {
+    crate::gauge(&name, value);
+}
+
+/// Adds `inc` to the counter `name`; see [`counter()`](crate::counter).
+#[tauri::command]
+pub fn counter(name: String, inc: u64) {
+    crate::counter(&name, inc);
+}
+
+/// Starts capturing the logs of the end-to-end test `name`; see
+/// [`TestCapture::begin()`].
+#[tauri::command]
+pub fn begin_test<R: Runtime>(app: tauri::AppHandle<R>, name: String) -> crate::Result<()> {
+    test_capture(&app)?.begin(&name);
+    Ok(())
+}
+
+/// Stops capturing the logs of the end-to-end test `name` and returns them;
+/// see [`TestCapture::end()`].
+#[tauri::command]
+pub fn end_test<R: Runtime>(app: tauri::AppHandle<R>, name: String) -> crate::Result<TestLogs> {
+    test_capture(&app)?.end(&name)
+}
+
+fn test_capture<R: Runtime>(app: &tauri::AppHandle<R>) -> crate::Result<State<'_, TestCapture>> {
+    use tauri::Manager;
+
+    app.try_state::<TestCapture>()
+        .ok_or(crate::Error::TestModeDisabled)
+}
+
+/// Sets the client-side route of the calling window; see
+/// [`CurrentRoutes::set()`].
+#[tauri::command]
+pub fn set_current_route<R: Runtime>(
+    webview_window: tauri::WebviewWindow<R>,
+    routes: State<'_, CurrentRoutes>,
+    route: String,
+) {
+    routes.set(webview_window.label(), &route);
+}
+
+/// Returns the JSON Schema of records and query results; see
+/// [`json_schema()`](crate::json_schema).
+#[cfg(feature = "schema")]
+#[tauri::command]
+pub fn get_schema() -> serde_json::Value {
+    crate::json_schema()
+}
+
+/// Returns [`Error::NotImplemented`](crate::Error::NotImplemented), as the
+/// `schema` feature is disabled.
+#[cfg(not(feature = "schema"))]
+#[tauri::command]
+pub fn get_schema() -> crate::Result<serde_json::Value> {
+    Err(crate::Error::NotImplemented)
+}
+
+/// Counts events per crate for `window_ms`, 10 seconds by default and at
+/// most 60 seconds, and recommends levels for the noisiest third-party
+/// crates; see [`analyze_noise()`](crate::analyze_noise).
+#[tauri::command]
+pub async fn analyze_noise<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    window_ms: Option<u64>,
+) -> crate::Result<NoiseReport> {
+    let window_ms = window_ms
+        .unwrap_or(DEFAULT_NOISE_WINDOW_MS)
+        .min(MAX_NOISE_WINDOW_MS);
+    let window = Duration::from_millis(window_ms);
+    tauri::async_runtime::spawn_blocking(move || crate::analyze_noise(&app, window)).await?
+}
+
+/// Sets the level of each recommended target; see
+/// [`apply_noise_filters()`](crate::apply_noise_filters).
+#[tauri::command]
+pub fn apply_noise_filters<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    recommendations: Vec<NoiseRecommendation>,
+) -> crate::Result<()> {
+    crate::apply_noise_filters(&app, &recommendations)
+}
+
+/// Writes a diagnostic report and returns the path of the zip file.
+///
+/// The report is written to the app cache directory, as
+/// [`output_dir`](crate::DiagnosticReportOptions::output_dir) cannot be set
+/// from the webview. See [`create_diagnostic_report()`](crate::create_diagnostic_report).
+#[cfg(feature = "diagnostics")]
+#[tauri::command]
+pub async fn create_diagnostic_report<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    options: Option<crate::DiagnosticReportOptions>,
+) -> crate::Result<String> {
+    // Reads logs and writes the zip file, so off the async runtime
+    let path = tauri::async_runtime::spawn_blocking(move || {
+        crate::create_diagnostic_report(&app, options.unwrap_or_default())
+    })
+    .await??;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Returns [`Error::NotImplemented`](crate::Error::NotImplemented), as the
+/// `diagnostics` feature is disabled.
+#[cfg(not(feature = "diagnostics"))]
+#[tauri::command]
+pub async fn create_diagnostic_report() -> crate::Result<String> {
+    Err(crate::Error::NotImplemented)
+}
+
+/// Starts CPU profiling and span timing capture.
+///
+/// See [`SpanAwareProfilingExt`](crate::SpanAwareProfilingExt).
+#[cfg(feature = "profiling")]
+#[tauri::command]
+pub fn start_span_aware_profile<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    options: Option<crate::StartOptions>,
+) -> crate::Result<()> {
+    use crate::SpanAwareProfilingExt;
+
+    match options {
+        Some(options) => app.start_span_aware_profile_with_options(options)?,
+        None => app.start_span_aware_profile()?,
+    }
+    Ok(())
+}
+
+/// Returns [`Error::NotImplemented`](crate::Error::NotImplemented), as the
+/// `profiling` feature is disabled.
+#[cfg(not(feature = "profiling"))]
+#[tauri::command]
+pub fn start_span_aware_profile() -> crate::Result<()> {
+    Err(crate::Error::NotImplemented)
+}
+
+/// Stops profiling and returns the report correlating the CPU profile with
+/// span timing, which is also saved as a profiling session.
+///
+/// See [`SpanAwareProfilingExt`](crate::SpanAwareProfilingExt).
+#[cfg(feature = "profiling")]
+#[tauri::command]
+pub fn stop_span_aware_profile<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> crate::Result<crate::SpanCorrelationReport> {
+    use crate::SpanAwareProfilingExt;
+
+    Ok(app.stop_span_aware_profile()?)
+}
+
+/// Returns [`Error::NotImplemented`](crate::Error::NotImplemented), as the
+/// `profiling` feature is disabled.
+#[cfg(not(feature = "profiling"))]
+#[tauri::command]
+pub fn stop_span_aware_profile() -> crate::Result<()> {
+    Err(crate::Error::NotImplemented)
+}
+
+/// Returns the saved profiling sessions, newest first.
+///
+/// See [`list_profiling_sessions()`](crate::list_profiling_sessions).
+#[cfg(feature = "profiling")]
+#[tauri::command]
+pub fn list_profiling_sessions<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> crate::Result<Vec<crate::ProfilingSession>> {
+    crate::list_profiling_sessions(&app)
+}
+
+/// Returns [`Error::NotImplemented`](crate::Error::NotImplemented), as the
+/// `profiling` feature is disabled.
+#[cfg(not(feature = "profiling"))]
+#[tauri::command]
+pub fn list_profiling_sessions() -> crate::Result<Vec<()>> {
+    Err(crate::Error::NotImplemented)
+}
+
+/// Returns the report of a saved profiling session.
+///
+/// See [`load_profiling_session()`](crate::load_profiling_session).
+#[cfg(feature = "profiling")]
+#[tauri::command]
+pub fn get_profiling_session<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    id: String,
+) -> crate::Result<crate::SpanCorrelationReport> {
+    crate::load_profiling_session(&app, &id)
+}
+
+/// Returns [`Error::NotImplemented`](crate::Error::NotImplemented), as the
+/// `profiling` feature is disabled.
+#[cfg(not(feature = "profiling"))]
+#[tauri::command]
+pub fn get_profiling_session() -> crate::Result<()> {
+    Err(crate::Error::NotImplemented)
+}
+
+/// Returns the busy time statistics of each span name, by total time.
+///
+/// See [`get_span_stats()`](crate::get_span_stats).
+#[cfg(feature = "profiling")]
+#[tauri::command]
+pub fn get_span_stats<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> crate::Result<Vec<crate::SpanStats>> {
+    crate::get_span_stats(&app)
+}
+
+/// Returns [`Error::NotImplemented`](crate::Error::NotImplemented), as the
+/// `profiling` feature is disabled.
+#[cfg(not(feature = "profiling"))]
+#[tauri::command]
+pub fn get_span_stats() -> crate::Result<Vec<()>> {
+    Err(crate::Error::NotImplemented)
+}
+
+/// Records that the calling webview answered a heartbeat ping; see
+/// [`Heartbeat`](crate::Heartbeat).
+///
+/// Does nothing unless heartbeats are enabled.
+#[tauri::command]
+pub fn heartbeat_pong<R: Runtime>(webview: tauri::Webview<R>, seq: u64) {
+    use tauri::Manager;
+
+    if let Some(heartbeat) = webview.try_state::<crate::Heartbeat>() {
+        heartbeat.pong(webview.label(), seq);
+    }
+}
+
+/// Signals that the calling webview listens to `tracing://log`, which
+/// resumes forwarding if it was paused; see
+/// [`WebviewForwarding`](crate::WebviewForwarding).
+///
+/// The guest-js listeners invoke it once they are registered.
+#[tauri::command]
+pub fn webview_ready<R: Runtime>(app: tauri::AppHandle<R>) {
+    crate::layer::handle_webview_ready(&app);
+}
+
 /// Generates a flamegraph SVG from the recorded profiling data.
 ///
 /// Returns the path to the generated SVG file.
@@ -74,6 +1096,8 @@ pub fn generate_flamegraph<R: Runtime>(app: AppHandle<R>) -> crate::Result<Strin
     }
 
     let svg_path = generate_flamegraph_svg(folded_path)?;
+    #[cfg(feature = "diagnostics")]
+    crate::diagnostics::record_capture(&app, &svg_path);
     Ok(svg_path.to_string_lossy().to_string())
 }
 
@@ -109,5 +1133,7 @@ pub fn generate_flamechart<R: Runtime>(app: AppHandle<R>) -> crate::Result<Strin
     }
 
     let svg_path = generate_flamechart_svg(folded_path)?;
+    #[cfg(feature = "diagnostics")]
+    crate::diagnostics::record_capture(&app, &svg_path);
     Ok(svg_path.to_string_lossy().to_string())
 }
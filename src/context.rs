@@ -0,0 +1,257 @@
+//! App-level context on every event.
+//!
+//! Events rarely say which user or session they belong to, which matters in
+//! apps where several accounts can sign in. [`LogContext`] holds key-value
+//! pairs, such as `user_id` or `session_id`, that are added to every
+//! subsequent event, from the backend or a webview, until they are removed,
+//! e.g. on logout with [`LogContext::clear()`]. JavaScript sets them with
+//! `setContext()` and clears them with `clearContext()`.
+//!
+//! The plugin's formatters write the pairs after the event's own fields, as
+//! `user_id=42`, and the Loki, GELF, OTLP, and RFC 5424 outputs add them like
+//! event fields, which take precedence. Custom layers can read the context of
+//! the event being dispatched with [`current_context()`].
+//!
+//! [`scoped_context()`] adds pairs to the events of a single future instead,
+//! such as the id of a background job, on top of the app-level context.
+//! [`spawn_traced()`](crate::spawn_traced) carries them into spawned tasks.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::layer::Context;
+
+/// Context pairs, in the order they were first set.
+pub(crate) type Entries = Arc<Vec<(String, String)>>;
+
+thread_local! {
+    static CURRENT_CONTEXT: RefCell<Option<Entries>> = const { RefCell::new(None) };
+}
+
+tokio::task_local! {
+    static SCOPED_CONTEXT: Entries;
+}
+
+/// Returns the context of the event currently being dispatched on this
+/// thread, or an empty list outside of events.
+///
+/// Only meaningful while an event is being recorded by layers that sit
+/// inside a [`LogContext`] layer.
+pub fn current_context() -> Vec<(String, String)> {
+    with_current_context(<[_]>::to_vec)
+}
+
+/// Calls `f` with the context of the event currently being dispatched.
+pub(crate) fn with_current_context<T>(f: impl FnOnce(&[(String, String)]) -> T) -> T {
+    CURRENT_CONTEXT.with(|current| match &*current.borrow() {
+        Some(entries) => f(entries),
+        None => f(&[]),
+    })
+}
+
+/// Adds `pairs` to every event of `future`, after the [`LogContext`] pairs,
+/// replacing those with the same key.
+///
+/// The pairs are task-local, so they follow the future across `.await`
+/// points and worker threads, and do not leak to other tasks. Nested scopes
+/// add to the pairs of the enclosing ones.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::scoped_context;
+/// # async fn sync_workspace() {}
+/// # async fn example() {
+/// scoped_context([("job", "sync"), ("workspace", "acme")], async {
+///     sync_workspace().await;
+///     tracing::info!("synced"); // synced job=sync workspace=acme
+/// })
+/// .await;
+/// # }
+/// ```
+pub fn scoped_context<K, V, F>(
+    pairs: impl IntoIterator<Item = (K, V)>,
+    future: F,
+) -> impl Future<Output = F::Output>
+where
+    K: Into<String>,
+    V: Into<String>,
+    F: Future,
+{
+    let mut entries = scoped_entries();
+    for (key, value) in pairs {
+        set_entry(Arc::make_mut(&mut entries), key.into(), value.into());
+    }
+    SCOPED_CONTEXT.scope(entries, future)
+}
+
+/// Returns the pairs of the enclosing [`scoped_context()`] calls.
+pub(crate) fn scoped_entries() -> Entries {
+    SCOPED_CONTEXT.try_with(Arc::clone).unwrap_or_default()
+}
+
+/// Runs `future` with the pairs returned by [`scoped_entries()`].
+pub(crate) fn with_scoped_entries<F: Future>(
+    entries: Entries,
+    future: F,
+) -> impl Future<Output = F::Output> {
+    SCOPED_CONTEXT.scope(entries, future)
+}
+
+/// Calls `f` with the pairs returned by [`scoped_entries()`].
+pub(crate) fn with_scoped_entries_sync<T>(entries: Entries, f: impl FnOnce() -> T) -> T {
+    SCOPED_CONTEXT.sync_scope(entries, f)
+}
+
+fn set_entry(entries: &mut Vec<(String, String)>, key: String, value: String) {
+    match entries.iter_mut().find(|(k, _)| *k == key) {
+        Some((_, v)) => *v = value,
+        None => entries.push((key, value)),
+    }
+}
+
+/// Key-value pairs added to every event, and the tracing layer that adds
+/// them.
+///
+/// Clones share the same context. The plugin manages an instance in Tauri
+/// state, which the default subscriber uses.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri::Manager;
+/// # use tauri_plugin_tracing::LogContext;
+/// # fn example(app: &tauri::AppHandle) {
+/// let context = app.state::<LogContext>();
+/// context.set("user_id", "42");
+/// context.set("workspace", "acme");
+/// tracing::info!("synced"); // synced user_id=42 workspace=acme
+///
+/// // On logout
+/// context.clear();
+/// # }
+/// ```
+///
+/// With a custom subscriber, add the layer after the output layers, and pass
+/// the same instance to
+/// [`Builder::with_log_context()`](crate::Builder::with_log_context) so the
+/// commands change it:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{Builder, ContextFields, LogContext};
+/// # use tracing_subscriber::{Registry, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+/// let context = LogContext::new();
+/// Registry::default()
+///     .with(fmt::layer().fmt_fields(ContextFields::new(fmt::format::DefaultFields::new())))
+///     .with(context.clone())
+///     .init();
+///
+/// let plugin = Builder::new().with_log_context(context).build::<tauri::Wry>();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LogContext {
+    entries: Arc<RwLock<Entries>>,
+}
+
+impl LogContext {
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `key` to `value` for every subsequent event.
+    ///
+    /// Replacing a value keeps the key's position.
+    pub fn set(&self, key: impl Into<String>, value: impl Into<String>) {
+        let (key, value) = (key.into(), value.into());
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        set_entry(Arc::make_mut(&mut entries), key, value);
+    }
+
+    /// Removes `key`, returning its value if it was set.
+    pub fn remove(&self, key: &str) -> Option<String> {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        let index = entries.iter().position(|(k, _)| k == key)?;
+        Some(Arc::make_mut(&mut entries).remove(index).1)
+    }
+
+    /// Removes every key.
+    pub fn clear(&self) {
+        *self.entries.write().unwrap_or_else(|e| e.into_inner()) = Entries::default();
+    }
+
+    /// Returns the pairs currently set, in the order they were first set.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.snapshot().to_vec()
+    }
+
+    fn snapshot(&self) -> Entries {
+        self.entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogContext {
+    fn event_enabled(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let mut entries = self.snapshot();
+        let scoped = scoped_entries();
+        if !scoped.is_empty() {
+            let merged = Arc::make_mut(&mut entries);
+            for (key, value) in scoped.iter() {
+                set_entry(merged, key.clone(), value.clone());
+            }
+        }
+        CURRENT_CONTEXT.with(|current| {
+            *current.borrow_mut() = (!entries.is_empty()).then_some(entries);
+        });
+        true
+    }
+
+    fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        // Inner layers have recorded the event by now
+        CURRENT_CONTEXT.with(|current| current.borrow_mut().take());
+    }
+}
+
+/// Wraps a field formatter to append the [`LogContext`] to events.
+///
+/// The plugin's formatters use this automatically. When composing your own
+/// subscriber, pass it to `fmt::layer().fmt_fields()`.
+#[derive(Debug, Clone, Default)]
+pub struct ContextFields<N> {
+    inner: N,
+}
+
+impl<N> ContextFields<N> {
+    /// Wraps `inner`.
+    pub fn new(inner: N) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'writer, N> FormatFields<'writer> for ContextFields<N>
+where
+    N: for<'w> FormatFields<'w>,
+{
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> std::fmt::Result {
+        self.inner.format_fields(writer.by_ref(), fields)?;
+        with_current_context(|entries| {
+            for (key, value) in entries {
+                write!(writer, " {key}={value}")?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,149 @@
+//! Backend records in the webviews' devtools console.
+//!
+//! Frontend developers live in the browser devtools, where backend logs only
+//! show up if the page calls `attachConsole()`, which misses the records
+//! logged before it runs. A [`DevtoolsConsoleLayer`] evaluates a `console.*`
+//! call in every webview for each backend event, so backend records appear
+//! interleaved with the frontend's own, at the matching console level and
+//! with their fields as an object.
+//!
+//! Evaluating scripts is only meant for development: with
+//! [`Builder::with_devtools_console()`](crate::Builder::with_devtools_console),
+//! the plugin only adds the layer in dev builds, see [`tauri::is_dev()`].
+//! Records that came from a webview are skipped, as they are already in its
+//! console.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::Level;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+use crate::RecordFields;
+use crate::layer::FieldVisitor;
+
+thread_local! {
+    /// Whether this thread is evaluating a record, so events logged while
+    /// evaluating are not evaluated in turn.
+    static EVALUATING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A tracing layer that logs backend events to the devtools console of every
+/// webview.
+///
+/// # Example
+///
+/// Use [`Builder::with_devtools_console()`](crate::Builder::with_devtools_console)
+/// with the default subscriber, or add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::DevtoolsConsoleLayer;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt, fmt};
+/// tauri::Builder::default().setup(|app| {
+///     let registry = Registry::default().with(fmt::layer());
+///     if tauri::is_dev() {
+///         registry
+///             .with(DevtoolsConsoleLayer::new(app.handle().clone()))
+///             .init();
+///     } else {
+///         registry.init();
+///     }
+///     Ok(())
+/// });
+/// ```
+pub struct DevtoolsConsoleLayer<R: Runtime> {
+    app_handle: AppHandle<R>,
+    _runtime: PhantomData<fn() -> R>,
+}
+
+impl<R: Runtime> DevtoolsConsoleLayer<R> {
+    /// Creates a layer that logs to the webviews of `app_handle`.
+    pub fn new(app_handle: AppHandle<R>) -> Self {
+        Self {
+            app_handle,
+            _runtime: PhantomData,
+        }
+    }
+}
+
+impl<S, R: Runtime> Layer<S> for DevtoolsConsoleLayer<R>
+where
+    S: tracing::Subscriber,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        // Records from webviews have no target
+        if metadata.target().is_empty() || EVALUATING.with(Cell::get) {
+            return;
+        }
+        let webviews = self.app_handle.webviews();
+        if webviews.is_empty() {
+            return;
+        }
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let script = console_call(
+            *metadata.level(),
+            metadata.target(),
+            &visitor.message(),
+            &visitor.fields,
+        );
+
+        EVALUATING.with(|evaluating| evaluating.set(true));
+        for webview in webviews.values() {
+            let _ = webview.eval(&script);
+        }
+        EVALUATING.with(|evaluating| evaluating.set(false));
+    }
+}
+
+/// Returns the `console.*` call that logs a record, matching the methods of
+/// the JavaScript `attachConsole()`.
+fn console_call(level: Level, target: &str, message: &str, fields: &RecordFields) -> String {
+    let method = match level {
+        Level::TRACE => "log",
+        Level::DEBUG => "debug",
+        Level::INFO => "info",
+        Level::WARN => "warn",
+        Level::ERROR => "error",
+    };
+    // JSON strings and objects are valid JavaScript literals, and `%` starts
+    // a substitution in the console's format string
+    let format = serde_json::Value::from(format!(
+        "%c{}%c {}",
+        target.replace('%', "%%"),
+        message.replace('%', "%%")
+    ));
+    let mut script = format!("console.{method}({format},\"color:gray\",\"\"");
+    if !fields.is_empty()
+        && let Ok(fields) = serde_json::to_string(fields)
+    {
+        script.push(',');
+        script.push_str(&fields);
+    }
+    script.push_str(");");
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn console_call_escapes_the_record() {
+        let fields = RecordFields::from([
+            ("path".to_string(), "C:\\notes \"1\"".to_string()),
+            ("attempt".to_string(), "2".to_string()),
+        ]);
+        assert_eq!(
+            console_call(Level::WARN, "my_app::sync", "100% \"done\"", &fields),
+            r#"console.warn("%cmy_app::sync%c 100%% \"done\"","color:gray","",{"path":"C:\\notes \"1\"","attempt":"2"});"#
+        );
+        assert_eq!(
+            console_call(Level::TRACE, "my_app", "tick", &RecordFields::default()),
+            r#"console.log("%cmy_app%c tick","color:gray","");"#
+        );
+    }
+}
@@ -0,0 +1,651 @@
+//! Support bundles for "report a problem" flows.
+//!
+//! Requires the `diagnostics` feature. [`create_diagnostic_report()`], or the
+//! `create_diagnostic_report` command, writes a zip file that an app can
+//! attach to a feedback form. It contains:
+//!
+//! - `manifest.json`: when and by which app the report was created, and the
+//!   files it contains with their size and SHA-256 hash.
+//! - `manifest.sig`: the HMAC-SHA256 of `manifest.json`, hex-encoded, with
+//!   the key set with
+//!   [`Builder::with_report_signing_key()`](crate::Builder::with_report_signing_key).
+//! - `system.json`: the OS, architecture, versions, and resource usage.
+//! - `config.json`: the plugin configuration, including the catalog of known
+//!   events, and the filter in effect.
+//! - `invariants.json`: the invariants violated since the app started, see
+//!   [`invariant_violations()`](crate::invariant_violations).
+//! - `logs/`: the most recent log files of the file targets, newest first,
+//!   up to [`DiagnosticReportOptions::max_log_bytes`].
+//! - `profile/`: the latest flamegraph or CPU profile, if one was generated.
+//! - `screenshots/`: files provided by the caller.
+//! - `description.txt`: the user's description of the problem, if provided.
+//!
+//! Reports may contain personal data from logs and screenshots. Let users
+//! review what is sent.
+//!
+//! [`verify_log_bundle()`] checks that a report's files match its manifest,
+//! and with the signing key, that the manifest was written with the key, so
+//! support can tell whether logs were edited after the report was created.
+//!
+//! The signature only catches accidental or casual modification. The key is
+//! embedded in the app, so anyone holding its binary can extract the key and
+//! sign a forged manifest.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager, Runtime};
+use zip::write::SimpleFileOptions;
+
+use crate::{FilterHandle, ResourceMonitor, ResourceSnapshot};
+
+/// The default limit on the size of logs included in a report.
+const DEFAULT_MAX_LOG_BYTES: u64 = 20 * 1024 * 1024;
+
+/// The name of the manifest in a report.
+const MANIFEST: &str = "manifest.json";
+
+/// The name of the manifest's signature in a report.
+const SIGNATURE: &str = "manifest.sig";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Options for [`create_diagnostic_report()`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct DiagnosticReportOptions {
+    /// The user's description of the problem.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Screenshots or other files to include.
+    #[serde(default)]
+    pub screenshots: Vec<Attachment>,
+    /// The most bytes of logs to include. Defaults to 20 MiB.
+    ///
+    /// When the newest log file alone is larger, only its end is included.
+    #[serde(default)]
+    pub max_log_bytes: Option<u64>,
+    /// The directory to write the report to. Defaults to `diagnostics` in the
+    /// app cache directory.
+    ///
+    /// Only settable from Rust, so a webview cannot have the report written
+    /// anywhere on the filesystem.
+    #[serde(skip)]
+    pub output_dir: Option<PathBuf>,
+}
+
+/// A file provided by the caller, such as a screenshot.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct Attachment {
+    /// The file name, e.g. `main-window.png`. Directories are ignored, and
+    /// names already used by an earlier attachment get a `-2`, `-3`, …
+    /// suffix.
+    pub name: String,
+    /// The file contents.
+    pub bytes: Vec<u8>,
+}
+
+/// Where a report finds logs and configuration, managed by the plugin.
+pub(crate) struct DiagnosticSources {
+    /// Log directories and the file name prefixes of their logs.
+    pub(crate) log_files: Vec<(PathBuf, String)>,
+    /// The plugin configuration, as set on the builder.
+    pub(crate) config: Value,
+    /// The latest flamegraph or CPU profile.
+    pub(crate) latest_capture: Mutex<Option<PathBuf>>,
+    /// The key the manifest is signed with.
+    pub(crate) signing_key: Option<Vec<u8>>,
+}
+
+/// Returns the plugin configuration set on `builder`, for `config.json`.
+pub(crate) fn builder_config(builder: &crate::Builder) -> Value {
+    let levels: serde_json::Map<String, Value> = builder
+        .filter
+        .iter()
+        .map(|(target, level)| (target.to_string(), level.to_string().into()))
+        .collect();
+    json!({
+        "level": builder.log_level.to_string(),
+        "targetLevels": levels,
+        "targetGlobs": builder
+            .target_globs
+            .iter()
+            .map(|(pattern, level)| json!({ "pattern": pattern, "level": level.to_string() }))
+            .collect::<Vec<_>>(),
+        "buildVariant": builder.configured_build_variant(),
+        "variantTargets": builder
+            .variant_targets
+            .iter()
+            .map(|(variant, target, level)| {
+                json!({ "variant": variant, "target": target, "level": level.to_string() })
+            })
+            .collect::<Vec<_>>(),
+        "spanLevel": builder.span_level.map(|level| level.to_string()),
+        "customFilter": builder.custom_filter.is_some(),
+        "customLayer": builder.custom_layer.is_some(),
+        "targets": builder.targets.iter().map(|target| format!("{target:?}")).collect::<Vec<_>>(),
+        "stderrThreshold": builder.stderr_threshold.map(|level| level.to_string()),
+        "rotation": format!("{:?}", builder.rotation),
+        "rotationStrategy": format!("{:?}", builder.rotation_strategy),
+        "maxFileSize": builder.max_file_size.map(|size| size.0),
+        "instanceStrategy": format!("{:?}", builder.instance_strategy),
+        "versionLayout": format!("{:?}", builder.version_layout),
+        "fileNameTemplate": builder.file_name_template,
+        "syncPolicy": format!("{:?}", builder.sync_policy),
+        "timezoneStrategy": format!("{:?}", builder.timezone_strategy),
+        "format": format!("{:?}", builder.log_format),
+        "defaultSubscriber": builder.set_default_subscriber,
+        "slowSpanThresholdMs": builder.slow_span_threshold.map(|d| d.as_millis() as u64),
+        "resourceMonitorIntervalMs": builder
+            .resource_monitor_interval
+            .map(|d| d.as_millis() as u64),
+        "heartbeatIntervalMs": builder.heartbeat_interval.map(|d| d.as_millis() as u64),
+        "eventLoopThresholdMs": builder.event_loop_threshold.map(|d| d.as_millis() as u64),
+        "captureOutput": builder.capture_output,
+        "persistFilter": builder.persist_filter,
+        "filterStats": builder.filter_stats,
+        "sampling": builder
+            .sampling
+            .iter()
+            .map(|(target, rate)| (target.clone(), rate.to_string()))
+            .collect::<BTreeMap<_, _>>(),
+        "breadcrumbs": builder.breadcrumbs,
+        "sequenceNumbers": builder.sequence_numbers,
+        "messageTranslator": builder.message_translator.is_some(),
+        "notifications": builder.notifications.map(|n| {
+            let (max, per) = n.rate_limit();
+            json!({ "errors": n.notifies_errors(), "max": max, "perMs": per.as_millis() as u64 })
+        }),
+        "logHistory": builder.log_history,
+        "testMode": builder.test_mode,
+        "navigationBreadcrumbs": builder.navigation_breadcrumbs,
+        "routeSpans": builder.route_spans,
+        "progressEvents": builder.progress_events,
+        "eventCatalog": builder
+            .event_catalog
+            .iter()
+            .map(|event| {
+                json!({
+                    "id": event.id,
+                    "level": event.level.to_string(),
+                    "description": event.description,
+                })
+            })
+            .collect::<Vec<_>>(),
+        "metricsIntervalMs": builder.metrics_interval.map(|d| d.as_millis() as u64),
+        "devtoolsConsole": builder.devtools_console,
+        "launchLogging": builder.launch_logging.is_some(),
+        "updateLogFile": builder.update_log_file,
+        "buildInfo": builder.build_info,
+        "reportSigning": builder.report_signing_key.is_some(),
+    })
+}
+
+/// Remembers `path` as the latest profiling capture to include in reports.
+pub(crate) fn record_capture<R: Runtime>(app: &AppHandle<R>, path: &Path) {
+    if let Some(sources) = app.try_state::<DiagnosticSources>() {
+        *sources
+            .latest_capture
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(path.to_path_buf());
+    }
+}
+
+/// Writes a diagnostic report and returns the path of the zip file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{DiagnosticReportOptions, create_diagnostic_report};
+/// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+/// let path = create_diagnostic_report(
+///     app,
+///     DiagnosticReportOptions {
+///         description: Some("Sync stopped after waking from sleep".into()),
+///         ..Default::default()
+///     },
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn create_diagnostic_report<R: Runtime>(
+    app: &AppHandle<R>,
+    options: DiagnosticReportOptions,
+) -> crate::Result<PathBuf> {
+    let created = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let output_dir = match options.output_dir {
+        Some(dir) => dir,
+        None => app.path().app_cache_dir()?.join("diagnostics"),
+    };
+    std::fs::create_dir_all(&output_dir)?;
+    let path = output_dir.join(format!("diagnostic-report-{}.zip", created.as_millis()));
+
+    let mut report = ReportWriter::new(File::create(&path)?);
+    let sources = app.try_state::<DiagnosticSources>();
+
+    report.add_json("system.json", &system_info(app))?;
+
+    let mut config = sources
+        .as_ref()
+        .map(|sources| sources.config.clone())
+        .unwrap_or_else(|| json!({}));
+    if let Some(filter) = app.try_state::<FilterHandle>() {
+        config["effectiveFilter"] = serde_json::to_value(filter.effective()).unwrap_or_default();
+    }
+    report.add_json("config.json", &config)?;
+    report.add_json("invariants.json", &crate::invariant_violations())?;
+
+    if let Some(sources) = &sources {
+        let mut budget = options.max_log_bytes.unwrap_or(DEFAULT_MAX_LOG_BYTES);
+        for log in recent_logs(&sources.log_files) {
+            if budget == 0 {
+                break;
+            }
+            let Some(name) = log.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let bytes = read_tail(&log, budget)?;
+            budget = budget.saturating_sub(bytes.len() as u64);
+            report.add(&format!("logs/{name}"), &bytes)?;
+        }
+
+        let capture = sources
+            .latest_capture
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if let Some(capture) = capture
+            && let Some(name) = capture.file_name().and_then(|name| name.to_str())
+            && let Ok(bytes) = std::fs::read(&capture)
+        {
+            report.add(&format!("profile/{name}"), &bytes)?;
+        }
+    }
+
+    let mut screenshot_names = HashSet::new();
+    for screenshot in &options.screenshots {
+        let name = Path::new(&screenshot.name)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("screenshot");
+        let name = unique_name(name, &mut screenshot_names);
+        report.add(&format!("screenshots/{name}"), &screenshot.bytes)?;
+    }
+
+    if let Some(description) = &options.description {
+        report.add("description.txt", description.as_bytes())?;
+    }
+
+    let package = app.package_info();
+    let manifest = json!({
+        "createdAtMs": created.as_millis() as u64,
+        "app": { "name": package.name, "version": package.version.to_string() },
+        "plugin": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+        "files": report.files,
+    });
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(std::io::Error::other)?;
+    report.add(MANIFEST, &manifest)?;
+    if let Some(key) = sources
+        .as_ref()
+        .and_then(|sources| sources.signing_key.as_ref())
+    {
+        if let Some(mac) = manifest_mac(key, &manifest) {
+            report.add(SIGNATURE, hex(&mac.finalize().into_bytes()).as_bytes())?;
+        }
+    }
+    report.finish()?;
+
+    tracing::info!(path = %path.display(), "diagnostic report created");
+    Ok(path)
+}
+
+/// The result of [`verify_log_bundle()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleVerification {
+    /// Files whose contents do not match their hash in the manifest.
+    pub modified: Vec<String>,
+    /// Files listed in the manifest but missing from the report.
+    pub missing: Vec<String>,
+    /// Files in the report but not listed in the manifest.
+    pub unlisted: Vec<String>,
+    /// Whether the manifest's signature matches the key.
+    pub signature: SignatureStatus,
+}
+
+impl BundleVerification {
+    /// Returns whether every file matches the manifest, and the signature
+    /// matches the key if one was given.
+    pub fn is_intact(&self) -> bool {
+        self.modified.is_empty()
+            && self.missing.is_empty()
+            && self.unlisted.is_empty()
+            && matches!(
+                self.signature,
+                SignatureStatus::Valid | SignatureStatus::NotChecked
+            )
+    }
+}
+
+/// Whether a report's manifest was signed with the expected key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// The manifest is signed with the key.
+    Valid,
+    /// The manifest is signed with another key, or was changed after it was
+    /// signed.
+    Invalid,
+    /// The report has no signature, though a key was given.
+    Missing,
+    /// No key was given, so the signature was not checked.
+    NotChecked,
+}
+
+/// Checks that the files of a diagnostic report match its manifest.
+///
+/// With the key set with
+/// [`Builder::with_report_signing_key()`](crate::Builder::with_report_signing_key),
+/// also checks the manifest's signature. Without it, a report whose files
+/// and manifest were both rewritten cannot be told apart from the original.
+/// With it, the report can still have been forged by someone who extracted
+/// the key from the app's binary.
+///
+/// Returns an error if the file is not a zip archive or has no readable
+/// manifest.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn example() -> tauri_plugin_tracing::Result<()> {
+/// let key = std::env::var("REPORT_SIGNING_KEY").unwrap_or_default();
+/// let verification = tauri_plugin_tracing::verify_log_bundle(
+///     "diagnostic-report-1718000000000.zip",
+///     Some(key.as_bytes()),
+/// )?;
+/// if !verification.is_intact() {
+///     eprintln!("report was modified: {verification:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_log_bundle(
+    path: impl AsRef<Path>,
+    key: Option<&[u8]>,
+) -> crate::Result<BundleVerification> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+    let manifest = read_entry(&mut archive, MANIFEST)?
+        .ok_or_else(|| crate::Error::InvalidManifest(format!("no {MANIFEST}")))?;
+    let files: Vec<(String, Option<String>)> = serde_json::from_slice::<Value>(&manifest)
+        .ok()
+        .and_then(|manifest| {
+            manifest["files"].as_array().map(|files| {
+                files
+                    .iter()
+                    .filter_map(|file| {
+                        let path = file["path"].as_str()?.to_string();
+                        Some((path, file["sha256"].as_str().map(str::to_string)))
+                    })
+                    .collect()
+            })
+        })
+        .ok_or_else(|| crate::Error::InvalidManifest(format!("{MANIFEST} has no file list")))?;
+
+    let mut verification = BundleVerification {
+        modified: Vec::new(),
+        missing: Vec::new(),
+        unlisted: Vec::new(),
+        signature: SignatureStatus::NotChecked,
+    };
+    for (path, sha256) in &files {
+        match read_entry(&mut archive, path)? {
+            None => verification.missing.push(path.clone()),
+            Some(bytes) if sha256.as_deref() != Some(sha256_hex(&bytes).as_str()) => {
+                verification.modified.push(path.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    verification.unlisted = archive
+        .file_names()
+        .filter(|name| {
+            *name != MANIFEST && *name != SIGNATURE && !files.iter().any(|(path, _)| path == name)
+        })
+        .map(str::to_string)
+        .collect();
+
+    if let Some(key) = key {
+        verification.signature = match read_entry(&mut archive, SIGNATURE)? {
+            None => SignatureStatus::Missing,
+            Some(signature) => {
+                let signature = unhex(signature.trim_ascii());
+                match (signature, manifest_mac(key, &manifest)) {
+                    // Compares in constant time, so signatures cannot be
+                    // guessed byte by byte
+                    (Some(signature), Some(mac)) if mac.verify_slice(&signature).is_ok() => {
+                        SignatureStatus::Valid
+                    }
+                    _ => SignatureStatus::Invalid,
+                }
+            }
+        };
+    }
+    Ok(verification)
+}
+
+/// Reads a file of a zip archive, or returns `None` if it has none by `name`.
+fn read_entry(archive: &mut zip::ZipArchive<File>, name: &str) -> crate::Result<Option<Vec<u8>>> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+fn system_info<R: Runtime>(app: &AppHandle<R>) -> Value {
+    let resources = app
+        .try_state::<ResourceMonitor>()
+        .and_then(|monitor| monitor.latest())
+        .unwrap_or_else(ResourceSnapshot::capture);
+    json!({
+        "os": std::env::consts::OS,
+        "osFamily": std::env::consts::FAMILY,
+        "arch": std::env::consts::ARCH,
+        "tauriVersion": tauri::VERSION,
+        "webviewVersion": tauri::webview_version().ok(),
+        "pid": std::process::id(),
+        "resources": resources,
+    })
+}
+
+/// Returns the log files in `log_files`' directories, newest first.
+fn recent_logs(log_files: &[(PathBuf, String)]) -> Vec<PathBuf> {
+    let mut logs: Vec<(Option<SystemTime>, PathBuf)> = Vec::new();
+    for (dir, prefix) in log_files {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let is_log = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| crate::is_log_file(name, prefix, true));
+            let path = entry.path();
+            if is_log && !logs.iter().any(|(_, seen)| *seen == path) {
+                logs.push((entry.metadata().and_then(|m| m.modified()).ok(), path));
+            }
+        }
+    }
+    logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    logs.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Reads at most the last `max_bytes` of a file.
+fn read_tail(path: &Path, max_bytes: u64) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len > max_bytes {
+        file.seek(SeekFrom::Start(len - max_bytes))?;
+    }
+    let mut bytes = Vec::new();
+    file.take(max_bytes).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+/// Returns the HMAC-SHA256 of `manifest` with `key`.
+fn manifest_mac(key: &[u8], manifest: &[u8]) -> Option<HmacSha256> {
+    // HMAC accepts keys of any length, so this does not fail
+    let mut mac = HmacSha256::new_from_slice(key).ok()?;
+    mac.update(manifest);
+    Some(mac)
+}
+
+/// Decodes a hex string, or returns `None` if it is not one.
+fn unhex(hex: &[u8]) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    hex.chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// Writes report files to a zip archive, keeping a list for the manifest.
+struct ReportWriter {
+    zip: zip::ZipWriter<File>,
+    files: Vec<BTreeMap<&'static str, Value>>,
+}
+
+impl ReportWriter {
+    fn new(file: File) -> Self {
+        Self {
+            zip: zip::ZipWriter::new(file),
+            files: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, name: &str, bytes: &[u8]) -> crate::Result<()> {
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        self.zip.start_file(name, options)?;
+        self.zip.write_all(bytes)?;
+        self.files.push(BTreeMap::from([
+            ("path", Value::from(name)),
+            ("bytes", Value::from(bytes.len())),
+            ("sha256", Value::from(sha256_hex(bytes))),
+        ]));
+        Ok(())
+    }
+
+    fn add_json(&mut self, name: &str, value: &impl Serialize) -> crate::Result<()> {
+        let bytes = serde_json::to_vec_pretty(value).map_err(std::io::Error::other)?;
+        self.add(name, &bytes)
+    }
+
+    fn finish(self) -> crate::Result<()> {
+        self.zip.finish()?;
+        Ok(())
+    }
+}
+
+/// Returns `name`, or `name` with a `-2`, `-3`, … suffix before its extension
+/// if it is already in `taken`, and adds the result to `taken`.
+///
+/// The zip format allows duplicate entries, but the writer refuses them, so
+/// two attachments with the same name would abort the report.
+fn unique_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let mut unique = name.to_owned();
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    };
+    let mut index = 2;
+    while !taken.insert(unique.clone()) {
+        unique = match extension {
+            Some(extension) => format!("{stem}-{index}.{extension}"),
+            None => format!("{stem}-{index}"),
+        };
+        index += 1;
+    }
+    unique
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_name_suffixes_duplicates() {
+        let mut taken = HashSet::new();
+        let names: Vec<_> = ["shot.png", "shot.png", "shot", "shot.png", ".png", ".png"]
+            .into_iter()
+            .map(|name| unique_name(name, &mut taken))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "shot.png",
+                "shot-2.png",
+                "shot",
+                "shot-3.png",
+                ".png",
+                ".png-2"
+            ]
+        );
+    }
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        let mac = |key: &[u8], message: &[u8]| {
+            hex(&manifest_mac(key, message).unwrap().finalize().into_bytes())
+        };
+        assert_eq!(
+            mac(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            mac(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn unhex_round_trips() {
+        assert_eq!(
+            unhex(hex(&[0x00, 0xab, 0xff]).as_bytes()),
+            Some(vec![0x00, 0xab, 0xff])
+        );
+        assert_eq!(unhex(b"abc"), None);
+        assert_eq!(unhex(b"zz"), None);
+    }
+}
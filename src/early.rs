@@ -0,0 +1,296 @@
+//! Buffering of events logged before the plugin is set up.
+//!
+//! The default subscriber is installed when the plugin is set up, so events
+//! logged earlier in `main`, while parsing configuration or registering
+//! plugins, are not recorded anywhere. [`init()`], called at the very top of
+//! `main`, buffers the events of the current thread until then, and the
+//! plugin replays them into its subscriber once it is installed.
+//!
+//! Replayed events keep their level, target, source location, and fields,
+//! and go through the subscriber's filters like any other event, but they
+//! are timestamped when they are replayed and are not in the spans they were
+//! logged in. At most 1000 events are buffered.
+//!
+//! With a custom subscriber installed before the plugin is built, the plugin
+//! replays the events into it. A custom subscriber installed later should
+//! call [`replay()`] right after it is installed.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! fn main() {
+//!     tauri_plugin_tracing::early::init();
+//!     tracing::info!("parsing config"); // Replayed when the plugin is set up
+//!
+//!     tauri::Builder::default().plugin(
+//!         tauri_plugin_tracing::Builder::new()
+//!             .with_default_subscriber()
+//!             .build(),
+//!     );
+//!     // .run(tauri::generate_context!("examples/default-subscriber/src-tauri/tauri.conf.json"))
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tracing::dispatcher::{self, DefaultGuard};
+use tracing::field::{Field, Value, Visit, display};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// The most events buffered before the plugin is set up.
+const MAX_EVENTS: usize = 1000;
+
+/// The most fields replayed for each event.
+const MAX_FIELDS: usize = 32;
+
+thread_local! {
+    static EARLY: RefCell<Option<(DefaultGuard, Arc<Mutex<Buffer>>)>> = const { RefCell::new(None) };
+}
+
+/// Buffers the events of the current thread until the plugin's subscriber
+/// is installed.
+///
+/// Call it at the very top of `main`, before anything logs. Calling it again
+/// does nothing.
+pub fn init() {
+    EARLY.with(|early| {
+        let mut early = early.borrow_mut();
+        if early.is_some() {
+            return;
+        }
+        let buffer = Arc::new(Mutex::new(Buffer::default()));
+        let guard = tracing::subscriber::set_default(EarlySubscriber {
+            buffer: buffer.clone(),
+            next_span: AtomicU64::new(1),
+        });
+        *early = Some((guard, buffer));
+    });
+}
+
+/// Stops buffering the events of the current thread and returns the
+/// buffered ones, if [`init()`] was called on it.
+///
+/// The plugin does this when it is set up; see [`replay()`] for custom
+/// subscribers.
+pub fn take() -> Option<EarlyEvents> {
+    let (guard, buffer) = EARLY.with(|early| early.borrow_mut().take())?;
+    drop(guard);
+    let buffer = std::mem::take(&mut *buffer.lock().unwrap_or_else(|e| e.into_inner()));
+    Some(EarlyEvents(buffer))
+}
+
+/// Stops buffering the events of the current thread and replays the
+/// buffered ones into the current subscriber.
+///
+/// Only needed with a custom subscriber installed after the plugin is set
+/// up, right after installing it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tracing_subscriber::util::SubscriberInitExt;
+/// tracing_subscriber::fmt().finish().init();
+/// tauri_plugin_tracing::early::replay();
+/// ```
+pub fn replay() {
+    if let Some(events) = take() {
+        events.replay();
+    }
+}
+
+/// The events buffered by [`init()`], returned by [`take()`].
+#[derive(Debug)]
+pub struct EarlyEvents(Buffer);
+
+impl EarlyEvents {
+    /// Returns the number of buffered events.
+    pub fn len(&self) -> usize {
+        self.0.events.len()
+    }
+
+    /// Returns whether no events were buffered.
+    pub fn is_empty(&self) -> bool {
+        self.0.events.is_empty()
+    }
+
+    /// Dispatches the buffered events to the current subscriber, in the
+    /// order they were logged.
+    pub fn replay(self) {
+        let Buffer { events, dropped } = self.0;
+        if events.is_empty() {
+            return;
+        }
+        tracing::debug!(
+            events = events.len(),
+            "replaying events logged before tracing was initialized"
+        );
+        for event in &events {
+            event.replay();
+        }
+        if dropped > 0 {
+            tracing::warn!(
+                dropped,
+                "dropped events logged before tracing was initialized, over the limit of {MAX_EVENTS}"
+            );
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Buffer {
+    events: Vec<EarlyEvent>,
+    dropped: u64,
+}
+
+#[derive(Debug)]
+struct EarlyEvent {
+    metadata: &'static Metadata<'static>,
+    values: Vec<(Field, Recorded)>,
+}
+
+/// A field value, as recorded.
+#[derive(Debug)]
+enum Recorded {
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Bool(bool),
+    Str(String),
+    /// Formatted with `Debug`, and replayed with `Display` so it is not
+    /// quoted.
+    Formatted(String),
+}
+
+impl Visit for EarlyEvent {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.values.push((field.clone(), Recorded::I64(value)));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.values.push((field.clone(), Recorded::U64(value)));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.values.push((field.clone(), Recorded::F64(value)));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.values.push((field.clone(), Recorded::Bool(value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.values
+            .push((field.clone(), Recorded::Str(value.to_string())));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.values
+            .push((field.clone(), Recorded::Formatted(format!("{value:?}"))));
+    }
+}
+
+impl EarlyEvent {
+    fn replay(&self) {
+        let fields = self.metadata.fields();
+        let values: Vec<(&Field, Box<dyn Value + '_>)> = self
+            .values
+            .iter()
+            .take(MAX_FIELDS)
+            .map(|(field, value)| {
+                let value: Box<dyn Value + '_> = match value {
+                    Recorded::I64(value) => Box::new(*value),
+                    Recorded::U64(value) => Box::new(*value),
+                    Recorded::F64(value) => Box::new(*value),
+                    Recorded::Bool(value) => Box::new(*value),
+                    Recorded::Str(value) => Box::new(value.as_str()),
+                    Recorded::Formatted(value) => Box::new(display(value)),
+                };
+                (field, value)
+            })
+            .collect();
+        let Some(padding) = fields.iter().next() else {
+            dispatch(self.metadata, &fields.value_set::<[_; 0]>(&[]));
+            return;
+        };
+        // Value sets have a fixed length, so unused entries repeat a field
+        // without a value
+        let mut entries: [(&Field, Option<&dyn Value>); MAX_FIELDS] =
+            [(&padding, None); MAX_FIELDS];
+        for (entry, (field, value)) in entries.iter_mut().zip(&values) {
+            *entry = (field, Some(value.as_ref()));
+        }
+        dispatch(self.metadata, &fields.value_set(&entries));
+    }
+}
+
+fn dispatch(metadata: &'static Metadata<'static>, values: &tracing::field::ValueSet<'_>) {
+    dispatcher::get_default(|dispatch| {
+        if dispatch.enabled(metadata) {
+            dispatch.event(&Event::new(metadata, values));
+        }
+    });
+}
+
+/// Buffers every event, and ignores spans.
+struct EarlySubscriber {
+    buffer: Arc<Mutex<Buffer>>,
+    next_span: AtomicU64,
+}
+
+impl Subscriber for EarlySubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        if buffer.events.len() >= MAX_EVENTS {
+            buffer.dropped += 1;
+            return;
+        }
+        let mut early = EarlyEvent {
+            metadata: event.metadata(),
+            values: Vec::new(),
+        };
+        event.record(&mut early);
+        buffer.events.push(early);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffers_until_taken() {
+        assert!(take().is_none());
+
+        init();
+        tracing::info!(rows = 3, "config parsed");
+        tracing::warn!(path = ?"/etc/app.toml", "config file missing");
+        let events = take().expect("buffered events");
+        tracing::info!("after take");
+
+        assert_eq!(events.len(), 2);
+        let event = &events.0.events[0];
+        assert_eq!(event.metadata.level(), &tracing::Level::INFO);
+        assert!(matches!(event.values[..], [_, (_, Recorded::I64(3))]));
+        assert!(take().is_none());
+    }
+}
@@ -0,0 +1,247 @@
+//! Clickable source locations in terminal output.
+//!
+//! [`EditorLinkWriter`] wraps the `file:line` of each event in an OSC 8
+//! hyperlink, which terminals such as iTerm2, WezTerm, Windows Terminal, and
+//! the VS Code terminal open when clicked. The link opens the file, or jumps
+//! to the line in an editor with an [`EditorScheme`] such as `vscode://`.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// How source location links are opened.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{Builder, EditorScheme};
+/// Builder::new()
+///     .with_editor_links(EditorScheme::VsCode)
+///     .with_default_subscriber();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditorScheme {
+    /// `file://` links, opened with the default app for the file.
+    File,
+    /// `vscode://file/` links, opened at the line in Visual Studio Code.
+    VsCode,
+    /// `cursor://file/` links, opened at the line in Cursor.
+    Cursor,
+    /// `zed://file/` links, opened at the line in Zed.
+    Zed,
+    /// `idea://open` links, opened at the line in JetBrains IDEs.
+    Idea,
+    /// A URL template, where `{path}` is replaced with the absolute path and
+    /// `{line}` with the line number, e.g. `"subl://open?url=file://{path}&line={line}"`.
+    Custom(String),
+}
+
+impl EditorScheme {
+    /// Returns the URL opening `path` at `line`.
+    pub fn url(&self, path: &Path, line: u32) -> String {
+        let mut path = path
+            .to_string_lossy()
+            .replace('\\', "/")
+            .replace(' ', "%20");
+        // Windows paths start with the drive letter
+        if !path.starts_with('/') {
+            path.insert(0, '/');
+        }
+        match self {
+            Self::File => format!("file://{path}"),
+            Self::VsCode => format!("vscode://file{path}:{line}"),
+            Self::Cursor => format!("cursor://file{path}:{line}"),
+            Self::Zed => format!("zed://file{path}:{line}"),
+            Self::Idea => format!("idea://open?file={path}&line={line}"),
+            Self::Custom(template) => template
+                .replace("{path}", &path)
+                .replace("{line}", &line.to_string()),
+        }
+    }
+}
+
+/// A [`MakeWriter`](tracing_subscriber::fmt::MakeWriter) that links the
+/// source location of each event to its file.
+///
+/// Relative paths, such as those of the app's own crate, are resolved
+/// against the working directory when the writer is created, which is the
+/// crate directory under `cargo run` and `tauri dev`. Only ANSI-colored
+/// output should be wrapped, as other output is not read by a terminal.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_plugin_tracing::{EditorLinkWriter, EditorScheme};
+/// use tauri_plugin_tracing::tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
+///
+/// tracing_subscriber::registry()
+///     .with(
+///         fmt::layer()
+///             .with_file(true)
+///             .with_line_number(true)
+///             .with_writer(EditorLinkWriter::new(std::io::stdout, EditorScheme::VsCode)),
+///     )
+///     .init();
+/// ```
+#[derive(Debug, Clone)]
+pub struct EditorLinkWriter<M> {
+    inner: M,
+    scheme: EditorScheme,
+    root: PathBuf,
+}
+
+impl<M> EditorLinkWriter<M> {
+    /// Creates a writer that writes to `inner` with links opened by `scheme`.
+    pub fn new(inner: M, scheme: EditorScheme) -> Self {
+        Self {
+            inner,
+            scheme,
+            root: std::env::current_dir().unwrap_or_default(),
+        }
+    }
+}
+
+/// A writer handle returned by the [`MakeWriter`](tracing_subscriber::fmt::MakeWriter) implementation.
+///
+/// This type implements [`std::io::Write`] and links the event's source location during writes.
+pub struct EditorLinkWriterGuard<'a, W> {
+    inner: W,
+    scheme: &'a EditorScheme,
+    root: &'a Path,
+    location: Option<(String, u32)>,
+}
+
+impl<W: Write> Write for EditorLinkWriterGuard<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let linked = self.location.as_ref().and_then(|(file, line)| {
+            let url = self.scheme.url(&self.root.join(file), *line);
+            link_location(buf, file, *line, &url)
+        });
+        match linked {
+            Some(linked) => self.inner.write_all(&linked)?,
+            None => self.inner.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<'a, M> tracing_subscriber::fmt::MakeWriter<'a> for EditorLinkWriter<M>
+where
+    M: tracing_subscriber::fmt::MakeWriter<'a>,
+{
+    type Writer = EditorLinkWriterGuard<'a, M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        EditorLinkWriterGuard {
+            inner: self.inner.make_writer(),
+            scheme: &self.scheme,
+            root: &self.root,
+            location: None,
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+        EditorLinkWriterGuard {
+            inner: self.inner.make_writer_for(meta),
+            scheme: &self.scheme,
+            root: &self.root,
+            location: meta
+                .file()
+                .zip(meta.line())
+                .map(|(file, line)| (file.to_string(), line)),
+        }
+    }
+}
+
+/// Wraps the first `file:line` in `buf` in an OSC 8 hyperlink to `url`.
+///
+/// SGR sequences and the separator between the file and line are skipped, as
+/// the formats dim them separately.
+fn link_location(buf: &[u8], file: &str, line: u32, url: &str) -> Option<Vec<u8>> {
+    let start = memchr::memmem::find(buf, file.as_bytes())?;
+    let mut end = start + file.len();
+    let line = line.to_string();
+    loop {
+        match buf.get(end..)? {
+            [b':', ..] => end += 1,
+            [0x1b, b'[', rest @ ..] => {
+                let len = rest.iter().position(|b| (0x40..=0x7e).contains(b))?;
+                end += 2 + len + 1;
+            }
+            rest if rest.starts_with(line.as_bytes())
+                && !rest.get(line.len()).is_some_and(u8::is_ascii_digit) =>
+            {
+                end += line.len();
+                break;
+            }
+            _ => return None,
+        }
+    }
+
+    let mut linked = Vec::with_capacity(buf.len() + url.len() + 16);
+    linked.extend_from_slice(&buf[..start]);
+    linked.extend_from_slice(b"\x1b]8;;");
+    linked.extend_from_slice(url.as_bytes());
+    linked.extend_from_slice(b"\x1b\\");
+    linked.extend_from_slice(&buf[start..end]);
+    linked.extend_from_slice(b"\x1b]8;;\x1b\\");
+    linked.extend_from_slice(&buf[end..]);
+    Some(linked)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn linked(buf: &[u8]) -> Option<String> {
+        link_location(buf, "src/main.rs", 42, "file:///app/src/main.rs")
+            .map(|linked| String::from_utf8(linked).unwrap())
+    }
+
+    #[test]
+    fn scheme_urls() {
+        let path = Path::new("/app/src/main.rs");
+        assert_eq!(EditorScheme::File.url(path, 42), "file:///app/src/main.rs");
+        assert_eq!(
+            EditorScheme::VsCode.url(path, 42),
+            "vscode://file/app/src/main.rs:42"
+        );
+        assert_eq!(
+            EditorScheme::Idea.url(Path::new(r"C:\my app\main.rs"), 7),
+            "idea://open?file=/C:/my%20app/main.rs&line=7"
+        );
+        let custom = EditorScheme::Custom("subl://open?url=file://{path}&line={line}".into());
+        assert_eq!(
+            custom.url(path, 42),
+            "subl://open?url=file:///app/src/main.rs&line=42"
+        );
+    }
+
+    #[test]
+    fn location_is_linked() {
+        assert_eq!(
+            linked(b" INFO app: src/main.rs:42: started").unwrap(),
+            " INFO app: \x1b]8;;file:///app/src/main.rs\x1b\\src/main.rs:42\x1b]8;;\x1b\\: started"
+        );
+    }
+
+    #[test]
+    fn dimmed_separators_are_skipped() {
+        assert_eq!(
+            linked(b"\x1b[2msrc/main.rs\x1b[0m\x1b[2m:\x1b[0m\x1b[2m42:\x1b[0m msg").unwrap(),
+            "\x1b[2m\x1b]8;;file:///app/src/main.rs\x1b\\src/main.rs\x1b[0m\x1b[2m:\x1b[0m\x1b[2m42\x1b]8;;\x1b\\:\x1b[0m msg"
+        );
+    }
+
+    #[test]
+    fn output_without_location_is_kept() {
+        assert_eq!(linked(b"src/main.rs is missing"), None);
+        assert_eq!(linked(b"src/main.rs:7 other line"), None);
+        assert_eq!(linked(b"src/main.rs:420 other line"), None);
+        assert_eq!(linked(b"no location"), None);
+    }
+}
@@ -0,0 +1,489 @@
+//! A local HTTP endpoint for test harnesses and diagnostics.
+//!
+//! Requires the `diagnostics-endpoint` feature. End-to-end test harnesses
+//! drive the app from outside and need to check its health, read its logs,
+//! raise log levels for a failing scenario, or profile a slow one, without
+//! going through the UI. With
+//! [`Builder::with_diagnostics_endpoint()`](crate::Builder::with_diagnostics_endpoint),
+//! the plugin serves a small JSON API on `127.0.0.1`, on a random port:
+//!
+//! - `GET /health`: the app's name, version, process id, and uptime.
+//! - `GET /logs/tail?lines=100`: the last lines of the file target, as
+//!   returned by [`tail_logs()`](crate::tail_logs).
+//! - `GET /filters`: the filter in effect, see [`FilterHandle`].
+//! - `POST /filters`: changes the filter, with a JSON body like
+//!   `{"level": "debug"}`, `{"target": "my_app::sync", "level": "trace"}`, or
+//!   `{"reset": true}`, and returns the filter in effect.
+//! - `POST /profile/start` and `POST /profile/stop`: span-aware CPU
+//!   profiling, with the `profiling` feature; `stop` returns the report.
+//!
+//! Every request needs an `Authorization: Bearer <token>` header with the
+//! token given to the builder. The endpoint's URL is logged under the
+//! [`ENDPOINT_TARGET`] target and written, with the process id, to
+//! `diagnostics-endpoint.json` in the app cache directory. Up to 8
+//! connections are served at a time, each on its own thread, and a client
+//! must send its request, with a body of at most 64 KiB, within 5 seconds.
+//! `/logs/tail` returns at most 10,000 lines.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tauri::{AppHandle, Manager, Runtime};
+use tracing_subscriber::filter::LevelFilter;
+
+use crate::FilterHandle;
+
+/// The target used for diagnostics endpoint events.
+pub const ENDPOINT_TARGET: &str = "app::endpoint";
+
+/// The name of the file the endpoint's URL is written to.
+const ENDPOINT_FILE: &str = "diagnostics-endpoint.json";
+
+/// The longest request line and headers accepted.
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+
+/// The largest request body accepted.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// How long a client may take to send its whole request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The most connections served at a time. Others are closed unanswered.
+const MAX_CONNECTIONS: usize = 8;
+
+/// The number of log lines returned by `/logs/tail` by default.
+const DEFAULT_TAIL_LINES: usize = 100;
+
+/// The most log lines returned by `/logs/tail`.
+const MAX_TAIL_LINES: usize = 10_000;
+
+/// The local HTTP endpoint, serving until dropped.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_diagnostics_endpoint()`](crate::Builder::with_diagnostics_endpoint)
+/// is used.
+pub struct DiagnosticsEndpoint {
+    addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+    file: Option<PathBuf>,
+}
+
+impl DiagnosticsEndpoint {
+    /// Starts serving on a random port of `127.0.0.1`, accepting requests
+    /// with `token`.
+    pub(crate) fn start<R: Runtime>(app: AppHandle<R>, token: String) -> crate::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let addr = listener.local_addr()?;
+        let stopped = Arc::new(AtomicBool::new(false));
+        let file = publish(&app, addr);
+        let server = Arc::new(Server {
+            app,
+            token,
+            started: Instant::now(),
+        });
+        let stop = stopped.clone();
+        let connections = Arc::new(AtomicUsize::new(0));
+        std::thread::Builder::new()
+            .name("tracing-endpoint".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    // A slow client only holds up its own connection
+                    if connections.fetch_add(1, Ordering::AcqRel) >= MAX_CONNECTIONS {
+                        connections.fetch_sub(1, Ordering::AcqRel);
+                        continue;
+                    }
+                    let (server, connections) = (server.clone(), connections.clone());
+                    let spawned = std::thread::Builder::new()
+                        .name("tracing-endpoint-conn".to_string())
+                        .spawn(move || {
+                            server.serve(stream);
+                            connections.fetch_sub(1, Ordering::AcqRel);
+                        });
+                    if spawned.is_err() {
+                        connections.fetch_sub(1, Ordering::AcqRel);
+                    }
+                }
+            })?;
+        tracing::info!(
+            target: ENDPOINT_TARGET,
+            %addr,
+            "diagnostics endpoint listening on http://{addr}"
+        );
+        Ok(Self {
+            addr,
+            stopped,
+            file,
+        })
+    }
+
+    /// Returns the address the endpoint listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for DiagnosticsEndpoint {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        // Wake up the blocked accept so the thread sees it is stopped
+        let _ = TcpStream::connect(self.addr);
+        if let Some(file) = &self.file {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
+
+/// Writes the endpoint's URL to the app cache directory, and returns the
+/// file's path.
+fn publish<R: Runtime>(app: &AppHandle<R>, addr: SocketAddr) -> Option<PathBuf> {
+    let dir = app.path().app_cache_dir().ok()?;
+    let path = dir.join(ENDPOINT_FILE);
+    let contents = json!({ "url": format!("http://{addr}"), "pid": std::process::id() });
+    let written =
+        std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, contents.to_string()));
+    match written {
+        Ok(()) => Some(path),
+        Err(e) => {
+            tracing::warn!(
+                target: ENDPOINT_TARGET,
+                "Failed to write {}: {e}",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
+struct Server<R: Runtime> {
+    app: AppHandle<R>,
+    token: String,
+    started: Instant,
+}
+
+/// A parsed HTTP request.
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+/// A JSON response.
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl std::fmt::Display) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.to_string() }),
+        }
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> std::io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            501 => "Not Implemented",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        };
+        let body = self.body.to_string();
+        write!(
+            stream,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.status,
+            body.len()
+        )?;
+        stream.flush()
+    }
+}
+
+/// A change of the filter, the body of `POST /filters`.
+#[derive(Debug, Deserialize)]
+struct FilterChange {
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    reset: bool,
+}
+
+impl<R: Runtime> Server<R> {
+    fn serve(&self, mut stream: TcpStream) {
+        let reader = DeadlineReader {
+            stream: &stream,
+            deadline: Instant::now() + REQUEST_TIMEOUT,
+        };
+        let response = match read_request(&mut BufReader::new(reader)) {
+            Ok(request) => self.handle(&request),
+            Err(e) => Response::error(400, e),
+        };
+        if response.status >= 500 {
+            tracing::warn!(
+                target: ENDPOINT_TARGET,
+                status = response.status,
+                "diagnostics endpoint request failed: {}",
+                response.body["error"]
+            );
+        }
+        let _ = response.write_to(&mut stream);
+    }
+
+    fn handle(&self, request: &Request) -> Response {
+        let authorized = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.token.as_bytes()));
+        if !authorized {
+            return Response::error(401, "missing or wrong bearer token");
+        }
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/health") => Ok(self.health()),
+            ("GET", "/logs/tail") => self.tail(&request.query),
+            ("GET", "/filters") => self.filters(None),
+            ("POST", "/filters") => self.filters(Some(&request.body)),
+            ("POST", "/profile/start") => self.start_profile(),
+            ("POST", "/profile/stop") => self.stop_profile(),
+            (_, "/health" | "/logs/tail" | "/filters" | "/profile/start" | "/profile/stop") => Ok(
+                Response::error(405, format!("{} is not allowed", request.method)),
+            ),
+            (_, path) => Ok(Response::error(404, format!("no endpoint at {path}"))),
+        };
+        result.unwrap_or_else(|e| Response::error(500, e))
+    }
+
+    fn health(&self) -> Response {
+        let package = self.app.package_info();
+        Response::ok(json!({
+            "status": "ok",
+            "app": { "name": package.name, "version": package.version.to_string() },
+            "pid": std::process::id(),
+            "uptimeMs": self.started.elapsed().as_millis() as u64,
+        }))
+    }
+
+    fn tail(&self, query: &str) -> crate::Result<Response> {
+        let lines = match query_param(query, "lines") {
+            Some(lines) => match lines.parse::<usize>() {
+                Ok(lines) => lines.min(MAX_TAIL_LINES),
+                Err(_) => return Ok(Response::error(400, "lines must be a number")),
+            },
+            None => DEFAULT_TAIL_LINES,
+        };
+        let tail = crate::tail_logs(&self.app, lines, false)?;
+        Ok(Response::ok(serde_json::to_value(tail).unwrap_or_default()))
+    }
+
+    fn filters(&self, change: Option<&[u8]>) -> crate::Result<Response> {
+        let Some(filter) = self.app.try_state::<FilterHandle>() else {
+            return Ok(Response::error(
+                503,
+                "the filter can only be changed with the default subscriber",
+            ));
+        };
+        if let Some(body) = change {
+            let change: FilterChange = match serde_json::from_slice(body) {
+                Ok(change) => change,
+                Err(e) => return Ok(Response::error(400, e)),
+            };
+            let level = match change.level.as_deref().map(str::parse::<LevelFilter>) {
+                Some(Ok(level)) => Some(level),
+                Some(Err(e)) => return Ok(Response::error(400, e)),
+                None if change.reset => None,
+                None => return Ok(Response::error(400, "level or reset is required")),
+            };
+            if change.reset {
+                filter.reset()?;
+            }
+            match (change.target, level) {
+                (Some(target), Some(level)) => filter.set_target(target, level)?,
+                (None, Some(level)) => filter.set_level(level)?,
+                (_, None) => {}
+            }
+            tracing::info!(target: ENDPOINT_TARGET, "filter changed by the diagnostics endpoint");
+        }
+        Ok(Response::ok(
+            serde_json::to_value(filter.effective()).unwrap_or_default(),
+        ))
+    }
+
+    #[cfg(feature = "profiling")]
+    fn start_profile(&self) -> crate::Result<Response> {
+        use crate::SpanAwareProfilingExt;
+
+        self.app.start_span_aware_profile()?;
+        Ok(Response::ok(json!({ "profiling": true })))
+    }
+
+    #[cfg(not(feature = "profiling"))]
+    fn start_profile(&self) -> crate::Result<Response> {
+        Ok(Response::error(501, "the profiling feature is disabled"))
+    }
+
+    #[cfg(feature = "profiling")]
+    fn stop_profile(&self) -> crate::Result<Response> {
+        use crate::SpanAwareProfilingExt;
+
+        let report = self.app.stop_span_aware_profile()?;
+        Ok(Response::ok(
+            serde_json::to_value(report).unwrap_or_default(),
+        ))
+    }
+
+    #[cfg(not(feature = "profiling"))]
+    fn stop_profile(&self) -> crate::Result<Response> {
+        Ok(Response::error(501, "the profiling feature is disabled"))
+    }
+}
+
+/// Reads from a stream until a deadline, however slowly the client sends.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(std::io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Reads an HTTP/1.1 request with at most [`MAX_BODY_BYTES`] of body.
+fn read_request(reader: &mut impl BufRead) -> std::io::Result<Request> {
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+
+    let mut head = reader.by_ref().take(MAX_HEAD_BYTES);
+    let mut line = String::new();
+    head.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid("malformed request line"));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        authorization: None,
+        body: Vec::new(),
+    };
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if head.read_line(&mut line)? == 0 {
+            return Err(invalid("headers are too long or incomplete"));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(invalid("malformed header"));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            request.authorization = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| invalid("malformed content length"))?;
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(invalid("body is too large"));
+    }
+    request.body = vec![0; content_length];
+    reader.read_exact(&mut request.body)?;
+    Ok(request)
+}
+
+/// Returns the value of the query parameter `name`.
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Compares two byte strings in time independent of where they differ, so
+/// tokens cannot be guessed byte by byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_requests() {
+        let raw = "POST /filters?lines=5&x=1 HTTP/1.1\r\nHost: 127.0.0.1\r\nauthorization: Bearer t0k\r\nContent-Length: 17\r\n\r\n{\"level\":\"info\"}\nextra";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(
+            request,
+            Request {
+                method: "POST".to_string(),
+                path: "/filters".to_string(),
+                query: "lines=5&x=1".to_string(),
+                authorization: Some("Bearer t0k".to_string()),
+                body: b"{\"level\":\"info\"}\n".to_vec(),
+            }
+        );
+        assert_eq!(query_param(&request.query, "lines"), Some("5"));
+        assert_eq!(query_param(&request.query, "line"), None);
+
+        assert!(read_request(&mut "GET\r\n\r\n".as_bytes()).is_err());
+        assert!(read_request(&mut "GET / HTTP/1.1\r\nHost: x".as_bytes()).is_err());
+        let huge = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert!(read_request(&mut huge.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn writes_json_responses() {
+        let mut out = Vec::new();
+        Response::error(401, "no").write_to(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 401 Unauthorized\r\nContent-Type: application/json\r\nContent-Length: 14\r\nConnection: close\r\n\r\n{\"error\":\"no\"}"
+        );
+    }
+}
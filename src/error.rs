@@ -33,15 +33,71 @@ pub enum Error {
     #[error("Internal logger disabled and cannot be acquired or attached")]
     LoggerNotInitialized,
 
+    /// The app set a subscriber before the plugin could set its default one.
+    #[error(
+        "A tracing subscriber is already set. Remove with_default_subscriber() to keep it, \
+         or use try_with_default_subscriber() to fall back to it"
+    )]
+    SubscriberAlreadySet,
+
     /// Failed to set the global default subscriber.
     #[error(transparent)]
     #[cfg_attr(feature = "specta", specta(skip))]
     SetGlobalDefault(#[from] tracing::subscriber::SetGlobalDefaultError),
 
+    /// An error writing a diagnostic report archive.
+    #[cfg(feature = "diagnostics")]
+    #[error(transparent)]
+    #[cfg_attr(feature = "specta", specta(skip))]
+    Zip(#[from] zip::result::ZipError),
+
+    /// A diagnostic report has no readable manifest.
+    #[cfg(feature = "diagnostics")]
+    #[error("Invalid diagnostic report manifest: {0}")]
+    InvalidManifest(String),
+
+    /// An error from CPU profiling.
+    #[cfg(feature = "profiling")]
+    #[error(transparent)]
+    #[cfg_attr(feature = "specta", specta(skip))]
+    Profiling(#[from] tauri_plugin_profiling::Error),
+
     /// The requested feature is not yet implemented.
     #[error("Not implemented")]
     NotImplemented,
 
+    /// No open operation has the given id.
+    #[error("Unknown operation: {0}")]
+    UnknownOperation(String),
+
+    /// The maximum number of operations are already open.
+    #[error("Too many open operations (at most {0})")]
+    TooManyOperations(usize),
+
+    /// No open frontend span has the given id.
+    #[error("Unknown span: {0}")]
+    UnknownSpan(String),
+
+    /// No running end-to-end test has the given name.
+    #[error("Unknown test: {0}")]
+    UnknownTest(String),
+
+    /// A test was delimited but test mode is not enabled.
+    #[error("Test mode is not enabled")]
+    TestModeDisabled,
+
+    /// Span statistics were requested but are not enabled.
+    #[error("Span statistics are not enabled")]
+    SpanStatsDisabled,
+
+    /// Filter statistics were requested but are not enabled.
+    #[error("Filter statistics are not enabled")]
+    FilterStatsDisabled,
+
+    /// The in-memory log history was requested but is not enabled.
+    #[error("Log history is not enabled")]
+    LogHistoryDisabled,
+
     /// A mutex lock was poisoned (another thread panicked while holding the lock).
     #[error("Lock poisoned: {0}")]
     LockPoisoned(String),
@@ -0,0 +1,83 @@
+//! Logging errors with their source chain.
+//!
+//! An error's `Display` usually describes only the outermost failure, e.g.
+//! "sync failed", without the "connection refused" that caused it. Errors
+//! recorded as error values, e.g. `error = &err as &dyn std::error::Error`,
+//! keep their [`source()`](std::error::Error::source) chain:
+//!
+//! - Text outputs list it as `error.sources=[...]`.
+//! - The RFC 5424, GELF, and Loki outputs record each source as a separate
+//!   field, `error.source.1` for the direct cause, `error.source.2` for its
+//!   cause, and so on.
+//!
+//! [`ResultExt::trace_err()`] logs the error of a `Result` this way, with a
+//! backtrace when `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` enables them.
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
+
+/// The target of errors logged with [`ResultExt::trace_err()`].
+pub const TRACED_ERROR_TARGET: &str = "tauri_plugin_tracing::error";
+
+/// Logs the error of a `Result` with its source chain.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_plugin_tracing::ResultExt;
+///
+/// fn load() -> std::io::Result<String> {
+///     std::fs::read_to_string("settings.json").trace_err("my_app::settings")
+/// }
+/// ```
+pub trait ResultExt<T, E> {
+    /// Logs the error, if any, as an `ERROR` event and returns `self`.
+    ///
+    /// Event targets are fixed when the code is compiled, so the event's
+    /// target is [`TRACED_ERROR_TARGET`] and `target` is recorded as its
+    /// `origin` field, next to the caller's `location`. The error is the
+    /// `error` field, and the backtrace, if captured, the `backtrace` field.
+    fn trace_err(self, target: &str) -> Self;
+}
+
+impl<T, E: Error + 'static> ResultExt<T, E> for Result<T, E> {
+    #[track_caller]
+    fn trace_err(self, target: &str) -> Self {
+        if let Err(error) = &self {
+            let location = std::panic::Location::caller();
+            let backtrace = Backtrace::capture();
+            let error: &(dyn Error + 'static) = error;
+            if backtrace.status() == BacktraceStatus::Captured {
+                tracing::error!(
+                    target: TRACED_ERROR_TARGET,
+                    origin = target,
+                    location = %location,
+                    error,
+                    backtrace = %backtrace,
+                    "{error}"
+                );
+            } else {
+                tracing::error!(
+                    target: TRACED_ERROR_TARGET,
+                    origin = target,
+                    location = %location,
+                    error,
+                    "{error}"
+                );
+            }
+        }
+        self
+    }
+}
+
+/// Returns the `Display` of each error in the source chain of `error`,
+/// starting with its direct cause.
+pub(crate) fn sources(error: &(dyn Error + 'static)) -> impl Iterator<Item = String> {
+    std::iter::successors(error.source(), |error| error.source()).map(|error| error.to_string())
+}
+
+/// Returns the name of the field for the `n`th source of the error field
+/// `name`, counting from 1.
+pub(crate) fn source_field(name: &str, n: usize) -> String {
+    format!("{name}.source.{n}")
+}
@@ -0,0 +1,135 @@
+//! Stable ids for known events.
+//!
+//! Support teams and analytics track error classes by their message, which
+//! changes with each rewording. [`event_ids!`](crate::event_ids) declares a
+//! catalog of known events, each with a stable id, a level, and a
+//! description, and [`log_id!`](crate::log_id) logs one of them with its id
+//! in the `event_id` field, which every output records like other fields.
+//!
+//! Registering the catalog with
+//! [`Builder::with_event_catalog()`](crate::Builder::with_event_catalog)
+//! includes it in diagnostic reports, so the ids in a report's logs can be
+//! looked up, and checks that no id is registered twice.
+
+use std::fmt;
+
+use tracing::Level;
+
+/// The field that holds the id of an event logged with
+/// [`log_id!`](crate::log_id).
+pub const EVENT_ID_FIELD: &str = "event_id";
+
+/// A known event, declared with [`event_ids!`](crate::event_ids).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventId {
+    /// The stable id, such as `E1001`.
+    pub id: &'static str,
+    /// The level the event is logged at.
+    pub level: Level,
+    /// What the event means, used as its message by default.
+    pub description: &'static str,
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id)
+    }
+}
+
+/// Declares known events as [`EventId`] constants named after their ids.
+///
+/// Each entry is the id, the level, and a description. Ids must be valid
+/// identifiers, and should never be reused for another event.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// tauri_plugin_tracing::event_ids! {
+///     /// The database could not be reached at startup.
+///     pub E1001: ERROR = "db connection failed";
+///     pub W2001: WARN = "sync retried";
+/// }
+/// ```
+#[macro_export]
+macro_rules! event_ids {
+    ($($(#[$attr:meta])* $vis:vis $id:ident: $level:ident = $description:literal;)*) => {
+        $(
+            $(#[$attr])*
+            $vis const $id: $crate::EventId = $crate::EventId {
+                id: stringify!($id),
+                level: $crate::tracing::Level::$level,
+                description: $description,
+            };
+        )*
+    };
+}
+
+/// Logs a known event at its level, with its id in the
+/// [`EVENT_ID_FIELD`] field.
+///
+/// Fields and a message may follow the event, as in [`tracing::event!`].
+/// The message defaults to the event's description.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{event_ids, log_id};
+/// event_ids! {
+///     pub E1001: ERROR = "db connection failed";
+/// }
+///
+/// # let host = "db.local";
+/// log_id!(E1001); // db connection failed event_id=E1001
+/// log_id!(E1001, %host, "could not connect to {host}");
+/// ```
+#[macro_export]
+macro_rules! log_id {
+    ($id:expr $(,)?) => {
+        $crate::log_id!($id, "{}", $id.description)
+    };
+    ($id:expr, $($arg:tt)+) => {
+        $crate::tracing::event!($id.level, event_id = %$id.id, $($arg)+)
+    };
+}
+
+/// Returns the ids registered more than once with different levels or
+/// descriptions.
+pub(crate) fn conflicting_ids(catalog: &[EventId]) -> Vec<&'static str> {
+    let mut conflicts = Vec::new();
+    for (n, event) in catalog.iter().enumerate() {
+        let conflicting = catalog[..n]
+            .iter()
+            .any(|other| other.id == event.id && other != event);
+        if conflicting && !conflicts.contains(&event.id) {
+            conflicts.push(event.id);
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    event_ids! {
+        E1001: ERROR = "db connection failed";
+        W2001: WARN = "sync retried";
+    }
+
+    #[test]
+    fn declares_ids_and_finds_conflicts() {
+        assert_eq!(E1001.id, "E1001");
+        assert_eq!(W2001.level, Level::WARN);
+        assert_eq!(E1001.to_string(), "E1001");
+
+        let reworded = EventId {
+            description: "database unreachable",
+            ..E1001
+        };
+        assert!(conflicting_ids(&[E1001, W2001, E1001]).is_empty());
+        assert_eq!(
+            conflicting_ids(&[E1001, W2001, reworded, reworded]),
+            ["E1001"]
+        );
+    }
+}
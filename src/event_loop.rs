@@ -0,0 +1,268 @@
+//! Detection of a blocked event loop.
+//!
+//! Window events, IPC, and the tasks posted with `run_on_main_thread()` all
+//! run on the main thread, so a synchronous command or a slow plugin hook
+//! blocking it freezes every window. With
+//! [`Builder::with_event_loop_watchdog()`](crate::Builder::with_event_loop_watchdog),
+//! a background thread posts a task to the main thread several times per
+//! threshold and measures how long it waits to run. When the loop is blocked
+//! for longer than the threshold, it logs a `WARN` event under the
+//! [`EVENT_LOOP_TARGET`] target with the `blocked_ms` duration so far, and an
+//! `INFO` event with the total duration once the loop runs again. Each block
+//! is logged once.
+//!
+//! ## Native backtraces
+//!
+//! With the `main-thread-backtrace` feature, the `WARN` event also records
+//! what the main thread is doing in a `backtrace` field, captured while it is
+//! still blocked. This is supported on Unix platforms, where the watchdog
+//! interrupts the main thread with `SIGURG` and walks its stack from the
+//! signal handler. Walking a stack from a signal handler is best effort, like
+//! in sampling profilers: the backtrace is missing when the app installed its
+//! own `SIGURG` handler or the main thread does not answer in time. Function
+//! names and source locations need debug symbols.
+
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Runtime};
+
+/// The target used for event loop events.
+pub const EVENT_LOOP_TARGET: &str = "app::event_loop";
+
+/// Watches the main thread's event loop for blocks.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_event_loop_watchdog()`](crate::Builder::with_event_loop_watchdog)
+/// is used. Watching stops when it is dropped.
+pub struct EventLoopWatchdog {
+    state: Arc<Mutex<State>>,
+    threshold: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    /// When the pending probe was posted, if it has not run yet.
+    probe_sent: Option<Instant>,
+    /// Whether the pending probe was reported blocked.
+    blocked: bool,
+}
+
+impl EventLoopWatchdog {
+    /// Starts watching the event loop for blocks longer than `threshold` on
+    /// a background thread.
+    ///
+    /// Must be called on the main thread, such as in a plugin's or the app's
+    /// setup hook, for native backtraces to be of the main thread.
+    pub fn start<R: Runtime>(app: AppHandle<R>, threshold: Duration) -> crate::Result<Self> {
+        let threshold = threshold.max(Duration::from_millis(20));
+        #[cfg(all(feature = "main-thread-backtrace", unix))]
+        native::register_main_thread();
+        let state = Arc::new(Mutex::new(State::default()));
+        let weak = Arc::downgrade(&state);
+        std::thread::Builder::new()
+            .name("tracing-event-loop".to_string())
+            .spawn(move || run_watchdog(app, weak, threshold))?;
+        Ok(Self { state, threshold })
+    }
+
+    /// Returns the duration after which the event loop is reported blocked.
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// Returns how long the event loop has been blocked, if it is blocked
+    /// for longer than the threshold.
+    pub fn blocked_for(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .probe_sent
+            .map(|sent| sent.elapsed())
+            .filter(|blocked| *blocked >= self.threshold)
+    }
+}
+
+fn run_watchdog<R: Runtime>(app: AppHandle<R>, state: Weak<Mutex<State>>, threshold: Duration) {
+    // Checking several times per threshold reports a block soon after it
+    // exceeds the threshold
+    let period = threshold / 4;
+
+    loop {
+        std::thread::sleep(period);
+        let Some(state) = state.upgrade() else {
+            return;
+        };
+        let now = Instant::now();
+        let mut guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(sent) = guard.probe_sent {
+            let blocked = now.duration_since(sent);
+            if !guard.blocked && blocked >= threshold {
+                guard.blocked = true;
+                drop(guard);
+                let backtrace = main_thread_backtrace();
+                tracing::warn!(
+                    target: EVENT_LOOP_TARGET,
+                    blocked_ms = blocked.as_millis() as u64,
+                    backtrace = backtrace.as_deref(),
+                    "event loop is blocked"
+                );
+            }
+            continue;
+        }
+        guard.probe_sent = Some(now);
+        drop(guard);
+
+        let probe = Arc::downgrade(&state);
+        let result = app.run_on_main_thread(move || {
+            let Some(state) = probe.upgrade() else {
+                return;
+            };
+            let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(sent) = state.probe_sent.take() else {
+                return;
+            };
+            let blocked = sent.elapsed();
+            if std::mem::take(&mut state.blocked) {
+                tracing::info!(
+                    target: EVENT_LOOP_TARGET,
+                    blocked_ms = blocked.as_millis() as u64,
+                    "event loop is responsive again"
+                );
+            } else if blocked >= threshold {
+                // Ended between two checks, too late for a backtrace
+                tracing::warn!(
+                    target: EVENT_LOOP_TARGET,
+                    blocked_ms = blocked.as_millis() as u64,
+                    "event loop was blocked"
+                );
+            }
+        });
+        // The event loop has exited
+        if result.is_err() {
+            state.lock().unwrap_or_else(|e| e.into_inner()).probe_sent = None;
+        }
+    }
+}
+
+#[cfg(all(feature = "main-thread-backtrace", unix))]
+fn main_thread_backtrace() -> Option<String> {
+    native::capture()
+}
+
+#[cfg(not(all(feature = "main-thread-backtrace", unix)))]
+fn main_thread_backtrace() -> Option<String> {
+    None
+}
+
+/// Captures the main thread's stack from a signal handler.
+#[cfg(all(feature = "main-thread-backtrace", unix))]
+mod native {
+    use std::fmt::Write;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+
+    const SIGNAL: libc::c_int = libc::SIGURG;
+    const MAX_FRAMES: usize = 128;
+
+    /// The main thread, if the signal handler is installed.
+    static MAIN_THREAD: OnceLock<Option<libc::pthread_t>> = OnceLock::new();
+    /// The instruction pointers of the captured frames, written by the
+    /// signal handler without allocating.
+    static FRAMES: [AtomicUsize; MAX_FRAMES] = [const { AtomicUsize::new(0) }; MAX_FRAMES];
+    static FRAME_COUNT: AtomicUsize = AtomicUsize::new(0);
+    static CAPTURED: AtomicBool = AtomicBool::new(false);
+    static CAPTURING: Mutex<()> = Mutex::new(());
+
+    /// Installs the signal handler and records the calling thread as the
+    /// main thread, unless the app handles the signal itself.
+    pub(super) fn register_main_thread() {
+        MAIN_THREAD.get_or_init(|| {
+            // Safety: the handler only writes to atomics and walks the stack
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                action.sa_flags = libc::SA_RESTART;
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut previous: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(SIGNAL, &action, &mut previous) != 0 {
+                    return None;
+                }
+                if previous.sa_sigaction != libc::SIG_DFL && previous.sa_sigaction != libc::SIG_IGN
+                {
+                    libc::sigaction(SIGNAL, &previous, std::ptr::null_mut());
+                    return None;
+                }
+                Some(libc::pthread_self())
+            }
+        });
+    }
+
+    extern "C" fn on_signal(_: libc::c_int) {
+        let handler = on_signal as extern "C" fn(libc::c_int) as usize;
+        let mut count = 0;
+        // Safety: captures are serialized by `CAPTURING`
+        unsafe {
+            backtrace::trace_unsynchronized(|frame| {
+                // Skip the frames of the handler itself
+                if frame.symbol_address() as usize == handler {
+                    count = 0;
+                    return true;
+                }
+                FRAMES[count].store(frame.ip() as usize, Ordering::Relaxed);
+                count += 1;
+                count < MAX_FRAMES
+            });
+        }
+        FRAME_COUNT.store(count, Ordering::Relaxed);
+        CAPTURED.store(true, Ordering::Release);
+    }
+
+    /// Interrupts the main thread to capture its stack, and symbolizes it.
+    pub(super) fn capture() -> Option<String> {
+        let thread = (*MAIN_THREAD.get()?)?;
+        let _capturing = CAPTURING.lock().unwrap_or_else(|e| e.into_inner());
+        CAPTURED.store(false, Ordering::Release);
+        // Safety: the main thread outlives the app
+        if unsafe { libc::pthread_kill(thread, SIGNAL) } != 0 {
+            return None;
+        }
+        let deadline = Instant::now() + Duration::from_millis(100);
+        while !CAPTURED.load(Ordering::Acquire) {
+            if Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut backtrace = String::new();
+        let count = FRAME_COUNT.load(Ordering::Relaxed);
+        for (n, frame) in FRAMES[..count].iter().enumerate() {
+            let ip = frame.load(Ordering::Relaxed);
+            if ip == 0 {
+                break;
+            }
+            // Return addresses point after the call
+            let addr = if n == 0 { ip } else { ip - 1 };
+            let mut resolved = false;
+            backtrace::resolve(addr as *mut _, |symbol| {
+                resolved = true;
+                match symbol.name() {
+                    Some(name) => {
+                        let _ = write!(backtrace, "\n{n:4}: {name:#}");
+                    }
+                    None => {
+                        let _ = write!(backtrace, "\n{n:4}: {ip:#x}");
+                    }
+                }
+                if let (Some(file), Some(line)) = (symbol.filename(), symbol.lineno()) {
+                    let _ = write!(backtrace, "\n        at {}:{line}", file.display());
+                }
+            });
+            if !resolved {
+                let _ = write!(backtrace, "\n{n:4}: {ip:#x}");
+            }
+        }
+        Some(backtrace)
+    }
+}
@@ -0,0 +1,278 @@
+//! Templated names for log files.
+//!
+//! The file target names its files like `app.2024-01-15.log`. Teams that
+//! collect logs from many machines often expect their own naming scheme, so
+//! [`Builder::with_file_name_template()`](crate::Builder::with_file_name_template)
+//! names them from a template like `"{prefix}.{date}.{hostname}.log"`
+//! instead, with these placeholders:
+//!
+//! - `{prefix}`: the target's file name, `app` by default, which includes
+//!   the app version with [`VersionLayout::FileName`](crate::VersionLayout::FileName).
+//! - `{date}`: the UTC date of the rotation period, as `2024-01-15`, with the
+//!   hour and minute for hourly and minutely rotation. It is left out, with
+//!   the dot before it, when files are not rotated by time.
+//! - `{hostname}`, `{pid}` and `{version}`: the machine's host name, the
+//!   process ID and the app version.
+//! - `{instance}`: the process ID of an instance that writes to its own file,
+//!   see [`InstanceStrategy`](crate::InstanceStrategy). It is left out, with
+//!   the separator before it, for the instance writing the shared file.
+//!   Without it, the process ID is appended to the part before the date, as
+//!   in `app-4242.2024-01-15.log`.
+//!
+//! The file appender writes the date itself, between dots and in a fixed
+//! format, so `{date}` must be a dot-separated part of its own, neither the
+//! first nor the last. Templates must end in `.log`, which retention and
+//! `get_log_stats` look for. Retention finds the files of other app versions
+//! by the target's file name, so templates used with a
+//! [`VersionLayout`](crate::VersionLayout) should start with `{prefix}`.
+
+use std::fmt;
+
+/// A placeholder in a file name template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Prefix,
+    Hostname,
+    Pid,
+    Version,
+    Instance,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// The values placeholders are replaced with.
+pub(crate) struct Values<'a> {
+    /// The target's file name.
+    pub(crate) prefix: &'a str,
+    pub(crate) version: &'a str,
+    /// The process ID of an instance that writes to its own file.
+    pub(crate) instance: Option<u32>,
+}
+
+/// A parsed file name template, split around its `{date}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileNameTemplate {
+    /// The parts before `.{date}.`, which become the file name prefix.
+    head: Vec<Part>,
+    /// The parts after `.{date}.`, which become the file name suffix.
+    tail: Vec<Part>,
+}
+
+/// Why a template cannot be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TemplateError(String);
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FileNameTemplate {
+    pub(crate) fn parse(template: &str) -> Result<Self, TemplateError> {
+        let error = |reason: &str| Err(TemplateError(reason.to_string()));
+        if !template.ends_with(".log") {
+            return error("it must end in .log");
+        }
+        if template.contains(['/', '\\']) {
+            return error("it must not contain path separators");
+        }
+        let Some((head, tail)) = template.split_once(".{date}.") else {
+            return error("{date} must be between dots, and neither first nor last");
+        };
+        let (head, tail) = (parse_parts(head)?, parse_parts(tail)?);
+        if head
+            .iter()
+            .all(|part| *part == Part::Placeholder(Placeholder::Instance))
+        {
+            return error("it must not start with {date} or {instance}.{date}");
+        }
+        Ok(Self { head, tail })
+    }
+
+    /// Returns whether the template places the instance's process ID itself.
+    pub(crate) fn has_instance(&self) -> bool {
+        self.head
+            .iter()
+            .chain(&self.tail)
+            .any(|part| *part == Part::Placeholder(Placeholder::Instance))
+    }
+
+    /// Returns the file name prefix and suffix the appenders write to, around
+    /// the date.
+    pub(crate) fn render(&self, values: &Values<'_>) -> (String, String) {
+        let hostname = hostname();
+        (
+            render_parts(&self.head, values, &hostname),
+            render_parts(&self.tail, values, &hostname),
+        )
+    }
+}
+
+fn parse_parts(template: &str) -> Result<Vec<Part>, TemplateError> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(Part::Literal(rest[..start].to_string()));
+        }
+        let Some(end) = rest[start..].find('}') else {
+            return Err(TemplateError(format!("unclosed placeholder in {rest:?}")));
+        };
+        let placeholder = match &rest[start + 1..start + end] {
+            "prefix" => Placeholder::Prefix,
+            "hostname" => Placeholder::Hostname,
+            "pid" => Placeholder::Pid,
+            "version" => Placeholder::Version,
+            "instance" => Placeholder::Instance,
+            "date" => return Err(TemplateError("{date} must appear once".to_string())),
+            name => return Err(TemplateError(format!("unknown placeholder {{{name}}}"))),
+        };
+        parts.push(Part::Placeholder(placeholder));
+        rest = &rest[start + end + 1..];
+    }
+    if rest.contains('}') {
+        return Err(TemplateError(format!("unopened placeholder in {rest:?}")));
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest.to_string()));
+    }
+    Ok(parts)
+}
+
+fn render_parts(parts: &[Part], values: &Values<'_>, hostname: &str) -> String {
+    let mut rendered = String::new();
+    // Whether a left out `{instance}` at the start takes the next separator
+    let mut skip_separator = false;
+    for part in parts {
+        let value = match part {
+            Part::Literal(literal) => {
+                let literal = if std::mem::take(&mut skip_separator) {
+                    literal.strip_prefix(is_separator).unwrap_or(literal)
+                } else {
+                    literal
+                };
+                rendered.push_str(literal);
+                continue;
+            }
+            Part::Placeholder(Placeholder::Instance) => match values.instance {
+                Some(pid) => pid.to_string(),
+                None => {
+                    match rendered.chars().last() {
+                        Some(last) if is_separator(last) => {
+                            rendered.pop();
+                        }
+                        Some(_) => {}
+                        None => skip_separator = true,
+                    }
+                    continue;
+                }
+            },
+            Part::Placeholder(Placeholder::Prefix) => values.prefix.to_string(),
+            Part::Placeholder(Placeholder::Hostname) => hostname.to_string(),
+            Part::Placeholder(Placeholder::Pid) => std::process::id().to_string(),
+            Part::Placeholder(Placeholder::Version) => values.version.to_string(),
+        };
+        rendered.push_str(&value);
+    }
+    rendered
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '.' | '-' | '_')
+}
+
+/// Returns the machine's host name, usable in a file name.
+fn hostname() -> String {
+    #[cfg(unix)]
+    let hostname = {
+        let mut buf = [0u8; 256];
+        // Safety: the buffer is writable for its length
+        let result = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+        (result == 0)
+            .then(|| std::ffi::CStr::from_bytes_until_nul(&buf).ok())
+            .flatten()
+            .map(|name| name.to_string_lossy().into_owned())
+    };
+    #[cfg(windows)]
+    let hostname = std::env::var("COMPUTERNAME").ok();
+    #[cfg(not(any(unix, windows)))]
+    let hostname: Option<String> = None;
+
+    hostname
+        .filter(|name| !name.is_empty())
+        .map(|name| name.replace(['/', '\\'], "_"))
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, instance: Option<u32>) -> (String, String) {
+        FileNameTemplate::parse(template).unwrap().render(&Values {
+            prefix: "app",
+            version: "1.4.2",
+            instance,
+        })
+    }
+
+    #[test]
+    fn renders_around_the_date() {
+        assert_eq!(
+            render("{prefix}-{version}.{date}.log", None),
+            ("app-1.4.2".to_string(), "log".to_string())
+        );
+        assert_eq!(
+            render("{prefix}.{date}.{instance}.log", Some(4242)),
+            ("app".to_string(), "4242.log".to_string())
+        );
+        assert_eq!(
+            render("collected.{prefix}.{date}.{pid}.log", None),
+            (
+                "collected.app".to_string(),
+                format!("{}.log", std::process::id())
+            )
+        );
+    }
+
+    #[test]
+    fn leaves_out_the_shared_instance() {
+        assert_eq!(
+            render("{prefix}.{date}.{instance}.log", None),
+            ("app".to_string(), "log".to_string())
+        );
+        assert_eq!(
+            render("{prefix}_{instance}.{date}.log", None),
+            ("app".to_string(), "log".to_string())
+        );
+        assert_eq!(
+            render("{instance}-{prefix}.{date}.log", None),
+            ("app".to_string(), "log".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unusable_templates() {
+        for template in [
+            "{prefix}.{date}.txt",
+            "{date}.{prefix}.log",
+            "{prefix}-{date}.log",
+            "{prefix}.{date}.{date}.log",
+            "{prefix}.{date}.{host}.log",
+            "{prefix}.{date}.{pid.log",
+            "logs/{prefix}.{date}.log",
+            "{instance}.{date}.log",
+        ] {
+            assert!(
+                FileNameTemplate::parse(template).is_err(),
+                "{template} should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! Runtime changes to the default subscriber's filter.
+//!
+//! When the plugin installs the default subscriber, it manages a
+//! [`FilterHandle`] that changes the global level and per-target levels
+//! without restarting the app. The `set_log_level`, `set_target_level` and
+//! `reset_log_filter` commands expose it to the frontend, and
+//! `get_effective_filter` returns the filter in effect so debug UIs can show
+//! what is being captured.
+//!
+//! With [`Builder::with_filter_persistence()`](crate::Builder::with_filter_persistence),
+//! changes are also saved to [`FILTER_FILE_NAME`] in the app config directory
+//! and restored at the next startup, so "enable debug logging" survives the
+//! restart that reproduces a bug.
+//!
+//! Each output of the default subscriber filters events on its own, so
+//! [`Builder::filter_for()`](crate::Builder::filter_for) can make one output
+//! more verbose than the others. Runtime changes apply to the outputs without
+//! a filter of their own.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde::{Deserialize, Serialize};
+use tracing::level_filters::STATIC_MAX_LEVEL;
+use tracing::subscriber::Interest;
+use tracing::{Level, Metadata, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::{Context, Filter, Layer};
+
+use crate::{LogLevel, Target};
+
+/// The file in the app config directory that persisted filter changes are
+/// stored in.
+pub const FILTER_FILE_NAME: &str = "tracing-filter.json";
+
+/// Filter changes made at runtime, as stored on disk.
+///
+/// Levels are stored as their names, e.g. `"debug"` or `"off"`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FilterOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    level: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    targets: BTreeMap<String, String>,
+}
+
+impl FilterOverrides {
+    /// Reads persisted overrides, or returns none if there are none.
+    ///
+    /// Unreadable files are ignored, so a corrupt file cannot prevent startup.
+    pub(crate) fn load(path: Option<&PathBuf>) -> Self {
+        path.and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.level.is_none() && self.targets.is_empty()
+    }
+
+    /// Applies the overrides on top of `base`.
+    pub(crate) fn apply_to(&self, base: &Targets) -> Targets {
+        let level = self
+            .level
+            .as_deref()
+            .and_then(|level| LevelFilter::from_str(level).ok())
+            .or_else(|| base.default_level())
+            .unwrap_or(LevelFilter::OFF);
+        let overrides = self.targets.iter().filter_map(|(target, level)| {
+            LevelFilter::from_str(level)
+                .ok()
+                .map(|level| (target.clone(), level))
+        });
+        Targets::new()
+            .with_targets(
+                base.iter()
+                    .map(|(target, level)| (target.to_string(), level)),
+            )
+            .with_targets(overrides)
+            .with_default(level)
+    }
+}
+
+/// The filter in effect, as returned by `get_effective_filter`.
+///
+/// Levels are the most verbose level recorded; `None` means nothing is
+/// recorded. They are capped at the most verbose level compiled into the
+/// build, see the `max-level-*` features.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EffectiveFilter {
+    /// The level for targets without a more specific level.
+    pub level: Option<LogLevel>,
+    /// Levels for targets and their submodules.
+    pub targets: BTreeMap<String, Option<LogLevel>>,
+}
+
+impl From<&Targets> for EffectiveFilter {
+    fn from(targets: &Targets) -> Self {
+        fn log_level(level: LevelFilter) -> Option<LogLevel> {
+            level.min(STATIC_MAX_LEVEL).into_level().map(LogLevel::from)
+        }
+        Self {
+            level: targets.default_level().and_then(log_level),
+            targets: targets
+                .iter()
+                .map(|(target, level)| (target.to_string(), log_level(level)))
+                .collect(),
+        }
+    }
+}
+
+/// Warns if `level` enables events that the `max-level-*` or
+/// `release-max-level-*` features compiled out of this build.
+pub(crate) fn warn_if_compiled_out(target: Option<&str>, level: LevelFilter) {
+    if level <= STATIC_MAX_LEVEL {
+        return;
+    }
+    match target {
+        Some(target) => tracing::warn!(
+            "Level {level} for {target} is more verbose than {STATIC_MAX_LEVEL}, the most verbose level compiled into this build"
+        ),
+        None => tracing::warn!(
+            "Max level {level} is more verbose than {STATIC_MAX_LEVEL}, the most verbose level compiled into this build"
+        ),
+    }
+}
+
+/// An output of the default subscriber, which filters events on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Output {
+    Stdout,
+    Stderr,
+    Webview,
+    File,
+    OsLog,
+    Pipe,
+    #[cfg(feature = "gelf")]
+    Gelf,
+    #[cfg(feature = "loki")]
+    Loki,
+    #[cfg(feature = "otlp")]
+    Otlp,
+}
+
+impl Output {
+    /// Returns the output a target writes to. Both file targets write to the
+    /// same output, as only the first is used.
+    pub(crate) fn of(target: &Target) -> Self {
+        match target {
+            Target::Stdout => Self::Stdout,
+            Target::Stderr => Self::Stderr,
+            Target::Webview => Self::Webview,
+            Target::LogDir { .. } | Target::Folder { .. } => Self::File,
+            Target::OsLog { .. } => Self::OsLog,
+            Target::Pipe { .. } => Self::Pipe,
+            #[cfg(feature = "gelf")]
+            Target::Gelf { .. } => Self::Gelf,
+            #[cfg(feature = "loki")]
+            Target::Loki { .. } => Self::Loki,
+            #[cfg(feature = "otlp")]
+            Target::Otlp { .. } => Self::Otlp,
+        }
+    }
+}
+
+/// The filter of an output of the default subscriber: either the filter in
+/// effect, which changes at runtime, or one set with
+/// [`Builder::filter_for()`](crate::Builder::filter_for).
+#[derive(Debug, Clone)]
+pub(crate) enum LayerFilter {
+    Shared(Arc<RwLock<Targets>>),
+    Own(Targets),
+}
+
+impl LayerFilter {
+    fn with<T>(&self, f: impl FnOnce(&Targets) -> T) -> T {
+        match self {
+            Self::Shared(targets) => f(&targets.read().unwrap_or_else(|e| e.into_inner())),
+            Self::Own(targets) => f(targets),
+        }
+    }
+}
+
+// Interest in callsites is rebuilt when the global filter is reloaded, which
+// happens after the shared filter changes
+impl<S> Filter<S> for LayerFilter {
+    fn enabled(&self, metadata: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        self.with(|targets| Filter::<S>::enabled(targets, metadata, cx))
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.with(|targets| Filter::<S>::callsite_enabled(targets, metadata))
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.with(|targets| Filter::<S>::max_level_hint(targets))
+    }
+}
+
+/// Filters spans with their own filter, set through
+/// [`Builder::with_span_level()`](crate::Builder::with_span_level), and events
+/// with the wrapped one.
+///
+/// Used both for an output's filter and, as a layer, for the global filter,
+/// so layers like the flamegraph see spans that no output records events for.
+#[derive(Debug, Clone)]
+pub(crate) struct SpanFilter<F> {
+    spans: Option<Targets>,
+    inner: F,
+}
+
+impl<F> SpanFilter<F> {
+    pub(crate) fn new(spans: Option<Targets>, inner: F) -> Self {
+        Self { spans, inner }
+    }
+
+    /// Returns the span filter if `metadata` is a span's.
+    fn spans_for(&self, metadata: &Metadata<'_>) -> Option<&Targets> {
+        self.spans.as_ref().filter(|_| metadata.is_span())
+    }
+}
+
+/// Returns the most verbose of two hints, where `None` is no hint.
+pub(crate) fn max_hint(a: Option<LevelFilter>, b: Option<LevelFilter>) -> Option<LevelFilter> {
+    Some(a?.max(b?))
+}
+
+impl<S, F: Filter<S>> Filter<S> for SpanFilter<F> {
+    fn enabled(&self, metadata: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        match self.spans_for(metadata) {
+            Some(spans) => Filter::<S>::enabled(spans, metadata, cx),
+            None => self.inner.enabled(metadata, cx),
+        }
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        match self.spans_for(metadata) {
+            Some(spans) => Filter::<S>::callsite_enabled(spans, metadata),
+            None => self.inner.callsite_enabled(metadata),
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        match &self.spans {
+            Some(spans) => max_hint(
+                Filter::<S>::max_level_hint(spans),
+                self.inner.max_level_hint(),
+            ),
+            None => self.inner.max_level_hint(),
+        }
+    }
+}
+
+impl<S: Subscriber, L: Layer<S>> Layer<S> for SpanFilter<L> {
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        match self.spans_for(metadata) {
+            Some(spans) => Layer::<S>::register_callsite(spans, metadata),
+            None => self.inner.register_callsite(metadata),
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        match self.spans_for(metadata) {
+            Some(spans) => Layer::<S>::enabled(spans, metadata, ctx),
+            None => self.inner.enabled(metadata, ctx),
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        match &self.spans {
+            Some(spans) => max_hint(
+                Layer::<S>::max_level_hint(spans),
+                self.inner.max_level_hint(),
+            ),
+            None => self.inner.max_level_hint(),
+        }
+    }
+}
+
+/// Returns a filter that enables what any of `filters` enables.
+pub(crate) fn union(filters: &[&Targets]) -> Targets {
+    // The most verbose level a filter enables for a target
+    fn level_for(filter: &Targets, target: &str) -> LevelFilter {
+        [
+            Level::TRACE,
+            Level::DEBUG,
+            Level::INFO,
+            Level::WARN,
+            Level::ERROR,
+        ]
+        .into_iter()
+        .find(|level| filter.would_enable(target, level))
+        .map_or(LevelFilter::OFF, LevelFilter::from_level)
+    }
+
+    // Every target is matched by its longest directive across the filters,
+    // whose level in each filter is the target's
+    let names: BTreeSet<&str> = filters
+        .iter()
+        .flat_map(|filter| filter.iter().map(|(target, _)| target))
+        .collect();
+    let default = filters
+        .iter()
+        .filter_map(|filter| filter.default_level())
+        .max()
+        .unwrap_or(LevelFilter::OFF);
+    Targets::new()
+        .with_targets(names.into_iter().map(|name| {
+            let level = filters
+                .iter()
+                .map(|filter| level_for(filter, name))
+                .max()
+                .unwrap_or(LevelFilter::OFF);
+            (name.to_string(), level)
+        }))
+        .with_default(default)
+}
+
+/// Replaces the filter of the running subscriber.
+pub(crate) type ReloadFn = Box<dyn Fn(Targets) -> crate::Result<()> + Send + Sync>;
+
+/// Changes the default subscriber's filter at runtime.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_default_subscriber()`](crate::Builder::with_default_subscriber)
+/// is used. Changes apply on top of the filter configured on the
+/// [`Builder`](crate::Builder), and [`reset()`](Self::reset) restores it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri::Manager;
+/// # use tauri_plugin_tracing::{FilterHandle, LevelFilter};
+/// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+/// let filter = app.state::<FilterHandle>();
+/// filter.set_target("my_app::sync", LevelFilter::TRACE)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FilterHandle {
+    base: RwLock<Targets>,
+    overrides: Mutex<FilterOverrides>,
+    reload: ReloadFn,
+    store_path: Option<PathBuf>,
+}
+
+impl FilterHandle {
+    pub(crate) fn new(
+        base: Targets,
+        overrides: FilterOverrides,
+        reload: ReloadFn,
+        store_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            base: RwLock::new(base),
+            overrides: Mutex::new(overrides),
+            reload,
+            store_path,
+        }
+    }
+
+    /// Returns the filter currently in effect.
+    pub fn current(&self) -> Targets {
+        let overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        overrides.apply_to(&self.base())
+    }
+
+    /// Returns the filter currently in effect in a serializable form.
+    pub fn effective(&self) -> EffectiveFilter {
+        EffectiveFilter::from(&self.current())
+    }
+
+    /// Returns the file changes are persisted to, if persistence is enabled.
+    pub fn store_path(&self) -> Option<&PathBuf> {
+        self.store_path.as_ref()
+    }
+
+    /// Sets the level for targets without a more specific level.
+    pub fn set_level(&self, level: LevelFilter) -> crate::Result<()> {
+        warn_if_compiled_out(None, level);
+        self.update(|overrides| overrides.level = Some(level.to_string()))
+    }
+
+    /// Removes a level set with [`set_level()`](Self::set_level), restoring
+    /// the configured default level.
+    pub fn clear_level(&self) -> crate::Result<()> {
+        self.update(|overrides| overrides.level = None)
+    }
+
+    /// Sets the level for a target and its submodules.
+    pub fn set_target(&self, target: impl Into<String>, level: LevelFilter) -> crate::Result<()> {
+        let target = target.into();
+        warn_if_compiled_out(Some(&target), level);
+        self.update(|overrides| {
+            overrides.targets.insert(target, level.to_string());
+        })
+    }
+
+    /// Removes a level set with [`set_target()`](Self::set_target).
+    pub fn clear_target(&self, target: &str) -> crate::Result<()> {
+        self.update(|overrides| {
+            overrides.targets.remove(target);
+        })
+    }
+
+    /// Discards all runtime changes, restoring the configured filter.
+    pub fn reset(&self) -> crate::Result<()> {
+        self.update(|overrides| *overrides = FilterOverrides::default())
+    }
+
+    /// Replaces the runtime changes with those in the persisted filter file,
+    /// e.g. after it was edited by hand.
+    ///
+    /// Does nothing if persistence is disabled. A missing or unreadable file
+    /// restores the configured filter.
+    pub fn reload_stored(&self) -> crate::Result<()> {
+        if self.store_path.is_none() {
+            return Ok(());
+        }
+        let stored = FilterOverrides::load(self.store_path.as_ref());
+        let mut overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        (self.reload)(stored.apply_to(&self.base()))?;
+        *overrides = stored;
+        Ok(())
+    }
+
+    /// Replaces the configured filter and discards runtime changes, so that
+    /// `base` is in effect.
+    pub(crate) fn replace_base(&self, base: Targets) -> crate::Result<()> {
+        let mut overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        (self.reload)(base.clone())?;
+        *self.base.write().unwrap_or_else(|e| e.into_inner()) = base;
+        *overrides = FilterOverrides::default();
+        self.persist(&overrides)
+    }
+
+    fn base(&self) -> Targets {
+        self.base.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn update(&self, change: impl FnOnce(&mut FilterOverrides)) -> crate::Result<()> {
+        let mut overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        change(&mut overrides);
+        (self.reload)(overrides.apply_to(&self.base()))?;
+        self.persist(&overrides)
+    }
+
+    fn persist(&self, overrides: &FilterOverrides) -> crate::Result<()> {
+        let Some(path) = &self.store_path else {
+            return Ok(());
+        };
+        if overrides.is_empty() {
+            return match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_vec_pretty(overrides).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for FilterHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilterHandle")
+            .field("current", &self.current())
+            .field("store_path", &self.store_path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_enables_what_any_filter_enables() {
+        let stdout = Targets::new()
+            .with_default(LevelFilter::INFO)
+            .with_target("hyper", LevelFilter::WARN);
+        let file = Targets::new()
+            .with_default(LevelFilter::DEBUG)
+            .with_target("my_app::db", LevelFilter::TRACE);
+
+        let global = union(&[&stdout, &file]);
+        assert_eq!(global.default_level(), Some(LevelFilter::DEBUG));
+        assert!(global.would_enable("hyper::client", &Level::DEBUG));
+        assert!(!global.would_enable("hyper::client", &Level::TRACE));
+        assert!(global.would_enable("my_app::db", &Level::TRACE));
+        assert!(!global.would_enable("my_app::ui", &Level::TRACE));
+    }
+
+    #[test]
+    fn span_filter_filters_spans_apart_from_events() {
+        use tracing_subscriber::{Registry, layer::SubscriberExt};
+
+        let events = Targets::new().with_default(LevelFilter::INFO);
+        let spans = Targets::new()
+            .with_default(LevelFilter::TRACE)
+            .with_target("hyper", LevelFilter::WARN);
+        let subscriber = Registry::default().with(SpanFilter::new(Some(spans), events));
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(tracing::span_enabled!(Level::TRACE));
+            assert!(!tracing::span_enabled!(target: "hyper::client", Level::INFO));
+            assert!(tracing::event_enabled!(Level::INFO));
+            assert!(!tracing::event_enabled!(Level::DEBUG));
+        });
+    }
+}
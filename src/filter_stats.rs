@@ -0,0 +1,195 @@
+//! Counts of the events the filter drops.
+//!
+//! Filters are usually tuned by guessing which crates are noisy. With
+//! [`Builder::with_filter_stats()`](crate::Builder::with_filter_stats), the
+//! plugin counts the events that no output records, by target, so
+//! [`get_filter_stats()`], or `getFilterStats()` in JavaScript, can show that
+//! e.g. two million `wry` events were dropped.
+//!
+//! Only the level filter of the default subscriber is counted, including
+//! runtime changes through the [`FilterHandle`](crate::FilterHandle). Events
+//! compiled out with the `max-level-*` features are never seen.
+//!
+//! Counting has a cost: the filter can no longer tell `tracing` ahead of time
+//! that a callsite is disabled, so each filtered event is checked and counted
+//! when it happens. This is meant for tuning filters during development or
+//! in a debug build, rather than for every release.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::subscriber::Interest;
+use tracing::{Metadata, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer};
+
+/// The events dropped for a target.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct TargetDrops {
+    /// The event target, usually a module path.
+    pub target: String,
+    /// The number of dropped events.
+    pub events: u64,
+}
+
+/// The result of [`get_filter_stats()`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct FilterStats {
+    /// How long events were counted, in milliseconds.
+    pub window_ms: f64,
+    /// The number of dropped events.
+    pub total_events: u64,
+    /// The dropped events of each target, most dropped first.
+    pub targets: Vec<TargetDrops>,
+}
+
+/// Counts the events dropped by the filter, by target.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_filter_stats()`](crate::Builder::with_filter_stats) is
+/// used with the default subscriber.
+#[derive(Clone)]
+pub struct DroppedEvents {
+    inner: Arc<Counts>,
+}
+
+struct Counts {
+    since: RwLock<Instant>,
+    targets: RwLock<HashMap<String, AtomicU64>>,
+}
+
+impl Default for DroppedEvents {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Counts {
+                since: RwLock::new(Instant::now()),
+                targets: RwLock::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+impl DroppedEvents {
+    /// Returns the counts since the plugin started or since the last reset.
+    pub fn stats(&self) -> FilterStats {
+        let since = *self.inner.since.read().unwrap_or_else(|e| e.into_inner());
+        let targets = self.inner.targets.read().unwrap_or_else(|e| e.into_inner());
+        let mut drops: Vec<TargetDrops> = targets
+            .iter()
+            .map(|(target, events)| TargetDrops {
+                target: target.clone(),
+                events: events.load(Ordering::Relaxed),
+            })
+            .collect();
+        drops.sort_by(|a, b| b.events.cmp(&a.events).then(a.target.cmp(&b.target)));
+        FilterStats {
+            window_ms: since.elapsed().as_secs_f64() * 1000.0,
+            total_events: drops.iter().map(|drops| drops.events).sum(),
+            targets: drops,
+        }
+    }
+
+    /// Clears the counts, e.g. after changing the filter.
+    pub fn reset(&self) {
+        let mut targets = self
+            .inner
+            .targets
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        targets.clear();
+        *self.inner.since.write().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    fn count(&self, target: &str) {
+        let targets = self.inner.targets.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(events) = targets.get(target) {
+            events.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        drop(targets);
+        self.inner
+            .targets
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(target.to_string())
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Returns the events dropped by the filter, by target.
+///
+/// Requires [`Builder::with_filter_stats()`](crate::Builder::with_filter_stats)
+/// and the default subscriber.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+/// let stats = tauri_plugin_tracing::get_filter_stats(app)?;
+/// for drops in stats.targets.iter().take(5) {
+///     println!("{}: {} events dropped", drops.target, drops.events);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn get_filter_stats<R: Runtime>(app: &AppHandle<R>) -> crate::Result<FilterStats> {
+    app.try_state::<DroppedEvents>()
+        .map(|dropped| dropped.stats())
+        .ok_or(crate::Error::FilterStatsDisabled)
+}
+
+/// Counts the events a filter layer disables, when counting is enabled.
+pub(crate) struct CountingFilter<L> {
+    dropped: Option<DroppedEvents>,
+    inner: L,
+}
+
+impl<L> CountingFilter<L> {
+    pub(crate) fn new(dropped: Option<DroppedEvents>, inner: L) -> Self {
+        Self { dropped, inner }
+    }
+}
+
+impl<S: Subscriber, L: Layer<S>> Layer<S> for CountingFilter<L> {
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        let interest = self.inner.register_callsite(metadata);
+        // Disabled events are checked when they happen, to be counted
+        if self.dropped.is_some() && metadata.is_event() && interest.is_never() {
+            Interest::sometimes()
+        } else {
+            interest
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        let enabled = self.inner.enabled(metadata, ctx);
+        if !enabled
+            && metadata.is_event()
+            && let Some(dropped) = &self.dropped
+        {
+            dropped.count(metadata.target());
+        }
+        enabled
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        match self.dropped {
+            // Every level must reach `enabled()` to be counted
+            Some(_) => Some(LevelFilter::TRACE),
+            None => self.inner.max_level_hint(),
+        }
+    }
+}
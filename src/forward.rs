@@ -0,0 +1,121 @@
+//! Forwarding of file log lines between instances of the same app.
+//!
+//! With [`InstanceStrategy::Forward`](crate::InstanceStrategy::Forward), the
+//! instance that holds the log file's lock is the primary. It listens on a
+//! loopback TCP port and writes every line it receives into its own log file,
+//! so one coherent file exists per machine. The port and a random token are
+//! stored in `<file_name>.port` next to the log file; secondary instances
+//! read it, connect, and send the token before their formatted lines.
+//!
+//! Secondary instances send from a background thread, so logging never
+//! blocks on the socket. Lines are written whole, but multi-line events (e.g.
+//! [`LogFormat::Pretty`](crate::LogFormat::Pretty)) from different instances
+//! may interleave. If the primary exits, lines from the remaining instances
+//! are dropped.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a secondary instance waits for the primary to accept.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The longest line the primary accepts; longer lines are truncated.
+const MAX_LINE_LEN: u64 = 1024 * 1024;
+
+/// Returns the file the primary's port and token are stored in.
+fn port_file(log_dir: &Path, file_name: &str) -> PathBuf {
+    log_dir.join(format!("{file_name}.port"))
+}
+
+/// Returns a token that differs between runs.
+fn random_token() -> String {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    format!("{:016x}", hasher.finish())
+}
+
+/// Accepts lines from secondary instances and writes them to `writer`.
+///
+/// Called by the primary instance once it has the log file's lock.
+pub(crate) fn listen<W>(log_dir: &Path, file_name: &str, writer: W) -> std::io::Result<()>
+where
+    W: Write + Clone + Send + 'static,
+{
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let token = random_token();
+
+    // Write to a temporary file first so secondaries never read a partial one
+    let path = port_file(log_dir, file_name);
+    let temp = path.with_extension("port.tmp");
+    std::fs::write(&temp, format!("{} {token}", listener.local_addr()?.port()))?;
+    std::fs::rename(&temp, &path)?;
+
+    std::thread::Builder::new()
+        .name("tracing-forward".to_string())
+        .spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let token = token.clone();
+                let writer = writer.clone();
+                let _ = std::thread::Builder::new()
+                    .name("tracing-forward-conn".to_string())
+                    .spawn(move || receive(stream, &token, writer));
+            }
+        })?;
+    Ok(())
+}
+
+/// Copies lines from one secondary instance, after checking its token.
+fn receive<W: Write>(stream: TcpStream, token: &str, mut writer: W) {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+
+    let authenticated = (&mut reader)
+        .take(token.len() as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .is_ok_and(|_| line.strip_suffix(b"\n") == Some(token.as_bytes()));
+    if !authenticated {
+        return;
+    }
+
+    loop {
+        line.clear();
+        match (&mut reader)
+            .take(MAX_LINE_LEN)
+            .read_until(b'\n', &mut line)
+        {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {
+                if !line.ends_with(b"\n") {
+                    line.push(b'\n');
+                }
+                // One write per line, so lines from different instances never mix
+                if writer.write_all(&line).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Connects to the primary instance, returning `None` if there is none.
+///
+/// Called by secondary instances that could not take the log file's lock.
+pub(crate) fn connect(log_dir: &Path, file_name: &str) -> Option<TcpStream> {
+    let contents = std::fs::read_to_string(port_file(log_dir, file_name)).ok()?;
+    let (port, token) = contents.trim().split_once(' ')?;
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port.parse().ok()?));
+
+    let mut stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).ok()?;
+    stream.write_all(format!("{token}\n").as_bytes()).ok()?;
+    Some(stream)
+}
@@ -0,0 +1,138 @@
+//! Webview frame timing for jank diagnosis.
+//!
+//! The JavaScript `startFrameMonitor()` helper samples frame durations with
+//! `requestAnimationFrame` and periodically sends them to the
+//! `report_frame_times` command. Each long frame is logged as a `WARN` event
+//! and the batch is summarized as a `DEBUG` event with percentile statistics,
+//! all under the [`FRAMES_TARGET`] target, so UI jank appears in the same
+//! timeline as backend spans.
+//!
+//! ```javascript
+//! import { startFrameMonitor } from '@fltsci/tauri-plugin-tracing';
+//!
+//! // Report frames longer than 50ms every 5 seconds
+//! const stop = startFrameMonitor({ thresholdMs: 50, reportIntervalMs: 5000 });
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// The target used for frame timing events.
+pub const FRAMES_TARGET: &str = "webview::frames";
+
+/// A single frame that exceeded the long-frame threshold.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct LongFrame {
+    /// When the frame started, in milliseconds since the Unix epoch.
+    pub timestamp_ms: f64,
+    /// How long the frame took, in milliseconds.
+    pub duration_ms: f64,
+}
+
+/// A batch of frame timings sent by the webview.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct FrameTimingReport {
+    /// Duration of every frame in the batch, in milliseconds.
+    pub frame_times_ms: Vec<f64>,
+    /// Frames that exceeded the sampler's threshold.
+    pub long_frames: Vec<LongFrame>,
+}
+
+/// Aggregate statistics over a batch of frame durations.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct FrameStats {
+    /// Number of frames in the batch.
+    pub count: usize,
+    /// Mean frame duration in milliseconds.
+    pub mean_ms: f64,
+    /// Median frame duration in milliseconds.
+    pub p50_ms: f64,
+    /// 95th percentile frame duration in milliseconds.
+    pub p95_ms: f64,
+    /// 99th percentile frame duration in milliseconds.
+    pub p99_ms: f64,
+    /// Longest frame duration in milliseconds.
+    pub max_ms: f64,
+}
+
+impl FrameStats {
+    /// Computes statistics over frame durations in milliseconds.
+    ///
+    /// Percentiles use the nearest-rank method. Non-finite values are ignored.
+    /// Returns `None` if there are no valid durations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tauri_plugin_tracing::FrameStats;
+    ///
+    /// let times: Vec<f64> = (1..=100).map(f64::from).collect();
+    /// let stats = FrameStats::from_frame_times(&times).unwrap();
+    /// assert_eq!(stats.count, 100);
+    /// assert_eq!(stats.p50_ms, 50.0);
+    /// assert_eq!(stats.p95_ms, 95.0);
+    /// assert_eq!(stats.p99_ms, 99.0);
+    /// assert_eq!(stats.max_ms, 100.0);
+    /// ```
+    pub fn from_frame_times(frame_times_ms: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = frame_times_ms
+            .iter()
+            .copied()
+            .filter(|t| t.is_finite())
+            .collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        let count = sorted.len();
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * count as f64).ceil() as usize;
+            sorted[rank.clamp(1, count) - 1]
+        };
+
+        Some(Self {
+            count,
+            mean_ms: sorted.iter().sum::<f64>() / count as f64,
+            p50_ms: percentile(50.0),
+            p95_ms: percentile(95.0),
+            p99_ms: percentile(99.0),
+            max_ms: sorted[count - 1],
+        })
+    }
+}
+
+/// Logs a frame timing report from the webview with the given label.
+///
+/// Returns the aggregate statistics for the batch.
+pub(crate) fn record_frame_report(window: &str, report: &FrameTimingReport) -> Option<FrameStats> {
+    for frame in &report.long_frames {
+        tracing::warn!(
+            target: FRAMES_TARGET,
+            w = window,
+            timestamp_ms = frame.timestamp_ms,
+            duration_ms = frame.duration_ms,
+            "long frame: {:.1}ms",
+            frame.duration_ms
+        );
+    }
+
+    let stats = FrameStats::from_frame_times(&report.frame_times_ms)?;
+    tracing::debug!(
+        target: FRAMES_TARGET,
+        w = window,
+        frames = stats.count,
+        long_frames = report.long_frames.len(),
+        mean_ms = stats.mean_ms,
+        p95_ms = stats.p95_ms,
+        p99_ms = stats.p99_ms,
+        max_ms = stats.max_ms,
+        "frame timing"
+    );
+    Some(stats)
+}
@@ -0,0 +1,170 @@
+//! Syncing of log files to disk.
+//!
+//! Neither file appender exposes its file, so [`SyncingWriter`] wraps the
+//! appender and syncs the file it currently writes to by opening it again:
+//! syncing any handle to a file flushes all of its written data. It runs on
+//! the non-blocking writer's worker thread, so syncing never blocks the code
+//! that logs.
+//!
+//! Failures are logged as warnings at most once a minute, as the warning is
+//! written to the same file and may fail to sync in turn.
+
+use std::cell::Cell;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::SyncPolicy;
+
+/// The minimum time between two warnings about failed syncs.
+const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// When a failed sync was last reported.
+static LAST_REPORT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Failed syncs since the last report.
+static UNREPORTED: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Whether this thread is reporting a failed sync, so a failure while
+    /// writing the warning is not reported in turn.
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns the path of the file an appender currently writes to.
+pub(crate) type CurrentPath = Arc<dyn Fn() -> PathBuf + Send + Sync>;
+
+/// Syncs `path` to disk.
+fn sync(path: &CurrentPath) {
+    let path = path();
+    // Windows only flushes handles with write access
+    let result = std::fs::OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.sync_data());
+    if let Err(e) = result {
+        report(&path, &e);
+    }
+}
+
+/// Logs a failed sync, unless one was reported recently.
+fn report(path: &std::path::Path, error: &io::Error) {
+    let failures = UNREPORTED.fetch_add(1, Ordering::Relaxed) + 1;
+    if REPORTING.with(Cell::get) {
+        return;
+    }
+    {
+        let mut last = LAST_REPORT.lock().unwrap_or_else(|e| e.into_inner());
+        if last.is_some_and(|at| at.elapsed() < REPORT_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+    }
+    UNREPORTED.fetch_sub(failures, Ordering::Relaxed);
+
+    REPORTING.with(|reporting| reporting.set(true));
+    tracing::warn!(
+        path = %path.display(),
+        failures,
+        "Failed to sync log file: {error}"
+    );
+    REPORTING.with(|reporting| reporting.set(false));
+}
+
+/// Wraps a file appender to sync its file according to a [`SyncPolicy`].
+pub(crate) struct SyncingWriter<W: Write> {
+    inner: W,
+    path: CurrentPath,
+    policy: SyncPolicy,
+    /// Records written since the last sync, for [`SyncPolicy::EveryN`].
+    pending: u32,
+    /// Whether records were flushed since the last sync, for
+    /// [`SyncPolicy::Interval`].
+    dirty: Arc<AtomicBool>,
+    /// Disconnects the interval thread when the writer is dropped.
+    _stop: Option<Sender<()>>,
+}
+
+impl<W: Write> SyncingWriter<W> {
+    pub(crate) fn new(inner: W, path: CurrentPath, policy: SyncPolicy) -> io::Result<Self> {
+        let dirty = Arc::new(AtomicBool::new(false));
+        let stop = match policy {
+            SyncPolicy::Interval(interval) => {
+                let interval = interval.max(Duration::from_millis(1));
+                let (stop, stopped) = std::sync::mpsc::channel::<()>();
+                let (path, dirty) = (path.clone(), dirty.clone());
+                std::thread::Builder::new()
+                    .name("tracing-fsync".to_string())
+                    .spawn(move || {
+                        loop {
+                            let result = stopped.recv_timeout(interval);
+                            if dirty.swap(false, Ordering::AcqRel) {
+                                sync(&path);
+                            }
+                            if let Err(RecvTimeoutError::Disconnected) = result {
+                                return;
+                            }
+                        }
+                    })?;
+                Some(stop)
+            }
+            SyncPolicy::Never | SyncPolicy::EveryN(_) => None,
+        };
+        Ok(Self {
+            inner,
+            path,
+            policy,
+            pending: 0,
+            dirty,
+            _stop: stop,
+        })
+    }
+}
+
+impl<W: Write> Write for SyncingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        match self.policy {
+            SyncPolicy::Never => {}
+            SyncPolicy::EveryN(n) => {
+                self.pending += 1;
+                if self.pending >= n {
+                    self.inner.flush()?;
+                    sync(&self.path);
+                    self.pending = 0;
+                }
+            }
+            SyncPolicy::Interval(_) => {}
+        }
+        Ok(written)
+    }
+
+    // The non-blocking writer flushes after each burst of records
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        if let SyncPolicy::Interval(_) = self.policy {
+            self.dirty.store(true, Ordering::Release);
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for SyncingWriter<W> {
+    fn drop(&mut self) {
+        match self.policy {
+            SyncPolicy::Never => {}
+            SyncPolicy::EveryN(_) if self.pending > 0 => {
+                let _ = self.inner.flush();
+                sync(&self.path);
+            }
+            SyncPolicy::EveryN(_) => {}
+            // The interval thread syncs once more when it is disconnected
+            SyncPolicy::Interval(_) => {
+                let _ = self.flush();
+            }
+        }
+    }
+}
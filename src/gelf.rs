@@ -0,0 +1,437 @@
+//! GELF output for Graylog.
+//!
+//! Requires the `gelf` feature. [`GelfLayer`] encodes each event as a
+//! [GELF 1.1](https://go2docs.graylog.org/current/getting_in_log_data/gelf.html)
+//! message and sends it to a Graylog input over UDP, TCP, or TLS:
+//!
+//! - The tracing level is mapped to a syslog severity (see
+//!   [`syslog_severity()`](crate::syslog_severity)).
+//! - The event's fields become additional `_`-prefixed fields, keeping numbers
+//!   numeric so they can be aggregated in Graylog.
+//! - The target, source location, and span names are added as `_target`,
+//!   `_file`, `_line`, and `_spans`.
+//!
+//! Messages are sent from a background thread, so logging never blocks on the
+//! network. If the collector cannot keep up, new messages are dropped.
+
+use std::io::Write;
+use std::net::{TcpStream, UdpSocket};
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, SyncSender};
+
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+
+use serde_json::{Map, Value};
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::rfc5424::system_hostname;
+use crate::syslog_severity;
+
+/// The default maximum size of a UDP datagram, including the chunk header.
+const DEFAULT_CHUNK_SIZE: usize = 8192;
+
+/// Size of the header that prefixes each chunk of a chunked UDP message.
+const CHUNK_HEADER_LEN: usize = 12;
+
+/// The most chunks a single message may be split into.
+const MAX_CHUNKS: usize = 128;
+
+/// Number of messages queued for sending before new ones are dropped.
+const QUEUE_CAPACITY: usize = 1024;
+
+/// How GELF messages are sent to the collector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GelfTransport {
+    /// UDP datagrams. Messages larger than one datagram are chunked.
+    #[default]
+    Udp,
+    /// A TCP stream of null-byte delimited messages.
+    ///
+    /// The connection is re-established on the next message if it drops.
+    Tcp,
+    /// A TCP stream of null-byte delimited messages, encrypted with TLS.
+    ///
+    /// The collector's certificate must be valid for its host name and chain
+    /// to one of the Mozilla root certificates from `webpki-roots`. The
+    /// connection is re-established on the next message if it drops.
+    Tls,
+}
+
+/// A tracing layer that sends events to a Graylog GELF input.
+///
+/// # Example
+///
+/// Use [`Target::Gelf`](crate::Target::Gelf) with the default subscriber, or
+/// add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{GelfLayer, GelfTransport};
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// # fn main() -> tauri_plugin_tracing::Result<()> {
+/// Registry::default()
+///     .with(GelfLayer::new("graylog.example.com", 12201, GelfTransport::Udp)?)
+///     .init();
+/// # Ok(())
+/// # }
+/// ```
+pub struct GelfLayer {
+    sender: SyncSender<Vec<u8>>,
+    host: String,
+    attributes: Map<String, Value>,
+}
+
+impl GelfLayer {
+    /// Creates a layer that sends messages to `host:port`.
+    ///
+    /// The address is resolved on the sending thread, so an unreachable
+    /// collector does not prevent the app from starting.
+    pub fn new(
+        host: impl Into<String>,
+        port: u16,
+        transport: GelfTransport,
+    ) -> crate::Result<Self> {
+        Self::with_chunk_size(host, port, transport, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a layer that splits UDP messages into datagrams of at most
+    /// `chunk_size` bytes.
+    ///
+    /// The default of 8192 bytes suits local networks; use a smaller size such
+    /// as 1420 when sending across the internet.
+    pub fn with_chunk_size(
+        host: impl Into<String>,
+        port: u16,
+        transport: GelfTransport,
+        chunk_size: usize,
+    ) -> crate::Result<Self> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(QUEUE_CAPACITY);
+        let address = (host.into(), port);
+        let chunk_size = chunk_size.max(CHUNK_HEADER_LEN + 1);
+        std::thread::Builder::new()
+            .name("tracing-gelf".to_string())
+            .spawn(move || match transport {
+                GelfTransport::Udp => send_udp(receiver, address, chunk_size),
+                GelfTransport::Tcp => send_tcp(receiver, address),
+                GelfTransport::Tls => send_tls(receiver, address),
+            })?;
+
+        Ok(Self {
+            sender,
+            host: system_hostname().unwrap_or_else(|| "unknown".to_string()),
+            attributes: Map::new(),
+        })
+    }
+
+    /// Overrides the `host` reported in each message.
+    ///
+    /// Defaults to the system hostname.
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    /// Adds `attributes` to every message as additional fields, e.g.
+    /// `_service.name`.
+    ///
+    /// Event fields with the same name take precedence.
+    pub fn with_attributes<K, V>(mut self, attributes: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let mut visitor = GelfVisitor::default();
+        for (key, value) in attributes {
+            visitor.insert_named(key.as_ref(), Value::String(value.into()));
+        }
+        self.attributes.extend(visitor.fields);
+        self
+    }
+}
+
+impl<S> Layer<S> for GelfLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = GelfVisitor::default();
+        event.record(&mut visitor);
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        let short_message = visitor
+            .message
+            .take()
+            .filter(|m| !m.is_empty())
+            .unwrap_or_else(|| metadata.target().to_string());
+
+        let mut message = Map::new();
+        message.insert("version".into(), "1.1".into());
+        message.insert("host".into(), self.host.clone().into());
+        message.insert("short_message".into(), short_message.into());
+        message.insert("timestamp".into(), timestamp.into());
+        message.insert("level".into(), syslog_severity(metadata.level()).into());
+        message.insert("_target".into(), metadata.target().into());
+        if let Some(file) = metadata.file() {
+            message.insert("_file".into(), file.into());
+        }
+        if let Some(line) = metadata.line() {
+            message.insert("_line".into(), line.into());
+        }
+        if let Some(scope) = ctx.event_scope(event) {
+            let spans: Vec<&str> = scope.from_root().map(|span| span.name()).collect();
+            message.insert("_spans".into(), spans.join(":").into());
+        }
+        if let Some(rate) = crate::current_sample_rate() {
+            message.insert("_sampled".into(), rate.to_string().into());
+        }
+        if let Some(seq) = crate::current_sequence() {
+            message.insert("_seq".into(), seq.into());
+        }
+        message.extend(self.attributes.clone());
+        let mut context = GelfVisitor::default();
+        crate::context::with_current_context(|entries| {
+            for (key, value) in entries {
+                context.insert_named(key, Value::String(value.clone()));
+            }
+        });
+        message.extend(context.fields);
+        message.extend(visitor.fields);
+
+        if let Ok(bytes) = serde_json::to_vec(&Value::Object(message)) {
+            // Drop the message if the queue is full rather than block the caller
+            let _ = self.sender.try_send(bytes);
+        }
+    }
+}
+
+/// Collects the message and additional fields of an event.
+#[derive(Default)]
+struct GelfVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl GelfVisitor {
+    fn insert(&mut self, field: &tracing::field::Field, value: Value) {
+        self.insert_named(field.name(), value);
+    }
+
+    fn insert_named(&mut self, name: &str, value: Value) {
+        if name == "message" {
+            self.message = Some(match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            });
+            return;
+        }
+        let name: String = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+            .collect();
+        if name.is_empty() {
+            return;
+        }
+        // `_id` is reserved by Graylog
+        let key = if name == "id" {
+            "_id_".to_string()
+        } else {
+            format!("_{name}")
+        };
+        self.fields.insert(key, value);
+    }
+}
+
+impl tracing::field::Visit for GelfVisitor {
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        // Non-finite numbers are not valid JSON
+        match serde_json::Number::from_f64(value) {
+            Some(number) => self.insert(field, Value::Number(number)),
+            None => self.insert(field, Value::String(value.to_string())),
+        }
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        // GELF field values must be strings or numbers
+        self.insert(field, value.to_string().into());
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.insert(field, value.to_string().into());
+        for (n, source) in crate::error_chain::sources(value).enumerate() {
+            let name = crate::error_chain::source_field(field.name(), n + 1);
+            self.insert_named(&name, source.into());
+        }
+    }
+}
+
+fn send_udp(receiver: Receiver<Vec<u8>>, address: (String, u16), chunk_size: usize) {
+    let mut socket: Option<UdpSocket> = None;
+    let mut next_id = message_id_seed();
+
+    for message in receiver {
+        if socket.is_none() {
+            socket = connect_udp(&address);
+        }
+        let Some(udp) = &socket else {
+            continue;
+        };
+
+        let sent = if message.len() <= chunk_size {
+            udp.send(&message).map(|_| ())
+        } else {
+            next_id = next_id.wrapping_add(1);
+            send_chunked(udp, &message, chunk_size, next_id)
+        };
+        if sent.is_err() {
+            socket = None;
+        }
+    }
+}
+
+fn connect_udp(address: &(String, u16)) -> Option<UdpSocket> {
+    use std::net::ToSocketAddrs;
+
+    let target = address.to_socket_addrs().ok()?.next()?;
+    let bind = if target.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect(target).ok()?;
+    Some(socket)
+}
+
+/// Sends a message as GELF chunks, or drops it if it needs too many.
+fn send_chunked(
+    socket: &UdpSocket,
+    message: &[u8],
+    chunk_size: usize,
+    id: u64,
+) -> std::io::Result<()> {
+    let payload_size = chunk_size - CHUNK_HEADER_LEN;
+    let count = message.len().div_ceil(payload_size);
+    if count > MAX_CHUNKS {
+        return Ok(());
+    }
+
+    let mut datagram = Vec::with_capacity(chunk_size);
+    for (sequence, chunk) in message.chunks(payload_size).enumerate() {
+        datagram.clear();
+        datagram.extend_from_slice(&[0x1e, 0x0f]);
+        datagram.extend_from_slice(&id.to_be_bytes());
+        datagram.push(sequence as u8);
+        datagram.push(count as u8);
+        datagram.extend_from_slice(chunk);
+        socket.send(&datagram)?;
+    }
+    Ok(())
+}
+
+/// Returns a starting point for chunked message ids that differs between runs.
+fn message_id_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.finish()
+}
+
+fn send_tcp(receiver: Receiver<Vec<u8>>, address: (String, u16)) {
+    let mut stream: Option<TcpStream> = None;
+
+    for mut message in receiver {
+        message.push(0);
+        // Retry once on a fresh connection in case the old one went stale
+        for _ in 0..2 {
+            if stream.is_none() {
+                stream = TcpStream::connect((address.0.as_str(), address.1)).ok();
+            }
+            let Some(tcp) = &mut stream else {
+                break;
+            };
+            if tcp.write_all(&message).is_ok() {
+                break;
+            }
+            stream = None;
+        }
+    }
+}
+
+fn send_tls(receiver: Receiver<Vec<u8>>, address: (String, u16)) {
+    let Some(config) = tls_config() else {
+        return;
+    };
+    let mut stream: Option<StreamOwned<ClientConnection, TcpStream>> = None;
+
+    for mut message in receiver {
+        message.push(0);
+        // Retry once on a fresh connection in case the old one went stale
+        for _ in 0..2 {
+            if stream.is_none() {
+                stream = connect_tls(&config, &address);
+            }
+            let Some(tls) = &mut stream else {
+                break;
+            };
+            // Flushing sends the encrypted records, and completes the
+            // handshake of a new connection
+            if tls.write_all(&message).and_then(|()| tls.flush()).is_ok() {
+                break;
+            }
+            stream = None;
+        }
+    }
+}
+
+/// Builds a client configuration that trusts the Mozilla root certificates.
+fn tls_config() -> Option<Arc<ClientConfig>> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    // The provider is passed explicitly, as the process default is ambiguous
+    // when another crate enables a different one
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .ok()?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Some(Arc::new(config))
+}
+
+fn connect_tls(
+    config: &Arc<ClientConfig>,
+    address: &(String, u16),
+) -> Option<StreamOwned<ClientConnection, TcpStream>> {
+    let name = rustls::pki_types::ServerName::try_from(address.0.clone()).ok()?;
+    let connection = ClientConnection::new(config.clone(), name).ok()?;
+    let tcp = TcpStream::connect((address.0.as_str(), address.1)).ok()?;
+    Some(StreamOwned::new(connection, tcp))
+}
@@ -0,0 +1,270 @@
+//! Heartbeats for diagnosing hangs from log files.
+//!
+//! A frozen UI leaves nothing in the logs, so a user's "the app froze" is
+//! hard to place. With [`Builder::with_heartbeat()`](crate::Builder::with_heartbeat),
+//! a background thread wakes up every interval and:
+//!
+//! - Logs a compact `INFO` event under the [`HEARTBEAT_TARGET`] target, so
+//!   the logs show the process was alive until the last heartbeat.
+//! - Logs a `WARN` event if the heartbeat itself is late, when the process
+//!   was suspended or starved of CPU.
+//! - Checks that the main thread, which runs the event loop of every window,
+//!   still runs the tasks posted to it. If it does not, it logs a `WARN`
+//!   event with the last known main thread activity.
+//! - Pings the webviews with the [`PING_EVENT`] event. Windows that answer
+//!   pings with the JavaScript `startHeartbeat()` helper are expected to
+//!   answer every ping, and a `WARN` event is logged with the last known main
+//!   thread activity when one misses a ping.
+//!
+//! Each hang is logged once, and its end is logged at `INFO` with how long
+//! it lasted. The last known main thread activity is the latest window event
+//! the plugin saw, unless the app records its own with
+//! [`Heartbeat::record_activity()`].
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Runtime, WindowEvent};
+
+use crate::plugin_name::channel;
+
+/// The target used for heartbeat and hang events.
+pub const HEARTBEAT_TARGET: &str = "app::heartbeat";
+
+/// The event the webviews are pinged with. Its payload is the sequence
+/// number to answer with.
+pub const PING_EVENT: &str = "tracing://ping";
+
+/// Liveness of the main thread and of the webviews that answer pings.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_heartbeat()`](crate::Builder::with_heartbeat) is used.
+/// Heartbeats stop when it is dropped.
+pub struct Heartbeat {
+    state: Arc<Mutex<State>>,
+    interval: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    /// The latest main thread activity, and when it happened.
+    activity: Option<(Instant, Cow<'static, str>)>,
+    /// When the pending main thread probe was posted, if it has not run yet.
+    probe_sent: Option<Instant>,
+    /// Whether the main thread was reported unresponsive.
+    main_thread_hung: bool,
+    /// The webviews that answer pings.
+    webviews: HashMap<String, WebviewLiveness>,
+}
+
+struct WebviewLiveness {
+    last_seq: u64,
+    answered_at: Instant,
+    /// Whether the webview was reported unresponsive.
+    hung: bool,
+}
+
+impl State {
+    /// Describes the last main thread activity for hang events.
+    fn last_activity(&self, now: Instant) -> String {
+        match &self.activity {
+            Some((at, activity)) => format!(
+                "{activity} ({:.1}s ago)",
+                now.duration_since(*at).as_secs_f64()
+            ),
+            None => "none".to_string(),
+        }
+    }
+}
+
+impl Heartbeat {
+    /// Starts a heartbeat every `interval` on a background thread.
+    pub fn start<R: Runtime>(app: AppHandle<R>, interval: Duration) -> crate::Result<Self> {
+        let interval = interval.max(Duration::from_millis(100));
+        let state = Arc::new(Mutex::new(State::default()));
+        let weak = Arc::downgrade(&state);
+        std::thread::Builder::new()
+            .name("tracing-heartbeat".to_string())
+            .spawn(move || run_heartbeat(app, weak, interval))?;
+        Ok(Self { state, interval })
+    }
+
+    /// Returns the heartbeat interval.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Records what the main thread is doing, to report if it hangs.
+    ///
+    /// Call this before long work on the main thread, such as loading a
+    /// project in a synchronous command, so a hang logged during it names it.
+    pub fn record_activity(&self, activity: impl Into<Cow<'static, str>>) {
+        self.lock().activity = Some((Instant::now(), activity.into()));
+    }
+
+    /// Records that the webview with the given label answered the ping with
+    /// sequence number `seq`.
+    pub fn pong(&self, webview: &str, seq: u64) {
+        let now = Instant::now();
+        let mut state = self.lock();
+        match state.webviews.get_mut(webview) {
+            Some(liveness) => {
+                if liveness.hung {
+                    tracing::info!(
+                        target: HEARTBEAT_TARGET,
+                        w = webview,
+                        hung_s = now.duration_since(liveness.answered_at).as_secs_f64(),
+                        "webview is responsive again"
+                    );
+                }
+                *liveness = WebviewLiveness {
+                    last_seq: liveness.last_seq.max(seq),
+                    answered_at: now,
+                    hung: false,
+                };
+            }
+            None => {
+                state.webviews.insert(
+                    webview.to_string(),
+                    WebviewLiveness {
+                        last_seq: seq,
+                        answered_at: now,
+                        hung: false,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Records window events as main thread activity, and forgets the
+    /// webviews of closed windows.
+    pub(crate) fn window_event(&self, window: &str, event: &WindowEvent) {
+        let kind = match event {
+            WindowEvent::Resized(_) => "resized",
+            WindowEvent::Moved(_) => "moved",
+            WindowEvent::CloseRequested { .. } => "close requested",
+            WindowEvent::Destroyed => "destroyed",
+            WindowEvent::Focused(true) => "focused",
+            WindowEvent::Focused(false) => "lost focus",
+            WindowEvent::ScaleFactorChanged { .. } => "scale factor changed",
+            WindowEvent::DragDrop(_) => "drag and drop",
+            WindowEvent::ThemeChanged(_) => "theme changed",
+            _ => "event",
+        };
+        let mut state = self.lock();
+        if let WindowEvent::Destroyed = event {
+            state.webviews.remove(window);
+        }
+        state.activity = Some((Instant::now(), format!("window {window} {kind}").into()));
+    }
+
+    /// Forgets the webview with the given label while it loads a page, as it
+    /// cannot answer pings until the new page starts answering them.
+    pub(crate) fn page_load_started(&self, webview: &str) {
+        self.lock().webviews.remove(webview);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn run_heartbeat<R: Runtime>(app: AppHandle<R>, state: Weak<Mutex<State>>, interval: Duration) {
+    let started = Instant::now();
+    let mut last_beat = started;
+    let mut seq: u64 = 0;
+
+    loop {
+        std::thread::sleep(interval);
+        let Some(state) = state.upgrade() else {
+            return;
+        };
+        let now = Instant::now();
+        seq += 1;
+
+        tracing::info!(
+            target: HEARTBEAT_TARGET,
+            seq,
+            uptime_s = now.duration_since(started).as_secs(),
+            "heartbeat"
+        );
+        // Sleeping overshoots a little, but not by a whole interval
+        let gap = now.duration_since(last_beat);
+        if gap >= interval * 2 {
+            tracing::warn!(
+                target: HEARTBEAT_TARGET,
+                gap_s = gap.as_secs_f64(),
+                missed = (gap.as_millis() / interval.as_millis().max(1)) as u64 - 1,
+                "heartbeat is late; the process was suspended or starved"
+            );
+        }
+        last_beat = now;
+
+        check_main_thread(&app, &state, now);
+        check_webviews(&state, seq, now);
+        let _ = app.emit(&channel(&app, PING_EVENT), seq);
+    }
+}
+
+/// Reports a main thread that has not run the previous probe, or posts a new
+/// one.
+fn check_main_thread<R: Runtime>(app: &AppHandle<R>, state: &Arc<Mutex<State>>, now: Instant) {
+    let mut guard = state.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(sent) = guard.probe_sent {
+        if !guard.main_thread_hung {
+            guard.main_thread_hung = true;
+            tracing::warn!(
+                target: HEARTBEAT_TARGET,
+                hung_s = now.duration_since(sent).as_secs_f64(),
+                last_activity = %guard.last_activity(now),
+                "main thread is unresponsive"
+            );
+        }
+        return;
+    }
+    guard.probe_sent = Some(now);
+    drop(guard);
+
+    let probe = Arc::downgrade(state);
+    let result = app.run_on_main_thread(move || {
+        let Some(state) = probe.upgrade() else {
+            return;
+        };
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        let sent = state.probe_sent.take();
+        if std::mem::take(&mut state.main_thread_hung)
+            && let Some(sent) = sent
+        {
+            tracing::info!(
+                target: HEARTBEAT_TARGET,
+                hung_s = sent.elapsed().as_secs_f64(),
+                "main thread is responsive again"
+            );
+        }
+    });
+    // The event loop has exited
+    if result.is_err() {
+        state.lock().unwrap_or_else(|e| e.into_inner()).probe_sent = None;
+    }
+}
+
+/// Reports the webviews that did not answer the previous ping.
+fn check_webviews(state: &Mutex<State>, seq: u64, now: Instant) {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    let last_activity = state.last_activity(now);
+    for (label, liveness) in &mut state.webviews {
+        if liveness.hung || liveness.last_seq + 1 >= seq {
+            continue;
+        }
+        liveness.hung = true;
+        tracing::warn!(
+            target: HEARTBEAT_TARGET,
+            w = label.as_str(),
+            last_answer_s = now.duration_since(liveness.answered_at).as_secs_f64(),
+            %last_activity,
+            "webview did not answer ping"
+        );
+    }
+}
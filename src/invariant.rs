@@ -0,0 +1,151 @@
+//! Reporting of violated invariants.
+//!
+//! Conditions that "should never happen" are usually checked with
+//! `debug_assert!()`, which does nothing in the field, or logged as ad-hoc
+//! errors that are hard to count. The [`invariant!`](crate::invariant) macro
+//! checks a condition and, when it does not hold, logs an `ERROR` event under
+//! the [`INVARIANT_TARGET`] target with the invariant's id in the `invariant`
+//! field, the condition's source text, and how often it was violated so far.
+//! JavaScript reports violations with `invariant()`.
+//!
+//! Like other errors, the event carries a breadcrumb trail with
+//! [`Builder::with_breadcrumbs()`](crate::Builder::with_breadcrumbs). The
+//! violations of each invariant are counted for the lifetime of the process,
+//! and returned by [`invariant_violations()`], or `getInvariantViolations()`
+//! in JavaScript, and included in diagnostic reports.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// The target used for invariant violations.
+pub const INVARIANT_TARGET: &str = "app::invariant";
+
+/// How often an invariant was violated.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ViolatedInvariant {
+    /// The invariant's id.
+    pub id: String,
+    /// The number of violations since the app started.
+    pub violations: u64,
+    /// When it was last violated, in milliseconds since the Unix epoch.
+    pub last_violation_ms: f64,
+}
+
+/// The violations of each invariant, by id.
+static VIOLATIONS: Mutex<BTreeMap<String, (u64, f64)>> = Mutex::new(BTreeMap::new());
+
+/// Counts a violation of the invariant `id` and returns its number of
+/// violations so far.
+///
+/// Called by [`invariant!`](crate::invariant), which also logs it.
+#[doc(hidden)]
+pub fn count_invariant_violation(id: &str) -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+        * 1000.0;
+    let mut violations = VIOLATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let (count, last) = violations.entry(id.to_string()).or_default();
+    *count += 1;
+    *last = now;
+    *count
+}
+
+/// Returns the invariants violated since the app started, most violated
+/// first.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// for invariant in tauri_plugin_tracing::invariant_violations() {
+///     println!("{}: violated {} times", invariant.id, invariant.violations);
+/// }
+/// ```
+pub fn invariant_violations() -> Vec<ViolatedInvariant> {
+    let violations = VIOLATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut invariants: Vec<ViolatedInvariant> = violations
+        .iter()
+        .map(|(id, (violations, last))| ViolatedInvariant {
+            id: id.clone(),
+            violations: *violations,
+            last_violation_ms: *last,
+        })
+        .collect();
+    invariants.sort_by(|a, b| b.violations.cmp(&a.violations).then(a.id.cmp(&b.id)));
+    invariants
+}
+
+/// Logs an `ERROR` event under [`INVARIANT_TARGET`] if a condition does not
+/// hold, and counts the violation.
+///
+/// The second argument is the invariant's id, which should be unique and
+/// stable across versions so violations can be tracked in the field. It
+/// defaults to the file and line of the check. Fields and a message for the
+/// event may follow, as in [`tracing::error!`]. The event records the id as
+/// `invariant`, the condition as `condition`, and the number of violations
+/// so far as `violations`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::invariant;
+/// # let (total, items) = (-1, 3);
+/// invariant!(total >= 0, "cart-total-non-negative", total, items, "cart total is negative");
+/// invariant!(items < 10_000);
+/// ```
+#[macro_export]
+macro_rules! invariant {
+    ($condition:expr $(,)?) => {
+        $crate::invariant!($condition, concat!(file!(), ":", line!()))
+    };
+    ($condition:expr, $id:expr $(,)?) => {
+        $crate::invariant!($condition, $id, "invariant violated: {}", stringify!($condition))
+    };
+    ($condition:expr, $id:expr, $($arg:tt)+) => {
+        if !$condition {
+            let id: &str = $id;
+            let violations = $crate::count_invariant_violation(id);
+            $crate::tracing::error!(
+                target: $crate::INVARIANT_TARGET,
+                invariant = id,
+                condition = stringify!($condition),
+                violations,
+                $($arg)+
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_violations_by_id() {
+        let items = 3;
+        for _ in 0..3 {
+            invariant!(items < 2, "unit-test-counted");
+        }
+        invariant!(items > 2, "unit-test-held");
+
+        let invariants = invariant_violations();
+        let counted = invariants
+            .iter()
+            .find(|invariant| invariant.id == "unit-test-counted")
+            .expect("violated invariant");
+        assert_eq!(counted.violations, 3);
+        assert!(counted.last_violation_ms > 0.0);
+        assert!(
+            !invariants
+                .iter()
+                .any(|invariant| invariant.id == "unit-test-held")
+        );
+    }
+}
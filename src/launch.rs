@@ -0,0 +1,148 @@
+//! How the app was launched.
+//!
+//! Bug reports rarely say whether the app was opened from a deep link, a file
+//! association, or the command line. With
+//! [`Builder::with_launch_logging()`](crate::Builder::with_launch_logging),
+//! the plugin logs the process arguments and working directory at startup,
+//! and on macOS and iOS the URLs the app is opened with, as `INFO` events
+//! under the [`LAUNCH_TARGET`] target.
+//!
+//! Deep links delivered through other plugins, and arguments forwarded by a
+//! second instance, are logged by passing them to the managed [`LaunchLog`]:
+//!
+//! ```rust,no_run
+//! # use tauri::Manager;
+//! # use tauri_plugin_tracing::LaunchLog;
+//! # fn example(app: &tauri::AppHandle, argv: Vec<String>, cwd: String) {
+//! // In the tauri-plugin-single-instance callback
+//! if let Some(launch) = app.try_state::<LaunchLog>() {
+//!     launch.log_forwarded_args(&argv, &cwd);
+//! }
+//! # }
+//! ```
+//!
+//! Values of sensitive flags, such as `--token secret` or `--token=secret`,
+//! and query parameters of the same name in URLs, are replaced with
+//! `REDACTED`.
+
+use tauri::Url;
+
+/// The target used for launch events.
+pub const LAUNCH_TARGET: &str = "app::launch";
+
+/// Replaces the values of sensitive arguments.
+const REDACTED: &str = "REDACTED";
+
+/// Logs launch arguments and URLs with sensitive values redacted.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_launch_logging()`](crate::Builder::with_launch_logging) is
+/// set.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchLog {
+    sensitive_args: Vec<String>,
+}
+
+impl LaunchLog {
+    /// Creates a log that redacts the values of `sensitive_args`, e.g.
+    /// `--token`.
+    pub fn new<I, S>(sensitive_args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            sensitive_args: sensitive_args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the flags whose values are redacted.
+    pub fn sensitive_args(&self) -> &[String] {
+        &self.sensitive_args
+    }
+
+    /// Returns `args` with the values of sensitive flags redacted.
+    ///
+    /// Both `--flag value` and `--flag=value` are recognized.
+    pub fn redact_args(&self, args: &[String]) -> Vec<String> {
+        let mut redact_next = false;
+        args.iter()
+            .map(|arg| {
+                if std::mem::take(&mut redact_next) {
+                    return REDACTED.to_string();
+                }
+                if let Some((flag, _)) = arg.split_once('=')
+                    && self.is_sensitive(flag)
+                {
+                    return format!("{flag}={REDACTED}");
+                }
+                redact_next = self.is_sensitive(arg);
+                arg.clone()
+            })
+            .collect()
+    }
+
+    /// Returns `url` with query parameters named like a sensitive flag, e.g.
+    /// `token` for `--token`, redacted.
+    pub fn redact_url(&self, url: &Url) -> String {
+        let sensitive = |name: &str| {
+            self.sensitive_args
+                .iter()
+                .any(|flag| flag.trim_start_matches('-') == name)
+        };
+        if !url.query_pairs().any(|(name, _)| sensitive(&name)) {
+            return url.to_string();
+        }
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if sensitive(&name) {
+                    REDACTED.into()
+                } else {
+                    value
+                };
+                (name.into_owned(), value.into_owned())
+            })
+            .collect();
+        let mut url = url.clone();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+        url.to_string()
+    }
+
+    /// Logs the arguments and working directory of the current process.
+    pub fn log_startup(&self) {
+        let args: Vec<String> = std::env::args_os()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        let cwd = std::env::current_dir().unwrap_or_default();
+        tracing::info!(
+            target: LAUNCH_TARGET,
+            args = ?self.redact_args(&args),
+            cwd = %cwd.display(),
+            "launched"
+        );
+    }
+
+    /// Logs the arguments and working directory forwarded by a second
+    /// instance of the app.
+    pub fn log_forwarded_args(&self, args: &[String], cwd: &str) {
+        tracing::info!(
+            target: LAUNCH_TARGET,
+            args = ?self.redact_args(args),
+            cwd,
+            "second instance launched"
+        );
+    }
+
+    /// Logs deep-link URLs the app was opened with.
+    pub fn log_deep_links(&self, urls: &[Url]) {
+        for url in urls {
+            let url = self.redact_url(url);
+            tracing::info!(target: LAUNCH_TARGET, url, "opened {url}");
+        }
+    }
+
+    fn is_sensitive(&self, arg: &str) -> bool {
+        self.sensitive_args.iter().any(|flag| flag == arg)
+    }
+}
@@ -1,48 +1,16 @@
 //! WebviewLayer for forwarding log events to the frontend.
 
-use serde::{Deserialize, Serialize};
-use serde_repr::{Deserialize_repr, Serialize_repr};
-use tauri::{AppHandle, Emitter, Runtime};
-use tracing_subscriber::Layer;
-
-/// A log message consisting of one or more string parts.
-///
-/// This type wraps a `Vec<String>` to allow logging multiple values in a single call.
-/// When displayed, the parts are joined with ", ".
-#[derive(Debug, Deserialize, Serialize, Clone)]
-#[serde(rename_all = "camelCase")]
-#[cfg_attr(feature = "specta", derive(specta::Type))]
-pub struct LogMessage(Vec<String>);
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
-impl std::ops::Deref for LogMessage {
-    type Target = Vec<String>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl std::ops::DerefMut for LogMessage {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
-impl std::fmt::Display for LogMessage {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.join(", "))
-    }
-}
+use tauri::{AppHandle, Emitter, Manager, RunEvent, Runtime, WindowEvent};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::LevelFilter;
 
-/// Payload for a log record, used when emitting events to the webview.
-#[derive(Debug, Serialize, Clone)]
-#[cfg_attr(feature = "specta", derive(specta::Type))]
-pub struct RecordPayload {
-    /// The formatted log message.
-    pub message: String,
-    /// The severity level of the log.
-    pub level: LogLevel,
-}
+use crate::plugin_name::channel;
+use crate::{LogLevel, RecordFields, RecordPayload};
 
 /// A tracing layer that emits log events to the webview via Tauri events.
 ///
@@ -50,6 +18,11 @@ pub struct RecordPayload {
 /// via the `tracing://log` event, allowing JavaScript code to receive
 /// logs using `attachLogger()` or `attachConsole()`.
 ///
+/// When the plugin is registered, forwarding pauses automatically while no
+/// webviews exist (e.g. tray-only mode) and records are buffered instead of
+/// emitted. Once a new frontend has registered its listener, the buffered
+/// records are emitted so it catches up. See [`WebviewForwarding`].
+///
 /// # Example
 ///
 /// By default, the plugin does not set up a global subscriber. Use this layer
@@ -74,16 +47,362 @@ pub struct RecordPayload {
 ///     });
 ///     // .run(tauri::generate_context!())
 /// ```
+///
+/// Events that would only reach the frontend to be dropped there can be
+/// rejected by the layer itself with [`with_max_level()`](Self::with_max_level)
+/// and [`with_blocked_targets()`](Self::with_blocked_targets). The layer
+/// rejects them in `Layer::enabled()` and `Layer::register_callsite()`, so
+/// their callsites are disabled before any layer visits or formats their
+/// fields. This disables them for the whole subscriber; to drop events from
+/// the frontend alone, wrap the layer in a per-layer filter with
+/// `.with_filter()` instead.
+///
+/// Records are translated for the frontend by the translator set with
+/// [`Builder::with_message_translator()`](crate::Builder::with_message_translator),
+/// or with [`with_message_translator()`](Self::with_message_translator).
 pub struct WebviewLayer<R: Runtime> {
-    app_handle: AppHandle<R>,
+    emitter: Box<dyn RecordEmitter>,
+    forwarding: WebviewForwarding,
+    translator: Option<MessageTranslator>,
+    max_level: LevelFilter,
+    blocked_targets: Vec<String>,
+    _runtime: PhantomData<fn() -> R>,
 }
 
 impl<R: Runtime> WebviewLayer<R> {
     /// Creates a new WebviewLayer that forwards log events to the given app handle.
     ///
-    /// Events are emitted via the `tracing://log` event channel.
+    /// Events are emitted via the `tracing://log` event channel, or the `log`
+    /// channel of the name set with
+    /// [`Builder::with_plugin_name()`](crate::Builder::with_plugin_name).
     pub fn new(app_handle: AppHandle<R>) -> Self {
-        Self { app_handle }
+        let forwarding = match app_handle.try_state::<WebviewForwarding>() {
+            Some(forwarding) => forwarding.inner().clone(),
+            None => {
+                let forwarding = WebviewForwarding::default();
+                app_handle.manage(forwarding.clone());
+                forwarding
+            }
+        };
+        let translator = app_handle
+            .try_state::<MessageTranslator>()
+            .map(|translator| translator.inner().clone());
+        Self {
+            forwarding,
+            translator,
+            ..Self::with_emitter(app_handle)
+        }
+    }
+
+    /// Creates a WebviewLayer that hands records to `emitter` instead of an app handle.
+    pub(crate) fn with_emitter(emitter: impl RecordEmitter) -> Self {
+        Self {
+            emitter: Box::new(emitter),
+            forwarding: WebviewForwarding::default(),
+            translator: None,
+            max_level: LevelFilter::TRACE,
+            blocked_targets: Vec::new(),
+            _runtime: PhantomData,
+        }
+    }
+
+    /// Returns the handle controlling whether records are forwarded or buffered.
+    pub fn forwarding(&self) -> WebviewForwarding {
+        self.forwarding.clone()
+    }
+
+    /// Forwards only events at or above `level`.
+    ///
+    /// Events below `level` are disabled for the whole subscriber, so other
+    /// layers do not see them either.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{WebviewLayer, LevelFilter};
+    /// # fn example(app: &tauri::AppHandle) {
+    /// let layer = WebviewLayer::new(app.clone()).with_max_level(LevelFilter::WARN);
+    /// # }
+    /// ```
+    pub fn with_max_level(mut self, level: LevelFilter) -> Self {
+        self.max_level = level;
+        self
+    }
+
+    /// Skips events whose target is one of `targets` or a module below one,
+    /// e.g. `"tao"` also skips `tao::platform_impl`.
+    ///
+    /// Skipped events are disabled for the whole subscriber, so other layers
+    /// do not see them either.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::WebviewLayer;
+    /// # fn example(app: &tauri::AppHandle) {
+    /// let layer = WebviewLayer::new(app.clone()).with_blocked_targets(["tao", "hyper"]);
+    /// # }
+    /// ```
+    pub fn with_blocked_targets<I, S>(mut self, targets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.blocked_targets = targets.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Replaces the message of each record with the one `translate` returns
+    /// for it, if any.
+    ///
+    /// Only the records sent to the frontend are translated; see
+    /// [`Builder::with_message_translator()`](crate::Builder::with_message_translator).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::WebviewLayer;
+    /// # fn example(app: &tauri::AppHandle) {
+    /// let layer = WebviewLayer::new(app.clone()).with_message_translator(|record| {
+    ///     (record.message == "sync failed").then(|| "Could not sync your changes".to_string())
+    /// });
+    /// # }
+    /// ```
+    pub fn with_message_translator<F>(mut self, translate: F) -> Self
+    where
+        F: Fn(&RecordPayload) -> Option<String> + Send + Sync + 'static,
+    {
+        self.translator = Some(MessageTranslator::new(translate));
+        self
+    }
+
+    /// Returns whether events with `metadata` are forwarded, judging by the
+    /// level and target alone. Spans are always let through.
+    fn forwards(&self, metadata: &tracing::Metadata<'_>) -> bool {
+        if !metadata.is_event() {
+            return true;
+        }
+        let target = metadata.target();
+        self.max_level >= *metadata.level()
+            && !self.blocked_targets.iter().any(|blocked| {
+                target
+                    .strip_prefix(blocked.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+    }
+}
+
+/// Rewrites the messages of records sent to the frontend.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_message_translator()`](crate::Builder::with_message_translator)
+/// is used, and [`WebviewLayer::new()`] picks it up.
+#[derive(Clone)]
+pub(crate) struct MessageTranslator(Arc<dyn Fn(&RecordPayload) -> Option<String> + Send + Sync>);
+
+impl MessageTranslator {
+    pub(crate) fn new<F>(translate: F) -> Self
+    where
+        F: Fn(&RecordPayload) -> Option<String> + Send + Sync + 'static,
+    {
+        Self(Arc::new(translate))
+    }
+
+    fn translate(&self, payload: &mut RecordPayload) {
+        if let Some(message) = (self.0)(payload) {
+            payload.message = message;
+        }
+    }
+}
+
+/// The number of records buffered while forwarding is paused.
+const DEFAULT_PAUSE_BUFFER: usize = 1000;
+
+/// Controls whether a [`WebviewLayer`] emits records or buffers them.
+///
+/// The plugin manages an instance of this type in Tauri state and pauses it
+/// when the last webview is destroyed, so records are not serialized for a
+/// frontend that does not exist. While paused, up to 1000 of the most recent
+/// records are kept. Forwarding resumes once a frontend listens again, when
+/// the guest-js `attachLogger()`, `onLog()`, or `attachConsole()` listener is
+/// registered, and the buffered records are emitted first so the frontend
+/// sees them in order. Frontends that listen to `tracing://log` directly
+/// should invoke the `webview_ready` command once they do.
+///
+/// The first record emitted after older records were dropped carries the
+/// number of dropped records as its `gap`, so the frontend can tell that
+/// records are missing.
+#[derive(Clone)]
+pub struct WebviewForwarding {
+    inner: Arc<ForwardingInner>,
+}
+
+struct ForwardingInner {
+    paused: AtomicBool,
+    /// Set while buffered records are emitted, so only one resume drains them.
+    resuming: AtomicBool,
+    buffer: Mutex<VecDeque<RecordPayload>>,
+    capacity: usize,
+    /// Records dropped since the last emitted record.
+    dropped: AtomicU64,
+}
+
+impl Default for WebviewForwarding {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_PAUSE_BUFFER)
+    }
+}
+
+impl WebviewForwarding {
+    /// Creates a handle that buffers at most `capacity` records while paused.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(ForwardingInner {
+                paused: AtomicBool::new(false),
+                resuming: AtomicBool::new(false),
+                buffer: Mutex::new(VecDeque::new()),
+                capacity,
+                dropped: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns `true` if records are currently being buffered.
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.load(Ordering::Acquire)
+    }
+
+    /// Stops emitting records and starts buffering them.
+    pub fn pause(&self) {
+        self.inner.paused.store(true, Ordering::Release);
+    }
+
+    /// Emits any buffered records through `app_handle` and resumes forwarding.
+    pub fn resume<R: Runtime>(&self, app_handle: &AppHandle<R>) {
+        self.resume_with(app_handle);
+    }
+
+    /// Returns the number of records waiting to be emitted.
+    pub fn buffered(&self) -> usize {
+        self.inner
+            .buffer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    /// Returns the number of records dropped since the last emitted record.
+    pub fn dropped(&self) -> u64 {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn resume_with(&self, emitter: &dyn RecordEmitter) {
+        // A concurrent resume is already draining the buffer in order
+        if self.inner.resuming.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        loop {
+            let (records, dropped) = {
+                let mut buffer = self.inner.buffer.lock().unwrap_or_else(|e| e.into_inner());
+                if buffer.is_empty() {
+                    // Under the lock, so no record is buffered after the check
+                    self.inner.paused.store(false, Ordering::Release);
+                    break;
+                }
+                // Dropped records were older than every buffered one
+                let dropped = self.inner.dropped.swap(0, Ordering::Relaxed);
+                (std::mem::take(&mut *buffer), dropped)
+            };
+            // Emitted without the lock, as emitting may log, and records
+            // logged meanwhile are buffered for the next round
+            for (n, mut payload) in records.into_iter().enumerate() {
+                if n == 0 && dropped > 0 {
+                    payload.gap = Some(dropped);
+                }
+                emitter.emit_record(payload);
+            }
+        }
+        self.inner.resuming.store(false, Ordering::Release);
+    }
+
+    fn forward(&self, payload: RecordPayload, emitter: &dyn RecordEmitter) {
+        if self.is_paused() {
+            let mut buffer = self.inner.buffer.lock().unwrap_or_else(|e| e.into_inner());
+            // Re-check under the lock so a concurrent resume cannot strand the record
+            if self.is_paused() {
+                if self.inner.capacity == 0 {
+                    self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                if buffer.len() >= self.inner.capacity {
+                    // The oldest record goes, so the gap is before the new front
+                    buffer.pop_front();
+                    self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                buffer.push_back(payload);
+                return;
+            }
+        }
+        self.emit(payload, emitter);
+    }
+
+    /// Emits `payload` with the number of records dropped before it.
+    fn emit(&self, mut payload: RecordPayload, emitter: &dyn RecordEmitter) {
+        let dropped = self.inner.dropped.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            payload.gap = Some(dropped);
+        }
+        emitter.emit_record(payload);
+    }
+}
+
+/// Pauses forwarding when the last webview is destroyed.
+pub(crate) fn handle_run_event<R: Runtime>(app_handle: &AppHandle<R>, event: &RunEvent) {
+    let RunEvent::WindowEvent {
+        label,
+        event: WindowEvent::Destroyed,
+        ..
+    } = event
+    else {
+        return;
+    };
+    let Some(forwarding) = app_handle.try_state::<WebviewForwarding>() else {
+        return;
+    };
+    // The destroyed window may still be registered while its event is handled
+    let remaining = app_handle
+        .webview_windows()
+        .keys()
+        .filter(|window| *window != label)
+        .count();
+    if remaining == 0 {
+        forwarding.pause();
+    }
+}
+
+/// Resumes forwarding once a frontend listens to records again.
+///
+/// A page that has finished loading may not have registered its listener
+/// yet, so the frontend signals it with the `webview_ready` command.
+pub(crate) fn handle_webview_ready<R: Runtime>(app_handle: &AppHandle<R>) {
+    if let Some(forwarding) = app_handle.try_state::<WebviewForwarding>()
+        && forwarding.is_paused()
+    {
+        forwarding.resume(app_handle);
+    }
+}
+
+/// Destination for records forwarded by a [`WebviewLayer`].
+///
+/// Implemented for [`AppHandle`], which emits on the plugin's `log` channel.
+/// Tests substitute an implementation that captures payloads instead.
+pub(crate) trait RecordEmitter: Send + Sync + 'static {
+    fn emit_record(&self, payload: RecordPayload);
+}
+
+impl<R: Runtime> RecordEmitter for AppHandle<R> {
+    fn emit_record(&self, payload: RecordPayload) {
+        let _ = self.emit(&channel(self, "tracing://log"), payload);
     }
 }
 
@@ -91,110 +410,83 @@ impl<S, R: Runtime> Layer<S> for WebviewLayer<R>
 where
     S: tracing::Subscriber,
 {
+    fn register_callsite(
+        &self,
+        metadata: &'static tracing::Metadata<'static>,
+    ) -> tracing::subscriber::Interest {
+        if self.forwards(metadata) {
+            tracing::subscriber::Interest::always()
+        } else {
+            tracing::subscriber::Interest::never()
+        }
+    }
+
+    fn enabled(
+        &self,
+        metadata: &tracing::Metadata<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) -> bool {
+        self.forwards(metadata)
+    }
+
     fn on_event(
         &self,
         event: &tracing::Event<'_>,
         _ctx: tracing_subscriber::layer::Context<'_, S>,
     ) {
-        let mut visitor = MessageVisitor::default();
+        let mut visitor = FieldVisitor::default();
         event.record(&mut visitor);
 
         let level: LogLevel = (*event.metadata().level()).into();
-        let payload = RecordPayload {
-            message: visitor.message,
+        let mut payload = RecordPayload {
+            message: visitor.message(),
             level,
+            fields: visitor.fields,
+            breadcrumbs: crate::current_breadcrumbs(),
+            seq: crate::current_sequence(),
+            gap: None,
         };
+        if let Some(translator) = &self.translator {
+            translator.translate(&mut payload);
+        }
 
-        let _ = self.app_handle.emit("tracing://log", payload);
+        self.forwarding.forward(payload, self.emitter.as_ref());
     }
 }
 
+/// Collects the `message` and the other fields of an event.
 #[derive(Default)]
-struct MessageVisitor {
-    message: String,
+pub(crate) struct FieldVisitor {
+    message: Option<String>,
+    first_field: Option<&'static str>,
+    pub(crate) fields: RecordFields,
 }
 
-impl tracing::field::Visit for MessageVisitor {
-    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
-        if field.name() == "message" || self.message.is_empty() {
-            self.message = format!("{:?}", value);
-        }
+impl FieldVisitor {
+    /// Returns the `message` field, or else the first other field.
+    pub(crate) fn message(&self) -> String {
+        self.message
+            .clone()
+            .or_else(|| self.fields.get(self.first_field?).cloned())
+            .unwrap_or_default()
     }
 
-    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
-        if field.name() == "message" || self.message.is_empty() {
-            self.message = value.to_string();
+    fn record(&mut self, field: &tracing::field::Field, value: String) {
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.first_field.get_or_insert(field.name());
+            self.fields.insert(field.name().to_string(), value);
         }
     }
 }
 
-/// An enum representing the available verbosity levels of the logger.
-///
-/// It is very similar to `log::Level`, but serializes to unsigned ints instead of strings.
-///
-/// # Examples
-///
-/// ```
-/// use tauri_plugin_tracing::LogLevel;
-///
-/// // Default is Info
-/// assert!(matches!(LogLevel::default(), LogLevel::Info));
-///
-/// // Convert to tracing::Level
-/// let level: tracing::Level = LogLevel::Debug.into();
-/// assert_eq!(level, tracing::Level::DEBUG);
-///
-/// // Convert from tracing::Level
-/// let log_level: LogLevel = tracing::Level::WARN.into();
-/// assert!(matches!(log_level, LogLevel::Warn));
-/// ```
-#[derive(Debug, Clone, Deserialize_repr, Serialize_repr, Default)]
-#[repr(u16)]
-#[cfg_attr(feature = "specta", derive(specta::Type))]
-pub enum LogLevel {
-    /// The "trace" level.
-    ///
-    /// Designates very low priority, often extremely verbose, information.
-    Trace = 1,
-    /// The "debug" level.
-    ///
-    /// Designates lower priority information.
-    Debug,
-    /// The "info" level.
-    ///
-    /// Designates useful information.
-    #[default]
-    Info,
-    /// The "warn" level.
-    ///
-    /// Designates hazardous situations.
-    Warn,
-    /// The "error" level.
-    ///
-    /// Designates very serious errors.
-    Error,
-}
-
-impl From<LogLevel> for tracing::Level {
-    fn from(log_level: LogLevel) -> Self {
-        match log_level {
-            LogLevel::Trace => tracing::Level::TRACE,
-            LogLevel::Debug => tracing::Level::DEBUG,
-            LogLevel::Info => tracing::Level::INFO,
-            LogLevel::Warn => tracing::Level::WARN,
-            LogLevel::Error => tracing::Level::ERROR,
-        }
+impl tracing::field::Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.record(field, format!("{:?}", value));
     }
-}
 
-impl From<tracing::Level> for LogLevel {
-    fn from(log_level: tracing::Level) -> Self {
-        match log_level {
-            tracing::Level::TRACE => LogLevel::Trace,
-            tracing::Level::DEBUG => LogLevel::Debug,
-            tracing::Level::INFO => LogLevel::Info,
-            tracing::Level::WARN => LogLevel::Warn,
-            tracing::Level::ERROR => LogLevel::Error,
-        }
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.record(field, value.to_string());
     }
 }
@@ -9,10 +9,45 @@
 //!
 //! ## Features
 //!
-//! - **`colored`**: Enables colored terminal output using ANSI escape codes
+//! Enabled by default:
+//!
+//! - **`callstack`**: Parses the JavaScript call stack of webview records to locate them, see [`CallStack`]
+//! - **`file`**: Enables file targets with time-based rotation, see [`Builder::with_file_logging()`]
+//! - **`rotation`**: Enables size-based rotation with [`Builder::with_max_file_size()`]
+//! - **`webview`**: Lets the default subscriber forward records to webviews for
+//!   [`Target::Webview`]. [`WebviewLayer`] is available without it, for
+//!   custom subscribers
+//!
+//! With `default-features = false`, the plugin only records webview logs and
+//! writes to stdout and stderr, without `tracing-appender`, `rolling-file`,
+//! `chrono`, or `memchr` in the dependency tree. Unavailable targets are
+//! ignored.
+//!
+//! Optional:
+//!
+//! - **`colored`**: Enables colored terminal output using ANSI escape codes, with a configurable [`ColorTheme`]
+//!   and clickable source locations, see [`EditorScheme`]
 //! - **`specta`**: Enables TypeScript type generation via the `specta` crate
 //! - **`flamegraph`**: Enables flamegraph/flamechart profiling support (wall-clock span timing)
 //! - **`profiling`**: Enables CPU profiling via [`tauri-plugin-profiling`](https://crates.io/crates/tauri-plugin-profiling)
+//! - **`gelf`**: Enables [`Target::Gelf`] for sending logs to Graylog
+//! - **`diagnostics`**: Enables [`create_diagnostic_report()`] for bundling logs, configuration, and system
+//!   information into a zip file for support requests, and [`verify_log_bundle()`] for checking them
+//! - **`diagnostics-endpoint`**: Enables [`Builder::with_diagnostics_endpoint()`], a token-protected HTTP
+//!   endpoint on localhost for test harnesses and diagnostics
+//! - **`loki`**: Enables [`Target::Loki`] for pushing logs to Grafana Loki
+//! - **`otlp`**: Enables [`Target::Otlp`] for exporting logs to OpenTelemetry collectors and backends
+//! - **`macros`**: Enables the [`traced_command`] attribute for logging Tauri command invocations
+//! - **`main-thread-backtrace`**: Adds the main thread's native backtrace to the warnings of the
+//!   [`EventLoopWatchdog`], on Unix platforms
+//! - **`max-level-*`**, **`release-max-level-*`**: Compile out events more verbose than a level, in all
+//!   builds or in release builds, like `tracing`'s features of the same names. This includes records
+//!   sent by the webview, which are dropped before any processing. Configuring a more verbose level
+//!   at runtime logs a warning
+//! - **`schema`**: Enables [`json_schema()`] for validating webview records and query results in other languages
+//! - **`sync-debug`**: Enables [`TracedMutex`] and [`TracedRwLock`] for finding long lock waits and holds
+//! - **`test-utils`**: Enables the [`test`] module for asserting on logs in tests, including on Tauri's mock runtime
+//! - **`tui`**: Enables [`TuiTailExt::spawn_tui_tail()`] for a live, filterable tail of the logs in the terminal
 //!
 //! ## Usage
 //!
@@ -279,6 +314,17 @@
 //!
 //! Or use the base [`ProfilingExt`](ProfilingExtBase) trait directly without tracing integration.
 //!
+//! [`SpanAwareProfilingExt`] also saves each [`SpanCorrelationReport`] as a
+//! profiling session in the app data directory. [`list_profiling_sessions()`]
+//! and [`SpanCorrelationReport::from_file()`] load them again, so regressions
+//! can be compared across app versions. With
+//! [`Builder::with_profiling_watchdog()`], a profile is also recorded when a
+//! span is busy for too long, capturing hangs as they happen.
+//!
+//! For visibility in production, [`Builder::with_span_stats()`] keeps busy
+//! time statistics of each span name at all times, without profiling. Read
+//! them with [`get_span_stats()`].
+//!
 //! From JavaScript (import from the profiling package directly):
 //!
 //! ```javascript
@@ -290,6 +336,18 @@
 //! console.log('CPU flamegraph:', result.flamegraphPath);
 //! ```
 //!
+//! Or run span-aware profiles through this plugin, to get the active spans as
+//! well:
+//!
+//! ```javascript
+//! import { startSpanAwareProfile, stopSpanAwareProfile } from '@fltsci/tauri-plugin-tracing';
+//!
+//! await startSpanAwareProfile();
+//! // ... do work ...
+//! const report = await stopSpanAwareProfile();
+//! console.table(report.activeSpans);
+//! ```
+//!
 //! ## JavaScript API
 //!
 //! ```javascript
@@ -300,20 +358,99 @@
 //! error('Something went wrong');
 //! ```
 
-mod callstack;
+mod breadcrumbs;
+mod build_info;
+mod capture;
+mod clock;
+#[cfg(feature = "colored")]
+mod color_theme;
 mod commands;
+mod context;
+mod devtools_console;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+pub mod early;
+#[cfg(feature = "colored")]
+mod editor_links;
+#[cfg(feature = "diagnostics-endpoint")]
+mod endpoint;
 mod error;
+mod error_chain;
+mod event_ids;
+mod event_loop;
+mod file_name;
+mod filter;
+mod filter_stats;
 #[cfg(feature = "flamegraph")]
 mod flamegraph;
+#[cfg(feature = "file")]
+mod forward;
+mod frames;
+#[cfg(feature = "file")]
+mod fsync;
+#[cfg(feature = "gelf")]
+mod gelf;
+mod heartbeat;
+mod invariant;
+mod launch;
 mod layer;
+mod log_files;
+mod log_tail;
+#[cfg(feature = "loki")]
+mod loki;
+mod marks;
+mod metrics;
+mod navigation;
+mod noise;
+mod notify;
+mod operations;
+#[cfg(target_vendor = "apple")]
+mod oslog;
+#[cfg(feature = "otlp")]
+mod otlp;
+mod pipe;
+mod plugin_name;
 #[cfg(feature = "profiling")]
 mod profiling;
+mod progress;
+mod reconfigure;
+mod resources;
+mod rfc5424;
+mod rotation;
+mod routes;
+mod sampling;
+#[cfg(feature = "schema")]
+mod schema;
+mod sequence;
+#[cfg(all(unix, desktop))]
+mod signals;
+mod slow_span;
+mod span_context;
+mod spawn;
+#[cfg(any(feature = "file", feature = "tui"))]
 mod strip_ansi;
+mod suppress;
+#[cfg(feature = "sync-debug")]
+mod sync_debug;
+mod target_glob;
+#[cfg(feature = "test-utils")]
+pub mod test;
+mod test_mode;
+#[cfg(feature = "tui")]
+mod tui;
 mod types;
+mod update;
+mod validate;
+#[cfg(feature = "profiling")]
+mod watchdog;
+mod webview_crash;
+mod webview_spans;
 
 use std::path::PathBuf;
+use std::time::Duration;
 use tauri::plugin::{self, TauriPlugin};
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Manager, RunEvent, Runtime};
+#[cfg(feature = "file")]
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{
     Layer as _, Registry,
@@ -322,14 +459,121 @@ use tracing_subscriber::{
     layer::SubscriberExt,
 };
 
+use file_name::FileNameTemplate;
+use filter::{FilterOverrides, LayerFilter, Output, SpanFilter};
+use filter_stats::CountingFilter;
+use layer::MessageTranslator;
+#[cfg(feature = "file")]
+use rotation::PeriodWatcher;
+use rotation::RotationHook;
+#[cfg(feature = "rotation")]
+use rotation::{RolloverCondition, RolloverWatcher};
+use target_glob::{GlobFilter, TargetGlobs};
+
 // Re-export public types from modules
-pub use callstack::{CallStack, CallStackLine};
-pub use commands::log;
+pub use breadcrumbs::{BreadcrumbFields, BreadcrumbLayer, current_breadcrumbs};
+pub use build_info::{BuildInfo, BuildVariant};
+pub use capture::{CAPTURED_STDERR_TARGET, CAPTURED_STDOUT_TARGET, OutputCapture};
+pub use clock::{ClockSample, ClockSync};
+#[cfg(feature = "colored")]
+pub use color_theme::{ColorTheme, ThemedWriter, ThemedWriterGuard};
+pub use commands::{LogLimits, LogRecord, log, log_batch};
+pub use context::{ContextFields, LogContext, current_context, scoped_context};
+pub use devtools_console::DevtoolsConsoleLayer;
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::{
+    Attachment, BundleVerification, DiagnosticReportOptions, SignatureStatus,
+    create_diagnostic_report, verify_log_bundle,
+};
+#[cfg(feature = "colored")]
+pub use editor_links::{EditorLinkWriter, EditorLinkWriterGuard, EditorScheme};
+#[cfg(feature = "diagnostics-endpoint")]
+pub use endpoint::{DiagnosticsEndpoint, ENDPOINT_TARGET};
 pub use error::{Error, Result};
-pub use layer::{LogLevel, LogMessage, RecordPayload, WebviewLayer};
+pub use error_chain::{ResultExt, TRACED_ERROR_TARGET};
+pub use event_ids::{EVENT_ID_FIELD, EventId};
+pub use event_loop::{EVENT_LOOP_TARGET, EventLoopWatchdog};
+pub use filter::{EffectiveFilter, FILTER_FILE_NAME, FilterHandle};
+pub use filter_stats::{DroppedEvents, FilterStats, TargetDrops, get_filter_stats};
+pub use frames::{FRAMES_TARGET, FrameStats, FrameTimingReport, LongFrame};
+#[cfg(feature = "gelf")]
+pub use gelf::{GelfLayer, GelfTransport};
+pub use heartbeat::{HEARTBEAT_TARGET, Heartbeat, PING_EVENT};
+pub use invariant::{
+    INVARIANT_TARGET, ViolatedInvariant, count_invariant_violation, invariant_violations,
+};
+pub use launch::{LAUNCH_TARGET, LaunchLog};
+pub use layer::{WebviewForwarding, WebviewLayer};
+pub use log_files::{LogFiles, LogStats};
+pub use log_tail::{LogTail, LogTailer, TAIL_EVENT, TailLines, stop_tail_logs, tail_logs};
+#[cfg(feature = "loki")]
+pub use loki::LokiLayer;
+pub use marks::{LogHistory, MARK_TARGET, MarkContext, logs_around_mark, mark};
+pub use metrics::{METRICS_EVENT, METRICS_TARGET, MetricsBatch, counter, gauge};
+pub use navigation::{NAVIGATION_TARGET, NavigationBreadcrumbs};
+pub use noise::{
+    CrateNoise, NOISY_CRATES, NoiseAnalyzer, NoiseRecommendation, NoiseReport, analyze_noise,
+    apply_noise_filters,
+};
+pub use notify::{NOTIFY_EVENT, Notifications, NotifyLayer, UserNotification};
+pub use operations::{
+    MAX_OPERATIONS, OPERATIONS_TARGET, OperationHandle, OperationOutcome, OperationRegistry,
+};
+#[cfg(target_vendor = "apple")]
+pub use oslog::OsLogLayer;
+#[cfg(feature = "otlp")]
+pub use otlp::{OtlpLayer, os_type};
+pub use pipe::PipeLayer;
+pub use plugin_name::DEFAULT_PLUGIN_NAME;
+pub use progress::{PROGRESS_EVENT, PROGRESS_TARGET, ProgressTracker, ProgressUpdate, progress};
+pub use reconfigure::ReconfigureExt;
+pub use resources::{RESOURCES_TARGET, ResourceMonitor, ResourceSnapshot};
+pub use rfc5424::{
+    RFC5424_RESOURCE_SD_ID, RFC5424_SD_ID, Rfc5424Format, SyslogFacility, syslog_severity,
+};
+pub use routes::{CurrentRoutes, ROUTE_TARGET};
+pub use sampling::{SampleRate, SampledFields, SamplingLayer, current_sample_rate};
+#[cfg(feature = "schema")]
+pub use schema::json_schema;
+pub use sequence::{SequenceFields, SequenceLayer, current_sequence};
+pub use slow_span::{SLOW_SPAN_TARGET, SlowSpanLayer};
+pub use span_context::{SpanContext, SpanContextExt, SpanContextRegistry};
+pub use spawn::{block_in_place_traced, spawn_blocking_traced, spawn_traced};
+#[cfg(any(feature = "file", feature = "tui"))]
 pub use strip_ansi::{StripAnsiWriter, StripAnsiWriterGuard};
+pub use suppress::{
+    SuppressGuard, SuppressionLayer, is_suppressed, suppress_logs, suppressed, with_suppressed,
+};
+#[cfg(feature = "sync-debug")]
+pub use sync_debug::{
+    DEFAULT_LOCK_THRESHOLD, SYNC_DEBUG_TARGET, TracedMutex, TracedMutexGuard, TracedRwLock,
+    TracedRwLockReadGuard, TracedRwLockWriteGuard,
+};
+pub use test_mode::{TEST_TARGET, TestCapture, TestLogs};
+#[cfg(feature = "tui")]
+pub use tui::{TuiTail, TuiTailExt, is_tailing};
 pub use types::{
-    FormatOptions, LogFormat, MaxFileSize, Rotation, RotationStrategy, Target, TimezoneStrategy,
+    FormatOptions, InstanceStrategy, LogFormat, MaxFileSize, Rotation, RotationStrategy,
+    SyncPolicy, Target, TimezoneStrategy, VersionLayout,
+};
+pub use update::{UPDATE_TARGET, UpdateLog};
+pub use validate::{CONFIG_TARGET, ConfigError, ConfigIssue, ValidatedConfig};
+#[cfg(feature = "profiling")]
+pub use watchdog::{ProfilingWatchdog, WATCHDOG_TARGET, WatchdogLayer};
+pub use webview_crash::{
+    WEBVIEW_CRASH_TARGET, WEBVIEW_CRASHED_EVENT, WebviewCrash, WebviewCrashReason, webview_crashed,
+    webview_terminated,
+};
+pub use webview_spans::WEBVIEW_SPAN_TARGET;
+
+#[cfg(feature = "macros")]
+pub use tauri_plugin_tracing_macros::traced_command;
+
+// Re-export the record types shared with tools outside the app
+#[cfg(feature = "callstack")]
+pub use tauri_plugin_tracing_core::CallStack;
+pub use tauri_plugin_tracing_core::{
+    Breadcrumb, CallStackLine, LogLevel, LogMessage, RecordFields, RecordPayload,
 };
 
 /// A boxed filter function for metadata-based log filtering.
@@ -370,6 +614,7 @@ pub use profiling::*;
 /// Re-export of the [`tracing`] crate for convenience.
 pub use tracing;
 /// Re-export of the [`tracing_appender`] crate for file logging configuration.
+#[cfg(feature = "file")]
 pub use tracing_appender;
 /// Re-export of the [`tracing_subscriber`] crate for subscriber configuration.
 pub use tracing_subscriber;
@@ -377,6 +622,10 @@ pub use tracing_subscriber;
 /// Re-export of [`tracing_subscriber::filter::LevelFilter`] for configuring log levels.
 pub use tracing_subscriber::filter::LevelFilter;
 
+/// Re-export of [`colored::Color`] for configuring a [`ColorTheme`].
+#[cfg(feature = "colored")]
+pub use colored::Color;
+
 #[cfg(target_os = "ios")]
 mod ios {
     swift_rs::swift!(pub fn tauri_log(
@@ -386,7 +635,12 @@ mod ios {
 
 /// Stores the WorkerGuard to ensure logs are flushed on shutdown.
 /// This must be kept alive for the lifetime of the application.
-struct LogGuard(#[allow(dead_code)] Option<WorkerGuard>);
+#[cfg(feature = "file")]
+struct LogGuard(#[allow(dead_code)] WorkerGuard);
+
+/// Holds the advisory lock on the log file for the lifetime of the application.
+#[cfg(feature = "file")]
+struct InstanceLock(#[allow(dead_code)] std::fs::File);
 
 /// Builder for configuring and creating the tracing plugin.
 ///
@@ -406,14 +660,24 @@ struct LogGuard(#[allow(dead_code)] Option<WorkerGuard>);
 /// ```
 pub struct Builder {
     builder: SubscriberBuilder,
+    plugin_name: &'static str,
     log_level: LevelFilter,
     filter: Targets,
+    target_globs: Vec<(String, LevelFilter)>,
+    span_level: Option<LevelFilter>,
     custom_filter: Option<FilterFn>,
     custom_layer: Option<BoxedLayer>,
     targets: Vec<Target>,
+    layer_filters: Vec<(Target, Targets)>,
+    stderr_threshold: Option<LevelFilter>,
     rotation: Rotation,
     rotation_strategy: RotationStrategy,
     max_file_size: Option<MaxFileSize>,
+    rotation_hook: Option<RotationHook>,
+    instance_strategy: InstanceStrategy,
+    version_layout: VersionLayout,
+    file_name_template: Option<String>,
+    sync_policy: SyncPolicy,
     timezone_strategy: TimezoneStrategy,
     log_format: LogFormat,
     show_file: bool,
@@ -423,24 +687,81 @@ pub struct Builder {
     show_target: bool,
     show_level: bool,
     set_default_subscriber: bool,
+    subscriber_fallback: bool,
+    slow_span_threshold: Option<Duration>,
+    resource_monitor_interval: Option<Duration>,
+    heartbeat_interval: Option<Duration>,
+    event_loop_threshold: Option<Duration>,
+    capture_output: bool,
+    persist_filter: bool,
+    filter_stats: bool,
+    signal_control: bool,
+    max_message_len: Option<usize>,
+    max_stack_frames: Option<usize>,
+    sampling: Vec<(String, SampleRate)>,
+    breadcrumbs: Option<usize>,
+    sequence_numbers: bool,
+    message_translator: Option<MessageTranslator>,
+    notifications: Option<Notifications>,
+    devtools_console: bool,
+    log_history: Option<usize>,
+    test_mode: bool,
+    log_context: LogContext,
+    navigation_breadcrumbs: bool,
+    route_spans: bool,
+    progress_events: bool,
+    event_catalog: Vec<EventId>,
+    metrics_interval: Option<Duration>,
+    launch_logging: Option<Vec<String>>,
+    update_log_file: Option<String>,
+    os_log_categories: Vec<(String, String)>,
+    resource_attributes: Vec<(String, String)>,
+    build_info: Option<BuildInfo>,
+    variant_targets: Vec<(BuildVariant, String, LevelFilter)>,
+    #[cfg(feature = "otlp")]
+    otlp_resource: Vec<(String, String)>,
+    #[cfg(feature = "diagnostics")]
+    report_signing_key: Option<Vec<u8>>,
+    #[cfg(feature = "diagnostics-endpoint")]
+    diagnostics_endpoint: Option<String>,
     #[cfg(feature = "colored")]
     use_colors: bool,
+    #[cfg(feature = "colored")]
+    color_theme: Option<ColorTheme>,
+    #[cfg(feature = "colored")]
+    editor_links: Option<EditorScheme>,
     #[cfg(feature = "flamegraph")]
     enable_flamegraph: bool,
+    #[cfg(feature = "profiling")]
+    profiling_watchdog: Option<ProfilingWatchdog>,
+    #[cfg(feature = "profiling")]
+    span_timing_fields: Option<Vec<String>>,
+    #[cfg(feature = "profiling")]
+    span_stats_window: Option<Duration>,
 }
 
 impl Default for Builder {
     fn default() -> Self {
         Self {
             builder: SubscriberBuilder::default(),
+            plugin_name: DEFAULT_PLUGIN_NAME,
             log_level: LevelFilter::WARN,
             filter: Targets::default(),
+            target_globs: Vec::new(),
+            span_level: None,
             custom_filter: None,
             custom_layer: None,
             targets: vec![Target::Stdout, Target::Webview],
+            layer_filters: Vec::new(),
+            stderr_threshold: None,
             rotation: Rotation::default(),
             rotation_strategy: RotationStrategy::default(),
             max_file_size: None,
+            rotation_hook: None,
+            instance_strategy: InstanceStrategy::default(),
+            version_layout: VersionLayout::default(),
+            file_name_template: None,
+            sync_policy: SyncPolicy::default(),
             timezone_strategy: TimezoneStrategy::default(),
             log_format: LogFormat::default(),
             show_file: false,
@@ -450,10 +771,57 @@ impl Default for Builder {
             show_target: true,
             show_level: true,
             set_default_subscriber: false,
+            subscriber_fallback: false,
+            slow_span_threshold: None,
+            resource_monitor_interval: None,
+            heartbeat_interval: None,
+            event_loop_threshold: None,
+            capture_output: false,
+            persist_filter: false,
+            filter_stats: false,
+            signal_control: false,
+            max_message_len: None,
+            max_stack_frames: None,
+            sampling: Vec::new(),
+            breadcrumbs: None,
+            sequence_numbers: false,
+            message_translator: None,
+            notifications: None,
+            devtools_console: false,
+            log_history: None,
+            test_mode: false,
+            log_context: LogContext::new(),
+            navigation_breadcrumbs: false,
+            route_spans: false,
+            progress_events: false,
+            event_catalog: Vec::new(),
+            metrics_interval: None,
+            launch_logging: None,
+            update_log_file: None,
+            os_log_categories: Vec::new(),
+            resource_attributes: Vec::new(),
+            build_info: None,
+            variant_targets: Vec::new(),
+            #[cfg(feature = "otlp")]
+            otlp_resource: Vec::new(),
+            #[cfg(feature = "diagnostics")]
+            report_signing_key: None,
+            #[cfg(feature = "diagnostics-endpoint")]
+            diagnostics_endpoint: None,
             #[cfg(feature = "colored")]
             use_colors: false,
+            #[cfg(feature = "colored")]
+            color_theme: None,
+            #[cfg(feature = "colored")]
+            editor_links: None,
             #[cfg(feature = "flamegraph")]
             enable_flamegraph: false,
+            #[cfg(feature = "profiling")]
+            profiling_watchdog: None,
+            #[cfg(feature = "profiling")]
+            span_timing_fields: None,
+            #[cfg(feature = "profiling")]
+            span_stats_window: None,
         }
     }
 }
@@ -466,6 +834,51 @@ impl Builder {
         Default::default()
     }
 
+    /// Registers the plugin under `name` instead of `tracing`.
+    ///
+    /// Apps that embed several webview "mini-apps" can give each its own
+    /// namespace. Under a custom name:
+    ///
+    /// - commands are invoked as `plugin:<name>|<command>`; call
+    ///   `setPluginName(name)` from the guest bindings before anything else,
+    /// - events, such as forwarded records, are emitted on `<name>://` channels
+    ///   instead of `tracing://`,
+    /// - log files without an explicit file name use `name` as their prefix
+    ///   instead of `app`.
+    ///
+    /// The permissions shipped with this crate only cover the `tracing` name,
+    /// so the app must declare the commands of the chosen name itself, in its
+    /// build script, and grant them as `<name>:allow-<command>`:
+    ///
+    /// ```rust,ignore
+    /// // build.rs
+    /// tauri_build::try_build(tauri_build::Attributes::new().plugin(
+    ///     "tracing-admin",
+    ///     tauri_build::InlinedPlugin::new().commands(&["log", "log_batch", "webview_ready"]),
+    /// ))
+    /// .expect("failed to run tauri-build");
+    /// ```
+    ///
+    /// Tauri manages plugin state by type, so instances registered in the same
+    /// app share their state, such as the log history and open operations, and
+    /// events are emitted under the name of the instance registered first.
+    /// Only one instance can set the default subscriber. Records from each
+    /// webview are still kept apart by the `w` field of their `log` span.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_plugin_name("tracing-admin")
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_plugin_name(mut self, name: &'static str) -> Self {
+        self.plugin_name = name;
+        self
+    }
+
     /// Sets the maximum log level.
     ///
     /// Events more verbose than this level will be filtered out.
@@ -501,6 +914,135 @@ impl Builder {
         self
     }
 
+    /// Sets the log level for a specific target in one build variant only.
+    ///
+    /// The variant is the one given to
+    /// [`with_build_info()`](Self::with_build_info), or else
+    /// [`BuildVariant::current()`]. Levels set for the app's variant take
+    /// precedence over those set with [`with_target()`](Self::with_target).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, BuildVariant, LevelFilter};
+    /// Builder::new()
+    ///     .with_target("my_app", LevelFilter::INFO)
+    ///     .with_variant_target(BuildVariant::Debug, "my_app", LevelFilter::TRACE);
+    /// ```
+    pub fn with_variant_target(
+        mut self,
+        variant: BuildVariant,
+        target: &str,
+        level: LevelFilter,
+    ) -> Self {
+        self.variant_targets
+            .push((variant, target.to_string(), level));
+        self
+    }
+
+    /// Sets the log level for targets matching a wildcard pattern.
+    ///
+    /// Patterns are made of `::`-separated segments. A segment that is only
+    /// `*` matches any number of segments, so `tao::*` matches `tao` and all
+    /// of its modules, and `*::hyper::*` matches `hyper` wherever it is
+    /// nested. A `*` within a segment matches any characters of that segment,
+    /// as in `tauri_plugin_*`.
+    ///
+    /// A target matching a pattern is filtered at the pattern's level rather
+    /// than the one from [`with_max_level()`](Self::with_max_level),
+    /// [`with_target()`](Self::with_target), [`filter_for()`](Self::filter_for),
+    /// or runtime changes through the [`FilterHandle`]. When several patterns
+    /// match a target, the most specific one is used: the one with the most
+    /// characters other than `*`, or the last added.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::DEBUG)
+    ///     .with_target_glob("tao::*", LevelFilter::WARN)
+    ///     .with_target_glob("*::hyper::*", LevelFilter::OFF);
+    /// ```
+    pub fn with_target_glob(mut self, pattern: &str, level: LevelFilter) -> Self {
+        self.target_globs.push((pattern.to_string(), level));
+        self
+    }
+
+    /// Sets the level of spans apart from the level of events.
+    ///
+    /// By default, spans and events are filtered alike. With a span level,
+    /// spans are recorded up to `level` while events keep the level from
+    /// [`with_max_level()`](Self::with_max_level), so the flamegraph, span
+    /// timing, and slow span layers can see `TRACE` spans while the outputs
+    /// only log `INFO` events. Levels set with [`with_target()`](Self::with_target)
+    /// and [`with_target_glob()`](Self::with_target_glob) still apply to
+    /// spans, but runtime changes through the [`FilterHandle`] only apply to
+    /// events.
+    ///
+    /// Outputs show the spans an event is in, and exporters like OTLP export
+    /// spans, at the span level.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::INFO)
+    ///     .with_span_level(LevelFilter::TRACE)
+    ///     .with_slow_span_threshold(Duration::from_millis(100))
+    ///     .with_default_subscriber();
+    /// ```
+    pub fn with_span_level(mut self, level: LevelFilter) -> Self {
+        self.span_level = Some(level);
+        self
+    }
+
+    /// Sets the filter of one output, replacing the filter configured with
+    /// [`with_max_level()`](Self::with_max_level) and
+    /// [`with_target()`](Self::with_target) for it.
+    ///
+    /// Each output filters events on its own, so a verbose log file does not
+    /// make stdout or the webview verbose as well. Both file targets share
+    /// the file output, and other fields of `target` are ignored. Runtime
+    /// changes through the [`FilterHandle`] do not apply to outputs with a
+    /// filter of their own.
+    ///
+    /// Layers that are not outputs, such as breadcrumbs, see every event
+    /// that any output records.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter, Target};
+    /// # use tracing_subscriber::filter::Targets;
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::INFO)
+    ///     .target(Target::LogDir { file_name: None })
+    ///     .filter_for(
+    ///         Target::LogDir { file_name: None },
+    ///         Targets::new()
+    ///             .with_default(LevelFilter::DEBUG)
+    ///             .with_target("my_app", LevelFilter::TRACE),
+    ///     )
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn filter_for(mut self, target: Target, filter: Targets) -> Self {
+        let output = filter::Output::of(&target);
+        self.layer_filters
+            .retain(|(existing, _)| filter::Output::of(existing) != output);
+        self.layer_filters.push((target, filter));
+        self
+    }
+
     /// Sets a custom filter function for metadata-based log filtering.
     ///
     /// The filter function receives the metadata for each log event and returns
@@ -595,268 +1137,1404 @@ impl Builder {
         self
     }
 
-    /// Enables colored output in the terminal.
-    ///
-    /// This adds ANSI color codes to log level indicators.
-    /// Only available when the `colored` feature is enabled.
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
-    #[cfg(feature = "colored")]
-    pub fn with_colors(mut self) -> Self {
-        self.builder = self.builder.with_ansi(true);
-        self.use_colors = true;
-        self
-    }
-
-    /// Enables file logging to the platform-standard log directory.
-    ///
-    /// Log files rotate daily with the naming pattern `app.YYYY-MM-DD.log`.
+    /// Warns about spans that stay open longer than `threshold`.
     ///
-    /// Platform log directories:
-    /// - **macOS**: `~/Library/Logs/{bundle_identifier}`
-    /// - **Linux**: `~/.local/share/{bundle_identifier}/logs`
-    /// - **Windows**: `%LOCALAPPDATA%/{bundle_identifier}/logs`
+    /// When a span closes after being entered for longer than the threshold in
+    /// total, a `WARN` event is emitted under the [`SLOW_SPAN_TARGET`] target
+    /// with the span name, its busy duration, and its parent chain. Time the
+    /// span is open but not entered, such as an async task waiting on I/O,
+    /// does not count. This catches UI-blocking backend work
+    /// without running a full profile.
     ///
-    /// This is a convenience method equivalent to calling
-    /// `.target(Target::LogDir { file_name: None })`.
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// For custom subscribers, add a [`SlowSpanLayer`] directly.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// # use std::time::Duration;
+    /// # use tauri_plugin_tracing::Builder;
     /// Builder::new()
-    ///     .with_max_level(LevelFilter::DEBUG)
-    ///     .with_file_logging()
+    ///     .with_slow_span_threshold(Duration::from_millis(100))
+    ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_file_logging(self) -> Self {
-        self.target(Target::LogDir { file_name: None })
+    pub fn with_slow_span_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_span_threshold = Some(threshold);
+        self
     }
 
-    /// Sets the rotation period for log files.
+    /// Records a CPU profile when a span stays entered longer than the
+    /// watchdog's threshold, so intermittent hangs are captured without
+    /// asking users to reproduce them.
     ///
-    /// This controls how often new log files are created. Only applies when
-    /// file logging is enabled.
+    /// Reports are saved as profiling sessions with the busy span as their
+    /// trigger; see [`list_profiling_sessions()`]. Register the profiling
+    /// plugin with [`init_profiling()`] as well.
+    ///
+    /// Only available when the `profiling` feature is enabled.
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// For custom subscribers, add the layer returned by
+    /// [`ProfilingWatchdog::start()`] directly.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tauri_plugin_tracing::{Builder, Rotation};
-    ///
+    /// # use std::time::Duration;
+    /// # use tauri_plugin_tracing::{Builder, ProfilingWatchdog};
     /// Builder::new()
-    ///     .with_file_logging()
-    ///     .with_rotation(Rotation::Hourly)  // Rotate every hour
+    ///     .with_profiling_watchdog(
+    ///         ProfilingWatchdog::new(Duration::from_secs(2))
+    ///             .with_cooldown(Duration::from_secs(3600)),
+    ///     )
+    ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
-        self.rotation = rotation;
+    #[cfg(feature = "profiling")]
+    pub fn with_profiling_watchdog(mut self, watchdog: ProfilingWatchdog) -> Self {
+        self.profiling_watchdog = Some(watchdog);
         self
     }
 
-    /// Sets the retention strategy for rotated log files.
+    /// Sets the span fields that span-aware profiles record, replacing
+    /// [`DEFAULT_SPAN_FIELDS`].
     ///
-    /// This controls how many old log files are kept. Cleanup happens when
-    /// the application starts.
+    /// Reports list instances of a span with different values of these
+    /// fields separately, e.g. `process_item{id=1}` and
+    /// `process_item{id=2}`.
+    ///
+    /// Only available when the `profiling` feature is enabled.
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// For custom subscribers, use [`SpanTimingLayer::with_fields()`].
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tauri_plugin_tracing::{Builder, RotationStrategy};
-    ///
+    /// # use tauri_plugin_tracing::Builder;
     /// Builder::new()
-    ///     .with_file_logging()
-    ///     .with_rotation_strategy(RotationStrategy::KeepSome(7))  // Keep 7 files
+    ///     .with_span_timing_fields(["id", "document"])
+    ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_rotation_strategy(mut self, strategy: RotationStrategy) -> Self {
-        self.rotation_strategy = strategy;
+    #[cfg(feature = "profiling")]
+    pub fn with_span_timing_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.span_timing_fields = Some(fields.into_iter().map(Into::into).collect());
         self
     }
 
-    /// Sets the maximum file size before rotating.
+    /// Keeps busy time statistics of each span name at all times, in
+    /// rolling windows of `window`.
     ///
-    /// When set, log files will rotate when they reach this size, in addition
-    /// to any time-based rotation configured via [`with_rotation()`](Self::with_rotation).
+    /// No events are stored, so this is cheap enough for production. Read
+    /// the statistics with [`get_span_stats()`], or `getSpanStats()` in
+    /// JavaScript, without starting a profile.
     ///
-    /// Use [`MaxFileSize`] for convenient size specification:
-    /// - `MaxFileSize::kb(100)` - 100 kilobytes
-    /// - `MaxFileSize::mb(10)` - 10 megabytes
-    /// - `MaxFileSize::gb(1)` - 1 gigabyte
+    /// Only available when the `profiling` feature is enabled.
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// For custom subscribers, use [`SpanTimingLayer::with_stats()`].
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tauri_plugin_tracing::{Builder, MaxFileSize};
-    ///
-    /// // Rotate when file reaches 10 MB
+    /// # use tauri_plugin_tracing::{Builder, DEFAULT_SPAN_STATS_WINDOW};
     /// Builder::new()
-    ///     .with_file_logging()
-    ///     .with_max_file_size(MaxFileSize::mb(10))
+    ///     .with_span_stats(DEFAULT_SPAN_STATS_WINDOW)
+    ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_max_file_size(mut self, size: MaxFileSize) -> Self {
-        self.max_file_size = Some(size);
+    #[cfg(feature = "profiling")]
+    pub fn with_span_stats(mut self, window: Duration) -> Self {
+        self.span_stats_window = Some(window);
         self
     }
 
-    /// Sets the timezone strategy for log timestamps.
+    /// Periodically logs process resource usage.
     ///
-    /// Controls whether timestamps are displayed in UTC or local time.
-    /// The default is [`TimezoneStrategy::Utc`].
+    /// Every `interval`, a background thread samples process RSS, CPU usage,
+    /// open file descriptors, and thread count, and logs them as structured
+    /// `INFO` fields under the [`RESOURCES_TARGET`] (`sys::resources`) target.
+    /// The latest [`ResourceSnapshot`] is available through the managed
+    /// [`ResourceMonitor`] state and is included in profiling reports.
+    ///
+    /// Unlike most builder options, this also applies to custom subscribers,
+    /// since the samples are ordinary tracing events.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tauri_plugin_tracing::{Builder, TimezoneStrategy};
-    ///
-    /// // Use local time for timestamps
+    /// # use std::time::Duration;
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
     /// Builder::new()
-    ///     .with_timezone_strategy(TimezoneStrategy::Local)
+    ///     .with_max_level(LevelFilter::INFO)
+    ///     .with_resource_monitor(Duration::from_secs(30))
+    ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_timezone_strategy(mut self, strategy: TimezoneStrategy) -> Self {
-        self.timezone_strategy = strategy;
+    pub fn with_resource_monitor(mut self, interval: Duration) -> Self {
+        self.resource_monitor_interval = Some(interval);
         self
     }
 
-    /// Sets the log output format style.
+    /// Logs a heartbeat every `interval`, and warns when the main thread or a
+    /// webview stops responding.
     ///
-    /// Controls the overall structure of log output. The default is [`LogFormat::Full`].
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// Each heartbeat is a compact `INFO` event under the [`HEARTBEAT_TARGET`]
+    /// (`app::heartbeat`) target. At each heartbeat, the plugin also checks
+    /// that the main thread runs the tasks posted to it, and pings the
+    /// webviews that call the JavaScript `startHeartbeat()` helper. Hangs are
+    /// logged as `WARN` events with the last known main thread activity; see
+    /// [`Heartbeat`].
+    ///
+    /// Like [`with_resource_monitor()`](Self::with_resource_monitor), this also
+    /// applies to custom subscribers.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tauri_plugin_tracing::{Builder, LogFormat};
-    ///
-    /// // Use compact format for shorter lines
+    /// # use std::time::Duration;
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
     /// Builder::new()
-    ///     .with_format(LogFormat::Compact)
+    ///     .with_max_level(LevelFilter::INFO)
+    ///     .with_heartbeat(Duration::from_secs(10))
     ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_format(mut self, format: LogFormat) -> Self {
-        self.log_format = format;
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat_interval = Some(interval);
         self
     }
 
-    /// Sets whether to include the source file path in log output.
+    /// Warns when the main thread's event loop is blocked for longer than
+    /// `threshold`.
     ///
-    /// When enabled, logs will show which file the log event originated from.
-    /// Default is `false`.
+    /// A background thread posts a task to the main thread several times per
+    /// threshold. Blocks are logged as `WARN` events under the
+    /// [`EVENT_LOOP_TARGET`] (`app::event_loop`) target with the `blocked_ms`
+    /// duration, and with the main thread's native backtrace when the
+    /// `main-thread-backtrace` feature is enabled; see [`EventLoopWatchdog`].
     ///
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// Unlike [`with_heartbeat()`](Self::with_heartbeat), which checks the
+    /// main thread once per heartbeat, this is meant for thresholds well
+    /// under a second, to find what makes the UI stutter. Like it, this also
+    /// applies to custom subscribers.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// # use tauri_plugin_tracing::Builder;
+    /// # use std::time::Duration;
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
     /// Builder::new()
-    ///     .with_file(true)
+    ///     .with_max_level(LevelFilter::INFO)
+    ///     .with_event_loop_watchdog(Duration::from_millis(250))
     ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_file(mut self, show: bool) -> Self {
-        self.show_file = show;
+    pub fn with_event_loop_watchdog(mut self, threshold: Duration) -> Self {
+        self.event_loop_threshold = Some(threshold);
         self
     }
 
-    /// Sets whether to include the source line number in log output.
+    /// Captures native output written directly to stdout and stderr.
     ///
-    /// When enabled, logs will show which line number the log event originated from.
-    /// Default is `false`.
+    /// Redirects the process's stdout and stderr file descriptors through pipes
+    /// and re-emits each captured line under the [`CAPTURED_STDOUT_TARGET`]
+    /// (`captured::stdout`) target at `INFO` or the [`CAPTURED_STDERR_TARGET`]
+    /// (`captured::stderr`) target at `WARN`. This catches output from C
+    /// libraries and `println!` debugging in dependencies, so nothing escapes
+    /// the log files.
     ///
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// Requires [`with_default_subscriber()`](Self::with_default_subscriber),
+    /// whose stdout and stderr targets keep writing to the original streams.
+    /// Without it, a warning is logged and output is left untouched, because
+    /// a layer of the app's subscriber that writes to [`std::io::stderr`]
+    /// would capture its own output forever. Layers added with
+    /// [`with_layer()`](Self::with_layer) must likewise write to the writers
+    /// from the managed [`OutputCapture`] state.
+    ///
+    /// Only supported on Unix platforms; elsewhere a warning is logged and
+    /// output is left untouched.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use tauri_plugin_tracing::Builder;
     /// Builder::new()
-    ///     .with_line_number(true)
+    ///     .with_output_capture()
+    ///     .with_file_logging()
     ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_line_number(mut self, show: bool) -> Self {
-        self.show_line_number = show;
+    pub fn with_output_capture(mut self) -> Self {
+        self.capture_output = true;
         self
     }
 
-    /// Sets whether to include the current thread ID in log output.
+    /// Persists runtime filter changes across app restarts.
     ///
-    /// When enabled, logs will show the ID of the thread that emitted the event.
-    /// Default is `false`.
+    /// Level changes made through [`FilterHandle`] or the `set_log_level` and
+    /// `set_target_level` commands are saved to [`FILTER_FILE_NAME`] in the
+    /// app config directory, and restored at the next startup. Calling
+    /// [`FilterHandle::reset()`] or the `reset_log_filter` command deletes the
+    /// file.
     ///
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use tauri_plugin_tracing::Builder;
     /// Builder::new()
-    ///     .with_thread_ids(true)
+    ///     .with_filter_persistence()
     ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_thread_ids(mut self, show: bool) -> Self {
-        self.show_thread_ids = show;
+    pub fn with_filter_persistence(mut self) -> Self {
+        self.persist_filter = true;
         self
     }
 
-    /// Sets whether to include the current thread name in log output.
+    /// Counts the events dropped by the filter, by target.
     ///
-    /// When enabled, logs will show the name of the thread that emitted the event.
-    /// Default is `false`.
+    /// The counts are read with [`get_filter_stats()`] or the
+    /// `get_filter_stats` command, to tune levels with data. Filtered events
+    /// are then checked when they happen rather than skipped ahead of time,
+    /// which costs more; see [`DroppedEvents`].
     ///
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// # use tauri_plugin_tracing::Builder;
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
     /// Builder::new()
-    ///     .with_thread_names(true)
+    ///     .with_max_level(LevelFilter::INFO)
+    ///     .with_filter_stats()
     ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_thread_names(mut self, show: bool) -> Self {
-        self.show_thread_names = show;
+    pub fn with_filter_stats(mut self) -> Self {
+        self.filter_stats = true;
         self
     }
 
-    /// Sets whether to include the log target (module path) in log output.
+    /// Lets Unix signals change the log filter of the running app.
     ///
-    /// When enabled, logs will show which module/target emitted the event.
-    /// Default is `true`.
+    /// `SIGUSR2` toggles debug logging and `SIGHUP` re-reads the filter
+    /// persisted with [`with_filter_persistence()`](Self::with_filter_persistence),
+    /// so packaged apps can be debugged without restarting them from a
+    /// terminal. Handling `SIGHUP` means it no longer terminates the app.
     ///
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// Only applies on Linux and macOS, with
+    /// [`with_default_subscriber()`](Self::with_default_subscriber).
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use tauri_plugin_tracing::Builder;
-    /// // Disable target display for cleaner output
     /// Builder::new()
-    ///     .with_target_display(false)
+    ///     .with_signal_control()
     ///     .with_default_subscriber()
     ///     .build::<tauri::Wry>();
     /// ```
-    pub fn with_target_display(mut self, show: bool) -> Self {
-        self.show_target = show;
+    pub fn with_signal_control(mut self) -> Self {
+        self.signal_control = true;
         self
     }
 
-    /// Sets whether to include the log level in log output.
+    /// Limits the length of messages received from the frontend.
     ///
-    /// When enabled, logs will show the severity level (TRACE, DEBUG, INFO, etc.).
-    /// Default is `true`.
+    /// Messages longer than `max_len` bytes are cut short and marked with a
+    /// trailing `…`, so a frontend accidentally logging a multi-megabyte
+    /// string cannot bloat log files or the webview echo. Structured `args`
+    /// longer than `max_len` have their strings shortened, so they stay valid
+    /// JSON, or are dropped. Truncated records carry a `truncated = true`
+    /// field.
     ///
-    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// The guest bindings also shorten messages before sending them, so
+    /// oversized records do not cross the IPC bridge.
+    ///
+    /// By default, messages are not limited.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use tauri_plugin_tracing::Builder;
-    /// // Disable level display
+    /// Builder::new()
+    ///     .with_max_message_len(64 * 1024)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_max_message_len(mut self, max_len: usize) -> Self {
+        self.max_message_len = Some(max_len);
+        self
+    }
+
+    /// Limits the number of call stack frames kept for frontend records.
+    ///
+    /// Frames beyond the first `max_frames` are dropped before the call site
+    /// is derived. The `Error` line that starts V8 stacks is not counted as a
+    /// frame. Records whose stack was cut carry a `truncated = true` field.
+    ///
+    /// The guest bindings also cut stacks before sending them.
+    ///
+    /// By default, call stacks are not limited.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_max_stack_frames(50)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_max_stack_frames(mut self, max_frames: usize) -> Self {
+        self.max_stack_frames = Some(max_frames);
+        self
+    }
+
+    /// Keeps only one in every N events for a high-volume target.
+    ///
+    /// Events whose target starts with `target` are sampled deterministically
+    /// per callsite before they reach any output, and kept events carry a
+    /// `sampled=1/N` field. This lets render and I/O loops stay instrumented
+    /// in production without drowning the logs. `WARN` and `ERROR` events are
+    /// never sampled. When several rules match, the most specific target wins.
+    ///
+    /// For custom subscribers, add the layer from
+    /// [`build_sampling_layer()`](Self::build_sampling_layer).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter, SampleRate};
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::DEBUG)
+    ///     .with_sampling("my_app::render", SampleRate::OneIn(100))
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_sampling(mut self, target: impl Into<String>, rate: SampleRate) -> Self {
+        self.sampling.push((target.into(), rate));
+        self
+    }
+
+    /// Attaches the last `capacity` events to every `ERROR` event.
+    ///
+    /// `DEBUG` and higher events that pass the filter are kept in a rolling
+    /// buffer. When an error is recorded, from the backend or a webview, the
+    /// buffer is appended to it as a `breadcrumbs=[...]` JSON field and sent
+    /// to the frontend as the payload's `breadcrumbs`. Custom layers added
+    /// with [`with_layer()`](Self::with_layer) can read it with
+    /// [`current_breadcrumbs()`]. See [`BreadcrumbLayer`].
+    ///
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::DEBUG)
+    ///     .with_breadcrumbs(20)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_breadcrumbs(mut self, capacity: usize) -> Self {
+        self.breadcrumbs = Some(capacity);
+        self
+    }
+
+    /// Numbers every event that passes the filter, across all outputs.
+    ///
+    /// The plugin's formatters append the number as a `seq=N` field, the
+    /// other outputs record it like an event field, and webview payloads
+    /// carry it as `seq`, so records can be matched across outputs and
+    /// missing numbers show that records were lost. See [`SequenceLayer`].
+    ///
+    /// For custom subscribers, add a [`SequenceLayer`] after the output
+    /// layers.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_sequence_numbers()
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_sequence_numbers(mut self) -> Self {
+        self.sequence_numbers = true;
+        self
+    }
+
+    /// Registers a callback that translates the messages of records sent to
+    /// webviews.
+    ///
+    /// Apps that show logs to end users can replace known messages with
+    /// localized or friendlier ones. The callback receives each record
+    /// forwarded to the frontend, with its level and fields, such as an
+    /// `event_id` from [`log_id!`](crate::log_id), and returns the message to
+    /// show instead, or `None` to keep the original. Files, the terminal, and
+    /// the other outputs always record the original message.
+    ///
+    /// Applies to the webview target and to [`WebviewLayer::new()`] in custom
+    /// subscribers. The callback runs on the thread that logs, so it should
+    /// be quick.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_default_subscriber()
+    ///     .with_message_translator(|record| match record.fields.get("event_id")?.as_str() {
+    ///         "E1001" => Some("Impossible de joindre le serveur".to_string()),
+    ///         _ => None,
+    ///     })
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_message_translator<F>(mut self, translate: F) -> Self
+    where
+        F: Fn(&RecordPayload) -> Option<String> + Send + Sync + 'static,
+    {
+        self.message_translator = Some(MessageTranslator::new(translate));
+        self
+    }
+
+    /// Emits a [`UserNotification`] as the [`NOTIFY_EVENT`]
+    /// (`tracing://notify`) event for the events selected by `notifications`,
+    /// so the frontend can show backend errors to the user, e.g. with the
+    /// JavaScript `onNotification()` helper.
+    ///
+    /// Events marked with a `notify = true` field are always notified, and
+    /// [`Notifications::errors()`] also notifies every `ERROR` event.
+    /// Notifications are rate-limited; see [`Notifications`].
+    ///
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    /// For custom subscribers, add the layer from [`Notifications::layer()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, Notifications};
+    /// Builder::new()
+    ///     .with_notifications(Notifications::errors())
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    ///
+    /// // Shown to the user with the title "Could not save"
+    /// tracing::warn!(notify = true, title = "Could not save", "the disk is full");
+    /// ```
+    pub fn with_notifications(mut self, notifications: Notifications) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Logs backend events to the devtools console of every webview in dev
+    /// builds.
+    ///
+    /// Each event is evaluated as a `console.*` call at its level, so backend
+    /// records appear interleaved with the frontend's in the browser
+    /// devtools, including those logged before the page could call
+    /// `attachConsole()`. Records are filtered like those of the
+    /// [`Target::Webview`] output. Nothing is evaluated in release builds,
+    /// see [`tauri::is_dev()`], or for records that came from a webview. See
+    /// [`DevtoolsConsoleLayer`].
+    ///
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::DEBUG)
+    ///     .with_devtools_console()
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_devtools_console(mut self) -> Self {
+        self.devtools_console = true;
+        self
+    }
+
+    /// Keeps the last `capacity` events in memory for [`logs_around_mark()`].
+    ///
+    /// Events that pass the filter are kept in a [`LogHistory`], which the
+    /// plugin manages in Tauri state. Markers that have left the history are
+    /// looked up in the log files instead.
+    ///
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_log_history(1000)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_log_history(mut self, capacity: usize) -> Self {
+        self.log_history = Some(capacity);
+        self
+    }
+
+    /// Captures the logs of each end-to-end test delimited by the
+    /// `begin_test` and `end_test` commands.
+    ///
+    /// The events of each test are written to `tests/{name}.log` in the app
+    /// log directory, and returned by `end_test`, so WebDriver suites can
+    /// attach them to failing tests; see [`TestCapture`]. Enable it only in
+    /// test builds.
+    ///
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// let mut builder = Builder::new().with_default_subscriber();
+    /// if std::env::var_os("E2E").is_some() {
+    ///     builder = builder.with_test_mode();
+    /// }
+    /// builder.build::<tauri::Wry>();
+    /// ```
+    pub fn with_test_mode(mut self) -> Self {
+        self.test_mode = true;
+        self
+    }
+
+    /// Uses `context` as the [`LogContext`] the plugin manages in Tauri
+    /// state.
+    ///
+    /// The plugin creates its own by default. Pass the instance added to a
+    /// custom subscriber so that `setContext()` and `clearContext()` from
+    /// JavaScript change it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LogContext};
+    /// let context = LogContext::new();
+    /// context.set("session_id", "8f3c");
+    /// Builder::new()
+    ///     .with_log_context(context)
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_log_context(mut self, context: LogContext) -> Self {
+        self.log_context = context;
+        self
+    }
+
+    /// Logs page loads, reloads, and window focus changes as `INFO` events,
+    /// so logs show which screen the user was on when an error occurred.
+    ///
+    /// Events are recorded under [`NAVIGATION_TARGET`] with the window label,
+    /// and appear in breadcrumb trails with
+    /// [`with_breadcrumbs()`](Self::with_breadcrumbs). See
+    /// [`NavigationBreadcrumbs`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::INFO)
+    ///     .with_navigation_breadcrumbs()
+    ///     .with_breadcrumbs(20)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_navigation_breadcrumbs(mut self) -> Self {
+        self.navigation_breadcrumbs = true;
+        self
+    }
+
+    /// Opens a long-lived `route` span for each client-side route a window
+    /// reports with `setCurrentRoute()`, and logs the window's records in it,
+    /// so their spans show the screen and its duration the time spent on it.
+    ///
+    /// Without this option, the route is only recorded on the `log` span of
+    /// each record. See [`CurrentRoutes`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_route_spans()
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_route_spans(mut self) -> Self {
+        self.route_spans = true;
+        self
+    }
+
+    /// Emits progress updates to the app as [`PROGRESS_EVENT`] events, at
+    /// most 10 times per second for each task, so the UI can show progress
+    /// bars for tasks reported with [`progress()`].
+    ///
+    /// Without this option, progress is only logged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_progress_events()
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_progress_events(mut self) -> Self {
+        self.progress_events = true;
+        self
+    }
+
+    /// Registers a catalog of known events, declared with [`event_ids!`].
+    ///
+    /// The catalog is included in diagnostic reports, so support can look up
+    /// the ids of events logged with [`log_id!`]. Calling this again adds
+    /// more events.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, event_ids};
+    /// event_ids! {
+    ///     pub E1001: ERROR = "db connection failed";
+    ///     pub W2001: WARN = "sync retried";
+    /// }
+    ///
+    /// Builder::new()
+    ///     .with_event_catalog([E1001, W2001])
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_event_catalog(mut self, events: impl IntoIterator<Item = EventId>) -> Self {
+        self.event_catalog.extend(events);
+        self
+    }
+
+    /// Emits metrics recorded with [`gauge()`] and [`counter()`] to the app
+    /// as [`METRICS_EVENT`] events, batched every `interval`, so the UI can
+    /// show live dashboards.
+    ///
+    /// Without this option, metrics are only logged, at `TRACE` under
+    /// [`METRICS_TARGET`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_metrics_events(Duration::from_millis(500))
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_metrics_events(mut self, interval: Duration) -> Self {
+        self.metrics_interval = Some(interval);
+        self
+    }
+
+    /// Logs how the app was launched: the process arguments and working
+    /// directory at startup, and on macOS and iOS the URLs it is opened with.
+    ///
+    /// Values of the `sensitive_args` flags, and URL query parameters of the
+    /// same name, are redacted. Events are recorded under [`LAUNCH_TARGET`].
+    /// The plugin manages a [`LaunchLog`] to log deep links and arguments
+    /// forwarded by a second instance.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_launch_logging(["--token", "--password"])
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_launch_logging<I, S>(mut self, sensitive_args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.launch_logging = Some(sensitive_args.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Also writes [`UpdateLog`] events to `{file_name}.log`, at every level
+    /// and regardless of the filter of other outputs.
+    ///
+    /// The file is created in the directory of the file target, or the app
+    /// log directory without one, and is never rotated.
+    ///
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_update_log_file("updates")
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_update_log_file(mut self, file_name: impl Into<String>) -> Self {
+        self.update_log_file = Some(file_name.into());
+        self
+    }
+
+    /// Logs events from `target` and its submodules under `category` in
+    /// Apple's unified logging system.
+    ///
+    /// By default, the category is the target's second path segment, e.g.
+    /// `sync` for `my_app::sync`. Use this to group modules differently in
+    /// Console.app. The longest matching target wins.
+    ///
+    /// Only applies to [`Target::OsLog`] with
+    /// [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, Target};
+    /// Builder::new()
+    ///     .target(Target::OsLog { subsystem: None })
+    ///     .with_os_log_category("my_app::db", "storage")
+    ///     .with_os_log_category("my_app::cache", "storage")
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_os_log_category(
+        mut self,
+        target: impl Into<String>,
+        category: impl Into<String>,
+    ) -> Self {
+        self.os_log_categories
+            .push((target.into(), category.into()));
+        self
+    }
+
+    /// Annotates every record sent to the remote targets and written in the
+    /// RFC 5424 format with app-level metadata, such as the environment.
+    ///
+    /// Attributes are added to:
+    ///
+    /// - The resource of [`Target::Otlp`] records, where `service.name`,
+    ///   `service.version`, and `os.type` have defaults.
+    /// - Each JSON log line of [`Target::Loki`], as top-level fields.
+    /// - Each [`Target::Gelf`] message, as additional fields.
+    /// - Each [`LogFormat::Rfc5424`] line, as the [`RFC5424_RESOURCE_SD_ID`]
+    ///   structured data element.
+    ///
+    /// Event fields with the same name take precedence. Calling this again
+    /// adds more attributes.
+    ///
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_resource_attributes([
+    ///         ("service.name", "my-app"),
+    ///         ("deployment.environment", "prod"),
+    ///     ])
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_resource_attributes<K, V>(
+        mut self,
+        attributes: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.resource_attributes.extend(
+            attributes
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
+    /// Describes the app's build, usually captured with [`build_info!`].
+    ///
+    /// The build variant, git hash, and enabled features are added to the
+    /// resource attributes as `build.variant`, `build.git_hash`, and
+    /// `build.features`, see
+    /// [`with_resource_attributes()`](Self::with_resource_attributes). The
+    /// variant also selects the levels set with
+    /// [`with_variant_target()`](Self::with_variant_target).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, build_info};
+    /// Builder::new()
+    ///     .with_build_info(build_info!())
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_build_info(mut self, build_info: BuildInfo) -> Self {
+        self.build_info = Some(build_info);
+        self
+    }
+
+    /// Sets a resource attribute of the records exported by [`Target::Otlp`]
+    /// only.
+    ///
+    /// Resource attributes describe the app to the backend. `service.name`
+    /// and `service.version` default to the app's package name and version,
+    /// and `os.type` to the platform, see [`os_type()`]; setting them here or
+    /// with [`with_resource_attributes()`](Self::with_resource_attributes)
+    /// replaces the defaults, and attributes set here take precedence. Other
+    /// useful attributes include `deployment.environment.name` and
+    /// `service.instance.id`.
+    ///
+    /// Only available when the `otlp` feature is enabled.
+    /// Only applies with [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, Target};
+    /// Builder::new()
+    ///     .target(Target::Otlp {
+    ///         url: "https://otel.example.com:4318/v1/logs".to_string(),
+    ///         headers: Vec::new(),
+    ///     })
+    ///     .with_otlp_resource_attribute("service.name", "my-app-desktop")
+    ///     .with_otlp_resource_attribute("deployment.environment.name", "beta")
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    #[cfg(feature = "otlp")]
+    pub fn with_otlp_resource_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.otlp_resource.push((key.into(), value.into()));
+        self
+    }
+
+    /// Signs the manifest of diagnostic reports with HMAC-SHA256 and `key`.
+    ///
+    /// The signature is written to `manifest.sig`, and
+    /// [`verify_log_bundle()`] checks it with the same key, so support can
+    /// tell whether a report's logs were edited after it was created. Keep
+    /// the key out of the frontend, e.g. by embedding it at build time.
+    ///
+    /// This only catches accidental or casual modification: anyone holding
+    /// the app's binary can extract the key and sign a forged manifest.
+    ///
+    /// Only available when the `diagnostics` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     // Set by the release pipeline, outside the repository
+    ///     .with_report_signing_key(option_env!("REPORT_SIGNING_KEY").unwrap_or_default())
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    #[cfg(feature = "diagnostics")]
+    pub fn with_report_signing_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.report_signing_key = Some(key.into());
+        self
+    }
+
+    /// Serves a JSON API on `127.0.0.1`, on a random port, for test harnesses
+    /// that drive the app from outside and for diagnostics.
+    ///
+    /// The endpoint reports the app's health, returns the last log lines,
+    /// reads and changes the filter, and starts and stops profiling; see
+    /// [`DiagnosticsEndpoint`]. Every request needs an
+    /// `Authorization: Bearer <token>` header with `token`, such as one the
+    /// harness passes in an environment variable. An empty token is an
+    /// error, and the endpoint is not started.
+    ///
+    /// Only available when the `diagnostics-endpoint` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// let mut builder = Builder::new().with_default_subscriber();
+    /// if let Ok(token) = std::env::var("E2E_ENDPOINT_TOKEN") {
+    ///     builder = builder.with_diagnostics_endpoint(token);
+    /// }
+    /// builder.build::<tauri::Wry>();
+    /// ```
+    #[cfg(feature = "diagnostics-endpoint")]
+    pub fn with_diagnostics_endpoint(mut self, token: impl Into<String>) -> Self {
+        self.diagnostics_endpoint = Some(token.into());
+        self
+    }
+
+    /// Enables colored output in the terminal.
+    ///
+    /// This adds ANSI color codes to log level indicators.
+    /// Only available when the `colored` feature is enabled.
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    #[cfg(feature = "colored")]
+    pub fn with_colors(mut self) -> Self {
+        self.builder = self.builder.with_ansi(true);
+        self.use_colors = true;
+        self
+    }
+
+    /// Enables colored output in the terminal with the colors of `theme`.
+    ///
+    /// Themes change the level colors, whether timestamps, targets, and
+    /// separators are dimmed, and how field names are highlighted, in every
+    /// [`LogFormat`] except [`LogFormat::Rfc5424`].
+    /// [`ColorTheme::high_contrast()`] and [`ColorTheme::no_dim()`] are
+    /// easier to read in some terminals.
+    ///
+    /// Only available when the `colored` feature is enabled.
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, ColorTheme};
+    /// Builder::new()
+    ///     .with_color_theme(ColorTheme::high_contrast())
+    ///     .with_default_subscriber();
+    /// ```
+    #[cfg(feature = "colored")]
+    pub fn with_color_theme(mut self, theme: ColorTheme) -> Self {
+        self.color_theme = Some(theme);
+        self.with_colors()
+    }
+
+    /// Enables colored output in the terminal with the source location of
+    /// each event linked to the file, opened by `scheme`.
+    ///
+    /// Shows the file and line number of events, see
+    /// [`with_file()`](Self::with_file) and
+    /// [`with_line_number()`](Self::with_line_number), as OSC 8 hyperlinks,
+    /// so clicking a log line in a supporting terminal such as iTerm2,
+    /// WezTerm, Windows Terminal, or the VS Code terminal jumps to the source.
+    /// Other terminals show the location as plain text. Meant for
+    /// development builds.
+    ///
+    /// Only available when the `colored` feature is enabled.
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, EditorScheme};
+    /// let builder = Builder::new();
+    /// #[cfg(debug_assertions)]
+    /// let builder = builder.with_editor_links(EditorScheme::VsCode);
+    /// builder.with_default_subscriber();
+    /// ```
+    #[cfg(feature = "colored")]
+    pub fn with_editor_links(mut self, scheme: EditorScheme) -> Self {
+        self.editor_links = Some(scheme);
+        self.show_file = true;
+        self.show_line_number = true;
+        self.with_colors()
+    }
+
+    /// Enables file logging to the platform-standard log directory.
+    ///
+    /// Log files rotate daily with the naming pattern `app.YYYY-MM-DD.log`.
+    ///
+    /// Platform log directories:
+    /// - **macOS**: `~/Library/Logs/{bundle_identifier}`
+    /// - **Linux**: `~/.local/share/{bundle_identifier}/logs`
+    /// - **Windows**: `%LOCALAPPDATA%/{bundle_identifier}/logs`
+    ///
+    /// This is a convenience method equivalent to calling
+    /// `.target(Target::LogDir { file_name: None })`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// Builder::new()
+    ///     .with_max_level(LevelFilter::DEBUG)
+    ///     .with_file_logging()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_file_logging(self) -> Self {
+        self.target(Target::LogDir { file_name: None })
+    }
+
+    /// Sets the rotation period for log files.
+    ///
+    /// This controls how often new log files are created. Only applies when
+    /// file logging is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, Rotation};
+    ///
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_rotation(Rotation::Hourly)  // Rotate every hour
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Sets the retention strategy for rotated log files.
+    ///
+    /// This controls how many old log files are kept. Cleanup happens when
+    /// the application starts.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, RotationStrategy};
+    ///
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_rotation_strategy(RotationStrategy::KeepSome(7))  // Keep 7 files
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_rotation_strategy(mut self, strategy: RotationStrategy) -> Self {
+        self.rotation_strategy = strategy;
+        self
+    }
+
+    /// Sets how file logging behaves when several instances of the app run
+    /// at once.
+    ///
+    /// By default all instances write to the same file, where their lines can
+    /// interleave. See [`InstanceStrategy`] for the alternatives. With
+    /// per-instance files, the retention strategy counts the files of all
+    /// instances, keeping the most recently modified.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, InstanceStrategy};
+    ///
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_instance_strategy(InstanceStrategy::ProcessId)
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_instance_strategy(mut self, strategy: InstanceStrategy) -> Self {
+        self.instance_strategy = strategy;
+        self
+    }
+
+    /// Sets how log files of different app versions are kept apart.
+    ///
+    /// By default all versions write to the same files. See
+    /// [`VersionLayout`] for the alternatives. The retention strategy then
+    /// counts the files of all versions, keeping the most recently modified,
+    /// so an update does not reset it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, RotationStrategy, VersionLayout};
+    ///
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_version_layout(VersionLayout::Directory)  // logs/1.4.2/app.2024-01-15.log
+    ///     .with_rotation_strategy(RotationStrategy::KeepSome(14))
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_version_layout(mut self, layout: VersionLayout) -> Self {
+        self.version_layout = layout;
+        self
+    }
+
+    /// Names log files from a template, such as
+    /// `"{prefix}.{date}.{hostname}.log"`.
+    ///
+    /// Templates can use the `{prefix}`, `{date}`, `{hostname}`, `{pid}`,
+    /// `{version}` and `{instance}` placeholders. `{date}` must be a
+    /// dot-separated part of its own, neither first nor last, as the file
+    /// appender writes the date between dots, and templates must end in
+    /// `.log`. An invalid template is reported by [`validate()`](Self::validate)
+    /// and at startup, and the default names are used instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, InstanceStrategy};
+    ///
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_instance_strategy(InstanceStrategy::ProcessId)
+    ///     // app.2024-01-15.build-07.4242.log
+    ///     .with_file_name_template("{prefix}.{date}.{hostname}.{instance}.log")
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_file_name_template(mut self, template: impl Into<String>) -> Self {
+        self.file_name_template = Some(template.into());
+        self
+    }
+
+    /// Sets how often log files are synced to disk.
+    ///
+    /// By default syncing is left to the operating system, so the last
+    /// records before a power loss or OS crash can be lost. See
+    /// [`SyncPolicy`] for the alternatives. Syncing happens on the file
+    /// writer's background thread, so it slows down how fast records are
+    /// written out, not the code that logs them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use tauri_plugin_tracing::{Builder, SyncPolicy};
+    ///
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_sync_policy(SyncPolicy::Interval(Duration::from_secs(1)))
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_sync_policy(mut self, policy: SyncPolicy) -> Self {
+        self.sync_policy = policy;
+        self
+    }
+
+    /// Sets the maximum file size before rotating.
+    ///
+    /// When set, log files will rotate when they reach this size, in addition
+    /// to any time-based rotation configured via [`with_rotation()`](Self::with_rotation).
+    ///
+    /// Use [`MaxFileSize`] for convenient size specification:
+    /// - `MaxFileSize::kb(100)` - 100 kilobytes
+    /// - `MaxFileSize::mb(10)` - 10 megabytes
+    /// - `MaxFileSize::gb(1)` - 1 gigabyte
+    ///
+    /// Requires the `rotation` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, MaxFileSize};
+    ///
+    /// // Rotate when file reaches 10 MB
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_max_file_size(MaxFileSize::mb(10))
+    ///     .build::<tauri::Wry>();
+    /// ```
+    #[cfg(feature = "rotation")]
+    pub fn with_max_file_size(mut self, size: MaxFileSize) -> Self {
+        self.max_file_size = Some(size);
+        self
+    }
+
+    /// Registers a callback that runs when the log file rotates.
+    ///
+    /// The callback receives the path of the file that was just finished and
+    /// the path of the new file, e.g. to upload or compress the finished file.
+    /// It runs on a background task, so it may block without delaying logging.
+    ///
+    /// With [`with_max_file_size()`](Self::with_max_file_size), the finished
+    /// file is the renamed `app.log.1`. If the rotation strategy keeps a single
+    /// file, it may already have been deleted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, Rotation};
+    ///
+    /// Builder::new()
+    ///     .with_file_logging()
+    ///     .with_rotation(Rotation::Hourly)
+    ///     .on_rotation(|old_path, new_path| {
+    ///         println!("finished {}, now writing {}", old_path.display(), new_path.display());
+    ///     })
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn on_rotation<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&std::path::Path, &std::path::Path) + Send + Sync + 'static,
+    {
+        self.rotation_hook = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Sets the timezone strategy for log timestamps.
+    ///
+    /// Controls whether timestamps are displayed in UTC or local time.
+    /// The default is [`TimezoneStrategy::Utc`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, TimezoneStrategy};
+    ///
+    /// // Use local time for timestamps
+    /// Builder::new()
+    ///     .with_timezone_strategy(TimezoneStrategy::Local)
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_timezone_strategy(mut self, strategy: TimezoneStrategy) -> Self {
+        self.timezone_strategy = strategy;
+        self
+    }
+
+    /// Sets the log output format style.
+    ///
+    /// Controls the overall structure of log output. The default is [`LogFormat::Full`].
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, LogFormat};
+    ///
+    /// // Use compact format for shorter lines
+    /// Builder::new()
+    ///     .with_format(LogFormat::Compact)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.log_format = format;
+        self
+    }
+
+    /// Sets whether to include the source file path in log output.
+    ///
+    /// When enabled, logs will show which file the log event originated from.
+    /// Default is `false`.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_file(true)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_file(mut self, show: bool) -> Self {
+        self.show_file = show;
+        self
+    }
+
+    /// Sets whether to include the source line number in log output.
+    ///
+    /// When enabled, logs will show which line number the log event originated from.
+    /// Default is `false`.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_line_number(true)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_line_number(mut self, show: bool) -> Self {
+        self.show_line_number = show;
+        self
+    }
+
+    /// Sets whether to include the current thread ID in log output.
+    ///
+    /// When enabled, logs will show the ID of the thread that emitted the event.
+    /// Default is `false`.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_thread_ids(true)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_thread_ids(mut self, show: bool) -> Self {
+        self.show_thread_ids = show;
+        self
+    }
+
+    /// Sets whether to include the current thread name in log output.
+    ///
+    /// When enabled, logs will show the name of the thread that emitted the event.
+    /// Default is `false`.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// Builder::new()
+    ///     .with_thread_names(true)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_thread_names(mut self, show: bool) -> Self {
+        self.show_thread_names = show;
+        self
+    }
+
+    /// Sets whether to include the log target (module path) in log output.
+    ///
+    /// When enabled, logs will show which module/target emitted the event.
+    /// Default is `true`.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// // Disable target display for cleaner output
+    /// Builder::new()
+    ///     .with_target_display(false)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_target_display(mut self, show: bool) -> Self {
+        self.show_target = show;
+        self
+    }
+
+    /// Sets whether to include the log level in log output.
+    ///
+    /// When enabled, logs will show the severity level (TRACE, DEBUG, INFO, etc.).
+    /// Default is `true`.
+    ///
+    /// Only applies when using [`with_default_subscriber()`](Self::with_default_subscriber).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::Builder;
+    /// // Disable level display
     /// Builder::new()
     ///     .with_level(false)
     ///     .with_default_subscriber()
@@ -936,6 +2614,29 @@ impl Builder {
         self
     }
 
+    /// Writes the events of [`Target::Stdout`] at `threshold` or more severe
+    /// to stderr instead, so `WARN` and `ERROR` events can be told apart from
+    /// the rest by tools that wrap the app, like CLI wrappers or CI harnesses.
+    ///
+    /// Has no effect without [`Target::Stdout`]. A [`Target::Stderr`] still
+    /// receives every event.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::{Builder, LevelFilter};
+    ///
+    /// // INFO and below to stdout, WARN and ERROR to stderr
+    /// Builder::new()
+    ///     .with_stderr_threshold(LevelFilter::WARN)
+    ///     .with_default_subscriber()
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn with_stderr_threshold(mut self, threshold: LevelFilter) -> Self {
+        self.stderr_threshold = Some(threshold);
+        self
+    }
+
     /// Enables the plugin to set up and register the global tracing subscriber.
     ///
     /// By default, this plugin does **not** call [`tracing::subscriber::set_global_default()`],
@@ -945,6 +2646,12 @@ impl Builder {
     /// Call this method if you want the plugin to handle all tracing setup for you,
     /// using the configuration from this builder (log levels, targets, file logging, etc.).
     ///
+    /// If the app has already set a subscriber, e.g. with
+    /// [early initialization](crate#early-initialization), the plugin fails to
+    /// initialize with [`Error::SubscriberAlreadySet`]. Use
+    /// [`try_with_default_subscriber()`](Self::try_with_default_subscriber) to
+    /// keep the app's subscriber instead.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -965,6 +2672,36 @@ impl Builder {
         self
     }
 
+    /// Like [`with_default_subscriber()`](Self::with_default_subscriber), but
+    /// keeps a subscriber the app has already set.
+    ///
+    /// In that case, the plugin logs a warning and only provides its commands
+    /// and layers, as without `with_default_subscriber()`. Add
+    /// [`WebviewLayer`] and [`build_filter()`](Self::build_filter) to the app's
+    /// subscriber to forward logs to the webview.
+    ///
+    /// Use this in libraries and templates that cannot know whether the app
+    /// sets up tracing itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter};
+    /// tauri::Builder::default()
+    ///     .plugin(
+    ///         Builder::new()
+    ///             .with_max_level(LevelFilter::DEBUG)
+    ///             .try_with_default_subscriber()
+    ///             .build()
+    ///     );
+    ///     // .run(tauri::generate_context!("examples/default-subscriber/src-tauri/tauri.conf.json"))
+    /// ```
+    pub fn try_with_default_subscriber(mut self) -> Self {
+        self.set_default_subscriber = true;
+        self.subscriber_fallback = true;
+        self
+    }
+
     /// Returns the configured log output targets.
     ///
     /// Use this when setting up your own subscriber to determine which
@@ -984,6 +2721,7 @@ impl Builder {
     ///         Target::Stderr => { /* add stderr layer */ }
     ///         Target::Webview => { /* add WebviewLayer */ }
     ///         Target::LogDir { .. } | Target::Folder { .. } => { /* add file layer */ }
+    ///         _ => { /* other targets */ }
     ///     }
     /// }
     /// ```
@@ -991,19 +2729,287 @@ impl Builder {
         &self.targets
     }
 
-    /// Returns the configured rotation period for file logging.
-    pub fn configured_rotation(&self) -> Rotation {
-        self.rotation
+    /// Returns the patterns set with [`with_target_glob()`](Self::with_target_glob),
+    /// in the order they were added.
+    pub fn configured_target_globs(&self) -> &[(String, LevelFilter)] {
+        &self.target_globs
+    }
+
+    /// Returns the level of spans, if set apart from the level of events.
+    pub fn configured_span_level(&self) -> Option<LevelFilter> {
+        self.span_level
+    }
+
+    /// Returns the filters set with [`filter_for()`](Self::filter_for).
+    pub fn configured_layer_filters(&self) -> &[(Target, Targets)] {
+        &self.layer_filters
+    }
+
+    /// Returns the level from which stdout events are written to stderr, if
+    /// set.
+    pub fn configured_stderr_threshold(&self) -> Option<LevelFilter> {
+        self.stderr_threshold
+    }
+
+    /// Returns the configured rotation period for file logging.
+    pub fn configured_rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Returns the configured rotation strategy for file logging.
+    pub fn configured_rotation_strategy(&self) -> RotationStrategy {
+        self.rotation_strategy
+    }
+
+    /// Returns the configured strategy for concurrent app instances.
+    pub fn configured_instance_strategy(&self) -> InstanceStrategy {
+        self.instance_strategy
+    }
+
+    /// Returns the configured layout of log files of different app versions.
+    pub fn configured_version_layout(&self) -> VersionLayout {
+        self.version_layout
+    }
+
+    /// Returns the configured template for log file names, if any.
+    pub fn configured_file_name_template(&self) -> Option<&str> {
+        self.file_name_template.as_deref()
+    }
+
+    /// Returns the configured policy for syncing log files to disk.
+    pub fn configured_sync_policy(&self) -> SyncPolicy {
+        self.sync_policy
+    }
+
+    /// Returns the configured maximum file size for rotation, if set.
+    pub fn configured_max_file_size(&self) -> Option<MaxFileSize> {
+        self.max_file_size
+    }
+
+    /// Returns whether a rotation callback is registered.
+    pub fn has_rotation_hook(&self) -> bool {
+        self.rotation_hook.is_some()
+    }
+
+    /// Returns whether a message translator is registered.
+    pub fn has_message_translator(&self) -> bool {
+        self.message_translator.is_some()
+    }
+
+    /// Returns the configured slow span threshold, if any.
+    pub fn configured_slow_span_threshold(&self) -> Option<Duration> {
+        self.slow_span_threshold
+    }
+
+    /// Returns the configured resource monitor interval, if any.
+    pub fn configured_resource_monitor_interval(&self) -> Option<Duration> {
+        self.resource_monitor_interval
+    }
+
+    /// Returns the configured heartbeat interval, if any.
+    pub fn configured_heartbeat_interval(&self) -> Option<Duration> {
+        self.heartbeat_interval
+    }
+
+    /// Returns the configured event loop watchdog threshold, if any.
+    pub fn configured_event_loop_threshold(&self) -> Option<Duration> {
+        self.event_loop_threshold
+    }
+
+    /// Returns whether native stdout/stderr capture is enabled.
+    pub fn configured_output_capture(&self) -> bool {
+        self.capture_output
+    }
+
+    /// Returns whether runtime filter changes are persisted.
+    pub fn configured_filter_persistence(&self) -> bool {
+        self.persist_filter
+    }
+
+    /// Returns whether the events dropped by the filter are counted.
+    pub fn configured_filter_stats(&self) -> bool {
+        self.filter_stats
+    }
+
+    /// Returns whether Unix signals can change the log filter.
+    pub fn configured_signal_control(&self) -> bool {
+        self.signal_control
+    }
+
+    /// Returns the configured maximum message length, if any.
+    pub fn configured_max_message_len(&self) -> Option<usize> {
+        self.max_message_len
+    }
+
+    /// Returns the configured maximum number of call stack frames, if any.
+    pub fn configured_max_stack_frames(&self) -> Option<usize> {
+        self.max_stack_frames
+    }
+
+    /// Returns the configured sampling rules.
+    pub fn configured_sampling(&self) -> &[(String, SampleRate)] {
+        &self.sampling
+    }
+
+    /// Returns the watchdog set with
+    /// [`with_profiling_watchdog()`](Self::with_profiling_watchdog), if any.
+    #[cfg(feature = "profiling")]
+    pub fn configured_profiling_watchdog(&self) -> Option<ProfilingWatchdog> {
+        self.profiling_watchdog
+    }
+
+    /// Returns the span fields set with
+    /// [`with_span_timing_fields()`](Self::with_span_timing_fields), if any.
+    #[cfg(feature = "profiling")]
+    pub fn configured_span_timing_fields(&self) -> Option<&[String]> {
+        self.span_timing_fields.as_deref()
+    }
+
+    /// Returns the span statistics window set with
+    /// [`with_span_stats()`](Self::with_span_stats), if any.
+    #[cfg(feature = "profiling")]
+    pub fn configured_span_stats(&self) -> Option<Duration> {
+        self.span_stats_window
+    }
+
+    /// Returns the configured breadcrumb trail length, if enabled.
+    pub fn configured_breadcrumbs(&self) -> Option<usize> {
+        self.breadcrumbs
+    }
+
+    /// Returns whether events are numbered.
+    pub fn configured_sequence_numbers(&self) -> bool {
+        self.sequence_numbers
+    }
+
+    /// Returns the configured notifications, if enabled.
+    pub fn configured_notifications(&self) -> Option<Notifications> {
+        self.notifications
+    }
+
+    /// Returns whether backend events are logged to the devtools console.
+    pub fn configured_devtools_console(&self) -> bool {
+        self.devtools_console
+    }
+
+    /// Returns the configured in-memory history length, if enabled.
+    pub fn configured_log_history(&self) -> Option<usize> {
+        self.log_history
+    }
+
+    /// Returns whether the logs of end-to-end tests are captured.
+    pub fn configured_test_mode(&self) -> bool {
+        self.test_mode
+    }
+
+    /// Returns the [`LogContext`] the plugin will manage.
+    pub fn configured_log_context(&self) -> &LogContext {
+        &self.log_context
+    }
+
+    /// Returns whether navigation and window lifecycle events are logged.
+    pub fn configured_navigation_breadcrumbs(&self) -> bool {
+        self.navigation_breadcrumbs
+    }
+
+    /// Returns whether each client-side route opens a `route` span.
+    pub fn configured_route_spans(&self) -> bool {
+        self.route_spans
+    }
+
+    /// Returns whether progress updates are emitted to the app.
+    pub fn configured_progress_events(&self) -> bool {
+        self.progress_events
+    }
+
+    /// Returns the known events registered with
+    /// [`with_event_catalog()`](Self::with_event_catalog).
+    pub fn configured_event_catalog(&self) -> &[EventId] {
+        &self.event_catalog
+    }
+
+    /// Returns the interval of metrics batches emitted to the app, if
+    /// enabled.
+    pub fn configured_metrics_interval(&self) -> Option<Duration> {
+        self.metrics_interval
+    }
+
+    /// Returns the flags redacted by launch logging, if enabled.
+    pub fn configured_launch_logging(&self) -> Option<&[String]> {
+        self.launch_logging.as_deref()
+    }
+
+    /// Returns the file name of the update log, if set.
+    pub fn configured_update_log_file(&self) -> Option<&str> {
+        self.update_log_file.as_deref()
+    }
+
+    /// Returns the color theme set with
+    /// [`with_color_theme()`](Self::with_color_theme), if any.
+    #[cfg(feature = "colored")]
+    pub fn configured_color_theme(&self) -> Option<&ColorTheme> {
+        self.color_theme.as_ref()
+    }
+
+    /// Returns the scheme set with
+    /// [`with_editor_links()`](Self::with_editor_links), if any.
+    #[cfg(feature = "colored")]
+    pub fn configured_editor_links(&self) -> Option<&EditorScheme> {
+        self.editor_links.as_ref()
     }
 
-    /// Returns the configured rotation strategy for file logging.
-    pub fn configured_rotation_strategy(&self) -> RotationStrategy {
-        self.rotation_strategy
+    /// Returns the unified logging categories set with
+    /// [`with_os_log_category()`](Self::with_os_log_category).
+    pub fn configured_os_log_categories(&self) -> &[(String, String)] {
+        &self.os_log_categories
     }
 
-    /// Returns the configured maximum file size for rotation, if set.
-    pub fn configured_max_file_size(&self) -> Option<MaxFileSize> {
-        self.max_file_size
+    /// Returns the attributes set with
+    /// [`with_resource_attributes()`](Self::with_resource_attributes).
+    pub fn configured_resource_attributes(&self) -> &[(String, String)] {
+        &self.resource_attributes
+    }
+
+    /// Returns the build metadata set with
+    /// [`with_build_info()`](Self::with_build_info).
+    pub fn configured_build_info(&self) -> Option<&BuildInfo> {
+        self.build_info.as_ref()
+    }
+
+    /// Returns the build variant that selects the levels set with
+    /// [`with_variant_target()`](Self::with_variant_target).
+    pub fn configured_build_variant(&self) -> BuildVariant {
+        self.build_info
+            .as_ref()
+            .map_or_else(BuildVariant::current, |info| info.variant)
+    }
+
+    /// Returns the levels set with
+    /// [`with_variant_target()`](Self::with_variant_target), for every
+    /// variant.
+    pub fn configured_variant_targets(&self) -> &[(BuildVariant, String, LevelFilter)] {
+        &self.variant_targets
+    }
+
+    /// Returns the resource attributes set with
+    /// [`with_otlp_resource_attribute()`](Self::with_otlp_resource_attribute).
+    #[cfg(feature = "otlp")]
+    pub fn configured_otlp_resource(&self) -> &[(String, String)] {
+        &self.otlp_resource
+    }
+
+    /// Returns the key set with
+    /// [`with_report_signing_key()`](Self::with_report_signing_key).
+    #[cfg(feature = "diagnostics")]
+    pub fn configured_report_signing_key(&self) -> Option<&[u8]> {
+        self.report_signing_key.as_deref()
+    }
+
+    /// Returns the token of the endpoint set with
+    /// [`with_diagnostics_endpoint()`](Self::with_diagnostics_endpoint).
+    #[cfg(feature = "diagnostics-endpoint")]
+    pub fn configured_diagnostics_endpoint(&self) -> Option<&str> {
+        self.diagnostics_endpoint.as_deref()
     }
 
     /// Returns the configured timezone strategy for timestamps.
@@ -1059,21 +3065,171 @@ impl Builder {
     ///     // .run(tauri::generate_context!("examples/default-subscriber/src-tauri/tauri.conf.json"))
     /// ```
     pub fn build_filter(&self) -> Targets {
-        self.filter.clone().with_default(self.log_level)
+        self.variant_filter().with_default(self.log_level)
+    }
+
+    /// Returns the per-target levels, with those of the build variant.
+    fn variant_filter(&self) -> Targets {
+        let variant = self.configured_build_variant();
+        self.variant_targets
+            .iter()
+            .filter(|(target_variant, _, _)| *target_variant == variant)
+            .fold(self.filter.clone(), |filter, (_, target, level)| {
+                filter.with_target(target.clone(), *level)
+            })
+    }
+
+    /// Returns a [`SamplingLayer`] with the rules configured via
+    /// [`with_sampling()`](Self::with_sampling).
+    ///
+    /// Use this when setting up your own subscriber. Add it after the output
+    /// layers and before the filter.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, SampleRate, SampledFields};
+    /// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt, fmt};
+    /// let builder = Builder::new().with_sampling("my_app::render", SampleRate::OneIn(100));
+    ///
+    /// Registry::default()
+    ///     .with(fmt::layer().fmt_fields(SampledFields::new(fmt::format::DefaultFields::new())))
+    ///     .with(builder.build_sampling_layer())
+    ///     .with(builder.build_filter())
+    ///     .init();
+    /// ```
+    pub fn build_sampling_layer(&self) -> SamplingLayer {
+        self.sampling
+            .iter()
+            .fold(SamplingLayer::new(), |layer, (target, rate)| {
+                layer.with_rule(target.clone(), *rate)
+            })
     }
 
     #[cfg(feature = "flamegraph")]
-    fn plugin_builder<R: Runtime>() -> plugin::Builder<R> {
-        plugin::Builder::new("tracing").invoke_handler(tauri::generate_handler![
+    fn plugin_builder<R: Runtime>(name: &'static str) -> plugin::Builder<R> {
+        plugin::Builder::new(name).invoke_handler(tauri::generate_handler![
             commands::log,
+            commands::log_batch,
+            commands::report_frame_times,
+            commands::sync_clock,
+            commands::operation_start,
+            commands::operation_end,
+            commands::operation_get,
+            commands::operation_list,
+            commands::span_start,
+            commands::span_end,
+            commands::set_log_level,
+            commands::set_target_level,
+            commands::reset_log_filter,
+            commands::get_effective_filter,
+            commands::get_log_stats,
+            commands::clear_logs,
+            commands::mark,
+            commands::get_logs_around_mark,
+            commands::set_context,
+            commands::remove_context,
+            commands::clear_context,
+            commands::set_current_route,
+            commands::report_invariant_violation,
+            commands::get_invariant_violations,
+            commands::progress,
+            commands::gauge,
+            commands::counter,
+            commands::begin_test,
+            commands::end_test,
+            commands::get_schema,
+            commands::analyze_noise,
+            commands::apply_noise_filters,
+            commands::create_diagnostic_report,
+            commands::start_span_aware_profile,
+            commands::stop_span_aware_profile,
+            commands::list_profiling_sessions,
+            commands::get_profiling_session,
+            commands::get_span_stats,
+            commands::get_filter_stats,
+            commands::tail_logs,
+            commands::stop_tail_logs,
+            commands::heartbeat_pong,
+            commands::webview_ready,
             commands::generate_flamegraph,
             commands::generate_flamechart
         ])
     }
 
     #[cfg(not(feature = "flamegraph"))]
-    fn plugin_builder<R: Runtime>() -> plugin::Builder<R> {
-        plugin::Builder::new("tracing").invoke_handler(tauri::generate_handler![commands::log,])
+    fn plugin_builder<R: Runtime>(name: &'static str) -> plugin::Builder<R> {
+        plugin::Builder::new(name).invoke_handler(tauri::generate_handler![
+            commands::log,
+            commands::log_batch,
+            commands::report_frame_times,
+            commands::sync_clock,
+            commands::operation_start,
+            commands::operation_end,
+            commands::operation_get,
+            commands::operation_list,
+            commands::span_start,
+            commands::span_end,
+            commands::set_log_level,
+            commands::set_target_level,
+            commands::reset_log_filter,
+            commands::get_effective_filter,
+            commands::get_log_stats,
+            commands::clear_logs,
+            commands::mark,
+            commands::get_logs_around_mark,
+            commands::set_context,
+            commands::remove_context,
+            commands::clear_context,
+            commands::set_current_route,
+            commands::report_invariant_violation,
+            commands::get_invariant_violations,
+            commands::progress,
+            commands::gauge,
+            commands::counter,
+            commands::begin_test,
+            commands::end_test,
+            commands::get_schema,
+            commands::analyze_noise,
+            commands::apply_noise_filters,
+            commands::create_diagnostic_report,
+            commands::start_span_aware_profile,
+            commands::stop_span_aware_profile,
+            commands::list_profiling_sessions,
+            commands::get_profiling_session,
+            commands::get_span_stats,
+            commands::get_filter_stats,
+            commands::tail_logs,
+            commands::stop_tail_logs,
+            commands::heartbeat_pong,
+            commands::webview_ready,
+        ])
+    }
+
+    /// Checks the configuration for options that are ignored or won't work.
+    ///
+    /// The plugin logs the same issues as warnings at startup; call this
+    /// before [`build()`](Self::build) to fail fast instead. Returns an
+    /// error if any [`ConfigIssue::is_error()`], and the remaining warnings
+    /// otherwise. Nothing is created on disk.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_tracing::Builder;
+    ///
+    /// # fn main() -> Result<(), tauri_plugin_tracing::ConfigError> {
+    /// let builder = Builder::new().with_file_logging();
+    /// let config = builder.validate()?;
+    /// for warning in config.warnings() {
+    ///     eprintln!("{warning}");
+    /// }
+    /// let _plugin = builder.build::<tauri::Wry>();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> std::result::Result<ValidatedConfig, ConfigError> {
+        validate::result(validate::issues(self))
     }
 
     /// Builds and returns the configured Tauri plugin.
@@ -1089,15 +3245,56 @@ impl Builder {
     ///     .plugin(Builder::new().build());
     ///     // .run(tauri::generate_context!("examples/default-subscriber/src-tauri/tauri.conf.json"))
     /// ```
-    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+    pub fn build<R: Runtime>(mut self) -> TauriPlugin<R> {
+        let plugin_name = self.plugin_name;
+        // Renamed instances keep their log files apart by default
+        if plugin_name != DEFAULT_PLUGIN_NAME {
+            for target in &mut self.targets {
+                if let Target::LogDir { file_name } | Target::Folder { file_name, .. } = target {
+                    file_name.get_or_insert_with(|| plugin_name.to_string());
+                }
+            }
+        }
+        #[cfg(feature = "diagnostics")]
+        let diagnostic_config = diagnostics::builder_config(&self);
+        #[cfg(desktop)]
+        let running_settings = reconfigure::RunningSettings::of(&self);
+        let config_issues = validate::issues(&self);
+        let filter = self.variant_filter();
+        let sampling_layer = (!self.sampling.is_empty()).then(|| self.build_sampling_layer());
+        let sequence_layer = self.sequence_numbers.then_some(SequenceLayer);
+        let message_translator = self.message_translator;
+        let breadcrumb_layer = self.breadcrumbs.map(BreadcrumbLayer::new);
+        let notifications = self.notifications;
+        let devtools_console = self.devtools_console;
+        let log_history = self.log_history.map(LogHistory::new);
+        let test_mode = self.test_mode;
+        let log_context = self.log_context;
+        let navigation_breadcrumbs = self.navigation_breadcrumbs;
+        let route_spans = self.route_spans;
+        let progress_events = self.progress_events;
+        let metrics_interval = self.metrics_interval;
+        let launch_log = self.launch_logging.map(LaunchLog::new);
         let log_level = self.log_level;
-        let filter = self.filter;
+        let target_globs = self.target_globs;
+        let span_level = self.span_level;
         let custom_filter = self.custom_filter;
         let custom_layer = self.custom_layer;
         let targets = self.targets;
+        let layer_filters = self.layer_filters;
+        let stderr_threshold = self.stderr_threshold;
         let rotation = self.rotation;
         let rotation_strategy = self.rotation_strategy;
         let max_file_size = self.max_file_size;
+        let rotation_hook = self.rotation_hook;
+        let instance_strategy = self.instance_strategy;
+        let version_layout = self.version_layout;
+        // Invalid templates are reported with the other configuration issues
+        let file_name_template = self
+            .file_name_template
+            .as_deref()
+            .and_then(|template| FileNameTemplate::parse(template).ok());
+        let sync_policy = self.sync_policy;
         let timezone_strategy = self.timezone_strategy;
         let format_options = FormatOptions {
             format: self.log_format,
@@ -1109,46 +3306,285 @@ impl Builder {
             level: self.show_level,
         };
         let set_default_subscriber = self.set_default_subscriber;
+        #[cfg(desktop)]
+        let subscriber_fallback = self.subscriber_fallback;
+        let slow_span_threshold = self.slow_span_threshold;
+        let resource_monitor_interval = self.resource_monitor_interval;
+        let heartbeat_interval = self.heartbeat_interval;
+        let event_loop_threshold = self.event_loop_threshold;
+        let capture_output = self.capture_output;
+        let persist_filter = self.persist_filter;
+        let filter_stats = self.filter_stats;
+        let update_log_file = self.update_log_file;
+        #[cfg(all(unix, desktop))]
+        let signal_control = self.signal_control;
+        let log_limits = commands::LogLimits {
+            max_message_len: self.max_message_len,
+            max_stack_frames: self.max_stack_frames,
+        };
 
         #[cfg(feature = "colored")]
         let use_colors = self.use_colors;
+        #[cfg(feature = "colored")]
+        let color_theme = self.color_theme;
+        #[cfg(feature = "colored")]
+        let editor_links = self.editor_links;
 
         #[cfg(feature = "flamegraph")]
         let enable_flamegraph = self.enable_flamegraph;
 
-        Self::plugin_builder()
+        #[cfg(feature = "profiling")]
+        let profiling_watchdog = self.profiling_watchdog;
+        #[cfg(feature = "profiling")]
+        let span_timing_fields = self.span_timing_fields;
+        #[cfg(feature = "profiling")]
+        let span_stats_window = self.span_stats_window;
+
+        #[cfg(target_vendor = "apple")]
+        let os_log_categories = self.os_log_categories;
+        // Build metadata comes first, so attributes set by the app follow it
+        let resource_attributes: Vec<(String, String)> = self
+            .build_info
+            .iter()
+            .flat_map(BuildInfo::attributes)
+            .chain(self.resource_attributes)
+            .collect();
+        #[cfg(feature = "otlp")]
+        let otlp_resource = self.otlp_resource;
+        #[cfg(feature = "diagnostics")]
+        let report_signing_key = self.report_signing_key;
+        #[cfg(feature = "diagnostics-endpoint")]
+        let diagnostics_endpoint = self.diagnostics_endpoint;
+
+        let configured_levels: Vec<(String, LevelFilter)> = filter
+            .iter()
+            .map(|(target, level)| (target.to_string(), level))
+            .collect();
+
+        Self::plugin_builder(plugin_name)
+            .js_init_script(log_limits.init_script())
             .setup(move |app, _api| {
+                // Stopped before the check, as the buffering subscriber is
+                // the current one
+                let early_events = early::take();
+
+                // Checked before anything is set up for the default subscriber
+                #[cfg(desktop)]
+                let set_default_subscriber =
+                    set_default_subscriber && !use_existing_subscriber(subscriber_fallback)?;
+
+                app.manage(plugin_name::PluginName(plugin_name));
+                app.manage(SpanContextRegistry::default());
+                app.manage(WebviewForwarding::default());
+                app.manage(log_limits);
+                app.manage(OperationRegistry::default());
+                app.manage(LogTailer::default());
+                app.manage(ClockSync::default());
+                app.manage(CurrentRoutes::new(route_spans));
+                app.manage(ProgressTracker::new(progress_events));
+                app.manage(log_context.clone());
+                // Picked up by the webview layers created from now on
+                if let Some(translator) = message_translator {
+                    app.manage(translator);
+                }
+                if navigation_breadcrumbs {
+                    app.manage(NavigationBreadcrumbs::default());
+                }
+
+                #[cfg(feature = "diagnostics")]
+                app.manage(diagnostics::DiagnosticSources {
+                    log_files: targets
+                        .iter()
+                        .filter_map(|target| {
+                            resolve_file_target(
+                                app,
+                                target,
+                                version_layout,
+                                file_name_template.as_ref(),
+                            )
+                            .ok()
+                            .flatten()
+                        })
+                        .map(|config| (config.log_dir, config.file_name))
+                        .collect(),
+                    config: diagnostic_config,
+                    latest_capture: Default::default(),
+                    signing_key: report_signing_key,
+                });
+
+                // Capture before the subscriber is built so its stdout and
+                // stderr layers can write to the original streams. Layers of
+                // the app's own subscriber may write to the captured streams,
+                // which would capture each line again forever
+                if capture_output && !set_default_subscriber {
+                    tracing::warn!(
+                        "Not capturing stdout/stderr without the plugin's default subscriber"
+                    );
+                } else if capture_output {
+                    match OutputCapture::start() {
+                        Ok(capture) => {
+                            app.manage(capture);
+                        }
+                        Err(e) => tracing::warn!("Failed to capture stdout/stderr: {e}"),
+                    }
+                }
+
+                if let Some(interval) = resource_monitor_interval {
+                    app.manage(ResourceMonitor::start(interval)?);
+                }
+
+                if let Some(interval) = heartbeat_interval {
+                    app.manage(Heartbeat::start(app.clone(), interval)?);
+                }
+
+                if let Some(interval) = metrics_interval {
+                    app.manage(metrics::MetricsBatcher::start(app.clone(), interval)?);
+                }
+
+                // An empty token is reported as a configuration error
+                #[cfg(feature = "diagnostics-endpoint")]
+                if let Some(token) = diagnostics_endpoint
+                    && !token.is_empty()
+                {
+                    app.manage(DiagnosticsEndpoint::start(app.clone(), token)?);
+                }
+
+                if let Some(threshold) = event_loop_threshold {
+                    app.manage(EventLoopWatchdog::start(app.clone(), threshold)?);
+                }
+
                 #[cfg(feature = "flamegraph")]
                 setup_flamegraph(app);
 
                 #[cfg(desktop)]
                 if set_default_subscriber {
-                    let guard = acquire_logger(
+                    acquire_logger(
                         app,
                         log_level,
                         filter,
+                        &target_globs,
+                        span_level,
                         custom_filter,
                         custom_layer,
                         &targets,
+                        &layer_filters,
+                        stderr_threshold,
                         rotation,
                         rotation_strategy,
                         max_file_size,
+                        rotation_hook,
+                        instance_strategy,
+                        version_layout,
+                        file_name_template.as_ref(),
+                        sync_policy,
                         timezone_strategy,
                         format_options,
+                        slow_span_threshold,
+                        sampling_layer,
+                        sequence_layer,
+                        breadcrumb_layer,
+                        notifications,
+                        devtools_console,
+                        log_history,
+                        test_mode
+                            .then(|| app.path().app_log_dir())
+                            .transpose()?
+                            .map(|dir| TestCapture::new(dir.join("tests"))),
+                        persist_filter,
+                        filter_stats,
+                        update_log_file,
                         #[cfg(feature = "colored")]
                         use_colors,
+                        #[cfg(feature = "colored")]
+                        color_theme,
+                        #[cfg(feature = "colored")]
+                        editor_links,
                         #[cfg(feature = "flamegraph")]
                         enable_flamegraph,
+                        #[cfg(feature = "profiling")]
+                        profiling_watchdog,
+                        #[cfg(feature = "profiling")]
+                        span_timing_fields,
+                        #[cfg(feature = "profiling")]
+                        span_stats_window,
+                        #[cfg(target_vendor = "apple")]
+                        os_log_categories,
+                        resource_attributes,
+                        #[cfg(feature = "otlp")]
+                        otlp_resource,
                     )?;
+                    app.manage(running_settings);
 
-                    // Store the guard in Tauri's state management to ensure logs flush on shutdown
-                    if guard.is_some() {
-                        app.manage(LogGuard(guard));
+                    #[cfg(unix)]
+                    if signal_control {
+                        signals::listen(app.clone());
                     }
                 }
+                if let Some(early_events) = early_events {
+                    early_events.replay();
+                }
+
+                filter::warn_if_compiled_out(None, log_level);
+                for (target, level) in &configured_levels {
+                    filter::warn_if_compiled_out(Some(target), *level);
+                }
+                validate::log_issues(&config_issues);
+
+                // Logged once the default subscriber is installed
+                if let Some(launch_log) = launch_log {
+                    launch_log.log_startup();
+                    app.manage(launch_log);
+                }
 
                 Ok(())
             })
+            .on_event(|app, event| {
+                layer::handle_run_event(app, event);
+                if let RunEvent::WindowEvent { label, event, .. } = event {
+                    if let Some(navigation) = app.try_state::<NavigationBreadcrumbs>() {
+                        navigation.window_event(label, event);
+                    }
+                    if let Some(heartbeat) = app.try_state::<Heartbeat>() {
+                        heartbeat.window_event(label, event);
+                    }
+                    if let tauri::WindowEvent::Destroyed = event
+                        && let Some(routes) = app.try_state::<CurrentRoutes>()
+                    {
+                        routes.forget(label);
+                    }
+                }
+                #[cfg(any(target_os = "macos", target_os = "ios"))]
+                if let RunEvent::Opened { urls } = event
+                    && let Some(launch) = app.try_state::<LaunchLog>()
+                {
+                    launch.log_deep_links(urls);
+                }
+                if let RunEvent::Exit = event
+                    && let Some(operations) = app.try_state::<OperationRegistry>()
+                {
+                    operations.warn_unfinished();
+                }
+            })
+            .on_webview_ready(|webview| webview_crash::watch(&webview))
+            .on_page_load(|webview, payload| match payload.event() {
+                tauri::webview::PageLoadEvent::Started => {
+                    // The new page has a new time origin
+                    if let Some(clock) = webview.try_state::<ClockSync>() {
+                        clock.forget(webview.label());
+                    }
+                    if let Some(routes) = webview.try_state::<CurrentRoutes>() {
+                        routes.forget(webview.label());
+                    }
+                    if let Some(heartbeat) = webview.try_state::<Heartbeat>() {
+                        heartbeat.page_load_started(webview.label());
+                    }
+                }
+                tauri::webview::PageLoadEvent::Finished => {
+                    if let Some(navigation) = webview.try_state::<NavigationBreadcrumbs>() {
+                        navigation.page_loaded(webview.label(), payload.url().as_str());
+                    }
+                }
+            })
             .build()
     }
 }
@@ -1157,61 +3593,245 @@ impl Builder {
 struct FileTargetConfig {
     log_dir: PathBuf,
     file_name: String,
+    /// What follows the date in file names, `log` unless templated.
+    file_suffix: String,
+    /// The file name and suffix of an instance writing to its own file, if
+    /// the template places its process ID.
+    instance_file_name: Option<(String, String)>,
+    /// The directory and file prefix shared by the logs of all app versions,
+    /// if they are kept apart.
+    versions: Option<(PathBuf, String, VersionLayout)>,
 }
 
 /// Resolves file target configuration from a Target.
 fn resolve_file_target<R: Runtime>(
     app_handle: &AppHandle<R>,
     target: &Target,
+    version_layout: VersionLayout,
+    template: Option<&FileNameTemplate>,
 ) -> Result<Option<FileTargetConfig>> {
-    match target {
-        Target::LogDir { file_name } => {
-            let log_dir = app_handle.path().app_log_dir()?;
-            std::fs::create_dir_all(&log_dir)?;
-            Ok(Some(FileTargetConfig {
-                log_dir,
-                file_name: file_name.clone().unwrap_or_else(|| "app".to_string()),
-            }))
-        }
-        Target::Folder { path, file_name } => {
-            std::fs::create_dir_all(path)?;
-            Ok(Some(FileTargetConfig {
-                log_dir: path.clone(),
-                file_name: file_name.clone().unwrap_or_else(|| "app".to_string()),
-            }))
+    let (base_dir, file_name) = match target {
+        Target::LogDir { file_name } => (app_handle.path().app_log_dir()?, file_name),
+        Target::Folder { path, file_name } => (path.clone(), file_name),
+        _ => return Ok(None),
+    };
+    let file_name = file_name.clone().unwrap_or_else(|| "app".to_string());
+    let version = app_handle.package_info().version.to_string();
+    let mut config = match version_layout {
+        VersionLayout::Flat => FileTargetConfig {
+            log_dir: base_dir,
+            file_name,
+            file_suffix: "log".to_string(),
+            instance_file_name: None,
+            versions: None,
+        },
+        VersionLayout::Directory => FileTargetConfig {
+            log_dir: base_dir.join(&version),
+            file_name: file_name.clone(),
+            file_suffix: "log".to_string(),
+            instance_file_name: None,
+            versions: Some((base_dir, file_name, version_layout)),
+        },
+        VersionLayout::FileName => FileTargetConfig {
+            log_dir: base_dir.clone(),
+            file_name: format!("{file_name}-{version}"),
+            file_suffix: "log".to_string(),
+            instance_file_name: None,
+            versions: Some((base_dir, file_name, version_layout)),
+        },
+    };
+    if let Some(template) = template {
+        let values = |instance| file_name::Values {
+            prefix: &config.file_name,
+            version: &version,
+            instance,
+        };
+        let instance_file_name = template
+            .has_instance()
+            .then(|| template.render(&values(Some(std::process::id()))));
+        (config.file_name, config.file_suffix) = template.render(&values(None));
+        config.instance_file_name = instance_file_name;
+    }
+    std::fs::create_dir_all(&config.log_dir)?;
+    Ok(Some(config))
+}
+
+/// Picks the file name and suffix this instance writes to, taking the lock on
+/// the log file if the strategy asks for one.
+#[cfg(feature = "file")]
+fn claim_file_name(
+    config: &FileTargetConfig,
+    strategy: InstanceStrategy,
+) -> Result<((String, String), Option<std::fs::File>)> {
+    let shared = || (config.file_name.clone(), config.file_suffix.clone());
+    let per_process = || {
+        config.instance_file_name.clone().unwrap_or_else(|| {
+            (
+                format!("{}-{}", config.file_name, std::process::id()),
+                config.file_suffix.clone(),
+            )
+        })
+    };
+    match strategy {
+        InstanceStrategy::Shared => Ok((shared(), None)),
+        InstanceStrategy::ProcessId => Ok((per_process(), None)),
+        InstanceStrategy::Lock | InstanceStrategy::Forward => {
+            let lock = std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(config.log_dir.join(format!("{}.lock", config.file_name)))?;
+            // Held by another instance, or unsupported by the file system
+            match lock.try_lock() {
+                Ok(()) => Ok((shared(), Some(lock))),
+                Err(_) => Ok((per_process(), None)),
+            }
         }
-        _ => Ok(None),
     }
 }
 
 /// Cleans up old log files based on the retention strategy.
+///
+/// With `all_instances`, files written by other instances under
+/// [`InstanceStrategy::ProcessId`] naming are counted too.
+#[cfg(feature = "file")]
 fn cleanup_old_logs(
     log_dir: &std::path::Path,
     file_prefix: &str,
     strategy: RotationStrategy,
+    all_instances: bool,
 ) -> Result<()> {
-    match strategy {
-        RotationStrategy::KeepAll => Ok(()),
-        RotationStrategy::KeepOne => cleanup_logs_keeping(log_dir, file_prefix, 1),
-        RotationStrategy::KeepSome(n) => cleanup_logs_keeping(log_dir, file_prefix, n as usize),
+    let keep = match strategy {
+        RotationStrategy::KeepAll => return Ok(()),
+        RotationStrategy::KeepOne => 1,
+        RotationStrategy::KeepSome(n) => n as usize,
+    };
+    cleanup_logs_keeping(log_dir, file_prefix, keep, all_instances)
+}
+
+/// Cleans up old log files of all app versions based on the retention
+/// strategy, keeping the most recently modified.
+///
+/// With [`VersionLayout::Directory`], the directories of older versions are
+/// removed once their last file is.
+#[cfg(feature = "file")]
+fn cleanup_old_version_logs(
+    base_dir: &std::path::Path,
+    file_prefix: &str,
+    layout: VersionLayout,
+    current_dir: &std::path::Path,
+    strategy: RotationStrategy,
+) -> Result<()> {
+    let keep = match strategy {
+        RotationStrategy::KeepAll => return Ok(()),
+        RotationStrategy::KeepOne => 1,
+        RotationStrategy::KeepSome(n) => n as usize,
+    };
+    let log_files = |dir: &std::path::Path, prefix: &str| -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.starts_with(prefix) && name.ends_with(".log"))
+            })
+            .map(|entry| entry.path())
+            .collect()
+    };
+
+    let mut files = match layout {
+        VersionLayout::Flat => return Ok(()),
+        // Each version's files, and those of its instances, in a directory
+        // named after it
+        VersionLayout::Directory => std::fs::read_dir(base_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .flat_map(|dir| {
+                let mut files = log_files(&dir, &format!("{file_prefix}."));
+                files.extend(log_files(&dir, &format!("{file_prefix}-")));
+                files
+            })
+            .collect(),
+        // `app-1.4.2.2024-01-15.log`, and `app-1.4.2-4242.2024-01-15.log` of
+        // other instances
+        VersionLayout::FileName => log_files(base_dir, &format!("{file_prefix}-")),
+    };
+
+    // Newest first
+    files.sort_by_key(|path| {
+        std::cmp::Reverse(std::fs::metadata(path).and_then(|m| m.modified()).ok())
+    });
+    for path in files.into_iter().skip(keep) {
+        if let Err(e) = std::fs::remove_file(&path) {
+            tracing::warn!("Failed to remove old log file {:?}: {}", path, e);
+            continue;
+        }
+        // Fails unless the directory is now empty
+        if layout == VersionLayout::Directory
+            && let Some(dir) = path.parent().filter(|dir| *dir != current_dir)
+        {
+            let _ = std::fs::remove_dir(dir);
+        }
     }
+
+    Ok(())
+}
+
+/// Returns whether `name` is a log file written by an instance with the
+/// given PID-suffixed prefix, e.g. `app-4242.2024-01-15.log` for `app`.
+fn is_instance_log(name: &str, file_prefix: &str) -> bool {
+    name.strip_prefix(file_prefix)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .and_then(|rest| rest.split_once('.'))
+        .is_some_and(|(pid, _)| !pid.is_empty() && pid.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Returns whether `name` is a log file of the target with `file_prefix`,
+/// including size-rotated files like `app.log.1` and, with `all_instances`,
+/// files of other instances.
+fn is_log_file(name: &str, file_prefix: &str, all_instances: bool) -> bool {
+    let is_target = name
+        .strip_prefix(file_prefix)
+        .is_some_and(|rest| rest.starts_with('.'))
+        || (all_instances && is_instance_log(name, file_prefix));
+    is_target && (name.ends_with(".log") || name.contains(".log."))
 }
 
 /// Helper to delete old log files, keeping only the most recent `keep` files.
-fn cleanup_logs_keeping(log_dir: &std::path::Path, file_prefix: &str, keep: usize) -> Result<()> {
+#[cfg(feature = "file")]
+fn cleanup_logs_keeping(
+    log_dir: &std::path::Path,
+    file_prefix: &str,
+    keep: usize,
+    all_instances: bool,
+) -> Result<()> {
     let prefix_with_dot = format!("{}.", file_prefix);
     let mut log_files: Vec<_> = std::fs::read_dir(log_dir)?
         .filter_map(|entry| entry.ok())
         .filter(|entry| {
-            entry
-                .file_name()
-                .to_str()
-                .is_some_and(|name| name.starts_with(&prefix_with_dot) && name.ends_with(".log"))
+            entry.file_name().to_str().is_some_and(|name| {
+                name.ends_with(".log")
+                    && (name.starts_with(&prefix_with_dot)
+                        || (all_instances && is_instance_log(name, file_prefix)))
+            })
         })
         .collect();
 
-    // Sort by filename (which includes date) in descending order (newest first)
-    log_files.sort_by_key(|entry| std::cmp::Reverse(entry.file_name()));
+    if all_instances {
+        // Names of different instances don't sort by date, so use the
+        // modification time (newest first)
+        log_files.sort_by_key(|entry| {
+            std::cmp::Reverse(entry.metadata().and_then(|m| m.modified()).ok())
+        });
+    } else {
+        // Sort by filename (which includes date) in descending order (newest first)
+        log_files.sort_by_key(|entry| std::cmp::Reverse(entry.file_name()));
+    }
 
     // Delete all but the most recent `keep` files
     for entry in log_files.into_iter().skip(keep) {
@@ -1223,6 +3843,89 @@ fn cleanup_logs_keeping(log_dir: &std::path::Path, file_prefix: &str, keep: usiz
     Ok(())
 }
 
+/// Opens the file target's writer with size-based rotation, combined with
+/// daily or hourly rotation if configured.
+#[cfg(feature = "rotation")]
+fn size_rotating_writer(
+    config: &FileTargetConfig,
+    rotation: Rotation,
+    rotation_strategy: RotationStrategy,
+    max_size: MaxFileSize,
+    rotation_hook: Option<RotationHook>,
+    sync_policy: SyncPolicy,
+) -> Result<(tracing_appender::non_blocking::NonBlocking, WorkerGuard)> {
+    use rolling_file::{BasicRollingFileAppender, RollingConditionBasic};
+
+    // Build rolling condition with both time and size triggers
+    let mut condition = RollingConditionBasic::new();
+    condition = match rotation {
+        Rotation::Daily => condition.daily(),
+        Rotation::Hourly => condition.hourly(),
+        Rotation::Minutely => condition, // rolling-file doesn't have minutely, use size only
+        Rotation::Never => condition,    // size-only rotation
+    };
+    condition = condition.max_size(max_size.0);
+
+    // Determine max file count from rotation strategy
+    let max_files = match rotation_strategy {
+        RotationStrategy::KeepAll => u32::MAX as usize,
+        RotationStrategy::KeepOne => 1,
+        RotationStrategy::KeepSome(n) => n as usize,
+    };
+
+    let log_path = config
+        .log_dir
+        .join(format!("{}.{}", config.file_name, config.file_suffix));
+    // The appender always writes to the same path
+    let current_path: fsync::CurrentPath = {
+        let log_path = log_path.clone();
+        std::sync::Arc::new(move || log_path.clone())
+    };
+    Ok(match rotation_hook {
+        Some(hook) => {
+            let file_appender = rolling_file::RollingFileAppender::new(
+                &log_path,
+                RolloverCondition::new(condition),
+                max_files,
+            )
+            .map_err(std::io::Error::other)?;
+            tracing_appender::non_blocking(fsync::SyncingWriter::new(
+                RolloverWatcher::new(file_appender, log_path, hook),
+                current_path,
+                sync_policy,
+            )?)
+        }
+        None => {
+            let file_appender = BasicRollingFileAppender::new(log_path, condition, max_files)
+                .map_err(std::io::Error::other)?;
+            tracing_appender::non_blocking(fsync::SyncingWriter::new(
+                file_appender,
+                current_path,
+                sync_policy,
+            )?)
+        }
+    })
+}
+
+/// Returns whether to keep a subscriber the app has already set instead of
+/// setting the default one, which fails unless `fallback` is set.
+#[cfg(desktop)]
+fn use_existing_subscriber(fallback: bool) -> Result<bool> {
+    let existing = tracing::dispatcher::get_default(|dispatch| {
+        !dispatch.is::<tracing::subscriber::NoSubscriber>()
+    });
+    if !existing {
+        return Ok(false);
+    }
+    if !fallback {
+        return Err(Error::SubscriberAlreadySet);
+    }
+    tracing::warn!(
+        "A tracing subscriber is already set, so the plugin's default subscriber is not used"
+    );
+    Ok(true)
+}
+
 /// Sets up the tracing subscriber based on configured targets.
 #[cfg(desktop)]
 #[allow(clippy::too_many_arguments)]
@@ -1230,31 +3933,101 @@ fn acquire_logger<R: Runtime>(
     app_handle: &AppHandle<R>,
     log_level: LevelFilter,
     filter: Targets,
+    target_globs: &[(String, LevelFilter)],
+    span_level: Option<LevelFilter>,
     custom_filter: Option<FilterFn>,
     custom_layer: Option<BoxedLayer>,
     targets: &[Target],
+    layer_filters: &[(Target, Targets)],
+    stderr_threshold: Option<LevelFilter>,
     rotation: Rotation,
     rotation_strategy: RotationStrategy,
     max_file_size: Option<MaxFileSize>,
+    rotation_hook: Option<RotationHook>,
+    instance_strategy: InstanceStrategy,
+    version_layout: VersionLayout,
+    file_name_template: Option<&FileNameTemplate>,
+    sync_policy: SyncPolicy,
     timezone_strategy: TimezoneStrategy,
     format_options: FormatOptions,
+    slow_span_threshold: Option<Duration>,
+    sampling_layer: Option<SamplingLayer>,
+    sequence_layer: Option<SequenceLayer>,
+    breadcrumb_layer: Option<BreadcrumbLayer>,
+    notifications: Option<Notifications>,
+    devtools_console: bool,
+    log_history: Option<LogHistory>,
+    test_capture: Option<TestCapture>,
+    persist_filter: bool,
+    filter_stats: bool,
+    update_log_file: Option<String>,
     #[cfg(feature = "colored")] use_colors: bool,
+    #[cfg(feature = "colored")] color_theme: Option<ColorTheme>,
+    #[cfg(feature = "colored")] editor_links: Option<EditorScheme>,
     #[cfg(feature = "flamegraph")] enable_flamegraph: bool,
-) -> Result<Option<WorkerGuard>> {
+    #[cfg(feature = "profiling")] profiling_watchdog: Option<ProfilingWatchdog>,
+    #[cfg(feature = "profiling")] span_timing_fields: Option<Vec<String>>,
+    #[cfg(feature = "profiling")] span_stats_window: Option<Duration>,
+    #[cfg(target_vendor = "apple")] os_log_categories: Vec<(String, String)>,
+    resource_attributes: Vec<(String, String)>,
+    #[cfg(feature = "otlp")] otlp_resource: Vec<(String, String)>,
+) -> Result<()> {
     use std::io;
+    use std::sync::{Arc, RwLock};
     use tracing_subscriber::fmt::time::OffsetTime;
+    use tracing_subscriber::fmt::writer::BoxMakeWriter;
 
+    // Spans keep the per-target levels, at their own default level
+    let span_filter = span_level.map(|level| filter.clone().with_default(level));
     let filter_with_default = filter.with_default(log_level);
 
+    // The filter can be changed at runtime, starting from any changes
+    // persisted by a previous run
+    let store_path = if persist_filter {
+        Some(app_handle.path().app_config_dir()?.join(FILTER_FILE_NAME))
+    } else {
+        None
+    };
+    let overrides = FilterOverrides::load(store_path.as_ref());
+    let restored_filter = !overrides.is_empty();
+
+    // Each output filters events on its own, with the filter in effect unless
+    // it has a filter of its own
+    let current_filter = Arc::new(RwLock::new(overrides.apply_to(&filter_with_default)));
+    let own_filter = |output: Output| {
+        layer_filters
+            .iter()
+            .find(|(target, _)| Output::of(target) == output)
+            .map(|(_, filter)| filter)
+    };
+    // Patterns from with_target_glob take precedence over every filter
+    let target_globs = Arc::new(TargetGlobs::new(target_globs));
+    let layer_filter = |output: Output| {
+        let filter = match own_filter(output) {
+            Some(filter) => LayerFilter::Own(filter.clone()),
+            None => LayerFilter::Shared(current_filter.clone()),
+        };
+        GlobFilter::new(
+            target_globs.clone(),
+            SpanFilter::new(span_filter.clone(), filter),
+        )
+    };
+    // Filters of outputs that are not configured do not widen the global one
+    let mut own_filters: Vec<Targets> = targets
+        .iter()
+        .filter_map(|target| own_filter(Output::of(target)).cloned())
+        .collect();
+
     // Determine which targets are enabled
     let has_stdout = targets.iter().any(|t| matches!(t, Target::Stdout));
     let has_stderr = targets.iter().any(|t| matches!(t, Target::Stderr));
-    let has_webview = targets.iter().any(|t| matches!(t, Target::Webview));
 
     // Find file target (only first one is used)
     let file_config = targets
         .iter()
-        .find_map(|t| resolve_file_target(app_handle, t).transpose())
+        .find_map(|t| {
+            resolve_file_target(app_handle, t, version_layout, file_name_template).transpose()
+        })
         .transpose()?;
 
     // Determine if ANSI should be enabled for stdout/stderr.
@@ -1280,18 +4053,96 @@ fn acquire_logger<R: Runtime>(
             }),
     };
 
+    let rfc5424 = Rfc5424Format::new(&app_handle.package_info().name)
+        .with_timezone_strategy(timezone_strategy)
+        .with_attributes(resource_attributes.iter().cloned());
+
     // Macro to create a formatted layer with the appropriate format style.
     // This is needed because .compact() and .pretty() return different types.
     macro_rules! make_layer {
         ($layer:expr, $format:expr) => {
             match $format {
-                LogFormat::Full => $layer.boxed(),
-                LogFormat::Compact => $layer.compact().boxed(),
-                LogFormat::Pretty => $layer.pretty().boxed(),
+                LogFormat::Full => $layer
+                    .fmt_fields(SequenceFields::new(SampledFields::new(
+                        BreadcrumbFields::new(
+                            ContextFields::new(fmt::format::DefaultFields::new()),
+                        ),
+                    )))
+                    .boxed(),
+                LogFormat::Compact => $layer
+                    .compact()
+                    .fmt_fields(SequenceFields::new(SampledFields::new(
+                        BreadcrumbFields::new(
+                            ContextFields::new(fmt::format::DefaultFields::new()),
+                        ),
+                    )))
+                    .boxed(),
+                LogFormat::Pretty => $layer
+                    .pretty()
+                    .fmt_fields(SequenceFields::new(SampledFields::new(
+                        BreadcrumbFields::new(ContextFields::new(fmt::format::Pretty::default())),
+                    )))
+                    .boxed(),
+                LogFormat::Rfc5424 => $layer.event_format(rfc5424.clone()).boxed(),
             }
         };
     }
 
+    // Write to the original streams when native output is being captured
+    let output_capture = app_handle.try_state::<OutputCapture>();
+    let stdout_writer = match &output_capture {
+        Some(capture) => BoxMakeWriter::new(capture.stdout_writer()),
+        None => BoxMakeWriter::new(io::stdout),
+    };
+    let stderr_stream = || match &output_capture {
+        Some(capture) => BoxMakeWriter::new(capture.stderr_writer()),
+        None => BoxMakeWriter::new(io::stderr),
+    };
+    // Severe stdout events go to stderr instead
+    let stdout_writer = match stderr_threshold {
+        Some(threshold) => {
+            use tracing_subscriber::fmt::writer::MakeWriterExt;
+            BoxMakeWriter::new(
+                stderr_stream()
+                    .with_filter(move |metadata: &tracing::Metadata<'_>| {
+                        *metadata.level() <= threshold
+                    })
+                    .or_else(stdout_writer),
+            )
+        }
+        None => stdout_writer,
+    };
+    let stderr_writer = stderr_stream();
+    #[cfg(feature = "colored")]
+    let (stdout_writer, stderr_writer) = match color_theme {
+        Some(theme) => (
+            BoxMakeWriter::new(ThemedWriter::new(stdout_writer, theme.clone())),
+            BoxMakeWriter::new(ThemedWriter::new(stderr_writer, theme)),
+        ),
+        None => (stdout_writer, stderr_writer),
+    };
+    #[cfg(feature = "colored")]
+    let (stdout_writer, stderr_writer) = match editor_links {
+        Some(scheme) => (
+            BoxMakeWriter::new(EditorLinkWriter::new(stdout_writer, scheme.clone())),
+            BoxMakeWriter::new(EditorLinkWriter::new(stderr_writer, scheme)),
+        ),
+        None => (stdout_writer, stderr_writer),
+    };
+    // Keep terminal output from drawing over a running tail
+    #[cfg(feature = "tui")]
+    let (stdout_writer, stderr_writer) = {
+        use tracing_subscriber::fmt::writer::MakeWriterExt;
+        (
+            BoxMakeWriter::new(
+                stdout_writer.with_filter(|_: &tracing::Metadata<'_>| !is_tailing()),
+            ),
+            BoxMakeWriter::new(
+                stderr_writer.with_filter(|_: &tracing::Metadata<'_>| !is_tailing()),
+            ),
+        )
+    };
+
     // Create optional layers based on targets
     let stdout_layer = if has_stdout {
         let layer = fmt::layer()
@@ -1302,8 +4153,9 @@ fn acquire_logger<R: Runtime>(
             .with_thread_ids(format_options.thread_ids)
             .with_thread_names(format_options.thread_names)
             .with_target(format_options.target)
-            .with_level(format_options.level);
-        Some(make_layer!(layer, format_options.format))
+            .with_level(format_options.level)
+            .with_writer(stdout_writer);
+        Some(make_layer!(layer, format_options.format).with_filter(layer_filter(Output::Stdout)))
     } else {
         None
     };
@@ -1318,105 +4170,193 @@ fn acquire_logger<R: Runtime>(
             .with_thread_names(format_options.thread_names)
             .with_target(format_options.target)
             .with_level(format_options.level)
-            .with_writer(io::stderr);
-        Some(make_layer!(layer, format_options.format))
+            .with_writer(stderr_writer);
+        Some(make_layer!(layer, format_options.format).with_filter(layer_filter(Output::Stderr)))
     } else {
         None
     };
 
-    let webview_layer = if has_webview {
-        Some(WebviewLayer::new(app_handle.clone()))
+    #[cfg(feature = "webview")]
+    let webview_layer = targets
+        .iter()
+        .any(|t| matches!(t, Target::Webview))
+        .then(|| WebviewLayer::new(app_handle.clone()).with_filter(layer_filter(Output::Webview)));
+    // Records are not forwarded to webviews without the `webview` feature
+    #[cfg(not(feature = "webview"))]
+    let webview_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    // Updater events are written to a file of their own at every level
+    let update_file_layer = if let Some(file_name) = update_log_file {
+        let log_dir = match &file_config {
+            Some(config) => config.log_dir.clone(),
+            None => app_handle.path().app_log_dir()?,
+        };
+        std::fs::create_dir_all(&log_dir)?;
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_dir.join(format!("{file_name}.log")))?;
+        let update_filter = Targets::new().with_target(UPDATE_TARGET, LevelFilter::TRACE);
+        own_filters.push(update_filter.clone());
+
+        let layer = fmt::layer()
+            .with_timer(make_timer())
+            .with_ansi(false)
+            .with_target(false)
+            .with_writer(std::sync::Mutex::new(file));
+        Some(make_layer!(layer, format_options.format).with_filter(update_filter))
     } else {
         None
     };
 
     // Set up file logging if configured
-    let (file_layer, guard) = if let Some(config) = file_config {
+    #[cfg(feature = "file")]
+    let file_layer = if let Some(mut config) = file_config {
         // Note: cleanup_old_logs only works reliably with time-based rotation
         // When using size-based rotation, files have numeric suffixes that may not sort correctly
         if max_file_size.is_none() {
-            cleanup_old_logs(&config.log_dir, &config.file_name, rotation_strategy)?;
+            match &config.versions {
+                Some((base_dir, file_prefix, layout)) => cleanup_old_version_logs(
+                    base_dir,
+                    file_prefix,
+                    *layout,
+                    &config.log_dir,
+                    rotation_strategy,
+                )?,
+                None => cleanup_old_logs(
+                    &config.log_dir,
+                    &config.file_name,
+                    rotation_strategy,
+                    instance_strategy != InstanceStrategy::Shared,
+                )?,
+            }
         }
 
-        // Use rolling-file crate when max_file_size is set (supports both size and time-based rotation)
-        // Otherwise use tracing-appender (time-based only)
-        if let Some(max_size) = max_file_size {
-            use rolling_file::{BasicRollingFileAppender, RollingConditionBasic};
-
-            // Build rolling condition with both time and size triggers
-            let mut condition = RollingConditionBasic::new();
-            condition = match rotation {
-                Rotation::Daily => condition.daily(),
-                Rotation::Hourly => condition.hourly(),
-                Rotation::Minutely => condition, // rolling-file doesn't have minutely, use size only
-                Rotation::Never => condition,    // size-only rotation
-            };
-            condition = condition.max_size(max_size.0);
-
-            // Determine max file count from rotation strategy
-            let max_files = match rotation_strategy {
-                RotationStrategy::KeepAll => u32::MAX as usize,
-                RotationStrategy::KeepOne => 1,
-                RotationStrategy::KeepSome(n) => n as usize,
-            };
-
-            let log_path = config.log_dir.join(format!("{}.log", config.file_name));
-            let file_appender = BasicRollingFileAppender::new(log_path, condition, max_files)
-                .map_err(std::io::Error::other)?;
+        let base_name = config.file_name.clone();
+        let (file_name, lock) = claim_file_name(&config, instance_strategy)?;
+        (config.file_name, config.file_suffix) = file_name;
+        let is_primary = lock.is_some();
+        if let Some(lock) = lock {
+            app_handle.manage(InstanceLock(lock));
+        }
 
-            let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-            // Wrap with StripAnsiWriter to remove ANSI codes that leak from shared span formatting
-            let strip_ansi_writer = StripAnsiWriter::new(non_blocking);
-
-            let layer = fmt::layer()
-                .with_timer(make_timer())
-                .with_ansi(false)
-                .with_file(format_options.file)
-                .with_line_number(format_options.line_number)
-                .with_thread_ids(format_options.thread_ids)
-                .with_thread_names(format_options.thread_names)
-                .with_target(format_options.target)
-                .with_level(format_options.level)
-                .with_writer(strip_ansi_writer);
-
-            (Some(make_layer!(layer, format_options.format)), Some(guard))
+        // Secondary instances send their lines to the primary if it is running
+        let forward_to = if instance_strategy == InstanceStrategy::Forward && !is_primary {
+            forward::connect(&config.log_dir, &base_name)
         } else {
-            // Time-based rotation only using tracing-appender with proper .log extension
-            use tracing_appender::rolling::RollingFileAppender;
-
-            let appender_rotation = match rotation {
-                Rotation::Daily => tracing_appender::rolling::Rotation::DAILY,
-                Rotation::Hourly => tracing_appender::rolling::Rotation::HOURLY,
-                Rotation::Minutely => tracing_appender::rolling::Rotation::MINUTELY,
-                Rotation::Never => tracing_appender::rolling::Rotation::NEVER,
-            };
-
-            let file_appender = RollingFileAppender::builder()
-                .rotation(appender_rotation)
-                .filename_prefix(&config.file_name)
-                .filename_suffix("log")
-                .build(&config.log_dir)
-                .map_err(std::io::Error::other)?;
+            None
+        };
+
+        let writes_locally = forward_to.is_none();
+
+        let (non_blocking, guard) = match (forward_to, max_file_size) {
+            (Some(stream), _) => tracing_appender::non_blocking(stream),
+            #[cfg(feature = "rotation")]
+            (None, Some(max_size)) => size_rotating_writer(
+                &config,
+                rotation,
+                rotation_strategy,
+                max_size,
+                rotation_hook,
+                sync_policy,
+            )?,
+            (None, _) => {
+                // Time-based rotation only using tracing-appender with proper .log extension
+                use tracing_appender::rolling::RollingFileAppender;
 
-            let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-            // Wrap with StripAnsiWriter to remove ANSI codes that leak from shared span formatting
-            let strip_ansi_writer = StripAnsiWriter::new(non_blocking);
-
-            let layer = fmt::layer()
-                .with_timer(make_timer())
-                .with_ansi(false)
-                .with_file(format_options.file)
-                .with_line_number(format_options.line_number)
-                .with_thread_ids(format_options.thread_ids)
-                .with_thread_names(format_options.thread_names)
-                .with_target(format_options.target)
-                .with_level(format_options.level)
-                .with_writer(strip_ansi_writer);
-
-            (Some(make_layer!(layer, format_options.format)), Some(guard))
+                let appender_rotation = match rotation {
+                    Rotation::Daily => tracing_appender::rolling::Rotation::DAILY,
+                    Rotation::Hourly => tracing_appender::rolling::Rotation::HOURLY,
+                    Rotation::Minutely => tracing_appender::rolling::Rotation::MINUTELY,
+                    Rotation::Never => tracing_appender::rolling::Rotation::NEVER,
+                };
+
+                let file_appender = RollingFileAppender::builder()
+                    .rotation(appender_rotation)
+                    .filename_prefix(&config.file_name)
+                    .filename_suffix(&config.file_suffix)
+                    .build(&config.log_dir)
+                    .map_err(std::io::Error::other)?;
+                let current_path: fsync::CurrentPath = {
+                    let (log_dir, file_name, suffix) = (
+                        config.log_dir.clone(),
+                        config.file_name.clone(),
+                        config.file_suffix.clone(),
+                    );
+                    std::sync::Arc::new(move || {
+                        rotation::current_path(&log_dir, &file_name, &suffix, rotation)
+                    })
+                };
+
+                match rotation_hook {
+                    Some(hook) => tracing_appender::non_blocking(fsync::SyncingWriter::new(
+                        PeriodWatcher::new(
+                            file_appender,
+                            config.log_dir.clone(),
+                            config.file_name.clone(),
+                            config.file_suffix.clone(),
+                            rotation,
+                            hook,
+                        ),
+                        current_path,
+                        sync_policy,
+                    )?),
+                    None => tracing_appender::non_blocking(fsync::SyncingWriter::new(
+                        file_appender,
+                        current_path,
+                        sync_policy,
+                    )?),
+                }
+            }
+        };
+
+        if instance_strategy == InstanceStrategy::Forward && is_primary {
+            forward::listen(&config.log_dir, &base_name, non_blocking.clone())?;
         }
+
+        app_handle.manage(LogFiles::new(
+            config.log_dir.clone(),
+            base_name,
+            config.file_name.clone(),
+            config.file_suffix.clone(),
+            max_file_size.is_some(),
+            instance_strategy != InstanceStrategy::Shared,
+            writes_locally,
+        ));
+
+        // Wrap with StripAnsiWriter to remove ANSI codes that leak from shared span formatting
+        let strip_ansi_writer = StripAnsiWriter::new(non_blocking);
+        // Store the guard in Tauri's state management to ensure logs flush on shutdown
+        app_handle.manage(LogGuard(guard));
+
+        let layer = fmt::layer()
+            .with_timer(make_timer())
+            .with_ansi(false)
+            .with_file(format_options.file)
+            .with_line_number(format_options.line_number)
+            .with_thread_ids(format_options.thread_ids)
+            .with_thread_names(format_options.thread_names)
+            .with_target(format_options.target)
+            .with_level(format_options.level)
+            .with_writer(strip_ansi_writer);
+
+        Some(make_layer!(layer, format_options.format).with_filter(layer_filter(Output::File)))
     } else {
-        (None, None)
+        None
+    };
+    // File targets are ignored without the `file` feature
+    #[cfg(not(feature = "file"))]
+    let file_layer: Option<tracing_subscriber::layer::Identity> = {
+        let _ = (
+            file_config,
+            rotation,
+            rotation_strategy,
+            max_file_size,
+            rotation_hook,
+            instance_strategy,
+            sync_policy,
+        );
+        None
     };
 
     // Create flame layer if flamegraph feature is enabled
@@ -1427,6 +4367,189 @@ fn acquire_logger<R: Runtime>(
         None
     };
 
+    let slow_span_layer = slow_span_threshold.map(SlowSpanLayer::new);
+    let notify_layer = notifications.map(|n| n.layer(app_handle.clone()));
+    // Evaluating scripts for every record is only meant for development
+    let devtools_layer = (devtools_console && tauri::is_dev()).then(|| {
+        DevtoolsConsoleLayer::new(app_handle.clone()).with_filter(layer_filter(Output::Webview))
+    });
+
+    // Span-aware profiles, including the watchdog's and those started from
+    // the frontend, record span timing from a capture of the plugin's own
+    // unless the app manages one
+    #[cfg(feature = "profiling")]
+    let span_timing_layer = if app_handle
+        .try_state::<std::sync::Arc<SpanTimingCapture>>()
+        .is_none()
+    {
+        let (mut layer, capture) = SpanTimingLayer::new();
+        if let Some(fields) = span_timing_fields {
+            layer = layer.with_fields(fields);
+        }
+        if let Some(window) = span_stats_window {
+            layer = layer.with_stats(window);
+        }
+        app_handle.manage(capture);
+        Some(layer)
+    } else {
+        None
+    };
+    #[cfg(feature = "profiling")]
+    let watchdog_layer = profiling_watchdog
+        .map(|watchdog| watchdog.start(app_handle))
+        .transpose()?;
+    #[cfg(not(feature = "profiling"))]
+    let (span_timing_layer, watchdog_layer): (
+        Option<tracing_subscriber::layer::Identity>,
+        Option<tracing_subscriber::layer::Identity>,
+    ) = (None, None);
+
+    // Only the first pipe target is used
+    let pipe_layer = targets
+        .iter()
+        .find_map(|t| match t {
+            Target::Pipe { handle } => Some(PipeLayer::inherited(*handle)),
+            _ => None,
+        })
+        .transpose()?
+        .map(|layer| layer.with_filter(layer_filter(Output::Pipe)));
+
+    // Only the first GELF target is used
+    #[cfg(feature = "gelf")]
+    let gelf_layer = targets
+        .iter()
+        .find_map(|t| match t {
+            Target::Gelf {
+                host,
+                port,
+                transport,
+            } => Some(GelfLayer::new(host.clone(), *port, *transport)),
+            _ => None,
+        })
+        .transpose()?
+        .map(|layer| {
+            layer
+                .with_attributes(resource_attributes.iter().cloned())
+                .with_filter(layer_filter(Output::Gelf))
+        });
+    #[cfg(not(feature = "gelf"))]
+    let gelf_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    // Only the first Loki target is used
+    #[cfg(feature = "loki")]
+    let loki_layer = targets
+        .iter()
+        .find_map(|t| match t {
+            Target::Loki { url, labels } => {
+                let package = app_handle.package_info();
+                let mut all_labels = vec![
+                    ("app".to_string(), package.name.clone()),
+                    ("version".to_string(), package.version.to_string()),
+                ];
+                all_labels.extend(labels.iter().cloned());
+                Some(LokiLayer::new(url.clone(), all_labels))
+            }
+            _ => None,
+        })
+        .transpose()?
+        .map(|layer| {
+            layer
+                .with_attributes(resource_attributes.iter().cloned())
+                .with_filter(layer_filter(Output::Loki))
+        });
+    #[cfg(not(feature = "loki"))]
+    let loki_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    // Only the first OTLP target is used
+    #[cfg(feature = "otlp")]
+    let otlp_layer = targets
+        .iter()
+        .find_map(|t| match t {
+            Target::Otlp { url, headers } => {
+                let package = app_handle.package_info();
+                let mut resource = vec![
+                    ("service.name".to_string(), package.name.clone()),
+                    ("service.version".to_string(), package.version.to_string()),
+                    ("os.type".to_string(), os_type().to_string()),
+                ];
+                resource.extend(resource_attributes.iter().cloned());
+                resource.extend(otlp_resource.iter().cloned());
+                Some(OtlpLayer::new(url.clone(), headers.clone(), resource))
+            }
+            _ => None,
+        })
+        .transpose()?
+        .map(|layer| layer.with_filter(layer_filter(Output::Otlp)));
+    #[cfg(not(feature = "otlp"))]
+    let otlp_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    // Only the first unified logging target is used
+    #[cfg(target_vendor = "apple")]
+    let os_log_layer = targets.iter().find_map(|t| match t {
+        Target::OsLog { subsystem } => {
+            let subsystem = subsystem
+                .clone()
+                .unwrap_or_else(|| app_handle.config().identifier.clone());
+            Some(
+                os_log_categories
+                    .iter()
+                    .fold(OsLogLayer::new(subsystem), |layer, (target, category)| {
+                        layer.with_category(target, category)
+                    })
+                    .with_filter(layer_filter(Output::OsLog)),
+            )
+        }
+        _ => None,
+    });
+    #[cfg(not(target_vendor = "apple"))]
+    let os_log_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    // The global filter lets through what any output records. The filter in
+    // effect changes at runtime through the managed FilterHandle, which
+    // updates the outputs without a filter of their own first
+    let (filter_layer, reload_handle) = tracing_subscriber::reload::Layer::new(filter::union(
+        &std::iter::once(&*current_filter.read().unwrap_or_else(|e| e.into_inner()))
+            .chain(&own_filters)
+            .collect::<Vec<_>>(),
+    ));
+    // Events the global filter drops are counted when enabled
+    let dropped_events = filter_stats.then(DroppedEvents::default);
+    if let Some(dropped) = &dropped_events {
+        app_handle.manage(dropped.clone());
+    }
+    let filter_handle = FilterHandle::new(
+        filter_with_default,
+        overrides,
+        Box::new(move |targets| {
+            let global = filter::union(
+                &std::iter::once(&targets)
+                    .chain(&own_filters)
+                    .collect::<Vec<_>>(),
+            );
+            *current_filter.write().unwrap_or_else(|e| e.into_inner()) = targets;
+            reload_handle
+                .reload(global)
+                .map_err(|_| Error::LoggerNotInitialized)
+        }),
+        store_path,
+    );
+
+    // The history is searched by logs_around_mark through Tauri state
+    if let Some(history) = &log_history {
+        app_handle.manage(history.clone());
+    }
+    // Tests are delimited by the begin_test and end_test commands
+    if let Some(capture) = &test_capture {
+        app_handle.manage(capture.clone());
+    }
+
+    // Counts events per crate while analyze_noise runs
+    let noise_analyzer = NoiseAnalyzer::new();
+    app_handle.manage(noise_analyzer.clone());
+
+    // Set in the plugin's setup, through the builder or from JavaScript
+    let log_context = app_handle.state::<LogContext>().inner().clone();
+
     // Create custom filter layer if configured
     let custom_filter_layer = custom_filter.map(|f| filter_fn(move |metadata| f(metadata)));
 
@@ -1452,11 +4575,45 @@ fn acquire_logger<R: Runtime>(
         .with(stdout_layer)
         .with(stderr_layer)
         .with(file_layer)
+        .with(update_file_layer)
         .with(webview_layer)
+        .with(slow_span_layer)
+        .with(notify_layer)
+        .with(devtools_layer)
+        .with(span_timing_layer)
+        .with(watchdog_layer)
+        .with(pipe_layer)
+        .with(gelf_layer)
+        .with(loki_layer)
+        .with(otlp_layer)
+        .with(os_log_layer)
+        // Breadcrumbs sit outside the outputs so they see the trail of errors
+        .with(breadcrumb_layer)
+        .with(log_history)
+        .with(test_capture)
+        .with(noise_analyzer)
+        // The context is captured for each event before the outputs see it
+        .with(log_context)
+        // Events are numbered once they pass the filter and sampling
+        .with(sequence_layer)
+        // Sampling sits outside the outputs so they see the rate of kept events
+        .with(sampling_layer)
         .with(custom_filter_layer)
-        .with(filter_with_default);
+        .with(CountingFilter::new(
+            dropped_events,
+            GlobFilter::new(
+                target_globs.clone(),
+                SpanFilter::new(span_filter, filter_layer),
+            ),
+        ))
+        // Suppressed events are disabled before any other layer sees them
+        .with(SuppressionLayer);
 
-    tracing::subscriber::set_global_default(subscriber)?;
+    tracing::subscriber::set_global_default(subscriber).map_err(|_| Error::SubscriberAlreadySet)?;
     tracing::info!("tracing initialized");
-    Ok(guard)
+    if restored_filter {
+        tracing::info!(filter = %filter_handle.current(), "restored persisted log filter");
+    }
+    app_handle.manage(filter_handle);
+    Ok(())
 }
@@ -0,0 +1,204 @@
+//! Disk usage of the file target, for settings UIs.
+//!
+//! When the default subscriber writes to a file, the plugin manages a
+//! [`LogFiles`] handle. The `get_log_stats` command returns [`LogStats`], so
+//! a settings page can show "Logs are using 48 MB", and `clear_logs` deletes
+//! the logs behind a "Clear logs" button.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// The size of the file target's logs, as returned by `get_log_stats`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogStats {
+    /// The directory logs are written to, or `None` without a file target.
+    pub log_dir: Option<PathBuf>,
+    /// The file this instance is writing to.
+    ///
+    /// `None` for secondary instances that forward their logs, see
+    /// [`InstanceStrategy::Forward`](crate::InstanceStrategy::Forward).
+    pub current_file: Option<PathBuf>,
+    /// The size of the current file in bytes.
+    pub current_file_bytes: u64,
+    /// The number of log files other than the current one, including files
+    /// of other instances.
+    pub rotated_files: usize,
+    /// The size of all log files in bytes.
+    pub total_bytes: u64,
+    /// When the newest rotated file was last written to, in milliseconds
+    /// since the Unix epoch. This is close to when it was rotated.
+    pub last_rotation_ms: Option<f64>,
+}
+
+/// The log files of the default subscriber's file target.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_default_subscriber()`](crate::Builder::with_default_subscriber)
+/// is used with a file target. Files are matched like retention with
+/// [`Builder::with_rotation_strategy()`](crate::Builder::with_rotation_strategy)
+/// does, so other files in the log directory are left alone.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri::Manager;
+/// # use tauri_plugin_tracing::LogFiles;
+/// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+/// if let Some(logs) = app.try_state::<LogFiles>() {
+///     let stats = logs.stats()?;
+///     println!("Logs are using {} bytes", stats.total_bytes);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogFiles {
+    log_dir: PathBuf,
+    /// The configured file name prefix.
+    base_name: String,
+    /// The prefix this instance writes to, which includes the process ID
+    /// for some instance strategies.
+    file_name: String,
+    /// What follows the date in this instance's file names.
+    file_suffix: String,
+    /// Whether `rolling-file` names the files, as `app.log`, `app.log.1`, ...
+    size_based: bool,
+    /// Whether files of other instances count as this target's logs.
+    all_instances: bool,
+    /// Whether this instance writes to its own file rather than forwarding.
+    writes_locally: bool,
+}
+
+impl LogFiles {
+    pub(crate) fn new(
+        log_dir: PathBuf,
+        base_name: String,
+        file_name: String,
+        file_suffix: String,
+        size_based: bool,
+        all_instances: bool,
+        writes_locally: bool,
+    ) -> Self {
+        Self {
+            log_dir,
+            base_name,
+            file_name,
+            file_suffix,
+            size_based,
+            all_instances,
+            writes_locally,
+        }
+    }
+
+    /// Returns the directory logs are written to.
+    pub fn log_dir(&self) -> &Path {
+        &self.log_dir
+    }
+
+    /// Returns the file this instance is currently writing to.
+    pub fn current_file(&self) -> Option<PathBuf> {
+        if !self.writes_locally {
+            return None;
+        }
+        let undated = format!("{}.{}", self.file_name, self.file_suffix);
+        if self.size_based {
+            return Some(self.log_dir.join(undated));
+        }
+        // Time-based names differ only in the date, so the newest sorts last.
+        // Other instances' names may share the prefix, but not the suffix.
+        let (prefix, suffix) = (
+            format!("{}.", self.file_name),
+            format!(".{}", self.file_suffix),
+        );
+        std::fs::read_dir(&self.log_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| {
+                *name == undated
+                    || name
+                        .strip_prefix(&prefix)
+                        .and_then(|rest| rest.strip_suffix(&suffix))
+                        .is_some_and(|date| !date.contains('.'))
+            })
+            .max()
+            .map(|name| self.log_dir.join(name))
+    }
+
+    /// Returns the number and size of the log files.
+    pub fn stats(&self) -> crate::Result<LogStats> {
+        let current_file = self.current_file();
+        let mut stats = LogStats {
+            log_dir: Some(self.log_dir.clone()),
+            ..Default::default()
+        };
+        let mut last_rotation: Option<SystemTime> = None;
+        for (path, metadata) in self.files()? {
+            stats.total_bytes += metadata.len();
+            if current_file.as_ref() == Some(&path) {
+                stats.current_file_bytes = metadata.len();
+                continue;
+            }
+            stats.rotated_files += 1;
+            if let Ok(modified) = metadata.modified() {
+                last_rotation = last_rotation.max(Some(modified));
+            }
+        }
+        stats.current_file = current_file;
+        stats.last_rotation_ms = last_rotation
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|since| since.as_secs_f64() * 1000.0);
+        Ok(stats)
+    }
+
+    /// Deletes the rotated log files and empties the current one, returning
+    /// the stats afterwards.
+    ///
+    /// The current file stays open for writing, so it is truncated rather
+    /// than deleted. Files that cannot be removed, e.g. ones still open in
+    /// another instance on Windows, are skipped with a warning.
+    pub fn clear(&self) -> crate::Result<LogStats> {
+        let current_file = self.current_file();
+        for (path, _) in self.files()? {
+            let result = if current_file.as_ref() == Some(&path) {
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|file| file.set_len(0))
+            } else {
+                std::fs::remove_file(&path)
+            };
+            if let Err(e) = result {
+                tracing::warn!("Failed to clear log file {:?}: {}", path, e);
+            }
+        }
+        tracing::info!(log_dir = %self.log_dir.display(), "Log files cleared");
+        self.stats()
+    }
+
+    /// Returns the log files of the target, most recently written first.
+    pub(crate) fn newest_first(&self) -> crate::Result<Vec<PathBuf>> {
+        let mut files = self.files()?;
+        files.sort_by_key(|(_, metadata)| std::cmp::Reverse(metadata.modified().ok()));
+        Ok(files.into_iter().map(|(path, _)| path).collect())
+    }
+
+    /// Returns the log files of the target and their metadata.
+    fn files(&self) -> crate::Result<Vec<(PathBuf, std::fs::Metadata)>> {
+        Ok(std::fs::read_dir(&self.log_dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.file_name().to_str().is_some_and(|name| {
+                    crate::is_log_file(name, &self.base_name, self.all_instances)
+                })
+            })
+            .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?)))
+            .filter(|(_, metadata)| metadata.is_file())
+            .collect())
+    }
+}
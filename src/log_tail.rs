@@ -0,0 +1,281 @@
+//! Live views of the file target's logs.
+//!
+//! A "live log view" screen needs the latest lines and then every new one,
+//! without re-reading the files. [`tail_logs()`], or `tailLogs()` in
+//! JavaScript, returns the last lines of the file this instance writes to,
+//! and with `follow` keeps watching it: new lines are emitted as
+//! [`TailLines`] on the [`TAIL_EVENT`] event until [`stop_tail_logs()`] is
+//! called. Following continues into the next file when the logs rotate.
+//!
+//! Lines are read from the file target, so they are formatted like in the
+//! file, and only complete lines are returned.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::LogFiles;
+use crate::plugin_name::channel;
+
+/// The event new lines are emitted on while following.
+pub const TAIL_EVENT: &str = "tracing://tail";
+
+/// How often the followed file is checked for new lines.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The last lines of the logs, as returned by [`tail_logs()`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LogTail {
+    /// The file the lines were read from, or `None` without a file target or
+    /// for instances that forward their logs.
+    pub path: Option<PathBuf>,
+    /// The lines, oldest first.
+    pub lines: Vec<String>,
+    /// Whether new lines are emitted on the [`TAIL_EVENT`] event.
+    pub following: bool,
+}
+
+/// The payload of the [`TAIL_EVENT`] event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TailLines {
+    /// The file the lines were read from.
+    pub path: PathBuf,
+    /// The new lines, oldest first.
+    pub lines: Vec<String>,
+}
+
+/// The follower of the file target's logs, if one is running.
+///
+/// The plugin manages an instance of this type in Tauri state.
+#[derive(Debug, Default)]
+pub struct LogTailer {
+    /// Set to stop the running follower.
+    stop: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl LogTailer {
+    /// Starts following `path` from `offset`, unless a follower is running.
+    fn follow<R: Runtime>(
+        &self,
+        app: AppHandle<R>,
+        files: LogFiles,
+        path: PathBuf,
+        offset: u64,
+    ) -> crate::Result<()> {
+        let mut running = self.stop.lock().unwrap_or_else(|e| e.into_inner());
+        if running.is_some() {
+            return Ok(());
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let follower = Follower {
+            files,
+            path,
+            offset,
+        };
+        let stopped = stop.clone();
+        std::thread::Builder::new()
+            .name("tracing-tail".to_string())
+            .spawn(move || follower.run(&app, &stopped))?;
+        *running = Some(stop);
+        Ok(())
+    }
+
+    /// Stops the running follower, if any.
+    pub fn stop(&self) {
+        let running = self.stop.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(stop) = running {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns whether new lines are being emitted.
+    pub fn is_following(&self) -> bool {
+        self.stop
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+}
+
+/// Returns the last `lines` lines of the file this instance writes to, and
+/// with `follow`, starts emitting new lines on the [`TAIL_EVENT`] event.
+///
+/// Only one follower runs at a time, shared by every caller, and it runs
+/// until [`stop_tail_logs()`] is called. Without a file target, no lines are
+/// returned and nothing is followed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+/// let tail = tauri_plugin_tracing::tail_logs(app, 200, false)?;
+/// for line in &tail.lines {
+///     println!("{line}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn tail_logs<R: Runtime>(
+    app: &AppHandle<R>,
+    lines: usize,
+    follow: bool,
+) -> crate::Result<LogTail> {
+    let Some(files) = app.try_state::<LogFiles>() else {
+        return Ok(LogTail::default());
+    };
+    let Some(path) = files.current_file() else {
+        return Ok(LogTail::default());
+    };
+    let (lines, offset) = last_lines(&path, lines)?;
+    if follow && let Some(tailer) = app.try_state::<LogTailer>() {
+        tailer.follow(app.clone(), files.inner().clone(), path.clone(), offset)?;
+    }
+    Ok(LogTail {
+        path: Some(path),
+        lines,
+        following: app
+            .try_state::<LogTailer>()
+            .is_some_and(|tailer| tailer.is_following()),
+    })
+}
+
+/// Stops emitting new lines started by [`tail_logs()`].
+pub fn stop_tail_logs<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(tailer) = app.try_state::<LogTailer>() {
+        tailer.stop();
+    }
+}
+
+/// Returns the last `count` complete lines of the file at `path`, and the
+/// offset after them.
+fn last_lines(path: &Path, count: usize) -> std::io::Result<(Vec<String>, u64)> {
+    const CHUNK: u64 = 64 * 1024;
+
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    // Read backwards until the chunk holds enough lines
+    let mut start = len;
+    let mut buf = Vec::new();
+    loop {
+        let next = start.saturating_sub(CHUNK);
+        let mut chunk = vec![0; (start - next) as usize];
+        file.seek(SeekFrom::Start(next))?;
+        file.read_exact(&mut chunk)?;
+        chunk.append(&mut buf);
+        buf = chunk;
+        start = next;
+        if start == 0 || buf.iter().filter(|b| **b == b'\n').count() > count {
+            break;
+        }
+    }
+
+    let (lines, complete) = complete_lines(&buf);
+    let skip = lines.len().saturating_sub(count);
+    Ok((
+        lines.into_iter().skip(skip).collect(),
+        start + complete as u64,
+    ))
+}
+
+/// Splits `buf` into its complete lines, returning them and their length in
+/// bytes. A line being written is left for the next read.
+fn complete_lines(buf: &[u8]) -> (Vec<String>, usize) {
+    let Some(end) = buf.iter().rposition(|b| *b == b'\n') else {
+        return (Vec::new(), 0);
+    };
+    let lines = String::from_utf8_lossy(&buf[..end])
+        .split('\n')
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .collect();
+    (lines, end + 1)
+}
+
+/// Follows the file target's current file.
+struct Follower {
+    files: LogFiles,
+    path: PathBuf,
+    /// The offset after the last emitted line.
+    offset: u64,
+}
+
+impl Follower {
+    fn run<R: Runtime>(mut self, app: &AppHandle<R>, stop: &AtomicBool) {
+        while !stop.load(Ordering::Relaxed) {
+            std::thread::sleep(POLL_INTERVAL);
+            self.emit_new_lines(app);
+            // The logs rotated to a new file, so finish the old one first
+            if let Some(current) = self.files.current_file()
+                && current != self.path
+            {
+                self.path = current;
+                self.offset = 0;
+                self.emit_new_lines(app);
+            }
+        }
+    }
+
+    fn emit_new_lines<R: Runtime>(&mut self, app: &AppHandle<R>) {
+        let Ok(mut file) = std::fs::File::open(&self.path) else {
+            return;
+        };
+        let Ok(len) = file.metadata().map(|metadata| metadata.len()) else {
+            return;
+        };
+        // Cleared, or renamed away by size-based rotation
+        if len < self.offset {
+            self.offset = 0;
+        }
+        if len == self.offset {
+            return;
+        }
+        let mut buf = Vec::new();
+        if file.seek(SeekFrom::Start(self.offset)).is_err() || file.read_to_end(&mut buf).is_err() {
+            return;
+        }
+        let (lines, complete) = complete_lines(&buf);
+        if lines.is_empty() {
+            return;
+        }
+        self.offset += complete as u64;
+        let _ = app.emit(
+            &channel(app, TAIL_EVENT),
+            TailLines {
+                path: self.path.clone(),
+                lines,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_lines_leaves_the_line_being_written() {
+        let path = std::env::temp_dir().join(format!("tracing-tail-{}.log", std::process::id()));
+        std::fs::write(&path, "one\ntwo\r\nthree\nfou").unwrap();
+
+        let (lines, offset) = last_lines(&path, 2).unwrap();
+        assert_eq!(lines, ["two", "three"]);
+        assert_eq!(offset, 15);
+        let (lines, _) = last_lines(&path, 10).unwrap();
+        assert_eq!(lines, ["one", "two", "three"]);
+        let (lines, _) = last_lines(&path, 0).unwrap();
+        assert!(lines.is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,351 @@
+//! Grafana Loki output.
+//!
+//! Requires the `loki` feature. [`LokiLayer`] batches events and pushes them
+//! to a Loki server's
+//! [push API](https://grafana.com/docs/loki/latest/reference/loki-http-api/#ingest-logs):
+//!
+//! - Each event becomes one JSON log line with its `message`, `target`,
+//!   source location, span names as `spans`, and other fields, so it can be
+//!   queried with LogQL's `| json` parser.
+//! - Streams are labeled with the configured labels, the event's `level`, and
+//!   for records from a webview, the `window` label.
+//!
+//! Events are sent from a background thread, at least once a second or every
+//! 100 events. If the server cannot keep up, new events are dropped, and a
+//! batch that fails to send is discarded.
+//!
+//! Logs pushed from end-user machines may contain personal data. Only add
+//! the target for users who opted in.
+
+use std::collections::BTreeMap;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, SyncSender};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{Map, Value};
+use tracing::Subscriber;
+use tracing::span::{Attributes, Id};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Number of events queued for sending before new ones are dropped.
+const QUEUE_CAPACITY: usize = 4096;
+
+/// Most events pushed in one request.
+const MAX_BATCH_LEN: usize = 100;
+
+/// Longest an event waits in a batch before it is pushed.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Timeout for a push request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A tracing layer that pushes events to Grafana Loki.
+///
+/// # Example
+///
+/// Use [`Target::Loki`](crate::Target::Loki) with the default subscriber, or
+/// add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::LokiLayer;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// # fn main() -> tauri_plugin_tracing::Result<()> {
+/// Registry::default()
+///     .with(LokiLayer::new(
+///         "https://loki.example.com/loki/api/v1/push",
+///         [("app", "my-app"), ("version", "1.0.0")],
+///     )?)
+///     .init();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LokiLayer {
+    sender: SyncSender<LokiEntry>,
+    attributes: Map<String, Value>,
+}
+
+impl LokiLayer {
+    /// Creates a layer that pushes to `url`, the server's
+    /// `/loki/api/v1/push` endpoint, labeling every stream with `labels`.
+    pub fn new<K, V>(
+        url: impl Into<String>,
+        labels: impl IntoIterator<Item = (K, V)>,
+    ) -> crate::Result<Self>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(QUEUE_CAPACITY);
+        let url = url.into();
+        let labels: BTreeMap<String, String> = labels
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+        std::thread::Builder::new()
+            .name("tracing-loki".to_string())
+            .spawn(move || send_batches(receiver, &url, &labels))?;
+
+        Ok(Self {
+            sender,
+            attributes: Map::new(),
+        })
+    }
+
+    /// Adds `attributes` to every log line as top-level fields, e.g.
+    /// `service.name`.
+    ///
+    /// Unlike labels, attributes do not create streams, so they suit values
+    /// with many distinct values such as an install id. Event fields with the
+    /// same name take precedence.
+    pub fn with_attributes<K, V>(mut self, attributes: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.attributes.extend(
+            attributes
+                .into_iter()
+                .map(|(key, value)| (key.into(), Value::String(value.into()))),
+        );
+        self
+    }
+}
+
+/// The webview label of a span, recorded from its `w` field.
+struct WindowLabel(String);
+
+impl<S> Layer<S> for LokiLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().fields().field("w").is_none() {
+            return;
+        }
+        let mut visitor = WindowVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(label), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(WindowLabel(label));
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = LokiVisitor::default();
+        event.record(&mut visitor);
+
+        let mut line = Map::new();
+        line.insert(
+            "message".into(),
+            visitor.message.take().unwrap_or_default().into(),
+        );
+        line.insert("target".into(), metadata.target().into());
+        if let Some(file) = metadata.file() {
+            line.insert("file".into(), file.into());
+        }
+        if let Some(number) = metadata.line() {
+            line.insert("line".into(), number.into());
+        }
+        let mut window = None;
+        if let Some(scope) = ctx.event_scope(event) {
+            let mut spans = Vec::new();
+            for span in scope.from_root() {
+                if let Some(label) = span.extensions().get::<WindowLabel>() {
+                    window = Some(label.0.clone());
+                }
+                spans.push(span.name());
+            }
+            line.insert("spans".into(), spans.join(":").into());
+        }
+        if let Some(rate) = crate::current_sample_rate() {
+            line.insert("sampled".into(), rate.to_string().into());
+        }
+        if let Some(seq) = crate::current_sequence() {
+            line.insert("seq".into(), seq.into());
+        }
+        for (key, value) in visitor.fields {
+            line.entry(key).or_insert(value);
+        }
+        crate::context::with_current_context(|entries| {
+            for (key, value) in entries {
+                line.entry(key.clone())
+                    .or_insert_with(|| Value::String(value.clone()));
+            }
+        });
+        for (key, value) in &self.attributes {
+            line.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+
+        let Ok(line) = serde_json::to_string(&Value::Object(line)) else {
+            return;
+        };
+        let entry = LokiEntry {
+            level: metadata.level().as_str().to_lowercase(),
+            window,
+            timestamp_ns: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+            line,
+        };
+        // Drop the event if the queue is full rather than block the caller
+        let _ = self.sender.try_send(entry);
+    }
+}
+
+/// An event waiting to be pushed.
+struct LokiEntry {
+    level: String,
+    window: Option<String>,
+    timestamp_ns: u128,
+    line: String,
+}
+
+/// Reads the `w` field of a webview span.
+struct WindowVisitor(Option<String>);
+
+impl tracing::field::Visit for WindowVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "w" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+/// Collects the message and other fields of an event.
+#[derive(Default)]
+struct LokiVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl LokiVisitor {
+    fn insert(&mut self, field: &tracing::field::Field, value: Value) {
+        match field.name() {
+            "message" => {
+                self.message = Some(match value {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                });
+            }
+            // The location of webview records
+            "" => {
+                self.fields.insert("location".into(), value);
+            }
+            name => {
+                self.fields.insert(name.into(), value);
+            }
+        }
+    }
+}
+
+impl tracing::field::Visit for LokiVisitor {
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        // Non-finite numbers are not valid JSON
+        match serde_json::Number::from_f64(value) {
+            Some(number) => self.insert(field, Value::Number(number)),
+            None => self.insert(field, Value::String(value.to_string())),
+        }
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.insert(field, value.to_string().into());
+        for (n, source) in crate::error_chain::sources(value).enumerate() {
+            let name = crate::error_chain::source_field(field.name(), n + 1);
+            self.fields.insert(name, source.into());
+        }
+    }
+}
+
+fn send_batches(receiver: Receiver<LokiEntry>, url: &str, labels: &BTreeMap<String, String>) {
+    let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+    let mut batch = Vec::new();
+    let mut started: Option<Instant> = None;
+
+    loop {
+        let wait = started.map_or(FLUSH_INTERVAL, |started| {
+            FLUSH_INTERVAL.saturating_sub(started.elapsed())
+        });
+        match receiver.recv_timeout(wait) {
+            Ok(entry) => {
+                let started = *started.get_or_insert_with(Instant::now);
+                batch.push(entry);
+                if batch.len() < MAX_BATCH_LEN && started.elapsed() < FLUSH_INTERVAL {
+                    continue;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) if batch.is_empty() => continue,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                push(&agent, url, labels, std::mem::take(&mut batch));
+                return;
+            }
+        }
+        push(&agent, url, labels, std::mem::take(&mut batch));
+        started = None;
+    }
+}
+
+/// Pushes a batch, grouping entries into streams by their labels.
+fn push(agent: &ureq::Agent, url: &str, labels: &BTreeMap<String, String>, batch: Vec<LokiEntry>) {
+    if batch.is_empty() {
+        return;
+    }
+    let mut streams: BTreeMap<(String, Option<String>), Vec<Value>> = BTreeMap::new();
+    for entry in batch {
+        streams
+            .entry((entry.level, entry.window))
+            .or_default()
+            .push(Value::from(vec![
+                Value::from(entry.timestamp_ns.to_string()),
+                Value::from(entry.line),
+            ]));
+    }
+    let streams: Vec<Value> = streams
+        .into_iter()
+        .map(|((level, window), values)| {
+            let mut stream: Map<String, Value> = labels
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone().into()))
+                .collect();
+            stream.insert("level".into(), level.into());
+            if let Some(window) = window {
+                stream.insert("window".into(), window.into());
+            }
+            serde_json::json!({ "stream": stream, "values": values })
+        })
+        .collect();
+
+    let body = serde_json::json!({ "streams": streams }).to_string();
+    // A failed batch is dropped; retrying could pile up behind an outage
+    let _ = agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&body);
+}
@@ -0,0 +1,191 @@
+//! Log context around user actions.
+//!
+//! "What happened right when I clicked X?" is hard to answer from a log file
+//! with thousands of lines. [`mark()`], or `mark()` in JavaScript, records a
+//! named marker event, and [`logs_around_mark()`], or `getLogsAroundMark()`,
+//! returns the records just before and after the latest marker with that
+//! label:
+//!
+//! - From the in-memory history kept with
+//!   [`Builder::with_log_history()`](crate::Builder::with_log_history), if the
+//!   marker is still in it.
+//! - Otherwise from the file target's logs, newest file first, as formatted
+//!   lines. This needs targets in the file output, which is the default.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+use crate::breadcrumbs::breadcrumb_from_event;
+use crate::{Breadcrumb, LogFiles};
+
+/// The target of marker events.
+pub const MARK_TARGET: &str = "tauri_plugin_tracing::mark";
+
+/// Records a marker event with `label` as its message.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// tauri_plugin_tracing::mark("checkout clicked");
+/// ```
+pub fn mark(label: &str) {
+    tracing::info!(target: MARK_TARGET, "{label}");
+}
+
+/// The records around a marker, as returned by `get_logs_around_mark`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "source", rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum MarkContext {
+    /// The marker was found in the in-memory history.
+    #[serde(rename_all = "camelCase")]
+    Memory {
+        /// The records before the marker, oldest first.
+        before: Vec<Breadcrumb>,
+        /// The marker event.
+        mark: Breadcrumb,
+        /// The records after the marker, oldest first.
+        after: Vec<Breadcrumb>,
+    },
+    /// The marker was found in a log file.
+    ///
+    /// Lines do not extend into other files.
+    #[serde(rename_all = "camelCase")]
+    File {
+        /// The file the marker was found in.
+        path: std::path::PathBuf,
+        /// The lines before the marker.
+        before: Vec<String>,
+        /// The marker line.
+        mark: String,
+        /// The lines after the marker.
+        after: Vec<String>,
+    },
+}
+
+/// Returns up to `before` records before and `after` records after the
+/// latest marker recorded with `label`, or `None` if it cannot be found.
+pub fn logs_around_mark<R: Runtime>(
+    app: &AppHandle<R>,
+    label: &str,
+    before: usize,
+    after: usize,
+) -> crate::Result<Option<MarkContext>> {
+    if let Some(history) = app.try_state::<LogHistory>()
+        && let Some(context) = history.around_mark(label, before, after)
+    {
+        return Ok(Some(context));
+    }
+    let Some(files) = app.try_state::<LogFiles>() else {
+        return Ok(None);
+    };
+    let suffix = format!(" {label}");
+    for path in files.newest_first()? {
+        let contents = String::from_utf8_lossy(&std::fs::read(&path)?).into_owned();
+        let lines: Vec<&str> = contents.lines().collect();
+        let Some(index) = lines
+            .iter()
+            .rposition(|line| line.contains(MARK_TARGET) && line.trim_end().ends_with(&suffix))
+        else {
+            continue;
+        };
+        let to_strings =
+            |lines: &[&str]| -> Vec<String> { lines.iter().map(|line| line.to_string()).collect() };
+        let end = (index + 1 + after).min(lines.len());
+        return Ok(Some(MarkContext::File {
+            path,
+            before: to_strings(&lines[index.saturating_sub(before)..index]),
+            mark: lines[index].to_string(),
+            after: to_strings(&lines[index + 1..end]),
+        }));
+    }
+    Ok(None)
+}
+
+/// A tracing layer that keeps the most recent events in memory.
+///
+/// Clones share the same history. The plugin manages an instance in Tauri
+/// state when [`Builder::with_log_history()`](crate::Builder::with_log_history)
+/// is used, and [`logs_around_mark()`] searches it before the log files.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::LogHistory;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// let history = LogHistory::new(1000);
+/// Registry::default().with(history.clone()).init();
+///
+/// tauri_plugin_tracing::mark("sync started");
+/// let context = history.around_mark("sync started", 20, 20);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogHistory {
+    records: Arc<Mutex<VecDeque<Breadcrumb>>>,
+    capacity: usize,
+}
+
+impl LogHistory {
+    /// Creates a history that keeps the last `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Returns the number of events kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns a copy of the events recorded so far, oldest first.
+    pub fn records(&self) -> Vec<Breadcrumb> {
+        self.records
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns up to `before` events before and `after` events after the
+    /// latest marker recorded with `label`, or `None` if it is not in the
+    /// history.
+    pub fn around_mark(&self, label: &str, before: usize, after: usize) -> Option<MarkContext> {
+        let records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        let index = records
+            .iter()
+            .rposition(|record| record.target == MARK_TARGET && record.message == label)?;
+        let end = (index + 1 + after).min(records.len());
+        Some(MarkContext::Memory {
+            before: records
+                .range(index.saturating_sub(before)..index)
+                .cloned()
+                .collect(),
+            mark: records[index].clone(),
+            after: records.range(index + 1..end).cloned().collect(),
+        })
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogHistory {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let record = breadcrumb_from_event(event);
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+}
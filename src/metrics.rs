@@ -0,0 +1,185 @@
+//! Lightweight metrics for live dashboards.
+//!
+//! Apps that show a few live numbers, such as frames per second, queue depth,
+//! or sync lag, need more than logs but less than a metrics pipeline.
+//! [`gauge()`] sets a metric to a value and [`counter()`] adds to one, from
+//! Rust or with the JavaScript `gauge()` and `counter()` helpers. Each call is
+//! logged as a `TRACE` event under the [`METRICS_TARGET`] target, with the
+//! `metric` field and the `value` or `inc` field, so metrics are off in logs
+//! unless the target's level allows them.
+//!
+//! With [`Builder::with_metrics_events()`](crate::Builder::with_metrics_events),
+//! calls are also batched and emitted to the app as the [`METRICS_EVENT`]
+//! event once per interval, with the latest value of each gauge and the
+//! increments of each counter since the previous batch, for `onMetrics()`
+//! listeners. Intervals without calls emit nothing.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::plugin_name::channel;
+
+/// The target used for metric events.
+pub const METRICS_TARGET: &str = "app::metrics";
+
+/// The event emitted to the app with batches of metrics.
+pub const METRICS_EVENT: &str = "tracing://metrics";
+
+/// The shortest interval between batches.
+const MIN_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The metrics recorded since the previous batch, once batches are emitted.
+static PENDING: Mutex<Option<Pending>> = Mutex::new(None);
+
+/// The payload of the [`METRICS_EVENT`] event.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct MetricsBatch {
+    /// The latest value of each gauge set since the previous batch.
+    pub gauges: BTreeMap<String, f64>,
+    /// The increments of each counter since the previous batch.
+    pub counters: BTreeMap<String, u64>,
+    /// The totals since the app started of the counters in `counters`.
+    pub totals: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    batch: MetricsBatch,
+    totals: BTreeMap<String, u64>,
+}
+
+impl Pending {
+    fn gauge(&mut self, name: &str, value: f64) {
+        self.batch.gauges.insert(name.to_string(), value);
+    }
+
+    fn counter(&mut self, name: &str, inc: u64) {
+        let count = self.batch.counters.entry(name.to_string()).or_default();
+        *count = count.saturating_add(inc);
+        let total = self.totals.entry(name.to_string()).or_default();
+        *total = total.saturating_add(inc);
+    }
+
+    /// Returns the metrics recorded since the previous batch, if any.
+    fn take(&mut self) -> Option<MetricsBatch> {
+        if self.batch.gauges.is_empty() && self.batch.counters.is_empty() {
+            return None;
+        }
+        let mut batch = std::mem::take(&mut self.batch);
+        batch.totals = batch
+            .counters
+            .keys()
+            .map(|name| (name.clone(), self.totals[name]))
+            .collect();
+        Some(batch)
+    }
+}
+
+/// Sets the gauge `name` to `value`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # let queue: Vec<u8> = Vec::new();
+/// tauri_plugin_tracing::gauge("sync.queue_depth", queue.len() as f64);
+/// ```
+pub fn gauge(name: &str, value: f64) {
+    tracing::trace!(target: METRICS_TARGET, metric = name, value, "{name} = {value}");
+    with_pending(|pending| pending.gauge(name, value));
+}
+
+/// Adds `inc` to the counter `name`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// tauri_plugin_tracing::counter("sync.retries", 1);
+/// ```
+pub fn counter(name: &str, inc: u64) {
+    tracing::trace!(target: METRICS_TARGET, metric = name, inc, "{name} += {inc}");
+    with_pending(|pending| pending.counter(name, inc));
+}
+
+fn with_pending(f: impl FnOnce(&mut Pending)) {
+    if let Some(pending) = PENDING.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        f(pending);
+    }
+}
+
+/// Emits batches of metrics every interval, until dropped.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_metrics_events()`](crate::Builder::with_metrics_events) is
+/// used.
+pub(crate) struct MetricsBatcher {
+    _alive: Arc<()>,
+}
+
+impl MetricsBatcher {
+    /// Starts emitting batches every `interval` on a background thread.
+    pub(crate) fn start<R: Runtime>(app: AppHandle<R>, interval: Duration) -> crate::Result<Self> {
+        let interval = interval.max(MIN_INTERVAL);
+        let alive = Arc::new(());
+        let weak = Arc::downgrade(&alive);
+        PENDING
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(Pending::default);
+        std::thread::Builder::new()
+            .name("tracing-metrics".to_string())
+            .spawn(move || run_batcher(app, weak, interval))?;
+        Ok(Self { _alive: alive })
+    }
+}
+
+fn run_batcher<R: Runtime>(app: AppHandle<R>, alive: Weak<()>, interval: Duration) {
+    loop {
+        std::thread::sleep(interval);
+        if alive.strong_count() == 0 {
+            return;
+        }
+        let batch = PENDING
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_mut()
+            .and_then(Pending::take);
+        if let Some(batch) = batch {
+            let _ = app.emit(&channel(&app, METRICS_EVENT), batch);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_latest_gauges_and_counter_increments() {
+        let mut pending = Pending::default();
+        assert_eq!(pending.take(), None);
+
+        pending.gauge("fps", 58.0);
+        pending.gauge("fps", 60.0);
+        pending.counter("retries", 2);
+        pending.counter("retries", 1);
+        let batch = pending.take().unwrap();
+        assert_eq!(batch.gauges, BTreeMap::from([("fps".to_string(), 60.0)]));
+        assert_eq!(batch.counters, BTreeMap::from([("retries".to_string(), 3)]));
+        assert_eq!(batch.totals, batch.counters);
+
+        pending.counter("retries", 4);
+        pending.counter("uploads", 1);
+        let batch = pending.take().unwrap();
+        assert!(batch.gauges.is_empty());
+        assert_eq!(batch.counters["retries"], 4);
+        assert_eq!(batch.totals["retries"], 7);
+        assert_eq!(batch.totals["uploads"], 1);
+        assert_eq!(pending.take(), None);
+    }
+}
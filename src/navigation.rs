@@ -0,0 +1,80 @@
+//! Navigation and window lifecycle events.
+//!
+//! An error is easier to reproduce knowing which screen the user was on.
+//! With [`Builder::with_navigation_breadcrumbs()`](crate::Builder::with_navigation_breadcrumbs),
+//! the plugin logs an `INFO` event under the [`NAVIGATION_TARGET`] target
+//! whenever a webview finishes loading a page, telling reloads apart from
+//! navigations, and whenever a window gains or loses focus or is closed. The
+//! window label is recorded in the `w` field, like other webview events.
+//!
+//! Combined with [`Builder::with_breadcrumbs()`](crate::Builder::with_breadcrumbs),
+//! the latest of these events appear in the breadcrumb trail of errors.
+//!
+//! Only full page loads are seen by the backend. Client-side route changes,
+//! e.g. with `history.pushState()`, are logged when the frontend's router
+//! calls `setCurrentRoute()`, see [`CurrentRoutes`](crate::CurrentRoutes).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::WindowEvent;
+
+/// The target used for navigation and window lifecycle events.
+pub const NAVIGATION_TARGET: &str = "webview::navigation";
+
+/// Logs page loads and window lifecycle events.
+///
+/// Remembers the last URL loaded by each window to recognize reloads. The
+/// plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_navigation_breadcrumbs()`](crate::Builder::with_navigation_breadcrumbs)
+/// is set.
+#[derive(Debug, Default)]
+pub struct NavigationBreadcrumbs {
+    urls: Mutex<HashMap<String, String>>,
+}
+
+impl NavigationBreadcrumbs {
+    /// Logs that the webview with the given label finished loading `url`.
+    pub fn page_loaded(&self, window: &str, url: &str) {
+        let previous = self
+            .urls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(window.to_string(), url.to_string());
+        match previous {
+            Some(previous) if previous == url => {
+                tracing::info!(target: NAVIGATION_TARGET, w = window, url, "reloaded {url}");
+            }
+            Some(previous) => tracing::info!(
+                target: NAVIGATION_TARGET,
+                w = window,
+                url,
+                from = previous,
+                "navigated to {url}"
+            ),
+            None => tracing::info!(target: NAVIGATION_TARGET, w = window, url, "loaded {url}"),
+        }
+    }
+
+    /// Logs focus changes and the closing of the window with the given label.
+    ///
+    /// Other events are ignored.
+    pub fn window_event(&self, window: &str, event: &WindowEvent) {
+        match event {
+            WindowEvent::Focused(true) => {
+                tracing::info!(target: NAVIGATION_TARGET, w = window, "window focused");
+            }
+            WindowEvent::Focused(false) => {
+                tracing::info!(target: NAVIGATION_TARGET, w = window, "window lost focus");
+            }
+            WindowEvent::Destroyed => {
+                self.urls
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(window);
+                tracing::info!(target: NAVIGATION_TARGET, w = window, "window closed");
+            }
+            _ => {}
+        }
+    }
+}
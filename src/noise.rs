@@ -0,0 +1,264 @@
+//! Finding the crates that flood the logs.
+//!
+//! Dependencies such as `tao`, `wry`, and `hyper` can log thousands of
+//! `DEBUG` and `TRACE` events a minute. [`analyze_noise()`], or
+//! `analyzeNoise()` in JavaScript, counts the events of each crate for a
+//! while and recommends raising the level of the noisiest third-party crates
+//! to `WARN`, and [`apply_noise_filters()`] applies the recommendations
+//! through the [`FilterHandle`].
+//!
+//! Only events that pass the current filter are counted, so crates that are
+//! already filtered out are not recommended again.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::Context;
+
+use crate::{FilterHandle, LogLevel};
+
+/// Crates that are known to log heavily below `WARN`, recommended whenever
+/// they do.
+pub const NOISY_CRATES: &[&str] = &[
+    "tao",
+    "wry",
+    "hyper",
+    "hyper_util",
+    "h2",
+    "reqwest",
+    "rustls",
+    "tungstenite",
+    "mio",
+];
+
+/// The share of all events above which any third-party crate is recommended.
+const NOISY_SHARE: f64 = 0.1;
+
+/// The event counts of a crate during an analysis.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct CrateNoise {
+    /// The crate name, the first segment of its targets.
+    pub name: String,
+    /// The number of events.
+    pub events: u64,
+    /// The number of `INFO`, `DEBUG`, and `TRACE` events.
+    pub verbose_events: u64,
+    /// The share of all events, from 0 to 1.
+    pub share: f64,
+}
+
+/// A level recommended for a target by [`analyze_noise()`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct NoiseRecommendation {
+    /// The target to set the level of.
+    pub target: String,
+    /// The recommended level.
+    pub level: LogLevel,
+    /// The number of events during the analysis the level would have removed.
+    #[serde(default)]
+    pub events_removed: u64,
+}
+
+/// The result of [`analyze_noise()`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct NoiseReport {
+    /// How long events were counted, in milliseconds.
+    pub window_ms: f64,
+    /// The number of events during the analysis.
+    pub total_events: u64,
+    /// The event counts of each crate, noisiest first.
+    pub crates: Vec<CrateNoise>,
+    /// The recommended levels, noisiest first.
+    pub recommendations: Vec<NoiseRecommendation>,
+}
+
+/// Event counts of a crate, by level from `TRACE` to `ERROR`.
+type Tally = [u64; 5];
+
+/// A tracing layer that counts events per crate while an analysis runs.
+///
+/// The plugin installs an instance in the default subscriber and manages it
+/// in Tauri state. It does nothing outside of [`analyze()`](Self::analyze).
+#[derive(Debug, Clone, Default)]
+pub struct NoiseAnalyzer {
+    active: Arc<AtomicUsize>,
+    counts: Arc<Mutex<HashMap<String, Tally>>>,
+}
+
+impl NoiseAnalyzer {
+    /// Creates an analyzer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts events for `window` and returns the recommendations, treating
+    /// crates whose names start with one of `own_crates` as first-party.
+    ///
+    /// Blocks the calling thread for `window`. Overlapping analyses each see
+    /// the events of their own window.
+    pub fn analyze(&self, window: Duration, own_crates: &[&str]) -> NoiseReport {
+        let start = self.snapshot();
+        self.active.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(window);
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        let end = self.snapshot();
+
+        let mut tallies: Vec<(String, Tally)> = end
+            .into_iter()
+            .map(|(name, mut tally)| {
+                let before = start.get(&name).copied().unwrap_or_default();
+                for (count, before) in tally.iter_mut().zip(before) {
+                    *count -= before;
+                }
+                (name, tally)
+            })
+            .filter(|(_, tally)| tally.iter().sum::<u64>() > 0)
+            .collect();
+        tallies.sort_by_key(|(name, tally)| {
+            (std::cmp::Reverse(tally.iter().sum::<u64>()), name.clone())
+        });
+
+        let total_events: u64 = tallies.iter().flat_map(|(_, tally)| tally).sum();
+        let mut report = NoiseReport {
+            window_ms: window.as_secs_f64() * 1000.0,
+            total_events,
+            ..Default::default()
+        };
+        for (name, tally) in tallies {
+            let events: u64 = tally.iter().sum();
+            let verbose_events: u64 = tally[..level_index(Level::WARN)].iter().sum();
+            let share = events as f64 / total_events as f64;
+            let first_party = own_crates.iter().any(|own| name.starts_with(own));
+            if !first_party
+                && verbose_events > 0
+                && (NOISY_CRATES.contains(&name.as_str()) || share >= NOISY_SHARE)
+            {
+                report.recommendations.push(NoiseRecommendation {
+                    target: name.clone(),
+                    level: Level::WARN.into(),
+                    events_removed: verbose_events,
+                });
+            }
+            report.crates.push(CrateNoise {
+                name,
+                events,
+                verbose_events,
+                share,
+            });
+        }
+        report
+    }
+
+    fn snapshot(&self) -> HashMap<String, Tally> {
+        self.counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for NoiseAnalyzer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        if self.active.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let metadata = event.metadata();
+        let name = metadata.target().split("::").next().unwrap_or_default();
+        let index = level_index(*metadata.level());
+        let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        // Avoid allocating the name for crates that were seen before
+        match counts.get_mut(name) {
+            Some(tally) => tally[index] += 1,
+            None => {
+                let mut tally = Tally::default();
+                tally[index] = 1;
+                counts.insert(name.to_string(), tally);
+            }
+        }
+    }
+}
+
+fn level_index(level: Level) -> usize {
+    match level {
+        Level::TRACE => 0,
+        Level::DEBUG => 1,
+        Level::INFO => 2,
+        Level::WARN => 3,
+        Level::ERROR => 4,
+    }
+}
+
+/// Counts events for `window` and recommends levels for the noisiest
+/// third-party crates.
+///
+/// The app's crate and this plugin count as first-party and are never
+/// recommended. Blocks the calling thread for `window`. Requires the default
+/// subscriber.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+/// let report = tauri_plugin_tracing::analyze_noise(app, Duration::from_secs(30))?;
+/// for recommendation in &report.recommendations {
+///     println!("{}: {} events", recommendation.target, recommendation.events_removed);
+/// }
+/// tauri_plugin_tracing::apply_noise_filters(app, &report.recommendations)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn analyze_noise<R: Runtime>(
+    app: &AppHandle<R>,
+    window: Duration,
+) -> crate::Result<NoiseReport> {
+    let analyzer = app
+        .try_state::<NoiseAnalyzer>()
+        .ok_or(crate::Error::LoggerNotInitialized)?;
+    let app_crate = app.package_info().crate_name.replace('-', "_");
+    let report = analyzer.analyze(window, &[&app_crate, "tauri_plugin_tracing"]);
+    tracing::info!(
+        total_events = report.total_events,
+        recommendations = report.recommendations.len(),
+        "noise analysis finished"
+    );
+    Ok(report)
+}
+
+/// Sets the level of each recommended target at runtime.
+///
+/// The levels are runtime filter changes, so
+/// [`FilterHandle::reset()`](crate::FilterHandle::reset) undoes them, and
+/// [`Builder::with_filter_persistence()`](crate::Builder::with_filter_persistence)
+/// keeps them across restarts.
+pub fn apply_noise_filters<R: Runtime>(
+    app: &AppHandle<R>,
+    recommendations: &[NoiseRecommendation],
+) -> crate::Result<()> {
+    let filter = app
+        .try_state::<FilterHandle>()
+        .ok_or(crate::Error::LoggerNotInitialized)?;
+    for recommendation in recommendations {
+        let level = LevelFilter::from_level(recommendation.level.clone().into());
+        filter.set_target(recommendation.target.clone(), level)?;
+        tracing::info!(
+            "Set level of {} to {} to reduce noise",
+            recommendation.target,
+            level
+        );
+    }
+    Ok(())
+}
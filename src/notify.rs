@@ -0,0 +1,263 @@
+//! User notifications for backend errors.
+//!
+//! Errors that the user should know about, like a failed save, are logged in
+//! the backend but shown by the frontend, so each command ends up returning
+//! them in its own way. A [`NotifyLayer`] emits a [`UserNotification`] as the
+//! [`NOTIFY_EVENT`] event for the events marked with a `notify = true` field,
+//! and optionally for every `ERROR` event, so the frontend can show them as
+//! toasts in one place:
+//!
+//! ```rust,no_run
+//! # let path = "notes.md";
+//! tracing::error!(notify = true, title = "Could not save", "{path} is read-only");
+//! ```
+//!
+//! The notification's body is the event's message, and its title is the
+//! event's `title` field, or else a title for its level, e.g. `Error`. An
+//! event marked with `notify = false` is never a notification, even when all
+//! `ERROR` events are.
+//!
+//! Notifications are rate-limited, so a failure repeated in a loop does not
+//! flood the user. The next notification after some were dropped tells how
+//! many in its `suppressed` field.
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+use tracing::Level;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+use crate::LogLevel;
+use crate::plugin_name::channel;
+
+/// The event emitted to the app for each notification.
+pub const NOTIFY_EVENT: &str = "tracing://notify";
+
+/// The payload of the [`NOTIFY_EVENT`] event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct UserNotification {
+    /// The event's `title` field, or a title for its level.
+    pub title: String,
+    /// The event's message.
+    pub body: String,
+    /// The event's level.
+    pub level: LogLevel,
+    /// The event's target.
+    pub target: String,
+    /// How many notifications were dropped by the rate limit since the
+    /// previous one.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub suppressed: u32,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+/// Which events become notifications, and how many.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use tauri_plugin_tracing::{Builder, Notifications};
+/// Builder::new()
+///     .with_notifications(
+///         Notifications::errors().with_rate_limit(5, Duration::from_secs(60)),
+///     )
+///     .with_default_subscriber()
+///     .build::<tauri::Wry>();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Notifications {
+    errors: bool,
+    max: u32,
+    per: Duration,
+}
+
+impl Notifications {
+    /// Notifies the events marked with a `notify = true` field.
+    ///
+    /// At most 3 notifications are emitted every 10 seconds.
+    pub fn marked() -> Self {
+        Self {
+            errors: false,
+            max: 3,
+            per: Duration::from_secs(10),
+        }
+    }
+
+    /// Notifies every `ERROR` event, and the other events marked with a
+    /// `notify = true` field.
+    pub fn errors() -> Self {
+        Self {
+            errors: true,
+            ..Self::marked()
+        }
+    }
+
+    /// Emits at most `max` notifications in any `per` period.
+    pub fn with_rate_limit(mut self, max: u32, per: Duration) -> Self {
+        self.max = max;
+        self.per = per;
+        self
+    }
+
+    /// Returns whether every `ERROR` event is a notification.
+    pub fn notifies_errors(&self) -> bool {
+        self.errors
+    }
+
+    /// Returns the rate limit, as the most notifications in a period.
+    pub fn rate_limit(&self) -> (u32, Duration) {
+        (self.max, self.per)
+    }
+
+    /// Returns the layer that emits notifications through `app_handle`.
+    ///
+    /// The plugin adds it to the default subscriber when
+    /// [`Builder::with_notifications()`](crate::Builder::with_notifications)
+    /// is used. Add it yourself when composing your own subscriber.
+    pub fn layer<R: Runtime>(self, app_handle: AppHandle<R>) -> NotifyLayer<R> {
+        NotifyLayer {
+            app_handle,
+            config: self,
+            sent: Mutex::new(RateLimit::default()),
+            _runtime: PhantomData,
+        }
+    }
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self::marked()
+    }
+}
+
+/// A tracing layer that emits [`UserNotification`]s for the events selected
+/// by its [`Notifications`].
+///
+/// Created with [`Notifications::layer()`].
+pub struct NotifyLayer<R: Runtime> {
+    app_handle: AppHandle<R>,
+    config: Notifications,
+    sent: Mutex<RateLimit>,
+    _runtime: PhantomData<fn() -> R>,
+}
+
+/// The times of recent notifications, and how many were dropped since the
+/// last one.
+#[derive(Default)]
+struct RateLimit {
+    sent: VecDeque<Instant>,
+    suppressed: u32,
+}
+
+impl RateLimit {
+    /// Returns the number of notifications suppressed before this one, or
+    /// `None` if this one is over the limit.
+    fn admit(&mut self, now: Instant, max: u32, per: Duration) -> Option<u32> {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= per)
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= max as usize {
+            self.suppressed += 1;
+            return None;
+        }
+        self.sent.push_back(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+impl<S, R: Runtime> Layer<S> for NotifyLayer<R>
+where
+    S: tracing::Subscriber,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        // Only events with a `notify` field can be marked
+        let may_notify = self.config.errors && *metadata.level() == Level::ERROR;
+        if !may_notify && metadata.fields().field("notify").is_none() {
+            return;
+        }
+        let mut visitor = NotifyVisitor::default();
+        event.record(&mut visitor);
+        if !visitor.notify.unwrap_or(may_notify) {
+            return;
+        }
+
+        let suppressed = {
+            let mut sent = self.sent.lock().unwrap_or_else(|e| e.into_inner());
+            match sent.admit(Instant::now(), self.config.max, self.config.per) {
+                Some(suppressed) => suppressed,
+                None => return,
+            }
+        };
+        let level = *metadata.level();
+        let notification = UserNotification {
+            title: visitor
+                .title
+                .unwrap_or_else(|| default_title(level).to_string()),
+            body: visitor.message.unwrap_or_default(),
+            level: level.into(),
+            target: metadata.target().to_string(),
+            suppressed,
+        };
+        let _ = self
+            .app_handle
+            .emit(&channel(&self.app_handle, NOTIFY_EVENT), notification);
+    }
+}
+
+fn default_title(level: Level) -> &'static str {
+    match level {
+        Level::ERROR => "Error",
+        Level::WARN => "Warning",
+        Level::INFO => "Information",
+        Level::DEBUG | Level::TRACE => "Debug",
+    }
+}
+
+/// Collects the fields a notification is made of.
+#[derive(Default)]
+struct NotifyVisitor {
+    notify: Option<bool>,
+    title: Option<String>,
+    message: Option<String>,
+}
+
+impl Visit for NotifyVisitor {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == "notify" {
+            self.notify = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "title" => self.title = Some(value.to_string()),
+            "message" => self.message = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "title" => self.title = Some(format!("{value:?}")),
+            "message" => self.message = Some(format!("{value:?}")),
+            _ => {}
+        }
+    }
+}
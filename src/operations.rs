@@ -0,0 +1,268 @@
+//! Long-running operations started from the frontend.
+//!
+//! Installs, imports, and uploads often outlive the code that started them,
+//! and may even outlive the page if the webview reloads. The `operation_start`
+//! command opens an `INFO` span for such an operation and returns an
+//! [`OperationHandle`] with a stable id. The span stays open in the
+//! [`OperationRegistry`] until `operation_end` is called with that id, so a
+//! reloaded page can re-attach to it with `operation_get` and keep logging
+//! under the same span.
+//!
+//! Operations still open when the app exits are reported as `WARN` events
+//! under the [`OPERATIONS_TARGET`] target.
+//!
+//! ```javascript
+//! import { startOperation, resumeOperation } from '@fltsci/tauri-plugin-tracing';
+//!
+//! const op = await startOperation('import', { files: 12 });
+//! sessionStorage.setItem('importOperation', op.id);
+//! op.logger.info('parsed manifest');
+//!
+//! // After a reload
+//! const resumed = await resumeOperation(sessionStorage.getItem('importOperation'));
+//! await resumed?.end('success');
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tracing::Span;
+
+use crate::span_context::{SpanContext, SpanContextRegistry};
+
+/// The target used for operation spans and lifecycle events.
+pub const OPERATIONS_TARGET: &str = "tauri_plugin_tracing::operation";
+
+/// Maximum number of open operations, so a frontend that never ends its
+/// operations cannot grow the registry without bound.
+pub const MAX_OPERATIONS: usize = 1024;
+
+/// How an operation finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum OperationOutcome {
+    /// The operation completed successfully.
+    #[default]
+    Success,
+    /// The operation failed.
+    Failure,
+    /// The operation was cancelled before completing.
+    Cancelled,
+}
+
+impl std::fmt::Display for OperationOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Cancelled => "cancelled",
+        })
+    }
+}
+
+/// Describes an open operation, as returned to the frontend.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct OperationHandle {
+    /// The operation's stable id.
+    pub id: String,
+    /// The name the operation was started with.
+    pub name: String,
+    /// Context for logging under the operation's span, if it is enabled.
+    pub span_context: Option<SpanContext>,
+    /// Milliseconds since the operation started.
+    pub elapsed_ms: f64,
+}
+
+struct Operation {
+    name: String,
+    span: Span,
+    span_context: Option<SpanContext>,
+    started: Instant,
+}
+
+impl Operation {
+    fn handle(&self, id: &str) -> OperationHandle {
+        OperationHandle {
+            id: id.to_string(),
+            name: self.name.clone(),
+            span_context: self.span_context.clone(),
+            elapsed_ms: self.started.elapsed().as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Open operations, by id and by the span id of their context.
+#[derive(Default)]
+struct Operations {
+    by_id: HashMap<String, Operation>,
+    by_span_id: HashMap<String, String>,
+}
+
+/// Keeps the spans of open operations alive.
+///
+/// At most [`MAX_OPERATIONS`] operations can be open at once.
+///
+/// The plugin manages an instance of this type in Tauri state.
+#[derive(Default)]
+pub struct OperationRegistry {
+    operations: Mutex<Operations>,
+    next_id: AtomicU64,
+}
+
+impl OperationRegistry {
+    /// Opens an operation span and returns its handle.
+    ///
+    /// `metadata` is recorded on the span as a JSON string. The span is also
+    /// registered with `span_registry` so frontend records can be re-parented
+    /// under it.
+    ///
+    /// Returns [`Error::TooManyOperations`](crate::Error::TooManyOperations)
+    /// if [`MAX_OPERATIONS`] operations are already open.
+    pub fn start(
+        &self,
+        name: &str,
+        metadata: Option<&serde_json::Value>,
+        span_registry: &SpanContextRegistry,
+    ) -> crate::Result<OperationHandle> {
+        let id = format!("op-{:x}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let metadata = metadata.map(|m| m.to_string());
+        let span = tracing::info_span!(
+            target: OPERATIONS_TARGET,
+            "operation",
+            operation = %name,
+            id = %id,
+            metadata = metadata.as_deref(),
+            outcome = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        );
+
+        let operation = Operation {
+            name: name.to_string(),
+            span_context: span_registry.register(&span),
+            span: span.clone(),
+            started: Instant::now(),
+        };
+        let handle = operation.handle(&id);
+        {
+            let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+            if operations.by_id.len() >= MAX_OPERATIONS {
+                if let Some(context) = &operation.span_context {
+                    span_registry.release(context);
+                }
+                return Err(crate::Error::TooManyOperations(MAX_OPERATIONS));
+            }
+            if let Some(context) = &operation.span_context {
+                operations
+                    .by_span_id
+                    .insert(context.span_id.clone(), id.clone());
+            }
+            operations.by_id.insert(id, operation);
+        }
+
+        span.in_scope(|| tracing::info!(target: OPERATIONS_TARGET, "operation `{name}` started"));
+        Ok(handle)
+    }
+
+    /// Closes an operation, recording its outcome and duration.
+    ///
+    /// Returns [`Error::UnknownOperation`](crate::Error::UnknownOperation) if
+    /// no open operation has the given id.
+    pub fn end(
+        &self,
+        id: &str,
+        outcome: OperationOutcome,
+        span_registry: &SpanContextRegistry,
+    ) -> crate::Result<OperationHandle> {
+        let operation = {
+            let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+            let operation = operations
+                .by_id
+                .remove(id)
+                .ok_or_else(|| crate::Error::UnknownOperation(id.to_string()))?;
+            if let Some(context) = &operation.span_context {
+                operations.by_span_id.remove(&context.span_id);
+            }
+            operation
+        };
+        let handle = operation.handle(id);
+        let name = &operation.name;
+
+        operation.span.record("outcome", outcome.to_string());
+        operation.span.record("duration_ms", handle.elapsed_ms);
+        operation.span.in_scope(|| match outcome {
+            OperationOutcome::Failure => tracing::warn!(
+                target: OPERATIONS_TARGET,
+                %outcome,
+                duration_ms = handle.elapsed_ms,
+                "operation `{name}` failed"
+            ),
+            _ => tracing::info!(
+                target: OPERATIONS_TARGET,
+                %outcome,
+                duration_ms = handle.elapsed_ms,
+                "operation `{name}` ended"
+            ),
+        });
+
+        if let Some(context) = &operation.span_context {
+            span_registry.release(context);
+        }
+        Ok(handle)
+    }
+
+    /// Returns the handle of an open operation.
+    pub fn get(&self, id: &str) -> Option<OperationHandle> {
+        self.operations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .by_id
+            .get(id)
+            .map(|operation| operation.handle(id))
+    }
+
+    /// Returns the handles of all open operations.
+    pub fn list(&self) -> Vec<OperationHandle> {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        let mut handles: Vec<_> = operations
+            .by_id
+            .iter()
+            .map(|(id, op)| op.handle(id))
+            .collect();
+        handles.sort_by(|a, b| b.elapsed_ms.total_cmp(&a.elapsed_ms));
+        handles
+    }
+
+    /// Looks up the span of an open operation by its propagated context.
+    ///
+    /// Used when the span has been evicted from the [`SpanContextRegistry`].
+    pub(crate) fn span_for(&self, context: &SpanContext) -> Option<Span> {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        let id = operations.by_span_id.get(&context.span_id)?;
+        operations
+            .by_id
+            .get(id)
+            .filter(|operation| operation.span_context.as_ref() == Some(context))
+            .map(|operation| operation.span.clone())
+    }
+
+    /// Logs a `WARN` event for every operation that is still open.
+    ///
+    /// The plugin calls this when the app exits.
+    pub fn warn_unfinished(&self) {
+        for handle in self.list() {
+            tracing::warn!(
+                target: OPERATIONS_TARGET,
+                id = %handle.id,
+                duration_ms = handle.elapsed_ms,
+                "operation `{}` never ended",
+                handle.name
+            );
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Apple unified logging output.
+//!
+//! Only available on macOS and iOS. [`OsLogLayer`] sends each event to
+//! `os_log`, so it shows up in Console.app and `log stream` alongside system
+//! logs. Events are logged under one subsystem, usually the app's bundle
+//! identifier, and a category derived from the event's target:
+//!
+//! - A category set with [`OsLogLayer::with_category()`] for the target or one
+//!   of its parent modules, the longest match winning.
+//! - Otherwise the target's second path segment, so `my_app::sync` and
+//!   `my_app::sync::queue` are both logged under `sync`.
+//! - Otherwise the target itself, e.g. `my_app` or `hyper`.
+//! - Records from the webview, which have an empty target, are logged under
+//!   `webview`.
+//!
+//! Levels are mapped to log types: `TRACE` and `DEBUG` to `debug`, `INFO` to
+//! `info`, `WARN` to `default`, and `ERROR` to `error`. Debug and info
+//! messages are only kept in memory unless logging is configured otherwise,
+//! see `man log`.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use oslog::OsLog;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+/// The category for records from the webview.
+const WEBVIEW_CATEGORY: &str = "webview";
+
+/// A tracing layer that sends events to Apple's unified logging system.
+///
+/// # Example
+///
+/// Use [`Target::OsLog`](crate::Target::OsLog) with the default subscriber,
+/// or add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::OsLogLayer;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// Registry::default()
+///     .with(OsLogLayer::new("com.example.app").with_category("my_app::db", "storage"))
+///     .init();
+/// ```
+pub struct OsLogLayer {
+    subsystem: String,
+    categories: Vec<(String, String)>,
+    logs: Mutex<HashMap<String, Arc<OsLog>>>,
+}
+
+impl OsLogLayer {
+    /// Creates a layer that logs under `subsystem`.
+    pub fn new(subsystem: impl Into<String>) -> Self {
+        Self {
+            subsystem: subsystem.into(),
+            categories: Vec::new(),
+            logs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Logs events from `target` and its submodules under `category`.
+    pub fn with_category(mut self, target: impl Into<String>, category: impl Into<String>) -> Self {
+        self.categories.push((target.into(), category.into()));
+        self
+    }
+
+    /// Returns the subsystem events are logged under.
+    pub fn subsystem(&self) -> &str {
+        &self.subsystem
+    }
+
+    /// Returns the category events from `target` are logged under.
+    pub fn category(&self, target: &str) -> String {
+        let configured = self
+            .categories
+            .iter()
+            .filter(|(prefix, _)| {
+                target
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len());
+        if let Some((_, category)) = configured {
+            return category.clone();
+        }
+        if target.is_empty() {
+            return WEBVIEW_CATEGORY.to_string();
+        }
+        let mut segments = target.split("::");
+        let first = segments.next().unwrap_or(target);
+        segments.next().unwrap_or(first).to_string()
+    }
+
+    fn log_for(&self, target: &str) -> Arc<OsLog> {
+        let category = self.category(target);
+        let mut logs = self.logs.lock().unwrap_or_else(|e| e.into_inner());
+        logs.entry(category)
+            .or_insert_with_key(|category| Arc::new(OsLog::new(&self.subsystem, category)))
+            .clone()
+    }
+}
+
+impl std::fmt::Debug for OsLogLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OsLogLayer")
+            .field("subsystem", &self.subsystem)
+            .field("categories", &self.categories)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for OsLogLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut message = MessageVisitor::default();
+        event.record(&mut message);
+
+        let level = match *metadata.level() {
+            Level::TRACE | Level::DEBUG => oslog::Level::Debug,
+            Level::INFO => oslog::Level::Info,
+            Level::WARN => oslog::Level::Default,
+            Level::ERROR => oslog::Level::Error,
+        };
+        self.log_for(metadata.target())
+            .with_level(level, &message.finish());
+    }
+}
+
+/// Formats an event as its message followed by `name=value` fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+}
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+            return;
+        }
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        // Webview records carry their location in an unnamed field
+        let _ = match field.name() {
+            "" => write!(self.fields, "{value:?}"),
+            name => write!(self.fields, "{name}={value:?}"),
+        };
+    }
+}
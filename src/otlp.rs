@@ -0,0 +1,382 @@
+//! OpenTelemetry logs output.
+//!
+//! Requires the `otlp` feature. [`OtlpLayer`] batches events and exports them
+//! as OTLP log records over HTTP with the
+//! [JSON encoding](https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding),
+//! to a collector's `/v1/logs` endpoint or any backend that accepts OTLP logs:
+//!
+//! - The message is the record's body, and the level its severity.
+//! - Event fields become attributes, with the target as `code.namespace` and
+//!   the source location as `code.filepath` and `code.lineno`. Records from a
+//!   webview carry its label as `tauri.window`.
+//! - Records are grouped into instrumentation scopes by target, and share the
+//!   configured resource attributes, such as `service.name`.
+//!
+//! Events are sent from a background thread, at least once a second or every
+//! 100 events. If the collector cannot keep up, new events are dropped, and a
+//! batch that fails to send is discarded. gRPC and protobuf are not
+//! supported; collectors accept OTLP/HTTP JSON on port 4318 by default.
+//!
+//! Logs exported from end-user machines may contain personal data. Only add
+//! the target for users who opted in.
+
+use std::collections::BTreeMap;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, SyncSender};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{Value, json};
+use tracing::span::{Attributes, Id};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Number of events queued for sending before new ones are dropped.
+const QUEUE_CAPACITY: usize = 4096;
+
+/// Most events exported in one request.
+const MAX_BATCH_LEN: usize = 100;
+
+/// Longest an event waits in a batch before it is exported.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Timeout for an export request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns the OpenTelemetry `os.type` of the current platform.
+///
+/// Used for the `os.type` resource attribute of the [`Target::Otlp`](crate::Target::Otlp)
+/// output.
+pub fn os_type() -> &'static str {
+    match std::env::consts::OS {
+        "macos" | "ios" => "darwin",
+        "dragonfly" => "dragonflybsd",
+        os => os,
+    }
+}
+
+/// A tracing layer that exports events as OpenTelemetry log records.
+///
+/// # Example
+///
+/// Use [`Target::Otlp`](crate::Target::Otlp) with the default subscriber, or
+/// add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::OtlpLayer;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// # fn main() -> tauri_plugin_tracing::Result<()> {
+/// Registry::default()
+///     .with(OtlpLayer::new(
+///         "https://otel.example.com:4318/v1/logs",
+///         [("Authorization", "Bearer <token>")],
+///         [("service.name", "my-app"), ("service.version", "1.0.0")],
+///     )?)
+///     .init();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct OtlpLayer {
+    sender: SyncSender<OtlpRecord>,
+}
+
+impl OtlpLayer {
+    /// Creates a layer that exports to `url`, the collector's `/v1/logs`
+    /// endpoint, sending `headers` with every request and describing the app
+    /// with the `resource` attributes.
+    pub fn new<K, V, RK, RV>(
+        url: impl Into<String>,
+        headers: impl IntoIterator<Item = (K, V)>,
+        resource: impl IntoIterator<Item = (RK, RV)>,
+    ) -> crate::Result<Self>
+    where
+        K: Into<String>,
+        V: Into<String>,
+        RK: Into<String>,
+        RV: Into<String>,
+    {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(QUEUE_CAPACITY);
+        let url = url.into();
+        let headers: Vec<(String, String)> = headers
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+        // Later attributes replace earlier ones with the same key
+        let resource: BTreeMap<String, String> = resource
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+        let resource: Vec<Value> = resource
+            .into_iter()
+            .map(|(key, value)| attribute(key, Value::String(value)))
+            .collect();
+        std::thread::Builder::new()
+            .name("tracing-otlp".to_string())
+            .spawn(move || send_batches(receiver, &url, &headers, &resource))?;
+
+        Ok(Self { sender })
+    }
+}
+
+/// The webview label of a span, recorded from its `w` field.
+struct WindowLabel(String);
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().fields().field("w").is_none() {
+            return;
+        }
+        let mut visitor = WindowVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(label), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(WindowLabel(label));
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = OtlpVisitor::default();
+        event.record(&mut visitor);
+
+        let mut attributes = vec![attribute("code.namespace", metadata.target().into())];
+        if let Some(file) = metadata.file() {
+            attributes.push(attribute("code.filepath", file.into()));
+        }
+        if let Some(line) = metadata.line() {
+            attributes.push(attribute("code.lineno", line.into()));
+        }
+        if let Some(scope) = ctx.event_scope(event) {
+            let mut spans = Vec::new();
+            let mut window = None;
+            for span in scope.from_root() {
+                if let Some(label) = span.extensions().get::<WindowLabel>() {
+                    window = Some(label.0.clone());
+                }
+                spans.push(span.name());
+            }
+            attributes.push(attribute("spans", spans.join(":").into()));
+            if let Some(window) = window {
+                attributes.push(attribute("tauri.window", window.into()));
+            }
+        }
+        if let Some(rate) = crate::current_sample_rate() {
+            attributes.push(attribute("sampled", rate.to_string().into()));
+        }
+        if let Some(seq) = crate::current_sequence() {
+            attributes.push(attribute("seq", seq.into()));
+        }
+        // Event fields take precedence over the log context
+        crate::context::with_current_context(|entries| {
+            for (key, value) in entries {
+                if !visitor.attributes.iter().any(|a| a["key"] == key.as_str()) {
+                    attributes.push(attribute(key.clone(), value.clone().into()));
+                }
+            }
+        });
+        attributes.extend(visitor.attributes);
+
+        let (severity_number, severity_text) = severity(*metadata.level());
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .to_string();
+        let record = json!({
+            "timeUnixNano": time,
+            "observedTimeUnixNano": time,
+            "severityNumber": severity_number,
+            "severityText": severity_text,
+            "body": { "stringValue": visitor.message.unwrap_or_default() },
+            "attributes": attributes,
+        });
+        // Drop the event if the queue is full rather than block the caller
+        let _ = self.sender.try_send(OtlpRecord {
+            target: metadata.target().to_string(),
+            record,
+        });
+    }
+}
+
+/// An event waiting to be exported.
+struct OtlpRecord {
+    target: String,
+    record: Value,
+}
+
+/// Returns the OpenTelemetry severity number and text of a level.
+fn severity(level: Level) -> (u8, &'static str) {
+    match level {
+        Level::TRACE => (1, "TRACE"),
+        Level::DEBUG => (5, "DEBUG"),
+        Level::INFO => (9, "INFO"),
+        Level::WARN => (13, "WARN"),
+        Level::ERROR => (17, "ERROR"),
+    }
+}
+
+/// Returns an OTLP key-value pair, encoding `value` as an `AnyValue`.
+fn attribute(key: impl Into<String>, value: Value) -> Value {
+    let value = match value {
+        Value::Bool(value) => json!({ "boolValue": value }),
+        // 64-bit integers are strings in OTLP JSON
+        Value::Number(number) if number.is_i64() || number.is_u64() => {
+            json!({ "intValue": number.to_string() })
+        }
+        Value::Number(number) => json!({ "doubleValue": number }),
+        Value::String(value) => json!({ "stringValue": value }),
+        other => json!({ "stringValue": other.to_string() }),
+    };
+    json!({ "key": key.into(), "value": value })
+}
+
+/// Reads the `w` field of a webview span.
+struct WindowVisitor(Option<String>);
+
+impl tracing::field::Visit for WindowVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "w" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+/// Collects the message and other fields of an event.
+#[derive(Default)]
+struct OtlpVisitor {
+    message: Option<String>,
+    attributes: Vec<Value>,
+}
+
+impl OtlpVisitor {
+    fn insert(&mut self, field: &tracing::field::Field, value: Value) {
+        match field.name() {
+            "message" => {
+                self.message = Some(match value {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                });
+            }
+            // The location of webview records
+            "" => self.attributes.push(attribute("location", value)),
+            name => self.attributes.push(attribute(name, value)),
+        }
+    }
+}
+
+impl tracing::field::Visit for OtlpVisitor {
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        // Non-finite numbers are not valid JSON
+        match serde_json::Number::from_f64(value) {
+            Some(number) => self.insert(field, Value::Number(number)),
+            None => self.insert(field, Value::String(value.to_string())),
+        }
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.insert(field, value.to_string().into());
+        for (n, source) in crate::error_chain::sources(value).enumerate() {
+            let name = crate::error_chain::source_field(field.name(), n + 1);
+            self.attributes.push(attribute(name, source.into()));
+        }
+    }
+}
+
+fn send_batches(
+    receiver: Receiver<OtlpRecord>,
+    url: &str,
+    headers: &[(String, String)],
+    resource: &[Value],
+) {
+    let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+    let mut batch = Vec::new();
+    let mut started: Option<Instant> = None;
+
+    loop {
+        let wait = started.map_or(FLUSH_INTERVAL, |started| {
+            FLUSH_INTERVAL.saturating_sub(started.elapsed())
+        });
+        match receiver.recv_timeout(wait) {
+            Ok(record) => {
+                let started = *started.get_or_insert_with(Instant::now);
+                batch.push(record);
+                if batch.len() < MAX_BATCH_LEN && started.elapsed() < FLUSH_INTERVAL {
+                    continue;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) if batch.is_empty() => continue,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                export(&agent, url, headers, resource, std::mem::take(&mut batch));
+                return;
+            }
+        }
+        export(&agent, url, headers, resource, std::mem::take(&mut batch));
+        started = None;
+    }
+}
+
+/// Returns the request body of a batch, grouping records into scopes by
+/// target.
+fn export_body(resource: &[Value], batch: Vec<OtlpRecord>) -> Value {
+    let mut scopes: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for record in batch {
+        scopes.entry(record.target).or_default().push(record.record);
+    }
+    let scope_logs: Vec<Value> = scopes
+        .into_iter()
+        .map(|(target, records)| json!({ "scope": { "name": target }, "logRecords": records }))
+        .collect();
+    json!({
+        "resourceLogs": [{
+            "resource": { "attributes": resource },
+            "scopeLogs": scope_logs,
+        }]
+    })
+}
+
+fn export(
+    agent: &ureq::Agent,
+    url: &str,
+    headers: &[(String, String)],
+    resource: &[Value],
+    batch: Vec<OtlpRecord>,
+) {
+    if batch.is_empty() {
+        return;
+    }
+    let body = export_body(resource, batch).to_string();
+    let mut request = agent.post(url).set("Content-Type", "application/json");
+    for (key, value) in headers {
+        request = request.set(key, value);
+    }
+    // A failed batch is dropped; retrying could pile up behind an outage
+    let _ = request.send_string(&body);
+}
@@ -0,0 +1,237 @@
+//! Structured output to a parent process.
+//!
+//! Apps that run headless under an orchestrating process can hand it their
+//! logs live with [`Target::Pipe`](crate::Target::Pipe). [`PipeLayer`] writes
+//! each event as one frame:
+//!
+//! - A 4-byte big-endian length, followed by that many bytes of UTF-8 JSON.
+//! - The JSON object has the event's `timestamp` in milliseconds since the
+//!   Unix epoch, `level`, `target`, `message`, source `file` and `line`, span
+//!   names as `spans`, and its other fields under `fields`.
+//!
+//! A reader in the parent process only has to read the length, then the
+//! record, no matter what the message contains:
+//!
+//! ```python
+//! while header := pipe.read(4):
+//!     record = json.loads(pipe.read(int.from_bytes(header, "big")))
+//! ```
+//!
+//! Frames are written from a background thread, so logging never blocks on a
+//! parent that is slow to read. If it cannot keep up, new events are dropped.
+//! Once the pipe is closed, nothing more is written.
+
+use std::io::Write;
+use std::sync::mpsc::{Receiver, SyncSender};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{Map, Value};
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Number of frames queued for writing before new ones are dropped.
+const QUEUE_CAPACITY: usize = 4096;
+
+/// A tracing layer that writes length-prefixed JSON records to a pipe.
+///
+/// # Example
+///
+/// Use [`Target::Pipe`](crate::Target::Pipe) with the default subscriber, or
+/// add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::PipeLayer;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// # fn main() -> tauri_plugin_tracing::Result<()> {
+/// Registry::default()
+///     .with(PipeLayer::new(std::io::stdout())?)
+///     .init();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PipeLayer {
+    sender: SyncSender<Vec<u8>>,
+}
+
+impl PipeLayer {
+    /// Creates a layer that writes frames to `writer`.
+    pub fn new(writer: impl Write + Send + 'static) -> crate::Result<Self> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(QUEUE_CAPACITY);
+        std::thread::Builder::new()
+            .name("tracing-pipe".to_string())
+            .spawn(move || write_frames(receiver, writer))?;
+        Ok(Self { sender })
+    }
+
+    /// Creates a layer that writes frames to a pipe inherited from the parent
+    /// process, or to stdout if `handle` is `None`.
+    ///
+    /// `handle` is a file descriptor on Unix and a `HANDLE` on Windows, e.g.
+    /// passed on the command line by the parent. It is duplicated, so the
+    /// original can be closed by the app. It must be open when this is
+    /// called.
+    pub fn inherited(handle: Option<u64>) -> crate::Result<Self> {
+        match handle {
+            Some(handle) => Self::new(inherited_pipe(handle)?),
+            None => Self::new(std::io::stdout()),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn inherited_pipe(handle: u64) -> std::io::Result<std::fs::File> {
+    use std::os::fd::{BorrowedFd, RawFd};
+
+    let fd = RawFd::try_from(handle).map_err(std::io::Error::other)?;
+    // Safety: the caller guarantees the descriptor is open; it is only
+    // borrowed long enough to duplicate it
+    let fd = unsafe { BorrowedFd::borrow_raw(fd) };
+    Ok(fd.try_clone_to_owned()?.into())
+}
+
+#[cfg(windows)]
+fn inherited_pipe(handle: u64) -> std::io::Result<std::fs::File> {
+    use std::os::windows::io::{BorrowedHandle, RawHandle};
+
+    // Safety: the caller guarantees the handle is open; it is only borrowed
+    // long enough to duplicate it
+    let handle = unsafe { BorrowedHandle::borrow_raw(handle as RawHandle) };
+    Ok(handle.try_clone_to_owned()?.into())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn inherited_pipe(_handle: u64) -> std::io::Result<std::fs::File> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+impl<S> Layer<S> for PipeLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = PipeVisitor::default();
+        event.record(&mut visitor);
+
+        let mut record = Map::new();
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        record.insert("timestamp".into(), timestamp_ms.into());
+        record.insert("level".into(), metadata.level().as_str().into());
+        record.insert("target".into(), metadata.target().into());
+        record.insert(
+            "message".into(),
+            visitor.message.take().unwrap_or_default().into(),
+        );
+        if let Some(file) = metadata.file() {
+            record.insert("file".into(), file.into());
+        }
+        if let Some(line) = metadata.line() {
+            record.insert("line".into(), line.into());
+        }
+        if let Some(scope) = ctx.event_scope(event) {
+            let spans: Vec<&str> = scope.from_root().map(|span| span.name()).collect();
+            record.insert("spans".into(), spans.into());
+        }
+        if !visitor.fields.is_empty() {
+            record.insert("fields".into(), Value::Object(visitor.fields));
+        }
+
+        if let Ok(frame) = encode_frame(&Value::Object(record)) {
+            // Drop the event if the queue is full rather than block the caller
+            let _ = self.sender.try_send(frame);
+        }
+    }
+}
+
+/// Encodes `record` as a length-prefixed frame.
+fn encode_frame(record: &Value) -> serde_json::Result<Vec<u8>> {
+    let mut frame = vec![0; 4];
+    serde_json::to_writer(&mut frame, record)?;
+    let len = u32::try_from(frame.len() - 4).map_err(serde::ser::Error::custom)?;
+    frame[..4].copy_from_slice(&len.to_be_bytes());
+    Ok(frame)
+}
+
+fn write_frames(receiver: Receiver<Vec<u8>>, mut writer: impl Write) {
+    for frame in receiver {
+        // The parent stopped reading
+        if writer.write_all(&frame).is_err() || writer.flush().is_err() {
+            return;
+        }
+    }
+}
+
+/// Collects the message and other fields of an event.
+#[derive(Default)]
+struct PipeVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl PipeVisitor {
+    fn insert(&mut self, field: &tracing::field::Field, value: Value) {
+        match field.name() {
+            "message" => {
+                self.message = Some(match value {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                });
+            }
+            // The location of webview records
+            "" => {
+                self.fields.insert("location".into(), value);
+            }
+            name => {
+                self.fields.insert(name.into(), value);
+            }
+        }
+    }
+}
+
+impl tracing::field::Visit for PipeVisitor {
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        // Non-finite numbers are not valid JSON
+        match serde_json::Number::from_f64(value) {
+            Some(number) => self.insert(field, Value::Number(number)),
+            None => self.insert(field, Value::String(value.to_string())),
+        }
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.insert(field, value.to_string().into());
+        for (n, source) in crate::error_chain::sources(value).enumerate() {
+            let name = crate::error_chain::source_field(field.name(), n + 1);
+            self.fields.insert(name, source.into());
+        }
+    }
+}
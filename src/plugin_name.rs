@@ -0,0 +1,34 @@
+//! The name the plugin is registered under.
+//!
+//! Commands are invoked as `plugin:<name>|<command>`, permissions are
+//! identified as `<name>:<permission>`, and events are emitted on
+//! `<name>://<event>` channels. The name is `tracing` unless the app chooses
+//! another with [`Builder::with_plugin_name()`](crate::Builder::with_plugin_name).
+
+use std::borrow::Cow;
+
+use tauri::{Manager, Runtime};
+
+/// The name the plugin is registered under by default.
+pub const DEFAULT_PLUGIN_NAME: &str = "tracing";
+
+/// The name the plugin was registered under, managed in Tauri state.
+pub(crate) struct PluginName(pub(crate) &'static str);
+
+/// Returns the channel of `event` for the plugin name managed by `manager`.
+///
+/// `event` is one of the plugin's `tracing://` event names, such as
+/// [`PING_EVENT`](crate::PING_EVENT). It is returned unchanged unless the
+/// plugin was registered under another name.
+pub(crate) fn channel<R: Runtime>(
+    manager: &impl Manager<R>,
+    event: &'static str,
+) -> Cow<'static, str> {
+    match manager.try_state::<PluginName>() {
+        Some(name) if name.0 != DEFAULT_PLUGIN_NAME => {
+            let event = event.strip_prefix("tracing://").unwrap_or(event);
+            Cow::Owned(format!("{}://{event}", name.0))
+        }
+        _ => Cow::Borrowed(event),
+    }
+}
@@ -60,14 +60,19 @@
 //!     .expect("error while running tauri application");
 //! ```
 
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
 
 use tauri::{AppHandle, Manager, Runtime};
 use tauri_plugin_profiling::ProfilingExt;
+use tracing::callsite::Identifier;
 use tracing::span::Attributes;
-use tracing::{Id, Span, Subscriber};
+use tracing::{Id, Metadata, Span, Subscriber};
 use tracing_subscriber::Layer;
 use tracing_subscriber::layer::Context;
 use tracing_subscriber::registry::LookupSpan;
@@ -84,10 +89,13 @@ pub use tauri_plugin_profiling::{
 // ============================================================================
 
 /// A recorded span timing event.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct SpanEvent {
-    /// Span name (target::name format)
-    pub name: String,
+    /// Span name (target::name format), shared by all events of spans from
+    /// the same callsite
+    pub name: Arc<str>,
     /// Span ID
     pub span_id: u64,
     /// Parent span ID (0 if none)
@@ -96,12 +104,28 @@ pub struct SpanEvent {
     pub event_type: SpanEventType,
     /// Timestamp relative to capture start (microseconds)
     pub timestamp_us: u64,
-    /// Thread ID where the event occurred
-    pub thread_id: u64,
+    /// Ordinal of the thread where the event occurred, numbered from 1 in the
+    /// order threads first record events
+    #[serde(default)]
+    pub thread_ordinal: u64,
+    /// Name of the thread where the event occurred, if it has one
+    #[serde(default)]
+    pub thread_name: Option<Arc<str>>,
+    /// Recorded span fields, see [`SpanTimingLayer::with_fields()`]
+    #[serde(default, skip_serializing_if = "SpanFields::is_empty")]
+    pub fields: SpanFields,
 }
 
+/// Span field values recorded by a [`SpanTimingLayer`], by field name.
+pub type SpanFields = BTreeMap<String, String>;
+
+/// The span fields that [`SpanTimingLayer`] records by default.
+pub const DEFAULT_SPAN_FIELDS: &[&str] = &["id", "path"];
+
 /// Type of span event.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
 pub enum SpanEventType {
     /// Span was entered
     Enter,
@@ -111,6 +135,18 @@ pub enum SpanEventType {
     Close,
 }
 
+/// The ordinal of the next thread to record a span event.
+static NEXT_THREAD_ORDINAL: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// The ordinal and name of the current thread, assigned when it first
+    /// records a span event.
+    static CURRENT_THREAD: (u64, Option<Arc<str>>) = (
+        NEXT_THREAD_ORDINAL.fetch_add(1, Ordering::Relaxed),
+        std::thread::current().name().map(Arc::from),
+    );
+}
+
 /// Shared state for span timing capture.
 #[derive(Debug)]
 pub struct SpanTimingCapture {
@@ -118,6 +154,8 @@ pub struct SpanTimingCapture {
     start_time: Mutex<Option<Instant>>,
     capturing: AtomicBool,
     next_id: AtomicU64,
+    stats_enabled: AtomicBool,
+    stats: Mutex<Option<RollingStats>>,
 }
 
 impl SpanTimingCapture {
@@ -128,6 +166,8 @@ impl SpanTimingCapture {
             start_time: Mutex::new(None),
             capturing: AtomicBool::new(false),
             next_id: AtomicU64::new(1),
+            stats_enabled: AtomicBool::new(false),
+            stats: Mutex::new(None),
         }
     }
 
@@ -157,7 +197,14 @@ impl SpanTimingCapture {
         self.capturing.load(Ordering::SeqCst)
     }
 
-    fn record_event(&self, name: String, span_id: u64, parent_id: u64, event_type: SpanEventType) {
+    fn record_event(
+        &self,
+        name: Arc<str>,
+        span_id: u64,
+        parent_id: u64,
+        event_type: SpanEventType,
+        fields: &SpanFields,
+    ) {
         if !self.capturing.load(Ordering::SeqCst) {
             return;
         }
@@ -168,13 +215,7 @@ impl SpanTimingCapture {
             0
         };
 
-        // Use hash of thread ID since as_u64() is unstable
-        let thread_id = {
-            use std::hash::{Hash, Hasher};
-            let mut hasher = std::collections::hash_map::DefaultHasher::new();
-            std::thread::current().id().hash(&mut hasher);
-            hasher.finish()
-        };
+        let (thread_ordinal, thread_name) = CURRENT_THREAD.with(|thread| thread.clone());
 
         let event = SpanEvent {
             name,
@@ -182,7 +223,9 @@ impl SpanTimingCapture {
             parent_id,
             event_type,
             timestamp_us,
-            thread_id,
+            thread_ordinal,
+            thread_name,
+            fields: fields.clone(),
         };
 
         if let Ok(mut events) = self.events.lock() {
@@ -193,14 +236,199 @@ impl SpanTimingCapture {
     fn next_id(&self) -> u64 {
         self.next_id.fetch_add(1, Ordering::SeqCst)
     }
+
+    /// Returns the busy time statistics of each span name, by total time, if
+    /// enabled with [`SpanTimingLayer::with_stats()`].
+    ///
+    /// Statistics cover the last one to two windows.
+    pub fn span_stats(&self) -> Option<Vec<SpanStats>> {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = stats.as_mut()?;
+        stats.rotate();
+        let mut merged = stats.current.clone();
+        for (name, histogram) in &stats.previous {
+            merged.entry(*name).or_default().merge(histogram);
+        }
+        let mut span_stats: Vec<SpanStats> = merged
+            .into_iter()
+            .map(|((target, name), histogram)| histogram.stats(format!("{target}::{name}")))
+            .collect();
+        span_stats.sort_by(|a, b| b.total_us.cmp(&a.total_us));
+        Some(span_stats)
+    }
+
+    fn enable_stats(&self, window: Duration) {
+        *self.stats.lock().unwrap_or_else(|e| e.into_inner()) = Some(RollingStats {
+            window,
+            started: Instant::now(),
+            current: HashMap::new(),
+            previous: HashMap::new(),
+        });
+        self.stats_enabled.store(true, Ordering::SeqCst);
+    }
+
+    fn stats_enabled(&self) -> bool {
+        self.stats_enabled.load(Ordering::Relaxed)
+    }
+
+    fn record_busy(&self, key: SpanKey, busy: Duration) {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(stats) = stats.as_mut() {
+            stats.rotate();
+            stats.current.entry(key).or_default().record(busy);
+        }
+    }
+}
+
+// ============================================================================
+// Span Statistics
+// ============================================================================
+
+/// The default window of [`SpanTimingLayer::with_stats()`].
+pub const DEFAULT_SPAN_STATS_WINDOW: Duration = Duration::from_secs(300);
+
+/// The busy time statistics of a span name, returned by [`get_span_stats()`].
+///
+/// Percentiles are estimated from power-of-two histogram buckets, so they are
+/// accurate to within a factor of two.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct SpanStats {
+    /// Span name (target::name format)
+    pub name: String,
+    /// Number of times the span was entered and exited
+    pub count: u64,
+    /// Total busy time (microseconds)
+    pub total_us: u64,
+    /// Mean busy time per entry (microseconds)
+    pub mean_us: u64,
+    /// Longest busy time of an entry (microseconds)
+    pub max_us: u64,
+    /// Estimated median busy time (microseconds)
+    pub p50_us: u64,
+    /// Estimated 90th percentile busy time (microseconds)
+    pub p90_us: u64,
+    /// Estimated 99th percentile busy time (microseconds)
+    pub p99_us: u64,
+}
+
+/// Busy times of a span name, in power-of-two microsecond buckets.
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    count: u64,
+    total_us: u64,
+    max_us: u64,
+    buckets: [u64; 32],
+}
+
+impl Histogram {
+    fn record(&mut self, busy: Duration) {
+        let us = busy.as_micros() as u64;
+        self.count += 1;
+        self.total_us += us;
+        self.max_us = self.max_us.max(us);
+        // Bucket `i` holds times below 2^i microseconds
+        let bucket = (u64::BITS - us.leading_zeros()) as usize;
+        self.buckets[bucket.min(self.buckets.len() - 1)] += 1;
+    }
+
+    fn merge(&mut self, other: &Histogram) {
+        self.count += other.count;
+        self.total_us += other.total_us;
+        self.max_us = self.max_us.max(other.max_us);
+        for (bucket, other) in self.buckets.iter_mut().zip(other.buckets) {
+            *bucket += other;
+        }
+    }
+
+    fn percentile(&self, quantile: f64) -> u64 {
+        let rank = (self.count as f64 * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return (1u64 << i).saturating_sub(1).min(self.max_us);
+            }
+        }
+        self.max_us
+    }
+
+    fn stats(&self, name: String) -> SpanStats {
+        SpanStats {
+            name,
+            count: self.count,
+            total_us: self.total_us,
+            mean_us: self.total_us.checked_div(self.count).unwrap_or(0),
+            max_us: self.max_us,
+            p50_us: self.percentile(0.5),
+            p90_us: self.percentile(0.9),
+            p99_us: self.percentile(0.99),
+        }
+    }
+}
+
+/// The target and name of a span.
+type SpanKey = (&'static str, &'static str);
+
+/// Histograms of the current and previous windows.
+#[derive(Debug)]
+struct RollingStats {
+    window: Duration,
+    started: Instant,
+    current: HashMap<SpanKey, Histogram>,
+    previous: HashMap<SpanKey, Histogram>,
+}
+
+impl RollingStats {
+    fn rotate(&mut self) {
+        let elapsed = self.started.elapsed();
+        if elapsed < self.window {
+            return;
+        }
+        self.previous = if elapsed < self.window * 2 {
+            std::mem::take(&mut self.current)
+        } else {
+            self.current.clear();
+            HashMap::new()
+        };
+        self.started = Instant::now();
+    }
+}
+
+/// Returns the busy time statistics of each span name, by total time.
+///
+/// Requires span statistics to be enabled, with
+/// [`Builder::with_span_stats()`](crate::Builder::with_span_stats) or
+/// [`SpanTimingLayer::with_stats()`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+/// for stats in tauri_plugin_tracing::get_span_stats(app)?.iter().take(5) {
+///     println!("{}: {} calls, p99 {}us", stats.name, stats.count, stats.p99_us);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn get_span_stats<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Vec<SpanStats>> {
+    app.try_state::<Arc<SpanTimingCapture>>()
+        .and_then(|capture| capture.span_stats())
+        .ok_or(crate::Error::SpanStatsDisabled)
 }
 
 /// A tracing layer that captures span timing for correlation with CPU profiles.
 ///
 /// Create with [`SpanTimingLayer::new()`], which returns both the layer and
 /// a [`SpanTimingCapture`] handle for controlling capture and retrieving events.
+///
+/// The [`DEFAULT_SPAN_FIELDS`] of each span are recorded, so instances such as
+/// `process_item{id=1}` and `process_item{id=2}` are reported separately.
 pub struct SpanTimingLayer {
     capture: Arc<SpanTimingCapture>,
+    fields: Vec<String>,
+    names: SpanNames,
 }
 
 impl SpanTimingLayer {
@@ -213,76 +441,213 @@ impl SpanTimingLayer {
         (
             Self {
                 capture: capture.clone(),
+                fields: DEFAULT_SPAN_FIELDS.iter().map(|f| f.to_string()).collect(),
+                names: SpanNames::default(),
             },
             capture,
         )
     }
+
+    /// Sets the span fields to record, replacing [`DEFAULT_SPAN_FIELDS`].
+    ///
+    /// Spans with the same name but different values of these fields are
+    /// reported separately. Pass an empty list to aggregate all instances of
+    /// a span under its name.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::SpanTimingLayer;
+    /// let (layer, capture) = SpanTimingLayer::new();
+    /// let layer = layer.with_fields(["id", "document"]);
+    /// ```
+    pub fn with_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Keeps per-span-name busy time statistics at all times, available
+    /// from [`SpanTimingCapture::span_stats()`] and [`get_span_stats()`].
+    ///
+    /// Unlike capture, no events are stored, so statistics are cheap enough
+    /// for production. They are kept in rolling windows of `window`, see
+    /// [`DEFAULT_SPAN_STATS_WINDOW`].
+    pub fn with_stats(self, window: Duration) -> Self {
+        self.capture.enable_stats(window);
+        self
+    }
 }
 
+/// When a span was entered, for span statistics. Spans entered on several
+/// threads at once have several entries.
+struct SpanEnteredAt(Vec<Instant>);
+
 impl Default for SpanTimingLayer {
     fn default() -> Self {
         Self::new().0
     }
 }
 
-// Store our internal ID on spans
-struct SpanTimingId(u64);
+// Store our internal ID, parent ID, name, and recorded fields on spans
+struct SpanTimingId {
+    id: u64,
+    parent_id: u64,
+    name: Arc<str>,
+    fields: SpanFields,
+}
 
+/// Span names in `target::name` format, interned by callsite so that spans
+/// created and entered many times do not format their name each time.
+#[derive(Default)]
+struct SpanNames(RwLock<HashMap<Identifier, Arc<str>>>);
+
+impl SpanNames {
+    fn get(&self, metadata: &'static Metadata<'static>) -> Arc<str> {
+        let callsite = metadata.callsite();
+        if let Some(name) = self
+            .0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&callsite)
+        {
+            return name.clone();
+        }
+        self.0
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(callsite)
+            .or_insert_with(|| format!("{}::{}", metadata.target(), metadata.name()).into())
+            .clone()
+    }
+}
+
+/// Collects the allowlisted fields of a span.
+struct FieldVisitor<'a> {
+    allowed: &'a [String],
+    fields: &'a mut SpanFields,
+}
+
+impl tracing::field::Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if self.allowed.iter().any(|name| name == field.name()) {
+            self.fields
+                .insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if self.allowed.iter().any(|name| name == field.name()) {
+            self.fields
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+}
 impl<S> Layer<S> for SpanTimingLayer
 where
     S: Subscriber + for<'a> LookupSpan<'a>,
 {
     fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
-        let internal_id = self.capture.next_id();
-
-        // Store our internal ID on the span
-        if let Some(span) = ctx.span(id) {
-            span.extensions_mut().insert(SpanTimingId(internal_id));
+        let mut fields = SpanFields::new();
+        if !self.fields.is_empty() {
+            attrs.record(&mut FieldVisitor {
+                allowed: &self.fields,
+                fields: &mut fields,
+            });
         }
 
         // Get parent ID
         let parent_id = attrs
             .parent()
             .and_then(|pid| ctx.span(pid))
-            .and_then(|span| span.extensions().get::<SpanTimingId>().map(|id| id.0))
+            .and_then(|span| span.extensions().get::<SpanTimingId>().map(|t| t.id))
             .or_else(|| {
                 ctx.lookup_current()
-                    .and_then(|span| span.extensions().get::<SpanTimingId>().map(|id| id.0))
+                    .and_then(|span| span.extensions().get::<SpanTimingId>().map(|t| t.id))
             })
             .unwrap_or(0);
 
-        let name = format!("{}::{}", attrs.metadata().target(), attrs.metadata().name());
-        self.capture
-            .record_event(name, internal_id, parent_id, SpanEventType::Enter);
+        // Store our internal ID on the span. Spans are recorded when they
+        // are entered, so spans created on one thread and entered on another
+        // are nested where they run.
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTimingId {
+                id: self.capture.next_id(),
+                parent_id,
+                name: self.names.get(span.metadata()),
+                fields,
+            });
+        }
     }
 
-    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+    fn on_record(&self, id: &Id, values: &tracing::span::Record<'_>, ctx: Context<'_, S>) {
+        if self.fields.is_empty() {
+            return;
+        }
         if let Some(span) = ctx.span(id)
-            && let Some(timing_id) = span.extensions().get::<SpanTimingId>()
+            && let Some(timing_id) = span.extensions_mut().get_mut::<SpanTimingId>()
         {
-            let name = format!("{}::{}", span.metadata().target(), span.metadata().name());
-            self.capture
-                .record_event(name, timing_id.0, 0, SpanEventType::Enter);
+            values.record(&mut FieldVisitor {
+                allowed: &self.fields,
+                fields: &mut timing_id.fields,
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if self.capture.stats_enabled()
+            && let Some(span) = ctx.span(id)
+        {
+            let mut extensions = span.extensions_mut();
+            match extensions.get_mut::<SpanEnteredAt>() {
+                Some(entered) => entered.0.push(Instant::now()),
+                None => extensions.insert(SpanEnteredAt(vec![Instant::now()])),
+            }
         }
+        self.record(id, &ctx, SpanEventType::Enter);
     }
 
     fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
-        if let Some(span) = ctx.span(id)
-            && let Some(timing_id) = span.extensions().get::<SpanTimingId>()
+        if self.capture.stats_enabled()
+            && let Some(span) = ctx.span(id)
+            && let Some(entered) = span
+                .extensions_mut()
+                .get_mut::<SpanEnteredAt>()
+                .and_then(|entered| entered.0.pop())
         {
-            let name = format!("{}::{}", span.metadata().target(), span.metadata().name());
+            let metadata = span.metadata();
             self.capture
-                .record_event(name, timing_id.0, 0, SpanEventType::Exit);
+                .record_busy((metadata.target(), metadata.name()), entered.elapsed());
         }
+        self.record(id, &ctx, SpanEventType::Exit);
     }
 
     fn on_close(&self, id: Id, ctx: Context<'_, S>) {
-        if let Some(span) = ctx.span(&id)
+        self.record(&id, &ctx, SpanEventType::Close);
+    }
+}
+
+impl SpanTimingLayer {
+    fn record<S>(&self, id: &Id, ctx: &Context<'_, S>, event_type: SpanEventType)
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        if !self.capture.is_capturing() {
+            return;
+        }
+        if let Some(span) = ctx.span(id)
             && let Some(timing_id) = span.extensions().get::<SpanTimingId>()
         {
-            let name = format!("{}::{}", span.metadata().target(), span.metadata().name());
-            self.capture
-                .record_event(name, timing_id.0, 0, SpanEventType::Close);
+            self.capture.record_event(
+                timing_id.name.clone(),
+                timing_id.id,
+                timing_id.parent_id,
+                event_type,
+                &timing_id.fields,
+            );
         }
     }
 }
@@ -292,10 +657,15 @@ where
 // ============================================================================
 
 /// A span that was active during CPU profiling.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct ActiveSpan {
     /// Span name
     pub name: String,
+    /// Recorded span fields, which distinguish instances of the span
+    #[serde(default, skip_serializing_if = "SpanFields::is_empty")]
+    pub fields: SpanFields,
     /// Total time the span was active (microseconds)
     pub total_time_us: u64,
     /// Number of times the span was entered
@@ -304,17 +674,265 @@ pub struct ActiveSpan {
     pub percentage: f64,
 }
 
+impl ActiveSpan {
+    /// Returns the span name with its recorded fields, e.g.
+    /// `app::process_item{id=1}`.
+    pub fn label(&self) -> String {
+        span_label(&self.name, &self.fields)
+    }
+}
+
+fn span_label(name: &str, fields: &SpanFields) -> String {
+    if fields.is_empty() {
+        return name.to_string();
+    }
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    format!("{name}{{{}}}", fields.join(" "))
+}
+
+/// The spans that were active on one thread during CPU profiling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ThreadSpans {
+    /// Thread ordinal, see [`SpanEvent::thread_ordinal`]
+    pub thread_ordinal: u64,
+    /// Thread name, if it has one
+    pub thread_name: Option<String>,
+    /// Spans that were active on the thread, sorted by total time
+    pub active_spans: Vec<ActiveSpan>,
+}
+
 /// Report correlating CPU profile with span timing.
-#[derive(Debug)]
+///
+/// [`SpanAwareProfilingExt::stop_span_aware_profile()`] saves each report as
+/// a profiling session, see [`list_profiling_sessions()`]. Use
+/// [`to_json()`](Self::to_json), [`write_json()`](Self::write_json), and
+/// [`from_file()`](Self::from_file) to keep reports elsewhere, e.g. to
+/// compare them across app versions or check them in CI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct SpanCorrelationReport {
     /// CPU profile result
+    #[cfg_attr(feature = "specta", specta(type = ProfileResultType))]
     pub profile: ProfileResult,
     /// Total profile duration in microseconds
     pub duration_us: u64,
     /// Spans that were active during profiling, sorted by total time
     pub active_spans: Vec<ActiveSpan>,
+    /// Spans that were active during profiling on each thread, by thread
+    /// ordinal
+    #[serde(default)]
+    pub threads: Vec<ThreadSpans>,
     /// Raw span events (for advanced analysis)
     pub events: Vec<SpanEvent>,
+    /// Latest resource snapshot, if the resource monitor is enabled
+    pub resources: Option<crate::ResourceSnapshot>,
+    /// When and by which app version the report was created
+    #[serde(default)]
+    pub metadata: ReportMetadata,
+}
+
+/// The TypeScript type of [`ProfileResult`], which does not implement
+/// `specta::Type`.
+#[cfg(feature = "specta")]
+#[derive(specta::Type)]
+#[specta(rename = "ProfileResult")]
+#[allow(dead_code)]
+struct ProfileResultType {
+    flamegraph_path: PathBuf,
+    sample_count: usize,
+    duration_ms: u64,
+}
+
+/// When and by which app version a [`SpanCorrelationReport`] was created.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ReportMetadata {
+    /// The app name
+    pub app_name: String,
+    /// The app version
+    pub app_version: String,
+    /// The version of this plugin
+    pub plugin_version: String,
+    /// The operating system, e.g. `macos`
+    pub os: String,
+    /// The CPU architecture, e.g. `aarch64`
+    pub arch: String,
+    /// When the report was created, in milliseconds since the Unix epoch
+    pub created_at_ms: u64,
+    /// The span that made a [`ProfilingWatchdog`](crate::ProfilingWatchdog)
+    /// record the profile, if one did
+    #[serde(default)]
+    pub trigger: Option<String>,
+}
+
+impl ReportMetadata {
+    fn capture<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let package = app.package_info();
+        Self {
+            app_name: package.name.clone(),
+            app_version: package.version.to_string(),
+            plugin_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            created_at_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|since| since.as_millis() as u64)
+                .unwrap_or_default(),
+            trigger: None,
+        }
+    }
+}
+
+impl SpanCorrelationReport {
+    /// Returns the report as pretty-printed JSON, e.g. for dashboards or CI
+    /// performance checks.
+    ///
+    /// Field names are camelCase, as in the report returned to the frontend.
+    pub fn to_json(&self) -> crate::Result<String> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        Ok(json)
+    }
+
+    /// Writes the report to `path` as JSON, creating its parent directories.
+    pub fn write_json(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Writes the report to `path` as JSON, same as
+    /// [`write_json()`](Self::write_json).
+    pub fn save(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        self.write_json(path)
+    }
+
+    /// Returns the span timing as folded stacks, one `frame;frame;... count`
+    /// line per stack, where the count is the time spent in the innermost
+    /// span itself, in microseconds.
+    ///
+    /// Each thread is a root frame, and spans are nested as they were
+    /// entered on it. Spans still entered when profiling stopped count until
+    /// the end of the profile.
+    pub fn folded_stacks(&self) -> Vec<String> {
+        struct Frame {
+            span_id: u64,
+            label: String,
+            entered_us: u64,
+            children_us: u64,
+        }
+
+        let mut stacks: BTreeMap<String, u64> = BTreeMap::new();
+        let mut threads: BTreeMap<u64, (String, Vec<Frame>)> = BTreeMap::new();
+
+        // Pops the innermost frame of a thread at `end_us`, adding its own
+        // time to its stack
+        fn pop(
+            stacks: &mut BTreeMap<String, u64>,
+            root: &str,
+            frames: &mut Vec<Frame>,
+            end_us: u64,
+        ) {
+            let Some(frame) = frames.pop() else {
+                return;
+            };
+            let total_us = end_us.saturating_sub(frame.entered_us);
+            if let Some(parent) = frames.last_mut() {
+                parent.children_us += total_us;
+            }
+            let self_us = total_us.saturating_sub(frame.children_us);
+            if self_us > 0 {
+                let mut stack = root.to_string();
+                for outer in frames.iter().chain([&frame]) {
+                    stack.push(';');
+                    stack.push_str(&outer.label);
+                }
+                *stacks.entry(stack).or_default() += self_us;
+            }
+        }
+
+        for event in &self.events {
+            let (root, frames) = threads.entry(event.thread_ordinal).or_insert_with(|| {
+                let root = match &event.thread_name {
+                    Some(name) => format!("Thread #{} ({name})", event.thread_ordinal),
+                    None => format!("Thread #{}", event.thread_ordinal),
+                };
+                (folded_frame(&root), Vec::new())
+            });
+            match event.event_type {
+                SpanEventType::Enter => frames.push(Frame {
+                    span_id: event.span_id,
+                    label: folded_frame(&span_label(&event.name, &event.fields)),
+                    entered_us: event.timestamp_us,
+                    children_us: 0,
+                }),
+                SpanEventType::Exit | SpanEventType::Close => {
+                    // Spans exited out of order also end the spans they
+                    // entered; exits of spans entered before profiling are
+                    // ignored
+                    if let Some(index) = frames.iter().rposition(|f| f.span_id == event.span_id) {
+                        while frames.len() > index {
+                            pop(&mut stacks, root, frames, event.timestamp_us);
+                        }
+                    }
+                }
+            }
+        }
+        for (root, frames) in threads.values_mut() {
+            while !frames.is_empty() {
+                pop(&mut stacks, root, frames, self.duration_us);
+            }
+        }
+
+        stacks
+            .into_iter()
+            .map(|(stack, count)| format!("{stack} {count}"))
+            .collect()
+    }
+
+    /// Renders the span timing as a flamegraph SVG at `path`, without CPU
+    /// samples or the `tracing-flame` layer.
+    ///
+    /// Widths are the time spent in each span, see
+    /// [`folded_stacks()`](Self::folded_stacks). Requires the `flamegraph`
+    /// feature.
+    #[cfg(feature = "flamegraph")]
+    pub fn write_flamegraph(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        use inferno::flamegraph::{self, Options};
+
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let lines = self.folded_stacks();
+
+        let mut options = Options::default();
+        options.title = "Span Flamegraph".to_string();
+        options.count_name = "μs".to_string();
+
+        let svg_file = std::fs::File::create(path)?;
+        flamegraph::from_lines(&mut options, lines.iter().map(|s| s.as_str()), svg_file)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Reads a report written with [`write_json()`](Self::write_json) or
+    /// [`save()`](Self::save).
+    pub fn from_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let json = std::fs::read(path)?;
+        let report = serde_json::from_slice(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(report)
+    }
 }
 
 impl std::fmt::Display for SpanCorrelationReport {
@@ -323,29 +941,50 @@ impl std::fmt::Display for SpanCorrelationReport {
         writeln!(f, "Duration: {:.2}ms", self.duration_us as f64 / 1000.0)?;
         writeln!(f, "Samples: {}", self.profile.sample_count)?;
         writeln!(f, "Flamegraph: {}", self.profile.flamegraph_path.display())?;
+        if let Some(resources) = &self.resources {
+            writeln!(f, "Resources: {resources}")?;
+        }
         writeln!(f)?;
         writeln!(f, "Active Spans (by time):")?;
         writeln!(f, "{:-<60}", "")?;
 
-        for span in &self.active_spans {
-            writeln!(
-                f,
-                "{:50} {:>6.1}% ({:.2}ms, {} entries)",
-                truncate_span_name(&span.name, 50),
-                span.percentage,
-                span.total_time_us as f64 / 1000.0,
-                span.enter_count
-            )?;
-        }
-
         if self.active_spans.is_empty() {
             writeln!(f, "(no spans recorded during profiling)")?;
+        } else if self.threads.is_empty() {
+            // Reports saved before spans were grouped by thread
+            write_active_spans(f, &self.active_spans)?;
+        }
+        for thread in &self.threads {
+            match &thread.thread_name {
+                Some(name) => writeln!(f, "Thread #{} ({name}):", thread.thread_ordinal)?,
+                None => writeln!(f, "Thread #{}:", thread.thread_ordinal)?,
+            }
+            write_active_spans(f, &thread.active_spans)?;
         }
 
         Ok(())
     }
 }
 
+fn write_active_spans(f: &mut std::fmt::Formatter<'_>, spans: &[ActiveSpan]) -> std::fmt::Result {
+    for span in spans {
+        writeln!(
+            f,
+            "  {:48} {:>6.1}% ({:.2}ms, {} entries)",
+            truncate_span_name(&span.label(), 48),
+            span.percentage,
+            span.total_time_us as f64 / 1000.0,
+            span.enter_count
+        )?;
+    }
+    Ok(())
+}
+
+/// Replaces the characters that separate frames and lines in folded stacks.
+fn folded_frame(label: &str) -> String {
+    label.replace([';', '\n', '\r'], " ")
+}
+
 fn truncate_span_name(name: &str, max_len: usize) -> String {
     if name.len() <= max_len {
         name.to_string()
@@ -356,20 +995,20 @@ fn truncate_span_name(name: &str, max_len: usize) -> String {
 
 /// Analyzes span events to compute active span statistics.
 fn analyze_span_events(events: &[SpanEvent], duration_us: u64) -> Vec<ActiveSpan> {
-    use std::collections::HashMap;
-
-    // Track active time per span name
+    // Track active time per span name and fields
     #[derive(Default)]
-    struct SpanStats {
+    struct Totals {
         total_time_us: u64,
         enter_count: u64,
         last_enter_time: Option<u64>,
     }
 
-    let mut stats: HashMap<String, SpanStats> = HashMap::new();
+    let mut stats: HashMap<(Arc<str>, SpanFields), Totals> = HashMap::new();
 
     for event in events {
-        let entry = stats.entry(event.name.clone()).or_default();
+        let entry = stats
+            .entry((event.name.clone(), event.fields.clone()))
+            .or_default();
 
         match event.event_type {
             SpanEventType::Enter => {
@@ -394,8 +1033,9 @@ fn analyze_span_events(events: &[SpanEvent], duration_us: u64) -> Vec<ActiveSpan
     let mut active_spans: Vec<_> = stats
         .into_iter()
         .filter(|(_, s)| s.total_time_us > 0 || s.enter_count > 0)
-        .map(|(name, s)| ActiveSpan {
-            name,
+        .map(|((name, fields), s)| ActiveSpan {
+            name: name.to_string(),
+            fields,
             total_time_us: s.total_time_us,
             enter_count: s.enter_count,
             percentage: if duration_us > 0 {
@@ -412,6 +1052,26 @@ fn analyze_span_events(events: &[SpanEvent], duration_us: u64) -> Vec<ActiveSpan
     active_spans
 }
 
+/// Groups span events by thread and computes the active spans of each.
+fn analyze_threads(events: &[SpanEvent], duration_us: u64) -> Vec<ThreadSpans> {
+    let mut threads: BTreeMap<u64, (Option<Arc<str>>, Vec<SpanEvent>)> = BTreeMap::new();
+    for event in events {
+        let (_, thread_events) = threads
+            .entry(event.thread_ordinal)
+            .or_insert_with(|| (event.thread_name.clone(), Vec::new()));
+        thread_events.push(event.clone());
+    }
+
+    threads
+        .into_iter()
+        .map(|(thread_ordinal, (thread_name, events))| ThreadSpans {
+            thread_ordinal,
+            thread_name: thread_name.as_deref().map(str::to_string),
+            active_spans: analyze_span_events(&events, duration_us),
+        })
+        .collect()
+}
+
 // ============================================================================
 // Basic Traced Profiling (existing functionality)
 // ============================================================================
@@ -454,6 +1114,8 @@ impl<R: Runtime, T: Manager<R>> TracedProfilingExt<R> for T {
             flamegraph = %result.flamegraph_path.display(),
             "CPU profiling stopped"
         );
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::record_capture(self.app_handle(), &result.flamegraph_path);
 
         if let Some(state) = self.app_handle().try_state::<ProfilingSpanGuard>()
             && let Ok(mut guard) = state.span.lock()
@@ -524,36 +1186,60 @@ impl<R: Runtime, T: Manager<R>> SpanAwareProfilingExt<R> for T {
     }
 
     fn stop_span_aware_profile(&self) -> ProfilingResult<SpanCorrelationReport> {
-        // Stop CPU profiling
-        let profile = self.app_handle().stop_cpu_profile()?;
-
-        // Stop span capture and get events
-        let events = if let Some(capture) = self.app_handle().try_state::<Arc<SpanTimingCapture>>()
-        {
-            capture.stop_capture()
-        } else {
-            Vec::new()
-        };
-
-        let duration_us = profile.duration_ms * 1000;
-        let active_spans = analyze_span_events(&events, duration_us);
-
-        tracing::info!(
-            samples = profile.sample_count,
-            duration_ms = profile.duration_ms,
-            spans_recorded = events.len(),
-            active_spans = active_spans.len(),
-            flamegraph = %profile.flamegraph_path.display(),
-            "Span-aware CPU profiling stopped"
-        );
+        stop_span_aware_impl(self.app_handle(), None)
+    }
+}
 
-        Ok(SpanCorrelationReport {
-            profile,
-            duration_us,
-            active_spans,
-            events,
-        })
+/// Stops a span-aware profile and saves the report, recording `trigger` as
+/// the span that started it.
+pub(crate) fn stop_span_aware_impl<R: Runtime>(
+    app: &AppHandle<R>,
+    trigger: Option<String>,
+) -> ProfilingResult<SpanCorrelationReport> {
+    // Stop CPU profiling
+    let profile = app.stop_cpu_profile()?;
+
+    // Stop span capture and get events
+    let events = if let Some(capture) = app.try_state::<Arc<SpanTimingCapture>>() {
+        capture.stop_capture()
+    } else {
+        Vec::new()
+    };
+
+    let duration_us = profile.duration_ms * 1000;
+    let active_spans = analyze_span_events(&events, duration_us);
+    let threads = analyze_threads(&events, duration_us);
+    let resources = app
+        .try_state::<crate::ResourceMonitor>()
+        .and_then(|monitor| monitor.latest());
+
+    tracing::info!(
+        samples = profile.sample_count,
+        duration_ms = profile.duration_ms,
+        spans_recorded = events.len(),
+        active_spans = active_spans.len(),
+        flamegraph = %profile.flamegraph_path.display(),
+        "Span-aware CPU profiling stopped"
+    );
+    #[cfg(feature = "diagnostics")]
+    crate::diagnostics::record_capture(app, &profile.flamegraph_path);
+
+    let report = SpanCorrelationReport {
+        profile,
+        duration_us,
+        active_spans,
+        threads,
+        events,
+        resources,
+        metadata: ReportMetadata {
+            trigger,
+            ..ReportMetadata::capture(app)
+        },
+    };
+    if let Err(e) = save_session(app, &report) {
+        tracing::warn!("Failed to save profiling session: {}", e);
     }
+    Ok(report)
 }
 
 fn start_span_aware_impl<R: Runtime>(
@@ -579,3 +1265,331 @@ fn start_span_aware_impl<R: Runtime>(
 
     Ok(())
 }
+
+// ============================================================================
+// Profiling Sessions
+// ============================================================================
+
+/// A saved [`SpanCorrelationReport`], as returned by
+/// [`list_profiling_sessions()`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ProfilingSession {
+    /// The session ID, for [`load_profiling_session()`]
+    pub id: String,
+    /// The report file
+    pub path: PathBuf,
+    /// The app version that recorded the session
+    pub app_version: String,
+    /// When the session was saved, in milliseconds since the Unix epoch
+    pub created_at_ms: u64,
+    /// Total profile duration in microseconds
+    pub duration_us: u64,
+    /// Total samples collected
+    pub sample_count: usize,
+    /// The span that made a [`ProfilingWatchdog`](crate::ProfilingWatchdog)
+    /// record the session, if one did
+    pub trigger: Option<String>,
+}
+
+/// Returns the directory profiling sessions are saved to, `profiling` in the
+/// app data directory.
+pub fn profiling_sessions_dir<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PathBuf> {
+    Ok(app.path().app_data_dir()?.join("profiling"))
+}
+
+/// Returns the saved profiling sessions, newest first.
+///
+/// Files that cannot be read as a [`SpanCorrelationReport`] are skipped.
+pub fn list_profiling_sessions<R: Runtime>(
+    app: &AppHandle<R>,
+) -> crate::Result<Vec<ProfilingSession>> {
+    let dir = profiling_sessions_dir(app)?;
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut sessions: Vec<ProfilingSession> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let id = session_id(&path)?;
+            let report = SpanCorrelationReport::from_file(&path).ok()?;
+            Some(ProfilingSession {
+                id,
+                path,
+                app_version: report.metadata.app_version,
+                created_at_ms: report.metadata.created_at_ms,
+                duration_us: report.duration_us,
+                sample_count: report.profile.sample_count,
+                trigger: report.metadata.trigger,
+            })
+        })
+        .collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.created_at_ms));
+    Ok(sessions)
+}
+
+/// Reads the saved profiling session with `id`.
+pub fn load_profiling_session<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+) -> crate::Result<SpanCorrelationReport> {
+    // IDs come from the frontend, so keep them inside the sessions directory
+    if id.is_empty() || id.contains(['/', '\\', '.']) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid profiling session ID: {id}"),
+        )
+        .into());
+    }
+    SpanCorrelationReport::from_file(profiling_sessions_dir(app)?.join(format!("{id}.json")))
+}
+
+fn save_session<R: Runtime>(
+    app: &AppHandle<R>,
+    report: &SpanCorrelationReport,
+) -> crate::Result<PathBuf> {
+    let path = profiling_sessions_dir(app)?
+        .join(format!("session-{}.json", report.metadata.created_at_ms));
+    report.save(&path)?;
+    tracing::debug!(path = %path.display(), "Profiling session saved");
+    Ok(path)
+}
+
+/// Returns the ID of a session file, its name without `.json`.
+fn session_id(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let id = name.strip_prefix("session-")?.strip_suffix(".json")?;
+    Some(format!("session-{id}"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> SpanCorrelationReport {
+        SpanCorrelationReport {
+            profile: ProfileResult {
+                flamegraph_path: PathBuf::from("/tmp/cpu.svg"),
+                sample_count: 42,
+                duration_ms: 10,
+            },
+            duration_us: 10_000,
+            active_spans: vec![ActiveSpan {
+                name: "app::load".into(),
+                fields: SpanFields::new(),
+                total_time_us: 4_000,
+                enter_count: 2,
+                percentage: 40.0,
+            }],
+            threads: Vec::new(),
+            events: vec![SpanEvent {
+                name: "app::load".into(),
+                span_id: 1,
+                parent_id: 0,
+                event_type: SpanEventType::Enter,
+                timestamp_us: 5,
+                thread_ordinal: 1,
+                thread_name: Some("main".into()),
+                fields: SpanFields::new(),
+            }],
+            resources: None,
+            metadata: ReportMetadata {
+                app_version: "1.2.3".into(),
+                created_at_ms: 1_700_000_000_000,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn report_round_trips_through_file() {
+        let report = sample_report();
+        let path = std::env::temp_dir()
+            .join(format!("tracing-profiling-{}", std::process::id()))
+            .join("session-1700000000000.json");
+
+        report.save(&path).unwrap();
+        let loaded = SpanCorrelationReport::from_file(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded.profile.sample_count, 42);
+        assert_eq!(loaded.active_spans[0].name, "app::load");
+        assert_eq!(loaded.events[0].event_type, SpanEventType::Enter);
+        assert_eq!(loaded.metadata, report.metadata);
+        assert_eq!(session_id(&path).as_deref(), Some("session-1700000000000"));
+    }
+
+    #[test]
+    fn report_json_uses_camel_case() {
+        let json: serde_json::Value =
+            serde_json::from_str(&sample_report().to_json().unwrap()).unwrap();
+        assert_eq!(json["durationUs"], 10_000);
+        assert_eq!(json["activeSpans"][0]["totalTimeUs"], 4_000);
+        assert_eq!(json["events"][0]["eventType"], "enter");
+        assert_eq!(json["metadata"]["appVersion"], "1.2.3");
+    }
+
+    #[test]
+    fn active_spans_are_grouped_by_thread() {
+        let event = |name: &str, event_type, timestamp_us, thread_ordinal| SpanEvent {
+            name: name.into(),
+            span_id: thread_ordinal,
+            parent_id: 0,
+            event_type,
+            timestamp_us,
+            thread_ordinal,
+            thread_name: (thread_ordinal == 1).then(|| "main".into()),
+            fields: SpanFields::new(),
+        };
+        // The same span name overlaps on two threads
+        let events = vec![
+            event("app::load", SpanEventType::Enter, 0, 1),
+            event("app::load", SpanEventType::Enter, 1_000, 2),
+            event("app::load", SpanEventType::Exit, 2_000, 1),
+            event("app::load", SpanEventType::Exit, 5_000, 2),
+        ];
+
+        let threads = analyze_threads(&events, 10_000);
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].thread_name.as_deref(), Some("main"));
+        assert_eq!(threads[0].active_spans[0].total_time_us, 2_000);
+        assert_eq!(threads[1].thread_name, None);
+        assert_eq!(threads[1].active_spans[0].total_time_us, 4_000);
+
+        let report = SpanCorrelationReport {
+            threads,
+            ..sample_report()
+        };
+        let output = report.to_string();
+        assert!(output.contains("Thread #1 (main):"));
+        assert!(output.contains("Thread #2:"));
+    }
+
+    #[test]
+    fn instances_with_different_fields_are_reported_separately() {
+        let event = |id: &str, event_type, timestamp_us| SpanEvent {
+            name: "app::process_item".into(),
+            span_id: 1,
+            parent_id: 0,
+            event_type,
+            timestamp_us,
+            thread_ordinal: 1,
+            thread_name: None,
+            fields: SpanFields::from([("id".to_string(), id.to_string())]),
+        };
+        let events = vec![
+            event("1", SpanEventType::Enter, 0),
+            event("1", SpanEventType::Exit, 3_000),
+            event("2", SpanEventType::Enter, 3_000),
+            event("2", SpanEventType::Exit, 4_000),
+        ];
+
+        let spans = analyze_span_events(&events, 10_000);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].label(), "app::process_item{id=1}");
+        assert_eq!(spans[0].total_time_us, 3_000);
+        assert_eq!(spans[1].label(), "app::process_item{id=2}");
+        assert_eq!(spans[1].total_time_us, 1_000);
+    }
+
+    #[test]
+    fn folded_stacks_nest_spans_per_thread() {
+        let event = |name: &str, span_id, event_type, timestamp_us, thread_ordinal| SpanEvent {
+            name: name.into(),
+            span_id,
+            parent_id: 0,
+            event_type,
+            timestamp_us,
+            thread_ordinal,
+            thread_name: (thread_ordinal == 1).then(|| "main".into()),
+            fields: SpanFields::new(),
+        };
+        let report = SpanCorrelationReport {
+            events: vec![
+                event("app::load", 1, SpanEventType::Enter, 0, 1),
+                event("app::parse", 2, SpanEventType::Enter, 1_000, 1),
+                event("app::parse", 2, SpanEventType::Exit, 3_000, 1),
+                event("app::load", 1, SpanEventType::Exit, 5_000, 1),
+                event("app::load", 1, SpanEventType::Close, 5_000, 1),
+                // Entered before profiling started
+                event("app::sync", 3, SpanEventType::Exit, 100, 2),
+                // Still entered when profiling stopped
+                event("app::save", 4, SpanEventType::Enter, 8_000, 2),
+            ],
+            ..sample_report()
+        };
+
+        assert_eq!(
+            report.folded_stacks(),
+            [
+                "Thread #1 (main);app::load 3000",
+                "Thread #1 (main);app::load;app::parse 2000",
+                "Thread #2;app::save 2000",
+            ]
+        );
+    }
+
+    #[test]
+    fn histogram_estimates_percentiles() {
+        let mut histogram = Histogram::default();
+        for us in 1..=100 {
+            histogram.record(Duration::from_micros(us));
+        }
+        let stats = histogram.stats("app::load".into());
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.total_us, 5_050);
+        assert_eq!(stats.mean_us, 50);
+        assert_eq!(stats.max_us, 100);
+        // 50 falls in the bucket of 32..64, 99 in that of 64..128
+        assert_eq!(stats.p50_us, 63);
+        assert_eq!(stats.p99_us, 100);
+    }
+
+    #[test]
+    fn span_stats_cover_two_windows() {
+        let capture = SpanTimingCapture::new();
+        assert_eq!(capture.span_stats(), None);
+
+        capture.enable_stats(Duration::from_millis(100));
+        capture.record_busy(("app", "load"), Duration::from_millis(2));
+        std::thread::sleep(Duration::from_millis(150));
+        capture.record_busy(("app", "load"), Duration::from_millis(4));
+
+        let stats = capture.span_stats().unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].name, "app::load");
+        assert_eq!(stats[0].count, 2);
+        assert_eq!(stats[0].max_us, 4_000);
+
+        std::thread::sleep(Duration::from_millis(250));
+        assert_eq!(capture.span_stats(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn span_names_are_shared_per_callsite() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let (layer, capture) = SpanTimingLayer::new();
+        let subscriber = tracing_subscriber::Registry::default().with(layer);
+        capture.start_capture();
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..2 {
+                tracing::info_span!("load").in_scope(|| {});
+            }
+        });
+
+        let events = capture.stop_capture();
+        assert_eq!(events.len(), 6);
+        assert!(
+            events
+                .iter()
+                .all(|event| Arc::ptr_eq(&event.name, &events[0].name))
+        );
+        assert!(events[0].name.ends_with("::load"));
+    }
+}
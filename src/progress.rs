@@ -0,0 +1,237 @@
+//! Progress of long-running tasks.
+//!
+//! Downloads, imports, and indexing report progress in their own way, often
+//! as a log line per item that floods the logs, or only to the UI.
+//! [`progress()`], or the `progress` command from JavaScript, reports how far
+//! a task is as `current` of `total` steps. The plugin logs it as an `INFO`
+//! event under the [`PROGRESS_TARGET`] target, with the `task`, `current`,
+//! and `total` fields, at most once per second for each task, besides its
+//! first and last update. With
+//! [`Builder::with_progress_events()`](crate::Builder::with_progress_events),
+//! updates are also emitted to the app as the [`PROGRESS_EVENT`] event, at
+//! most 10 times per second for each task, so the UI can show progress bars
+//! from the same calls.
+//!
+//! Progress must not go backwards: an update below the task's previous one
+//! is dropped with a `WARN` event. A task ends when `current` reaches
+//! `total`, after which its id can be reused.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::plugin_name::channel;
+
+/// The target used for progress events.
+pub const PROGRESS_TARGET: &str = "app::progress";
+
+/// The event emitted to the app for progress updates.
+pub const PROGRESS_EVENT: &str = "tracing://progress";
+
+/// The shortest time between two logged updates of a task.
+const LOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The shortest time between two emitted updates of a task.
+const EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The payload of the [`PROGRESS_EVENT`] event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ProgressUpdate {
+    /// The task's id.
+    pub task_id: String,
+    /// The number of steps done.
+    pub current: u64,
+    /// The number of steps in the task.
+    pub total: u64,
+    /// What the task is doing.
+    pub message: Option<String>,
+    /// Whether the task is complete.
+    pub done: bool,
+}
+
+/// Validates and throttles the progress of each task.
+///
+/// The plugin manages an instance of this type in Tauri state.
+#[derive(Debug, Default)]
+pub struct ProgressTracker {
+    tasks: Mutex<HashMap<String, Task>>,
+    emit: bool,
+}
+
+#[derive(Debug)]
+struct Task {
+    current: u64,
+    logged: Instant,
+    emitted: Instant,
+}
+
+/// What to do with an update.
+#[derive(Debug, PartialEq, Eq)]
+enum Admit {
+    /// The update is below the previous one.
+    Backwards { previous: u64 },
+    /// The update is recorded, and logged and emitted unless throttled.
+    Update { log: bool, emit: bool },
+}
+
+impl ProgressTracker {
+    /// Creates a tracker that emits [`PROGRESS_EVENT`] events if `emit` is
+    /// set.
+    pub fn new(emit: bool) -> Self {
+        Self {
+            tasks: Mutex::default(),
+            emit,
+        }
+    }
+
+    /// Records an update of the task `task_id`, and logs and emits it unless
+    /// throttled.
+    ///
+    /// `current` is capped at `total`.
+    pub fn report<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        task_id: &str,
+        current: u64,
+        total: u64,
+        message: Option<&str>,
+    ) {
+        let current = current.min(total);
+        let done = current == total;
+        match self.admit(task_id, current, done, Instant::now()) {
+            Admit::Backwards { previous } => tracing::warn!(
+                target: PROGRESS_TARGET,
+                task = task_id,
+                current,
+                previous,
+                "progress of {task_id} went backwards from {previous} to {current}"
+            ),
+            Admit::Update { log, emit } => {
+                if log {
+                    let message = message.unwrap_or(if done { "done" } else { "in progress" });
+                    tracing::info!(
+                        target: PROGRESS_TARGET,
+                        task = task_id,
+                        current,
+                        total,
+                        "{task_id}: {message} ({current}/{total})"
+                    );
+                }
+                if emit && self.emit {
+                    let _ = app.emit(
+                        &channel(app, PROGRESS_EVENT),
+                        ProgressUpdate {
+                            task_id: task_id.to_string(),
+                            current,
+                            total,
+                            message: message.map(str::to_string),
+                            done,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    fn admit(&self, task_id: &str, current: u64, done: bool, now: Instant) -> Admit {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(task) = tasks.get_mut(task_id) else {
+            if !done {
+                tasks.insert(
+                    task_id.to_string(),
+                    Task {
+                        current,
+                        logged: now,
+                        emitted: now,
+                    },
+                );
+            }
+            return Admit::Update {
+                log: true,
+                emit: true,
+            };
+        };
+        if current < task.current {
+            return Admit::Backwards {
+                previous: task.current,
+            };
+        }
+        if done {
+            tasks.remove(task_id);
+            return Admit::Update {
+                log: true,
+                emit: true,
+            };
+        }
+        task.current = current;
+        let log = now.duration_since(task.logged) >= LOG_INTERVAL;
+        let emit = now.duration_since(task.emitted) >= EMIT_INTERVAL;
+        if log {
+            task.logged = now;
+        }
+        if emit {
+            task.emitted = now;
+        }
+        Admit::Update { log, emit }
+    }
+}
+
+/// Reports the progress of the task `task_id` as `current` of `total` steps.
+///
+/// See the [module documentation](self) for how updates are logged and
+/// emitted. Does nothing if the plugin is not initialized.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn example(app: &tauri::AppHandle, files: &[std::path::PathBuf]) {
+/// for (n, _file) in files.iter().enumerate() {
+///     // ... import the file
+///     tauri_plugin_tracing::progress(app, "import", n as u64 + 1, files.len() as u64, None);
+/// }
+/// # }
+/// ```
+pub fn progress<R: Runtime>(
+    app: &AppHandle<R>,
+    task_id: &str,
+    current: u64,
+    total: u64,
+    message: Option<&str>,
+) {
+    if let Some(tracker) = app.try_state::<ProgressTracker>() {
+        tracker.report(app, task_id, current, total, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttles_and_rejects_backwards_progress() {
+        let tracker = ProgressTracker::new(true);
+        let start = Instant::now();
+        let update = |log, emit| Admit::Update { log, emit };
+
+        assert_eq!(tracker.admit("sync", 1, false, start), update(true, true));
+        let soon = start + Duration::from_millis(50);
+        assert_eq!(tracker.admit("sync", 2, false, soon), update(false, false));
+        let later = start + Duration::from_millis(200);
+        assert_eq!(tracker.admit("sync", 3, false, later), update(false, true));
+        assert_eq!(
+            tracker.admit("sync", 2, false, later),
+            Admit::Backwards { previous: 3 }
+        );
+        let second = start + LOG_INTERVAL;
+        assert_eq!(tracker.admit("sync", 4, false, second), update(true, true));
+        assert_eq!(tracker.admit("sync", 10, true, second), update(true, true));
+
+        // The id can be reused once the task is done
+        assert_eq!(tracker.admit("sync", 0, false, second), update(true, true));
+    }
+}
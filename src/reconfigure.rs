@@ -0,0 +1,241 @@
+//! Applying a new configuration to a running app.
+//!
+//! Apps with a logging settings page can rebuild a [`Builder`] from the
+//! user's choices and pass it to [`ReconfigureExt::reconfigure()`]. The log
+//! level and per-target levels take effect immediately. Settings that shape
+//! the subscriber itself, such as targets, rotation, and the format, are
+//! fixed when it is installed; those that differ from the running
+//! configuration are returned, so the app can ask for a restart.
+
+use std::collections::BTreeMap;
+
+use tauri::{Manager, Runtime};
+
+use crate::{Builder, FilterHandle};
+
+/// Settings that hold closures or layers, which cannot be compared and are
+/// never hot-applied.
+const OPAQUE_SETTINGS: &[&str] = &[
+    "custom_filter",
+    "custom_layer",
+    "rotation_hook",
+    "message_translator",
+];
+
+/// The settings of the running default subscriber that need a restart to
+/// change, managed by the plugin.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RunningSettings(BTreeMap<&'static str, String>);
+
+impl RunningSettings {
+    /// Captures the settings of `builder` that cannot be hot-applied.
+    pub(crate) fn of(builder: &Builder) -> Self {
+        let debug = |value: &dyn std::fmt::Debug| format!("{value:?}");
+        let opaque = |set: bool| if set { "set" } else { "unset" }.to_string();
+        let mut settings = BTreeMap::from([
+            (
+                "default_subscriber",
+                builder.set_default_subscriber.to_string(),
+            ),
+            ("custom_filter", opaque(builder.custom_filter.is_some())),
+            ("custom_layer", opaque(builder.custom_layer.is_some())),
+            ("target_globs", debug(&builder.target_globs)),
+            ("span_level", debug(&builder.span_level)),
+            ("targets", debug(&builder.targets)),
+            ("filter_for", debug(&builder.layer_filters)),
+            ("stderr_threshold", debug(&builder.stderr_threshold)),
+            ("rotation", debug(&builder.rotation)),
+            ("rotation_strategy", debug(&builder.rotation_strategy)),
+            ("max_file_size", debug(&builder.max_file_size)),
+            ("rotation_hook", opaque(builder.rotation_hook.is_some())),
+            ("instance_strategy", debug(&builder.instance_strategy)),
+            ("version_layout", debug(&builder.version_layout)),
+            ("file_name_template", debug(&builder.file_name_template)),
+            ("sync_policy", debug(&builder.sync_policy)),
+            ("timezone_strategy", debug(&builder.timezone_strategy)),
+            ("format", debug(&builder.log_format)),
+            ("show_file", builder.show_file.to_string()),
+            ("show_line_number", builder.show_line_number.to_string()),
+            ("show_thread_ids", builder.show_thread_ids.to_string()),
+            ("show_thread_names", builder.show_thread_names.to_string()),
+            ("show_target", builder.show_target.to_string()),
+            ("show_level", builder.show_level.to_string()),
+            ("slow_span_threshold", debug(&builder.slow_span_threshold)),
+            (
+                "resource_monitor",
+                debug(&builder.resource_monitor_interval),
+            ),
+            ("heartbeat", debug(&builder.heartbeat_interval)),
+            ("event_loop_watchdog", debug(&builder.event_loop_threshold)),
+            ("capture_output", builder.capture_output.to_string()),
+            ("filter_persistence", builder.persist_filter.to_string()),
+            ("filter_stats", builder.filter_stats.to_string()),
+            ("signal_control", builder.signal_control.to_string()),
+            ("max_message_len", debug(&builder.max_message_len)),
+            ("max_stack_frames", debug(&builder.max_stack_frames)),
+            ("sampling", debug(&builder.sampling)),
+            ("breadcrumbs", debug(&builder.breadcrumbs)),
+            ("sequence_numbers", builder.sequence_numbers.to_string()),
+            (
+                "message_translator",
+                opaque(builder.message_translator.is_some()),
+            ),
+            ("notifications", debug(&builder.notifications)),
+            ("devtools_console", builder.devtools_console.to_string()),
+            ("log_history", debug(&builder.log_history)),
+            (
+                "navigation_breadcrumbs",
+                builder.navigation_breadcrumbs.to_string(),
+            ),
+            ("route_spans", builder.route_spans.to_string()),
+            ("progress_events", builder.progress_events.to_string()),
+            ("event_catalog", debug(&builder.event_catalog)),
+            ("test_mode", builder.test_mode.to_string()),
+            ("metrics_events", debug(&builder.metrics_interval)),
+            ("launch_logging", debug(&builder.launch_logging)),
+            ("update_log_file", debug(&builder.update_log_file)),
+            ("os_log_categories", debug(&builder.os_log_categories)),
+            ("resource_attributes", debug(&builder.resource_attributes)),
+            ("build_info", debug(&builder.build_info)),
+        ]);
+        #[cfg(feature = "otlp")]
+        settings.insert("otlp_resource", debug(&builder.otlp_resource));
+        #[cfg(feature = "diagnostics-endpoint")]
+        settings.insert("diagnostics_endpoint", debug(&builder.diagnostics_endpoint));
+        #[cfg(feature = "diagnostics")]
+        settings.insert(
+            "report_signing_key",
+            builder
+                .report_signing_key
+                .as_deref()
+                .map(crate::diagnostics::sha256_hex)
+                .unwrap_or_default(),
+        );
+        #[cfg(feature = "colored")]
+        settings.extend([
+            ("colors", builder.use_colors.to_string()),
+            ("color_theme", debug(&builder.color_theme)),
+            ("editor_links", debug(&builder.editor_links)),
+        ]);
+        #[cfg(feature = "flamegraph")]
+        settings.insert("flamegraph", builder.enable_flamegraph.to_string());
+        #[cfg(feature = "profiling")]
+        settings.extend([
+            ("profiling_watchdog", debug(&builder.profiling_watchdog)),
+            ("span_timing_fields", debug(&builder.span_timing_fields)),
+            ("span_stats", debug(&builder.span_stats_window)),
+        ]);
+        Self(settings)
+    }
+
+    /// Returns the settings of `other` that differ from these, or that
+    /// cannot be compared.
+    pub(crate) fn changed(&self, other: &Self) -> Vec<&'static str> {
+        other
+            .0
+            .iter()
+            .filter(|(name, value)| {
+                self.0.get(*name) != Some(*value)
+                    || (OPAQUE_SETTINGS.contains(*name) && *value == "set")
+            })
+            .map(|(name, _)| *name)
+            .collect()
+    }
+}
+
+/// Extension trait for applying a new [`Builder`] to a running app.
+///
+/// Implemented for anything that implements [`Manager`] (e.g. [`AppHandle`](tauri::AppHandle),
+/// [`App`](tauri::App), [`WebviewWindow`](tauri::WebviewWindow)).
+pub trait ReconfigureExt<R: Runtime> {
+    /// Applies the log level and per-target levels of `builder` to the
+    /// default subscriber, and returns the names of the other settings that
+    /// differ from the running configuration and need a restart.
+    ///
+    /// Levels changed at runtime, e.g. with `set_target_level`, are
+    /// discarded, including persisted ones. Settings that hold closures or
+    /// layers, such as `custom_layer`, are returned whenever they are set.
+    ///
+    /// Returns [`Error::LoggerNotInitialized`](crate::Error::LoggerNotInitialized)
+    /// unless the plugin installed the default subscriber.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri_plugin_tracing::{Builder, LevelFilter, ReconfigureExt};
+    /// # fn example(app: &tauri::AppHandle) -> tauri_plugin_tracing::Result<()> {
+    /// let pending = app.reconfigure(
+    ///     Builder::new()
+    ///         .with_max_level(LevelFilter::DEBUG)
+    ///         .with_target("my_app::sync", LevelFilter::TRACE)
+    ///         .with_file_logging()
+    ///         .with_default_subscriber(),
+    /// )?;
+    /// if !pending.is_empty() {
+    ///     println!("Restart to apply: {}", pending.join(", "));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn reconfigure(&self, builder: Builder) -> crate::Result<Vec<&'static str>>;
+}
+
+impl<R: Runtime, T: Manager<R>> ReconfigureExt<R> for T {
+    fn reconfigure(&self, builder: Builder) -> crate::Result<Vec<&'static str>> {
+        let (Some(running), Some(filter)) = (
+            self.try_state::<RunningSettings>(),
+            self.try_state::<FilterHandle>(),
+        ) else {
+            return Err(crate::Error::LoggerNotInitialized);
+        };
+
+        let base = builder.build_filter();
+        crate::filter::warn_if_compiled_out(None, builder.log_level);
+        for (target, level) in base.iter() {
+            crate::filter::warn_if_compiled_out(Some(target), level);
+        }
+        filter.replace_base(base)?;
+
+        let pending = running.changed(&RunningSettings::of(&builder));
+        tracing::info!(filter = %filter.current(), ?pending, "applied new logging configuration");
+        Ok(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LevelFilter, LogFormat, Rotation, Target};
+
+    #[test]
+    fn levels_are_not_pending() {
+        let running = RunningSettings::of(&Builder::new().with_max_level(LevelFilter::INFO));
+        let new = Builder::new()
+            .with_max_level(LevelFilter::TRACE)
+            .with_target("hyper", LevelFilter::WARN);
+        assert!(running.changed(&RunningSettings::of(&new)).is_empty());
+    }
+
+    #[test]
+    fn changed_settings_are_pending() {
+        let running = RunningSettings::of(&Builder::new());
+        let new = Builder::new()
+            .target(Target::Stderr)
+            .with_rotation(Rotation::Hourly)
+            .with_format(LogFormat::Compact);
+        assert_eq!(
+            running.changed(&RunningSettings::of(&new)),
+            ["format", "rotation", "targets"]
+        );
+    }
+
+    #[test]
+    fn opaque_settings_are_pending_when_set() {
+        let builder = || Builder::new().filter(|_| true);
+        let running = RunningSettings::of(&builder());
+        assert_eq!(
+            running.changed(&RunningSettings::of(&builder())),
+            ["custom_filter"]
+        );
+    }
+}
@@ -0,0 +1,337 @@
+//! Periodic sampling of process resource usage.
+//!
+//! Enable with [`Builder::with_resource_monitor()`](crate::Builder::with_resource_monitor).
+//! Each sample is logged as a structured `INFO` event under the
+//! [`RESOURCES_TARGET`] target, and the most recent [`ResourceSnapshot`] is kept
+//! in the [`ResourceMonitor`] state so it can be attached to profiling reports
+//! or user-submitted diagnostics.
+//!
+//! Memory, CPU, and thread counts are read on Linux, macOS, and Windows, and
+//! CPU time on other Unix platforms. Open file descriptors are only counted on
+//! Unix. Unavailable metrics are reported as `None` and omitted from the
+//! logged event.
+
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// The target used for resource snapshot events.
+pub const RESOURCES_TARGET: &str = "sys::resources";
+
+/// A point-in-time snapshot of process resource usage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ResourceSnapshot {
+    /// Resident set size in bytes.
+    pub rss_bytes: Option<u64>,
+    /// CPU usage since the previous sample, as a percentage of one core.
+    ///
+    /// May exceed 100% when the process uses several cores. Always `None`
+    /// for a standalone [`capture()`](Self::capture).
+    pub cpu_percent: Option<f64>,
+    /// Number of open file descriptors.
+    pub open_fds: Option<u64>,
+    /// Number of threads in the process.
+    pub thread_count: Option<u64>,
+}
+
+impl ResourceSnapshot {
+    /// Captures the current resource usage of this process.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tauri_plugin_tracing::ResourceSnapshot;
+    ///
+    /// let snapshot = ResourceSnapshot::capture();
+    /// assert!(snapshot.cpu_percent.is_none());
+    /// ```
+    pub fn capture() -> Self {
+        Self {
+            rss_bytes: rss_bytes(),
+            cpu_percent: None,
+            open_fds: open_fds(),
+            thread_count: thread_count(),
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let na = || "n/a".to_string();
+        write!(
+            f,
+            "rss={} cpu={} fds={} threads={}",
+            self.rss_bytes
+                .map_or_else(na, |b| format!("{:.1}MB", b as f64 / 1e6)),
+            self.cpu_percent.map_or_else(na, |p| format!("{p:.1}%")),
+            self.open_fds.map_or_else(na, |n| n.to_string()),
+            self.thread_count.map_or_else(na, |n| n.to_string()),
+        )
+    }
+}
+
+/// Handle to the periodic resource sampler.
+///
+/// The plugin manages an instance of this type in Tauri state when
+/// [`Builder::with_resource_monitor()`](crate::Builder::with_resource_monitor)
+/// is used. Sampling stops when the monitor is dropped.
+pub struct ResourceMonitor {
+    latest: Arc<Mutex<Option<ResourceSnapshot>>>,
+    interval: Duration,
+}
+
+impl ResourceMonitor {
+    /// Starts sampling every `interval` on a background thread.
+    pub fn start(interval: Duration) -> crate::Result<Self> {
+        let latest = Arc::new(Mutex::new(None));
+        let weak = Arc::downgrade(&latest);
+        std::thread::Builder::new()
+            .name("tracing-resource-monitor".to_string())
+            .spawn(move || run_sampler(weak, interval))?;
+        Ok(Self { latest, interval })
+    }
+
+    /// Returns the most recent snapshot, if one has been taken.
+    pub fn latest(&self) -> Option<ResourceSnapshot> {
+        self.latest
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Returns the sampling interval.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+fn run_sampler(latest: Weak<Mutex<Option<ResourceSnapshot>>>, interval: Duration) {
+    let mut previous: Option<(Instant, f64)> = None;
+
+    loop {
+        std::thread::sleep(interval);
+        let Some(latest) = latest.upgrade() else {
+            return;
+        };
+
+        let mut snapshot = ResourceSnapshot::capture();
+        let now = Instant::now();
+        let cpu_seconds = cpu_seconds();
+        if let (Some((then, prev_cpu)), Some(cpu)) = (previous, cpu_seconds) {
+            let wall = now.duration_since(then).as_secs_f64();
+            if wall > 0.0 {
+                snapshot.cpu_percent = Some((cpu - prev_cpu).max(0.0) / wall * 100.0);
+            }
+        }
+        previous = cpu_seconds.map(|cpu| (now, cpu));
+
+        tracing::info!(
+            target: RESOURCES_TARGET,
+            rss_bytes = snapshot.rss_bytes,
+            cpu_percent = snapshot.cpu_percent,
+            open_fds = snapshot.open_fds,
+            thread_count = snapshot.thread_count,
+            "resource snapshot"
+        );
+
+        *latest.lock().unwrap_or_else(|e| e.into_inner()) = Some(snapshot);
+    }
+}
+
+/// Reads a `Key: value` line from `/proc/self/status`.
+#[cfg(target_os = "linux")]
+fn proc_status_value(key: &str) -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(target_os = "linux")]
+fn rss_bytes() -> Option<u64> {
+    proc_status_value("VmRSS").map(|kb| kb * 1024)
+}
+
+#[cfg(target_os = "macos")]
+fn rss_bytes() -> Option<u64> {
+    proc_task_info().map(|info| info.pti_resident_size)
+}
+
+#[cfg(windows)]
+fn rss_bytes() -> Option<u64> {
+    use windows_sys::Win32::System::ProcessStatus::{
+        GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS,
+    };
+    use windows_sys::Win32::System::Threading::GetCurrentProcess;
+
+    // SAFETY: the counters are plain data that the call fills in, and the
+    // pseudo handle of the current process needs no closing
+    unsafe {
+        let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+        let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        if GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size) == 0 {
+            return None;
+        }
+        Some(counters.WorkingSetSize as u64)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn rss_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn thread_count() -> Option<u64> {
+    proc_status_value("Threads")
+}
+
+#[cfg(target_os = "macos")]
+fn thread_count() -> Option<u64> {
+    proc_task_info().and_then(|info| u64::try_from(info.pti_threadnum).ok())
+}
+
+#[cfg(windows)]
+fn thread_count() -> Option<u64> {
+    use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+    use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let pid = std::process::id();
+    // SAFETY: the snapshot handle is closed before returning, and each entry
+    // is plain data with its size set as the API requires
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return None;
+        }
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+        let mut count = None;
+        let mut found = Process32FirstW(snapshot, &mut entry) != 0;
+        while found {
+            if entry.th32ProcessID == pid {
+                count = Some(u64::from(entry.cntThreads));
+                break;
+            }
+            found = Process32NextW(snapshot, &mut entry) != 0;
+        }
+        CloseHandle(snapshot);
+        count
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn thread_count() -> Option<u64> {
+    None
+}
+
+/// Reads the resident size and thread count of this process.
+#[cfg(target_os = "macos")]
+fn proc_task_info() -> Option<libc::proc_taskinfo> {
+    // SAFETY: the info is plain data that the call fills in, and its size is
+    // passed along with it
+    unsafe {
+        let mut info: libc::proc_taskinfo = std::mem::zeroed();
+        let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+        let written = libc::proc_pidinfo(
+            libc::getpid(),
+            libc::PROC_PIDTASKINFO,
+            0,
+            (&raw mut info).cast(),
+            size,
+        );
+        (written == size).then_some(info)
+    }
+}
+
+/// Total user and system CPU time consumed by the process, in seconds.
+#[cfg(target_os = "linux")]
+fn cpu_seconds() -> Option<f64> {
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    // The command name may contain spaces, so skip past its closing paren
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    // The times are in clock ticks (`USER_HZ`)
+    // SAFETY: sysconf(3) has no memory safety requirements
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks_per_sec <= 0 {
+        return None;
+    }
+    Some((utime + stime) as f64 / ticks_per_sec as f64)
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn cpu_seconds() -> Option<f64> {
+    // SAFETY: the usage is plain data that the call fills in
+    let usage = unsafe {
+        let mut usage: libc::rusage = std::mem::zeroed();
+        if libc::getrusage(libc::RUSAGE_SELF, &mut usage) != 0 {
+            return None;
+        }
+        usage
+    };
+    let seconds = |time: libc::timeval| time.tv_sec as f64 + time.tv_usec as f64 / 1e6;
+    Some(seconds(usage.ru_utime) + seconds(usage.ru_stime))
+}
+
+#[cfg(windows)]
+fn cpu_seconds() -> Option<f64> {
+    use windows_sys::Win32::Foundation::FILETIME;
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, GetProcessTimes};
+
+    // SAFETY: the times are plain data that the call fills in, and the pseudo
+    // handle of the current process needs no closing
+    let (kernel, user) = unsafe {
+        let mut creation: FILETIME = std::mem::zeroed();
+        let mut exit: FILETIME = std::mem::zeroed();
+        let mut kernel: FILETIME = std::mem::zeroed();
+        let mut user: FILETIME = std::mem::zeroed();
+        if GetProcessTimes(
+            GetCurrentProcess(),
+            &mut creation,
+            &mut exit,
+            &mut kernel,
+            &mut user,
+        ) == 0
+        {
+            return None;
+        }
+        (kernel, user)
+    };
+    // FILETIME counts 100-nanosecond intervals
+    let seconds = |time: FILETIME| {
+        ((u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime)) as f64 / 1e7
+    };
+    Some(seconds(kernel) + seconds(user))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn cpu_seconds() -> Option<f64> {
+    None
+}
+
+#[cfg(unix)]
+fn open_fds() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    let dir = "/proc/self/fd";
+    #[cfg(not(target_os = "linux"))]
+    let dir = "/dev/fd";
+
+    // Reading the directory opens one descriptor of its own
+    let count = std::fs::read_dir(dir).ok()?.count() as u64;
+    Some(count.saturating_sub(1))
+}
+
+#[cfg(not(unix))]
+fn open_fds() -> Option<u64> {
+    None
+}
@@ -0,0 +1,390 @@
+//! RFC 5424 (syslog) line format.
+//!
+//! [`Rfc5424Format`] formats each event as a single syslog line:
+//!
+//! ```text
+//! <PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID [STRUCTURED-DATA] MSG
+//! <14>1 2024-01-15T10:30:00.123456Z my-host my-app 4242 my_app::db [fields@32473 rows="3"] query done
+//! ```
+//!
+//! The tracing level becomes the syslog severity (see [`syslog_severity()`]),
+//! the event target becomes the MSGID, and the event's fields are written as
+//! structured data, so logs can be shipped to syslog pipelines without
+//! transformation. Attributes set with [`Rfc5424Format::with_attributes()`]
+//! are written as a second structured data element on every line.
+//!
+//! Select it with [`LogFormat::Rfc5424`](crate::LogFormat::Rfc5424), or pass it
+//! to `fmt::layer().event_format()` when composing your own subscriber.
+
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::TimezoneStrategy;
+
+/// The SD-ID used for event fields.
+///
+/// 32473 is the private enterprise number reserved for documentation by
+/// RFC 5612; collectors treat it as an opaque identifier.
+pub const RFC5424_SD_ID: &str = "fields@32473";
+
+/// The SD-ID used for attributes set with [`Rfc5424Format::with_attributes()`].
+pub const RFC5424_RESOURCE_SD_ID: &str = "resource@32473";
+
+/// The value written for a header field that is unknown.
+const NILVALUE: &str = "-";
+
+/// Syslog facility codes, as defined by RFC 5424 section 6.2.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyslogFacility {
+    /// User-level messages (1). The default.
+    #[default]
+    User,
+    /// System daemons (3).
+    Daemon,
+    /// Locally used facility 0 (16).
+    Local0,
+    /// Locally used facility 1 (17).
+    Local1,
+    /// Locally used facility 2 (18).
+    Local2,
+    /// Locally used facility 3 (19).
+    Local3,
+    /// Locally used facility 4 (20).
+    Local4,
+    /// Locally used facility 5 (21).
+    Local5,
+    /// Locally used facility 6 (22).
+    Local6,
+    /// Locally used facility 7 (23).
+    Local7,
+}
+
+impl SyslogFacility {
+    /// Returns the numeric facility code.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::User => 1,
+            Self::Daemon => 3,
+            Self::Local0 => 16,
+            Self::Local1 => 17,
+            Self::Local2 => 18,
+            Self::Local3 => 19,
+            Self::Local4 => 20,
+            Self::Local5 => 21,
+            Self::Local6 => 22,
+            Self::Local7 => 23,
+        }
+    }
+}
+
+/// Maps a tracing level to a syslog severity code.
+///
+/// | Level   | Severity        |
+/// |---------|-----------------|
+/// | `ERROR` | 3 (Error)       |
+/// | `WARN`  | 4 (Warning)     |
+/// | `INFO`  | 6 (Informational) |
+/// | `DEBUG` | 7 (Debug)       |
+/// | `TRACE` | 7 (Debug)       |
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing::syslog_severity;
+/// use tracing::Level;
+///
+/// assert_eq!(syslog_severity(&Level::WARN), 4);
+/// assert_eq!(syslog_severity(&Level::TRACE), 7);
+/// ```
+pub fn syslog_severity(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    }
+}
+
+/// Formats events as RFC 5424 syslog lines.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{Rfc5424Format, SyslogFacility};
+/// # use tracing_subscriber::{Registry, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+/// Registry::default()
+///     .with(fmt::layer().event_format(
+///         Rfc5424Format::new("my-app").with_facility(SyslogFacility::Local0),
+///     ))
+///     .init();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rfc5424Format {
+    app_name: String,
+    hostname: String,
+    procid: u32,
+    facility: SyslogFacility,
+    offset: time::UtcOffset,
+    attributes: Vec<(String, String)>,
+}
+
+impl Rfc5424Format {
+    /// Creates a formatter that reports `app_name` as the APP-NAME.
+    ///
+    /// The hostname is detected from the system, and timestamps are in UTC.
+    pub fn new(app_name: impl AsRef<str>) -> Self {
+        Self {
+            app_name: header_field(app_name.as_ref(), 48),
+            hostname: header_field(&system_hostname().unwrap_or_default(), 255),
+            procid: std::process::id(),
+            facility: SyslogFacility::default(),
+            offset: time::UtcOffset::UTC,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Overrides the detected HOSTNAME.
+    pub fn with_hostname(mut self, hostname: impl AsRef<str>) -> Self {
+        self.hostname = header_field(hostname.as_ref(), 255);
+        self
+    }
+
+    /// Sets the facility used to compute each line's priority.
+    pub fn with_facility(mut self, facility: SyslogFacility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    /// Writes `attributes` on every line, in a structured data element with
+    /// the [`RFC5424_RESOURCE_SD_ID`].
+    pub fn with_attributes<K, V>(mut self, attributes: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (key, value) in attributes {
+            let name = param_name(key.as_ref());
+            if !name.is_empty() {
+                self.attributes
+                    .push((name, escape_param_value(value.as_ref())));
+            }
+        }
+        self
+    }
+
+    /// Writes timestamps in UTC or with the local offset.
+    ///
+    /// As with the other formats, the local offset is captured once and falls
+    /// back to UTC if it cannot be determined.
+    pub fn with_timezone_strategy(mut self, strategy: TimezoneStrategy) -> Self {
+        self.offset = match strategy {
+            TimezoneStrategy::Utc => time::UtcOffset::UTC,
+            TimezoneStrategy::Local => {
+                time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC)
+            }
+        };
+        self
+    }
+
+    fn write_timestamp(&self, writer: &mut Writer<'_>) -> std::fmt::Result {
+        let now = time::OffsetDateTime::now_utc().to_offset(self.offset);
+        write!(
+            writer,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}",
+            now.year(),
+            u8::from(now.month()),
+            now.day(),
+            now.hour(),
+            now.minute(),
+            now.second(),
+            now.microsecond()
+        )?;
+        if self.offset.is_utc() {
+            writer.write_char('Z')
+        } else {
+            let (hours, minutes, _) = self.offset.as_hms();
+            let sign = if self.offset.is_negative() { '-' } else { '+' };
+            write!(writer, "{sign}{:02}:{:02}", hours.abs(), minutes.abs())
+        }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for Rfc5424Format
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let metadata = event.metadata();
+        let priority = self.facility.code() * 8 + syslog_severity(metadata.level());
+
+        write!(writer, "<{priority}>1 ")?;
+        self.write_timestamp(&mut writer)?;
+        let msgid = header_field(metadata.target(), 32);
+        write!(
+            writer,
+            " {} {} {} {} ",
+            self.hostname, self.app_name, self.procid, msgid
+        )?;
+
+        let mut visitor = SdVisitor::default();
+        event.record(&mut visitor);
+
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<&str> = scope.from_root().map(|span| span.name()).collect();
+            visitor.params.push(("spans".to_string(), spans.join(":")));
+        }
+
+        if let Some(rate) = crate::current_sample_rate() {
+            visitor
+                .params
+                .push(("sampled".to_string(), rate.to_string()));
+        }
+        if let Some(seq) = crate::current_sequence() {
+            visitor.params.push(("seq".to_string(), seq.to_string()));
+        }
+
+        // Event fields take precedence over the log context
+        crate::context::with_current_context(|entries| {
+            for (key, value) in entries {
+                let name = param_name(key);
+                if !name.is_empty() && !visitor.params.iter().any(|(n, _)| *n == name) {
+                    visitor.params.push((name, value.clone()));
+                }
+            }
+        });
+
+        if visitor.params.is_empty() && self.attributes.is_empty() {
+            writer.write_str(NILVALUE)?;
+        }
+        if !visitor.params.is_empty() {
+            write!(writer, "[{RFC5424_SD_ID}")?;
+            for (name, value) in &visitor.params {
+                write!(writer, " {name}=\"{}\"", escape_param_value(value))?;
+            }
+            writer.write_char(']')?;
+        }
+        if !self.attributes.is_empty() {
+            write!(writer, "[{RFC5424_RESOURCE_SD_ID}")?;
+            for (name, value) in &self.attributes {
+                write!(writer, " {name}=\"{value}\"")?;
+            }
+            writer.write_char(']')?;
+        }
+
+        if !visitor.message.is_empty() {
+            write!(writer, " {}", visitor.message)?;
+        }
+        writeln!(writer)
+    }
+}
+
+/// Collects the message and structured-data parameters of an event.
+#[derive(Default)]
+struct SdVisitor {
+    message: String,
+    params: Vec<(String, String)>,
+}
+
+impl SdVisitor {
+    fn push(&mut self, field: &tracing::field::Field, value: String) {
+        self.push_named(field.name(), value);
+    }
+
+    fn push_named(&mut self, name: &str, value: String) {
+        if name == "message" {
+            // Keep each event on a single line
+            self.message = value.replace(['\n', '\r'], " ");
+        } else {
+            let name = param_name(name);
+            if !name.is_empty() {
+                self.params.push((name, value));
+            }
+        }
+    }
+}
+
+impl tracing::field::Visit for SdVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.push(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.push(field, format!("{value:?}"));
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.push(field, value.to_string());
+        for (n, source) in crate::error_chain::sources(value).enumerate() {
+            let name = crate::error_chain::source_field(field.name(), n + 1);
+            self.push_named(&name, source);
+        }
+    }
+}
+
+/// Restricts a header field to printable ASCII without spaces.
+///
+/// Returns the NILVALUE for empty fields.
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    if field.is_empty() {
+        NILVALUE.to_string()
+    } else {
+        field
+    }
+}
+
+/// Restricts an SD-PARAM name to the characters RFC 5424 allows.
+fn param_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(32)
+        .collect()
+}
+
+/// Escapes `"`, `\` and `]` in an SD-PARAM value.
+fn escape_param_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' | '\\' | ']' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(unix)]
+pub(crate) fn system_hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    // SAFETY: `buf` is a valid buffer of the given length
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|b| *b == 0).unwrap_or(buf.len());
+    Some(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn system_hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
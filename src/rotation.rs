@@ -0,0 +1,229 @@
+//! Notifications for log file rotation.
+//!
+//! Neither file appender reports when it rotates, so the file target's writer
+//! is wrapped to detect it:
+//!
+//! - Time-based rotation with `tracing-appender` starts a new file when the
+//!   current UTC period ends. [`PeriodWatcher`] tracks the period of the last
+//!   write and derives both file names the same way the appender does.
+//! - Size-based rotation with `rolling-file` renames `app.log` to `app.log.1`
+//!   and reopens `app.log`. [`RolloverCondition`] wraps the appender's
+//!   rolling condition and flags each rollover for [`RolloverWatcher`].
+//!
+//! Detection runs on the non-blocking writer's worker thread, and the hook
+//! itself runs on a blocking task, so slow hooks delay neither the code that
+//! logs nor the writes that follow.
+
+#[cfg(feature = "file")]
+use std::io::{self, Write};
+use std::path::Path;
+#[cfg(feature = "file")]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[cfg(feature = "rotation")]
+use rolling_file::{RollingCondition, RollingConditionBasic, RollingFileAppender};
+
+#[cfg(feature = "file")]
+use crate::Rotation;
+
+/// A callback run with the finished file's path and the new file's path.
+pub(crate) type RotationHook = Arc<dyn Fn(&Path, &Path) + Send + Sync>;
+
+/// Runs `hook` on a blocking task.
+#[cfg(feature = "file")]
+fn notify(hook: &RotationHook, old_path: PathBuf, new_path: PathBuf) {
+    let hook = hook.clone();
+    tauri::async_runtime::spawn_blocking(move || hook(&old_path, &new_path));
+}
+
+/// Detects the period changes of a time-rotated `tracing-appender` file.
+#[cfg(feature = "file")]
+pub(crate) struct PeriodWatcher<W> {
+    inner: W,
+    log_dir: PathBuf,
+    file_name: String,
+    suffix: String,
+    rotation: Rotation,
+    period: Option<i64>,
+    hook: RotationHook,
+}
+
+#[cfg(feature = "file")]
+impl<W> PeriodWatcher<W> {
+    pub(crate) fn new(
+        inner: W,
+        log_dir: PathBuf,
+        file_name: String,
+        suffix: String,
+        rotation: Rotation,
+        hook: RotationHook,
+    ) -> Self {
+        let period = current_period(rotation);
+        Self {
+            inner,
+            log_dir,
+            file_name,
+            suffix,
+            rotation,
+            period,
+            hook,
+        }
+    }
+
+    fn path_for(&self, period: i64) -> Option<PathBuf> {
+        period_path(
+            &self.log_dir,
+            &self.file_name,
+            &self.suffix,
+            self.rotation,
+            period,
+        )
+    }
+}
+
+/// Returns the path `tracing-appender` uses for a period.
+#[cfg(feature = "file")]
+fn period_path(
+    log_dir: &Path,
+    file_name: &str,
+    suffix: &str,
+    rotation: Rotation,
+    period: i64,
+) -> Option<PathBuf> {
+    let start = time::OffsetDateTime::from_unix_timestamp(period * period_secs(rotation)?).ok()?;
+    let day = format!(
+        "{:04}-{:02}-{:02}",
+        start.year(),
+        u8::from(start.month()),
+        start.day()
+    );
+    let date = match rotation {
+        Rotation::Daily => day,
+        Rotation::Hourly => format!("{day}-{:02}", start.hour()),
+        Rotation::Minutely => format!("{day}-{:02}-{:02}", start.hour(), start.minute()),
+        Rotation::Never => return None,
+    };
+    Some(log_dir.join(format!("{file_name}.{date}.{suffix}")))
+}
+
+/// Returns the path a time-rotated `tracing-appender` file currently writes
+/// to.
+#[cfg(feature = "file")]
+pub(crate) fn current_path(
+    log_dir: &Path,
+    file_name: &str,
+    suffix: &str,
+    rotation: Rotation,
+) -> PathBuf {
+    current_period(rotation)
+        .and_then(|period| period_path(log_dir, file_name, suffix, rotation, period))
+        .unwrap_or_else(|| log_dir.join(format!("{file_name}.{suffix}")))
+}
+
+#[cfg(feature = "file")]
+impl<W: Write> Write for PeriodWatcher<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        let period = current_period(self.rotation);
+        if period != self.period
+            && let (Some(old), Some(new)) = (self.period, period)
+            && let (Some(old_path), Some(new_path)) = (self.path_for(old), self.path_for(new))
+        {
+            notify(&self.hook, old_path, new_path);
+        }
+        self.period = period;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Returns the length of a rotation period in seconds.
+#[cfg(feature = "file")]
+fn period_secs(rotation: Rotation) -> Option<i64> {
+    match rotation {
+        Rotation::Daily => Some(86_400),
+        Rotation::Hourly => Some(3_600),
+        Rotation::Minutely => Some(60),
+        Rotation::Never => None,
+    }
+}
+
+/// Returns the index of the current UTC rotation period.
+///
+/// `tracing-appender` rounds dates in UTC, so periods line up with the epoch.
+#[cfg(feature = "file")]
+fn current_period(rotation: Rotation) -> Option<i64> {
+    Some(time::OffsetDateTime::now_utc().unix_timestamp() / period_secs(rotation)?)
+}
+
+/// A `rolling-file` condition that remembers when it triggered a rollover.
+#[cfg(feature = "rotation")]
+#[derive(Debug)]
+pub(crate) struct RolloverCondition {
+    inner: RollingConditionBasic,
+    rolled_over: bool,
+}
+
+#[cfg(feature = "rotation")]
+impl RolloverCondition {
+    pub(crate) fn new(inner: RollingConditionBasic) -> Self {
+        Self {
+            inner,
+            rolled_over: false,
+        }
+    }
+}
+
+#[cfg(feature = "rotation")]
+impl RollingCondition for RolloverCondition {
+    fn should_rollover(
+        &mut self,
+        now: &chrono::DateTime<chrono::Local>,
+        current_filesize: u64,
+    ) -> bool {
+        let rollover = self.inner.should_rollover(now, current_filesize);
+        self.rolled_over |= rollover;
+        rollover
+    }
+}
+
+/// Reports the rollovers of a size-rotated `rolling-file` appender.
+#[cfg(feature = "rotation")]
+pub(crate) struct RolloverWatcher {
+    inner: RollingFileAppender<RolloverCondition>,
+    path: PathBuf,
+    hook: RotationHook,
+}
+
+#[cfg(feature = "rotation")]
+impl RolloverWatcher {
+    pub(crate) fn new(
+        inner: RollingFileAppender<RolloverCondition>,
+        path: PathBuf,
+        hook: RotationHook,
+    ) -> Self {
+        Self { inner, path, hook }
+    }
+}
+
+#[cfg(feature = "rotation")]
+impl Write for RolloverWatcher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        let condition = self.inner.condition_mut();
+        if std::mem::take(&mut condition.rolled_over) {
+            let mut old_path = self.path.clone().into_os_string();
+            old_path.push(".1");
+            notify(&self.hook, old_path.into(), self.path.clone());
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,111 @@
+//! Client-side routes of each window.
+//!
+//! Single-page apps change screens without loading a page, so the backend
+//! only learns the route when the frontend tells it. The frontend's router
+//! calls `setCurrentRoute()` after each navigation, and the plugin remembers
+//! the route of each window in [`CurrentRoutes`]. It logs an `INFO` event
+//! under the [`ROUTE_TARGET`] target for each change, and records the route
+//! in the `route` field of the `log` span of the window's subsequent records,
+//! unless a record brings its own.
+//!
+//! With [`Builder::with_route_spans()`](crate::Builder::with_route_spans),
+//! each route also opens a long-lived `route` span that the window's records
+//! are logged in, until the next route or page load. The span's duration is
+//! the time spent on the screen.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tracing::Span;
+
+/// The target used for route changes and route spans.
+pub const ROUTE_TARGET: &str = "webview::route";
+
+/// The current client-side route of each window.
+///
+/// The plugin manages an instance of this type in Tauri state, which the
+/// `set_current_route` command updates. Routes are forgotten when a page
+/// starts loading or the window is closed.
+#[derive(Debug, Default)]
+pub struct CurrentRoutes {
+    routes: Mutex<HashMap<String, PageRoute>>,
+    spans: bool,
+}
+
+#[derive(Debug)]
+struct PageRoute {
+    route: String,
+    span: Option<Span>,
+}
+
+impl CurrentRoutes {
+    /// Creates an empty set of routes that opens a `route` span for each
+    /// route if `spans` is set.
+    pub fn new(spans: bool) -> Self {
+        Self {
+            routes: Mutex::default(),
+            spans,
+        }
+    }
+
+    /// Sets the route of the window with the given label, and logs the
+    /// change.
+    ///
+    /// Setting the same route again does nothing.
+    pub fn set(&self, window: &str, route: &str) {
+        let mut routes = self.routes.lock().unwrap_or_else(|e| e.into_inner());
+        if routes.get(window).is_some_and(|page| page.route == route) {
+            return;
+        }
+        // The previous span closes before the next one opens
+        let from = routes.remove(window).map(|previous| previous.route);
+        match &from {
+            Some(from) => tracing::info!(
+                target: ROUTE_TARGET,
+                w = window,
+                route,
+                from,
+                "route changed to {route}"
+            ),
+            None => tracing::info!(target: ROUTE_TARGET, w = window, route, "route set to {route}"),
+        }
+        let span = self.spans.then(
+            || tracing::info_span!(target: ROUTE_TARGET, parent: None, "route", w = window, route),
+        );
+        routes.insert(
+            window.to_string(),
+            PageRoute {
+                route: route.to_string(),
+                span,
+            },
+        );
+    }
+
+    /// Returns the route of the window with the given label, if it was set.
+    pub fn route(&self, window: &str) -> Option<String> {
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(window)
+            .map(|page| page.route.clone())
+    }
+
+    /// Returns the `route` span of the window with the given label, if route
+    /// spans are enabled and its route was set.
+    pub fn span(&self, window: &str) -> Option<Span> {
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(window)
+            .and_then(|page| page.span.clone())
+    }
+
+    /// Forgets the route of the window with the given label, closing its
+    /// span.
+    pub fn forget(&self, window: &str) {
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(window);
+    }
+}
@@ -0,0 +1,195 @@
+//! Deterministic sampling of high-volume events.
+//!
+//! Render loops and I/O polling can emit thousands of events per second.
+//! [`SamplingLayer`] keeps one in every N events for configured targets and
+//! drops the rest before they reach any output, so those code paths can stay
+//! instrumented in production. Sampling is counted per callsite, so each
+//! event macro keeps the first of every N of its own events.
+//!
+//! Kept events are annotated with a `sampled=1/N` field by the plugin's
+//! formatters. Custom layers can read the rate of the event being dispatched
+//! with [`current_sample_rate()`].
+//!
+//! `WARN` and `ERROR` events are never sampled.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tracing::callsite::Identifier;
+use tracing::{Level, Metadata, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::layer::Context;
+
+thread_local! {
+    static CURRENT_RATE: Cell<Option<SampleRate>> = const { Cell::new(None) };
+}
+
+/// How many events of a sampled target are kept.
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing::SampleRate;
+///
+/// assert_eq!(SampleRate::OneIn(100).to_string(), "1/100");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleRate {
+    /// Keep the first of every N events. `OneIn(0)` and `OneIn(1)` keep all events.
+    OneIn(u32),
+}
+
+impl SampleRate {
+    fn keeps(self, count: u64) -> bool {
+        match self {
+            Self::OneIn(n) => n <= 1 || count.is_multiple_of(u64::from(n)),
+        }
+    }
+}
+
+impl std::fmt::Display for SampleRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OneIn(n) => write!(f, "1/{n}"),
+        }
+    }
+}
+
+/// Returns the sample rate of the event currently being dispatched on this
+/// thread, if it was kept by a [`SamplingLayer`].
+///
+/// Only meaningful while an event is being recorded by layers that sit
+/// inside the sampling layer.
+pub fn current_sample_rate() -> Option<SampleRate> {
+    CURRENT_RATE.with(Cell::get)
+}
+
+/// A tracing layer that keeps one in every N events for configured targets.
+///
+/// Targets match by module path prefix, like
+/// [`Builder::with_target()`](crate::Builder::with_target), and the most
+/// specific rule wins.
+///
+/// Add the layer after the output layers, so it filters events before they
+/// are written and the outputs can see [`current_sample_rate()`].
+///
+/// # Example
+///
+/// Use [`Builder::with_sampling()`](crate::Builder::with_sampling) with the
+/// default subscriber, or add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{SampleRate, SamplingLayer};
+/// # use tracing_subscriber::{Registry, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+/// Registry::default()
+///     .with(fmt::layer())
+///     .with(SamplingLayer::new().with_rule("my_app::render", SampleRate::OneIn(100)))
+///     .init();
+/// ```
+#[derive(Debug, Default)]
+pub struct SamplingLayer {
+    rules: Vec<(String, SampleRate)>,
+    counters: Mutex<HashMap<Identifier, u64>>,
+}
+
+impl SamplingLayer {
+    /// Creates a layer with no sampling rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Samples events whose target starts with `target` at `rate`.
+    pub fn with_rule(mut self, target: impl Into<String>, rate: SampleRate) -> Self {
+        self.rules.push((target.into(), rate));
+        self
+    }
+
+    /// Returns the configured rules.
+    pub fn rules(&self) -> &[(String, SampleRate)] {
+        &self.rules
+    }
+
+    /// Returns the sample rate that applies to an event, if any.
+    fn rate_for(&self, metadata: &Metadata<'_>) -> Option<SampleRate> {
+        if *metadata.level() <= Level::WARN {
+            return None;
+        }
+        let target = metadata.target();
+        self.rules
+            .iter()
+            .filter(|(prefix, _)| {
+                target
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, rate)| *rate)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SamplingLayer {
+    fn event_enabled(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) -> bool {
+        // A layer inside this one may reject the previous event, so
+        // `on_event` never cleared its rate
+        CURRENT_RATE.with(|r| r.set(None));
+        let metadata = event.metadata();
+        let Some(rate) = self.rate_for(metadata) else {
+            return true;
+        };
+
+        let count = {
+            let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+            let counter = counters.entry(metadata.callsite()).or_default();
+            let count = *counter;
+            *counter += 1;
+            count
+        };
+        let keep = rate.keeps(count);
+        if keep {
+            CURRENT_RATE.with(|r| r.set(Some(rate)));
+        }
+        keep
+    }
+
+    fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        // Inner layers have recorded the event by now
+        CURRENT_RATE.with(|r| r.set(None));
+    }
+}
+
+/// Wraps a field formatter to append `sampled=1/N` to sampled events.
+///
+/// The plugin's formatters use this automatically. When composing your own
+/// subscriber, pass it to `fmt::layer().fmt_fields()`.
+#[derive(Debug, Clone, Default)]
+pub struct SampledFields<N> {
+    inner: N,
+}
+
+impl<N> SampledFields<N> {
+    /// Wraps `inner`.
+    pub fn new(inner: N) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'writer, N> FormatFields<'writer> for SampledFields<N>
+where
+    N: for<'w> FormatFields<'w>,
+{
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> std::fmt::Result {
+        self.inner.format_fields(writer.by_ref(), fields)?;
+        if let Some(rate) = current_sample_rate() {
+            write!(writer, " sampled={rate}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! JSON Schema for the records and results exchanged with the frontend.
+//!
+//! Requires the `schema` feature. [`json_schema()`], or the `get_schema`
+//! command, returns a JSON Schema (draft 7) document whose `definitions`
+//! describe:
+//!
+//! - `RecordPayload`: a record emitted on the `tracing://log` event.
+//! - `LogRecord`: a record sent with the `log` command, and `LogRecordBatch`
+//!   the arguments of the `log_batch` command.
+//! - `MarkContext`, `LogStats`, and `EffectiveFilter`: the results of the
+//!   `get_logs_around_mark`, `get_log_stats`, and `get_effective_filter`
+//!   commands.
+//!
+//! Frontends without the TypeScript bindings, and test drivers in other
+//! languages, can validate what they send and receive against it.
+
+use schemars::r#gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::Schema;
+use serde_json::{Value, json};
+
+use crate::{EffectiveFilter, LogRecord, LogStats, MarkContext, RecordPayload};
+
+/// Returns the JSON Schema of the plugin's records and query results, see
+/// the [module documentation](self).
+///
+/// # Example
+///
+/// ```rust
+/// let schema = tauri_plugin_tracing::json_schema();
+/// assert!(schema["definitions"]["RecordPayload"].is_object());
+/// ```
+pub fn json_schema() -> Value {
+    let mut generator = SchemaGenerator::new(SchemaSettings::draft07());
+    generator.subschema_for::<RecordPayload>();
+    let batch = generator.subschema_for::<Vec<LogRecord>>();
+    generator.subschema_for::<MarkContext>();
+    generator.subschema_for::<LogStats>();
+    generator.subschema_for::<EffectiveFilter>();
+
+    let mut definitions = generator.take_definitions();
+    definitions.insert(
+        "LogRecordBatch".to_string(),
+        Schema::Object(
+            serde_json::from_value(json!({
+                "description": "The arguments of the `log_batch` command.",
+                "type": "object",
+                "required": ["records"],
+                "properties": { "records": batch },
+            }))
+            .unwrap_or_default(),
+        ),
+    );
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "tauri-plugin-tracing",
+        "definitions": definitions,
+    })
+}
@@ -0,0 +1,114 @@
+//! Sequence numbers shared by every output.
+//!
+//! Records travel to several outputs, such as a log file, the terminal, and
+//! the webview, and each can lose some: the webview misses records while no
+//! frontend is loaded, and a collector's queue drops records when it cannot
+//! keep up. [`SequenceLayer`] numbers every event that passes the filter, in
+//! the order they are dispatched, so a record can be matched across outputs
+//! and missing numbers show that records are missing.
+//!
+//! The plugin's formatters append the number as a `seq=N` field, the RFC 5424,
+//! Loki, GELF, and OTLP outputs add it like an event field, and webview
+//! payloads carry it as `seq`. Custom layers can read the number of the event
+//! being dispatched with [`current_sequence()`].
+//!
+//! Numbers start at 1 and are shared by every subscriber in the process. An
+//! output with its own level or targets skips the numbers of the events it
+//! does not record, so gaps in its numbers are only losses when it records
+//! everything the filter allows. Webview payloads report records dropped on
+//! the way to the frontend explicitly, as their `gap`; see
+//! [`WebviewForwarding`](crate::WebviewForwarding).
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::layer::Context;
+
+/// The number of the next event.
+static NEXT: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static CURRENT_SEQUENCE: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Returns the sequence number of the event currently being dispatched on
+/// this thread, if it was numbered by a [`SequenceLayer`].
+///
+/// Only meaningful while an event is being recorded by layers that sit
+/// inside the sequence layer.
+pub fn current_sequence() -> Option<u64> {
+    CURRENT_SEQUENCE.with(Cell::get)
+}
+
+/// A tracing layer that numbers every event.
+///
+/// Add the layer after the output layers and before the filter, so only
+/// events that reach an output are numbered and the outputs can see
+/// [`current_sequence()`].
+///
+/// # Example
+///
+/// Use [`Builder::with_sequence_numbers()`](crate::Builder::with_sequence_numbers)
+/// with the default subscriber, or add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{SequenceFields, SequenceLayer};
+/// # use tracing_subscriber::{Registry, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+/// Registry::default()
+///     .with(fmt::layer().fmt_fields(SequenceFields::new(fmt::format::DefaultFields::new())))
+///     .with(SequenceLayer)
+///     .init();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SequenceLayer;
+
+impl<S: Subscriber> Layer<S> for SequenceLayer {
+    fn event_enabled(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let seq = NEXT.fetch_add(1, Ordering::Relaxed);
+        CURRENT_SEQUENCE.with(|current| current.set(Some(seq)));
+        true
+    }
+
+    fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        // Inner layers have recorded the event by now
+        CURRENT_SEQUENCE.with(|current| current.set(None));
+    }
+}
+
+/// Wraps a field formatter to append `seq=N` to numbered events.
+///
+/// The plugin's formatters use this automatically. When composing your own
+/// subscriber, pass it to `fmt::layer().fmt_fields()`.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceFields<N> {
+    inner: N,
+}
+
+impl<N> SequenceFields<N> {
+    /// Wraps `inner`.
+    pub fn new(inner: N) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'writer, N> FormatFields<'writer> for SequenceFields<N>
+where
+    N: for<'w> FormatFields<'w>,
+{
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> std::fmt::Result {
+        self.inner.format_fields(writer.by_ref(), fields)?;
+        if let Some(seq) = current_sequence() {
+            write!(writer, " seq={seq}")?;
+        }
+        Ok(())
+    }
+}
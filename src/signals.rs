@@ -0,0 +1,78 @@
+//! Filter changes driven by Unix signals.
+//!
+//! Packaged apps, such as AppImages and Flatpaks, are rarely started from a
+//! terminal, so there is nowhere to pass a `RUST_LOG`-style setting. With
+//! [`Builder::with_signal_control()`](crate::Builder::with_signal_control),
+//! the running app's [`FilterHandle`] responds to:
+//!
+//! - `SIGUSR2`: switches targets without a more specific level to `DEBUG`,
+//!   or, if they are already at `DEBUG` or more verbose, restores the
+//!   configured level.
+//! - `SIGHUP`: re-reads the persisted filter file, see
+//!   [`FilterHandle::reload_stored()`].
+//!
+//! ```sh
+//! kill -USR2 $(pidof my-app)
+//! ```
+
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::signal::unix::{SignalKind, signal};
+use tracing_subscriber::filter::LevelFilter;
+
+use crate::FilterHandle;
+
+/// Starts handling `SIGUSR2` and `SIGHUP` in the background.
+pub(crate) fn listen<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let (mut usr2, mut hup) = match (
+            signal(SignalKind::user_defined2()),
+            signal(SignalKind::hangup()),
+        ) {
+            (Ok(usr2), Ok(hup)) => (usr2, hup),
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::warn!("Failed to install signal handlers: {e}");
+                return;
+            }
+        };
+        loop {
+            tokio::select! {
+                Some(()) = usr2.recv() => toggle_debug(&app),
+                Some(()) = hup.recv() => reload_stored(&app),
+                else => break,
+            }
+        }
+    });
+}
+
+fn toggle_debug<R: Runtime>(app: &AppHandle<R>) {
+    let Some(filter) = app.try_state::<FilterHandle>() else {
+        return;
+    };
+    let verbose = filter
+        .current()
+        .default_level()
+        .is_some_and(|level| level >= LevelFilter::DEBUG);
+    let result = if verbose {
+        tracing::info!("SIGUSR2 received, restoring the configured log level");
+        filter.clear_level()
+    } else {
+        let result = filter.set_level(LevelFilter::DEBUG);
+        tracing::info!("SIGUSR2 received, debug logging enabled");
+        result
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to change the log level: {e}");
+    }
+}
+
+fn reload_stored<R: Runtime>(app: &AppHandle<R>) {
+    let Some(filter) = app.try_state::<FilterHandle>() else {
+        return;
+    };
+    match filter.reload_stored() {
+        Ok(()) => {
+            tracing::info!(filter = %filter.current(), "SIGHUP received, log filter reloaded")
+        }
+        Err(e) => tracing::warn!("Failed to reload the log filter: {e}"),
+    }
+}
@@ -0,0 +1,134 @@
+//! Automatic detection of slow spans.
+//!
+//! [`SlowSpanLayer`] measures how long each span is busy and emits a `WARN`
+//! event when a span closes after exceeding a configured threshold. This
+//! catches UI-blocking backend work without running a full profile.
+
+use std::time::{Duration, Instant};
+
+use tracing::span;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// The target used for slow span warnings.
+pub const SLOW_SPAN_TARGET: &str = "tauri_plugin_tracing::slow_span";
+
+/// Busy time stored in each span's extensions.
+#[derive(Default)]
+struct BusyTime {
+    busy: Duration,
+    /// When the outermost current entry started
+    entered_at: Option<Instant>,
+    /// Entries that have not exited yet, on any thread
+    depth: usize,
+}
+
+/// A tracing layer that warns about spans exceeding a duration threshold.
+///
+/// The duration is the time the span was entered, like the `time.busy` field
+/// of `FmtSpan::CLOSE`, so long-lived spans that are mostly idle, such as a
+/// route span or an async task waiting on I/O, are not reported. Warnings are
+/// emitted under the [`SLOW_SPAN_TARGET`] target and include the span name,
+/// the busy duration in milliseconds, and the chain of parent spans.
+///
+/// # Example
+///
+/// Use [`Builder::with_slow_span_threshold()`](crate::Builder::with_slow_span_threshold)
+/// with the default subscriber, or add the layer when composing your own:
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use tauri_plugin_tracing::SlowSpanLayer;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt, fmt};
+/// Registry::default()
+///     .with(fmt::layer())
+///     .with(SlowSpanLayer::new(Duration::from_millis(100)))
+///     .init();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SlowSpanLayer {
+    threshold: Duration,
+}
+
+impl SlowSpanLayer {
+    /// Creates a layer that warns about spans busy for at least `threshold`.
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold }
+    }
+
+    /// Returns the configured threshold.
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+}
+
+impl<S> Layer<S> for SlowSpanLayer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(BusyTime::default());
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(time) = extensions.get_mut::<BusyTime>() {
+            if time.depth == 0 {
+                time.entered_at = Some(Instant::now());
+            }
+            time.depth += 1;
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(time) = extensions.get_mut::<BusyTime>() {
+            time.depth = time.depth.saturating_sub(1);
+            if time.depth == 0
+                && let Some(entered_at) = time.entered_at.take()
+            {
+                time.busy += entered_at.elapsed();
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(elapsed) = span.extensions().get::<BusyTime>().map(|time| {
+            // A span closed while still entered, e.g. by `mem::forget` of a
+            // guard, is busy until now
+            time.busy + time.entered_at.map_or(Duration::ZERO, |at| at.elapsed())
+        }) else {
+            return;
+        };
+        if elapsed < self.threshold {
+            return;
+        }
+
+        let mut parents: Vec<&str> = span.scope().skip(1).map(|s| s.name()).collect();
+        parents.reverse();
+        let parents = parents.join(" > ");
+        let name = span.name();
+        let duration_ms = elapsed.as_secs_f64() * 1000.0;
+
+        tracing::warn!(
+            target: SLOW_SPAN_TARGET,
+            span = name,
+            duration_ms,
+            parents = %parents,
+            "slow span `{name}` was busy for {elapsed:?} (threshold {:?})",
+            self.threshold
+        );
+    }
+}
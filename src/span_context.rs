@@ -0,0 +1,350 @@
+//! Span context propagation across the IPC boundary.
+//!
+//! Backend commands can hand a [`SpanContext`] to the frontend as part of their
+//! response. When the frontend passes that context back with a `log` call, the
+//! resulting event is recorded as a child of the original backend span, giving
+//! end-to-end traces that cross from Rust to JavaScript and back.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tauri_plugin_tracing::{SpanContext, SpanContextExt};
+//!
+//! #[derive(serde::Serialize)]
+//! struct LoadResponse {
+//!     items: Vec<String>,
+//!     span: Option<SpanContext>,
+//! }
+//!
+//! #[tauri::command]
+//! #[tracing::instrument(skip_all)]
+//! fn load_items(app: tauri::AppHandle) -> LoadResponse {
+//!     LoadResponse {
+//!         items: vec![],
+//!         span: app.current_span_context(),
+//!     }
+//! }
+//! ```
+//!
+//! Then, from JavaScript:
+//!
+//! ```javascript
+//! import { withSpanContext } from '@fltsci/tauri-plugin-tracing';
+//!
+//! const { items, span } = await invoke('load_items');
+//! withSpanContext(span).info('rendered', items.length, 'items');
+//! ```
+//!
+//! # W3C Trace Context
+//!
+//! A [`SpanContext`] converts to and from a W3C
+//! [`traceparent`](https://www.w3.org/TR/trace-context/#traceparent-header) header,
+//! so webview-initiated HTTP requests can carry the same trace id to servers
+//! that participate in distributed tracing:
+//!
+//! ```javascript
+//! import { tracedFetch } from '@fltsci/tauri-plugin-tracing';
+//!
+//! // Adds a `traceparent` header and logs the request with the same value
+//! const response = await tracedFetch('https://api.example.com/items', {}, { context: span });
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Runtime};
+use tracing::Span;
+use tracing_subscriber::Registry;
+use tracing_subscriber::registry::LookupSpan;
+
+/// The number of propagated spans kept alive before the oldest are released.
+const DEFAULT_CAPACITY: usize = 256;
+
+/// Identifies a backend span that the frontend can attach log records to.
+///
+/// Both ids are lowercase hex strings so they survive the trip through
+/// JavaScript without losing precision. The `trace_id` is shared by every
+/// propagated span that descends from the same propagated root.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpanContext {
+    /// 128-bit trace identifier, as 32 hex characters.
+    pub trace_id: String,
+    /// 64-bit span identifier, as 16 hex characters.
+    pub span_id: String,
+}
+
+impl SpanContext {
+    /// Formats this context as a W3C `traceparent` header value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tauri_plugin_tracing::SpanContext;
+    ///
+    /// let context = SpanContext {
+    ///     trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".to_string(),
+    ///     span_id: "00f067aa0ba902b7".to_string(),
+    /// };
+    /// assert_eq!(
+    ///     context.to_traceparent(),
+    ///     "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+    /// );
+    /// ```
+    pub fn to_traceparent(&self) -> String {
+        format!("00-{}-{}-01", self.trace_id, self.span_id)
+    }
+
+    /// Parses a W3C `traceparent` header value.
+    ///
+    /// Returns `None` if the value is malformed, uses the reserved `ff` version,
+    /// or contains an all-zero trace or parent id. Fields appended by future
+    /// versions of the specification are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tauri_plugin_tracing::SpanContext;
+    ///
+    /// let context = SpanContext::from_traceparent(
+    ///     "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+    /// ).unwrap();
+    /// assert_eq!(context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+    /// assert_eq!(context.span_id, "00f067aa0ba902b7");
+    ///
+    /// // All-zero ids are invalid
+    /// assert!(SpanContext::from_traceparent(
+    ///     "00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+    /// ).is_none());
+    /// ```
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        if !is_hex(version, 2) || version == "ff" || !is_hex(flags, 2) {
+            return None;
+        }
+        // Version 00 has exactly four fields
+        if version == "00" && parts.next().is_some() {
+            return None;
+        }
+        if !is_hex(trace_id, 32) || !is_hex(span_id, 16) {
+            return None;
+        }
+        if trace_id.bytes().all(|b| b == b'0') || span_id.bytes().all(|b| b == b'0') {
+            return None;
+        }
+
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+        })
+    }
+}
+
+/// Returns `true` if `value` is exactly `len` lowercase hex characters.
+fn is_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+struct PropagatedSpan {
+    span: Span,
+    trace_id: u128,
+}
+
+#[derive(Default)]
+struct RegistryInner {
+    spans: HashMap<u64, PropagatedSpan>,
+    order: VecDeque<u64>,
+}
+
+/// Keeps propagated spans alive so that frontend records can be re-parented
+/// under them.
+///
+/// The plugin manages an instance of this type in Tauri state. Registered spans
+/// stay open until they are released or evicted to make room for newer ones,
+/// so a span handed to the frontend does not close while the frontend may still
+/// log against it.
+pub struct SpanContextRegistry {
+    inner: Mutex<RegistryInner>,
+    capacity: usize,
+    next_id: AtomicU64,
+    hasher: std::collections::hash_map::RandomState,
+}
+
+impl Default for SpanContextRegistry {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl SpanContextRegistry {
+    /// Creates a registry that keeps at most `capacity` spans alive.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(RegistryInner::default()),
+            capacity: capacity.max(1),
+            next_id: AtomicU64::new(1),
+            hasher: std::collections::hash_map::RandomState::new(),
+        }
+    }
+
+    /// Registers a span and returns the context the frontend should echo back.
+    ///
+    /// Returns `None` for disabled spans. If an ancestor of `span` has already
+    /// been registered, the new context shares its trace id.
+    pub fn register(&self, span: &Span) -> Option<SpanContext> {
+        let span_id = span.id()?;
+        let ancestors = span_ancestors(span);
+
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some((id, existing)) = inner
+            .spans
+            .iter()
+            .find(|(_, p)| p.span.id().as_ref() == Some(&span_id))
+        {
+            return Some(format_context(existing.trace_id, *id));
+        }
+
+        let trace_id = ancestors
+            .iter()
+            .find_map(|ancestor| {
+                inner
+                    .spans
+                    .values()
+                    .find(|p| p.span.id().as_ref() == Some(ancestor))
+                    .map(|p| p.trace_id)
+            })
+            .unwrap_or_else(|| self.random_trace_id());
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        inner.spans.insert(
+            id,
+            PropagatedSpan {
+                span: span.clone(),
+                trace_id,
+            },
+        );
+        inner.order.push_back(id);
+
+        while inner.order.len() > self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.spans.remove(&oldest);
+            }
+        }
+
+        Some(format_context(trace_id, id))
+    }
+
+    /// Looks up the span for a context previously returned by [`register()`](Self::register).
+    pub fn get(&self, context: &SpanContext) -> Option<Span> {
+        let id = u64::from_str_radix(&context.span_id, 16).ok()?;
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.spans.get(&id).map(|p| p.span.clone())
+    }
+
+    /// Releases a propagated span, allowing it to close.
+    ///
+    /// Returns `true` if the span was registered.
+    pub fn release(&self, context: &SpanContext) -> bool {
+        let Ok(id) = u64::from_str_radix(&context.span_id, 16) else {
+            return false;
+        };
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.order.retain(|o| *o != id);
+        inner.spans.remove(&id).is_some()
+    }
+
+    /// Returns the number of spans currently kept alive.
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .spans
+            .len()
+    }
+
+    /// Returns `true` if no spans are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn random_trace_id(&self) -> u128 {
+        let mut high = self.hasher.build_hasher();
+        high.write_u64(self.next_id.load(Ordering::Relaxed));
+        high.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default(),
+        );
+        let high = high.finish();
+        let mut low = self.hasher.build_hasher();
+        low.write_u64(high);
+        ((high as u128) << 64) | low.finish() as u128
+    }
+}
+
+fn format_context(trace_id: u128, span_id: u64) -> SpanContext {
+    SpanContext {
+        trace_id: format!("{trace_id:032x}"),
+        span_id: format!("{span_id:016x}"),
+    }
+}
+
+/// Returns the ids of a span's ancestors, nearest first.
+///
+/// Only available when the span's subscriber is built on a [`Registry`];
+/// otherwise the span is treated as a root.
+fn span_ancestors(span: &Span) -> Vec<tracing::Id> {
+    span.with_subscriber(|(id, dispatch)| {
+        dispatch
+            .downcast_ref::<Registry>()
+            .and_then(|registry| registry.span(id))
+            .map(|span_ref| span_ref.scope().skip(1).map(|s| s.id()).collect())
+            .unwrap_or_default()
+    })
+    .unwrap_or_default()
+}
+
+/// Extension trait for handing span contexts to the frontend.
+///
+/// Implemented for anything that implements [`Manager`] (e.g. [`AppHandle`](tauri::AppHandle),
+/// [`App`](tauri::App), [`WebviewWindow`](tauri::WebviewWindow)).
+pub trait SpanContextExt<R: Runtime> {
+    /// Registers `span` for propagation and returns its context.
+    ///
+    /// Returns `None` if the span is disabled or the plugin is not registered.
+    fn span_context(&self, span: &Span) -> Option<SpanContext>;
+
+    /// Registers the current span for propagation and returns its context.
+    fn current_span_context(&self) -> Option<SpanContext> {
+        self.span_context(&Span::current())
+    }
+
+    /// Releases a previously propagated span, allowing it to close.
+    fn release_span_context(&self, context: &SpanContext) -> bool;
+}
+
+impl<R: Runtime, T: Manager<R>> SpanContextExt<R> for T {
+    fn span_context(&self, span: &Span) -> Option<SpanContext> {
+        self.try_state::<SpanContextRegistry>()?.register(span)
+    }
+
+    fn release_span_context(&self, context: &SpanContext) -> bool {
+        self.try_state::<SpanContextRegistry>()
+            .is_some_and(|registry| registry.release(context))
+    }
+}
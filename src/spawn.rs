@@ -0,0 +1,144 @@
+//! Context propagation to spawned work.
+//!
+//! A task spawned with `tauri::async_runtime::spawn()` starts outside of the
+//! span it was spawned from, so its events lose the `log` span's window,
+//! route, and session, and the command or operation span they belong to.
+//! Task-local state, such as [`scoped_context()`](crate::scoped_context)
+//! pairs and [`suppressed()`](crate::suppressed) logging, is lost as well.
+//!
+//! [`spawn_traced()`], [`spawn_blocking_traced()`], and
+//! [`block_in_place_traced()`] capture the current span, subscriber, scoped
+//! context, and suppression when they are called, and re-enter them in the
+//! spawned work.
+
+use std::future::Future;
+
+use tauri::async_runtime::JoinHandle;
+use tracing::Span;
+use tracing::dispatcher::{self, Dispatch};
+use tracing::instrument::{Instrument, WithSubscriber};
+
+use crate::context::{Entries, scoped_entries, with_scoped_entries, with_scoped_entries_sync};
+use crate::suppress::{is_suppressed, suppressed, with_suppressed};
+
+/// The tracing context of the caller, re-entered by spawned work.
+struct Captured {
+    span: Span,
+    dispatch: Dispatch,
+    context: Entries,
+    suppressed: bool,
+}
+
+impl Captured {
+    fn current() -> Self {
+        Self {
+            span: Span::current(),
+            dispatch: dispatcher::get_default(Dispatch::clone),
+            context: scoped_entries(),
+            suppressed: is_suppressed(),
+        }
+    }
+
+    async fn run<F: Future>(self, future: F) -> F::Output {
+        let Self {
+            span,
+            dispatch,
+            context,
+            suppressed: is_suppressed,
+        } = self;
+        let future = with_scoped_entries(context, future);
+        let future = async move {
+            if is_suppressed {
+                suppressed(future).await
+            } else {
+                future.await
+            }
+        };
+        future.instrument(span).with_subscriber(dispatch).await
+    }
+
+    fn call<T>(self, f: impl FnOnce() -> T) -> T {
+        let Self {
+            span,
+            dispatch,
+            context,
+            suppressed,
+        } = self;
+        let run = move || {
+            if suppressed { with_suppressed(f) } else { f() }
+        };
+        dispatcher::with_default(&dispatch, || {
+            span.in_scope(|| with_scoped_entries_sync(context, run))
+        })
+    }
+}
+
+/// Spawns `future` on Tauri's async runtime, in the current span and with
+/// the current scoped context.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn upload(path: &str) {}
+/// # async fn example() {
+/// tracing::info_span!("export", format = "pdf")
+///     .in_scope(|| {
+///         tauri_plugin_tracing::spawn_traced(async {
+///             upload("report.pdf").await;
+///             tracing::info!("uploaded"); // in the `export` span
+///         })
+///     })
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub fn spawn_traced<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tauri::async_runtime::spawn(Captured::current().run(future))
+}
+
+/// Runs `f` on Tauri's blocking thread pool, in the current span and with
+/// the current scoped context.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn compress(path: &str) -> std::io::Result<u64> { Ok(0) }
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes = tauri_plugin_tracing::spawn_blocking_traced(|| compress("logs")).await??;
+/// # Ok(())
+/// # }
+/// ```
+pub fn spawn_blocking_traced<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let captured = Captured::current();
+    tauri::async_runtime::spawn_blocking(move || captured.call(f))
+}
+
+/// Runs blocking code in an async task with
+/// [`tokio::task::block_in_place()`], in the current span and with the
+/// current scoped context.
+///
+/// Like `block_in_place()`, this panics on a current-thread runtime.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn checksum(path: &str) -> u32 { 0 }
+/// # async fn example() {
+/// let sum = tauri_plugin_tracing::block_in_place_traced(|| checksum("update.bin"));
+/// # }
+/// ```
+pub fn block_in_place_traced<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let captured = Captured::current();
+    tokio::task::block_in_place(move || captured.call(f))
+}
@@ -1,7 +1,10 @@
 //! ANSI escape code stripping for clean file output.
 
-use std::io::Write;
-use std::sync::{Mutex, MutexGuard};
+use std::cell::RefCell;
+use std::io::{ErrorKind, IoSlice, Write};
+
+use tracing::Metadata;
+use tracing_subscriber::fmt::MakeWriter;
 
 /// A writer wrapper that strips ANSI escape codes from all output.
 ///
@@ -10,8 +13,10 @@ use std::sync::{Mutex, MutexGuard};
 /// formatting between layers, ANSI codes from one layer can leak into others.
 /// This wrapper strips those codes at write time.
 ///
-/// Uses a zero-copy fast path when no ANSI codes are present. Thread-safe
-/// via internal Mutex.
+/// Wraps a [`MakeWriter`] and strips the output of each writer it makes, so
+/// it adds no lock of its own: wrapping a `NonBlocking` writer leaves logging
+/// threads independent. Wrap a plain [`Write`] type in a
+/// [`Mutex`](std::sync::Mutex) to share it between threads.
 ///
 /// # Example
 ///
@@ -28,86 +33,197 @@ use std::sync::{Mutex, MutexGuard};
 ///     .with(fmt::layer().with_writer(StripAnsiWriter::new(non_blocking)).with_ansi(false))
 ///     .init();
 /// ```
-pub struct StripAnsiWriter<W> {
-    pub(crate) inner: Mutex<W>,
+pub struct StripAnsiWriter<M> {
+    pub(crate) inner: M,
 }
 
-impl<W> StripAnsiWriter<W> {
-    /// Creates a new `StripAnsiWriter` that wraps the given writer.
-    pub fn new(inner: W) -> Self {
-        Self {
-            inner: Mutex::new(inner),
-        }
+impl<M> StripAnsiWriter<M> {
+    /// Creates a new `StripAnsiWriter` that wraps the given [`MakeWriter`].
+    pub fn new(inner: M) -> Self {
+        Self { inner }
     }
 }
 
+/// The escape byte that starts ANSI sequences.
+const ESC: u8 = 0x1b;
+
+/// The most text segments passed to one `write_vectored()` call.
+const MAX_SEGMENTS: usize = 32;
+
+/// The largest stripped write whose buffer is kept for the next one, in bytes.
+const MAX_RETAINED_CAPACITY: usize = 64 * 1024;
+
+thread_local! {
+    /// The stripped output of a write, reused across writes on each thread.
+    static STRIPPED: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
 /// Strips ANSI escape codes from input and writes to output.
 /// Returns the number of bytes from input that were processed.
+///
+/// CSI sequences (`ESC [`), such as colors and cursor movements, and OSC
+/// sequences (`ESC ]`), such as editor link hyperlinks, are removed. Other
+/// escapes are kept.
 pub(crate) fn strip_ansi_and_write<W: Write>(writer: &mut W, buf: &[u8]) -> std::io::Result<usize> {
     let input_len = buf.len();
 
     // Fast path: use memchr to check for ESC byte. If none, write directly.
-    let Some(first_esc) = memchr::memchr(0x1b, buf) else {
+    if memchr::memchr(ESC, buf).is_none() {
         writer.write_all(buf)?;
         return Ok(input_len);
-    };
-
-    // Slow path: ANSI codes present, need to strip them
-    // Pre-allocate with capacity for worst case (all kept)
-    let mut output = Vec::with_capacity(input_len);
-
-    // Copy everything before first ESC
-    output.extend_from_slice(&buf[..first_esc]);
-    let mut i = first_esc;
-
-    while i < buf.len() {
-        if buf[i] == 0x1b && i + 1 < buf.len() && buf[i + 1] == b'[' {
-            // Found ESC[, skip the SGR sequence
-            i += 2;
-            while i < buf.len() {
-                let c = buf[i];
-                i += 1;
-                if c == b'm' {
-                    break;
-                }
-                if !c.is_ascii_digit() && c != b';' {
-                    break;
+    }
+
+    // Slow path: write the text between escape sequences straight from the
+    // input, in as few calls as possible
+    let mut segments = [IoSlice::new(&[]); MAX_SEGMENTS];
+    let mut count = 0;
+    for segment in TextSegments::new(buf) {
+        if count == MAX_SEGMENTS {
+            write_all_vectored(writer, &mut segments[..count])?;
+            count = 0;
+        }
+        segments[count] = IoSlice::new(segment);
+        count += 1;
+    }
+    write_all_vectored(writer, &mut segments[..count])?;
+    Ok(input_len)
+}
+
+/// Writes all of `segments`, like the unstable `Write::write_all_vectored()`.
+fn write_all_vectored<W: Write>(
+    writer: &mut W,
+    mut segments: &mut [IoSlice<'_>],
+) -> std::io::Result<()> {
+    while !segments.is_empty() {
+        match writer.write_vectored(segments) {
+            Ok(0) => return Err(ErrorKind::WriteZero.into()),
+            Ok(written) => IoSlice::advance_slices(&mut segments, written),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// The text of a buffer between ANSI escape sequences, as non-empty slices.
+struct TextSegments<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TextSegments<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for TextSegments<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut start = self.pos;
+        let mut search = start;
+        while let Some(offset) = memchr::memchr(ESC, &self.buf[search..]) {
+            let esc = search + offset;
+            match escape_len(&self.buf[esc..]) {
+                Some(len) => {
+                    self.pos = esc + len;
+                    if esc > start {
+                        return Some(&self.buf[start..esc]);
+                    }
+                    // Sequences back to back
+                    start = self.pos;
+                    search = start;
                 }
+                // Not a sequence we strip, so the ESC is part of the text
+                None => search = esc + 1,
             }
-        } else {
-            output.push(buf[i]);
-            i += 1;
         }
+        self.pos = self.buf.len();
+        (start < self.buf.len()).then(|| &self.buf[start..])
     }
+}
 
-    writer.write_all(&output)?;
-    Ok(input_len)
+/// Returns the length of the CSI or OSC sequence at the start of `seq`, which
+/// starts with ESC, or `None` for other escapes.
+///
+/// Sequences cut off at the end of `seq` extend to its end.
+fn escape_len(seq: &[u8]) -> Option<usize> {
+    match seq.get(1)? {
+        // CSI: parameter and intermediate bytes, then a final byte
+        b'[' => {
+            for (i, &c) in seq.iter().enumerate().skip(2) {
+                match c {
+                    0x20..=0x3f => {}
+                    0x40..=0x7e => return Some(i + 1),
+                    // Malformed, the sequence ends before this byte
+                    _ => return Some(i),
+                }
+            }
+            Some(seq.len())
+        }
+        // OSC: terminated by BEL or ST (`ESC \`)
+        b']' => {
+            for (i, &c) in seq.iter().enumerate().skip(2) {
+                if c == 0x07 {
+                    return Some(i + 1);
+                }
+                if c == ESC && seq.get(i + 1) == Some(&b'\\') {
+                    return Some(i + 2);
+                }
+            }
+            Some(seq.len())
+        }
+        _ => None,
+    }
 }
 
 /// A writer handle returned by the [`MakeWriter`](tracing_subscriber::fmt::MakeWriter) implementation.
 ///
 /// This type implements [`std::io::Write`] and strips ANSI codes during writes.
-pub struct StripAnsiWriterGuard<'a, W> {
-    guard: MutexGuard<'a, W>,
+pub struct StripAnsiWriterGuard<W> {
+    inner: W,
 }
 
-impl<W: Write> Write for StripAnsiWriterGuard<'_, W> {
+impl<W: Write> Write for StripAnsiWriterGuard<W> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        strip_ansi_and_write(&mut *self.guard, buf)
+        if memchr::memchr(ESC, buf).is_none() {
+            self.inner.write_all(buf)?;
+            return Ok(buf.len());
+        }
+        // Writers that send each write on its own, like `NonBlocking`, would
+        // interleave the segments of concurrent threads, so the stripped
+        // output is gathered first and written with one call
+        STRIPPED.with(|stripped| match stripped.try_borrow_mut() {
+            Ok(mut stripped) => {
+                strip_ansi_and_write(&mut *stripped, buf)?;
+                let result = self.inner.write_all(&stripped);
+                stripped.clear();
+                stripped.shrink_to(MAX_RETAINED_CAPACITY);
+                result.map(|()| buf.len())
+            }
+            Err(_) => strip_ansi_and_write(&mut self.inner, buf),
+        })
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
-        self.guard.flush()
+        self.inner.flush()
     }
 }
 
 // Implement MakeWriter so this can be used with fmt::layer().with_writer()
-impl<'a, W: Write + 'a> tracing_subscriber::fmt::MakeWriter<'a> for StripAnsiWriter<W> {
-    type Writer = StripAnsiWriterGuard<'a, W>;
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for StripAnsiWriter<M> {
+    type Writer = StripAnsiWriterGuard<M::Writer>;
 
     fn make_writer(&'a self) -> Self::Writer {
         StripAnsiWriterGuard {
-            guard: self.inner.lock().unwrap_or_else(|e| e.into_inner()),
+            inner: self.inner.make_writer(),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        StripAnsiWriterGuard {
+            inner: self.inner.make_writer_for(meta),
         }
     }
 }
@@ -116,7 +232,7 @@ impl<'a, W: Write + 'a> tracing_subscriber::fmt::MakeWriter<'a> for StripAnsiWri
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
-    use tracing_subscriber::fmt::MakeWriter;
+    use std::sync::Mutex;
 
     #[test]
     fn strip_ansi_fast_path_no_escape() {
@@ -187,7 +303,7 @@ mod tests {
 
     #[test]
     fn strip_ansi_writer_works() {
-        let inner = Vec::new();
+        let inner = Mutex::new(Vec::new());
         let writer = StripAnsiWriter::new(inner);
         {
             let mut guard = writer.make_writer();
@@ -214,4 +330,99 @@ mod tests {
         assert_eq!(written, input.len());
         assert_eq!(output, b"");
     }
+
+    #[test]
+    fn strip_ansi_removes_cursor_and_private_mode_sequences() {
+        let mut output = Vec::new();
+        let input = b"\x1b[2K\x1b[1Aline\x1b[?25h done";
+        let written = strip_ansi_and_write(&mut output, input).unwrap();
+        assert_eq!(written, input.len());
+        assert_eq!(output, b"line done");
+    }
+
+    #[test]
+    fn strip_ansi_removes_osc_hyperlinks() {
+        let mut output = Vec::new();
+        // Terminated by ST, as editor links are, and by BEL
+        let input = b"at \x1b]8;;file:///app/src/main.rs\x1b\\src/main.rs:42\x1b]8;;\x1b\\ and \x1b]8;;https://example.com\x07link\x1b]8;;\x07";
+        let written = strip_ansi_and_write(&mut output, input).unwrap();
+        assert_eq!(written, input.len());
+        assert_eq!(output, b"at src/main.rs:42 and link");
+    }
+
+    #[test]
+    fn strip_ansi_writes_many_segments() {
+        let mut output = Vec::new();
+        let input = "\x1b[2mx\x1b[0m ".repeat(MAX_SEGMENTS * 3);
+        let written = strip_ansi_and_write(&mut output, input.as_bytes()).unwrap();
+        assert_eq!(written, input.len());
+        assert_eq!(output, "x ".repeat(MAX_SEGMENTS * 3).as_bytes());
+    }
+
+    #[test]
+    fn strip_ansi_handles_partial_writes() {
+        /// Accepts one byte per call.
+        struct Trickle(Vec<u8>);
+
+        impl Write for Trickle {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.extend(buf.first());
+                Ok(buf.len().min(1))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut output = Trickle(Vec::new());
+        let input = b"\x1b[1;31mBold Red\x1b[0m Normal";
+        let written = strip_ansi_and_write(&mut output, input).unwrap();
+        assert_eq!(written, input.len());
+        assert_eq!(output.0, b"Bold Red Normal");
+    }
+
+    #[test]
+    fn strip_ansi_writer_keeps_concurrent_lines_whole() {
+        use std::sync::Arc;
+
+        /// Appends each write on its own, like `NonBlocking` sends each write
+        /// as a message.
+        struct PerWrite(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for PerWrite {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let shared = output.clone();
+        let writer = StripAnsiWriter::new(move || PerWrite(shared.clone()));
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let writer = &writer;
+                scope.spawn(move || {
+                    for _ in 0..100 {
+                        let line = format!("\x1b[2mthread\x1b[0m \x1b[32m{thread}\x1b[0m line\n");
+                        writer.make_writer().write_all(line.as_bytes()).unwrap();
+                    }
+                });
+            }
+        });
+
+        let output = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+        assert_eq!(output.lines().count(), 400);
+        for line in output.lines() {
+            assert!(
+                line.starts_with("thread ") && line.ends_with(" line"),
+                "{line}"
+            );
+        }
+    }
 }
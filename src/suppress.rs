@@ -0,0 +1,136 @@
+//! Local suppression of event recording.
+//!
+//! Sensitive code paths, such as password handling or license checks, may
+//! call into libraries that log more than they should. Instead of changing
+//! the global filter, those paths can run with logging suppressed:
+//!
+//! - [`with_suppressed()`] runs a closure,
+//! - [`suppressed()`] wraps a future, following it across `.await` points and
+//!   threads,
+//! - [`suppress_logs()`] returns a guard for the rest of a synchronous scope.
+//!
+//! While suppressed, [`SuppressionLayer`] disables every event before any
+//! other layer sees it, including the webview layer. Spans are still created,
+//! so span timing is unaffected.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::marker::PhantomData;
+
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+tokio::task_local! {
+    static SUPPRESSED: ();
+}
+
+thread_local! {
+    static GUARDS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns `true` if events on the current thread or task are suppressed.
+pub fn is_suppressed() -> bool {
+    GUARDS.with(Cell::get) > 0 || SUPPRESSED.try_with(|_| ()).is_ok()
+}
+
+/// Runs `f` with event recording suppressed.
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing::{is_suppressed, with_suppressed};
+///
+/// let checked = with_suppressed(|| {
+///     assert!(is_suppressed());
+///     "hunter2".len() > 4
+/// });
+/// assert!(checked);
+/// assert!(!is_suppressed());
+/// ```
+pub fn with_suppressed<T>(f: impl FnOnce() -> T) -> T {
+    SUPPRESSED.sync_scope((), f)
+}
+
+/// Wraps a future so its events are suppressed whenever it is polled.
+///
+/// The suppression is task-local, so it follows the future across `.await`
+/// points and worker threads, and does not leak to other tasks.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::suppressed;
+/// # async fn verify_license(key: &str) -> bool { !key.is_empty() }
+/// # async fn example() {
+/// let valid = suppressed(verify_license("XXXX-XXXX")).await;
+/// # }
+/// ```
+pub fn suppressed<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    SUPPRESSED.scope((), future)
+}
+
+/// Suppresses event recording on the current thread until the guard is
+/// dropped.
+///
+/// The guard cannot be sent to another thread, so it cannot be held across
+/// an `.await` in a task that may move between threads; use [`suppressed()`]
+/// for async code.
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing::{is_suppressed, suppress_logs};
+///
+/// {
+///     let _guard = suppress_logs();
+///     assert!(is_suppressed());
+/// }
+/// assert!(!is_suppressed());
+/// ```
+pub fn suppress_logs() -> SuppressGuard {
+    GUARDS.with(|guards| guards.set(guards.get() + 1));
+    SuppressGuard {
+        _not_send: PhantomData,
+    }
+}
+
+/// Keeps event recording suppressed on the current thread while it exists.
+///
+/// Returned by [`suppress_logs()`].
+#[derive(Debug)]
+#[must_use = "events are only suppressed while the guard is held"]
+pub struct SuppressGuard {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for SuppressGuard {
+    fn drop(&mut self) {
+        GUARDS.with(|guards| guards.set(guards.get().saturating_sub(1)));
+    }
+}
+
+/// A tracing layer that disables events while recording is suppressed.
+///
+/// The plugin's default subscriber includes it. When composing your own
+/// subscriber, add it last, so suppressed events are disabled before any
+/// other layer sees them.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::SuppressionLayer;
+/// # use tracing_subscriber::{Registry, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+/// Registry::default()
+///     .with(fmt::layer())
+///     .with(SuppressionLayer)
+///     .init();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SuppressionLayer;
+
+impl<S: Subscriber> Layer<S> for SuppressionLayer {
+    fn event_enabled(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) -> bool {
+        !is_suppressed()
+    }
+}
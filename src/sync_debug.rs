@@ -0,0 +1,381 @@
+//! Lock instrumentation for diagnosing hangs.
+//!
+//! Requires the `sync-debug` feature. [`TracedMutex`] and [`TracedRwLock`]
+//! wrap the standard library locks and emit a `WARN` event under the
+//! [`SYNC_DEBUG_TARGET`] target when a lock is waited on, or held, for longer
+//! than a threshold. Each warning names the lock and the span that held it,
+//! which is usually enough to find the code path blocking the UI thread.
+//!
+//! The wrappers measure every acquisition, so use them while hunting a hang
+//! rather than leaving them in hot paths.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{
+    LockResult, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+use std::time::{Duration, Instant};
+
+/// The target used for lock warnings.
+pub const SYNC_DEBUG_TARGET: &str = "tauri_plugin_tracing::sync_debug";
+
+/// The threshold used by locks created without one.
+pub const DEFAULT_LOCK_THRESHOLD: Duration = Duration::from_millis(100);
+
+/// The code that holds a lock.
+#[derive(Debug, Clone)]
+struct LockOwner {
+    span: Option<&'static str>,
+    thread: String,
+}
+
+impl LockOwner {
+    fn current() -> Self {
+        let thread = std::thread::current();
+        Self {
+            span: tracing::Span::current().metadata().map(|m| m.name()),
+            thread: thread
+                .name()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{:?}", thread.id())),
+        }
+    }
+}
+
+/// Name, threshold and current owner shared by both lock types.
+#[derive(Debug)]
+struct LockState {
+    name: String,
+    threshold: Duration,
+    owner: Mutex<Option<LockOwner>>,
+}
+
+impl LockState {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            threshold: DEFAULT_LOCK_THRESHOLD,
+            owner: Mutex::new(None),
+        }
+    }
+
+    fn owner(&self) -> Option<LockOwner> {
+        self.owner.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn set_owner(&self, owner: Option<LockOwner>) {
+        *self.owner.lock().unwrap_or_else(|e| e.into_inner()) = owner;
+    }
+
+    /// Warns if acquiring the lock took longer than the threshold.
+    ///
+    /// `owner` is the code that held the lock when the wait started.
+    fn check_wait(&self, started: Instant, owner: Option<LockOwner>) {
+        let waited = started.elapsed();
+        if waited < self.threshold {
+            return;
+        }
+        tracing::warn!(
+            target: SYNC_DEBUG_TARGET,
+            lock = %self.name,
+            wait_ms = waited.as_secs_f64() * 1000.0,
+            owner_span = owner.as_ref().and_then(|o| o.span),
+            owner_thread = owner.as_ref().map(|o| o.thread.as_str()),
+            "waited {}ms for lock `{}`",
+            waited.as_millis(),
+            self.name
+        );
+    }
+
+    /// Warns if the lock was held for longer than the threshold.
+    fn check_hold(&self, acquired: Instant, owner: &LockOwner) {
+        let held = acquired.elapsed();
+        if held < self.threshold {
+            return;
+        }
+        tracing::warn!(
+            target: SYNC_DEBUG_TARGET,
+            lock = %self.name,
+            held_ms = held.as_secs_f64() * 1000.0,
+            owner_span = owner.span,
+            owner_thread = %owner.thread,
+            "lock `{}` held for {}ms",
+            self.name,
+            held.as_millis()
+        );
+    }
+}
+
+/// Maps the guard inside a [`LockResult`], keeping the poison state.
+fn map_lock_result<G, T>(result: LockResult<G>, f: impl FnOnce(G) -> T) -> LockResult<T> {
+    match result {
+        Ok(guard) => Ok(f(guard)),
+        Err(poisoned) => Err(PoisonError::new(f(poisoned.into_inner()))),
+    }
+}
+
+/// A [`Mutex`] that warns about long waits and long holds.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use tauri_plugin_tracing::TracedMutex;
+///
+/// let cache = TracedMutex::new("thumbnail-cache", Vec::<u8>::new())
+///     .with_threshold(Duration::from_millis(16));
+///
+/// cache.lock().unwrap().push(1);
+/// ```
+pub struct TracedMutex<T> {
+    state: LockState,
+    inner: Mutex<T>,
+}
+
+impl<T> TracedMutex<T> {
+    /// Creates a mutex named `name`, using [`DEFAULT_LOCK_THRESHOLD`].
+    pub fn new(name: impl Into<String>, value: T) -> Self {
+        Self {
+            state: LockState::new(name.into()),
+            inner: Mutex::new(value),
+        }
+    }
+
+    /// Sets how long the lock may be waited on or held before warning.
+    pub fn with_threshold(mut self, threshold: Duration) -> Self {
+        self.state.threshold = threshold;
+        self
+    }
+
+    /// Returns the lock's name.
+    pub fn name(&self) -> &str {
+        &self.state.name
+    }
+
+    /// Returns the warning threshold.
+    pub fn threshold(&self) -> Duration {
+        self.state.threshold
+    }
+
+    /// Acquires the mutex, blocking the current thread until it is available.
+    ///
+    /// See [`Mutex::lock()`].
+    pub fn lock(&self) -> LockResult<TracedMutexGuard<'_, T>> {
+        let owner = self.state.owner();
+        let started = Instant::now();
+        let result = self.inner.lock();
+        self.state.check_wait(started, owner);
+        map_lock_result(result, |guard| {
+            let owner = LockOwner::current();
+            self.state.set_owner(Some(owner.clone()));
+            TracedMutexGuard {
+                state: &self.state,
+                acquired: Instant::now(),
+                owner,
+                guard,
+            }
+        })
+    }
+
+    /// Consumes the mutex, returning the underlying data.
+    pub fn into_inner(self) -> LockResult<T> {
+        self.inner.into_inner()
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    pub fn get_mut(&mut self) -> LockResult<&mut T> {
+        self.inner.get_mut()
+    }
+}
+
+impl<T> std::fmt::Debug for TracedMutex<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TracedMutex")
+            .field("name", &self.state.name)
+            .field("threshold", &self.state.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The guard returned by [`TracedMutex::lock()`].
+///
+/// Warns on drop if the lock was held for longer than the threshold.
+pub struct TracedMutexGuard<'a, T> {
+    state: &'a LockState,
+    acquired: Instant,
+    owner: LockOwner,
+    guard: MutexGuard<'a, T>,
+}
+
+impl<T> Deref for TracedMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for TracedMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for TracedMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        // Still holding the lock, so no other owner can have been recorded
+        self.state.set_owner(None);
+        self.state.check_hold(self.acquired, &self.owner);
+    }
+}
+
+/// A [`RwLock`] that warns about long waits and long holds.
+///
+/// Waits report the writer that held the lock, if any. Hold durations are
+/// checked for both readers and writers.
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing::TracedRwLock;
+///
+/// let settings = TracedRwLock::new("settings", String::from("dark"));
+///
+/// assert_eq!(*settings.read().unwrap(), "dark");
+/// *settings.write().unwrap() = String::from("light");
+/// ```
+pub struct TracedRwLock<T> {
+    state: LockState,
+    inner: RwLock<T>,
+}
+
+impl<T> TracedRwLock<T> {
+    /// Creates a lock named `name`, using [`DEFAULT_LOCK_THRESHOLD`].
+    pub fn new(name: impl Into<String>, value: T) -> Self {
+        Self {
+            state: LockState::new(name.into()),
+            inner: RwLock::new(value),
+        }
+    }
+
+    /// Sets how long the lock may be waited on or held before warning.
+    pub fn with_threshold(mut self, threshold: Duration) -> Self {
+        self.state.threshold = threshold;
+        self
+    }
+
+    /// Returns the lock's name.
+    pub fn name(&self) -> &str {
+        &self.state.name
+    }
+
+    /// Returns the warning threshold.
+    pub fn threshold(&self) -> Duration {
+        self.state.threshold
+    }
+
+    /// Acquires shared read access, blocking until it is available.
+    ///
+    /// See [`RwLock::read()`].
+    pub fn read(&self) -> LockResult<TracedRwLockReadGuard<'_, T>> {
+        let owner = self.state.owner();
+        let started = Instant::now();
+        let result = self.inner.read();
+        self.state.check_wait(started, owner);
+        map_lock_result(result, |guard| TracedRwLockReadGuard {
+            state: &self.state,
+            acquired: Instant::now(),
+            owner: LockOwner::current(),
+            guard,
+        })
+    }
+
+    /// Acquires exclusive write access, blocking until it is available.
+    ///
+    /// See [`RwLock::write()`].
+    pub fn write(&self) -> LockResult<TracedRwLockWriteGuard<'_, T>> {
+        let owner = self.state.owner();
+        let started = Instant::now();
+        let result = self.inner.write();
+        self.state.check_wait(started, owner);
+        map_lock_result(result, |guard| {
+            let owner = LockOwner::current();
+            self.state.set_owner(Some(owner.clone()));
+            TracedRwLockWriteGuard {
+                state: &self.state,
+                acquired: Instant::now(),
+                owner,
+                guard,
+            }
+        })
+    }
+
+    /// Consumes the lock, returning the underlying data.
+    pub fn into_inner(self) -> LockResult<T> {
+        self.inner.into_inner()
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    pub fn get_mut(&mut self) -> LockResult<&mut T> {
+        self.inner.get_mut()
+    }
+}
+
+impl<T> std::fmt::Debug for TracedRwLock<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TracedRwLock")
+            .field("name", &self.state.name)
+            .field("threshold", &self.state.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The guard returned by [`TracedRwLock::read()`].
+pub struct TracedRwLockReadGuard<'a, T> {
+    state: &'a LockState,
+    acquired: Instant,
+    owner: LockOwner,
+    guard: RwLockReadGuard<'a, T>,
+}
+
+impl<T> Deref for TracedRwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> Drop for TracedRwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.state.check_hold(self.acquired, &self.owner);
+    }
+}
+
+/// The guard returned by [`TracedRwLock::write()`].
+pub struct TracedRwLockWriteGuard<'a, T> {
+    state: &'a LockState,
+    acquired: Instant,
+    owner: LockOwner,
+    guard: RwLockWriteGuard<'a, T>,
+}
+
+impl<T> Deref for TracedRwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for TracedRwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for TracedRwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.state.set_owner(None);
+        self.state.check_hold(self.acquired, &self.owner);
+    }
+}
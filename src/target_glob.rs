@@ -0,0 +1,302 @@
+//! Wildcard target directives.
+//!
+//! [`Builder::with_target()`](crate::Builder::with_target) matches a target
+//! and its submodules, so it needs the exact prefix of the modules to filter.
+//! [`Builder::with_target_glob()`](crate::Builder::with_target_glob) matches
+//! patterns of `::`-separated segments instead:
+//!
+//! - A segment that is only `*` matches any number of segments, including
+//!   none: `tao::*` matches `tao` and `tao::platform_impl::window`, and
+//!   `*::hyper::*` matches `hyper` and `reqwest::hyper::client`.
+//! - A `*` within a segment matches any characters in that segment:
+//!   `my_app::*_sync` matches `my_app::file_sync`.
+//!
+//! A target that matches a pattern is filtered at the pattern's level,
+//! whatever its level from [`with_target()`](crate::Builder::with_target),
+//! [`with_max_level()`](crate::Builder::with_max_level), or runtime changes
+//! through the [`FilterHandle`](crate::FilterHandle). When several patterns
+//! match, the one with the most literal characters is used, and the last
+//! added of equally specific patterns.
+//!
+//! Patterns are compiled once, when the plugin is built, and matched without
+//! allocating.
+
+use std::sync::Arc;
+
+use tracing::subscriber::Interest;
+use tracing::{Metadata, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Filter, Layer};
+
+/// A segment of a compiled target pattern.
+#[derive(Debug, Clone)]
+enum Segment {
+    /// `*`, matching any number of segments.
+    Any,
+    Literal(String),
+    /// A segment with wildcards, matching any characters.
+    Glob(Vec<char>),
+}
+
+impl Segment {
+    fn matches(&self, segment: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Literal(literal) => literal == segment,
+            Self::Glob(pattern) => {
+                wildcard_match(pattern, segment.chars(), |c| *c == '*', |p, c| p == c)
+            }
+        }
+    }
+}
+
+/// A compiled target pattern.
+#[derive(Debug, Clone)]
+struct TargetGlob {
+    segments: Vec<Segment>,
+    /// The number of characters other than `*`, for specificity.
+    literal_len: usize,
+}
+
+impl TargetGlob {
+    fn new(pattern: &str) -> Self {
+        let segments = pattern
+            .split("::")
+            .map(|segment| match segment {
+                "*" => Segment::Any,
+                _ if segment.contains('*') => Segment::Glob(segment.chars().collect()),
+                _ => Segment::Literal(segment.to_string()),
+            })
+            .collect();
+        Self {
+            segments,
+            literal_len: pattern.chars().filter(|c| *c != '*').count(),
+        }
+    }
+
+    fn matches(&self, target: &str) -> bool {
+        wildcard_match(
+            &self.segments,
+            target.split("::"),
+            |segment| matches!(segment, Segment::Any),
+            |segment, s| segment.matches(s),
+        )
+    }
+}
+
+/// Matches `items` against `pattern`, in which wildcards match any run of
+/// items, including none.
+///
+/// Backtracks only to the most recent wildcard, so matching is linear in
+/// practice, and does not allocate.
+fn wildcard_match<P, I: Iterator + Clone>(
+    pattern: &[P],
+    mut items: I,
+    is_wildcard: impl Fn(&P) -> bool,
+    matches: impl Fn(&P, &I::Item) -> bool,
+) -> bool {
+    let mut p = 0;
+    // The last wildcard, and the items after those it matched
+    let mut star: Option<(usize, I)> = None;
+    loop {
+        let mut rest = items.clone();
+        let Some(item) = rest.next() else {
+            break;
+        };
+        match pattern.get(p) {
+            Some(pat) if is_wildcard(pat) => {
+                star = Some((p, items.clone()));
+                p += 1;
+                continue;
+            }
+            Some(pat) if matches(pat, &item) => {
+                p += 1;
+                items = rest;
+                continue;
+            }
+            _ => {}
+        }
+        match &mut star {
+            // Let the wildcard match one more item
+            Some((star_p, star_items)) => {
+                star_items.next();
+                p = *star_p + 1;
+                items = star_items.clone();
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(is_wildcard)
+}
+
+/// Levels for targets matching wildcard patterns.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TargetGlobs(Vec<(TargetGlob, LevelFilter)>);
+
+impl TargetGlobs {
+    pub(crate) fn new<'a>(patterns: impl IntoIterator<Item = &'a (String, LevelFilter)>) -> Self {
+        Self(
+            patterns
+                .into_iter()
+                .map(|(pattern, level)| (TargetGlob::new(pattern), *level))
+                .collect(),
+        )
+    }
+
+    /// Returns the level of the most specific pattern matching `target`.
+    pub(crate) fn level_for(&self, target: &str) -> Option<LevelFilter> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, (glob, _))| glob.matches(target))
+            .max_by_key(|(n, (glob, _))| (glob.literal_len, *n))
+            .map(|(_, (_, level))| *level)
+    }
+
+    fn max_level(&self) -> Option<LevelFilter> {
+        self.0.iter().map(|(_, level)| *level).max()
+    }
+}
+
+/// Applies [`TargetGlobs`] on top of a filter, either an output's filter or,
+/// as a layer, the global filter.
+#[derive(Debug, Clone)]
+pub(crate) struct GlobFilter<F> {
+    globs: Arc<TargetGlobs>,
+    inner: F,
+}
+
+impl<F> GlobFilter<F> {
+    pub(crate) fn new(globs: Arc<TargetGlobs>, inner: F) -> Self {
+        Self { globs, inner }
+    }
+
+    fn level_for(&self, metadata: &Metadata<'_>) -> Option<LevelFilter> {
+        self.globs.level_for(metadata.target())
+    }
+
+    fn max_level_hint(&self, inner: Option<LevelFilter>) -> Option<LevelFilter> {
+        match self.globs.max_level() {
+            Some(globs) => crate::filter::max_hint(Some(globs), inner),
+            None => inner,
+        }
+    }
+}
+
+fn interest(level: LevelFilter, metadata: &Metadata<'_>) -> Interest {
+    if level >= *metadata.level() {
+        Interest::always()
+    } else {
+        Interest::never()
+    }
+}
+
+impl<S, F: Filter<S>> Filter<S> for GlobFilter<F> {
+    fn enabled(&self, metadata: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        match self.level_for(metadata) {
+            Some(level) => level >= *metadata.level(),
+            None => self.inner.enabled(metadata, cx),
+        }
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        match self.level_for(metadata) {
+            Some(level) => interest(level, metadata),
+            None => self.inner.callsite_enabled(metadata),
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.max_level_hint(self.inner.max_level_hint())
+    }
+}
+
+impl<S: Subscriber, L: Layer<S>> Layer<S> for GlobFilter<L> {
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        match self.level_for(metadata) {
+            Some(level) => interest(level, metadata),
+            None => self.inner.register_callsite(metadata),
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        match self.level_for(metadata) {
+            Some(level) => level >= *metadata.level(),
+            None => self.inner.enabled(metadata, ctx),
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.max_level_hint(self.inner.max_level_hint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn globs(patterns: &[(&str, LevelFilter)]) -> TargetGlobs {
+        let patterns: Vec<(String, LevelFilter)> = patterns
+            .iter()
+            .map(|(pattern, level)| (pattern.to_string(), *level))
+            .collect();
+        TargetGlobs::new(&patterns)
+    }
+
+    #[test]
+    fn wildcard_segments_match_any_number_of_segments() {
+        let tao = TargetGlob::new("tao::*");
+        assert!(tao.matches("tao"));
+        assert!(tao.matches("tao::platform_impl::window"));
+        assert!(!tao.matches("taox"));
+        assert!(!tao.matches("my_app::tao"));
+
+        let hyper = TargetGlob::new("*::hyper::*");
+        assert!(hyper.matches("hyper"));
+        assert!(hyper.matches("hyper::client"));
+        assert!(hyper.matches("reqwest::hyper::client::pool"));
+        assert!(!hyper.matches("hyperx::client"));
+        assert!(!hyper.matches("my_app"));
+
+        let middle = TargetGlob::new("my_app::*::db");
+        assert!(middle.matches("my_app::db"));
+        assert!(middle.matches("my_app::sync::remote::db"));
+        assert!(!middle.matches("my_app::db::pool"));
+    }
+
+    #[test]
+    fn wildcards_within_segments_match_characters() {
+        let glob = TargetGlob::new("my_app::*_sync");
+        assert!(glob.matches("my_app::file_sync"));
+        assert!(glob.matches("my_app::_sync"));
+        assert!(!glob.matches("my_app::file_sync::queue"));
+        assert!(!glob.matches("my_app::sync"));
+
+        assert!(TargetGlob::new("tauri_*::*").matches("tauri_plugin_fs::commands"));
+        assert!(TargetGlob::new("*").matches("anything::at::all"));
+    }
+
+    #[test]
+    fn most_specific_pattern_wins() {
+        let globs = globs(&[
+            ("*::hyper::*", LevelFilter::OFF),
+            ("my_app::*", LevelFilter::DEBUG),
+            ("my_app::hyper::*", LevelFilter::TRACE),
+            ("tao::*", LevelFilter::WARN),
+            ("tao::*", LevelFilter::ERROR),
+        ]);
+        assert_eq!(globs.level_for("hyper::client"), Some(LevelFilter::OFF));
+        assert_eq!(globs.level_for("my_app::ui"), Some(LevelFilter::DEBUG));
+        assert_eq!(
+            globs.level_for("my_app::hyper::client"),
+            Some(LevelFilter::TRACE)
+        );
+        assert_eq!(globs.level_for("tao::window"), Some(LevelFilter::ERROR));
+        assert_eq!(globs.level_for("wry"), None);
+        assert_eq!(globs.max_level(), Some(LevelFilter::TRACE));
+    }
+}
@@ -0,0 +1,410 @@
+//! Utilities for asserting on logs in tests.
+//!
+//! Requires the `test-utils` feature. [`with_captured_logs()`] runs a closure
+//! with a thread-local subscriber that records every event, so tests can check
+//! that commands emit the expected events, levels, and fields without setting
+//! up files or a webview:
+//!
+//! ```
+//! use tauri_plugin_tracing::test::with_captured_logs;
+//! use tracing::Level;
+//!
+//! let events = with_captured_logs(|| {
+//!     tracing::info!(user = "alice", "logged in");
+//! });
+//!
+//! assert_eq!(events.len(), 1);
+//! assert_eq!(events[0].level, Level::INFO);
+//! assert_eq!(events[0].message, "logged in");
+//! assert_eq!(events[0].field("user"), Some("alice"));
+//! ```
+//!
+//! For commands that need a Tauri context, [`mock_app()`] builds an app on the
+//! mock runtime with the plugin registered, and [`invoke_command()`] calls a
+//! command through IPC on the current thread:
+//!
+//! ```no_run
+//! use tauri_plugin_tracing::Builder;
+//! use tauri_plugin_tracing::test::{invoke_command, mock_app, mock_window, with_captured_logs};
+//!
+//! let app = mock_app(Builder::new()).unwrap();
+//! let window = mock_window(&app, "main").unwrap();
+//!
+//! let events = with_captured_logs(|| {
+//!     invoke_command(
+//!         &window,
+//!         "plugin:tracing|log",
+//!         serde_json::json!({ "level": 3, "message": ["hello"] }),
+//!     )
+//!     .unwrap();
+//! });
+//! assert!(events.iter().any(|e| e.message == "hello"));
+//! ```
+//!
+//! To check what reaches the frontend, [`listen_webview_records()`] collects the
+//! payloads a mock app emits on the `tracing://log` channel, and
+//! [`capturing_webview_layer()`] builds a [`WebviewLayer`] that records payloads
+//! without an app at all.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use tauri::test::{MockRuntime, mock_builder, mock_context, noop_assets};
+use tauri::{App, Listener, WebviewWindow, WebviewWindowBuilder};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::layer::RecordEmitter;
+use crate::plugin_name::channel;
+use crate::{RecordPayload, WebviewLayer};
+
+/// An event recorded by a [`CapturingLayer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedEvent {
+    /// The event's level.
+    pub level: Level,
+    /// The event's target.
+    pub target: String,
+    /// The formatted `message` field, or an empty string if there is none.
+    pub message: String,
+    /// All other fields, formatted with their `Debug` representation
+    /// (strings are recorded without quotes).
+    pub fields: BTreeMap<String, String>,
+    /// Names of the spans the event occurred in, from the root to the innermost.
+    pub spans: Vec<String>,
+}
+
+impl CapturedEvent {
+    /// Returns the value of a field, if it was recorded.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+
+    /// Returns `true` if the event occurred inside a span with the given name.
+    pub fn in_span(&self, name: &str) -> bool {
+        self.spans.iter().any(|span| span == name)
+    }
+}
+
+/// A tracing layer that records every event it sees.
+///
+/// Clones share the same storage, so keep one clone to read the events after
+/// handing the layer to a subscriber.
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing::test::CapturingLayer;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let layer = CapturingLayer::new();
+/// let subscriber = tracing_subscriber::registry().with(layer.clone());
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::warn!("disk almost full");
+/// });
+///
+/// assert_eq!(layer.events()[0].message, "disk almost full");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CapturingLayer {
+    events: Arc<Mutex<Vec<CapturedEvent>>>,
+}
+
+impl CapturingLayer {
+    /// Creates an empty capturing layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the events recorded so far.
+    pub fn events(&self) -> Vec<CapturedEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Removes and returns the events recorded so far.
+    pub fn take(&self) -> Vec<CapturedEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl<S> Layer<S> for CapturingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let spans = ctx
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| span.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let metadata = event.metadata();
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(CapturedEvent {
+                level: *metadata.level(),
+                target: metadata.target().to_string(),
+                message: visitor.message,
+                fields: visitor.fields,
+                spans,
+            });
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl tracing::field::Visit for FieldVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields
+                .insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.fields
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+}
+
+/// Runs `f` and returns every event it emitted on the current thread.
+///
+/// Events are captured at all levels, regardless of any global subscriber.
+/// Work spawned onto other threads is not captured.
+pub fn with_captured_logs<F: FnOnce()>(f: F) -> Vec<CapturedEvent> {
+    let layer = CapturingLayer::new();
+    let subscriber = tracing_subscriber::registry().with(layer.clone());
+    tracing::subscriber::with_default(subscriber, f);
+    layer.take()
+}
+
+/// The plugin's commands, granted to every window of a [`mock_app()`].
+const COMMANDS: &[&str] = &[
+    "log",
+    "log_batch",
+    "report_frame_times",
+    "sync_clock",
+    "operation_start",
+    "operation_end",
+    "operation_get",
+    "operation_list",
+    "span_start",
+    "span_end",
+    "set_log_level",
+    "set_target_level",
+    "reset_log_filter",
+    "get_effective_filter",
+    "get_log_stats",
+    "clear_logs",
+    "mark",
+    "get_logs_around_mark",
+    "set_context",
+    "remove_context",
+    "clear_context",
+    "set_current_route",
+    "report_invariant_violation",
+    "get_invariant_violations",
+    "progress",
+    "gauge",
+    "counter",
+    "begin_test",
+    "end_test",
+    "get_schema",
+    "analyze_noise",
+    "apply_noise_filters",
+    "create_diagnostic_report",
+    "start_span_aware_profile",
+    "stop_span_aware_profile",
+    "list_profiling_sessions",
+    "get_profiling_session",
+    "get_span_stats",
+    "get_filter_stats",
+    "tail_logs",
+    "stop_tail_logs",
+    "heartbeat_pong",
+    "webview_ready",
+    #[cfg(feature = "flamegraph")]
+    "generate_flamegraph",
+    #[cfg(feature = "flamegraph")]
+    "generate_flamechart",
+];
+
+/// Builds an app on the mock runtime with the plugin from `builder` registered.
+///
+/// The mock context has no capabilities, so the plugin's commands are granted
+/// to every window directly, under the builder's plugin name.
+pub fn mock_app(builder: crate::Builder) -> crate::Result<App<MockRuntime>> {
+    let mut context = mock_context(noop_assets());
+    *context.runtime_authority_mut() =
+        tauri::runtime_authority!(Default::default(), plugin_acl(builder.plugin_name));
+
+    Ok(mock_builder().plugin(builder.build()).build(context)?)
+}
+
+fn plugin_acl(plugin_name: &str) -> tauri::utils::acl::resolved::Resolved {
+    use tauri::utils::acl::resolved::{Resolved, ResolvedCommand};
+
+    let any_window = glob::Pattern::new("*").ok().into_iter().collect::<Vec<_>>();
+    let allowed_commands = COMMANDS
+        .iter()
+        .map(|command| {
+            let resolved = ResolvedCommand {
+                windows: any_window.clone(),
+                ..Default::default()
+            };
+            (format!("plugin:{plugin_name}|{command}"), vec![resolved])
+        })
+        .collect();
+
+    Resolved {
+        allowed_commands,
+        ..Default::default()
+    }
+}
+
+/// Creates a webview window on a mock app.
+pub fn mock_window(
+    app: &App<MockRuntime>,
+    label: &str,
+) -> crate::Result<WebviewWindow<MockRuntime>> {
+    Ok(WebviewWindowBuilder::new(app, label, Default::default()).build()?)
+}
+
+/// Invokes a command through IPC, as the frontend would.
+///
+/// `command` is the full command name, e.g. `"plugin:tracing|log"`. Synchronous
+/// commands run on the current thread, so their events are visible to
+/// [`with_captured_logs()`].
+///
+/// Returns the command's response, or the error it was rejected with.
+pub fn invoke_command(
+    window: &WebviewWindow<MockRuntime>,
+    command: &str,
+    args: serde_json::Value,
+) -> Result<serde_json::Value, serde_json::Value> {
+    let request = tauri::webview::InvokeRequest {
+        cmd: command.into(),
+        callback: tauri::ipc::CallbackFn(0),
+        error: tauri::ipc::CallbackFn(1),
+        url: if cfg!(any(windows, target_os = "android")) {
+            "http://tauri.localhost"
+        } else {
+            "tauri://localhost"
+        }
+        .parse::<tauri::Url>()
+        .map_err(|e| serde_json::Value::String(e.to_string()))?,
+        body: tauri::ipc::InvokeBody::Json(args),
+        headers: Default::default(),
+        invoke_key: tauri::test::INVOKE_KEY.to_string(),
+    };
+
+    tauri::test::get_ipc_response(window, request).and_then(|body| {
+        body.deserialize::<serde_json::Value>()
+            .map_err(|e| serde_json::Value::String(e.to_string()))
+    })
+}
+
+/// Records forwarded to the frontend, collected by [`listen_webview_records()`]
+/// or [`capturing_webview_layer()`].
+///
+/// Clones share the same storage.
+#[derive(Debug, Clone, Default)]
+pub struct CapturedRecords {
+    records: Arc<Mutex<Vec<RecordPayload>>>,
+}
+
+impl CapturedRecords {
+    /// Returns a copy of the records collected so far.
+    pub fn records(&self) -> Vec<RecordPayload> {
+        self.records
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Removes and returns the records collected so far.
+    pub fn take(&self) -> Vec<RecordPayload> {
+        std::mem::take(&mut *self.records.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Returns the messages of the records collected so far.
+    pub fn messages(&self) -> Vec<String> {
+        self.records().into_iter().map(|r| r.message).collect()
+    }
+
+    fn push(&self, payload: RecordPayload) {
+        self.records
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(payload);
+    }
+}
+
+impl RecordEmitter for CapturedRecords {
+    fn emit_record(&self, payload: RecordPayload) {
+        self.push(payload);
+    }
+}
+
+/// Creates a [`WebviewLayer`] that records payloads instead of emitting them.
+///
+/// # Example
+///
+/// ```
+/// use tauri_plugin_tracing::test::capturing_webview_layer;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let (layer, records) = capturing_webview_layer();
+/// let subscriber = tracing_subscriber::registry().with(layer);
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!("to the frontend");
+/// });
+///
+/// assert_eq!(records.messages(), vec!["to the frontend"]);
+/// ```
+pub fn capturing_webview_layer() -> (WebviewLayer<MockRuntime>, CapturedRecords) {
+    let records = CapturedRecords::default();
+    (WebviewLayer::with_emitter(records.clone()), records)
+}
+
+/// Collects every record a mock app emits on the `tracing://log` channel, or
+/// the `log` channel of its plugin name.
+///
+/// Unlike [`capturing_webview_layer()`], this observes the real emission path,
+/// so it verifies that records are serialized and delivered as the frontend
+/// would receive them.
+pub fn listen_webview_records(app: &App<MockRuntime>) -> CapturedRecords {
+    let records = CapturedRecords::default();
+    let sink = records.clone();
+    app.listen_any(channel(app, "tracing://log"), move |event| {
+        if let Ok(payload) = serde_json::from_str::<RecordPayload>(event.payload()) {
+            sink.push(payload);
+        }
+    });
+    records
+}
@@ -0,0 +1,241 @@
+//! Per-test log capture for end-to-end test suites.
+//!
+//! WebDriver suites run many tests against one app instance, so the app's
+//! log file mixes the logs of every test, and a failing test's report
+//! either has no logs or all of them. With
+//! [`Builder::with_test_mode()`](crate::Builder::with_test_mode), the
+//! `begin_test` and `end_test` commands, or `beginTest()` and `endTest()` in
+//! JavaScript, delimit a test: the events recorded in between are written to
+//! a log file of their own, named after the test, and returned to the caller
+//! to attach to the test's report.
+//!
+//! One test is captured at a time, and only events that pass the
+//! subscriber's filter are captured. The start and end of each test are
+//! logged under the [`TEST_TARGET`] target, so the app's own logs show the
+//! boundaries too.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+use crate::Breadcrumb;
+use crate::breadcrumbs::breadcrumb_from_event;
+
+/// The target used for the start and end of tests.
+pub const TEST_TARGET: &str = "app::test";
+
+/// The most events captured for one test.
+const MAX_RECORDS: usize = 100_000;
+
+/// The logs of a test, as returned by [`TestCapture::end()`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct TestLogs {
+    /// The test's name.
+    pub name: String,
+    /// The file the logs were written to, or `None` if it could not be
+    /// written.
+    pub path: Option<PathBuf>,
+    /// The events recorded during the test, oldest first.
+    pub records: Vec<Breadcrumb>,
+    /// The number of events over the limit of 100 000, which are not
+    /// captured.
+    pub dropped: u64,
+}
+
+/// A tracing layer that captures the events of the running test.
+///
+/// Clones share the same capture. The plugin manages an instance in Tauri
+/// state when [`Builder::with_test_mode()`](crate::Builder::with_test_mode)
+/// is used with the default subscriber.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::TestCapture;
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// let capture = TestCapture::new("target/e2e-logs");
+/// Registry::default().with(capture.clone()).init();
+///
+/// capture.begin("checkout applies coupon");
+/// tracing::info!("coupon applied");
+/// let logs = capture.end("checkout applies coupon")?;
+/// # Ok::<(), tauri_plugin_tracing::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestCapture {
+    running: Arc<Mutex<Option<RunningTest>>>,
+    dir: PathBuf,
+}
+
+#[derive(Debug)]
+struct RunningTest {
+    name: String,
+    records: Vec<Breadcrumb>,
+    dropped: u64,
+}
+
+impl TestCapture {
+    /// Creates a capture that writes the logs of each test to `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            running: Arc::default(),
+            dir: dir.into(),
+        }
+    }
+
+    /// Returns the directory test logs are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Starts capturing the events of the test `name`.
+    ///
+    /// A test that has not ended yet is discarded with a warning.
+    pub fn begin(&self, name: &str) {
+        let previous = self.lock().replace(RunningTest {
+            name: name.to_string(),
+            records: Vec::new(),
+            dropped: 0,
+        });
+        if let Some(previous) = previous {
+            tracing::warn!(
+                target: TEST_TARGET,
+                test = previous.name,
+                "test {} did not end before {name} began; its logs are discarded",
+                previous.name
+            );
+        }
+        tracing::info!(target: TEST_TARGET, test = name, "test started: {name}");
+    }
+
+    /// Stops capturing the events of the test `name`, writes them to
+    /// `{name}.log` in the capture's directory, and returns them.
+    ///
+    /// Characters other than ASCII letters, digits, `-`, `_`, and `.` are
+    /// replaced with `_` in the file name. Returns
+    /// [`Error::UnknownTest`](crate::Error::UnknownTest) if `name` is not the
+    /// running test.
+    pub fn end(&self, name: &str) -> crate::Result<TestLogs> {
+        if !self.is_running(name) {
+            return Err(crate::Error::UnknownTest(name.to_string()));
+        }
+        tracing::info!(target: TEST_TARGET, test = name, "test ended: {name}");
+        let test = self
+            .lock()
+            .take_if(|test| test.name == name)
+            .ok_or_else(|| crate::Error::UnknownTest(name.to_string()))?;
+
+        let path = self.dir.join(format!("{}.log", file_name(name)));
+        let path = match write_records(&path, &test.records) {
+            Ok(()) => Some(path),
+            Err(e) => {
+                tracing::warn!(
+                    target: TEST_TARGET,
+                    test = name,
+                    "Failed to write the logs of {name} to {}: {e}",
+                    path.display()
+                );
+                None
+            }
+        };
+        Ok(TestLogs {
+            name: test.name,
+            path,
+            records: test.records,
+            dropped: test.dropped,
+        })
+    }
+
+    /// Returns whether `name` is the running test.
+    pub fn is_running(&self, name: &str) -> bool {
+        self.lock().as_ref().is_some_and(|test| test.name == name)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<RunningTest>> {
+        self.running.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<S: Subscriber> Layer<S> for TestCapture {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut running = self.lock();
+        let Some(test) = running.as_mut() else {
+            return;
+        };
+        if test.records.len() >= MAX_RECORDS {
+            test.dropped += 1;
+            return;
+        }
+        test.records.push(breadcrumb_from_event(event));
+    }
+}
+
+/// Returns `name` with the characters that are not safe in file names
+/// replaced.
+fn file_name(name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .take(100)
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match safe.trim_start_matches('.') {
+        "" => "test".to_string(),
+        safe => safe.to_string(),
+    }
+}
+
+/// Writes `records` to `path` as lines of timestamp, level, target, and
+/// message.
+fn write_records(path: &Path, records: &[Breadcrumb]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    for record in records {
+        let nanos = (record.timestamp_ms * 1000.0).round() as i128 * 1000;
+        let timestamp = time::OffsetDateTime::from_unix_timestamp_nanos(nanos)
+            .ok()
+            .and_then(|at| {
+                at.format(&time::format_description::well_known::Rfc3339)
+                    .ok()
+            })
+            .unwrap_or_default();
+        let level = tracing::Level::from(record.level.clone());
+        writeln!(
+            file,
+            "{timestamp} {level:>5} {}: {}",
+            record.target, record.message
+        )?;
+    }
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn makes_test_names_safe_file_names() {
+        assert_eq!(
+            file_name("checkout applies coupon"),
+            "checkout_applies_coupon"
+        );
+        assert_eq!(file_name("../etc/passwd"), "_etc_passwd");
+        assert_eq!(file_name("login::ok-1.2"), "login__ok-1.2");
+        assert_eq!(file_name(""), "test");
+        assert_eq!(file_name(&"x".repeat(200)).len(), 100);
+    }
+}
@@ -0,0 +1,483 @@
+//! A live tail of the logs in the terminal.
+//!
+//! Requires the `tui` feature. [`TuiTail`] renders the events kept by a
+//! [`LogHistory`] full-screen in the terminal the app was started from, so
+//! developers can follow the logs without opening the devtools:
+//!
+//! - `t`, `d`, `i`, `w`, and `e` show or hide the `TRACE`, `DEBUG`, `INFO`,
+//!   `WARN`, and `ERROR` levels.
+//! - `/` searches targets, `Enter` keeps the search, and `Esc` clears it.
+//! - `Space` pauses and resumes the tail.
+//! - `q` or `Ctrl+C` quits and restores the terminal.
+//!
+//! While the tail runs, the plugin's stdout and stderr targets are muted so
+//! their output does not draw over it. Only supported on Unix platforms.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+
+use tauri::{Manager, Runtime};
+
+use crate::{Breadcrumb, LogHistory, LogLevel};
+
+/// Whether a tail currently owns the terminal.
+static TAILING: AtomicBool = AtomicBool::new(false);
+
+/// Returns whether a [`TuiTail`] currently owns the terminal.
+///
+/// Layers that write to the terminal should skip events while it does.
+pub fn is_tailing() -> bool {
+    TAILING.load(Ordering::Relaxed)
+}
+
+/// A running terminal tail, see the [module documentation](self).
+///
+/// Dropping the handle leaves the tail running until the user quits it.
+///
+/// # Example
+///
+/// Use [`TuiTailExt::spawn_tui_tail()`] with the default subscriber, or pass
+/// the [`LogHistory`] of a custom subscriber:
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing::{LogHistory, TuiTail};
+/// # use tracing_subscriber::{Registry, layer::SubscriberExt, util::SubscriberInitExt};
+/// let history = LogHistory::new(10_000);
+/// Registry::default().with(history.clone()).init();
+///
+/// let tail = TuiTail::spawn(history)?;
+/// # Ok::<(), tauri_plugin_tracing::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TuiTail {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl TuiTail {
+    /// Takes over the terminal and tails `history` on a background thread.
+    ///
+    /// Fails if the process has no controlling terminal or a tail is already
+    /// running. Returns [`Error::NotImplemented`](crate::Error::NotImplemented)
+    /// on platforms without Unix terminals.
+    #[cfg(unix)]
+    pub fn spawn(history: LogHistory) -> crate::Result<Self> {
+        if TAILING
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err(std::io::Error::other("a terminal tail is already running").into());
+        }
+        let terminal = match unix::Terminal::open() {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                TAILING.store(false, Ordering::SeqCst);
+                return Err(e.into());
+            }
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = std::thread::Builder::new()
+            .name("tracing-tui-tail".to_string())
+            .spawn(move || {
+                unix::run(terminal, history, &thread_stop);
+                TAILING.store(false, Ordering::SeqCst);
+            });
+        match thread {
+            Ok(thread) => Ok(Self { stop, thread }),
+            Err(e) => {
+                TAILING.store(false, Ordering::SeqCst);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Takes over the terminal and tails `history` on a background thread.
+    ///
+    /// Returns [`Error::NotImplemented`](crate::Error::NotImplemented) on
+    /// platforms without Unix terminals.
+    #[cfg(not(unix))]
+    pub fn spawn(_history: LogHistory) -> crate::Result<Self> {
+        Err(crate::Error::NotImplemented)
+    }
+
+    /// Returns whether the user has quit the tail.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Quits the tail and waits until the terminal is restored.
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = self.thread.join();
+    }
+}
+
+/// Extension trait for tailing the logs in the terminal.
+///
+/// Implemented for anything that implements [`Manager`] (e.g. [`AppHandle`](tauri::AppHandle),
+/// [`App`](tauri::App), [`WebviewWindow`](tauri::WebviewWindow)).
+pub trait TuiTailExt<R: Runtime> {
+    /// Tails the in-memory history kept with
+    /// [`Builder::with_log_history()`](crate::Builder::with_log_history) in
+    /// the terminal.
+    ///
+    /// Returns [`Error::LogHistoryDisabled`](crate::Error::LogHistoryDisabled)
+    /// without a history.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tauri::Manager;
+    /// # use tauri_plugin_tracing::{Builder, TuiTailExt};
+    /// tauri::Builder::default()
+    ///     .plugin(
+    ///         Builder::new()
+    ///             .with_log_history(10_000)
+    ///             .with_default_subscriber()
+    ///             .build(),
+    ///     )
+    ///     .setup(|app| {
+    ///         #[cfg(debug_assertions)]
+    ///         app.spawn_tui_tail()?;
+    ///         Ok(())
+    ///     });
+    /// ```
+    fn spawn_tui_tail(&self) -> crate::Result<TuiTail>;
+}
+
+impl<R: Runtime, T: Manager<R>> TuiTailExt<R> for T {
+    fn spawn_tui_tail(&self) -> crate::Result<TuiTail> {
+        let history = self
+            .try_state::<LogHistory>()
+            .ok_or(crate::Error::LogHistoryDisabled)?;
+        TuiTail::spawn(history.inner().clone())
+    }
+}
+
+/// The levels from `TRACE` to `ERROR`, with their key and ANSI color.
+const LEVELS: [(&str, u8, &str); 5] = [
+    ("TRACE", b't', "35"),
+    ("DEBUG", b'd', "34"),
+    (" INFO", b'i', "32"),
+    (" WARN", b'w', "33"),
+    ("ERROR", b'e', "31"),
+];
+
+fn level_index(level: &LogLevel) -> usize {
+    match level {
+        LogLevel::Trace => 0,
+        LogLevel::Debug => 1,
+        LogLevel::Info => 2,
+        LogLevel::Warn => 3,
+        LogLevel::Error => 4,
+    }
+}
+
+/// What the tail shows.
+#[derive(Debug, Clone)]
+struct View {
+    levels: [bool; 5],
+    search: String,
+    searching: bool,
+    paused: Option<Vec<Breadcrumb>>,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            levels: [true; 5],
+            search: String::new(),
+            searching: false,
+            paused: None,
+        }
+    }
+}
+
+impl View {
+    /// Applies a key press, returning `false` to quit.
+    fn key(&mut self, key: u8, history: &LogHistory) -> bool {
+        // Ctrl+C
+        if key == 0x03 {
+            return false;
+        }
+        if self.searching {
+            match key {
+                b'\r' | b'\n' => self.searching = false,
+                0x1b => {
+                    self.searching = false;
+                    self.search.clear();
+                }
+                0x7f | 0x08 => {
+                    self.search.pop();
+                }
+                key if key.is_ascii_graphic() => self.search.push(key as char),
+                _ => {}
+            }
+            return true;
+        }
+        match key {
+            b'q' => return false,
+            b' ' => {
+                self.paused = match self.paused {
+                    Some(_) => None,
+                    None => Some(history.records()),
+                }
+            }
+            b'/' => self.searching = true,
+            0x1b => self.search.clear(),
+            key => {
+                if let Some(index) = LEVELS.iter().position(|(_, k, _)| *k == key) {
+                    self.levels[index] = !self.levels[index];
+                }
+            }
+        }
+        true
+    }
+
+    fn shows(&self, record: &Breadcrumb) -> bool {
+        self.levels[level_index(&record.level)] && record.target.contains(self.search.as_str())
+    }
+
+    /// Renders a frame of `width` columns and `height` rows.
+    fn render(&self, records: &[Breadcrumb], width: usize, height: usize) -> String {
+        let rows = height.saturating_sub(2);
+        let records = self.paused.as_deref().unwrap_or(records);
+        let shown: Vec<&Breadcrumb> = records.iter().filter(|r| self.shows(r)).collect();
+
+        let mut frame = String::from("\x1b[H");
+        let mut header = String::from("\x1b[7m tail ");
+        for ((name, _, color), shown) in LEVELS.iter().zip(self.levels) {
+            if shown {
+                let _ = write!(header, " \x1b[{color}m{}\x1b[39m", name.trim_start());
+            } else {
+                let _ = write!(header, " \x1b[2m{}\x1b[22m", name.trim_start());
+            }
+        }
+        if self.searching || !self.search.is_empty() {
+            let _ = write!(header, "  /{}", self.search);
+            if self.searching {
+                header.push('_');
+            }
+        }
+        if self.paused.is_some() {
+            header.push_str("  PAUSED");
+        }
+        let _ = write!(header, "  {}/{} events", shown.len(), records.len());
+        frame.push_str(&header);
+        frame.push_str("\x1b[K\x1b[0m\r\n");
+
+        let skip = shown.len().saturating_sub(rows);
+        for record in &shown[skip..] {
+            frame.push_str(&format_record(record, width));
+            frame.push_str("\x1b[K\r\n");
+        }
+        for _ in shown.len().min(rows)..rows {
+            frame.push_str("\x1b[K\r\n");
+        }
+        frame.push_str(
+            "\x1b[2mq quit  space pause  t/d/i/w/e levels  / search  esc clear\x1b[0m\x1b[K",
+        );
+        frame
+    }
+}
+
+/// Formats a record as a line of at most `width` columns.
+fn format_record(record: &Breadcrumb, width: usize) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(record.timestamp_ms as i64)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%H:%M:%S%.3f")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let (level, _, color) = LEVELS[level_index(&record.level)];
+    let text: String = format!("{time} {level} {}: {}", record.target, record.message)
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(width)
+        .collect();
+    // Color the level, which starts after the timestamp
+    match text
+        .char_indices()
+        .nth(time.chars().count() + 1 + level.len())
+    {
+        Some((end, _)) => {
+            let start = time.len() + 1;
+            format!(
+                "\x1b[2m{}\x1b[22m \x1b[{color}m{}\x1b[39m{}",
+                &text[..time.len()],
+                &text[start..end],
+                &text[end..]
+            )
+        }
+        None => text,
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::os::fd::AsRawFd;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::View;
+    use crate::LogHistory;
+
+    /// The controlling terminal in raw mode, restored when dropped.
+    pub(super) struct Terminal {
+        tty: File,
+        saved: libc::termios,
+    }
+
+    impl Terminal {
+        /// Opens the controlling terminal and switches it to raw mode on the
+        /// alternate screen.
+        pub(super) fn open() -> std::io::Result<Self> {
+            let mut tty = File::options().read(true).write(true).open("/dev/tty")?;
+            let fd = tty.as_raw_fd();
+            // SAFETY: termios is plain data, initialized by tcgetattr below
+            let mut saved: libc::termios = unsafe { std::mem::zeroed() };
+            // SAFETY: fd is open and saved is a valid termios
+            if unsafe { libc::tcgetattr(fd, &mut saved) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let mut raw = saved;
+            // SAFETY: raw is a valid termios
+            unsafe { libc::cfmakeraw(&mut raw) };
+            // Wake up every 100ms to pick up new events
+            raw.c_cc[libc::VMIN] = 0;
+            raw.c_cc[libc::VTIME] = 1;
+            // SAFETY: fd is open and raw is a valid termios
+            if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            tty.write_all(b"\x1b[?1049h\x1b[?25l")?;
+            Ok(Self { tty, saved })
+        }
+
+        /// Returns the terminal's width and height.
+        fn size(&self) -> (usize, usize) {
+            // SAFETY: winsize is plain data
+            let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+            // SAFETY: the descriptor is open and size is a valid winsize
+            if unsafe { libc::ioctl(self.tty.as_raw_fd(), libc::TIOCGWINSZ, &mut size) } != 0
+                || size.ws_col == 0
+            {
+                return (80, 24);
+            }
+            (size.ws_col as usize, size.ws_row as usize)
+        }
+    }
+
+    impl Drop for Terminal {
+        fn drop(&mut self) {
+            let _ = self.tty.write_all(b"\x1b[?25h\x1b[?1049l");
+            // SAFETY: the descriptor is open and saved came from tcgetattr
+            unsafe { libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSANOW, &self.saved) };
+        }
+    }
+
+    pub(super) fn run(mut terminal: Terminal, history: LogHistory, stop: &AtomicBool) {
+        let mut view = View::default();
+        let mut keys = [0u8; 16];
+        while !stop.load(Ordering::Relaxed) {
+            let (width, height) = terminal.size();
+            let frame = view.render(&history.records(), width, height);
+            if terminal.tty.write_all(frame.as_bytes()).is_err() {
+                return;
+            }
+            // Blocks for up to 100ms
+            let read = match terminal.tty.read(&mut keys) {
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => 0,
+                Err(_) => return,
+            };
+            for &key in &keys[..read] {
+                if !view.key(key, &history) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn record(level: LogLevel, target: &str, message: &str) -> Breadcrumb {
+        Breadcrumb {
+            timestamp_ms: 0.0,
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn keys_toggle_levels_and_search_targets() {
+        let history = LogHistory::new(10);
+        let mut view = View::default();
+        let info = record(LogLevel::Info, "app::sync", "synced");
+        let debug = record(LogLevel::Debug, "hyper::client", "connected");
+        assert!(view.shows(&info) && view.shows(&debug));
+
+        assert!(view.key(b'd', &history));
+        assert!(!view.shows(&debug));
+        assert!(view.key(b'd', &history));
+
+        for key in b"/hyper\r" {
+            assert!(view.key(*key, &history));
+        }
+        assert!(!view.searching);
+        assert!(!view.shows(&info) && view.shows(&debug));
+        // Keys are not level toggles while searching
+        assert!(view.key(b'/', &history));
+        assert!(view.key(b'd', &history));
+        assert!(view.key(0x1b, &history));
+        assert!(view.levels[1]);
+        assert!(view.search.is_empty() && view.shows(&info));
+
+        assert!(!view.key(b'q', &history));
+    }
+
+    #[test]
+    fn pause_freezes_the_records() {
+        let history = LogHistory::new(10);
+        let mut view = View::default();
+        assert!(view.key(b' ', &history));
+        let records = [record(LogLevel::Info, "app", "new")];
+        let frame = view.render(&records, 80, 5);
+        assert!(frame.contains("PAUSED") && !frame.contains("new"));
+        assert!(view.key(b' ', &history));
+        assert!(view.render(&records, 80, 5).contains("app: new"));
+    }
+
+    #[test]
+    fn frames_keep_the_newest_records_that_fit() {
+        let view = View::default();
+        let records: Vec<Breadcrumb> = (0..10)
+            .map(|i| record(LogLevel::Warn, "app", &format!("event {i}")))
+            .collect();
+        let frame = view.render(&records, 80, 5);
+        assert_eq!(frame.matches("\r\n").count(), 4);
+        assert!(!frame.contains("event 6") && frame.contains("event 7"));
+        assert!(frame.contains("event 9"));
+    }
+
+    #[test]
+    fn records_are_truncated_to_the_width() {
+        let line = format_record(&record(LogLevel::Error, "app", "a\nvery long message"), 30);
+        let mut plain = Vec::new();
+        crate::strip_ansi::strip_ansi_and_write(&mut plain, line.as_bytes()).unwrap();
+        let plain = String::from_utf8(plain).unwrap();
+        assert_eq!(plain.chars().count(), 30);
+        assert!(plain.ends_with("app: a very"));
+        assert!(line.contains("\x1b[31mERROR\x1b[39m"));
+    }
+}
@@ -40,10 +40,13 @@ pub enum Target {
     /// Print logs to stderr.
     Stderr,
 
-    /// Forward logs to the webview via the `tracing://log` event.
+    /// Forward logs to the webview via the `tracing://log` event, or the
+    /// `log` event of the name set with
+    /// [`Builder::with_plugin_name()`](crate::Builder::with_plugin_name).
     ///
     /// This allows JavaScript code to receive logs using `attachLogger()`
-    /// or `attachConsole()`.
+    /// or `attachConsole()`. Ignored by the default subscriber without the
+    /// `webview` feature.
     Webview,
 
     /// Write logs to the platform-standard log directory.
@@ -54,7 +57,8 @@ pub enum Target {
     /// - **Windows**: `%LOCALAPPDATA%/{bundle_identifier}/logs`
     ///
     /// The `file_name` parameter sets the log file prefix. Defaults to `"app"`
-    /// if `None`, producing files like `app.2024-01-15.log`.
+    /// if `None`, producing files like `app.2024-01-15.log`, or to the plugin
+    /// name set with [`Builder::with_plugin_name()`](crate::Builder::with_plugin_name).
     LogDir {
         /// The log file prefix. Defaults to `"app"` if `None`.
         file_name: Option<String>,
@@ -63,13 +67,85 @@ pub enum Target {
     /// Write logs to a custom directory.
     ///
     /// The `file_name` parameter sets the log file prefix. Defaults to `"app"`
-    /// if `None`, producing files like `app.2024-01-15.log`.
+    /// if `None`, producing files like `app.2024-01-15.log`, or to the plugin
+    /// name set with [`Builder::with_plugin_name()`](crate::Builder::with_plugin_name).
     Folder {
         /// The directory path to write log files to.
         path: PathBuf,
         /// The log file prefix. Defaults to `"app"` if `None`.
         file_name: Option<String>,
     },
+
+    /// Send logs to Apple's unified logging system, viewable in Console.app.
+    ///
+    /// The `subsystem` defaults to the app's bundle identifier. Events are
+    /// logged under a category derived from their target, see
+    /// [`OsLogLayer`](crate::OsLogLayer) and
+    /// [`Builder::with_os_log_category()`](crate::Builder::with_os_log_category).
+    ///
+    /// Only used on macOS and iOS and ignored on other platforms, so it can
+    /// be configured unconditionally. Only the first `OsLog` target is used.
+    OsLog {
+        /// The subsystem to log under. Defaults to the bundle identifier if
+        /// `None`.
+        subsystem: Option<String>,
+    },
+
+    /// Write length-prefixed JSON records to a pipe inherited from the parent
+    /// process, for apps run headless by orchestration tooling.
+    ///
+    /// Only the first pipe target is used. See [`PipeLayer`](crate::PipeLayer)
+    /// for the format. Don't combine a pipe to stdout with
+    /// [`Target::Stdout`], whose lines would corrupt the frames.
+    Pipe {
+        /// The inherited file descriptor (Unix) or `HANDLE` (Windows) to write
+        /// to. Defaults to stdout if `None`.
+        handle: Option<u64>,
+    },
+
+    /// Send logs to a Graylog GELF input.
+    ///
+    /// Only available when the `gelf` feature is enabled. Only the first GELF
+    /// target is used. See [`GelfLayer`](crate::GelfLayer).
+    #[cfg(feature = "gelf")]
+    Gelf {
+        /// The collector's hostname or IP address.
+        host: String,
+        /// The collector's port, typically 12201.
+        port: u16,
+        /// Whether to send over UDP, TCP, or TLS.
+        transport: crate::GelfTransport,
+    },
+
+    /// Push logs to a Grafana Loki server.
+    ///
+    /// Only available when the `loki` feature is enabled. Only the first Loki
+    /// target is used. Streams are labeled with `app` and `version` from the
+    /// app's package info unless `labels` sets them. See
+    /// [`LokiLayer`](crate::LokiLayer).
+    #[cfg(feature = "loki")]
+    Loki {
+        /// The server's push endpoint, e.g.
+        /// `https://loki.example.com/loki/api/v1/push`.
+        url: String,
+        /// Labels added to every stream.
+        labels: Vec<(String, String)>,
+    },
+
+    /// Export logs to an OpenTelemetry collector or backend over OTLP/HTTP.
+    ///
+    /// Only available when the `otlp` feature is enabled. Only the first OTLP
+    /// target is used. Records describe the app with `service.name`,
+    /// `service.version`, and `os.type` resource attributes, see
+    /// [`Builder::with_otlp_resource_attribute()`](crate::Builder::with_otlp_resource_attribute)
+    /// and [`OtlpLayer`](crate::OtlpLayer).
+    #[cfg(feature = "otlp")]
+    Otlp {
+        /// The logs endpoint, e.g. `https://otel.example.com:4318/v1/logs`.
+        url: String,
+        /// Headers sent with every request, e.g. for authentication.
+        headers: Vec<(String, String)>,
+    },
 }
 
 /// Time-based rotation period for log files.
@@ -124,6 +200,104 @@ pub enum RotationStrategy {
     KeepSome(u32),
 }
 
+/// How often log files are flushed to disk.
+///
+/// Written lines reach the operating system right away, so they survive the
+/// app crashing, but not the machine losing power or the OS crashing until
+/// they are synced to disk. Syncing more often makes it likelier that the
+/// last records before a crash survive, at the cost of throughput.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_plugin_tracing::{Builder, SyncPolicy};
+///
+/// Builder::new()
+///     .with_file_logging()
+///     .with_sync_policy(SyncPolicy::EveryN(100))
+///     .build::<tauri::Wry>();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncPolicy {
+    /// Leave syncing to the operating system.
+    #[default]
+    Never,
+    /// Sync after every `n` records, and when the app exits. `EveryN(1)`
+    /// syncs every record.
+    EveryN(u32),
+    /// Sync within the interval of a record being written, from a background
+    /// thread, and when the app exits.
+    Interval(std::time::Duration),
+}
+
+/// How file logging behaves when several instances of the app run at once.
+///
+/// Instances that write to the same file can interleave and corrupt each
+/// other's lines.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_plugin_tracing::{Builder, InstanceStrategy};
+///
+/// Builder::new()
+///     .with_file_logging()
+///     .with_instance_strategy(InstanceStrategy::Lock)
+///     .build::<tauri::Wry>();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InstanceStrategy {
+    /// All instances write to the same file.
+    #[default]
+    Shared,
+    /// Each instance writes to its own file, named with its process ID, e.g.
+    /// `app-4242.2024-01-15.log`.
+    ProcessId,
+    /// The first instance takes an advisory lock on `app.lock` in the log
+    /// directory and writes to the usual file. Instances started while the
+    /// lock is held, or on file systems without locking, fall back to
+    /// [`ProcessId`](Self::ProcessId) naming.
+    Lock,
+    /// Like [`Lock`](Self::Lock), but instead of writing their own files,
+    /// instances started while the lock is held send their lines to the
+    /// instance holding it, over a loopback TCP connection. This keeps one
+    /// coherent log file per machine.
+    ///
+    /// If no instance is accepting lines, e.g. because the one holding the
+    /// lock is still starting, falls back to [`ProcessId`](Self::ProcessId)
+    /// naming.
+    Forward,
+}
+
+/// How log files of different app versions are kept apart.
+///
+/// Separating them makes it easy to tell logs from before and after an
+/// update apart. The version is the app's package version.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_plugin_tracing::{Builder, VersionLayout};
+///
+/// Builder::new()
+///     .with_file_logging()
+///     .with_version_layout(VersionLayout::Directory)
+///     .build::<tauri::Wry>();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionLayout {
+    /// Files of all versions are written to the log directory under the same
+    /// name.
+    #[default]
+    Flat,
+    /// Files are written to a directory per version, e.g.
+    /// `logs/1.4.2/app.2024-01-15.log`.
+    Directory,
+    /// The version is added to the file name, e.g.
+    /// `app-1.4.2.2024-01-15.log`.
+    FileName,
+}
+
 /// Log output format style.
 ///
 /// Controls the overall structure and verbosity of log output.
@@ -156,6 +330,15 @@ pub enum LogFormat {
     /// Includes colorful formatting, indentation, and verbose span information.
     /// Best suited for local development and debugging.
     Pretty,
+
+    /// RFC 5424 syslog lines with structured data.
+    ///
+    /// Output: `<14>1 2024-01-15T10:30:00.000000Z host my-app 4242 my_app - message`
+    ///
+    /// Uses the app's product name as the APP-NAME and the event fields as
+    /// structured data. The file, line number, thread, target, and level
+    /// display options do not apply. See [`Rfc5424Format`](crate::Rfc5424Format).
+    Rfc5424,
 }
 
 /// Configuration options for log output formatting.
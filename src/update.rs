@@ -0,0 +1,144 @@
+//! Logging of the app updater's lifecycle.
+//!
+//! Failed auto-updates in the field are hard to diagnose when nothing about
+//! them was logged. [`UpdateLog`] records each step of an update, from the
+//! check to the install, as events under the [`UPDATE_TARGET`] target, and is
+//! meant to be called from the callbacks of `tauri-plugin-updater`:
+//!
+//! ```rust,ignore
+//! use tauri_plugin_tracing::UpdateLog;
+//! use tauri_plugin_updater::UpdaterExt;
+//!
+//! let log = UpdateLog::new();
+//! log.checking(&app.package_info().version.to_string());
+//! match app.updater()?.check().await {
+//!     Ok(Some(update)) => {
+//!         log.available(&update.version);
+//!         let result = update
+//!             .download_and_install(
+//!                 |chunk_length, content_length| log.progress(chunk_length, content_length),
+//!                 || log.downloaded(),
+//!             )
+//!             .await;
+//!         match result {
+//!             Ok(()) => log.installed(),
+//!             Err(e) => log.failed(&e),
+//!         }
+//!     }
+//!     Ok(None) => log.up_to_date(),
+//!     Err(e) => log.failed(&e),
+//! }
+//! ```
+//!
+//! Progress is logged at `DEBUG` every 10% of the download, failures at
+//! `ERROR`, and every other step at `INFO`. With
+//! [`Builder::with_update_log_file()`](crate::Builder::with_update_log_file)
+//! the events are also written to a file of their own, regardless of the log
+//! level.
+
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// The target used for updater events.
+pub const UPDATE_TARGET: &str = "app::update";
+
+/// Logs the steps of an app update.
+///
+/// Methods take `&self`, so the same instance can be borrowed by the
+/// download progress and completion callbacks.
+#[derive(Debug, Default)]
+pub struct UpdateLog {
+    version: Mutex<Option<String>>,
+    download_started: Mutex<Option<Instant>>,
+    downloaded: AtomicU64,
+    logged_tenths: AtomicU64,
+}
+
+impl UpdateLog {
+    /// Creates a log for one update.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Logs that the app is checking for an update from `current_version`.
+    pub fn checking(&self, current_version: &str) {
+        tracing::info!(target: UPDATE_TARGET, current_version, "checking for updates");
+    }
+
+    /// Logs that no update is available.
+    pub fn up_to_date(&self) {
+        tracing::info!(target: UPDATE_TARGET, "no update available");
+    }
+
+    /// Logs that `version` is available.
+    pub fn available(&self, version: &str) {
+        *self.version.lock().unwrap_or_else(|e| e.into_inner()) = Some(version.to_string());
+        tracing::info!(target: UPDATE_TARGET, version, "update {version} available");
+    }
+
+    /// Logs the progress of the download after each chunk.
+    ///
+    /// The arguments match the chunk callback of `download_and_install()`.
+    /// `DEBUG` events are logged at most every 10% of `content_length`, and
+    /// only the start of downloads of unknown length.
+    pub fn progress(&self, chunk_length: usize, content_length: Option<u64>) {
+        let chunk_length = chunk_length as u64;
+        let started = *self
+            .download_started
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(Instant::now);
+        let downloaded = self.downloaded.fetch_add(chunk_length, Ordering::Relaxed) + chunk_length;
+        let percent = content_length
+            .filter(|total| *total > 0)
+            .map(|total| (downloaded * 100 / total).min(100));
+        let tenths = percent.unwrap_or_default() / 10;
+        let first_chunk = downloaded == chunk_length;
+        if self.logged_tenths.fetch_max(tenths, Ordering::Relaxed) < tenths || first_chunk {
+            tracing::debug!(
+                target: UPDATE_TARGET,
+                downloaded,
+                content_length,
+                percent,
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "downloading update"
+            );
+        }
+    }
+
+    /// Logs that the download finished.
+    pub fn downloaded(&self) {
+        let elapsed_ms = self
+            .download_started
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .map(|started| started.elapsed().as_millis() as u64);
+        tracing::info!(
+            target: UPDATE_TARGET,
+            bytes = self.downloaded.load(Ordering::Relaxed),
+            elapsed_ms,
+            "update downloaded"
+        );
+    }
+
+    /// Logs that the update was installed.
+    pub fn installed(&self) {
+        let version = self.version();
+        tracing::info!(target: UPDATE_TARGET, version, "update installed");
+    }
+
+    /// Logs that a step of the update failed with `error`.
+    pub fn failed(&self, error: &dyn Display) {
+        let version = self.version();
+        tracing::error!(target: UPDATE_TARGET, version, "update failed: {error}");
+    }
+
+    fn version(&self) -> Option<String> {
+        self.version
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
@@ -0,0 +1,317 @@
+//! Validation of the builder's configuration.
+//!
+//! Some combinations of options are silently ignored, like a maximum file
+//! size without a file target, or fail only once the app runs, like a log
+//! folder that cannot be written to. At startup, the plugin logs a `WARN`
+//! event under the [`CONFIG_TARGET`] target for each [`ConfigIssue`] it finds.
+//! Apps that want to fail fast instead can call
+//! [`Builder::validate()`](crate::Builder::validate) before building the
+//! plugin.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::event_ids::conflicting_ids;
+use crate::file_name::FileNameTemplate;
+use crate::filter::Output;
+use crate::{Builder, InstanceStrategy, RotationStrategy, SyncPolicy, Target, VersionLayout};
+
+/// The target used for configuration warnings.
+pub const CONFIG_TARGET: &str = "tauri_plugin_tracing::config";
+
+/// A problem with a [`Builder`]'s configuration.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ConfigIssue {
+    /// A file logging option is set, but no file target is configured. Holds
+    /// the name of the builder method.
+    FileOptionWithoutFileTarget(&'static str),
+    /// [`RotationStrategy::KeepSome(0)`](RotationStrategy::KeepSome) deletes
+    /// every log file. This is an error.
+    KeepNoFiles,
+    /// A target is configured after one writing to the same output, and is
+    /// ignored.
+    IgnoredTarget(Target),
+    /// [`Builder::filter_for()`] is set for a target that is not configured.
+    FilterForMissingTarget(Target),
+    /// [`Target::Pipe`] writes to stdout, where [`Target::Stdout`] lines
+    /// corrupt its frames.
+    PipeSharesStdout,
+    /// [`Builder::with_stderr_threshold()`] is set without a
+    /// [`Target::Stdout`] to split.
+    StderrThresholdWithoutStdout,
+    /// An id of [`Builder::with_event_catalog()`] is registered for
+    /// different events. Holds the id.
+    ConflictingEventId(&'static str),
+    /// The token of
+    /// [`Builder::with_diagnostics_endpoint()`](crate::Builder::with_diagnostics_endpoint)
+    /// is empty, so the endpoint is not started. This is an error.
+    #[cfg(feature = "diagnostics-endpoint")]
+    EmptyEndpointToken,
+    /// The directory of a [`Target::Folder`] cannot be created or written
+    /// to. This is an error.
+    UnwritableFolder {
+        /// The configured directory.
+        path: PathBuf,
+        /// Why it cannot be written to.
+        reason: String,
+    },
+    /// The template of [`Builder::with_file_name_template()`] cannot be
+    /// used, so files get the default names. This is an error.
+    InvalidFileNameTemplate {
+        /// The configured template.
+        template: String,
+        /// Why it cannot be used.
+        reason: String,
+    },
+}
+
+impl ConfigIssue {
+    /// Returns whether the issue prevents logging from working as configured,
+    /// rather than an option being ignored.
+    pub fn is_error(&self) -> bool {
+        #[cfg(feature = "diagnostics-endpoint")]
+        if matches!(self, Self::EmptyEndpointToken) {
+            return true;
+        }
+        matches!(
+            self,
+            Self::KeepNoFiles
+                | Self::UnwritableFolder { .. }
+                | Self::InvalidFileNameTemplate { .. }
+        )
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileOptionWithoutFileTarget(method) => {
+                write!(f, "{method}() has no effect without a file target")
+            }
+            Self::KeepNoFiles => write!(f, "RotationStrategy::KeepSome(0) deletes every log file"),
+            Self::IgnoredTarget(target) => {
+                write!(
+                    f,
+                    "{target:?} is ignored, as only the first target of its kind is used"
+                )
+            }
+            Self::FilterForMissingTarget(target) => {
+                write!(
+                    f,
+                    "filter_for() is set for {target:?}, which is not a target"
+                )
+            }
+            Self::PipeSharesStdout => write!(
+                f,
+                "Target::Pipe writes to stdout, where Target::Stdout lines corrupt its records"
+            ),
+            Self::StderrThresholdWithoutStdout => write!(
+                f,
+                "with_stderr_threshold() has no effect without Target::Stdout"
+            ),
+            Self::ConflictingEventId(id) => {
+                write!(f, "event id {id} is registered for different events")
+            }
+            #[cfg(feature = "diagnostics-endpoint")]
+            Self::EmptyEndpointToken => write!(
+                f,
+                "with_diagnostics_endpoint() needs a token, so the endpoint is not started"
+            ),
+            Self::UnwritableFolder { path, reason } => {
+                write!(
+                    f,
+                    "log folder {} cannot be written to: {reason}",
+                    path.display()
+                )
+            }
+            Self::InvalidFileNameTemplate { template, reason } => {
+                write!(
+                    f,
+                    "file name template {template:?} cannot be used: {reason}"
+                )
+            }
+        }
+    }
+}
+
+/// A configuration without errors, as returned by
+/// [`Builder::validate()`](crate::Builder::validate).
+#[derive(Debug, Clone)]
+pub struct ValidatedConfig {
+    warnings: Vec<ConfigIssue>,
+}
+
+impl ValidatedConfig {
+    /// Returns the issues that are not errors, such as ignored options.
+    pub fn warnings(&self) -> &[ConfigIssue] {
+        &self.warnings
+    }
+}
+
+/// A configuration with at least one error, as returned by
+/// [`Builder::validate()`](crate::Builder::validate).
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    issues: Vec<ConfigIssue>,
+}
+
+impl ConfigError {
+    /// Returns all issues, errors and warnings.
+    pub fn issues(&self) -> &[ConfigIssue] {
+        &self.issues
+    }
+
+    /// Returns the issues that are errors.
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.is_error())
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid tracing configuration")?;
+        for (n, issue) in self.errors().enumerate() {
+            write!(f, "{} {issue}", if n == 0 { ":" } else { ";" })?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Sorts `issues` into a validated configuration or an error.
+pub(crate) fn result(issues: Vec<ConfigIssue>) -> Result<ValidatedConfig, ConfigError> {
+    if issues.iter().any(ConfigIssue::is_error) {
+        Err(ConfigError { issues })
+    } else {
+        Ok(ValidatedConfig { warnings: issues })
+    }
+}
+
+/// Returns the issues with `builder`'s configuration.
+pub(crate) fn issues(builder: &Builder) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    let has_file_target = builder
+        .targets
+        .iter()
+        .any(|target| Output::of(target) == Output::File);
+    if !has_file_target {
+        let file_options = [
+            ("with_max_file_size", builder.max_file_size.is_some()),
+            ("on_rotation", builder.rotation_hook.is_some()),
+            (
+                "with_rotation_strategy",
+                !matches!(builder.rotation_strategy, RotationStrategy::KeepAll),
+            ),
+            (
+                "with_instance_strategy",
+                builder.instance_strategy != InstanceStrategy::Shared,
+            ),
+            (
+                "with_version_layout",
+                builder.version_layout != VersionLayout::Flat,
+            ),
+            ("with_sync_policy", builder.sync_policy != SyncPolicy::Never),
+            (
+                "with_file_name_template",
+                builder.file_name_template.is_some(),
+            ),
+        ];
+        issues.extend(
+            file_options
+                .into_iter()
+                .filter(|(_, set)| *set)
+                .map(|(method, _)| ConfigIssue::FileOptionWithoutFileTarget(method)),
+        );
+    }
+    if matches!(builder.rotation_strategy, RotationStrategy::KeepSome(0)) {
+        issues.push(ConfigIssue::KeepNoFiles);
+    }
+    if let Some(template) = &builder.file_name_template
+        && let Err(reason) = FileNameTemplate::parse(template)
+    {
+        issues.push(ConfigIssue::InvalidFileNameTemplate {
+            template: template.clone(),
+            reason: reason.to_string(),
+        });
+    }
+
+    // Stdout, stderr, and webview targets are deduplicated rather than ignored
+    let mut outputs = Vec::new();
+    for target in &builder.targets {
+        let output = Output::of(target);
+        if outputs.contains(&output) {
+            if !matches!(output, Output::Stdout | Output::Stderr | Output::Webview) {
+                issues.push(ConfigIssue::IgnoredTarget(target.clone()));
+            }
+        } else {
+            outputs.push(output);
+        }
+    }
+    for (target, _) in &builder.layer_filters {
+        if !outputs.contains(&Output::of(target)) {
+            issues.push(ConfigIssue::FilterForMissingTarget(target.clone()));
+        }
+    }
+    let pipe_to_stdout = builder
+        .targets
+        .iter()
+        .find(|target| matches!(target, Target::Pipe { .. }))
+        .is_some_and(|target| matches!(target, Target::Pipe { handle: None }));
+    if pipe_to_stdout && outputs.contains(&Output::Stdout) {
+        issues.push(ConfigIssue::PipeSharesStdout);
+    }
+    if builder.stderr_threshold.is_some() && !outputs.contains(&Output::Stdout) {
+        issues.push(ConfigIssue::StderrThresholdWithoutStdout);
+    }
+    issues.extend(
+        conflicting_ids(&builder.event_catalog)
+            .into_iter()
+            .map(ConfigIssue::ConflictingEventId),
+    );
+    #[cfg(feature = "diagnostics-endpoint")]
+    if builder.diagnostics_endpoint.as_deref() == Some("") {
+        issues.push(ConfigIssue::EmptyEndpointToken);
+    }
+
+    // Only the first file target is used
+    if let Some(Target::Folder { path, .. }) = builder
+        .targets
+        .iter()
+        .find(|target| Output::of(target) == Output::File)
+        && let Err(reason) = check_writable(path)
+    {
+        issues.push(ConfigIssue::UnwritableFolder {
+            path: path.clone(),
+            reason,
+        });
+    }
+
+    issues
+}
+
+/// Checks that `dir` is, or can be created as, a writable directory, without
+/// creating it.
+fn check_writable(dir: &Path) -> Result<(), String> {
+    let existing = dir
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .ok_or_else(|| "no parent directory exists".to_string())?;
+    let metadata = std::fs::metadata(existing).map_err(|e| e.to_string())?;
+    if !metadata.is_dir() {
+        return Err(format!("{} is not a directory", existing.display()));
+    }
+    if metadata.permissions().readonly() {
+        return Err(format!("{} is read-only", existing.display()));
+    }
+    Ok(())
+}
+
+/// Logs a warning for each of `issues`.
+pub(crate) fn log_issues(issues: &[ConfigIssue]) {
+    for issue in issues {
+        tracing::warn!(target: CONFIG_TARGET, issue = ?issue, "{issue}");
+    }
+}
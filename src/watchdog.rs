@@ -0,0 +1,213 @@
+//! Automatic CPU profiles of intermittent hangs.
+//!
+//! Requires the `profiling` feature. When a span stays entered longer than
+//! the threshold of a [`ProfilingWatchdog`], the watchdog records a short
+//! span-aware CPU profile while the span is still busy, and saves the report
+//! as a profiling session, see
+//! [`list_profiling_sessions()`](crate::list_profiling_sessions). Hangs that
+//! users cannot reproduce on demand are captured when they happen.
+//!
+//! Spans are watched while entered, so spans that stay open while waiting,
+//! such as those of operations or instrumented futures, do not count. Tauri
+//! commands are watched when they are instrumented, e.g. with
+//! `#[traced_command]`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::span;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::{SpanAwareProfilingExt, SpanTimingCapture};
+
+/// The target used for watchdog events.
+pub const WATCHDOG_TARGET: &str = "tauri_plugin_tracing::watchdog";
+
+/// Spans that are currently entered, with when they were entered, their
+/// name, and how many times they are entered.
+type BusySpans = Mutex<HashMap<span::Id, (Instant, &'static str, usize)>>;
+
+/// Records a CPU profile when a span is busy for too long.
+///
+/// Profiles need the profiling plugin, see
+/// [`init_profiling()`](crate::init_profiling), and are rate-limited by a
+/// cooldown.
+///
+/// # Example
+///
+/// Use [`Builder::with_profiling_watchdog()`](crate::Builder::with_profiling_watchdog)
+/// with the default subscriber:
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use tauri_plugin_tracing::{Builder, ProfilingWatchdog, init_profiling};
+/// tauri::Builder::default()
+///     .plugin(init_profiling())
+///     .plugin(
+///         Builder::new()
+///             .with_profiling_watchdog(ProfilingWatchdog::new(Duration::from_secs(2)))
+///             .with_default_subscriber()
+///             .build(),
+///     );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfilingWatchdog {
+    threshold: Duration,
+    profile_duration: Duration,
+    cooldown: Duration,
+}
+
+impl ProfilingWatchdog {
+    /// Creates a watchdog for spans entered for at least `threshold`.
+    ///
+    /// Profiles last 5 seconds, at most one every 10 minutes.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            profile_duration: Duration::from_secs(5),
+            cooldown: Duration::from_secs(600),
+        }
+    }
+
+    /// Sets how long each profile records.
+    pub fn with_profile_duration(mut self, duration: Duration) -> Self {
+        self.profile_duration = duration;
+        self
+    }
+
+    /// Sets the least time between the starts of two profiles.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Returns the configured threshold.
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// Returns how long each profile records.
+    pub fn profile_duration(&self) -> Duration {
+        self.profile_duration
+    }
+
+    /// Returns the least time between the starts of two profiles.
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    /// Starts watching on a background thread and returns the layer that
+    /// reports busy spans to it.
+    ///
+    /// Profiles include span timing when a [`SpanTimingCapture`] is managed
+    /// in Tauri state. The watchdog stops when the layer is dropped.
+    pub fn start<R: Runtime>(self, app: &AppHandle<R>) -> crate::Result<WatchdogLayer> {
+        let busy = Arc::new(BusySpans::default());
+        let weak = Arc::downgrade(&busy);
+        let app = app.clone();
+        std::thread::Builder::new()
+            .name("tracing-profiling-watchdog".to_string())
+            .spawn(move || run_watchdog(weak, app, self))?;
+        Ok(WatchdogLayer { busy })
+    }
+}
+
+/// A tracing layer that reports busy spans to a [`ProfilingWatchdog`].
+///
+/// Created with [`ProfilingWatchdog::start()`].
+#[derive(Debug, Clone)]
+pub struct WatchdogLayer {
+    busy: Arc<BusySpans>,
+}
+
+impl<S> Layer<S> for WatchdogLayer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(metadata) = ctx.metadata(id) else {
+            return;
+        };
+        let mut busy = self.busy.lock().unwrap_or_else(|e| e.into_inner());
+        busy.entry(id.clone())
+            .or_insert_with(|| (Instant::now(), metadata.name(), 0))
+            .2 += 1;
+    }
+
+    fn on_exit(&self, id: &span::Id, _ctx: Context<'_, S>) {
+        let mut busy = self.busy.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = busy.get_mut(id) {
+            entry.2 -= 1;
+            if entry.2 == 0 {
+                busy.remove(id);
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, _ctx: Context<'_, S>) {
+        self.busy
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id);
+    }
+}
+
+fn run_watchdog<R: Runtime>(busy: Weak<BusySpans>, app: AppHandle<R>, config: ProfilingWatchdog) {
+    let poll = (config.threshold / 4).clamp(Duration::from_millis(10), Duration::from_secs(1));
+    let mut last_profile: Option<Instant> = None;
+
+    loop {
+        std::thread::sleep(poll);
+        let Some(busy) = busy.upgrade() else {
+            return;
+        };
+        if last_profile.is_some_and(|at| at.elapsed() < config.cooldown) {
+            continue;
+        }
+        let slowest = busy
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .map(|(entered, name, _)| (entered.elapsed(), *name))
+            .max();
+        drop(busy);
+        let Some((elapsed, name)) = slowest.filter(|(elapsed, _)| *elapsed >= config.threshold)
+        else {
+            continue;
+        };
+        // Leave profiles started by the app alone
+        if app
+            .try_state::<Arc<SpanTimingCapture>>()
+            .is_some_and(|capture| capture.is_capturing())
+        {
+            continue;
+        }
+
+        last_profile = Some(Instant::now());
+        tracing::warn!(
+            target: WATCHDOG_TARGET,
+            span = name,
+            elapsed_ms = elapsed.as_secs_f64() * 1000.0,
+            "span `{name}` busy for {elapsed:?}, recording a CPU profile for {:?}",
+            config.profile_duration
+        );
+        if let Err(e) = app.start_span_aware_profile() {
+            tracing::warn!(target: WATCHDOG_TARGET, "Failed to start CPU profile: {}", e);
+            continue;
+        }
+        std::thread::sleep(config.profile_duration);
+        match crate::profiling::stop_span_aware_impl(&app, Some(name.to_string())) {
+            Ok(report) => tracing::info!(
+                target: WATCHDOG_TARGET,
+                span = name,
+                samples = report.profile.sample_count,
+                "watchdog profile saved"
+            ),
+            Err(e) => tracing::warn!(target: WATCHDOG_TARGET, "Failed to stop CPU profile: {}", e),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Webview process crashes.
+//!
+//! When the web content process of a webview crashes, is killed, or stops
+//! responding, the window goes blank or freezes without anything reaching
+//! the logs: the frontend can no longer log, and the backend is not told.
+//! The plugin watches each webview's process and logs an `ERROR` event under
+//! the [`WEBVIEW_CRASH_TARGET`] target with the [`WebviewCrashReason`] and
+//! the platform's code for it. The window label is recorded in the `w` field,
+//! like other webview events.
+//!
+//! It also emits a [`WebviewCrash`] as the [`WEBVIEW_CRASHED_EVENT`] event to
+//! the app, so the backend or the remaining windows can react, e.g. by
+//! reloading the webview:
+//!
+//! ```rust,no_run
+//! # use tauri::{Listener, Manager};
+//! # use tauri_plugin_tracing::{WEBVIEW_CRASHED_EVENT, WebviewCrash};
+//! # fn setup(app: &tauri::App) {
+//! let handle = app.handle().clone();
+//! app.listen(WEBVIEW_CRASHED_EVENT, move |event| {
+//!     if let Ok(crash) = serde_json::from_str::<WebviewCrash>(event.payload())
+//!         && let Some(webview) = handle.get_webview(&crash.label)
+//!     {
+//!         let _ = webview.reload();
+//!     }
+//! });
+//! # }
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Windows:** Failures of the WebView2 browser, renderer, GPU, and
+//!   utility processes are reported, including renderers that stop
+//!   responding.
+//! - **Linux:** Crashes and memory limit kills are reported, as well as
+//!   renderers that stop responding. A renderer that recovers is logged as a
+//!   `WARN` event.
+//! - **macOS / iOS:** Tauri only reports termination to the app's builder, so
+//!   pass [`webview_terminated()`] to
+//!   `tauri::Builder::on_web_content_process_terminate()`.
+//! - **Android:** Unsupported.
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Runtime, Webview};
+
+use crate::plugin_name::channel;
+
+/// The target used for webview crash events.
+pub const WEBVIEW_CRASH_TARGET: &str = "webview::crash";
+
+/// The event emitted to the app when a webview crashes.
+pub const WEBVIEW_CRASHED_EVENT: &str = "tracing://webview-crashed";
+
+/// Why a webview's process failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum WebviewCrashReason {
+    /// The web content process crashed.
+    Crashed,
+    /// The web content process was killed for using too much memory.
+    OutOfMemory,
+    /// The web content process was terminated, e.g. by the OS.
+    Terminated,
+    /// The web content process stopped responding.
+    Unresponsive,
+    /// Another process the webview depends on failed, such as the GPU
+    /// process.
+    OtherProcessFailed,
+}
+
+impl WebviewCrashReason {
+    fn description(self) -> &'static str {
+        match self {
+            Self::Crashed => "web content process crashed",
+            Self::OutOfMemory => "web content process exceeded its memory limit",
+            Self::Terminated => "web content process was terminated",
+            Self::Unresponsive => "web content process is unresponsive",
+            Self::OtherProcessFailed => "webview process failed",
+        }
+    }
+}
+
+/// The payload of the [`WEBVIEW_CRASHED_EVENT`] event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebviewCrash {
+    /// The label of the webview.
+    pub label: String,
+    /// Why the webview's process failed.
+    pub reason: WebviewCrashReason,
+    /// The platform's code for the failure, if any: a
+    /// `COREWEBVIEW2_PROCESS_FAILED_KIND` on Windows, and a
+    /// `WebKitWebProcessTerminationReason` on Linux.
+    pub code: Option<i32>,
+}
+
+/// Logs that the process of `webview` failed, and emits a
+/// [`WEBVIEW_CRASHED_EVENT`] event.
+///
+/// The plugin calls this on the platforms where it can watch webviews, so
+/// apps only need it for failures they detect themselves.
+pub fn webview_crashed<R: Runtime>(
+    webview: &Webview<R>,
+    reason: WebviewCrashReason,
+    code: Option<i32>,
+) {
+    let label = webview.label();
+    let description = reason.description();
+    tracing::error!(
+        target: WEBVIEW_CRASH_TARGET,
+        w = label,
+        ?reason,
+        code,
+        "{description}"
+    );
+    let crash = WebviewCrash {
+        label: label.to_string(),
+        reason,
+        code,
+    };
+    let app = webview.app_handle();
+    if let Err(e) = app.emit(&channel(app, WEBVIEW_CRASHED_EVENT), &crash) {
+        tracing::warn!(
+            target: WEBVIEW_CRASH_TARGET,
+            w = label,
+            "failed to emit webview crash: {e}"
+        );
+    }
+}
+
+/// Logs that the web content process of `webview` was terminated.
+///
+/// Tauri only reports this on macOS and iOS, through the app's builder:
+///
+/// ```rust,no_run
+/// # #[cfg(any(target_os = "macos", target_os = "ios"))]
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_tracing::Builder::new().build())
+///     .on_web_content_process_terminate(tauri_plugin_tracing::webview_terminated);
+/// ```
+pub fn webview_terminated<R: Runtime>(webview: &Webview<R>) {
+    webview_crashed(webview, WebviewCrashReason::Terminated, None);
+}
+
+/// Starts watching the process of `webview` for failures.
+pub(crate) fn watch<R: Runtime>(webview: &Webview<R>) {
+    if let Err(e) = watch_platform(webview) {
+        tracing::debug!(
+            target: WEBVIEW_CRASH_TARGET,
+            w = webview.label(),
+            "cannot watch webview process: {e}"
+        );
+    }
+}
+
+#[cfg(windows)]
+fn watch_platform<R: Runtime>(webview: &Webview<R>) -> tauri::Result<()> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::*;
+    use webview2_com::ProcessFailedEventHandler;
+
+    let handle = webview.clone();
+    webview.with_webview(move |platform| {
+        // Safety: the controller is alive for the duration of the callback,
+        // and the handler is called on the webview's thread
+        let result = unsafe {
+            let failed = handle.clone();
+            platform.controller().CoreWebView2().and_then(|core| {
+                let mut token = 0;
+                core.add_ProcessFailed(
+                    &ProcessFailedEventHandler::create(Box::new(move |_, args| {
+                        let Some(args) = args else { return Ok(()) };
+                        let mut kind = COREWEBVIEW2_PROCESS_FAILED_KIND::default();
+                        args.ProcessFailedKind(&mut kind)?;
+                        let reason = match kind {
+                            COREWEBVIEW2_PROCESS_FAILED_KIND_RENDER_PROCESS_UNRESPONSIVE => {
+                                WebviewCrashReason::Unresponsive
+                            }
+                            COREWEBVIEW2_PROCESS_FAILED_KIND_BROWSER_PROCESS_EXITED
+                            | COREWEBVIEW2_PROCESS_FAILED_KIND_RENDER_PROCESS_EXITED
+                            | COREWEBVIEW2_PROCESS_FAILED_KIND_FRAME_RENDER_PROCESS_EXITED => {
+                                WebviewCrashReason::Crashed
+                            }
+                            _ => WebviewCrashReason::OtherProcessFailed,
+                        };
+                        webview_crashed(&failed, reason, Some(kind.0));
+                        Ok(())
+                    })),
+                    &mut token,
+                )
+            })
+        };
+        if let Err(e) = result {
+            tracing::debug!(
+                target: WEBVIEW_CRASH_TARGET,
+                w = handle.label(),
+                "cannot watch webview process: {e}"
+            );
+        }
+    })
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn watch_platform<R: Runtime>(webview: &Webview<R>) -> tauri::Result<()> {
+    use webkit2gtk::{WebProcessTerminationReason, WebViewExt};
+
+    let handle = webview.clone();
+    webview.with_webview(move |platform| {
+        let inner = platform.inner();
+        let terminated = handle.clone();
+        inner.connect_web_process_terminated(move |_, reason| {
+            let (reason, code) = match reason {
+                WebProcessTerminationReason::Crashed => (WebviewCrashReason::Crashed, 0),
+                WebProcessTerminationReason::ExceededMemoryLimit => {
+                    (WebviewCrashReason::OutOfMemory, 1)
+                }
+                WebProcessTerminationReason::TerminatedByApi => (WebviewCrashReason::Terminated, 2),
+                WebProcessTerminationReason::__Unknown(code) => {
+                    (WebviewCrashReason::OtherProcessFailed, code)
+                }
+            };
+            webview_crashed(&terminated, reason, Some(code));
+        });
+        inner.connect_is_web_process_responsive_notify(move |inner| {
+            if inner.is_web_process_responsive() {
+                tracing::warn!(
+                    target: WEBVIEW_CRASH_TARGET,
+                    w = handle.label(),
+                    "web content process is responsive again"
+                );
+            } else {
+                webview_crashed(&handle, WebviewCrashReason::Unresponsive, None);
+            }
+        });
+    })
+}
+
+// Reported through the app's builder on macOS and iOS
+#[cfg(not(any(
+    windows,
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn watch_platform<R: Runtime>(_webview: &Webview<R>) -> tauri::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Spans around frontend callbacks.
+//!
+//! The `span_start` command opens an `INFO` span for a UI handler or other
+//! callback, optionally as a child of a propagated backend span or another
+//! frontend span, and returns its [`SpanContext`]. Records logged with that
+//! context are recorded under the span. The `span_end` command records the
+//! callback's duration and any exception, then closes the span.
+//!
+//! The JavaScript `withSpan()` helper wraps both commands:
+//!
+//! ```javascript
+//! import { withSpan } from '@fltsci/tauri-plugin-tracing';
+//!
+//! await withSpan('save-clicked', async (log) => {
+//!   log.debug('saving', doc.id);
+//!   await save(doc);
+//! });
+//! ```
+//!
+//! Successful spans end with a `DEBUG` event and failed ones with an `ERROR`
+//! event, both under the [`WEBVIEW_SPAN_TARGET`] target.
+
+use tracing::Span;
+
+use crate::CallStackLine;
+use crate::span_context::{SpanContext, SpanContextRegistry};
+
+/// The target used for frontend spans and their end events.
+pub const WEBVIEW_SPAN_TARGET: &str = "tauri_plugin_tracing::webview_span";
+
+/// Opens a span named `name` for a frontend callback.
+///
+/// Returns `None` if the span is disabled by the filter.
+pub(crate) fn start(
+    label: &str,
+    name: &str,
+    parent: Option<Span>,
+    span_registry: &SpanContextRegistry,
+) -> Option<SpanContext> {
+    let w = CallStackLine::from(label);
+    let span = match parent {
+        Some(parent) => tracing::info_span!(
+            target: WEBVIEW_SPAN_TARGET,
+            parent: &parent,
+            "webview_span",
+            name = %name,
+            w = %w,
+            duration_ms = tracing::field::Empty,
+            error = tracing::field::Empty,
+        ),
+        None => tracing::info_span!(
+            target: WEBVIEW_SPAN_TARGET,
+            "webview_span",
+            name = %name,
+            w = %w,
+            duration_ms = tracing::field::Empty,
+            error = tracing::field::Empty,
+        ),
+    };
+    span_registry.register(&span)
+}
+
+/// Records the outcome of a frontend span and closes it.
+///
+/// Returns [`Error::UnknownSpan`](crate::Error::UnknownSpan) if the span has
+/// already ended or was evicted from the registry.
+pub(crate) fn end(
+    context: &SpanContext,
+    duration_ms: Option<f64>,
+    error: Option<&str>,
+    span_registry: &SpanContextRegistry,
+) -> crate::Result<()> {
+    let span = span_registry
+        .get(context)
+        .ok_or_else(|| crate::Error::UnknownSpan(context.span_id.clone()))?;
+
+    if let Some(duration_ms) = duration_ms {
+        span.record("duration_ms", duration_ms);
+    }
+    if let Some(error) = error {
+        span.record("error", error);
+    }
+    span.in_scope(|| match error {
+        Some(error) => tracing::error!(
+            target: WEBVIEW_SPAN_TARGET,
+            duration_ms,
+            error,
+            "span failed"
+        ),
+        None => tracing::debug!(target: WEBVIEW_SPAN_TARGET, duration_ms, "span ended"),
+    });
+
+    // The span closes once the registry lets go of it
+    span_registry.release(context);
+    Ok(())
+}
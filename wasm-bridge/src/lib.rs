@@ -0,0 +1,179 @@
+//! Forwards `tracing` events from Rust compiled to WebAssembly to
+//! [`tauri-plugin-tracing`](https://docs.rs/tauri-plugin-tracing).
+//!
+//! Apps that run Rust in the webview, e.g. with Leptos, Yew, or Dioxus, can
+//! install [`WebviewBridgeLayer`] in the frontend's subscriber. Each event is
+//! sent through the plugin's `log` command with its level, target, and source
+//! location, so frontend and backend Rust logs end up in the same outputs.
+//!
+//! The plugin's `allow-log` permission must be granted to the window.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tracing_subscriber::filter::LevelFilter;
+//!
+//! tauri_plugin_tracing_wasm::init(LevelFilter::DEBUG);
+//!
+//! tracing::info!(target: "app::ui", count = 3, "items loaded");
+//! ```
+//!
+//! The backend records the event like other webview records, with the
+//! target and source file as its location, e.g. `app::ui@ui.rs:42`, and the
+//! other fields as `args=[{"count":3}]`.
+
+use serde::Serialize;
+use tauri_plugin_tracing_core::{LogLevel, LogMessage};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use wasm_bindgen::prelude::*;
+
+/// The plugin command that records webview logs.
+const LOG_COMMAND: &str = "plugin:tracing|log";
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI_INTERNALS__"], js_name = invoke, catch)]
+    async fn tauri_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// Installs a global subscriber that forwards events up to `max_level` to
+/// the backend.
+///
+/// Use [`WebviewBridgeLayer`] directly to combine it with other layers, such
+/// as one that also writes to the browser console.
+///
+/// # Panics
+///
+/// Panics if a global subscriber has already been set.
+pub fn init(max_level: LevelFilter) {
+    tracing_subscriber::registry()
+        .with(WebviewBridgeLayer::new().with_filter(max_level))
+        .init();
+}
+
+/// A tracing layer that sends events to the backend through the plugin's
+/// `log` command.
+///
+/// Events are sent in the background. Spans are not forwarded.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tauri_plugin_tracing_wasm::WebviewBridgeLayer;
+/// # use tracing_subscriber::{Layer, Registry, filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
+/// Registry::default()
+///     .with(WebviewBridgeLayer::new().with_filter(LevelFilter::INFO))
+///     .init();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebviewBridgeLayer {
+    _private: (),
+}
+
+impl WebviewBridgeLayer {
+    /// Creates a layer that forwards every event it sees.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for WebviewBridgeLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+
+        let mut message = vec![serde_json::Value::String(fields.message)];
+        if !fields.values.is_empty() {
+            message.push(serde_json::Value::Object(fields.values));
+        }
+        let args = LogArgs {
+            level: (*metadata.level()).into(),
+            message: message.into(),
+            call_stack: location(metadata),
+            target: metadata.target(),
+            timestamp: js_sys::Date::now(),
+        };
+        let Ok(args) = args.serialize(&serde_wasm_bindgen::Serializer::json_compatible()) else {
+            return;
+        };
+        wasm_bindgen_futures::spawn_local(async move {
+            // Logging the failure through tracing would recurse
+            if let Err(error) = tauri_invoke(LOG_COMMAND, args).await {
+                web_sys::console::error_2(&"failed to forward tracing event:".into(), &error);
+            }
+        });
+    }
+}
+
+/// Arguments of the plugin's `log` command.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogArgs<'a> {
+    level: LogLevel,
+    message: LogMessage,
+    call_stack: Option<String>,
+    target: &'a str,
+    timestamp: f64,
+}
+
+/// Formats the event's source location as `file:line`.
+fn location(metadata: &Metadata<'_>) -> Option<String> {
+    let file = metadata.file()?;
+    Some(match metadata.line() {
+        Some(line) => format!("{file}:{line}"),
+        None => file.to_string(),
+    })
+}
+
+/// Collects the `message` field and the other fields as JSON values.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    values: serde_json::Map<String, serde_json::Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: serde_json::Value) {
+        self.values.insert(field.name().to_string(), value);
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.insert(field, value.into());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.insert(field, format!("{value:?}").into());
+        }
+    }
+}